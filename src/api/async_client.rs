@@ -0,0 +1,620 @@
+//! Non-blocking API client for async applications
+//!
+//! [`AsyncApiClient`] offers the requests of [`ApiClient`](super::ApiClient)
+//! as `async` methods built on `reqwest::Client`, so services running on a
+//! Tokio runtime, such as indexers and bots, can verify classes without
+//! dedicating a thread to each blocking call. Requests, responses, and errors
+//! are the same as those of the blocking client.
+//!
+//! Payload transforms, polling strategies, and progress observers are attached
+//! the same way. HTTP recording is only supported by the blocking client.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use log::{debug, warn};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use url::Url;
+
+use crate::{core::class_hash::ClassHash, utils::errors::RequestFailure};
+
+use super::client::{
+    api_url, auth_failure, compressed_body, job_logs_url, job_outcome, may_resubmit, parse_job,
+    rate_limit_delay, retry_after_hint, submit_retry_delay, verification_request_body,
+};
+use super::errors::ApiClientError;
+use super::models::{
+    Capabilities, ClassSearchResults, ClassVerification, ClassVerificationInfo, Error, FileInfo,
+    JobLogs, ProjectMetadataInfo, VerificationJob, VerificationJobDispatch,
+};
+use super::polling::{default_rate_limit_retry, default_submit_retry, FixedInterval, PollStrategy};
+use super::progress::{NoProgress, ProgressObserver};
+use super::transforms::{default_transforms, PayloadTransform};
+use super::types::VerifyJobStatus;
+
+/// Async counterpart of [`ApiClient`](super::ApiClient)
+///
+/// Cloning is cheap and clones share the connection pool, so one client can
+/// serve many tasks.
+#[derive(Clone)]
+pub struct AsyncApiClient {
+    base: Url,
+    client: Client,
+    poll_strategy: Option<Arc<dyn PollStrategy>>,
+    /// `Retry-After` from the most recent job status response
+    retry_after: Arc<Mutex<Option<Duration>>>,
+    progress: Arc<dyn ProgressObserver>,
+    /// Last status reported to `progress` for each job
+    observed_status: Arc<Mutex<HashMap<String, VerifyJobStatus>>>,
+    /// Applied in order to the files of every verification request
+    transforms: Vec<Arc<dyn PayloadTransform>>,
+    /// Sent as a bearer token with every request
+    api_key: Option<String>,
+    /// Delays between retries of submissions failing with a transient error
+    submit_retry: Arc<dyn PollStrategy>,
+    /// Delays before sending rate limited requests again
+    rate_limit_retry: Arc<dyn PollStrategy>,
+    /// Send verification requests gzip-compressed
+    compress: bool,
+    /// Set once the API refused a compressed request
+    compression_refused: Arc<AtomicBool>,
+}
+
+impl AsyncApiClient {
+    /// # Errors
+    ///
+    /// Fails if provided `Url` cannot be a base.
+    pub fn new(base: Url) -> Result<Self, ApiClientError> {
+        if base.cannot_be_a_base() {
+            Err(ApiClientError::CannotBeBase(base))
+        } else {
+            Ok(Self {
+                base,
+                client: Client::new(),
+                poll_strategy: None,
+                retry_after: Arc::default(),
+                progress: Arc::new(NoProgress),
+                observed_status: Arc::default(),
+                transforms: default_transforms(),
+                api_key: None,
+                submit_retry: default_submit_retry(),
+                rate_limit_retry: default_rate_limit_retry(),
+                compress: false,
+                compression_refused: Arc::default(),
+            })
+        }
+    }
+
+    /// Use `strategy` when polling verification jobs
+    #[must_use]
+    pub fn with_poll_strategy(mut self, strategy: Arc<dyn PollStrategy>) -> Self {
+        self.poll_strategy = Some(strategy);
+        self
+    }
+
+    /// Report upload and job status progress to `observer`
+    #[must_use]
+    pub fn with_progress_observer(mut self, observer: Arc<dyn ProgressObserver>) -> Self {
+        self.progress = observer;
+        self
+    }
+
+    /// Pass the files of verification requests through `transforms`, in
+    /// order, instead of the default pipeline
+    #[must_use]
+    pub fn with_transforms(mut self, transforms: Vec<Arc<dyn PayloadTransform>>) -> Self {
+        self.transforms = transforms;
+        self
+    }
+
+    /// Retry submissions failing with a transient error (5xx, or a refused
+    /// connection) after the delays of `strategy`
+    ///
+    /// By default, submissions are retried up to 3 times with exponential
+    /// backoff.
+    #[must_use]
+    pub fn with_submit_retry(mut self, strategy: Arc<dyn PollStrategy>) -> Self {
+        self.submit_retry = strategy;
+        self
+    }
+
+    /// Send requests the API rate limits (`429 Too Many Requests`) again
+    /// after the delays of `strategy`, which is given the `Retry-After` of
+    /// the response as hint
+    ///
+    /// By default, a request is sent up to 5 more times, waiting as long as
+    /// the server asks.
+    #[must_use]
+    pub fn with_rate_limit_retry(mut self, strategy: Arc<dyn PollStrategy>) -> Self {
+        self.rate_limit_retry = strategy;
+        self
+    }
+
+    /// Send verification requests gzip-compressed (`Content-Encoding: gzip`)
+    ///
+    /// If the API refuses a compressed request with `415 Unsupported Media
+    /// Type`, it is sent again uncompressed, as are later requests.
+    #[must_use]
+    pub const fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Authenticate every request with `api_key`, as a bearer token
+    #[must_use]
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Delay requested by the server in the last job status response
+    #[must_use]
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after.lock().ok().and_then(|hint| *hint)
+    }
+
+    /// Send `request` and tell the observer how long it took
+    ///
+    /// Rate limited requests are sent again after the delays of the rate
+    /// limit strategy. Responses refusing the API key, or its absence, are
+    /// returned as [`ApiClientError::Unauthorized`].
+    async fn send(&self, request: RequestBuilder) -> Result<Response, ApiClientError> {
+        let mut request = match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        }
+        .build()?;
+        let request_url = request.url().clone();
+        let url = request_url.as_str();
+        let endpoint = url
+            .strip_prefix(self.base.as_str())
+            .unwrap_or(url)
+            .trim_start_matches('/')
+            .to_string();
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            // Request bodies are in memory, so requests can be cloned
+            let again = request.try_clone();
+            let started = Instant::now();
+            let response = self.client.execute(request).await;
+            self.progress.request_completed(
+                &endpoint,
+                response
+                    .as_ref()
+                    .ok()
+                    .map(|response| response.status().as_u16()),
+                started.elapsed(),
+            );
+            let response = response?;
+
+            let delay = rate_limit_delay(
+                self.rate_limit_retry.as_ref(),
+                attempt,
+                response.status(),
+                response.headers(),
+            );
+            if let (Some(again), Some(delay)) = (again, delay) {
+                self.progress.rate_limited(&endpoint, delay);
+                tokio::time::sleep(delay).await;
+                request = again;
+                continue;
+            }
+            return auth_failure(request_url, response.status(), self.api_key.is_some())
+                .map_or(Ok(response), Err);
+        }
+    }
+
+    fn observe_status(&self, job: &VerificationJob) {
+        let Ok(mut observed) = self.observed_status.lock() else {
+            return;
+        };
+        if observed.insert(job.job_id.clone(), job.status) != Some(job.status) {
+            self.progress.status_changed(&job.job_id, job.status);
+        }
+    }
+
+    /// Submit `files` for verification of the class `class_hash`
+    ///
+    /// Files are read from disk before the request is sent.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network request failure or if can't
+    /// gather file contents for submission.
+    pub async fn verify_class(
+        &self,
+        class_hash: &ClassHash,
+        license: Option<String>,
+        name: &str,
+        project_metadata: ProjectMetadataInfo,
+        files: &[FileInfo],
+    ) -> Result<String, ApiClientError> {
+        let url = api_url(&self.base, &["class-verify", class_hash.as_ref()])?;
+        let body = verification_request_body(
+            &self.transforms,
+            license,
+            name,
+            project_metadata,
+            files,
+            &url,
+        )?;
+
+        let compressed = compressed_body(
+            &body,
+            self.compress && !self.compression_refused.load(Ordering::Relaxed),
+        )?;
+        let (response, body_len) = match compressed {
+            Some(gzipped) => {
+                let body_len = gzipped.len() as u64;
+                let response = self.submit(class_hash, &url, &gzipped, true).await?;
+                if response.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE {
+                    warn!("The API does not accept compressed requests; sending them uncompressed");
+                    self.compression_refused.store(true, Ordering::Relaxed);
+                    (
+                        self.submit(class_hash, &url, &body, false).await?,
+                        body.len() as u64,
+                    )
+                } else {
+                    (response, body_len)
+                }
+            }
+            None => (
+                self.submit(class_hash, &url, &body, false).await?,
+                body.len() as u64,
+            ),
+        };
+
+        match response.status() {
+            StatusCode::OK => (),
+            StatusCode::BAD_REQUEST => {
+                return Err(ApiClientError::from(RequestFailure::new(
+                    url,
+                    StatusCode::BAD_REQUEST,
+                    response.json::<Error>().await?.error,
+                )));
+            }
+            StatusCode::PAYLOAD_TOO_LARGE => {
+                return Err(ApiClientError::from(RequestFailure::new(
+                    url,
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    "Request payload too large. Maximum allowed size is 10MB.".to_string(),
+                )));
+            }
+            status_code => {
+                return Err(ApiClientError::from(RequestFailure::new(
+                    url,
+                    status_code,
+                    response.text().await?,
+                )));
+            }
+        }
+
+        self.progress.bytes_uploaded(body_len);
+        Ok(response.json::<VerificationJobDispatch>().await?.job_id)
+    }
+
+    /// Send the verification request `body` for `class_hash` to `url`,
+    /// retrying transient failures, gzip-compressed if `gzipped`
+    async fn submit(
+        &self,
+        class_hash: &ClassHash,
+        url: &Url,
+        body: &[u8],
+        gzipped: bool,
+    ) -> Result<Response, ApiClientError> {
+        self.progress.upload_started(body.len() as u64);
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let mut request = self
+                .client
+                .post(url.clone())
+                .header(reqwest::header::CONTENT_TYPE, "application/json");
+            if gzipped {
+                request = request.header(reqwest::header::CONTENT_ENCODING, "gzip");
+            }
+            let response = self.send(request.body(body.to_vec())).await;
+            let outcome = response
+                .as_ref()
+                .map(|response| (response.status(), response.headers()));
+            match submit_retry_delay(self.submit_retry.as_ref(), attempt, outcome) {
+                Some(retry)
+                    if !retry.may_have_created_job
+                        || may_resubmit(
+                            class_hash,
+                            &self.check_class_verification(class_hash).await,
+                        ) =>
+                {
+                    tokio::time::sleep(retry.delay).await;
+                }
+                _ => return response,
+            }
+        }
+    }
+
+    /// Current state of a job, in any status
+    async fn fetch_job(&self, job_id: &str) -> Result<VerificationJob, ApiClientError> {
+        let url = api_url(&self.base, &["class-verify", "job", job_id])?;
+        let response = self.send(self.client.get(url.clone())).await?;
+        if let Ok(mut retry_after) = self.retry_after.lock() {
+            *retry_after = retry_after_hint(response.headers());
+        }
+
+        match response.status() {
+            StatusCode::OK => (),
+            StatusCode::NOT_FOUND => return Err(ApiClientError::JobNotFound(job_id.to_string())),
+            status_code => {
+                return Err(ApiClientError::from(RequestFailure::new(
+                    url,
+                    status_code,
+                    response.text().await?,
+                )));
+            }
+        }
+
+        let data = parse_job(&url, &response.text().await?)?;
+        self.observe_status(&data);
+        Ok(data)
+    }
+
+    /// The finished job, or `None` while it is in progress
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error or if the verification has
+    /// failed.
+    pub async fn get_job_status(
+        &self,
+        job_id: &str,
+    ) -> Result<Option<VerificationJob>, ApiClientError> {
+        job_outcome(self.fetch_job(job_id).await?)
+    }
+
+    /// Get raw job status data regardless of completion state
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be parsed.
+    pub async fn get_job_status_raw(
+        &self,
+        job_id: &str,
+    ) -> Result<VerificationJob, ApiClientError> {
+        self.fetch_job(job_id).await
+    }
+
+    /// Compiler output of a job, from line `offset` on
+    ///
+    /// Returns `None` if the API does not expose job logs.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error or if the response cannot be parsed.
+    pub async fn get_job_logs(
+        &self,
+        job_id: &str,
+        offset: usize,
+    ) -> Result<Option<JobLogs>, ApiClientError> {
+        let url = job_logs_url(&self.base, job_id, offset)?;
+        let response = self.send(self.client.get(url.clone())).await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(Some(response.json().await?)),
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => Ok(None),
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text().await?,
+            ))),
+        }
+    }
+
+    /// Submission limits of the API
+    ///
+    /// Returns `None` if the API does not expose its capabilities.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error or if the response cannot be parsed.
+    pub async fn get_capabilities(&self) -> Result<Option<Capabilities>, ApiClientError> {
+        let url = api_url(&self.base, &["class-verify", "capabilities"])?;
+        let response = self.send(self.client.get(url.clone())).await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(Some(response.json().await?)),
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => Ok(None),
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text().await?,
+            ))),
+        }
+    }
+
+    /// Check if a class is verified
+    ///
+    /// A class the explorer does not know is reported as
+    /// [`ClassVerification::NotDeclared`].
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error or if the response cannot be parsed.
+    pub async fn check_class_verification(
+        &self,
+        class_hash: &ClassHash,
+    ) -> Result<ClassVerification, ApiClientError> {
+        let url = api_url(&self.base, &["class-verify", "check", class_hash.as_ref()])?;
+        let response = self.send(self.client.get(url.clone())).await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(ClassVerification::from(
+                response.json::<ClassVerificationInfo>().await?,
+            )),
+            StatusCode::NOT_FOUND => Ok(ClassVerification::NotDeclared),
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text().await?,
+            ))),
+        }
+    }
+
+    /// Find classes whose contract name matches `name`
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ApiClientError::SearchUnsupported`] if the API has no
+    /// search endpoint, or `Err` on network error.
+    pub async fn search_classes(
+        &self,
+        name: &str,
+    ) -> Result<Vec<ClassVerificationInfo>, ApiClientError> {
+        let mut url = api_url(&self.base, &["class-verify", "search"])?;
+        url.query_pairs_mut().append_pair("name", name);
+        let response = self.send(self.client.get(url.clone())).await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<ClassSearchResults>().await?.classes),
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => {
+                Err(ApiClientError::SearchUnsupported(self.base.clone()))
+            }
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text().await?,
+            ))),
+        }
+    }
+
+    /// Poll a job until it finishes
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error, if the verification has failed, or
+    /// if the polling strategy gives up first.
+    pub async fn poll_verification_status(
+        &self,
+        job_id: &str,
+    ) -> Result<VerificationJob, ApiClientError> {
+        self.poll_verification_status_with_callback(job_id, None)
+            .await
+    }
+
+    /// Poll a job until it finishes, passing every in-progress state to `callback`
+    ///
+    /// Uses the client's [`PollStrategy`], or a fixed 2 second interval for up
+    /// to 10 minutes if none is set. The delay between checks does not block
+    /// the runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error, if the verification has failed, or
+    /// if the polling strategy gives up first.
+    pub async fn poll_verification_status_with_callback(
+        &self,
+        job_id: &str,
+        callback: Option<&(dyn Fn(&VerificationJob) + Sync)>,
+    ) -> Result<VerificationJob, ApiClientError> {
+        let default_strategy = FixedInterval::default();
+        let strategy = self.poll_strategy.as_deref().unwrap_or(&default_strategy);
+
+        let mut attempt = 0;
+        let started = Instant::now();
+        loop {
+            attempt += 1;
+            let job = self.fetch_job(job_id).await?;
+            let Some(delay) =
+                strategy.next_delay_within(attempt, self.retry_after(), started.elapsed())
+            else {
+                return job_outcome(job)?.ok_or(ApiClientError::InProgress);
+            };
+            if let Some(job) = job_outcome(job.clone())? {
+                return Ok(job);
+            }
+
+            if let Some(callback) = callback {
+                callback(&job);
+            }
+            debug!("Job {job_id} didn't finish, retrying in {delay:?}");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_async_client() {
+        fn assert_send<T: Send>(_: &T) {}
+
+        let mut server = mockito::Server::new();
+        let _check = server
+            .mock("GET", "/class-verify/check/0x1")
+            .with_body(r#"{"verified": true, "class_hash": "0x1", "name": "Token"}"#)
+            .create();
+        let _processing = server
+            .mock("GET", "/class-verify/job/job-1")
+            .with_body(r#"{"job_id": "job-1", "status": 5}"#)
+            .expect(2)
+            .create();
+        let _done = server
+            .mock("GET", "/class-verify/job/job-1")
+            .with_body(r#"{"job_id": "job-1", "status": 4, "class_hash": "0x1"}"#)
+            .create();
+        let _missing = server
+            .mock("GET", "/class-verify/job/job-2")
+            .with_status(404)
+            .create();
+
+        let client = AsyncApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_poll_strategy(Arc::new(FixedInterval::new(Duration::from_millis(10), 5)));
+        let checks = AtomicUsize::new(0);
+        let callback = |job: &VerificationJob| {
+            assert_eq!(job.status, VerifyJobStatus::Processing);
+            checks.fetch_add(1, Ordering::Relaxed);
+        };
+
+        // Futures must be Send so they can be spawned on a multi-threaded runtime
+        assert_send(&client.poll_verification_status("job-1"));
+
+        tokio_test::block_on(async {
+            let class_hash = ClassHash::new("0x1").unwrap();
+            assert!(client
+                .check_class_verification(&class_hash)
+                .await
+                .unwrap()
+                .is_verified());
+
+            let job = client
+                .poll_verification_status_with_callback("job-1", Some(&callback))
+                .await
+                .unwrap();
+            assert_eq!(job.status, VerifyJobStatus::Success);
+            assert_eq!(checks.load(Ordering::Relaxed), 2);
+
+            assert!(matches!(
+                client.get_job_status("job-2").await,
+                Err(ApiClientError::JobNotFound(job_id)) if job_id == "job-2"
+            ));
+        });
+        drop(server);
+    }
+}
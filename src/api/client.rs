@@ -1,29 +1,72 @@
-use std::{collections::HashMap, fs, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, Read, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
-use backon::{BlockingRetryable, ExponentialBuilder};
+use flate2::{write::GzEncoder, Compression};
 use log::{debug, info, warn};
 use reqwest::{
     blocking::{self, Client},
+    header::CONTENT_TYPE,
     StatusCode,
 };
 use url::Url;
 
-use crate::{core::class_hash::ClassHash, utils::errors::RequestFailure};
+use crate::{
+    core::class_hash::ClassHash,
+    output::sink::{self, Marker},
+    utils::errors::RequestFailure,
+};
 
 use super::errors::{ApiClientError, VerificationError};
 use super::models::{
-    ClassVerificationInfo, Error, FileInfo, ProjectMetadataInfo, VerificationJob,
-    VerificationJobDispatch, VerificationRequest,
+    Capabilities, ClassSearchResults, ClassVerification, ClassVerificationInfo, ContractInfo,
+    Error, FileInfo, JobLogs, ProjectMetadataInfo, VerificationJob, VerificationJobDispatch,
+    VerificationRequest,
+};
+use super::polling::{default_rate_limit_retry, default_submit_retry, FixedInterval, PollStrategy};
+use super::progress::{NoProgress, ProgressObserver, STREAMED_UPLOAD_SIZE};
+use super::recording::{self, Exchange, HttpRecorder};
+use super::transforms::{
+    default_transforms, prune_workspace_members, PayloadFiles, PayloadTransform,
 };
 use super::types::VerifyJobStatus;
 
 // TODO: Option blindness?
 type JobStatus = Option<VerificationJob>;
 
+/// How long [`ApiClient::health_check`] waits for the API
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Clone)]
 pub struct ApiClient {
     base: Url,
     client: Client,
+    poll_strategy: Option<Arc<dyn PollStrategy>>,
+    /// `Retry-After` from the most recent job status response
+    retry_after: Arc<Mutex<Option<Duration>>>,
+    progress: Arc<dyn ProgressObserver>,
+    /// Last status reported to `progress` for each job
+    observed_status: Arc<Mutex<HashMap<String, VerifyJobStatus>>>,
+    recorder: Option<Arc<HttpRecorder>>,
+    /// Applied in order to the files of every verification request
+    transforms: Vec<Arc<dyn PayloadTransform>>,
+    /// Sent as a bearer token with every request
+    api_key: Option<String>,
+    /// Delays between retries of submissions failing with a transient error
+    submit_retry: Arc<dyn PollStrategy>,
+    /// Delays before sending rate limited requests again
+    rate_limit_retry: Arc<dyn PollStrategy>,
+    /// Send verification requests gzip-compressed
+    compress: bool,
+    /// Set once the API refused a compressed request
+    compression_refused: Arc<AtomicBool>,
 }
 
 /**
@@ -44,59 +87,338 @@ impl ApiClient {
             Ok(Self {
                 base,
                 client: blocking::Client::new(),
+                poll_strategy: None,
+                retry_after: Arc::default(),
+                progress: Arc::new(NoProgress),
+                observed_status: Arc::default(),
+                recorder: recording::global_recorder(),
+                transforms: default_transforms(),
+                api_key: None,
+                submit_retry: default_submit_retry(),
+                rate_limit_retry: default_rate_limit_retry(),
+                compress: false,
+                compression_refused: Arc::default(),
             })
         }
     }
 
+    /// Use `strategy` when polling verification jobs
+    #[must_use]
+    pub fn with_poll_strategy(mut self, strategy: Arc<dyn PollStrategy>) -> Self {
+        self.poll_strategy = Some(strategy);
+        self
+    }
+
+    /// Retry submissions failing with a transient error (5xx, or a refused
+    /// connection) after the delays of `strategy`
+    ///
+    /// By default, submissions are retried up to 3 times with exponential
+    /// backoff.
+    #[must_use]
+    pub fn with_submit_retry(mut self, strategy: Arc<dyn PollStrategy>) -> Self {
+        self.submit_retry = strategy;
+        self
+    }
+
+    /// Send requests the API rate limits (`429 Too Many Requests`) again
+    /// after the delays of `strategy`, which is given the `Retry-After` of
+    /// the response as hint
+    ///
+    /// By default, a request is sent up to 5 more times, waiting as long as
+    /// the server asks.
+    #[must_use]
+    pub fn with_rate_limit_retry(mut self, strategy: Arc<dyn PollStrategy>) -> Self {
+        self.rate_limit_retry = strategy;
+        self
+    }
+
+    /// Send verification requests gzip-compressed (`Content-Encoding: gzip`)
+    ///
+    /// If the API refuses a compressed request with `415 Unsupported Media
+    /// Type`, it is sent again uncompressed, as are later requests.
+    #[must_use]
+    pub const fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// The configured polling strategy, if any
+    ///
+    /// Callers fall back to their own default when none was configured.
+    #[must_use]
+    pub fn poll_strategy(&self) -> Option<&dyn PollStrategy> {
+        self.poll_strategy.as_deref()
+    }
+
+    /// Report collection, upload, and job status progress to `observer`
+    #[must_use]
+    pub fn with_progress_observer(mut self, observer: Arc<dyn ProgressObserver>) -> Self {
+        self.progress = observer;
+        self
+    }
+
+    /// The attached progress observer, or one that ignores every event
+    #[must_use]
+    pub fn progress(&self) -> &dyn ProgressObserver {
+        self.progress.as_ref()
+    }
+
+    /// Pass the files of verification requests through `transforms`, in
+    /// order, instead of the default pipeline
+    #[must_use]
+    pub fn with_transforms(mut self, transforms: Vec<Arc<dyn PayloadTransform>>) -> Self {
+        self.transforms = transforms;
+        self
+    }
+
+    /// Contents of `files` as a verification request would submit them,
+    /// after the payload transforms
+    ///
     /// # Errors
     ///
-    /// Will return `Err` if the URL cannot be a base.
-    pub fn verify_class_url(&self, class_hash: &ClassHash) -> Result<Url, ApiClientError> {
-        let mut url = self.base.clone();
-        let url_clone = url.clone();
-        url.path_segments_mut()
-            .map_err(|()| ApiClientError::CannotBeBase(url_clone))?
-            .extend(&["class-verify", class_hash.as_ref()]);
-        Ok(url)
+    /// Returns an error if a file cannot be read or a transform rejects the files.
+    pub fn payload_files(&self, files: &[FileInfo]) -> Result<PayloadFiles, ApiClientError> {
+        payload_files(&self.transforms, files)
     }
 
-    /// Filter out dev-dependencies from Scarb.toml content to prevent
-    /// compilation issues on remote servers that don't have cargo installed
-    fn filter_scarb_toml_content(content: &str) -> String {
-        let mut lines = Vec::new();
-        let mut in_dev_deps = false;
+    /// Authenticate every request with `api_key`, as a bearer token
+    #[must_use]
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
 
-        for line in content.lines() {
-            // Check if we're entering a dev-dependencies section
-            if line.trim_start().starts_with("[dev-dependencies]") {
-                in_dev_deps = true;
-                // Add a comment instead of the section
-                lines.push("# [dev-dependencies] section removed for remote compilation");
-                continue;
-            }
+    /// Record every request and its response with `recorder`
+    ///
+    /// Clients record into the directory given to
+    /// [`record_http_to`](super::record_http_to) by default, if any.
+    #[must_use]
+    pub fn with_recorder(mut self, recorder: Arc<HttpRecorder>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
 
-            // Check if we're entering a new section (but not dev-dependencies)
-            if line.trim_start().starts_with('[')
-                && !line.trim_start().starts_with("[dev-dependencies]")
-            {
-                // If we were in dev-deps and hit a new section, add empty line before it
-                if in_dev_deps {
-                    lines.push("");
-                }
-                in_dev_deps = false;
-                lines.push(line);
+    /// Send `request` and tell the observer how long it took
+    ///
+    /// Rate limited requests are sent again after the delays of the rate
+    /// limit strategy. Responses refusing the API key, or its absence, are
+    /// returned as [`ApiClientError::Unauthorized`].
+    fn send(
+        &self,
+        request: blocking::RequestBuilder,
+    ) -> Result<blocking::Response, ApiClientError> {
+        let mut request = match &self.api_key {
+            Some(api_key) => request.bearer_auth(api_key),
+            None => request,
+        }
+        .build()?;
+        let url = request.url().clone();
+        let endpoint = self.endpoint(&url);
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            // Only streamed bodies cannot be cloned; `verify_class` sends
+            // those again itself
+            let again = request.try_clone();
+            let started = Instant::now();
+            let response = self.execute(request, &endpoint);
+            self.progress.request_completed(
+                &endpoint,
+                response
+                    .as_ref()
+                    .ok()
+                    .map(|response| response.status().as_u16()),
+                started.elapsed(),
+            );
+            let response = response?;
+
+            let delay = rate_limit_delay(
+                self.rate_limit_retry.as_ref(),
+                attempt,
+                response.status(),
+                response.headers(),
+            );
+            if let (Some(again), Some(delay)) = (again, delay) {
+                self.progress.rate_limited(&endpoint, delay);
+                std::thread::sleep(delay);
+                request = again;
                 continue;
             }
+            return auth_failure(url, response.status(), self.api_key.is_some())
+                .map_or(Ok(response), Err);
+        }
+    }
 
-            // Skip lines that are part of dev-dependencies
-            if in_dev_deps {
-                continue;
-            }
+    /// `url` relative to the API base, e.g. `class-verify/job/<id>`
+    fn endpoint(&self, url: &Url) -> String {
+        let url = url.as_str();
+        url.strip_prefix(self.base.as_str())
+            .unwrap_or(url)
+            .trim_start_matches('/')
+            .to_string()
+    }
+
+    /// Execute `request`, recording the exchange if a recorder is attached
+    fn execute(
+        &self,
+        request: blocking::Request,
+        endpoint: &str,
+    ) -> Result<blocking::Response, reqwest::Error> {
+        let Some(recorder) = &self.recorder else {
+            return self.client.execute(request);
+        };
+
+        let mut exchange = Exchange {
+            method: request.method().to_string(),
+            endpoint: endpoint.to_string(),
+            request_headers: recording::redact_headers(request.headers()),
+            request_body: request
+                .body()
+                .and_then(blocking::Body::as_bytes)
+                .and_then(recording::redact_body),
+            status: 0,
+            response_headers: BTreeMap::new(),
+            response_body: String::new(),
+        };
 
-            lines.push(line);
+        let response = self.client.execute(request)?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.bytes()?;
+
+        exchange.status = status.as_u16();
+        exchange.response_headers = recording::redact_headers(&headers);
+        exchange.response_body = String::from_utf8_lossy(&body).into_owned();
+        if let Err(e) = recorder.record(&exchange) {
+            warn!(
+                "Failed to record HTTP exchange in {}: {e}",
+                recorder.dir().display()
+            );
+        }
+
+        // Hand the consumed body back to the caller
+        let mut replayed = http::Response::new(body);
+        *replayed.status_mut() = status;
+        *replayed.headers_mut() = headers;
+        Ok(replayed.into())
+    }
+
+    /// Tell the observer about `job`'s status if it changed since the last check
+    fn observe_status(&self, job: &VerificationJob) {
+        let Ok(mut observed) = self.observed_status.lock() else {
+            return;
+        };
+        if observed.insert(job.job_id.clone(), job.status) != Some(job.status) {
+            self.progress.status_changed(&job.job_id, job.status);
+        }
+    }
+
+    /// Delay requested by the server in the last job status response
+    #[must_use]
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after.lock().ok().and_then(|hint| *hint)
+    }
+
+    fn record_retry_after(&self, response: &blocking::Response) {
+        if let Ok(mut retry_after) = self.retry_after.lock() {
+            *retry_after = retry_after_hint(response.headers());
+        }
+    }
+
+    /// Send the verification request `body` for `class_hash` to `url`,
+    /// retrying transient failures, gzip-compressed if `gzipped`
+    fn submit(
+        &self,
+        class_hash: &ClassHash,
+        url: &Url,
+        body: &[u8],
+        gzipped: bool,
+    ) -> Result<blocking::Response, ApiClientError> {
+        let body_len = body.len() as u64;
+        self.progress.upload_started(body_len);
+        // Large requests are streamed so their upload can be followed; recorded
+        // exchanges need the body in memory
+        let streamed = body_len >= STREAMED_UPLOAD_SIZE && self.recorder.is_none();
+        let mut attempt = 0;
+        let mut rate_limited = 0;
+        loop {
+            attempt += 1;
+            let mut request = self
+                .client
+                .post(url.clone())
+                .header(reqwest::header::CONTENT_TYPE, "application/json");
+            if gzipped {
+                request = request.header(reqwest::header::CONTENT_ENCODING, "gzip");
+            }
+            let response = self.send(if streamed {
+                request.body(blocking::Body::sized(
+                    UploadReader::new(body.to_vec(), Arc::clone(&self.progress)),
+                    body_len,
+                ))
+            } else {
+                request.body(body.to_vec())
+            });
+            let outcome = response
+                .as_ref()
+                .map(|response| (response.status(), response.headers()));
+            // Streamed bodies cannot be sent again by `send`, so their rate
+            // limits are waited out here
+            let delay = submit_retry_delay(self.submit_retry.as_ref(), attempt, outcome)
+                .filter(|retry| {
+                    !retry.may_have_created_job
+                        || may_resubmit(class_hash, &self.check_class_verification(class_hash))
+                })
+                .map(|retry| retry.delay)
+                .or_else(|| {
+                    let response = response.as_ref().ok().filter(|_| streamed)?;
+                    rate_limited += 1;
+                    let delay = rate_limit_delay(
+                        self.rate_limit_retry.as_ref(),
+                        rate_limited,
+                        response.status(),
+                        response.headers(),
+                    )?;
+                    self.progress.rate_limited(&self.endpoint(url), delay);
+                    Some(delay)
+                });
+            match delay {
+                Some(delay) => std::thread::sleep(delay),
+                None => return response,
+            }
         }
+    }
 
-        lines.join("\n")
+    /// JSON body [`verify_class`](Self::verify_class) sends for the same
+    /// arguments, after the payload transforms
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL cannot be a base, a file cannot be read or
+    /// a transform rejects the files.
+    pub fn verification_request_body(
+        &self,
+        class_hash: &ClassHash,
+        license: Option<String>,
+        name: &str,
+        project_metadata: ProjectMetadataInfo,
+        files: &[FileInfo],
+    ) -> Result<Vec<u8>, ApiClientError> {
+        verification_request_body(
+            &self.transforms,
+            license,
+            name,
+            project_metadata,
+            files,
+            &self.verify_class_url(class_hash)?,
+        )
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the URL cannot be a base.
+    pub fn verify_class_url(&self, class_hash: &ClassHash) -> Result<Url, ApiClientError> {
+        api_url(&self.base, &["class-verify", class_hash.as_ref()])
     }
 
     /// # Errors
@@ -111,87 +433,36 @@ impl ApiClient {
         project_metadata: ProjectMetadataInfo,
         files: &[FileInfo],
     ) -> Result<String, ApiClientError> {
-        // Prepare license value
-        let license_value = license.map_or_else(
-            || "NONE".to_string(),
-            |lic| if lic == "MIT" { "MIT".to_string() } else { lic },
-        );
-
-        // Add Dojo version if available
-        let dojo_version = project_metadata.dojo_version.as_ref().map_or_else(
-            || {
-                debug!("📤 No dojo_version to include in API request");
-                None
-            },
-            |dojo_version| {
-                info!("📤 Adding dojo_version to API request: {dojo_version}");
-                Some(dojo_version.clone())
-            },
-        );
-
-        info!(
-            "🌐 API request payload prepared - build_tool: '{}', dojo_version: {:?}",
-            project_metadata.build_tool, project_metadata.dojo_version
-        );
+        let url = self.verify_class_url(class_hash)?;
+        let body =
+            self.verification_request_body(class_hash, license, name, project_metadata, files)?;
 
-        // Collect files into HashMap
-        let mut files_map = HashMap::new();
-        for file in files {
-            let mut file_content = fs::read_to_string(file.path.as_path())?;
-
-            // Filter out dev-dependencies from Scarb.toml files
-            if file.name == "Scarb.toml" || file.name.ends_with("/Scarb.toml") {
-                let original_len = file_content.len();
-                file_content = Self::filter_scarb_toml_content(&file_content);
-                if original_len != file_content.len() {
-                    warn!(
-                        "Filtered dev-dependencies from {} (size: {} -> {} bytes)",
-                        file.name,
-                        original_len,
-                        file_content.len()
-                    );
+        // Send JSON request
+        let compressed = compressed_body(
+            &body,
+            self.compress && !self.compression_refused.load(Ordering::Relaxed),
+        )?;
+        let (response, body_len) = match compressed {
+            Some(gzipped) => {
+                let body_len = gzipped.len() as u64;
+                let response = self.submit(class_hash, &url, &gzipped, true)?;
+                if response.status() == StatusCode::UNSUPPORTED_MEDIA_TYPE {
+                    warn!("The API does not accept compressed requests; sending them uncompressed");
+                    self.compression_refused.store(true, Ordering::Relaxed);
+                    (
+                        self.submit(class_hash, &url, &body, false)?,
+                        body.len() as u64,
+                    )
+                } else {
+                    (response, body_len)
                 }
             }
-
-            files_map.insert(file.name.clone(), file_content);
-        }
-
-        // Build JSON request body
-        let request_body = VerificationRequest {
-            compiler_version: project_metadata.cairo_version.to_string(),
-            scarb_version: project_metadata.scarb_version.to_string(),
-            package_name: project_metadata.package_name.clone(),
-            name: name.to_string(),
-            contract_file: project_metadata.contract_file.clone(),
-            contract_name: project_metadata.contract_file.clone(),
-            project_dir_path: project_metadata.project_dir_path.clone(),
-            build_tool: project_metadata.build_tool,
-            license: license_value,
-            dojo_version,
-            files: files_map,
+            None => (
+                self.submit(class_hash, &url, &body, false)?,
+                body.len() as u64,
+            ),
         };
 
-        let url = self.verify_class_url(class_hash)?;
-
-        // Debug logging
-        debug!("🚀 === API REQUEST PAYLOAD DEBUG ===");
-        debug!("🎯 Target URL: {url}");
-        debug!("🏗️  Request Method: POST");
-        debug!("📦 Content-Type: application/json");
-        if let Ok(json_str) = serde_json::to_string_pretty(&request_body) {
-            debug!("📋 Request Body: {json_str}");
-        }
-        debug!("📊 Total files: {}", files.len());
-        debug!("🚀 === END API REQUEST PAYLOAD ===");
-
-        // Send JSON request
-        let response = self
-            .client
-            .post(url.clone())
-            .json(&request_body) // Changed from .multipart(body)
-            .send()
-            .map_err(ApiClientError::Reqwest)?;
-
         // Error handling (unchanged)
         match response.status() {
             StatusCode::OK => (),
@@ -218,6 +489,7 @@ impl ApiClient {
             }
         }
 
+        self.progress.bytes_uploaded(body_len);
         Ok(response.json::<VerificationJobDispatch>()?.job_id)
     }
 
@@ -225,12 +497,7 @@ impl ApiClient {
     ///
     /// Will return `Err` if the URL cannot be a base.
     pub fn get_job_status_url(&self, job_id: impl AsRef<str>) -> Result<Url, ApiClientError> {
-        let mut url = self.base.clone();
-        let url_clone = url.clone();
-        url.path_segments_mut()
-            .map_err(|()| ApiClientError::CannotBeBase(url_clone))?
-            .extend(&["class-verify", "job", job_id.as_ref()]);
-        Ok(url)
+        api_url(&self.base, &["class-verify", "job", job_id.as_ref()])
     }
 
     /// # Errors
@@ -242,7 +509,8 @@ impl ApiClient {
         job_id: impl Into<String> + Clone,
     ) -> Result<JobStatus, ApiClientError> {
         let url = self.get_job_status_url(job_id.clone().into())?;
-        let response = self.client.get(url.clone()).send()?;
+        let response = self.send(self.client.get(url.clone()))?;
+        self.record_retry_after(&response);
 
         match response.status() {
             StatusCode::OK => (),
@@ -256,70 +524,9 @@ impl ApiClient {
             }
         }
 
-        let response_text = response.text()?;
-        log::debug!("Raw API Response: {response_text}");
-
-        let data: VerificationJob = serde_json::from_str(&response_text).map_err(|e| {
-            log::error!("Failed to parse JSON response: {e}");
-            log::error!("Response text: {response_text}");
-            ApiClientError::from(RequestFailure::new(
-                url.clone(),
-                StatusCode::OK,
-                format!("Failed to parse JSON response: {e}"),
-            ))
-        })?;
-
-        // Debug logging to see the actual response
-        log::debug!("Parsed API Response: job_id={}, status={:?}, status_description={:?}, message={:?}, error_category={:?}",
-                   data.job_id, data.status, data.status_description, data.message, data.error_category);
-
-        match data.status {
-            VerifyJobStatus::Success => Ok(Some(data)),
-            VerifyJobStatus::Fail => {
-                let error_message = data
-                    .message
-                    .or_else(|| data.status_description.clone())
-                    .unwrap_or_else(|| "unknown failure".to_owned());
-
-                // Parse specific error types from the server response
-                let parsed_error = if error_message.contains("Payload too large")
-                    || error_message.contains("payload too large")
-                {
-                    "Request payload too large. The project files exceed the maximum allowed size of 10MB. Try reducing file sizes or removing unnecessary files."
-                } else {
-                    &error_message
-                };
-
-                Err(ApiClientError::from(
-                    VerificationError::VerificationFailure(parsed_error.to_owned()),
-                ))
-            }
-            VerifyJobStatus::CompileFailed => {
-                let error_message = data
-                    .message
-                    .or_else(|| data.status_description.clone())
-                    .unwrap_or_else(|| "unknown failure".to_owned());
-
-                // Parse specific error types from the server response
-                let parsed_error = if error_message.contains("Payload too large")
-                    || error_message.contains("payload too large")
-                {
-                    "Request payload too large. The project files exceed the maximum allowed size of 10MB. Try reducing file sizes or removing unnecessary files."
-                } else if error_message.contains("Couldn't connect to cairo compilation service") {
-                    "Cairo compilation service is currently unavailable. Please try again later."
-                } else {
-                    &error_message
-                };
-
-                Err(ApiClientError::from(VerificationError::CompilationFailure(
-                    parsed_error.to_owned(),
-                )))
-            }
-            VerifyJobStatus::Submitted
-            | VerifyJobStatus::Compiled
-            | VerifyJobStatus::Processing
-            | VerifyJobStatus::Unknown => Ok(None),
-        }
+        let data = parse_job(&url, &response.text()?)?;
+        self.observe_status(&data);
+        job_outcome(data)
     }
 
     /// Get raw job status data regardless of completion state
@@ -335,7 +542,8 @@ impl ApiClient {
         job_id: impl Into<String> + Clone,
     ) -> Result<VerificationJob, ApiClientError> {
         let url = self.get_job_status_url(job_id.clone().into())?;
-        let response = self.client.get(url.clone()).send()?;
+        let response = self.send(self.client.get(url.clone()))?;
+        self.record_retry_after(&response);
 
         match response.status() {
             StatusCode::OK => (),
@@ -349,14 +557,8 @@ impl ApiClient {
             }
         }
 
-        let response_text = response.text()?;
-        let data: VerificationJob = serde_json::from_str(&response_text).map_err(|e| {
-            ApiClientError::from(RequestFailure::new(
-                url.clone(),
-                StatusCode::OK,
-                format!("Failed to parse JSON response: {e}"),
-            ))
-        })?;
+        let data = parse_job(&url, &response.text()?)?;
+        self.observe_status(&data);
 
         Ok(data)
     }
@@ -368,36 +570,223 @@ impl ApiClient {
         (self.get_job_status(job_id)?).map_or_else(|| Err(ApiClientError::InProgress), Ok)
     }
 
+    /// # Errors
+    ///
+    /// Will return `Err` if the URL cannot be a base.
+    pub fn get_job_logs_url(
+        &self,
+        job_id: impl AsRef<str>,
+        offset: usize,
+    ) -> Result<Url, ApiClientError> {
+        job_logs_url(&self.base, job_id.as_ref(), offset)
+    }
+
+    /// Compiler output of a job, from line `offset` on
+    ///
+    /// Returns `None` if the API does not expose job logs, so watchers can
+    /// stop asking.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error or if the response cannot be parsed.
+    pub fn get_job_logs(
+        &self,
+        job_id: &str,
+        offset: usize,
+    ) -> Result<Option<JobLogs>, ApiClientError> {
+        let url = self.get_job_logs_url(job_id, offset)?;
+        let response = self.send(self.client.get(url.clone()))?;
+
+        match response.status() {
+            StatusCode::OK => Ok(Some(response.json()?)),
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => Ok(None),
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text()?,
+            ))),
+        }
+    }
+
+    /// Check that the base URL answers as a verifier API
+    ///
+    /// Asks for the capabilities of the API, waiting at most
+    /// [`HEALTH_CHECK_TIMEOUT`]. APIs that do not report their capabilities
+    /// pass, unless they answer with a web page.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ApiClientError::UnhealthyApi`] if the API cannot be
+    /// reached, fails on its side, or does not answer as a verifier API.
+    pub fn health_check(&self) -> Result<(), ApiClientError> {
+        let url = self.get_capabilities_url()?;
+        let unhealthy = |reason: String| ApiClientError::UnhealthyApi {
+            url: self.base.clone(),
+            reason,
+        };
+        let response = self
+            .send(self.client.get(url).timeout(HEALTH_CHECK_TIMEOUT))
+            .map_err(|e| match e {
+                ApiClientError::Reqwest(e) => unhealthy(format!("it cannot be reached ({e})")),
+                e => e,
+            })?;
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        if status.is_server_error() {
+            return Err(unhealthy(format!("it answered {status}")));
+        }
+        if content_type.contains("html") {
+            return Err(unhealthy(format!(
+                "it answered {status} with a web page, not the verifier API"
+            )));
+        }
+        if status == StatusCode::OK {
+            response
+                .json::<Capabilities>()
+                .map_err(|e| unhealthy(format!("its capabilities cannot be read ({e})")))?;
+        }
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the URL cannot be a base.
+    pub fn get_capabilities_url(&self) -> Result<Url, ApiClientError> {
+        api_url(&self.base, &["class-verify", "capabilities"])
+    }
+
+    /// Submission limits of the API
+    ///
+    /// Returns `None` if the API does not expose its capabilities.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` on network error or if the response cannot be parsed.
+    pub fn get_capabilities(&self) -> Result<Option<Capabilities>, ApiClientError> {
+        let url = self.get_capabilities_url()?;
+        let response = self.send(self.client.get(url.clone()))?;
+
+        match response.status() {
+            StatusCode::OK => Ok(Some(response.json()?)),
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => Ok(None),
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text()?,
+            ))),
+        }
+    }
+
     /// # Errors
     ///
     /// Will return `Err` if the URL cannot be a base.
     pub fn get_check_class_url(&self, class_hash: &ClassHash) -> Result<Url, ApiClientError> {
-        let mut url = self.base.clone();
-        let url_clone = url.clone();
-        url.path_segments_mut()
-            .map_err(|()| ApiClientError::CannotBeBase(url_clone))?
-            .extend(&["class-verify", "check", class_hash.as_ref()]);
-        Ok(url)
+        api_url(&self.base, &["class-verify", "check", class_hash.as_ref()])
     }
 
     /// Check if a class is verified
     ///
+    /// A class the explorer does not know is reported as
+    /// [`ClassVerification::NotDeclared`].
+    ///
     /// # Errors
     ///
-    /// Will return `Err` on network error or if the class is not found on-chain.
+    /// Will return `Err` on network error or if the response cannot be parsed.
     pub fn check_class_verification(
         &self,
         class_hash: &ClassHash,
-    ) -> Result<ClassVerificationInfo, ApiClientError> {
+    ) -> Result<ClassVerification, ApiClientError> {
         let url = self.get_check_class_url(class_hash)?;
-        let response = self.client.get(url.clone()).send()?;
+        let response = self.send(self.client.get(url.clone()))?;
 
         match response.status() {
             StatusCode::OK => {
                 let info: ClassVerificationInfo = response.json()?;
-                Ok(info)
+                Ok(ClassVerification::from(info))
+            }
+            StatusCode::NOT_FOUND => Ok(ClassVerification::NotDeclared),
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text()?,
+            ))),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the URL cannot be a base.
+    pub fn get_contract_url(&self, address: &str) -> Result<Url, ApiClientError> {
+        api_url(&self.base, &["contracts", address])
+    }
+
+    /// Look up the class hash of the contract deployed at `address`
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ApiClientError::ContractNotFound`] if the explorer does
+    /// not know the contract, or `Err` on network error.
+    pub fn get_contract_class_hash(&self, address: &str) -> Result<String, ApiClientError> {
+        let url = self.get_contract_url(address)?;
+        let response = self.send(self.client.get(url.clone()))?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let info: ContractInfo = response.json()?;
+                Ok(info.class_hash)
+            }
+            StatusCode::NOT_FOUND => Err(ApiClientError::ContractNotFound(address.to_string())),
+            status_code => Err(ApiClientError::from(RequestFailure::new(
+                url,
+                status_code,
+                response.text()?,
+            ))),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if the URL cannot be a base.
+    pub fn get_search_classes_url(&self, name: &str) -> Result<Url, ApiClientError> {
+        let mut url = api_url(&self.base, &["class-verify", "search"])?;
+        url.query_pairs_mut().append_pair("name", name);
+        Ok(url)
+    }
+
+    /// Find classes whose contract name matches `name`
+    ///
+    /// Results include both verified and unverified classes known to the
+    /// explorer. An API that supports search answers with an empty list when
+    /// nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ApiClientError::SearchUnsupported`] if the API has no
+    /// search endpoint, or `Err` on network error.
+    pub fn search_classes(&self, name: &str) -> Result<Vec<ClassVerificationInfo>, ApiClientError> {
+        let url = self.get_search_classes_url(name)?;
+        let response = self.send(self.client.get(url.clone()))?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let results: ClassSearchResults = response.json()?;
+                Ok(results.classes)
+            }
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => {
+                Err(ApiClientError::SearchUnsupported(self.base.clone()))
             }
-            StatusCode::NOT_FOUND => Err(ApiClientError::ClassNotFound(class_hash.to_string())),
             status_code => Err(ApiClientError::from(RequestFailure::new(
                 url,
                 status_code,
@@ -407,15 +796,338 @@ impl ApiClient {
     }
 }
 
-pub enum Status {
-    InProgress,
-    Finished(ApiClientError),
+/// `base` with `segments` appended to its path
+pub(super) fn api_url(base: &Url, segments: &[&str]) -> Result<Url, ApiClientError> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|()| ApiClientError::CannotBeBase(base.clone()))?
+        .extend(segments);
+    Ok(url)
+}
+
+pub(super) fn job_logs_url(base: &Url, job_id: &str, offset: usize) -> Result<Url, ApiClientError> {
+    let mut url = api_url(base, &["class-verify", "job", job_id, "logs"])?;
+    url.query_pairs_mut()
+        .append_pair("offset", &offset.to_string());
+    Ok(url)
+}
+
+/// A failed submission to send again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SubmitRetry {
+    pub delay: Duration,
+    /// The server failed while handling the request, and may have created a
+    /// job for it before failing
+    pub may_have_created_job: bool,
+}
+
+/// How to submit again after `response`, if it failed with a transient error
+/// and `strategy` allows another attempt
+///
+/// `response` is the status and headers of the response, or the error if
+/// none came. A submission is a POST that creates a job, so it is only sent
+/// again as such when it cannot have reached the API: on `502 Bad Gateway`,
+/// `503 Service Unavailable` and failures to connect. Other server errors may
+/// come after the job was created, so whether it was has to be looked up with
+/// [`may_resubmit`] first. `429 Too Many Requests` is waited out with
+/// [`rate_limit_delay`].
+pub(super) fn submit_retry_delay(
+    strategy: &dyn PollStrategy,
+    attempt: u32,
+    response: Result<(StatusCode, &reqwest::header::HeaderMap), &ApiClientError>,
+) -> Option<SubmitRetry> {
+    let (reason, hint, may_have_created_job) = match response {
+        Ok((status @ (StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE), headers)) => {
+            (status.to_string(), retry_after_hint(headers), false)
+        }
+        Ok((status, headers)) if status.is_server_error() => {
+            (status.to_string(), retry_after_hint(headers), true)
+        }
+        Err(ApiClientError::Reqwest(e)) if e.is_connect() => (e.to_string(), None, false),
+        _ => return None,
+    };
+    let delay = strategy.next_delay(attempt, hint)?;
+    warn!("Submission failed ({reason}), retrying in {delay:?}");
+    Some(SubmitRetry {
+        delay,
+        may_have_created_job,
+    })
+}
+
+/// Whether the submission of `class_hash` may be sent again after a server
+/// error, judging by `verification`, the class's state on the check endpoint
+///
+/// The API cannot list the jobs of a class, so a class that is verified is
+/// taken as a sign that the failed request created a job. A class that cannot
+/// be checked is not submitted again either.
+pub(super) fn may_resubmit(
+    class_hash: &ClassHash,
+    verification: &Result<ClassVerification, ApiClientError>,
+) -> bool {
+    match verification {
+        Ok(ClassVerification::NotVerified | ClassVerification::NotDeclared) => true,
+        Ok(ClassVerification::Verified(_)) => {
+            warn!(
+                "Class {class_hash} was verified by the failed submission; not submitting it again"
+            );
+            false
+        }
+        Err(e) => {
+            warn!(
+                "Cannot tell whether the failed submission of {class_hash} created a job ({e}); not submitting it again"
+            );
+            false
+        }
+    }
+}
+
+/// Delay before sending a request again, if its response with `status` and
+/// `headers` is `429 Too Many Requests` and `strategy` allows another attempt
+pub(super) fn rate_limit_delay(
+    strategy: &dyn PollStrategy,
+    attempt: u32,
+    status: StatusCode,
+    headers: &reqwest::header::HeaderMap,
+) -> Option<Duration> {
+    if status != StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+    let delay = strategy.next_delay(attempt, retry_after_hint(headers))?;
+    info!("Rate limited by the API, retrying in {delay:?}");
+    Some(delay)
+}
+
+/// `body` gzip-compressed, if it is to be `compressed`
+pub(super) fn compressed_body(body: &[u8], compressed: bool) -> io::Result<Option<Vec<u8>>> {
+    if !compressed {
+        return Ok(None);
+    }
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body)?;
+    let gzipped = encoder.finish()?;
+    debug!(
+        "Compressed the verification request from {} to {} bytes",
+        body.len(),
+        gzipped.len()
+    );
+    Ok(Some(gzipped))
+}
+
+/// Body of a streamed request, telling the progress observer how much of it
+/// was sent
+struct UploadReader {
+    body: io::Cursor<Vec<u8>>,
+    total: u64,
+    progress: Arc<dyn ProgressObserver>,
+}
+
+impl UploadReader {
+    fn new(body: Vec<u8>, progress: Arc<dyn ProgressObserver>) -> Self {
+        Self {
+            total: body.len() as u64,
+            body: io::Cursor::new(body),
+            progress,
+        }
+    }
+}
+
+impl Read for UploadReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.body.read(buf)?;
+        if read > 0 {
+            self.progress
+                .upload_progress(self.body.position(), self.total);
+        }
+        Ok(read)
+    }
 }
 
-const fn is_is_progress(status: &Status) -> bool {
-    match status {
-        Status::InProgress => true,
-        Status::Finished(_) => false,
+/// The error for a response with `status` refusing the request to `url`, if
+/// it does
+pub(super) fn auth_failure(
+    url: Url,
+    status: StatusCode,
+    api_key_sent: bool,
+) -> Option<ApiClientError> {
+    let reason = match (status, api_key_sent) {
+        (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN, false) => {
+            "it requires an API key and none was sent"
+        }
+        (StatusCode::UNAUTHORIZED, true) => "the API key was not accepted",
+        (StatusCode::FORBIDDEN, true) => "the API key does not grant access to this request",
+        _ => return None,
+    };
+    Some(ApiClientError::Unauthorized {
+        url,
+        status: status.as_u16(),
+        reason,
+    })
+}
+
+/// Delay requested by a `Retry-After` header
+///
+/// Only the delay-seconds form is supported; HTTP dates are ignored.
+pub(super) fn retry_after_hint(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Contents of `files`, without the workspace members they leave out,
+/// passed through `transforms`
+pub(super) fn payload_files(
+    transforms: &[Arc<dyn PayloadTransform>],
+    files: &[FileInfo],
+) -> Result<PayloadFiles, ApiClientError> {
+    let mut files_map = PayloadFiles::new();
+    for file in files {
+        files_map.insert(file.name.clone(), fs::read_to_string(file.path.as_path())?);
+    }
+    prune_workspace_members(&mut files_map);
+    for transform in transforms {
+        transform
+            .apply(&mut files_map)
+            .map_err(|reason| ApiClientError::TransformRejected {
+                transform: transform.name(),
+                reason,
+            })?;
+    }
+    Ok(files_map)
+}
+
+/// JSON body of a verification request for `files`, passed through `transforms`
+pub(super) fn verification_request_body(
+    transforms: &[Arc<dyn PayloadTransform>],
+    license: Option<String>,
+    name: &str,
+    project_metadata: ProjectMetadataInfo,
+    files: &[FileInfo],
+    url: &Url,
+) -> Result<Vec<u8>, ApiClientError> {
+    // Prepare license value
+    let license_value = license.map_or_else(
+        || "NONE".to_string(),
+        |lic| if lic == "MIT" { "MIT".to_string() } else { lic },
+    );
+
+    // Add Dojo version if available
+    let dojo_version = project_metadata.dojo_version.as_ref().map_or_else(
+        || {
+            debug!("📤 No dojo_version to include in API request");
+            None
+        },
+        |dojo_version| {
+            info!("📤 Adding dojo_version to API request: {dojo_version}");
+            Some(dojo_version.clone())
+        },
+    );
+
+    info!(
+        "🌐 API request payload prepared - build_tool: '{}', dojo_version: {:?}",
+        project_metadata.build_tool, project_metadata.dojo_version
+    );
+
+    let files_map = payload_files(transforms, files)?;
+
+    // Build JSON request body
+    let request_body = VerificationRequest {
+        compiler_version: project_metadata.cairo_version.to_string(),
+        scarb_version: project_metadata.scarb_version.to_string(),
+        package_name: project_metadata.package_name.clone(),
+        name: name.to_string(),
+        contract_file: project_metadata.contract_file.clone(),
+        contract_name: project_metadata.contract_file.clone(),
+        project_dir_path: project_metadata.project_dir_path.clone(),
+        build_tool: project_metadata.build_tool,
+        license: license_value,
+        dojo_version,
+        note: project_metadata.note,
+        files: files_map,
+    };
+
+    // Debug logging
+    debug!("🚀 === API REQUEST PAYLOAD DEBUG ===");
+    debug!("🎯 Target URL: {url}");
+    debug!("🏗️  Request Method: POST");
+    debug!("📦 Content-Type: application/json");
+    if let Ok(json_str) = serde_json::to_string_pretty(&request_body) {
+        debug!("📋 Request Body: {json_str}");
+    }
+    debug!("📊 Total files: {}", files.len());
+    debug!("🚀 === END API REQUEST PAYLOAD ===");
+
+    Ok(serde_json::to_vec(&request_body).map_err(std::io::Error::from)?)
+}
+
+/// Parse a job status response received from `url`
+pub(super) fn parse_job(url: &Url, response_text: &str) -> Result<VerificationJob, ApiClientError> {
+    log::debug!("Raw API Response: {response_text}");
+    let data: VerificationJob = serde_json::from_str(response_text).map_err(|e| {
+        log::error!("Failed to parse JSON response: {e}");
+        log::error!("Response text: {response_text}");
+        ApiClientError::from(RequestFailure::new(
+            url.clone(),
+            StatusCode::OK,
+            format!("Failed to parse JSON response: {e}"),
+        ))
+    })?;
+
+    // Debug logging to see the actual response
+    log::debug!("Parsed API Response: job_id={}, status={:?}, status_description={:?}, message={:?}, error_category={:?}",
+               data.job_id, data.status, data.status_description, data.message, data.error_category);
+    Ok(data)
+}
+
+/// The finished job, `None` while it is in progress, or the failure it ended with
+pub(super) fn job_outcome(data: VerificationJob) -> Result<JobStatus, ApiClientError> {
+    match data.status {
+        VerifyJobStatus::Success => Ok(Some(data)),
+        VerifyJobStatus::Fail => {
+            let error_message = data
+                .message
+                .or_else(|| data.status_description.clone())
+                .unwrap_or_else(|| "unknown failure".to_owned());
+
+            // Parse specific error types from the server response
+            let parsed_error = if error_message.contains("Payload too large")
+                || error_message.contains("payload too large")
+            {
+                "Request payload too large. The project files exceed the maximum allowed size of 10MB. Try reducing file sizes or removing unnecessary files."
+            } else {
+                &error_message
+            };
+
+            Err(ApiClientError::from(
+                VerificationError::VerificationFailure(parsed_error.to_owned()),
+            ))
+        }
+        VerifyJobStatus::CompileFailed => {
+            let error_message = data
+                .message
+                .or_else(|| data.status_description.clone())
+                .unwrap_or_else(|| "unknown failure".to_owned());
+
+            // Parse specific error types from the server response
+            let parsed_error = if error_message.contains("Payload too large")
+                || error_message.contains("payload too large")
+            {
+                "Request payload too large. The project files exceed the maximum allowed size of 10MB. Try reducing file sizes or removing unnecessary files."
+            } else if error_message.contains("Couldn't connect to cairo compilation service") {
+                "Cairo compilation service is currently unavailable. Please try again later."
+            } else {
+                &error_message
+            };
+
+            Err(ApiClientError::from(VerificationError::CompilationFailure(
+                parsed_error.to_owned(),
+            )))
+        }
+        VerifyJobStatus::Submitted
+        | VerifyJobStatus::Compiled
+        | VerifyJobStatus::Processing
+        | VerifyJobStatus::Unknown => Ok(None),
     }
 }
 
@@ -432,7 +1144,8 @@ pub fn poll_verification_status(
 
 /// Poll verification status with an optional callback for status updates
 ///
-/// This function polls the verification service for job status with exponential backoff.
+/// This function polls the verification service for job status using the client's
+/// [`PollStrategy`], or a fixed 2 second interval for up to 10 minutes if none is set.
 /// If a callback is provided, it will be called with the current status on each poll iteration.
 ///
 /// # Arguments
@@ -449,56 +1162,51 @@ pub fn poll_verification_status_with_callback(
     job_id: &str,
     callback: Option<&dyn Fn(&VerificationJob)>,
 ) -> Result<VerificationJob, ApiClientError> {
-    let fetch = || -> Result<VerificationJob, Status> {
-        let result: Option<VerificationJob> = api
-            .get_job_status(job_id.to_owned())
-            .map_err(Status::Finished)?;
-
-        result.ok_or(Status::InProgress)
-    };
+    let default_strategy = FixedInterval::default();
+    let strategy = api.poll_strategy().unwrap_or(&default_strategy);
 
-    let mut retry_count = 0;
+    let mut attempt = 0;
     let mut last_status: Option<VerificationJob> = None;
+    let started = Instant::now();
 
-    // Fixed 2s polling interval (using ExponentialBuilder with same min/max)
-    fetch
-        .retry(
-            ExponentialBuilder::default()
-                .with_min_delay(Duration::from_secs(2))
-                .with_max_delay(Duration::from_secs(2)) // Same as min = fixed 2s interval
-                .with_max_times(300), // Max 300 retries * 2s = 10 minutes
-        )
-        .when(is_is_progress)
-        .notify(|_, dur: Duration| {
-            retry_count += 1;
-
-            // Try to get current status and show it via callback
-            if let Some(ref cb) = callback {
-                // Use get_job_status_raw to get full job details even for in-progress jobs
-                match api.get_job_status_raw(job_id.to_owned()) {
-                    Ok(current_status) => {
-                        last_status = Some(current_status.clone());
-                        cb(&current_status);
-                    }
-                    Err(_) => {
-                        // Error getting status, show last status or fallback message
-                        if let Some(ref status) = last_status {
-                            cb(status);
-                        } else {
-                            println!("⏳ Checking status... (retry in {dur:?})");
-                        }
+    loop {
+        attempt += 1;
+        if let Some(job) = api.get_job_status(job_id.to_owned())? {
+            return Ok(job);
+        }
+
+        let Some(dur) = strategy.next_delay_within(attempt, api.retry_after(), started.elapsed())
+        else {
+            return Err(ApiClientError::InProgress);
+        };
+
+        // Try to get current status and show it via callback
+        if let Some(ref cb) = callback {
+            // Use get_job_status_raw to get full job details even for in-progress jobs
+            match api.get_job_status_raw(job_id.to_owned()) {
+                Ok(current_status) => {
+                    last_status = Some(current_status.clone());
+                    cb(&current_status);
+                }
+                Err(_) => {
+                    // Error getting status, show last status or fallback message
+                    if let Some(ref status) = last_status {
+                        cb(status);
+                    } else {
+                        sink::line(format!(
+                            "{} Checking status... (retry in {dur:?})",
+                            sink::marker(Marker::Pending)
+                        ));
                     }
                 }
-            } else {
-                // No callback, use simple message
-                println!("Job: {job_id} didn't finish, retrying in {dur:?}");
             }
-        })
-        .call()
-        .map_err(|err| match err {
-            Status::InProgress => ApiClientError::InProgress,
-            Status::Finished(e) => e,
-        })
+        } else {
+            // No callback, use simple message
+            sink::line(format!("Job: {job_id} didn't finish, retrying in {dur:?}"));
+        }
+
+        std::thread::sleep(dur);
+    }
 }
 
 #[cfg(test)]
@@ -506,100 +1214,607 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_filter_scarb_toml_removes_dev_dependencies() {
-        let input = r#"[package]
-name = "test"
-version = "0.1.0"
+    #[allow(clippy::unwrap_used)]
+    fn test_search_classes() {
+        let mut server = mockito::Server::new();
+        let _mock = server
+            .mock("GET", "/class-verify/search")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "name".into(),
+                "My Token".into(),
+            ))
+            .with_body(
+                r#"{"classes": [
+                    {"verified": true, "class_hash": "0x1", "name": "My Token", "version": "1.0.0"},
+                    {"verified": false, "class_hash": "0x2"}
+                ]}"#,
+            )
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap()).unwrap();
+
+        let classes = client.search_classes("My Token").unwrap();
+        assert_eq!(classes.len(), 2);
+        assert!(classes[0].verified);
+        assert_eq!(classes[1].class_hash, "0x2");
+        assert!(!classes[1].verified);
+        drop(server);
+    }
 
-[dependencies]
-starknet = "2.10.1"
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_search_classes_unsupported() {
+        let mut server = mockito::Server::new();
+        let _mock = server
+            .mock("GET", mockito::Matcher::Any)
+            .with_status(404)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap()).unwrap();
+
+        let err = client.search_classes("MyToken").unwrap_err();
+        assert_eq!(err.error_code(), "E070");
+        drop(server);
+    }
 
-[dev-dependencies]
-assert_macros = "2.10.1"
-snforge_std = "0.38.3"
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_check_class_verification_states() {
+        let mut server = mockito::Server::new();
+        let _verified = server
+            .mock("GET", "/class-verify/check/0x1")
+            .with_body(r#"{"verified": true, "class_hash": "0x1", "name": "MyToken"}"#)
+            .create();
+        let _not_verified = server
+            .mock("GET", "/class-verify/check/0x2")
+            .with_body(r#"{"verified": false, "class_hash": "0x2"}"#)
+            .create();
+        let _missing = server
+            .mock("GET", "/class-verify/check/0x3")
+            .with_status(404)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap()).unwrap();
+        let check = |hash: &str| {
+            client
+                .check_class_verification(&ClassHash::new(hash).unwrap())
+                .unwrap()
+        };
 
-[scripts]
-test = "snforge test"
-"#;
+        let verified = check("0x1");
+        assert_eq!(verified.state(), "verified");
+        assert_eq!(verified.info().unwrap().name.as_deref(), Some("MyToken"));
+        assert!(matches!(check("0x2"), ClassVerification::NotVerified));
+        assert!(matches!(check("0x3"), ClassVerification::NotDeclared));
+        drop(server);
+    }
 
-        let expected = r#"[package]
-name = "test"
-version = "0.1.0"
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_health_check() {
+        let check = |status: usize, content_type: &str, body: &str| {
+            let mut server = mockito::Server::new();
+            let _capabilities = server
+                .mock("GET", "/class-verify/capabilities")
+                .with_status(status)
+                .with_header("content-type", content_type)
+                .with_body(body)
+                .create();
+            ApiClient::new(Url::parse(&server.url()).unwrap())
+                .unwrap()
+                .health_check()
+        };
 
-[dependencies]
-starknet = "2.10.1"
+        assert!(check(200, "application/json", r#"{"max_file_size": 1024}"#).is_ok());
+        // APIs without the capabilities endpoint are still usable
+        assert!(check(404, "application/json", r#"{"error": "not found"}"#).is_ok());
 
-# [dev-dependencies] section removed for remote compilation
+        let web_page = check(200, "text/html; charset=utf-8", "<html></html>").unwrap_err();
+        assert_eq!(web_page.error_code(), "E083");
+        assert!(web_page.to_string().contains("with a web page"));
+        let down = check(503, "text/plain", "unavailable").unwrap_err();
+        assert!(down.to_string().contains("it answered 503"));
+    }
 
-[scripts]
-test = "snforge test""#;
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_get_contract_class_hash() {
+        let mut server = mockito::Server::new();
+        let _contract = server
+            .mock("GET", "/contracts/0x123")
+            .with_body(r#"{"address": "0x123", "classHash": "0x456"}"#)
+            .create();
+        let _missing = server
+            .mock("GET", "/contracts/0x789")
+            .with_status(404)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap()).unwrap();
+
+        assert_eq!(client.get_contract_class_hash("0x123").unwrap(), "0x456");
+        let err = client.get_contract_class_hash("0x789").unwrap_err();
+        assert_eq!(err.error_code(), "E075");
+        drop(server);
+    }
 
-        let result = ApiClient::filter_scarb_toml_content(input);
-        assert_eq!(result, expected);
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_api_key_is_sent_as_bearer_token() {
+        let mut server = mockito::Server::new();
+        let _contract = server
+            .mock("GET", "/contracts/0x123")
+            .match_header("authorization", "Bearer internal-key")
+            .with_body(r#"{"address": "0x123", "classHash": "0x456"}"#)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_api_key("internal-key");
+
+        assert_eq!(client.get_contract_class_hash("0x123").unwrap(), "0x456");
+        let anonymous = ApiClient::new(Url::parse(&server.url()).unwrap()).unwrap();
+        assert!(anonymous.get_contract_class_hash("0x123").is_err());
+        drop(server);
     }
 
     #[test]
-    fn test_filter_scarb_toml_preserves_other_sections() {
-        let input = r#"[package]
-name = "test"
+    #[allow(clippy::unwrap_used)]
+    fn test_refused_api_key() {
+        let mut server = mockito::Server::new();
+        let _unauthorized = server
+            .mock("GET", "/class-verify/job/job-1")
+            .with_status(401)
+            .create();
+        let _forbidden = server
+            .mock("GET", "/class-verify/job/job-2")
+            .with_status(403)
+            .create();
+        let base = Url::parse(&server.url()).unwrap();
+
+        let anonymous = ApiClient::new(base.clone()).unwrap();
+        let err = anonymous.get_job_status("job-1").unwrap_err();
+        assert_eq!(err.error_code(), "E085");
+        assert!(err
+            .to_string()
+            .contains("(401): it requires an API key and none was sent"));
+        assert!(!err.is_unreachable());
+
+        let client = ApiClient::new(base).unwrap().with_api_key("expired-key");
+        let err = client.get_job_status("job-1").unwrap_err();
+        assert!(err.to_string().contains("the API key was not accepted"));
+        let err = client.get_job_status("job-2").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("the API key does not grant access to this request"));
+        drop(server);
+    }
 
-[dependencies]
-cairo = "2.0.0"
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_unreachable_api() {
+        let mut server = mockito::Server::new();
+        let _unavailable = server
+            .mock("GET", "/class-verify/job/job-1")
+            .with_status(503)
+            .create();
+        let _missing = server
+            .mock("GET", "/class-verify/job/job-2")
+            .with_status(404)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap()).unwrap();
+
+        assert!(client.get_job_status("job-1").unwrap_err().is_unreachable());
+        assert!(!client.get_job_status("job-2").unwrap_err().is_unreachable());
+
+        // Nothing listens on the discard port
+        let offline = ApiClient::new(Url::parse("http://127.0.0.1:9/").unwrap()).unwrap();
+        assert!(offline
+            .get_job_status("job-1")
+            .unwrap_err()
+            .is_unreachable());
+        drop(server);
+    }
 
-[tool.fmt]
-max-line-length = 120
-"#;
+    #[derive(Debug, Default)]
+    struct RecordingObserver(Mutex<Vec<String>>);
 
-        let expected = r#"[package]
-name = "test"
+    impl RecordingObserver {
+        fn push(&self, event: String) {
+            self.0
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(event);
+        }
+    }
 
-[dependencies]
-cairo = "2.0.0"
+    impl ProgressObserver for RecordingObserver {
+        fn upload_started(&self, bytes: u64) {
+            self.push(format!("upload {}", bytes > 0));
+        }
 
-[tool.fmt]
-max-line-length = 120"#;
+        fn bytes_uploaded(&self, bytes: u64) {
+            self.push(format!("uploaded {}", bytes > 0));
+        }
+
+        fn status_changed(&self, job_id: &str, status: VerifyJobStatus) {
+            self.push(format!("{job_id} {status}"));
+        }
 
-        let result = ApiClient::filter_scarb_toml_content(input);
-        assert_eq!(result, expected);
+        fn upload_progress(&self, sent: u64, total: u64) {
+            self.push(format!("sent {sent}/{total}"));
+        }
+
+        fn rate_limited(&self, endpoint: &str, delay: Duration) {
+            self.push(format!(
+                "rate limited on {endpoint} for {}s",
+                delay.as_secs()
+            ));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_progress_observer_events() {
+        let mut server = mockito::Server::new();
+        let _submit = server
+            .mock("POST", "/class-verify/0x1")
+            .with_body(r#"{"job_id": "job-1"}"#)
+            .create();
+        let _status = server
+            .mock("GET", "/class-verify/job/job-1")
+            .with_body(r#"{"job_id": "job-1", "status": 5}"#)
+            .create();
+        let observer = Arc::new(RecordingObserver::default());
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_progress_observer(observer.clone());
+
+        let metadata = ProjectMetadataInfo::new(
+            semver::Version::new(2, 11, 4),
+            semver::Version::new(2, 11, 4),
+            String::new(),
+            "src/lib.cairo".to_string(),
+            "my_package".to_string(),
+            crate::core::project::ProjectType::Scarb,
+            None,
+        );
+        let class_hash = ClassHash::new("0x1").unwrap();
+        client
+            .verify_class(&class_hash, None, "MyContract", metadata, &[])
+            .unwrap();
+        // Repeated checks of an unchanged status are reported once
+        client.get_job_status_raw("job-1").unwrap();
+        assert!(client.get_job_status("job-1").unwrap().is_none());
+
+        let events = observer.0.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            [
+                "upload true",
+                "uploaded true",
+                &format!("job-1 {}", VerifyJobStatus::Processing)
+            ]
+        );
+        drop(server);
     }
 
     #[test]
-    fn test_filter_scarb_toml_handles_no_dev_dependencies() {
-        let input = r#"[package]
-name = "test"
-version = "1.0.0"
+    #[allow(clippy::unwrap_used)]
+    fn test_submission_retries_transient_errors() {
+        let mut server = mockito::Server::new();
+        let unavailable = server
+            .mock("POST", "/class-verify/0x1")
+            .with_status(503)
+            .expect(1)
+            .create();
+        let rate_limited = server
+            .mock("POST", "/class-verify/0x1")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(1)
+            .create();
+        let submitted = server
+            .mock("POST", "/class-verify/0x1")
+            .with_body(r#"{"job_id": "job-1"}"#)
+            .expect(1)
+            .create();
+        let rejected = server
+            .mock("POST", "/class-verify/0x2")
+            .with_status(400)
+            .with_body(r#"{"error": "invalid license"}"#)
+            .expect(1)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_submit_retry(Arc::new(FixedInterval::new(Duration::from_millis(1), 2)));
+        let metadata = ProjectMetadataInfo::new(
+            semver::Version::new(2, 11, 4),
+            semver::Version::new(2, 11, 4),
+            String::new(),
+            "src/lib.cairo".to_string(),
+            "my_package".to_string(),
+            crate::core::project::ProjectType::Scarb,
+            None,
+        );
+        let submit = |class_hash: &str| {
+            client.verify_class(
+                &ClassHash::new(class_hash).unwrap(),
+                None,
+                "MyContract",
+                metadata.clone(),
+                &[],
+            )
+        };
 
-[dependencies]
-starknet = "2.10.1"
-"#;
+        assert_eq!(submit("0x1").unwrap(), "job-1");
+        unavailable.assert();
+        rate_limited.assert();
+        submitted.assert();
 
-        // Should remain unchanged except for trailing newline
-        let result = ApiClient::filter_scarb_toml_content(input);
-        assert_eq!(result, input.lines().collect::<Vec<_>>().join("\n"));
+        // Requests the API rejects are not retried
+        let err = submit("0x2").unwrap_err();
+        assert!(err.to_string().contains("invalid license"));
+        rejected.assert();
+        drop(server);
     }
 
     #[test]
-    fn test_filter_scarb_toml_handles_dev_deps_at_end() {
-        let input = r#"[package]
-name = "test"
+    #[allow(clippy::unwrap_used)]
+    fn test_submission_after_server_error_checks_the_class_first() {
+        let mut server = mockito::Server::new();
+        let failed = server
+            .mock("POST", "/class-verify/0x1")
+            .with_status(500)
+            .expect(1)
+            .create();
+        let _verified = server
+            .mock("GET", "/class-verify/check/0x1")
+            .with_body(r#"{"verified": true, "class_hash": "0x1"}"#)
+            .create();
+        let timed_out = server
+            .mock("POST", "/class-verify/0x2")
+            .with_status(504)
+            .expect(1)
+            .create();
+        let submitted = server
+            .mock("POST", "/class-verify/0x2")
+            .with_body(r#"{"job_id": "job-2"}"#)
+            .expect(1)
+            .create();
+        let not_verified = server
+            .mock("GET", "/class-verify/check/0x2")
+            .with_body(r#"{"verified": false, "class_hash": "0x2"}"#)
+            .expect(1)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_submit_retry(Arc::new(FixedInterval::new(Duration::from_millis(1), 2)));
+        let metadata = ProjectMetadataInfo::new(
+            semver::Version::new(2, 11, 4),
+            semver::Version::new(2, 11, 4),
+            String::new(),
+            "src/lib.cairo".to_string(),
+            "my_package".to_string(),
+            crate::core::project::ProjectType::Scarb,
+            None,
+        );
+        let submit = |class_hash: &str| {
+            client.verify_class(
+                &ClassHash::new(class_hash).unwrap(),
+                None,
+                "MyContract",
+                metadata.clone(),
+                &[],
+            )
+        };
+
+        // The failed request verified the class, so it is not sent again
+        assert!(submit("0x1").is_err());
+        failed.assert();
+
+        assert_eq!(submit("0x2").unwrap(), "job-2");
+        timed_out.assert();
+        not_verified.assert();
+        submitted.assert();
+        drop(server);
+    }
 
-[dependencies]
-starknet = "2.10.1"
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_rate_limited_requests_wait_and_retry() {
+        let mut server = mockito::Server::new();
+        let rate_limited = server
+            .mock("GET", "/class-verify/job/job-1")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(2)
+            .create();
+        let done = server
+            .mock("GET", "/class-verify/job/job-1")
+            .with_body(r#"{"job_id": "job-1", "status": 5}"#)
+            .expect(1)
+            .create();
+        let observer = Arc::new(RecordingObserver::default());
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_progress_observer(observer.clone());
+
+        assert!(client.get_job_status("job-1").unwrap().is_none());
+        rate_limited.assert();
+        done.assert();
+        let events = observer.0.lock().unwrap().clone();
+        assert_eq!(events[0], "rate limited on class-verify/job/job-1 for 0s");
+        assert_eq!(events.len(), 3);
+
+        // Once the retries are used up, the 429 is returned
+        let _limited = server
+            .mock("GET", "/class-verify/job/job-2")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(2)
+            .create();
+        let client =
+            client.with_rate_limit_retry(Arc::new(FixedInterval::new(Duration::from_millis(1), 1)));
+        let err = client.get_job_status("job-2").unwrap_err();
+        assert!(err.to_string().contains("429"), "{err}");
+        drop(server);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_large_submissions_report_upload_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.cairo");
+        fs::write(&path, "// padding\n".repeat(150_000)).unwrap();
+        let files = [FileInfo {
+            name: "src/lib.cairo".to_string(),
+            path,
+        }];
+
+        let mut server = mockito::Server::new();
+        let submitted = server
+            .mock("POST", "/class-verify/0x1")
+            .with_body(r#"{"job_id": "job-1"}"#)
+            .expect(1)
+            .create();
+        let observer = Arc::new(RecordingObserver::default());
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_progress_observer(observer.clone());
+        let metadata = ProjectMetadataInfo::new(
+            semver::Version::new(2, 11, 4),
+            semver::Version::new(2, 11, 4),
+            String::new(),
+            "src/lib.cairo".to_string(),
+            "my_package".to_string(),
+            crate::core::project::ProjectType::Scarb,
+            None,
+        );
+        let job_id = client
+            .verify_class(
+                &ClassHash::new("0x1").unwrap(),
+                None,
+                "MyContract",
+                metadata,
+                &files,
+            )
+            .unwrap();
+        assert_eq!(job_id, "job-1");
+        submitted.assert();
+
+        let events = observer.0.lock().unwrap().clone();
+        let sent: Vec<_> = events.iter().filter(|e| e.starts_with("sent ")).collect();
+        assert!(sent.len() > 1, "{events:?}");
+        let (last, total) = sent.last().unwrap()[5..].split_once('/').unwrap();
+        assert_eq!(last, total);
+        assert!(total.parse::<u64>().unwrap() >= STREAMED_UPLOAD_SIZE);
+        assert_eq!(events.last().unwrap(), "uploaded true");
+        drop(server);
+    }
 
-[dev-dependencies]
-test_lib = "1.0.0"
-another_lib = "2.0.0"
-"#;
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_compressed_submissions_fall_back_when_refused() {
+        let mut server = mockito::Server::new();
+        let accepted = server
+            .mock("POST", "/class-verify/0x1")
+            .match_header("content-encoding", "gzip")
+            .with_body(r#"{"job_id": "job-1"}"#)
+            .expect(1)
+            .create();
+        let refused = server
+            .mock("POST", "/class-verify/0x2")
+            .match_header("content-encoding", "gzip")
+            .with_status(415)
+            .expect(1)
+            .create();
+        let uncompressed = server
+            .mock("POST", "/class-verify/0x2")
+            .match_header("content-encoding", mockito::Matcher::Missing)
+            .with_body(r#"{"job_id": "job-2"}"#)
+            .expect(2)
+            .create();
+        let client = ApiClient::new(Url::parse(&server.url()).unwrap())
+            .unwrap()
+            .with_compression(true);
+        let metadata = ProjectMetadataInfo::new(
+            semver::Version::new(2, 11, 4),
+            semver::Version::new(2, 11, 4),
+            String::new(),
+            "src/lib.cairo".to_string(),
+            "my_package".to_string(),
+            crate::core::project::ProjectType::Scarb,
+            None,
+        );
+        let submit = |client: &ApiClient, class_hash: &str| {
+            client
+                .verify_class(
+                    &ClassHash::new(class_hash).unwrap(),
+                    None,
+                    "MyContract",
+                    metadata.clone(),
+                    &[],
+                )
+                .unwrap()
+        };
 
-        let expected = r#"[package]
-name = "test"
+        assert_eq!(submit(&client, "0x1"), "job-1");
+        accepted.assert();
 
-[dependencies]
-starknet = "2.10.1"
+        // Refused compressed requests are sent again, and later ones
+        // uncompressed from the start
+        assert_eq!(submit(&client, "0x2"), "job-2");
+        assert_eq!(submit(&client, "0x2"), "job-2");
+        refused.assert();
+        uncompressed.assert();
+        drop(server);
+    }
 
-# [dev-dependencies] section removed for remote compilation"#;
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_request_body_covers_request_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<_> = ["src/lib.cairo", "Scarb.toml", "src/token.cairo"]
+            .into_iter()
+            .map(|name| {
+                let path = dir.path().join(name.replace('/', "_"));
+                fs::write(&path, name).unwrap();
+                FileInfo {
+                    name: name.to_string(),
+                    path,
+                }
+            })
+            .collect();
+        let client = ApiClient::new(Url::parse("https://api.example.com").unwrap()).unwrap();
+        let metadata = ProjectMetadataInfo::new(
+            semver::Version::new(2, 11, 4),
+            semver::Version::new(2, 11, 4),
+            String::new(),
+            "src/lib.cairo".to_string(),
+            "my_package".to_string(),
+            crate::core::project::ProjectType::Scarb,
+            None,
+        );
+        let body = |license: &str, metadata: &ProjectMetadataInfo| {
+            client
+                .verification_request_body(
+                    &ClassHash::new("0x1").unwrap(),
+                    Some(license.to_string()),
+                    "MyContract",
+                    metadata.clone(),
+                    &files,
+                )
+                .unwrap()
+        };
 
-        let result = ApiClient::filter_scarb_toml_content(input);
-        assert_eq!(result, expected);
+        // Files are serialized in order, so identical requests are identical
+        let first = body("MIT", &metadata);
+        assert_eq!(first, body("MIT", &metadata));
+        let json: serde_json::Value = serde_json::from_slice(&first).unwrap();
+        let names: Vec<_> = json["files"].as_object().unwrap().keys().collect();
+        assert_eq!(names, ["Scarb.toml", "src/lib.cairo", "src/token.cairo"]);
+
+        assert_ne!(first, body("Apache-2.0", &metadata));
+        let mut other = metadata.clone();
+        other.scarb_version = semver::Version::new(2, 12, 0);
+        assert_ne!(first, body("MIT", &other));
+        let mut other = metadata;
+        other.contract_file = "src/token.cairo".to_string();
+        assert_ne!(first, body("MIT", &other));
     }
 }
@@ -1,7 +1,21 @@
 use thiserror::Error;
 use url::Url;
 
-use crate::utils::errors::RequestFailure;
+use crate::utils::{
+    errors::RequestFailure,
+    suggestion::{Suggestion, SuggestionList},
+};
+
+/// Suggestions for [`ApiClientError::ClassNotFound`]
+///
+/// Its code E012 is shared with invalid dependency paths, which are the ones
+/// `voyager explain E012` describes.
+const CLASS_NOT_FOUND_SUGGESTIONS: &[&str] = &[
+    "Check that the class hash is correct",
+    "Verify the class has been declared on the network",
+    "Ensure you're using the correct network (mainnet/sepolia)",
+    "Use a block explorer to verify the class hash exists",
+];
 
 #[derive(Debug, Error)]
 pub enum VerificationError {
@@ -21,64 +35,44 @@ impl VerificationError {
         }
     }
 
+    /// Next steps for the failure, depending on what the API reported
     #[must_use]
-    pub fn suggestions(&self) -> Vec<&'static str> {
-        match self {
-            Self::CompilationFailure(msg) => {
-                let mut suggestions = vec![
-                    "Check that all dependencies are properly declared in Scarb.toml",
-                    "Verify that the contract syntax is correct",
-                    "Ensure all imports are valid and accessible",
-                    "Check for typos in function names and variable declarations",
-                ];
-
-                if msg.contains("not found") {
-                    suggestions.push("Verify that all modules and dependencies are available");
-                }
-
-                if msg.contains("syntax") {
-                    suggestions.push("Review the Cairo syntax documentation");
-                }
-
-                suggestions
-            }
-            Self::VerificationFailure(msg) => {
-                let mut suggestions = vec![
-                    "Ensure the compiled class hash matches the declared class hash",
-                    "Verify that the source code corresponds to the deployed contract",
-                    "Check that all dependencies are at the correct versions",
-                    "Confirm that the contract was compiled with the same Cairo version",
-                ];
-
-                if msg.contains("hash") {
-                    suggestions.push("Double-check the class hash value");
-                }
-
-                if msg.contains("version") {
-                    suggestions.push("Verify Cairo compiler version compatibility");
-                }
-
-                suggestions
-            }
-        }
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let mut suggestions = Suggestion::for_code(self.error_code(), &[]);
+        let extra = match self {
+            Self::CompilationFailure(msg) => [
+                msg.contains("not found")
+                    .then_some("Verify that all modules and dependencies are available"),
+                msg.contains("syntax")
+                    .then_some("Review the Cairo syntax documentation"),
+            ],
+            Self::VerificationFailure(msg) => [
+                msg.contains("hash")
+                    .then_some("Double-check the class hash value"),
+                msg.contains("version")
+                    .then_some("Verify Cairo compiler version compatibility"),
+            ],
+        };
+        suggestions.extend(extra.into_iter().flatten().map(Suggestion::new));
+        suggestions
     }
 }
 
 #[derive(Debug, Error)]
 pub enum ApiClientError {
-    #[error("[E006] Invalid base URL: {0}\n\nSuggestions:\n  • Provide a valid HTTP or HTTPS URL\n  • Example: https://api.example.com\n  • Ensure the URL includes the protocol (http:// or https://)")]
+    #[error("[E006] Invalid base URL: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     CannotBeBase(Url),
 
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
 
-    #[error("[E007] Verification job is still in progress\n\nSuggestions:\n  • Wait a moment before checking again\n  • Use --wait to automatically wait for completion\n  • Check the job status periodically")]
+    #[error("[E007] Verification job is still in progress{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     InProgress,
 
     #[error(transparent)]
     Failure(#[from] RequestFailure),
 
-    #[error("[E008] Job '{0}' not found\n\nSuggestions:\n  • Check that the job ID is correct\n  • Verify the job was submitted successfully\n  • The job may have expired from the server\n  • Try submitting a new verification request")]
+    #[error("[E008] Job '{0}' not found{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     JobNotFound(String),
 
     #[error(transparent)]
@@ -87,11 +81,33 @@ pub enum ApiClientError {
     #[error(transparent)]
     IoError(#[from] std::io::Error),
 
-    #[error("[E009] Invalid URL format: {0}\n\nSuggestions:\n  • Check the URL format is correct\n  • Ensure proper encoding of special characters\n  • Use absolute URLs with protocol (http:// or https://)")]
+    #[error("[E009] Invalid URL format: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     UrlCannotBeBase(#[from] url::ParseError),
 
-    #[error("[E012] Class '{0}' not found on-chain\n\nSuggestions:\n  • Check that the class hash is correct\n  • Verify the class has been declared on the network\n  • Ensure you're using the correct network (mainnet/sepolia)\n  • Use a block explorer to verify the class hash exists")]
+    #[error("[E012] Class '{0}' not found on-chain{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     ClassNotFound(String),
+
+    #[error("[E075] No contract found at '{0}'{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ContractNotFound(String),
+
+    #[error("[E070] Class search is not supported by '{0}'{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    SearchUnsupported(Url),
+
+    #[error("[E083] The API at '{url}' is not usable: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    UnhealthyApi { url: Url, reason: String },
+
+    #[error("[E085] {url} refused the request ({status}): {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    Unauthorized {
+        url: Url,
+        status: u16,
+        reason: &'static str,
+    },
+
+    #[error("[E072] Payload transform '{transform}' rejected the submission: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    TransformRejected {
+        transform: &'static str,
+        reason: String,
+    },
 }
 
 impl ApiClientError {
@@ -106,6 +122,45 @@ impl ApiClientError {
             Self::Verify(v) => v.error_code(),
             Self::UrlCannotBeBase(_) => "E009",
             Self::ClassNotFound(_) => "E012",
+            Self::ContractNotFound(_) => "E075",
+            Self::SearchUnsupported(_) => "E070",
+            Self::TransformRejected { .. } => "E072",
+            Self::UnhealthyApi { .. } => "E083",
+            Self::Unauthorized { .. } => "E085",
+        }
+    }
+
+    /// Whether the API could not be reached or failed on its side, rather
+    /// than rejecting the request
+    #[must_use]
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            Self::Reqwest(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            Self::Failure(f) => f.status.is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// Next steps for the error, from the failure the API reported where
+    /// there is one
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        match self {
+            Self::Reqwest(_) | Self::IoError(_) => Vec::new(),
+            Self::Failure(f) => f.suggestions(),
+            Self::Verify(e) => e.suggestions(),
+            Self::ClassNotFound(_) => Suggestion::from_templates(CLASS_NOT_FOUND_SUGGESTIONS, &[]),
+            Self::TransformRejected { transform, .. } => {
+                Suggestion::for_code(self.error_code(), &[("transform", transform)])
+            }
+            Self::CannotBeBase(_)
+            | Self::InProgress
+            | Self::JobNotFound(_)
+            | Self::UrlCannotBeBase(_)
+            | Self::ContractNotFound(_)
+            | Self::SearchUnsupported(_)
+            | Self::UnhealthyApi { .. }
+            | Self::Unauthorized { .. } => Suggestion::for_code(self.error_code(), &[]),
         }
     }
 }
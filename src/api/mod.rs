@@ -14,7 +14,7 @@
 //! ## Example Usage
 //!
 //! ```rust,no_run
-//! use verifier::api::{ApiClient, ClassVerificationInfo};
+//! use verifier::api::{ApiClient, ClassVerification};
 //! use verifier::core::class_hash::ClassHash;
 //! use url::Url;
 //!
@@ -24,30 +24,67 @@
 //!
 //! // Check if a class is verified
 //! let class_hash = ClassHash::new("0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18")?;
-//! let info = client.check_class_verification(&class_hash)?;
-//! println!("Verified: {}", info.verified);
+//! match client.check_class_verification(&class_hash)? {
+//!     ClassVerification::Verified(info) => println!("Verified as {:?}", info.name),
+//!     ClassVerification::NotVerified => println!("Declared, not verified yet"),
+//!     ClassVerification::NotDeclared => println!("Not declared on this network"),
+//! }
 //!
 //! // Get verification job status
 //! let job_status = client.get_job_status("job-id")?;
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! Async applications use [`AsyncApiClient`] instead, which has the same
+//! requests as `async` methods and must run on a Tokio runtime:
+//!
+//! ```rust,no_run
+//! use verifier::api::AsyncApiClient;
+//! use url::Url;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = AsyncApiClient::new(Url::parse("https://api.voyager.online/beta")?)?;
+//! let job = client.poll_verification_status("job-id").await?;
+//! println!("Verified {:?}", job.name);
+//! # Ok(())
+//! # }
+//! ```
 
 // Re-export the API module components
 pub use self::{
+    async_client::AsyncApiClient,
     client::{poll_verification_status_with_callback, ApiClient},
     errors::{ApiClientError, VerificationError},
     models::{
-        ClassVerificationInfo, FileInfo, ProjectMetadataInfo, VerificationJob,
-        VerificationJobDispatch,
+        Capabilities, ClassSearchResults, ClassVerification, ClassVerificationInfo, ContractInfo,
+        FileInfo, JobLogs, ProjectMetadataInfo, VerificationJob, VerificationJobDispatch,
+        VersionRange,
+    },
+    polling::{
+        poll_verification_status, Deadline, ExponentialBackoff, FixedInterval, PollStrategy,
+        PollStrategyKind, PollingConfig, ServerHinted,
+    },
+    progress::{NoProgress, ProgressObserver, STREAMED_UPLOAD_SIZE},
+    recording::{load_recording, record_http_to, Exchange, HttpRecorder},
+    rpc::{BlockId, ClassChange, ClassHistory, EmittedEvent, RpcClient, RpcError},
+    simulator::{MockServer, DEFAULT_SIMULATION_STEP},
+    transforms::{
+        default_transforms, DevDependencies, GeneratedFiles, LineEndings, PayloadFiles,
+        PayloadTransform, PayloadTransformKind, SecretScan,
     },
-    polling::poll_verification_status,
     types::{JobStatus, Status, VerifyJobStatus},
 };
 
 // Module declarations
+mod async_client;
 mod client;
 mod errors;
 mod models;
 mod polling;
+mod progress;
+mod recording;
+mod rpc;
+mod simulator;
+mod transforms;
 mod types;
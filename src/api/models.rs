@@ -2,7 +2,7 @@ use super::types::VerifyJobStatus;
 use crate::core::project::ProjectType;
 use semver;
 use serde::{Deserialize, Serialize, Serializer};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Serialize an optional f64 timestamp as an integer
@@ -18,7 +18,7 @@ where
 }
 
 /// Response from the class verification check endpoint
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ClassVerificationInfo {
     pub verified: bool,
     pub class_hash: String,
@@ -37,6 +37,69 @@ pub struct ClassVerificationInfo {
     pub contract_file: Option<String>,
 }
 
+/// What the check endpoint knows of a class
+#[derive(Debug, Clone)]
+pub enum ClassVerification {
+    /// The class is verified, with the details of its sources
+    Verified(ClassVerificationInfo),
+    /// The class is declared but its sources are not verified
+    NotVerified,
+    /// No class with this hash is declared on the network
+    NotDeclared,
+}
+
+impl ClassVerification {
+    #[must_use]
+    pub const fn is_verified(&self) -> bool {
+        matches!(self, Self::Verified(_))
+    }
+
+    /// The details of a verified class
+    #[must_use]
+    pub const fn info(&self) -> Option<&ClassVerificationInfo> {
+        match self {
+            Self::Verified(info) => Some(info),
+            Self::NotVerified | Self::NotDeclared => None,
+        }
+    }
+
+    /// Name of the state in machine-readable output
+    #[must_use]
+    pub const fn state(&self) -> &'static str {
+        match self {
+            Self::Verified(_) => "verified",
+            Self::NotVerified => "not_verified",
+            Self::NotDeclared => "not_declared",
+        }
+    }
+}
+
+impl From<ClassVerificationInfo> for ClassVerification {
+    /// State of a class the check endpoint answered for
+    fn from(info: ClassVerificationInfo) -> Self {
+        if info.verified {
+            Self::Verified(info)
+        } else {
+            Self::NotVerified
+        }
+    }
+}
+
+/// Response from the explorer's contract endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContractInfo {
+    /// Class the contract currently runs
+    #[serde(alias = "classHash")]
+    pub class_hash: String,
+}
+
+/// Response from the class search endpoint
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClassSearchResults {
+    #[serde(default)]
+    pub classes: Vec<ClassVerificationInfo>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Error {
     pub error: String,
@@ -47,7 +110,66 @@ pub struct VerificationJobDispatch {
     pub job_id: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Response from the job log endpoint: compiler output from a line offset on
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobLogs {
+    #[serde(default)]
+    pub lines: Vec<String>,
+    /// Offset to request next, to receive only lines not seen yet
+    pub next_offset: usize,
+}
+
+/// Limits the API enforces on submissions, from the capabilities endpoint
+///
+/// Sizes are in bytes; limits and toolchain ranges the server does not report
+/// are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Capabilities {
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+    #[serde(default)]
+    pub max_payload_size: Option<u64>,
+    /// Cairo compiler versions the API builds with
+    #[serde(default)]
+    pub cairo: Option<VersionRange>,
+    #[serde(default)]
+    pub scarb: Option<VersionRange>,
+    /// Sierra versions the API can compile classes to
+    #[serde(default)]
+    pub sierra: Option<VersionRange>,
+}
+
+/// Inclusive range of toolchain versions
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VersionRange {
+    pub min: semver::Version,
+    pub max: semver::Version,
+}
+
+impl VersionRange {
+    #[must_use]
+    pub const fn new(min: semver::Version, max: semver::Version) -> Self {
+        Self { min, max }
+    }
+
+    /// Whether `version` is within the range
+    ///
+    /// Pre-releases count as their release, so `2.13.0-rc.1` is within a
+    /// range starting at `2.13.0`.
+    #[must_use]
+    pub fn contains(&self, version: &semver::Version) -> bool {
+        let release = semver::Version::new(version.major, version.minor, version.patch);
+        self.min <= release && release <= self.max
+    }
+}
+
+impl std::fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} to {}", self.min, self.max)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct VerificationJob {
     pub job_id: String,
     pub status: VerifyJobStatus,
@@ -64,6 +186,12 @@ pub struct VerificationJob {
     pub license: Option<String>,
     pub dojo_version: Option<String>,
     pub build_tool: Option<String>,
+    /// Class hash of the remote build, reported by some APIs when it did not match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub computed_class_hash: Option<String>,
+    /// Note submitted with `--note`, echoed by APIs that store it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 impl VerificationJob {
@@ -142,6 +270,11 @@ impl VerificationJob {
         self.build_tool.as_deref()
     }
 
+    #[must_use]
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
     #[must_use]
     pub const fn is_completed(&self) -> bool {
         matches!(
@@ -174,6 +307,7 @@ pub struct ProjectMetadataInfo {
     pub package_name: String,
     pub build_tool: String,           // "scarb" or "sozo"
     pub dojo_version: Option<String>, // Dojo version for Dojo projects
+    pub note: Option<String>,         // Why the verification was submitted
 }
 
 impl ProjectMetadataInfo {
@@ -201,8 +335,16 @@ impl ProjectMetadataInfo {
                 "scarb".to_string()
             },
             dojo_version,
+            note: None,
         }
     }
+
+    /// Attach a note on why the verification was submitted
+    #[must_use]
+    pub fn with_note(mut self, note: Option<String>) -> Self {
+        self.note = note;
+        self
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -219,5 +361,9 @@ pub struct VerificationRequest {
     pub license: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dojo_version: Option<String>,
-    pub files: HashMap<String, String>, // filename -> content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Contents by file name, ordered so that identical requests serialize
+    /// identically
+    pub files: BTreeMap<String, String>,
 }
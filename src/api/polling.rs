@@ -1,3 +1,378 @@
-// This module contains polling functionality that was moved to the client.rs file
-// Re-export from client for backward compatibility
+//! Polling strategies for verification jobs
+//!
+//! Verification backends have different rate limits, so the delay between
+//! status checks is pluggable. A [`PollStrategy`] decides how long to wait
+//! before the next check and when to give up; it is attached to an
+//! [`ApiClient`](super::ApiClient) with `with_poll_strategy` and used by
+//! both single-job polling and batch watching.
+//!
+//! [`PollingConfig`] tunes the delays, the number of checks and the total
+//! time of a strategy, from `--poll-interval`, `--poll-timeout` and
+//! `.voyager.toml`.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+// Polling itself lives in client.rs; re-export for backward compatibility
 pub use super::client::poll_verification_status;
+
+/// Decides the delay between status checks of a verification job
+pub trait PollStrategy: fmt::Debug + Send + Sync {
+    /// Delay before the next status check, or `None` to stop polling
+    ///
+    /// `attempt` is the number of checks made so far, starting at 1. `hint` is
+    /// the delay requested by the server in its last response (`Retry-After`),
+    /// if any.
+    fn next_delay(&self, attempt: u32, hint: Option<Duration>) -> Option<Duration>;
+
+    /// How long polling may take in total, if it is bounded
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// [`next_delay`](Self::next_delay), shortened to end at the
+    /// [`timeout`](Self::timeout), or `None` once `elapsed` reaches it
+    fn next_delay_within(
+        &self,
+        attempt: u32,
+        hint: Option<Duration>,
+        elapsed: Duration,
+    ) -> Option<Duration> {
+        let delay = self.next_delay(attempt, hint)?;
+        match self.timeout() {
+            Some(timeout) => {
+                let remaining = timeout.checked_sub(elapsed).filter(|r| !r.is_zero())?;
+                Some(delay.min(remaining))
+            }
+            None => Some(delay),
+        }
+    }
+}
+
+/// Checks at a constant interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedInterval {
+    pub interval: Duration,
+    pub max_attempts: u32,
+}
+
+impl FixedInterval {
+    #[must_use]
+    pub const fn new(interval: Duration, max_attempts: u32) -> Self {
+        Self {
+            interval,
+            max_attempts,
+        }
+    }
+}
+
+impl Default for FixedInterval {
+    /// Every 2 seconds for up to 10 minutes
+    fn default() -> Self {
+        Self::new(Duration::from_secs(2), 300)
+    }
+}
+
+impl PollStrategy for FixedInterval {
+    fn next_delay(&self, attempt: u32, _hint: Option<Duration>) -> Option<Duration> {
+        (attempt <= self.max_attempts).then_some(self.interval)
+    }
+}
+
+/// Multiplies the delay by `factor` after every check, up to `max_delay`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExponentialBackoff {
+    pub min_delay: Duration,
+    pub max_delay: Duration,
+    pub factor: u32,
+    pub max_attempts: u32,
+}
+
+impl Default for ExponentialBackoff {
+    /// Starts at 2 seconds and settles at one check per minute, for about 25 minutes
+    fn default() -> Self {
+        Self {
+            min_delay: Duration::from_secs(2),
+            max_delay: Duration::from_mins(1),
+            factor: 2,
+            max_attempts: 30,
+        }
+    }
+}
+
+impl PollStrategy for ExponentialBackoff {
+    fn next_delay(&self, attempt: u32, _hint: Option<Duration>) -> Option<Duration> {
+        if attempt > self.max_attempts {
+            return None;
+        }
+
+        let delay = self
+            .factor
+            .checked_pow(attempt.saturating_sub(1))
+            .and_then(|multiplier| self.min_delay.checked_mul(multiplier))
+            .unwrap_or(self.max_delay);
+        Some(delay.min(self.max_delay))
+    }
+}
+
+/// Waits as long as the server asks, falling back to another strategy when it
+/// gives no hint
+///
+/// The fallback also decides when to give up, so a server cannot keep a
+/// client polling forever.
+#[derive(Debug, Clone)]
+pub struct ServerHinted {
+    pub fallback: Arc<dyn PollStrategy>,
+    /// Upper bound for server-requested delays
+    pub max_hint: Duration,
+}
+
+impl Default for ServerHinted {
+    fn default() -> Self {
+        Self {
+            fallback: Arc::new(FixedInterval::default()),
+            max_hint: Duration::from_mins(5),
+        }
+    }
+}
+
+impl PollStrategy for ServerHinted {
+    fn next_delay(&self, attempt: u32, hint: Option<Duration>) -> Option<Duration> {
+        let fallback = self.fallback.next_delay(attempt, None)?;
+        Some(hint.map_or(fallback, |hint| hint.min(self.max_hint)))
+    }
+}
+
+/// Gives up once `timeout` has passed since the first check
+///
+/// The delays come from `inner`, which also bounds the number of checks.
+#[derive(Debug, Clone)]
+pub struct Deadline {
+    pub inner: Arc<dyn PollStrategy>,
+    pub timeout: Duration,
+}
+
+impl PollStrategy for Deadline {
+    fn next_delay(&self, attempt: u32, hint: Option<Duration>) -> Option<Duration> {
+        self.inner.next_delay(attempt, hint)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        Some(self.timeout)
+    }
+}
+
+/// Settings replacing those of the default strategies
+///
+/// `min_delay` is the interval of [`FixedInterval`] and the first delay of
+/// [`ExponentialBackoff`]; `max_delay` bounds exponential and server-hinted
+/// delays. With a `timeout` and no `max_attempts`, the timeout alone decides
+/// when to give up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PollingConfig {
+    pub max_attempts: Option<u32>,
+    pub min_delay: Option<Duration>,
+    pub max_delay: Option<Duration>,
+    pub timeout: Option<Duration>,
+}
+
+impl PollingConfig {
+    /// These settings, with those of `other` where these have none
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            max_attempts: self.max_attempts.or(other.max_attempts),
+            min_delay: self.min_delay.or(other.min_delay),
+            max_delay: self.max_delay.or(other.max_delay),
+            timeout: self.timeout.or(other.timeout),
+        }
+    }
+
+    /// The strategy `kind` with these settings
+    #[must_use]
+    pub fn build(&self, kind: PollStrategyKind) -> Arc<dyn PollStrategy> {
+        let max_attempts = |default: u32| {
+            self.max_attempts.unwrap_or_else(|| {
+                if self.timeout.is_some() {
+                    u32::MAX
+                } else {
+                    default
+                }
+            })
+        };
+        let fixed = |defaults: FixedInterval| FixedInterval {
+            interval: self.min_delay.unwrap_or(defaults.interval),
+            max_attempts: max_attempts(defaults.max_attempts),
+        };
+        let strategy: Arc<dyn PollStrategy> = match kind {
+            PollStrategyKind::Fixed => Arc::new(fixed(FixedInterval::default())),
+            PollStrategyKind::Exponential => {
+                let defaults = ExponentialBackoff::default();
+                Arc::new(ExponentialBackoff {
+                    min_delay: self.min_delay.unwrap_or(defaults.min_delay),
+                    max_delay: self.max_delay.unwrap_or(defaults.max_delay),
+                    factor: defaults.factor,
+                    max_attempts: max_attempts(defaults.max_attempts),
+                })
+            }
+            PollStrategyKind::ServerHinted => {
+                let defaults = ServerHinted::default();
+                Arc::new(ServerHinted {
+                    fallback: Arc::new(fixed(FixedInterval::default())),
+                    max_hint: self.max_delay.unwrap_or(defaults.max_hint),
+                })
+            }
+        };
+        match self.timeout {
+            Some(timeout) => Arc::new(Deadline {
+                inner: strategy,
+                timeout,
+            }),
+            None => strategy,
+        }
+    }
+}
+
+/// Delays between retries of a submission that failed with a transient error
+///
+/// Up to 3 retries, after 2, 4 and 8 seconds, or as long as the server asks
+/// with `Retry-After`, up to a minute. Rate limited submissions are retried
+/// after the delays of [`default_rate_limit_retry`] instead.
+#[must_use]
+pub fn default_submit_retry() -> Arc<dyn PollStrategy> {
+    Arc::new(ServerHinted {
+        fallback: Arc::new(ExponentialBackoff {
+            min_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(30),
+            factor: 2,
+            max_attempts: 3,
+        }),
+        max_hint: Duration::from_mins(1),
+    })
+}
+
+/// Delays before sending a request again after `429 Too Many Requests`
+///
+/// As long as the server asks with `Retry-After`, up to 5 minutes, or from 5
+/// seconds doubling up to a minute; up to 5 times.
+#[must_use]
+pub fn default_rate_limit_retry() -> Arc<dyn PollStrategy> {
+    Arc::new(ServerHinted {
+        fallback: Arc::new(ExponentialBackoff {
+            min_delay: Duration::from_secs(5),
+            max_delay: Duration::from_mins(1),
+            factor: 2,
+            max_attempts: 5,
+        }),
+        max_hint: Duration::from_mins(5),
+    })
+}
+
+/// Polling strategies selectable by name in `.voyager.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollStrategyKind {
+    Fixed,
+    Exponential,
+    ServerHinted,
+}
+
+impl PollStrategyKind {
+    /// The strategy with its default settings
+    #[must_use]
+    pub fn build(self) -> Arc<dyn PollStrategy> {
+        match self {
+            Self::Fixed => Arc::new(FixedInterval::default()),
+            Self::Exponential => Arc::new(ExponentialBackoff::default()),
+            Self::ServerHinted => Arc::new(ServerHinted::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_interval() {
+        let strategy = FixedInterval::new(Duration::from_secs(5), 2);
+        assert_eq!(
+            strategy.next_delay(1, Some(Duration::from_mins(1))),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(strategy.next_delay(2, None), Some(Duration::from_secs(5)));
+        assert_eq!(strategy.next_delay(3, None), None);
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let strategy = ExponentialBackoff {
+            min_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+            factor: 2,
+            max_attempts: 40,
+        };
+        let delays: Vec<u64> = (1..=6)
+            .filter_map(|attempt| strategy.next_delay(attempt, None))
+            .map(|delay| delay.as_secs())
+            .collect();
+        assert_eq!(delays, [1, 2, 4, 8, 10, 10]);
+
+        // Large exponents must saturate at max_delay rather than overflow
+        assert_eq!(strategy.next_delay(40, None), Some(Duration::from_secs(10)));
+        assert_eq!(strategy.next_delay(41, None), None);
+    }
+
+    #[test]
+    fn test_server_hinted() {
+        let strategy = ServerHinted {
+            fallback: Arc::new(FixedInterval::new(Duration::from_secs(2), 3)),
+            max_hint: Duration::from_secs(30),
+        };
+        assert_eq!(strategy.next_delay(1, None), Some(Duration::from_secs(2)));
+        assert_eq!(
+            strategy.next_delay(2, Some(Duration::from_secs(10))),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            strategy.next_delay(3, Some(Duration::from_mins(10))),
+            Some(Duration::from_secs(30))
+        );
+        // The fallback still bounds the number of checks
+        assert_eq!(strategy.next_delay(4, Some(Duration::from_secs(10))), None);
+    }
+
+    #[test]
+    fn test_polling_config() {
+        let secs = Duration::from_secs;
+        let polling = PollingConfig {
+            min_delay: Some(secs(5)),
+            timeout: Some(secs(12)),
+            ..PollingConfig::default()
+        };
+        let strategy = polling.build(PollStrategyKind::Fixed);
+        assert_eq!(strategy.timeout(), Some(secs(12)));
+        // Without max_attempts, only the timeout ends polling
+        assert_eq!(strategy.next_delay(1000, None), Some(secs(5)));
+        assert_eq!(strategy.next_delay_within(1, None, secs(0)), Some(secs(5)));
+        assert_eq!(strategy.next_delay_within(3, None, secs(10)), Some(secs(2)));
+        assert_eq!(strategy.next_delay_within(4, None, secs(12)), None);
+
+        let configured = PollingConfig {
+            max_attempts: Some(3),
+            min_delay: Some(secs(1)),
+            max_delay: Some(secs(8)),
+            timeout: None,
+        };
+        let strategy = polling.or(configured).build(PollStrategyKind::Exponential);
+        let delays: Vec<u64> = (1..=4)
+            .filter_map(|attempt| strategy.next_delay_within(attempt, None, secs(0)))
+            .map(|delay| delay.as_secs())
+            .collect();
+        assert_eq!(delays, [5, 8, 8]);
+        assert_eq!(
+            PollingConfig::default()
+                .build(PollStrategyKind::Fixed)
+                .timeout(),
+            None
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Progress reporting for applications embedding the verifier
+//!
+//! The CLI reports progress with `println!` and log messages. Applications
+//! that draw their own progress UI attach a [`ProgressObserver`] to an
+//! [`ApiClient`](super::ApiClient) with `with_progress_observer`; it is
+//! notified as files are collected, the payload is uploaded, jobs change
+//! status, and API requests complete. Every method has an empty default, so observers only implement the
+//! events they care about.
+
+use super::types::VerifyJobStatus;
+use std::{fmt, time::Duration};
+
+/// Size from which verification requests are streamed, with their upload
+/// progress reported
+pub const STREAMED_UPLOAD_SIZE: u64 = 1024 * 1024;
+
+/// Receives progress events of a verification
+pub trait ProgressObserver: fmt::Debug + Send + Sync {
+    /// Collection of the project's source files started
+    fn files_collection_started(&self) {}
+
+    /// Source files were collected; `bytes` is their combined size on disk
+    fn files_collected(&self, _files: usize, _bytes: u64) {}
+
+    /// The verification request of `bytes` bytes is being sent
+    fn upload_started(&self, _bytes: u64) {}
+
+    /// `sent` of the `total` bytes of the verification request were sent
+    ///
+    /// Only reported by [`ApiClient`](super::ApiClient) for requests of at
+    /// least [`STREAMED_UPLOAD_SIZE`]; `sent` starts over when the request is
+    /// sent again.
+    fn upload_progress(&self, _sent: u64, _total: u64) {}
+
+    /// The server accepted `bytes` bytes of the verification request
+    fn bytes_uploaded(&self, _bytes: u64) {}
+
+    /// A job was seen in a new status, including its first observed status
+    fn status_changed(&self, _job_id: &str, _status: VerifyJobStatus) {}
+
+    /// An API request to `endpoint` (relative to the API base) finished
+    ///
+    /// `status` is the HTTP status, or `None` if no response was received.
+    fn request_completed(&self, _endpoint: &str, _status: Option<u16>, _elapsed: Duration) {}
+
+    /// The API rate limited a request to `endpoint`, which is sent again
+    /// after `delay`
+    fn rate_limited(&self, _endpoint: &str, _delay: Duration) {}
+}
+
+/// Observer that ignores every event; used when none is attached
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressObserver for NoProgress {}
@@ -0,0 +1,304 @@
+//! Recording of API traffic for bug reports
+//!
+//! With `--record-http <DIR>`, every request the [`ApiClient`](super::ApiClient)
+//! sends is written to `DIR` together with the server's response, one JSON
+//! file per exchange, numbered in the order they happened. The recordings are
+//! sanitized so they can be attached to an issue: header values are replaced
+//! unless the client relies on them, and the source files in a submission are
+//! replaced by their size. [`MockServer::replay`](super::MockServer::replay)
+//! serves a recording back, so a maintainer can run the same command against
+//! the exact responses the user saw.
+
+use log::debug;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+};
+
+/// Placeholder written instead of header values
+const REDACTED: &str = "<redacted>";
+
+/// Headers whose values are kept, as the client reads them
+const KEPT_HEADERS: [&str; 3] = ["content-type", "content-length", "retry-after"];
+
+static RECORDER: OnceLock<Arc<HttpRecorder>> = OnceLock::new();
+
+/// One request and the response it got
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Exchange {
+    pub method: String,
+    /// Request path and query relative to the API base URL, e.g. `class-verify/job/<id>`
+    pub endpoint: String,
+    pub request_headers: BTreeMap<String, String>,
+    pub request_body: Option<Value>,
+    pub status: u16,
+    pub response_headers: BTreeMap<String, String>,
+    pub response_body: String,
+}
+
+/// Sanitized copies of `headers`
+#[must_use]
+pub(super) fn redact_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if KEPT_HEADERS.contains(&name.as_str()) {
+                value.to_str().unwrap_or(REDACTED).to_string()
+            } else {
+                REDACTED.to_string()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+/// Sanitized copy of a request body
+///
+/// Submitted source files are replaced by their size; everything else in a
+/// JSON body is kept. Bodies that are not JSON are dropped.
+#[must_use]
+pub(super) fn redact_body(body: &[u8]) -> Option<Value> {
+    let mut value: Value = serde_json::from_slice(body).ok()?;
+    if let Some(Value::Object(files)) = value.get_mut("files") {
+        for content in files.values_mut() {
+            let size = content.as_str().map_or(0, str::len);
+            *content = Value::String(format!("<{size} bytes>"));
+        }
+    }
+    Some(value)
+}
+
+/// Writes exchanges to a directory
+#[derive(Debug)]
+pub struct HttpRecorder {
+    dir: PathBuf,
+    next: AtomicUsize,
+}
+
+impl HttpRecorder {
+    /// Record into `dir`, creating it if needed
+    ///
+    /// Numbering continues after the recordings already in `dir`, so several
+    /// invocations can be recorded into the same directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created or read.
+    pub fn create(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let last = recording_files(&dir)?
+            .iter()
+            .filter_map(|path| sequence_number(path))
+            .max()
+            .unwrap_or(0);
+        Ok(Self {
+            dir,
+            next: AtomicUsize::new(last + 1),
+        })
+    }
+
+    /// Directory the recordings are written to
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write `exchange` to the next file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn record(&self, exchange: &Exchange) -> io::Result<()> {
+        let number = self.next.fetch_add(1, Ordering::SeqCst);
+        let path = self.dir.join(format!(
+            "{number:03}-{}.json",
+            exchange.method.to_lowercase()
+        ));
+        debug!(
+            "Recording {} {} to {}",
+            exchange.method,
+            exchange.endpoint,
+            path.display()
+        );
+        fs::write(
+            path,
+            serde_json::to_vec_pretty(exchange).map_err(io::Error::other)?,
+        )
+    }
+}
+
+/// Record the traffic of every [`ApiClient`](super::ApiClient) created from now on into `dir`
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created, or if recording was
+/// already started.
+pub fn record_http_to(dir: impl Into<PathBuf>) -> io::Result<()> {
+    let recorder = Arc::new(HttpRecorder::create(dir)?);
+    RECORDER
+        .set(recorder)
+        .map_err(|_| io::Error::other("HTTP recording was already started"))
+}
+
+/// The recorder installed with [`record_http_to`], if any
+pub(super) fn global_recorder() -> Option<Arc<HttpRecorder>> {
+    RECORDER.get().cloned()
+}
+
+/// Read the exchanges recorded in `dir`, in the order they happened
+///
+/// # Errors
+///
+/// Returns an error if the directory or a recording cannot be read.
+pub fn load_recording(dir: impl AsRef<Path>) -> io::Result<Vec<Exchange>> {
+    recording_files(dir.as_ref())?
+        .into_iter()
+        .map(|path| {
+            let content = fs::read(&path)?;
+            serde_json::from_slice(&content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect()
+}
+
+/// Number a recording file name starts with, e.g. 12 for `012-get.json`
+fn sequence_number(path: &Path) -> Option<usize> {
+    let name = path.file_name()?.to_str()?;
+    name.split_once('-')?.0.parse().ok()
+}
+
+/// Recording files in `dir`, sorted by number
+///
+/// Numbers are padded to three digits only, so names no longer sort like the
+/// numbers past 999. Files without a number come last, by name.
+fn recording_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort_by_cached_key(|path| (sequence_number(path).unwrap_or(usize::MAX), path.clone()));
+    Ok(files)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{ApiClient, ApiClientError, MockServer},
+        core::class_hash::ClassHash,
+    };
+    use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+    use serde_json::json;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_redaction() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        let redacted = redact_headers(&headers);
+        assert_eq!(redacted["content-type"], "application/json");
+        assert_eq!(redacted["authorization"], REDACTED);
+
+        let body = json!({
+            "name": "MyToken",
+            "files": { "src/lib.cairo": "mod token;", "Scarb.toml": "" },
+        });
+        let redacted = redact_body(body.to_string().as_bytes()).unwrap();
+        assert_eq!(redacted["name"], "MyToken");
+        assert_eq!(redacted["files"]["src/lib.cairo"], "<10 bytes>");
+        assert_eq!(redacted["files"]["Scarb.toml"], "<0 bytes>");
+        assert!(redact_body(b"not json").is_none());
+    }
+
+    #[test]
+    fn test_replay_order_past_999() {
+        let temp_dir = TempDir::new().unwrap();
+        let recorder = HttpRecorder {
+            dir: temp_dir.path().to_path_buf(),
+            next: AtomicUsize::new(998),
+        };
+        for job in ["a", "b", "c"] {
+            recorder
+                .record(&Exchange {
+                    method: "GET".to_string(),
+                    endpoint: format!("class-verify/job/{job}"),
+                    request_headers: BTreeMap::new(),
+                    request_body: None,
+                    status: 200,
+                    response_headers: BTreeMap::new(),
+                    response_body: String::new(),
+                })
+                .unwrap();
+        }
+
+        let exchanges = load_recording(temp_dir.path()).unwrap();
+        let endpoints: Vec<_> = exchanges.iter().map(|e| e.endpoint.as_str()).collect();
+        assert_eq!(
+            endpoints,
+            [
+                "class-verify/job/a",
+                "class-verify/job/b",
+                "class-verify/job/c"
+            ]
+        );
+        let recorder = HttpRecorder::create(temp_dir.path()).unwrap();
+        assert_eq!(recorder.next.load(Ordering::SeqCst), 1001);
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let temp_dir = TempDir::new().unwrap();
+        let class_hash = ClassHash::new("0x1234").unwrap();
+
+        let server = MockServer::start(Duration::from_mins(1)).unwrap();
+        let recorder = Arc::new(HttpRecorder::create(temp_dir.path()).unwrap());
+        let client = ApiClient::new(server.url().clone())
+            .unwrap()
+            .with_recorder(recorder);
+        assert!(!client
+            .check_class_verification(&class_hash)
+            .unwrap()
+            .is_verified());
+        assert!(matches!(
+            client.get_job_status("missing"),
+            Err(ApiClientError::JobNotFound(_))
+        ));
+        drop(server);
+
+        let exchanges = load_recording(temp_dir.path()).unwrap();
+        let endpoints: Vec<_> = exchanges.iter().map(|e| e.endpoint.as_str()).collect();
+        assert_eq!(
+            endpoints,
+            ["class-verify/check/0x1234", "class-verify/job/missing"]
+        );
+        assert_eq!(exchanges[1].status, 404);
+
+        let replay = MockServer::replay(exchanges).unwrap();
+        let client = ApiClient::new(replay.url().clone()).unwrap();
+        assert!(!client
+            .check_class_verification(&class_hash)
+            .unwrap()
+            .is_verified());
+        assert!(matches!(
+            client.get_job_status("missing"),
+            Err(ApiClientError::JobNotFound(_))
+        ));
+        assert!(matches!(
+            client.search_classes("MyToken"),
+            Err(ApiClientError::SearchUnsupported(_))
+        ));
+    }
+}
@@ -0,0 +1,716 @@
+//! Minimal Starknet JSON-RPC client
+//!
+//! The Voyager API only knows about classes that were submitted to it, so
+//! on-chain state (which class a contract currently runs, and which ones it
+//! ran before, whether a class was declared at all) is read from a Starknet
+//! node instead. Only the handful of methods the verifier needs are
+//! implemented.
+
+use reqwest::blocking::Client;
+use serde::{
+    de::{DeserializeOwned, IgnoredAny},
+    Deserialize,
+};
+use serde_json::{json, Value};
+use thiserror::Error;
+use url::Url;
+
+use crate::utils::suggestion::{Suggestion, SuggestionList};
+
+/// `CONTRACT_NOT_FOUND` error code from the Starknet JSON-RPC specification
+const CONTRACT_NOT_FOUND: i64 = 20;
+
+/// `CLASS_HASH_NOT_FOUND` error code from the Starknet JSON-RPC specification
+const CLASS_HASH_NOT_FOUND: i64 = 28;
+
+/// `sn_keccak("Upgraded")`, emitted with the new class hash by the
+/// upgradeable component of `OpenZeppelin` Contracts on every `replace_class`
+const UPGRADED: &str = "0x2db340e6c609371026731f47050d3976552c89b4fbb012941663841c59d1af3";
+
+#[derive(Debug, Error)]
+pub enum RpcError {
+    #[error("[E060] RPC request to '{url}' failed: {source}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    Request {
+        url: Url,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("[E061] RPC method '{method}' returned error {code}: {message}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    Rpc {
+        method: String,
+        code: i64,
+        message: String,
+    },
+
+    #[error("[E062] No contract is deployed at '{0}'{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ContractNotFound(String),
+
+    #[error("[E063] RPC node at '{url}' is on chain {actual}, but the selected network is {expected}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ChainIdMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl RpcError {
+    #[must_use]
+    pub const fn error_code(&self) -> &'static str {
+        match self {
+            Self::Request { .. } => "E060",
+            Self::Rpc { .. } => "E061",
+            Self::ContractNotFound(_) => "E062",
+            Self::ChainIdMismatch { .. } => "E063",
+        }
+    }
+
+    /// Next steps for reaching the RPC node and checking it serves the selected network
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        Suggestion::for_code(self.error_code(), &[])
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcErrorObject>,
+}
+
+#[derive(Deserialize)]
+struct RpcErrorObject {
+    code: i64,
+    message: String,
+}
+
+/// Block to query state at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockId {
+    Latest,
+    Number(u64),
+}
+
+impl BlockId {
+    fn to_json(self) -> Value {
+        match self {
+            Self::Latest => json!("latest"),
+            Self::Number(number) => json!({ "block_number": number }),
+        }
+    }
+}
+
+/// A class a contract ran from `since_block` on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassChange {
+    pub class_hash: String,
+    /// First block at which the contract used this class
+    pub since_block: u64,
+}
+
+/// The classes a contract has used, newest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassHistory {
+    pub changes: Vec<ClassChange>,
+    /// Whether the contract's `Upgraded` events account for every change of
+    /// its class. Otherwise the history was searched block by block, which
+    /// misses a class that was replaced and restored between two searched
+    /// blocks.
+    pub complete: bool,
+}
+
+/// An event emitted by a contract
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct EmittedEvent {
+    pub keys: Vec<String>,
+    pub data: Vec<String>,
+    /// Block the event was emitted in; unset for pending events
+    pub block_number: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct EventsChunk {
+    events: Vec<EmittedEvent>,
+    continuation_token: Option<String>,
+}
+
+/// Events requested per `starknet_getEvents` call
+const EVENTS_CHUNK_SIZE: u64 = 1000;
+
+#[derive(Clone)]
+pub struct RpcClient {
+    url: Url,
+    client: Client,
+}
+
+impl RpcClient {
+    #[must_use]
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            client: Client::new(),
+        }
+    }
+
+    fn call<T: DeserializeOwned>(&self, method: &str, params: &Value) -> Result<T, RpcError> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let to_request_error = |source| RpcError::Request {
+            url: self.url.clone(),
+            source,
+        };
+
+        let response: RpcResponse<T> = self
+            .client
+            .post(self.url.clone())
+            .json(&request)
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .and_then(reqwest::blocking::Response::json)
+            .map_err(to_request_error)?;
+
+        match (response.result, response.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => Err(RpcError::Rpc {
+                method: method.to_string(),
+                code: error.code,
+                message: error.message,
+            }),
+            (None, None) => Err(RpcError::Rpc {
+                method: method.to_string(),
+                code: 0,
+                message: "response contained neither a result nor an error".to_string(),
+            }),
+        }
+    }
+
+    /// Number of the latest accepted block
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub fn block_number(&self) -> Result<u64, RpcError> {
+        self.call("starknet_blockNumber", &json!([]))
+    }
+
+    /// Chain id of the node's network, such as `SN_MAIN` or `SN_SEPOLIA`
+    ///
+    /// The node returns the id as a hex-encoded short string; ids that do not
+    /// decode to ASCII are returned as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub fn chain_id(&self) -> Result<String, RpcError> {
+        let raw: String = self.call("starknet_chainId", &json!([]))?;
+        Ok(decode_short_string(&raw).unwrap_or(raw))
+    }
+
+    /// Check that the node is on chain `expected`
+    ///
+    /// Pointing the explorer of one network at a node of another otherwise
+    /// surfaces much later, as classes that appear not to be declared.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RpcError::ChainIdMismatch`] if the node is on another chain,
+    /// or an error if the request fails.
+    pub fn ensure_chain_id(&self, expected: &str) -> Result<(), RpcError> {
+        let actual = self.chain_id()?;
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(RpcError::ChainIdMismatch {
+                url: self.url.to_string(),
+                expected: expected.to_string(),
+                actual,
+            })
+        }
+    }
+
+    /// Class hash of the contract at `address`, or `None` if it was not
+    /// deployed yet at `block`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub fn class_hash_at(&self, address: &str, block: BlockId) -> Result<Option<String>, RpcError> {
+        match self.call(
+            "starknet_getClassHashAt",
+            &json!({ "block_id": block.to_json(), "contract_address": address }),
+        ) {
+            Ok(class_hash) => Ok(Some(class_hash)),
+            Err(RpcError::Rpc { code, .. }) if code == CONTRACT_NOT_FOUND => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether the class `class_hash` is declared at the latest block
+    ///
+    /// The class definition the node returns is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub fn is_declared(&self, class_hash: &str) -> Result<bool, RpcError> {
+        match self.call::<IgnoredAny>(
+            "starknet_getClass",
+            &json!({ "block_id": BlockId::Latest.to_json(), "class_hash": class_hash }),
+        ) {
+            Ok(_) => Ok(true),
+            Err(RpcError::Rpc { code, .. }) if code == CLASS_HASH_NOT_FOUND => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Every class the contract at `address` has used
+    ///
+    /// Upgrades through `replace_class` are read from the contract's
+    /// `Upgraded` events, and checked against the class the contract ran in
+    /// the block before each of them. Contracts that upgrade without emitting
+    /// the event fall back to [`search_class_history`](Self::search_class_history),
+    /// and the history is reported as possibly incomplete. An upgrade without
+    /// an event is only noticed if it left a class other than the one the
+    /// next event or the latest block starts from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RpcError::ContractNotFound`] if nothing is deployed at
+    /// `address`, or an error if a request fails.
+    pub fn class_history(&self, address: &str) -> Result<ClassHistory, RpcError> {
+        let latest = self.block_number()?;
+        let current = self
+            .class_hash_at(address, BlockId::Number(latest))?
+            .ok_or_else(|| RpcError::ContractNotFound(address.to_string()))?;
+
+        if let Some(changes) = self.upgrade_history(address, latest, &current)? {
+            return Ok(ClassHistory {
+                changes,
+                complete: true,
+            });
+        }
+        Ok(ClassHistory {
+            changes: self.search_class_history(address, latest, current)?,
+            complete: false,
+        })
+    }
+
+    /// The class history from the `Upgraded` events of the contract at
+    /// `address`, newest first, or `None` if the events do not account for
+    /// every change of its class up to `current` at block `latest`
+    fn upgrade_history(
+        &self,
+        address: &str,
+        latest: u64,
+        current: &str,
+    ) -> Result<Option<Vec<ClassChange>>, RpcError> {
+        let deployed_at = self.deployment_block(address, latest)?;
+        let Some(initial) = self.class_hash_at(address, BlockId::Number(deployed_at))? else {
+            return Ok(None);
+        };
+        let mut changes = vec![ClassChange {
+            class_hash: initial,
+            since_block: deployed_at,
+        }];
+
+        for event in self.events(address, &[UPGRADED])? {
+            let (Some(class_hash), Some(block)) = (event.data.first(), event.block_number) else {
+                continue;
+            };
+            // The class at the deployment block already includes upgrades
+            // made in that block
+            if block <= deployed_at || block > latest {
+                continue;
+            }
+            let before = self.class_hash_at(address, BlockId::Number(block - 1))?;
+            let previous = changes.last().map(|change| change.class_hash.as_str());
+            if before
+                .as_deref()
+                .zip(previous)
+                .is_none_or(|(a, b)| !same_felt(a, b))
+            {
+                return Ok(None);
+            }
+            changes.push(ClassChange {
+                class_hash: class_hash.clone(),
+                since_block: block,
+            });
+        }
+
+        if changes
+            .last()
+            .is_none_or(|change| !same_felt(&change.class_hash, current))
+        {
+            return Ok(None);
+        }
+        changes.reverse();
+        Ok(Some(changes))
+    }
+
+    /// First block at which a contract is deployed at `address`, given that
+    /// one is at block `latest`
+    fn deployment_block(&self, address: &str, latest: u64) -> Result<u64, RpcError> {
+        let mut low = 0;
+        let mut high = latest;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.class_hash_at(address, BlockId::Number(mid))?.is_some() {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Every class the contract at `address` has used, newest first, searched
+    /// from `current`, its class at block `latest`
+    ///
+    /// Upgrades are found by binary searching for the block at which the
+    /// class hash last changed, then repeating from the block before it until
+    /// the deployment block is reached. This takes a few dozen requests per
+    /// upgrade. The class hash over blocks is not monotonic, so an upgrade
+    /// that was later reverted to an earlier class in between two searched
+    /// blocks can be missed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails.
+    pub fn search_class_history(
+        &self,
+        address: &str,
+        latest: u64,
+        mut current: String,
+    ) -> Result<Vec<ClassChange>, RpcError> {
+        let mut history = Vec::new();
+        let mut upper = latest;
+
+        loop {
+            // Find the first block in 0..=upper that already ran `current`
+            let mut low = 0;
+            let mut high = upper;
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if self.class_hash_at(address, BlockId::Number(mid))?.as_ref() == Some(&current) {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+
+            history.push(ClassChange {
+                class_hash: current.clone(),
+                since_block: low,
+            });
+
+            if low == 0 {
+                break;
+            }
+            upper = low - 1;
+            match self.class_hash_at(address, BlockId::Number(upper))? {
+                Some(previous) => current = previous,
+                // Deployed at `low`
+                None => break,
+            }
+        }
+
+        Ok(history)
+    }
+
+    /// Every event emitted by the contract at `address` whose first key is
+    /// one of `selectors`, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails.
+    pub fn events(&self, address: &str, selectors: &[&str]) -> Result<Vec<EmittedEvent>, RpcError> {
+        let mut events = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut filter = json!({
+                "from_block": BlockId::Number(0).to_json(),
+                "to_block": BlockId::Latest.to_json(),
+                "address": address,
+                "keys": [selectors],
+                "chunk_size": EVENTS_CHUNK_SIZE,
+            });
+            if let Some(token) = continuation_token {
+                filter["continuation_token"] = json!(token);
+            }
+            let chunk: EventsChunk =
+                self.call("starknet_getEvents", &json!({ "filter": filter }))?;
+            events.extend(chunk.events);
+            match chunk.continuation_token {
+                Some(token) => continuation_token = Some(token),
+                None => return Ok(events),
+            }
+        }
+    }
+}
+
+/// Whether two felts in hex are the same, however they are padded
+fn same_felt(a: &str, b: &str) -> bool {
+    let digits = |felt: &str| {
+        felt.trim_start_matches("0x")
+            .trim_start_matches('0')
+            .to_ascii_lowercase()
+    };
+    digits(a) == digits(b)
+}
+
+/// Decode a Cairo short string such as `0x534e5f4d41494e` (`SN_MAIN`)
+fn decode_short_string(hex: &str) -> Option<String> {
+    let digits = hex.strip_prefix("0x")?;
+    let digits = if digits.len() % 2 == 1 {
+        format!("0{digits}")
+    } else {
+        digits.to_string()
+    };
+    let bytes = (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let text = String::from_utf8(bytes).ok()?;
+    (!text.is_empty() && text.chars().all(|c| c.is_ascii_graphic())).then_some(text)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    const ADDRESS: &str = "0x1";
+
+    /// Mock a node where the contract was deployed at block 10 and ran each
+    /// of `classes` from the block given with it on, emitting `Upgraded` for
+    /// the `upgrades`; the chain head is block 100
+    fn mock_node(
+        server: &mut mockito::ServerGuard,
+        classes: &[(u64, &str)],
+        upgrades: &[(u64, &str)],
+    ) -> Vec<mockito::Mock> {
+        let events: Vec<_> = upgrades
+            .iter()
+            .map(|(block, class_hash)| {
+                json!({ "keys": [UPGRADED], "data": [class_hash], "block_number": block })
+            })
+            .collect();
+        let mut mocks = vec![
+            server
+                .mock("POST", "/")
+                .match_body(Matcher::PartialJson(
+                    json!({ "method": "starknet_blockNumber" }),
+                ))
+                .with_body(r#"{"jsonrpc":"2.0","id":1,"result":100}"#)
+                .create(),
+            server
+                .mock("POST", "/")
+                .match_body(Matcher::PartialJson(json!({
+                    "method": "starknet_getEvents",
+                    "params": { "filter": { "keys": [[UPGRADED]] } },
+                })))
+                .with_body(
+                    json!({ "jsonrpc": "2.0", "id": 1, "result": { "events": events } })
+                        .to_string(),
+                )
+                .create(),
+        ];
+
+        for block in 0..=100_u64 {
+            let body = classes
+                .iter()
+                .rev()
+                .find(|(since, _)| *since <= block)
+                .map_or_else(
+                    || {
+                        r#"{"jsonrpc":"2.0","id":1,"error":{"code":20,"message":"Contract not found"}}"#
+                            .to_string()
+                    },
+                    |(_, class_hash)| {
+                        json!({ "jsonrpc": "2.0", "id": 1, "result": class_hash }).to_string()
+                    },
+                );
+            mocks.push(
+                server
+                    .mock("POST", "/")
+                    .match_body(Matcher::PartialJson(json!({
+                        "method": "starknet_getClassHashAt",
+                        "params": { "block_id": { "block_number": block } },
+                    })))
+                    .with_body(body)
+                    .create(),
+            );
+        }
+        mocks
+    }
+
+    fn change(class_hash: &str, since_block: u64) -> ClassChange {
+        ClassChange {
+            class_hash: class_hash.to_string(),
+            since_block,
+        }
+    }
+
+    #[test]
+    fn test_class_history_finds_upgrades() {
+        let mut server = mockito::Server::new();
+        let _mocks = mock_node(&mut server, &[(10, "0xa"), (40, "0xb")], &[(40, "0xb")]);
+        let client = RpcClient::new(Url::parse(&server.url()).unwrap());
+
+        let history = client.class_history(ADDRESS).unwrap();
+
+        assert_eq!(
+            history,
+            ClassHistory {
+                changes: vec![change("0xb", 40), change("0xa", 10)],
+                complete: true,
+            }
+        );
+        drop(server);
+    }
+
+    #[test]
+    fn test_class_history_keeps_restored_classes() {
+        let mut server = mockito::Server::new();
+        let _mocks = mock_node(
+            &mut server,
+            &[(10, "0xa"), (40, "0xb"), (45, "0xa")],
+            &[(40, "0x0b"), (45, "0xa")],
+        );
+        let client = RpcClient::new(Url::parse(&server.url()).unwrap());
+
+        let history = client.class_history(ADDRESS).unwrap();
+
+        assert_eq!(
+            history,
+            ClassHistory {
+                changes: vec![change("0xa", 45), change("0x0b", 40), change("0xa", 10)],
+                complete: true,
+            }
+        );
+        drop(server);
+    }
+
+    #[test]
+    fn test_class_history_without_events_is_searched() {
+        let mut server = mockito::Server::new();
+        let _mocks = mock_node(&mut server, &[(10, "0xa"), (40, "0xb")], &[]);
+        let client = RpcClient::new(Url::parse(&server.url()).unwrap());
+
+        let history = client.class_history(ADDRESS).unwrap();
+
+        assert_eq!(
+            history,
+            ClassHistory {
+                changes: vec![change("0xb", 40), change("0xa", 10)],
+                complete: false,
+            }
+        );
+        drop(server);
+    }
+
+    #[test]
+    fn test_chain_id_mismatch() {
+        let mut server = mockito::Server::new();
+        let _mock = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(
+                json!({ "method": "starknet_chainId" }),
+            ))
+            .with_body(r#"{"jsonrpc":"2.0","id":1,"result":"0x534e5f5345504f4c4941"}"#)
+            .create();
+        let client = RpcClient::new(Url::parse(&server.url()).unwrap());
+
+        assert_eq!(client.chain_id().unwrap(), "SN_SEPOLIA");
+        assert!(client.ensure_chain_id("SN_SEPOLIA").is_ok());
+
+        let err = client.ensure_chain_id("SN_MAIN").unwrap_err();
+        assert_eq!(err.error_code(), "E063");
+        assert!(err.to_string().contains("on chain SN_SEPOLIA"));
+
+        assert_eq!(decode_short_string("0x1").as_deref(), None);
+        assert_eq!(decode_short_string("0xzz"), None);
+        drop(server);
+    }
+
+    #[test]
+    fn test_events_follow_continuation_tokens() {
+        let mut server = mockito::Server::new();
+        let _first = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({
+                "method": "starknet_getEvents",
+                "params": { "filter": { "address": ADDRESS, "keys": [["0xe"]] } },
+            })))
+            .with_body(
+                r#"{"jsonrpc":"2.0","id":1,"result":{"events":[{"from_address":"0x1","keys":["0xe"],"data":["0x1"],"block_number":5}],"continuation_token":"5-1"}}"#,
+            )
+            .create();
+        let _second = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({
+                "method": "starknet_getEvents",
+                "params": { "filter": { "continuation_token": "5-1" } },
+            })))
+            .with_body(
+                r#"{"jsonrpc":"2.0","id":1,"result":{"events":[{"from_address":"0x1","keys":["0xe"],"data":["0x2"],"block_number":7}]}}"#,
+            )
+            .create();
+        let client = RpcClient::new(Url::parse(&server.url()).unwrap());
+
+        let events = client.events(ADDRESS, &["0xe"]).unwrap();
+        let data: Vec<_> = events.iter().map(|event| event.data[0].as_str()).collect();
+        assert_eq!(data, ["0x1", "0x2"]);
+        drop(server);
+    }
+
+    #[test]
+    fn test_is_declared() {
+        let mut server = mockito::Server::new();
+        let _declared = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({
+                "method": "starknet_getClass",
+                "params": { "class_hash": "0xa" },
+            })))
+            .with_body(r#"{"jsonrpc":"2.0","id":1,"result":{"sierra_program":[],"abi":"[]"}}"#)
+            .create();
+        let _missing = server
+            .mock("POST", "/")
+            .match_body(Matcher::PartialJson(json!({
+                "method": "starknet_getClass",
+                "params": { "class_hash": "0xb" },
+            })))
+            .with_body(
+                r#"{"jsonrpc":"2.0","id":1,"error":{"code":28,"message":"Class hash not found"}}"#,
+            )
+            .create();
+        let client = RpcClient::new(Url::parse(&server.url()).unwrap());
+
+        assert!(client.is_declared("0xa").unwrap());
+        assert!(!client.is_declared("0xb").unwrap());
+        drop(server);
+    }
+
+    #[test]
+    fn test_rpc_error_is_reported() {
+        let mut server = mockito::Server::new();
+        let _mock = server
+            .mock("POST", "/")
+            .with_body(
+                r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"Method not found"}}"#,
+            )
+            .create();
+        let client = RpcClient::new(Url::parse(&server.url()).unwrap());
+
+        let err = client.block_number().unwrap_err();
+        assert_eq!(err.error_code(), "E061");
+        assert!(err.to_string().contains("Method not found"));
+        drop(server);
+    }
+}
@@ -0,0 +1,470 @@
+//! Embedded mock verification server
+//!
+//! Backs `voyager verify --simulate`. The server listens on a random local port
+//! and implements the endpoints used by [`ApiClient`](super::ApiClient):
+//! submission, job status and logs, the class check, and capabilities. Every submitted job moves
+//! through Submitted → Processing → Compiled → Success, one stage per `step`,
+//! so demos, docs, and tests can go through the whole verification flow
+//! without network access. Requests compressed with `--compress` are
+//! decompressed like the real API does.
+//!
+//! [`MockServer::replay`] serves the responses of a recording made with
+//! `--record-http` instead, to reproduce a user's run against the exact
+//! responses they got.
+
+use super::{recording::Exchange, types::VerifyJobStatus};
+use flate2::read::GzDecoder;
+use log::debug;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use url::Url;
+
+/// Time each simulated job spends in a stage by default
+pub const DEFAULT_SIMULATION_STEP: Duration = Duration::from_secs(2);
+
+/// Payload limit reported by the simulated capabilities endpoint, as Voyager's
+const SIMULATED_MAX_PAYLOAD_SIZE: u64 = 10 * 1024 * 1024;
+
+struct SimulatedJob {
+    class_hash: String,
+    request: Value,
+    submitted_at: Instant,
+    /// Unix timestamp of the submission, as reported by the real API
+    created_timestamp: f64,
+}
+
+impl SimulatedJob {
+    fn status(&self, step: Duration) -> VerifyJobStatus {
+        let stage = self.submitted_at.elapsed().as_millis() / step.as_millis().max(1);
+        match stage {
+            0 => VerifyJobStatus::Submitted,
+            1 => VerifyJobStatus::Processing,
+            2 => VerifyJobStatus::Compiled,
+            _ => VerifyJobStatus::Success,
+        }
+    }
+
+    /// Compiler output so far, one line per quarter of the Processing stage
+    fn log(&self, step: Duration) -> Vec<String> {
+        if self.status(step) == VerifyJobStatus::Submitted {
+            return vec![];
+        }
+        let package = self.request["package_name"].as_str().unwrap_or("contract");
+        let lines = [
+            format!("   Compiling {package} (simulated)"),
+            "    Checking Cairo sources".to_string(),
+            "    Generating Sierra class".to_string(),
+            "    Finished `release` profile target(s)".to_string(),
+        ];
+        let processing = self.submitted_at.elapsed().saturating_sub(step);
+        let shown = processing.as_millis() / (step / 4).as_millis().max(1) + 1;
+        lines
+            .into_iter()
+            .take(usize::try_from(shown).unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    fn field(&self, name: &str) -> Value {
+        self.request.get(name).cloned().unwrap_or(Value::Null)
+    }
+}
+
+#[derive(Default)]
+struct State {
+    jobs: HashMap<String, SimulatedJob>,
+    next_id: u64,
+}
+
+/// Recorded exchanges and whether each has been served
+struct Replay {
+    exchanges: Vec<Exchange>,
+    served: Vec<bool>,
+}
+
+impl Replay {
+    /// The first unserved exchange for the request, or the last served one
+    ///
+    /// Falling back to an exchange already served keeps polling loops going
+    /// when the replaying run polls more often than the recorded one.
+    fn respond(&mut self, method: &str, path: &str) -> (String, String) {
+        let endpoint = path.trim_start_matches('/');
+        let matching: Vec<usize> = (0..self.exchanges.len())
+            .filter(|&i| {
+                self.exchanges[i].method == method && self.exchanges[i].endpoint == endpoint
+            })
+            .collect();
+        let next = matching
+            .iter()
+            .find(|&&i| !self.served[i])
+            .or_else(|| matching.last());
+
+        let Some(&index) = next else {
+            return (
+                "404 Not Found".to_string(),
+                json!({ "error": "No recorded response" }).to_string(),
+            );
+        };
+        self.served[index] = true;
+        let exchange = &self.exchanges[index];
+        let status = StatusCode::from_u16(exchange.status)
+            .map_or_else(|_| exchange.status.to_string(), |status| status.to_string());
+        (status, exchange.response_body.clone())
+    }
+}
+
+enum Backend {
+    Simulated(State, Duration),
+    Replay(Replay),
+}
+
+impl Backend {
+    fn respond(&mut self, method: &str, path: &str, body: &[u8]) -> (String, String) {
+        match self {
+            Self::Simulated(state, step) => {
+                let (status, response) = route(method, path, body, state, *step);
+                (status.to_string(), response.to_string())
+            }
+            Self::Replay(replay) => replay.respond(method, path),
+        }
+    }
+}
+
+/// A mock verification server running on a background thread
+///
+/// The server stops when the value is dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    url: Url,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Start a server on `127.0.0.1` that advances jobs one stage every `step`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no local port can be bound.
+    pub fn start(step: Duration) -> io::Result<Self> {
+        Self::spawn(Backend::Simulated(State::default(), step))
+    }
+
+    /// Start a server on `127.0.0.1` that answers with recorded responses
+    ///
+    /// Each request gets the response of the first recorded exchange with the
+    /// same method and endpoint that was not served yet, so repeated requests
+    /// such as status polls are answered in the recorded order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no local port can be bound.
+    pub fn replay(exchanges: Vec<Exchange>) -> io::Result<Self> {
+        let served = vec![false; exchanges.len()];
+        Self::spawn(Backend::Replay(Replay { exchanges, served }))
+    }
+
+    fn spawn(mut backend: Backend) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let addr = listener.local_addr()?;
+        let url = Url::parse(&format!("http://{addr}/")).map_err(io::Error::other)?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let result = stream.and_then(|stream| serve(stream, &mut backend));
+                    if let Err(e) = result {
+                        debug!("Mock server connection failed: {e}");
+                    }
+                }
+            })
+        };
+
+        debug!("Mock verification server listening on {url}");
+        Ok(Self {
+            addr,
+            url,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Base URL to pass to [`ApiClient::new`](super::ApiClient::new)
+    #[must_use]
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Handle one request; every response closes the connection
+fn serve(mut stream: TcpStream, backend: &mut Backend) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut gzipped = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("content-encoding") {
+                gzipped = value.trim().eq_ignore_ascii_case("gzip");
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    // Requests sent with --compress are gzip-compressed
+    let (status, response) = match decoded_body(body, gzipped) {
+        Ok(body) => backend.respond(&method, &path, &body),
+        Err(e) => (
+            "400 Bad Request".to_string(),
+            json!({ "error": format!("Invalid gzip body: {e}") }).to_string(),
+        ),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    )?;
+    stream.flush()
+}
+
+/// `body`, decompressed if it is `gzipped`
+fn decoded_body(body: Vec<u8>, gzipped: bool) -> io::Result<Vec<u8>> {
+    if !gzipped {
+        return Ok(body);
+    }
+    let mut decoded = Vec::new();
+    GzDecoder::new(body.as_slice()).read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+fn route(
+    method: &str,
+    path: &str,
+    body: &[u8],
+    state: &mut State,
+    step: Duration,
+) -> (&'static str, Value) {
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    match (method, segments.as_slice()) {
+        ("POST", ["class-verify", class_hash]) => {
+            let Ok(request) = serde_json::from_slice::<Value>(body) else {
+                return ("400 Bad Request", json!({ "error": "Invalid JSON body" }));
+            };
+            state.next_id += 1;
+            let job_id = format!("simulated-{}", state.next_id);
+            state.jobs.insert(
+                job_id.clone(),
+                SimulatedJob {
+                    class_hash: (*class_hash).to_string(),
+                    request,
+                    submitted_at: Instant::now(),
+                    created_timestamp: unix_now(),
+                },
+            );
+            ("200 OK", json!({ "job_id": job_id }))
+        }
+        ("GET", ["class-verify", "job", job_id]) => state.jobs.get(*job_id).map_or_else(
+            || ("404 Not Found", json!({ "error": "Job not found" })),
+            |job| ("200 OK", job_json(job_id, job, step)),
+        ),
+        ("GET", ["class-verify", "job", job_id, "logs"]) => {
+            let Some(job) = state.jobs.get(*job_id) else {
+                return ("404 Not Found", json!({ "error": "Job not found" }));
+            };
+            let log = job.log(step);
+            let offset = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("offset="))
+                .and_then(|offset| offset.parse().ok())
+                .unwrap_or(0)
+                .min(log.len());
+            (
+                "200 OK",
+                json!({ "lines": log[offset..], "next_offset": log.len() }),
+            )
+        }
+        ("GET", ["class-verify", "check", class_hash]) => {
+            let verified = state.jobs.values().find(|job| {
+                job.class_hash == *class_hash && job.status(step) == VerifyJobStatus::Success
+            });
+            let info = verified.map_or_else(
+                || json!({ "verified": false, "class_hash": class_hash }),
+                |job| {
+                    json!({
+                        "verified": true,
+                        "class_hash": class_hash,
+                        "name": job.field("name"),
+                        "license": job.field("license"),
+                        "verified_timestamp": unix_now(),
+                        "contract_file": job.field("contract_file"),
+                    })
+                },
+            );
+            ("200 OK", info)
+        }
+        ("GET", ["class-verify", "capabilities"]) => (
+            "200 OK",
+            json!({ "max_payload_size": SIMULATED_MAX_PAYLOAD_SIZE }),
+        ),
+        _ => ("404 Not Found", json!({ "error": "Not found" })),
+    }
+}
+
+fn job_json(job_id: &str, job: &SimulatedJob, step: Duration) -> Value {
+    let status = job.status(step);
+    json!({
+        "job_id": job_id,
+        "status": status,
+        "status_description": status.to_string(),
+        "message": null,
+        "error_category": null,
+        "class_hash": job.class_hash,
+        "created_timestamp": job.created_timestamp,
+        "updated_timestamp": unix_now(),
+        "contract_file": job.field("contract_file"),
+        "name": job.field("name"),
+        "license": job.field("license"),
+        "dojo_version": job.field("dojo_version"),
+        "build_tool": job.field("build_tool"),
+        "note": job.field("note"),
+    })
+}
+
+fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jobs_advance_through_stages() {
+        let step = Duration::from_mins(1);
+        let mut job = SimulatedJob {
+            class_hash: "0x1234".to_string(),
+            request: json!({ "name": "MyContract" }),
+            submitted_at: Instant::now(),
+            created_timestamp: 0.0,
+        };
+        assert_eq!(job.status(step), VerifyJobStatus::Submitted);
+
+        job.submitted_at = Instant::now().checked_sub(step * 2).unwrap();
+        assert_eq!(job.status(step), VerifyJobStatus::Compiled);
+
+        job.submitted_at = Instant::now().checked_sub(step * 10).unwrap();
+        assert_eq!(job.status(step), VerifyJobStatus::Success);
+    }
+
+    #[test]
+    fn test_route_job_logs() {
+        let step = Duration::from_mins(1);
+        let mut state = State::default();
+        let job = SimulatedJob {
+            class_hash: "0x1234".to_string(),
+            request: json!({ "package_name": "token" }),
+            // Halfway through Processing
+            submitted_at: Instant::now().checked_sub(step * 3 / 2).unwrap(),
+            created_timestamp: 0.0,
+        };
+        state.jobs.insert("simulated-1".to_string(), job);
+
+        let (status, logs) = route(
+            "GET",
+            "/class-verify/job/simulated-1/logs?offset=0",
+            &[],
+            &mut state,
+            step,
+        );
+        assert_eq!(status, "200 OK");
+        assert_eq!(logs["next_offset"], 3);
+        assert_eq!(logs["lines"][0], "   Compiling token (simulated)");
+
+        let (_, logs) = route(
+            "GET",
+            "/class-verify/job/simulated-1/logs?offset=2",
+            &[],
+            &mut state,
+            step,
+        );
+        assert_eq!(logs["lines"], json!(["    Generating Sierra class"]));
+    }
+
+    #[test]
+    fn test_gzipped_submission() {
+        use flate2::{write::GzEncoder, Compression};
+
+        let server = MockServer::start(DEFAULT_SIMULATION_STEP).unwrap();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"name":"MyContract"}"#).unwrap();
+
+        let response = reqwest::blocking::Client::new()
+            .post(server.url().join("class-verify/0x1234").unwrap())
+            .header(reqwest::header::CONTENT_ENCODING, "gzip")
+            .body(encoder.finish().unwrap())
+            .send()
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = response.json().unwrap();
+        assert_eq!(body["job_id"], "simulated-1");
+        drop(server);
+    }
+
+    #[test]
+    fn test_route_unknown_job() {
+        let mut state = State::default();
+        let (status, _) = route(
+            "GET",
+            "/class-verify/job/missing",
+            &[],
+            &mut state,
+            DEFAULT_SIMULATION_STEP,
+        );
+        assert_eq!(status, "404 Not Found");
+    }
+}
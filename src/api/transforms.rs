@@ -0,0 +1,469 @@
+//! Transforms applied to the files of a verification request
+//!
+//! Before a verification request is sent, its files pass through the
+//! [`PayloadTransform`]s attached to the [`ApiClient`](super::ApiClient), in
+//! order. A transform may rewrite files, leave some out, or reject the
+//! submission. Workspace members left out of the request are dropped from the
+//! workspace manifest first, by [`prune_workspace_members`]. Only
+//! [`DevDependencies`] runs by default; `payload-transforms`
+//! in `.voyager.toml` selects the pipeline by name, and applications embedding
+//! the verifier can attach their own transforms with `with_transforms`.
+
+use log::{debug, warn};
+use regex::Regex;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, LazyLock},
+};
+
+/// Files of a verification request: relative name to contents
+pub type PayloadFiles = BTreeMap<String, String>;
+
+/// A step of the payload pipeline
+pub trait PayloadTransform: fmt::Debug + Send + Sync {
+    /// Name reported when the transform rejects a submission
+    fn name(&self) -> &'static str;
+
+    /// Rewrite `files` in place
+    ///
+    /// # Errors
+    ///
+    /// Returns why the submission must not be sent.
+    fn apply(&self, files: &mut PayloadFiles) -> Result<(), String>;
+}
+
+/// Removes `[dev-dependencies]` from every `Scarb.toml`
+///
+/// The remote compiler does not need them, and some cannot be resolved there.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DevDependencies;
+
+impl PayloadTransform for DevDependencies {
+    fn name(&self) -> &'static str {
+        "dev-dependencies"
+    }
+
+    fn apply(&self, files: &mut PayloadFiles) -> Result<(), String> {
+        for (name, content) in files.iter_mut() {
+            if !is_manifest(name) {
+                continue;
+            }
+            let filtered = filter_dev_dependencies(content);
+            if filtered.len() != content.len() {
+                warn!(
+                    "Filtered dev-dependencies from {name} (size: {} -> {} bytes)",
+                    content.len(),
+                    filtered.len()
+                );
+            }
+            *content = filtered;
+        }
+        Ok(())
+    }
+}
+
+/// Filter out dev-dependencies from Scarb.toml content to prevent
+/// compilation issues on remote servers that don't have cargo installed
+fn filter_dev_dependencies(content: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_dev_deps = false;
+
+    for line in content.lines() {
+        // Check if we're entering a dev-dependencies section
+        if line.trim_start().starts_with("[dev-dependencies]") {
+            in_dev_deps = true;
+            // Add a comment instead of the section
+            lines.push("# [dev-dependencies] section removed for remote compilation");
+            continue;
+        }
+
+        // Check if we're entering a new section (but not dev-dependencies)
+        if line.trim_start().starts_with('[')
+            && !line.trim_start().starts_with("[dev-dependencies]")
+        {
+            // If we were in dev-deps and hit a new section, add empty line before it
+            if in_dev_deps {
+                lines.push("");
+            }
+            in_dev_deps = false;
+            lines.push(line);
+            continue;
+        }
+
+        // Skip lines that are part of dev-dependencies
+        if in_dev_deps {
+            continue;
+        }
+
+        lines.push(line);
+    }
+
+    lines.join("\n")
+}
+
+/// Whether `name` is a Scarb manifest
+fn is_manifest(name: &str) -> bool {
+    name == "Scarb.toml" || name.ends_with("/Scarb.toml")
+}
+
+/// Drops the members whose manifest is not among `files` from
+/// `[workspace] members` of every workspace manifest
+///
+/// Sources of a large workspace are pruned to the package being verified and
+/// its dependencies, and the remote build must not look for the members left
+/// out. Glob entries are kept, since they only match the members sent.
+pub fn prune_workspace_members(files: &mut PayloadFiles) {
+    let manifests: Vec<String> = files
+        .keys()
+        .filter(|name| is_manifest(name))
+        .cloned()
+        .collect();
+    for name in &manifests {
+        let dir = name.strip_suffix("Scarb.toml").unwrap_or_default();
+        let Some(content) = files.get_mut(name) else {
+            continue;
+        };
+        if let Some(pruned) = pruned_members(content, dir, &manifests) {
+            debug!("Left out workspace members missing from the payload in {name}");
+            *content = pruned;
+        }
+    }
+}
+
+/// `content` with the members of its workspace not in `manifests` removed,
+/// or `None` if it lists none
+///
+/// `dir` is the directory of the manifest, relative to the payload root.
+fn pruned_members(content: &str, dir: &str, manifests: &[String]) -> Option<String> {
+    let mut manifest: toml::Table = content.parse().ok()?;
+    let members = manifest
+        .get_mut("workspace")?
+        .get_mut("members")?
+        .as_array_mut()?;
+    let listed = members.len();
+    members.retain(|member| {
+        member.as_str().is_none_or(|path| {
+            let path = path.trim_start_matches("./").trim_end_matches('/');
+            path.contains(['*', '?', '[']) || manifests.contains(&format!("{dir}{path}/Scarb.toml"))
+        })
+    });
+    (members.len() != listed).then(|| manifest.to_string())
+}
+
+/// Converts CRLF line endings to LF
+///
+/// Keeps sources checked out on Windows identical to the same sources
+/// checked out elsewhere.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineEndings;
+
+impl PayloadTransform for LineEndings {
+    fn name(&self) -> &'static str {
+        "line-endings"
+    }
+
+    fn apply(&self, files: &mut PayloadFiles) -> Result<(), String> {
+        for (name, content) in files.iter_mut() {
+            if content.contains("\r\n") {
+                debug!("Normalized line endings of {name}");
+                *content = content.replace("\r\n", "\n");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Private keys, and keys or mnemonics assigned to secret-looking names
+static SECRET_REGEX: LazyLock<Result<Regex, regex::Error>> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)-----BEGIN [A-Z ]*PRIVATE KEY-----|(private[_-]?key|secret|mnemonic|seed[_-]?phrase)\w*["']?[^=\n]{0,40}?[:=]\s*["']?(0x[0-9a-f]{32,}|[a-z]+( [a-z]+){11,})"#,
+    )
+});
+
+/// Rejects submissions containing private keys or other secrets
+///
+/// Verified sources are published by the explorer, so a leaked key could not
+/// be taken back.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SecretScan;
+
+impl PayloadTransform for SecretScan {
+    fn name(&self) -> &'static str {
+        "secret-scan"
+    }
+
+    fn apply(&self, files: &mut PayloadFiles) -> Result<(), String> {
+        let regex = SECRET_REGEX.as_ref().map_err(ToString::to_string)?;
+        let findings: Vec<String> = files
+            .iter()
+            .flat_map(|(name, content)| {
+                content
+                    .lines()
+                    .enumerate()
+                    .filter(|(_, line)| regex.is_match(line))
+                    .map(move |(index, _)| format!("{name}:{}", index + 1))
+            })
+            .collect();
+        if findings.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("possible secrets found at {}", findings.join(", ")))
+        }
+    }
+}
+
+/// Markers of generated files, looked for in the first lines of a file
+const GENERATED_MARKERS: [&str; 2] = ["@generated", "DO NOT EDIT"];
+
+/// Number of leading lines searched for [`GENERATED_MARKERS`]
+const GENERATED_HEADER_LINES: usize = 5;
+
+/// Leaves out files marked as generated
+///
+/// Only suitable for projects whose generated files are not needed to
+/// compile the contract, such as generated bindings or ABIs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GeneratedFiles;
+
+impl PayloadTransform for GeneratedFiles {
+    fn name(&self) -> &'static str {
+        "generated-files"
+    }
+
+    fn apply(&self, files: &mut PayloadFiles) -> Result<(), String> {
+        files.retain(|name, content| {
+            let generated = content
+                .lines()
+                .take(GENERATED_HEADER_LINES)
+                .any(|line| GENERATED_MARKERS.iter().any(|marker| line.contains(marker)));
+            if generated {
+                debug!("Leaving out generated file {name}");
+            }
+            !generated
+        });
+        Ok(())
+    }
+}
+
+/// The built-in transforms, selectable by name in `.voyager.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadTransformKind {
+    DevDependencies,
+    LineEndings,
+    SecretScan,
+    GeneratedFiles,
+}
+
+impl PayloadTransformKind {
+    /// Every built-in transform
+    pub const ALL: [Self; 4] = [
+        Self::DevDependencies,
+        Self::LineEndings,
+        Self::SecretScan,
+        Self::GeneratedFiles,
+    ];
+
+    /// The transform called `name`
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.build().name() == name.to_lowercase())
+    }
+
+    /// The transform with its default settings
+    #[must_use]
+    pub fn build(self) -> Arc<dyn PayloadTransform> {
+        match self {
+            Self::DevDependencies => Arc::new(DevDependencies),
+            Self::LineEndings => Arc::new(LineEndings),
+            Self::SecretScan => Arc::new(SecretScan),
+            Self::GeneratedFiles => Arc::new(GeneratedFiles),
+        }
+    }
+}
+
+/// The pipeline used when none is configured
+#[must_use]
+pub fn default_transforms() -> Vec<Arc<dyn PayloadTransform>> {
+    vec![PayloadTransformKind::DevDependencies.build()]
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> PayloadFiles {
+        entries
+            .iter()
+            .map(|(name, content)| ((*name).to_string(), (*content).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_scarb_toml_removes_dev_dependencies() {
+        let input = r#"[package]
+name = "test"
+version = "0.1.0"
+
+[dependencies]
+starknet = "2.10.1"
+
+[dev-dependencies]
+assert_macros = "2.10.1"
+snforge_std = "0.38.3"
+
+[scripts]
+test = "snforge test"
+"#;
+
+        let expected = r#"[package]
+name = "test"
+version = "0.1.0"
+
+[dependencies]
+starknet = "2.10.1"
+
+# [dev-dependencies] section removed for remote compilation
+
+[scripts]
+test = "snforge test""#;
+
+        let result = filter_dev_dependencies(input);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_filter_scarb_toml_preserves_other_sections() {
+        let input = r#"[package]
+name = "test"
+
+[dependencies]
+cairo = "2.0.0"
+
+[tool.fmt]
+max-line-length = 120
+"#;
+
+        let expected = r#"[package]
+name = "test"
+
+[dependencies]
+cairo = "2.0.0"
+
+[tool.fmt]
+max-line-length = 120"#;
+
+        let result = filter_dev_dependencies(input);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_filter_scarb_toml_handles_no_dev_dependencies() {
+        let input = r#"[package]
+name = "test"
+version = "1.0.0"
+
+[dependencies]
+starknet = "2.10.1"
+"#;
+
+        // Should remain unchanged except for trailing newline
+        let result = filter_dev_dependencies(input);
+        assert_eq!(result, input.lines().collect::<Vec<_>>().join("\n"));
+    }
+
+    #[test]
+    fn test_filter_scarb_toml_handles_dev_deps_at_end() {
+        let input = r#"[package]
+name = "test"
+
+[dependencies]
+starknet = "2.10.1"
+
+[dev-dependencies]
+test_lib = "1.0.0"
+another_lib = "2.0.0"
+"#;
+
+        let expected = r#"[package]
+name = "test"
+
+[dependencies]
+starknet = "2.10.1"
+
+# [dev-dependencies] section removed for remote compilation"#;
+
+        let result = filter_dev_dependencies(input);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_builtin_transforms() {
+        let mut payload = files(&[
+            (
+                "token/Scarb.toml",
+                "[package]\nname = \"token\"\n\n[dev-dependencies]\nsnforge_std = \"0.38.3\"\n",
+            ),
+            ("token/src/lib.cairo", "mod token;\r\nmod utils;\r\n"),
+            (
+                "token/src/abi.cairo",
+                "// @generated by abigen\nmod abi {}\n",
+            ),
+        ]);
+        for kind in PayloadTransformKind::ALL {
+            kind.build().apply(&mut payload).unwrap();
+        }
+        assert_eq!(
+            payload["token/Scarb.toml"],
+            "[package]\nname = \"token\"\n\n# [dev-dependencies] section removed for remote compilation"
+        );
+        assert_eq!(payload["token/src/lib.cairo"], "mod token;\nmod utils;\n");
+        assert!(!payload.contains_key("token/src/abi.cairo"));
+
+        let mut leaky = files(&[
+            ("src/lib.cairo", "mod token;\n"),
+            (
+                "src/deploy.cairo",
+                "// deployer\nconst PRIVATE_KEY: felt252 = 0x4d5a1b2c3d4e5f60718293a4b5c6d7e8f9012345;\n",
+            ),
+        ]);
+        let reason = SecretScan.apply(&mut leaky).unwrap_err();
+        assert_eq!(reason, "possible secrets found at src/deploy.cairo:2");
+
+        assert_eq!(
+            PayloadTransformKind::from_name("Secret-Scan"),
+            Some(PayloadTransformKind::SecretScan)
+        );
+        assert_eq!(PayloadTransformKind::from_name("compression"), None);
+    }
+
+    #[test]
+    fn test_prune_workspace_members() {
+        let mut payload = files(&[
+            (
+                "Scarb.toml",
+                "[workspace]\nmembers = [\"token\", \"./utils/\", \"vault\", \"examples/*\"]\n",
+            ),
+            ("token/Scarb.toml", "[package]\nname = \"token\"\n"),
+            ("utils/Scarb.toml", "[package]\nname = \"utils\"\n"),
+        ]);
+        prune_workspace_members(&mut payload);
+        let manifest: toml::Table = payload["Scarb.toml"].parse().unwrap();
+        assert_eq!(
+            manifest["workspace"]["members"],
+            toml::Value::from(vec!["token", "./utils/", "examples/*"])
+        );
+
+        // Manifests listing every member are sent as written
+        let complete = "[workspace]\n# all of them\nmembers = [\"token\"]\n";
+        let mut payload = files(&[
+            ("Scarb.toml", complete),
+            ("token/Scarb.toml", "[package]\nname = \"token\"\n"),
+        ]);
+        prune_workspace_members(&mut payload);
+        assert_eq!(payload["Scarb.toml"], complete);
+    }
+}
@@ -1,12 +1,31 @@
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
+use log::{debug, warn};
 use regex::Regex;
 use reqwest::Url;
 use scarb_metadata::{Metadata, MetadataCommand, MetadataCommandError};
 use spdx::LicenseId;
-use std::{env, fmt::Display, io, path::PathBuf, sync::LazyLock};
+use std::{
+    env,
+    fmt::Display,
+    io,
+    net::SocketAddr,
+    path::PathBuf,
+    sync::{LazyLock, OnceLock},
+    time::{Duration, Instant},
+};
 use thiserror::Error;
 
-use crate::core::{class_hash::ClassHash, project::ProjectType};
+use crate::api::PollingConfig;
+use crate::core::{
+    class_hash::ClassHash,
+    project::{has_dojo_files, is_dojo_dependency, ProjectType},
+};
+use crate::filesystem::collector::SizeLimits;
+use crate::storage::history::RetentionPolicy;
+use crate::utils::{
+    suggestion::{Suggestion, SuggestionList},
+    voyager,
+};
 
 static VALID_NAME_REGEX: LazyLock<Result<Regex, regex::Error>> =
     LazyLock::new(|| Regex::new(r"^[a-zA-Z0-9_-]+$"));
@@ -17,21 +36,39 @@ fn get_name_validation_regex() -> Result<&'static Regex, String> {
         .map_or_else(|_| Err("Internal regex compilation error".to_string()), Ok)
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Project(Metadata);
+/// A Scarb project given with `--path`
+///
+/// Parsing `--path` only locates the manifest; `scarb metadata` runs the first
+/// time the metadata is needed, so commands and code paths that never look at
+/// the project work without scarb installed.
+#[derive(Debug, Clone)]
+pub struct Project {
+    manifest: Utf8PathBuf,
+    metadata: OnceLock<Metadata>,
+}
+
+/// Projects are the same if they have the same manifest, whether or not
+/// their metadata was read
+impl PartialEq for Project {
+    fn eq(&self, other: &Self) -> bool {
+        self.manifest == other.manifest
+    }
+}
+
+impl Eq for Project {}
 
 #[derive(Error, Debug)]
 pub enum ProjectError {
-    #[error("[E020] Scarb project manifest not found at: {0}\n\nSuggestions:\n  • Check that you're in a Scarb project directory\n  • Verify that Scarb.toml exists in the specified path\n  • Run 'scarb init' to create a new project\n  • Use --manifest-path to specify the correct path")]
+    #[error("[E020] Scarb project manifest not found at: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     MissingManifest(Utf8PathBuf),
 
-    #[error("[E021] Failed to read project metadata: {0}\n\nSuggestions:\n  • Check that Scarb.toml is valid TOML format\n  • Verify all dependencies are properly declared\n  • Run 'scarb metadata --format-version 1' to see the full error\n  • Run 'scarb check' to validate your project\n  • Ensure scarb is installed and up to date")]
+    #[error("[E021] Failed to read project metadata: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     MetadataError(#[from] MetadataCommandError),
 
-    #[error("[E022] File system error\n\nSuggestions:\n  • Check file permissions\n  • Verify the path exists and is accessible\n  • Ensure you have read access to the directory")]
+    #[error("[E022] File system error{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Io(#[from] io::Error),
 
-    #[error("[E023] Path contains invalid UTF-8 characters\n\nSuggestions:\n  • Use only ASCII characters in file paths\n  • Avoid special characters in directory names\n  • Check for hidden or control characters in the path")]
+    #[error("[E023] Path contains invalid UTF-8 characters{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Utf8(#[from] camino::FromPathBufError),
 }
 
@@ -45,105 +82,165 @@ impl ProjectError {
             Self::Utf8(_) => "E023",
         }
     }
+
+    /// Next steps for making the project readable by Scarb
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        Suggestion::for_code(self.error_code(), &[])
+    }
 }
 
 #[allow(dead_code)]
 impl Project {
+    /// Project with the manifest at `manifest`; its metadata is read on first use
+    ///
     /// # Errors
     ///
-    /// Returns an error if the manifest file doesn't exist or can't be read
+    /// Returns an error if the manifest file doesn't exist
     pub fn new(manifest: &Utf8PathBuf) -> Result<Self, ProjectError> {
-        manifest.try_exists().map_err(|err| match err.kind() {
-            io::ErrorKind::NotFound => ProjectError::MissingManifest(manifest.clone()),
-            _ => ProjectError::from(err),
-        })?;
+        if !manifest.try_exists()? {
+            return Err(ProjectError::MissingManifest(manifest.clone()));
+        }
+
+        Ok(Self {
+            manifest: manifest.clone(),
+            metadata: OnceLock::new(),
+        })
+    }
 
-        let root = manifest.parent().ok_or_else(|| {
+    /// Run `scarb metadata` for the project, unless it already ran
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if scarb cannot be run or fails to read the project
+    pub fn load(&self) -> Result<&Metadata, ProjectError> {
+        if let Some(metadata) = self.metadata.get() {
+            return Ok(metadata);
+        }
+        if !self.manifest.try_exists()? {
+            return Err(ProjectError::MissingManifest(self.manifest.clone()));
+        }
+
+        let root = self.manifest.parent().ok_or_else(|| {
             ProjectError::Io(io::Error::new(
                 io::ErrorKind::NotFound,
                 "Couldn't get parent directory of Scarb manifest file",
             ))
         })?;
 
+        let started = Instant::now();
         let metadata = MetadataCommand::new()
             .json()
-            .manifest_path(manifest)
+            .manifest_path(&self.manifest)
             .current_dir(root)
             .exec()?;
+        debug!(
+            "scarb metadata for {} took {:.2?}",
+            self.manifest,
+            started.elapsed()
+        );
 
-        Ok(Self(metadata))
-    }
-
-    #[must_use]
-    pub const fn manifest_path(&self) -> &Utf8PathBuf {
-        &self.0.workspace.manifest_path
+        Ok(self.metadata.get_or_init(|| metadata))
     }
 
-    #[must_use]
-    pub const fn root_dir(&self) -> &Utf8PathBuf {
-        &self.0.workspace.root
+    /// Manifest of the workspace, read with [`Project::load`] if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project metadata cannot be read
+    pub fn manifest_path(&self) -> Result<&Utf8PathBuf, ProjectError> {
+        Ok(&self.load()?.workspace.manifest_path)
     }
 
-    #[must_use]
-    pub const fn metadata(&self) -> &Metadata {
-        &self.0
+    /// Root directory of the workspace, read with [`Project::load`] if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project metadata cannot be read
+    pub fn root_dir(&self) -> Result<&Utf8PathBuf, ProjectError> {
+        Ok(&self.load()?.workspace.root)
     }
 
-    #[must_use]
-    pub fn get_license(&self) -> Option<LicenseId> {
-        self.0.packages.first().and_then(|pkg| {
-            pkg.manifest_metadata
-                .license
-                .as_ref()
-                .and_then(|license_str| {
-                    // Handle common SPDX identifiers directly
-                    match license_str.as_str() {
-                        "MIT" => spdx::license_id("MIT License"),
-                        "Apache-2.0" => spdx::license_id("Apache License 2.0"),
-                        "GPL-3.0" => spdx::license_id("GNU General Public License v3.0 only"),
-                        "BSD-3-Clause" => spdx::license_id("BSD 3-Clause License"),
-                        // Try exact match
-                        _ => spdx::license_id(license_str).or_else(|| {
-                            // Try imprecise matching
-                            spdx::imprecise_license_id(license_str).map(|(lic, _)| lic)
-                        }),
-                    }
-                })
-        })
+    /// Get the license declared by the package selected for verification
+    ///
+    /// `package` is the `--package` selection; without it the workspace's only
+    /// member is used. Dependencies are never consulted. Returns `None` when no
+    /// single package is selected or it declares no recognizable license, so
+    /// callers can fall back to the workspace manifest.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project metadata cannot be read
+    pub fn get_license(&self, package: Option<&str>) -> Result<Option<LicenseId>, ProjectError> {
+        let metadata = self.load()?;
+        let mut members = metadata
+            .packages
+            .iter()
+            .filter(|pkg| metadata.workspace.members.contains(&pkg.id));
+
+        let selected = match package {
+            Some(name) => members.find(|pkg| pkg.name == name),
+            None => match (members.next(), members.next()) {
+                (Some(only), None) => Some(only),
+                _ => None,
+            },
+        };
+
+        Ok(selected
+            .and_then(|pkg| pkg.manifest_metadata.license.as_deref())
+            .and_then(parse_manifest_license))
     }
 
     /// Detect if this is a Dojo project by analyzing dependencies
     ///
+    /// A Dojo dependency, by name or by git repository, or the profiles and
+    /// manifests of sozo next to a manifest make it a Dojo project. Dojo
+    /// imports alone are not conclusive and give [`ProjectType::Auto`].
+    ///
     /// # Errors
     ///
     /// Returns an error if the project metadata cannot be analyzed
     pub fn detect_project_type(&self) -> Result<ProjectType, ProjectError> {
-        let metadata = self.metadata();
+        let metadata = self.load()?;
+
+        // Check for a Dojo dependency in any package
+        let dojo_dependency = metadata
+            .packages
+            .iter()
+            .flat_map(|package| &package.dependencies)
+            .find(|dep| is_dojo_dependency(&dep.name, &dep.source.repr));
+        if let Some(dep) = dojo_dependency {
+            debug!("Dojo dependency found: {} ({})", dep.name, dep.source.repr);
+            return Ok(ProjectType::Dojo);
+        }
 
-        // Check for dojo-core dependency in any package
-        for package in &metadata.packages {
-            for dep in &package.dependencies {
-                if dep.name == "dojo_core" || dep.name == "dojo-core" || dep.name == "dojo" {
-                    return Ok(ProjectType::Dojo);
-                }
-            }
+        // Check for sozo profiles and manifests at the root and in members
+        let member_dirs = metadata
+            .packages
+            .iter()
+            .filter(|package| metadata.workspace.members.contains(&package.id))
+            .filter_map(|package| package.manifest_path.parent());
+        if std::iter::once(metadata.workspace.root.as_path())
+            .chain(member_dirs)
+            .any(has_dojo_files)
+        {
+            return Ok(ProjectType::Dojo);
         }
 
         // Check for dojo namespace imports in source files
-        if self.has_dojo_imports() {
-            return Ok(ProjectType::Dojo);
+        if Self::has_dojo_imports(&metadata.workspace.root) {
+            return Ok(ProjectType::Auto);
         }
 
         // Default to Scarb if no Dojo indicators found
         Ok(ProjectType::Scarb)
     }
 
-    /// Check if source files contain Dojo-specific imports
-    fn has_dojo_imports(&self) -> bool {
+    /// Check if the source files under `root` contain Dojo-specific imports
+    fn has_dojo_imports(root: &Utf8Path) -> bool {
         use std::fs;
         use walkdir::WalkDir;
 
-        let root = self.root_dir();
         let src_dir = root.join("src");
 
         if !src_dir.exists() {
@@ -170,9 +267,25 @@ impl Project {
     }
 }
 
+/// Map a manifest `license` value to an SPDX license
+fn parse_manifest_license(license_str: &str) -> Option<LicenseId> {
+    // Handle common SPDX identifiers directly
+    match license_str {
+        "MIT" => spdx::license_id("MIT License"),
+        "Apache-2.0" => spdx::license_id("Apache License 2.0"),
+        "GPL-3.0" => spdx::license_id("GNU General Public License v3.0 only"),
+        "BSD-3-Clause" => spdx::license_id("BSD 3-Clause License"),
+        // Try exact match
+        _ => spdx::license_id(license_str).or_else(|| {
+            // Try imprecise matching
+            spdx::imprecise_license_id(license_str).map(|(lic, _)| lic)
+        }),
+    }
+}
+
 impl Display for Project {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.manifest_path())
+        write!(f, "{}", self.manifest)
     }
 }
 
@@ -180,6 +293,24 @@ impl Display for Project {
 ///
 /// Returns an error if the project path is invalid or the manifest cannot be read
 pub fn project_value_parser(raw: &str) -> Result<Project, ProjectError> {
+    Project::new(&manifest_path(raw)?)
+}
+
+/// Like [`project_value_parser`], but a missing manifest is only reported by
+/// [`Project::load`], since `verify --stdin-tar` needs no project
+///
+/// # Errors
+///
+/// Returns an error if the project path is invalid
+pub fn deferred_project_value_parser(raw: &str) -> Result<Project, ProjectError> {
+    Ok(Project {
+        manifest: manifest_path(raw)?,
+        metadata: OnceLock::new(),
+    })
+}
+
+/// The manifest of the project at `raw`, a directory or a manifest file
+fn manifest_path(raw: &str) -> Result<Utf8PathBuf, ProjectError> {
     let path = PathBuf::from(raw);
 
     let absolute = if path.is_absolute() {
@@ -192,13 +323,11 @@ pub fn project_value_parser(raw: &str) -> Result<Project, ProjectError> {
 
     let utf8 = Utf8PathBuf::try_from(absolute)?;
 
-    let manifest = if utf8.is_file() {
+    Ok(if utf8.is_file() {
         utf8
     } else {
         utf8.join("Scarb.toml")
-    };
-
-    Project::new(&manifest)
+    })
 }
 
 #[derive(clap::Parser)]
@@ -238,6 +367,17 @@ Examples:
 pub struct Args {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Record every API request and response into DIR, for bug reports
+    ///
+    /// Header values and submitted file contents are left out.
+    #[arg(
+        long = "record-http",
+        global = true,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath
+    )]
+    pub record_http: Option<PathBuf>,
 }
 
 #[derive(clap::Subcommand)]
@@ -270,11 +410,17 @@ pub enum Commands {
     ///
     /// Queries the verification service for the current status of a submitted
     /// verification job. The job ID is returned when you submit a verification.
+    /// With --class-hash, the latest job for that class in local history is
+    /// shown instead.
     ///
     /// Examples:
     ///   # Using predefined network
     ///   voyager status --network mainnet --job 12345678-1234-1234-1234-123456789012
     ///
+    ///   # Latest job for a class
+    ///   voyager status --network mainnet \
+    ///     --class-hash 0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18
+    ///
     ///   # Using development network
     ///   voyager status --network dev --job 12345678-1234-1234-1234-123456789012
     ///
@@ -309,6 +455,20 @@ pub enum Commands {
     ///   voyager history stats
     History(HistoryArgs),
 
+    /// List the latest verified class of each contract
+    ///
+    /// Groups the local verification history by contract name and shows, per
+    /// network, the class hash that was verified last. Useful to look up the
+    /// current class of a contract before redeploying it.
+    ///
+    /// Examples:
+    ///   # All contracts
+    ///   voyager contracts
+    ///
+    ///   # One contract on mainnet, as JSON
+    ///   voyager contracts `MyToken` --network mainnet --json
+    Contracts(ContractsArgs),
+
     /// Check if a class is already verified
     ///
     /// Queries the verification service to check if a given class hash
@@ -325,6 +485,213 @@ pub enum Commands {
     ///   # Output as JSON
     ///   voyager check --network mainnet --class-hash 0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18 --json
     Check(CheckArgs),
+
+    /// Set up verification for a project
+    ///
+    /// Writes a .voyager.toml with recommended policy defaults, plus CI
+    /// workflow snippets for GitHub Actions and GitLab CI. The license,
+    /// project type and packages are read from the Scarb project, and its
+    /// contracts are listed as commented examples; --template starts from a
+    /// template with placeholder contracts instead. With --from-deployments,
+    /// the contracts are those a deployment tool recorded.
+    ///
+    /// Examples:
+    ///   # Scarb project in the current directory
+    ///   voyager init
+    ///
+    ///   # ERC20 token project
+    ///   voyager init --template erc20
+    ///
+    ///   # Dojo world on mainnet, without CI files
+    ///   voyager init --template dojo-world --network mainnet --no-ci
+    ///
+    ///   # Contracts declared by an sncast deployment script, from its state file
+    ///   voyager init --from-deployments scripts/state.json
+    Init(InitArgs),
+
+    /// Verify every class an upgradeable contract has used
+    ///
+    /// Reads the contract's class history from a Starknet node, lists the
+    /// current and previous class hashes with their verification status, and
+    /// offers to verify the unverified ones in a batch using the sources at --path.
+    ///
+    /// Examples:
+    ///   # List the class history of a proxy
+    ///   voyager verify-upgrade --network mainnet --rpc-url <https://rpc.example.com> \
+    ///     --address 0x0123...
+    ///
+    ///   # Verify unverified implementations without prompting
+    ///   voyager verify-upgrade --network mainnet --rpc-url <https://rpc.example.com> \
+    ///     --address 0x0123... --contract-name `MyContract` --yes --watch
+    VerifyUpgrade(VerifyUpgradeArgs),
+
+    /// Find class hashes by contract name
+    ///
+    /// Searches the explorer for classes whose contract name matches NAME and
+    /// lists their class hashes with verification status. Useful when only the
+    /// contract's name is known.
+    ///
+    /// Examples:
+    ///   # Find candidate classes on mainnet
+    ///   voyager search --network mainnet `MyToken`
+    ///
+    ///   # Only verified classes, as JSON
+    ///   voyager search --network mainnet `MyToken` --verified-only --json
+    Search(SearchArgs),
+
+    /// Show the dependency graph relevant to verification
+    ///
+    /// Prints the packages a package depends on, as resolved by Scarb, and
+    /// flags where each one comes from: path dependencies are uploaded with
+    /// the sources, git and registry dependencies are fetched by the remote
+    /// compiler.
+    ///
+    /// Examples:
+    ///   # Summary of a workspace package
+    ///   voyager deps --package `my_token`
+    ///
+    ///   # Render with Graphviz
+    ///   voyager deps --package `my_token` --format dot | dot -Tsvg > deps.svg
+    Deps(DepsArgs),
+
+    /// List the Starknet contracts defined in a project
+    ///
+    /// Scans the sources of each workspace package the way `voyager verify`
+    /// looks up --contract-name, and prints every `#[starknet::contract]`
+    /// module with the file defining it. Use the names shown as
+    /// --contract-name.
+    ///
+    /// Examples:
+    ///   voyager list-contracts
+    ///
+    ///   # One workspace package, as JSON
+    ///   voyager list-contracts --package `my_token` --json
+    ListContracts(ListContractsArgs),
+
+    /// Report the size of what would be submitted
+    ///
+    /// Collects the files like `voyager verify` does and prints size totals
+    /// per directory, the largest files, how well they compress, and files
+    /// that are not needed to compile the contract. Nothing is sent.
+    ///
+    /// Examples:
+    ///   voyager analyze --package `my_token`
+    ///
+    ///   # Include test files, as --test-files would
+    ///   voyager analyze --package `my_token` --test-files
+    Analyze(AnalyzeArgs),
+
+    /// Build a contract locally and compare its class hash with a deployed one
+    ///
+    /// Runs `scarb build`, computes the class hash of the compiled Sierra class
+    /// with `starkli class-hash`, and reports whether it matches --class-hash,
+    /// together with the toolchain versions involved. This checks the sources
+    /// without relying on the explorer. Exits with status 1 on a mismatch.
+    ///
+    /// Examples:
+    ///   voyager reproduce --class-hash 0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18 \
+    ///     --contract-name `MyContract`
+    Reproduce(ReproduceArgs),
+
+    /// Explain an error code
+    ///
+    /// Prints a longer description of an error code, its common causes and
+    /// how to fix it. Without a code, lists all error codes.
+    ///
+    /// Examples:
+    ///   voyager explain E019
+    ///
+    ///   # List all error codes
+    ///   voyager explain
+    Explain(ExplainArgs),
+
+    /// Re-verify the contracts of .voyager.toml on a schedule
+    ///
+    /// Runs until stopped. At every time matched by `cron` under [schedule]
+    /// in .voyager.toml, reports [[contracts]] that were verified before but
+    /// no longer are, then re-submits every contract that is not verified,
+    /// like `voyager verify --skip-if-verified --watch`.
+    ///
+    /// Examples:
+    ///   voyager daemon --network mainnet
+    ///
+    ///   # Run once now, e.g. from an external scheduler
+    ///   voyager daemon --network mainnet --once
+    Daemon(DaemonArgs),
+
+    /// Compare two dry-run payloads
+    ///
+    /// Reads the output of two `voyager verify --dry-run --format json` runs
+    /// and prints the metadata that changed and the files that were added,
+    /// removed, or whose contents differ. Useful to find out why a setup that
+    /// verified before fails after a refactor. Nothing is sent.
+    ///
+    /// Examples:
+    ///   voyager verify --class-hash 0x044... --contract-name `MyToken` \
+    ///     --dry-run --format json > before.json
+    ///   voyager diff-dryrun before.json after.json
+    DiffDryrun(DiffDryrunArgs),
+
+    /// Package a contract's sources for verification elsewhere
+    ///
+    /// Collects the files like `voyager verify` does and writes them, with
+    /// the toolchain versions, class hash and a digest of each file, to a
+    /// single .voyagerpkg file. `voyager verify --from-pack` submits it later
+    /// without the project, e.g. from a release machine holding the API key.
+    /// Nothing is sent.
+    ///
+    /// Examples:
+    ///   voyager pack --class-hash 0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18 \
+    ///     --contract-name `MyToken` --output token.voyagerpkg
+    ///
+    ///   # Submit the package
+    ///   voyager verify --network mainnet --from-pack token.voyagerpkg --watch
+    #[command(visible_alias = "package")]
+    Pack(PackArgs),
+
+    /// Print a markdown snippet to share a verification job
+    ///
+    /// The snippet links the job on the explorer and gives the `voyager
+    /// status` command that checks it, e.g. for a pull request description.
+    /// The contract name and class hash are added when the job is in local
+    /// history.
+    ///
+    /// Examples:
+    ///   voyager share --network sepolia --job 12345678-1234-1234-1234-123456789012
+    Share(ShareArgs),
+
+    /// Write the sources of a contract's package to one file
+    ///
+    /// Follows the `mod` declarations from the crate root and inlines each
+    /// module's file, marked with its path, for manual review, audits, and
+    /// explorers that verify a single file. Dependencies are not inlined; the
+    /// header of the file lists them. Nothing is sent.
+    ///
+    /// Examples:
+    ///   voyager flatten --contract-name `MyToken` -o flattened.cairo
+    ///
+    ///   # A contract of a workspace package, to stdout
+    ///   voyager flatten --contract-name `MyToken` --package `my_token`
+    Flatten(FlattenArgs),
+
+    /// Tools for developing Voyager and its frontends
+    #[cfg(feature = "dev-tools")]
+    #[command(hide = true)]
+    Dev(DevArgs),
+}
+
+impl Commands {
+    /// Whether the command reads `.voyager.toml`
+    ///
+    /// Only these commands load the config file, so a broken policy or
+    /// environment setting does not stop commands that would ignore it.
+    #[must_use]
+    pub const fn reads_config(&self) -> bool {
+        !matches!(
+            self,
+            Self::Init(_) | Self::ListContracts(_) | Self::Explain(_) | Self::DiffDryrun(_)
+        )
+    }
 }
 
 /// # Errors
@@ -364,6 +731,21 @@ pub fn license_value_parser(license: &str) -> Result<LicenseId, String> {
     Err(format!("Unrecognized license: {license}{guess}"))
 }
 
+/// # Errors
+///
+/// Returns an error if the address is not a hex felt
+pub fn contract_address_value_parser(address: &str) -> Result<String, String> {
+    let digits = address
+        .strip_prefix("0x")
+        .ok_or_else(|| "Contract address must start with 0x".to_string())?;
+
+    if digits.is_empty() || digits.len() > 64 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Contract address must be 0x followed by 1 to 64 hex digits".to_string());
+    }
+
+    Ok(address.to_lowercase())
+}
+
 /// # Errors
 ///
 /// Returns an error if the contract name contains invalid characters
@@ -442,20 +824,88 @@ pub struct VerifyArgs {
     #[command(flatten)]
     pub network_url: Network,
 
+    /// Use the URL, network and API key of [endpoints.<NAME>] in .voyager.toml
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["network", "url"])]
+    pub endpoint: Option<String>,
+
     /// Perform dry run (preview what would be submitted without sending)
     #[arg(long, default_value_t = false)]
     pub dry_run: bool,
 
+    /// Output format: `json` prints only the payload of a dry run; `table`
+    /// and `csv` render the summary of a batch
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+
     /// Path to Scarb project directory (default: current directory)
     #[arg(
         long,
         value_name = "DIR",
         value_hint = clap::ValueHint::DirPath,
-        value_parser = project_value_parser,
+        value_parser = deferred_project_value_parser,
         default_value = "."
     )]
     pub path: Project,
 
+    /// Read the sources and a voyager-manifest.json from a tar archive on stdin instead of --path
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["wizard", "resume", "from_receipt"]
+    )]
+    pub stdin_tar: bool,
+
+    /// Submit the sources of a .voyagerpkg file written by `voyager pack` instead of --path
+    #[arg(
+        long = "from-pack",
+        visible_alias = "from-bundle",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["wizard", "resume", "from_receipt", "stdin_tar", "contract_address"]
+    )]
+    pub from_pack: Option<Utf8PathBuf>,
+
+    /// Verify every contract of --address-file that the workspace defines, as a batch
+    #[arg(
+        long,
+        default_value_t = false,
+        requires = "address_file",
+        conflicts_with_all = ["class_hash", "contract_address", "contract_name", "wizard", "resume", "stdin_tar", "from_pack", "from_receipt", "targets"]
+    )]
+    pub all_deployed: bool,
+
+    /// Deployments file with the name and class hash of each contract, such as a Dojo manifest
+    #[arg(
+        long = "address-file",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        requires = "all_deployed"
+    )]
+    pub address_file: Option<Utf8PathBuf>,
+
+    /// Verify the systems, models and events of a manifest written by `sozo migrate`, as a batch
+    ///
+    /// Without a file, the only `manifest_<profile>.json` of the project is read.
+    #[arg(
+        long = "from-dojo-manifest",
+        value_name = "FILE",
+        num_args = 0..=1,
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["all_deployed", "class_hash", "contract_address", "contract_name", "wizard", "resume", "stdin_tar", "from_pack", "from_receipt", "targets"]
+    )]
+    pub from_dojo_manifest: Option<Option<Utf8PathBuf>>,
+
+    /// Verify the unverified systems, models and events registered with the Dojo world at this address
+    ///
+    /// The world's registrations are read from --rpc-url.
+    #[arg(
+        long,
+        value_name = "ADDRESS",
+        value_parser = contract_address_value_parser,
+        conflicts_with_all = ["all_deployed", "from_dojo_manifest", "class_hash", "contract_address", "contract_name", "wizard", "resume", "stdin_tar", "from_pack", "from_receipt", "targets"]
+    )]
+    pub world: Option<String>,
+
     /// Class hash of the deployed contract to verify
     #[arg(
         long = "class-hash",
@@ -464,10 +914,45 @@ pub struct VerifyArgs {
     )]
     pub class_hash: Option<ClassHash>,
 
+    /// Starknet JSON-RPC endpoint to confirm the class is declared before uploading
+    #[arg(long, value_name = "URL", env = "STARKNET_RPC_URL")]
+    pub rpc_url: Option<Url>,
+
+    /// Note on why the verification was submitted, sent with it and kept in history
+    #[arg(long, value_name = "TEXT")]
+    pub note: Option<String>,
+
+    /// Verify the class of the contract deployed at this address, looked up on the explorer
+    #[arg(
+        long = "contract-address",
+        value_name = "ADDRESS",
+        value_parser = contract_address_value_parser,
+        conflicts_with_all = ["class_hash", "from_receipt", "wizard", "resume", "stdin_tar"]
+    )]
+    pub contract_address: Option<String>,
+
+    /// Take the class hash, and the network if recorded, from a starkli or sncast JSON receipt
+    #[arg(
+        long = "from-receipt",
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        conflicts_with_all = ["class_hash", "wizard", "resume"]
+    )]
+    pub from_receipt: Option<Utf8PathBuf>,
+
     /// Wait indefinitely for verification result (polls until completion)
     #[arg(long, default_value_t = false)]
     pub watch: bool,
 
+    /// Delay between status checks (e.g. 5s, 1m), or the first delay of the
+    /// exponential poll strategy
+    #[arg(long, value_name = "DURATION", value_parser = super::prompt::parse_timeout)]
+    pub poll_interval: Option<Duration>,
+
+    /// Stop waiting for the job after this long (e.g. 30m, 2h)
+    #[arg(long, value_name = "DURATION", value_parser = super::prompt::parse_timeout)]
+    pub poll_timeout: Option<Duration>,
+
     /// SPDX license identifier (e.g., MIT, Apache-2.0)
     #[arg(
         long,
@@ -476,7 +961,7 @@ pub struct VerifyArgs {
     )]
     pub license: Option<LicenseId>,
 
-    /// Name of the contract for verification
+    /// Name of the contract for verification (default: the only contract of the package)
     #[arg(
         long = "contract-name",
         value_name = "NAME",
@@ -484,6 +969,22 @@ pub struct VerifyArgs {
     )]
     pub contract_name: Option<String>,
 
+    /// File defining the contract, relative to the project root; skips automatic detection
+    #[arg(long, value_name = "PATH")]
+    pub contract_file: Option<Utf8PathBuf>,
+
+    /// Additional directory to submit, e.g. shared Cairo code outside the workspace (repeatable)
+    #[arg(long = "extra-source", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    pub extra_sources: Vec<Utf8PathBuf>,
+
+    /// Submit documentation (docs/**/*.md and READMEs) for the explorer to show with the sources
+    #[arg(long, default_value_t = false)]
+    pub include_docs: bool,
+
+    /// Documentation globs eligible for --include-docs (from `doc-paths` in config)
+    #[arg(skip)]
+    pub doc_paths: Vec<String>,
+
     /// Select specific package for verification (required for workspace projects)
     #[arg(
         long,
@@ -517,6 +1018,23 @@ pub struct VerifyArgs {
     #[arg(long, default_value_t = false)]
     pub wizard: bool,
 
+    /// Never prompt; questions get their default answer (implied when stdin is not a terminal)
+    #[arg(long, default_value_t = false, conflicts_with = "wizard")]
+    pub non_interactive: bool,
+
+    /// Submit without asking for confirmation after the summary
+    #[arg(
+        long,
+        short = 'y',
+        visible_alias = "no-confirm",
+        default_value_t = false
+    )]
+    pub yes: bool,
+
+    /// Give up on a prompt after this long (e.g. 30s, 2m) and take the safe choice
+    #[arg(long, value_name = "DURATION", value_parser = super::prompt::parse_timeout)]
+    pub prompt_timeout: Option<Duration>,
+
     /// Send desktop notifications when verification completes (requires --watch)
     #[cfg(feature = "notifications")]
     #[arg(long, default_value_t = false)]
@@ -529,100 +1047,760 @@ pub struct VerifyArgs {
     /// Delay in seconds between batch contract submissions (for rate limiting)
     #[arg(long, value_name = "SECONDS")]
     pub batch_delay: Option<u64>,
-}
 
-#[derive(clap::Args)]
-pub struct StatusArgs {
-    /// Network to verify on (mainnet, sepolia, dev). If not specified, --url is required
-    #[arg(long, value_enum)]
-    pub network: Option<NetworkKind>,
+    /// Number of batch contracts to submit concurrently; --batch-delay applies to each worker
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub jobs: usize,
 
-    #[command(flatten)]
-    pub network_url: Network,
+    /// Resume an interrupted batch verification: the latest one for this project, or the one with the given ID
+    #[arg(
+        long,
+        value_name = "BATCH_ID",
+        num_args = 0..=1,
+        default_missing_value = "latest",
+        conflicts_with_all = ["class_hash", "wizard", "dry_run", "simulate"]
+    )]
+    pub resume: Option<String>,
 
-    /// Verification job ID (UUID format)
-    #[arg(long, value_name = "UUID")]
-    pub job: String,
+    /// Refuse to submit if the project has uncommitted git changes
+    #[arg(long, default_value_t = false)]
+    pub require_clean_git: bool,
 
-    /// Show detailed error messages from the remote compiler
-    #[arg(long, short = 'v', default_value_t = false)]
-    pub verbose: bool,
+    /// Submit even if the working tree has uncommitted changes (overrides --require-clean-git)
+    #[arg(long, default_value_t = false)]
+    pub allow_dirty: bool,
 
-    /// Output format for status information
-    #[arg(long, value_enum, default_value = "text")]
-    pub format: OutputFormat,
-}
+    /// Submit under a single license even if workspace members declare different ones
+    #[arg(long, default_value_t = false)]
+    pub allow_license_mismatch: bool,
 
-#[derive(clap::Args)]
-pub struct CheckArgs {
-    /// Network to check on (mainnet, sepolia, dev). If not specified, --url is required
+    /// Refuse to submit Cairo files without an SPDX header naming the submitted license
+    #[arg(long, default_value_t = false)]
+    pub require_license_headers: bool,
+
+    /// Build the files to submit locally first, and stop if they don't compile
+    #[arg(long, default_value_t = false)]
+    pub preflight: bool,
+
+    /// Send the verification request gzip-compressed, to fit large projects in the payload limit
+    #[arg(long, default_value_t = false)]
+    pub compress: bool,
+
+    /// Build the contract locally and warn if its class hash differs from --class-hash
+    #[arg(long, default_value_t = false, conflicts_with_all = ["stdin_tar", "from_pack"])]
+    pub compare_class_hash: bool,
+
+    /// Skip submission when the class is already verified on Voyager
+    #[arg(long, default_value_t = false)]
+    pub skip_if_verified: bool,
+
+    /// Re-verify even if the class is already verified (overrides --skip-if-verified),
+    /// and upload sources that were already submitted
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+
+    /// What to do if history shows a pending job for the same class and network (default: warn)
+    #[arg(long, value_enum, value_name = "POLICY")]
+    pub on_duplicate: Option<DuplicatePolicy>,
+
+    /// Write batch verification results as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    pub results: Option<Utf8PathBuf>,
+
+    /// Skip batch contracts unchanged since the verified state in this file, and record verified ones there
+    #[arg(long, value_name = "FILE")]
+    pub state: Option<Utf8PathBuf>,
+
+    /// On failure, write a triage archive (payload, logs, metadata, server response) to this file
+    #[arg(long, value_name = "FILE")]
+    pub bundle: Option<Utf8PathBuf>,
+
+    /// Time zone for displayed timestamps (default: local)
     #[arg(long, value_enum)]
-    pub network: Option<NetworkKind>,
+    pub timezone: Option<Timezone>,
 
-    #[command(flatten)]
-    pub network_url: Network,
+    /// Submit to an embedded mock server instead of Voyager (no network access needed)
+    #[arg(
+        long,
+        default_value_t = false,
+        conflicts_with_all = ["network", "url", "wizard", "dry_run"]
+    )]
+    pub simulate: bool,
 
-    /// Class hash to check (0x-prefixed hex)
+    /// Submit to several explorers: `voyager` and names configured as [targets.<name>] in .voyager.toml
     #[arg(
-        long = "class-hash",
-        value_name = "HASH",
-        value_parser = ClassHash::new
+        long,
+        value_name = "NAMES",
+        value_delimiter = ',',
+        conflicts_with_all = ["simulate", "stdin_tar", "from_pack"]
     )]
-    pub class_hash: ClassHash,
+    pub targets: Vec<String>,
 
-    /// Output result as JSON
+    /// Serve Prometheus metrics at http://<ADDR>/metrics while watching (e.g. 127.0.0.1:9464)
+    #[arg(long, value_name = "ADDR", conflicts_with = "dry_run")]
+    pub metrics_addr: Option<SocketAddr>,
+
+    /// Pruning applied when the submission is added to history (from `[history]` in config)
+    #[arg(skip)]
+    pub history_retention: RetentionPolicy,
+
+    /// Size limits of the submitted files (from `[voyager.limits]` in config)
+    #[arg(skip)]
+    pub size_limits: SizeLimits,
+
+    /// API key sent with every request, for private or rate-limited APIs
+    /// (default: `VOYAGER_API_KEY`, or the key of the `--endpoint` entry in config)
+    #[arg(long, value_name = "KEY")]
+    pub api_key: Option<String>,
+
+    /// Explorer linked to for verified classes (from the `--endpoint` entry in config)
+    #[arg(skip)]
+    pub explorer_url: Option<Url>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InitTemplate {
+    /// ERC20 token contract
+    Erc20,
+
+    /// ERC721 NFT contract
+    Erc721,
+
+    /// Dojo world with its systems
+    DojoWorld,
+}
+
+#[derive(clap::Args)]
+pub struct VerifyUpgradeArgs {
+    /// Address of the upgradeable contract
+    #[arg(long, value_name = "ADDRESS", value_parser = contract_address_value_parser)]
+    pub address: String,
+
+    #[command(flatten)]
+    pub verify: VerifyArgs,
+}
+
+#[derive(clap::Args)]
+pub struct InitArgs {
+    /// Project template to start from, instead of the values found in the
+    /// Scarb project at --path
+    #[arg(long, value_enum)]
+    pub template: Option<InitTemplate>,
+
+    /// Write the contracts recorded by sncast or a starkli deployment script
+    /// instead of placeholders
+    ///
+    /// Takes the state file of `sncast script run`
+    /// (`<script>_<network>_state.json`), or a JSON deployments file: a list
+    /// of contracts, an object with a `contracts` list, or an object of
+    /// contracts by name, each with a `class_hash`.
+    #[arg(long, value_name = "FILE")]
+    pub from_deployments: Option<Utf8PathBuf>,
+
+    /// Network written to the generated config
+    #[arg(long, value_enum, default_value = "sepolia")]
+    pub network: NetworkKind,
+
+    /// Project directory to write the files into
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    pub path: Utf8PathBuf,
+
+    /// Don't write CI workflow snippets
+    #[arg(long, default_value_t = false)]
+    pub no_ci: bool,
+
+    /// Overwrite files that already exist
+    #[arg(long, default_value_t = false)]
+    pub force: bool,
+}
+
+#[derive(clap::Args)]
+pub struct StatusArgs {
+    /// Network to verify on (mainnet, sepolia, dev). If not specified, --url is required
+    #[arg(long, value_enum)]
+    pub network: Option<NetworkKind>,
+
+    #[command(flatten)]
+    pub network_url: Network,
+
+    /// Use the URL, network and API key of [endpoints.<NAME>] in .voyager.toml
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["network", "url"])]
+    pub endpoint: Option<String>,
+
+    /// Verification job ID (UUID format)
+    #[arg(
+        long,
+        value_name = "UUID",
+        required_unless_present = "class_hash",
+        conflicts_with = "class_hash"
+    )]
+    pub job: Option<String>,
+
+    /// Show the latest job for this class hash, from history or the API
+    #[arg(long = "class-hash", value_name = "HASH", value_parser = ClassHash::new)]
+    pub class_hash: Option<ClassHash>,
+
+    /// Show detailed error messages from the remote compiler
+    #[arg(long, short = 'v', default_value_t = false)]
+    pub verbose: bool,
+
+    /// Output format for status information
+    #[arg(long, value_enum, default_value = "text")]
+    pub format: OutputFormat,
+
+    /// Time zone for displayed timestamps (default: local)
+    #[arg(long, value_enum)]
+    pub timezone: Option<Timezone>,
+
+    /// Delay between status checks (e.g. 5s, 1m), or the first delay of the
+    /// exponential poll strategy
+    #[arg(long, value_name = "DURATION", value_parser = super::prompt::parse_timeout)]
+    pub poll_interval: Option<Duration>,
+
+    /// Stop waiting for the job after this long (e.g. 30m, 2h)
+    #[arg(long, value_name = "DURATION", value_parser = super::prompt::parse_timeout)]
+    pub poll_timeout: Option<Duration>,
+
+    /// API key sent with every request, for private or rate-limited APIs
+    /// (default: `VOYAGER_API_KEY`, or the key of the `--endpoint` entry in config)
+    #[arg(long, value_name = "KEY")]
+    pub api_key: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct CheckArgs {
+    /// Network to check on (mainnet, sepolia, dev). If not specified, --url is required
+    #[arg(long, value_enum)]
+    pub network: Option<NetworkKind>,
+
+    #[command(flatten)]
+    pub network_url: Network,
+
+    /// Use the URL, network and API key of [endpoints.<NAME>] in .voyager.toml
+    #[arg(long, value_name = "NAME", conflicts_with_all = ["network", "url"])]
+    pub endpoint: Option<String>,
+
+    /// Class hash to check (0x-prefixed hex)
+    #[arg(
+        long = "class-hash",
+        value_name = "HASH",
+        value_parser = ClassHash::new
+    )]
+    pub class_hash: ClassHash,
+
+    /// Output result as JSON
+    #[arg(long, short, default_value_t = false)]
+    pub json: bool,
+
+    /// Show detailed error messages
+    #[arg(short, long, default_value_t = false)]
+    pub verbose: bool,
+
+    /// Always query the API instead of using a cached result
+    #[arg(long, default_value_t = false)]
+    pub no_cache: bool,
+
+    /// How long a cached verified result stays valid, in seconds (default: 300)
+    #[arg(long, value_name = "SECONDS")]
+    pub cache_ttl: Option<u64>,
+
+    /// Time zone for displayed timestamps (default: local)
+    #[arg(long, value_enum)]
+    pub timezone: Option<Timezone>,
+
+    /// Starknet JSON-RPC endpoint to confirm the class is declared on-chain
+    #[arg(long, value_name = "URL", env = "STARKNET_RPC_URL")]
+    pub rpc_url: Option<Url>,
+
+    /// API key sent with every request, for private or rate-limited APIs
+    /// (default: `VOYAGER_API_KEY`, or the key of the `--endpoint` entry in config)
+    #[arg(long, value_name = "KEY")]
+    pub api_key: Option<String>,
+}
+
+impl CheckArgs {
+    /// Take the URL, network, API key and RPC of `endpoint`
+    ///
+    /// An RPC URL given with `--rpc-url` (or `STARKNET_RPC_URL`) and an API
+    /// key given with `--api-key` are kept.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: super::config::Endpoint) -> Self {
+        self.network_url = Network::new(endpoint.url, UrlSource::Config);
+        self.network = endpoint.network;
+        self.api_key = self.api_key.or(endpoint.api_key);
+        self.rpc_url = self.rpc_url.or(endpoint.rpc_url);
+        self
+    }
+
+    /// Merge configuration file values with CLI arguments
+    /// CLI arguments take precedence over config file values
+    #[must_use]
+    pub fn merge_with_config(mut self, config: &super::config::Config) -> Self {
+        // Merge network if not provided via CLI
+        if self.network.is_none() {
+            self.network = config.parse_network();
+        }
+
+        // Merge timezone if not provided via CLI
+        if self.timezone.is_none() {
+            self.timezone = config.parse_timezone();
+        }
+
+        // Merge verbose flag
+        if let Some(verbose) = config.voyager.verbose {
+            if !self.verbose {
+                self.verbose = verbose;
+            }
+        }
+
+        // Merge cache TTL if not provided via CLI
+        if self.cache_ttl.is_none() {
+            self.cache_ttl = config.voyager.check_cache_ttl;
+        }
+
+        // Take the API key from VOYAGER_API_KEY unless an endpoint set one
+        if self.api_key.is_none() {
+            self.api_key.clone_from(&config.voyager.api_key);
+        }
+
+        // Select the API from config if neither --url nor --network was given
+        self.network_url
+            .merge_with_config(self.network.as_ref(), config);
+
+        self
+    }
+
+    /// Validate that all required fields are set after config merging
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if required fields are missing or invalid
+    pub fn validate(&self) -> Result<(), String> {
+        self.network_url.validate()?;
+
+        Ok(())
+    }
+}
+
+#[derive(clap::Args)]
+pub struct SearchArgs {
+    /// Network to search on (mainnet, sepolia, dev). If not specified, --url is required
+    #[arg(long, value_enum)]
+    pub network: Option<NetworkKind>,
+
+    #[command(flatten)]
+    pub network_url: Network,
+
+    /// Contract name to search for
+    #[arg(value_name = "NAME")]
+    pub name: String,
+
+    /// Only list verified classes
+    #[arg(long, default_value_t = false)]
+    pub verified_only: bool,
+
+    /// Output results as JSON
     #[arg(long, short, default_value_t = false)]
     pub json: bool,
 
-    /// Show detailed error messages
-    #[arg(short, long, default_value_t = false)]
-    pub verbose: bool,
+    /// Time zone for displayed timestamps (default: local)
+    #[arg(long, value_enum)]
+    pub timezone: Option<Timezone>,
+}
+
+impl SearchArgs {
+    /// Merge configuration file values with CLI arguments
+    /// CLI arguments take precedence over config file values
+    #[must_use]
+    pub fn merge_with_config(mut self, config: &super::config::Config) -> Self {
+        if self.network.is_none() {
+            self.network = config.parse_network();
+        }
+
+        if self.timezone.is_none() {
+            self.timezone = config.parse_timezone();
+        }
+
+        // Select the API from config if neither --url nor --network was given
+        self.network_url
+            .merge_with_config(self.network.as_ref(), config);
+
+        self
+    }
+
+    /// Validate that all required fields are set after config merging
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if required fields are missing or invalid
+    pub fn validate(&self) -> Result<(), String> {
+        self.network_url.validate()?;
+
+        if self.name.trim().is_empty() {
+            return Err("Contract name to search for cannot be empty".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(clap::Args)]
+pub struct DepsArgs {
+    /// Path to Scarb project directory (default: current directory)
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = project_value_parser,
+        default_value = "."
+    )]
+    pub path: Project,
+
+    /// Package to show dependencies of (required for workspace projects)
+    #[arg(
+        long,
+        value_name = "PACKAGE_ID",
+        value_parser = package_name_value_parser
+    )]
+    pub package: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DepsFormat::Text)]
+    pub format: DepsFormat,
+}
+
+impl DepsArgs {
+    /// Merge configuration file values with CLI arguments
+    /// CLI arguments take precedence over config file values
+    #[must_use]
+    pub fn merge_with_config(mut self, config: &super::config::Config) -> Self {
+        if self.package.is_none() {
+            self.package.clone_from(&config.workspace.default_package);
+        }
+
+        self
+    }
+}
+
+#[derive(clap::Args)]
+pub struct ListContractsArgs {
+    /// Path to Scarb project directory (default: current directory)
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = project_value_parser,
+        default_value = "."
+    )]
+    pub path: Project,
+
+    /// Only list the contracts of this workspace package
+    #[arg(
+        long,
+        value_name = "PACKAGE_ID",
+        value_parser = package_name_value_parser
+    )]
+    pub package: Option<String>,
+
+    /// Output results as JSON
+    #[arg(long, short, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(clap::Args)]
+pub struct AnalyzeArgs {
+    /// Path to Scarb project directory (default: current directory)
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = project_value_parser,
+        default_value = "."
+    )]
+    pub path: Project,
+
+    /// Package that would be verified (required for workspace projects)
+    #[arg(
+        long,
+        value_name = "PACKAGE_ID",
+        value_parser = package_name_value_parser
+    )]
+    pub package: Option<String>,
+
+    /// Include Scarb.lock, as --lock-file would
+    #[arg(long, default_value_t = false)]
+    pub lock_file: bool,
+
+    /// Include test files from src/, as --test-files would
+    #[arg(long, default_value_t = false)]
+    pub test_files: bool,
+
+    /// Include an additional directory, as --extra-source would (repeatable)
+    #[arg(long = "extra-source", value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
+    pub extra_sources: Vec<Utf8PathBuf>,
+
+    /// Include documentation, as --include-docs would
+    #[arg(long, default_value_t = false)]
+    pub include_docs: bool,
+
+    /// Documentation globs eligible for --include-docs (from `doc-paths` in config)
+    #[arg(skip)]
+    pub doc_paths: Vec<String>,
+
+    /// Size limits the payload is checked against (from `[voyager.limits]` in config)
+    #[arg(skip)]
+    pub size_limits: SizeLimits,
+}
+
+impl AnalyzeArgs {
+    /// Merge configuration file values with CLI arguments
+    /// CLI arguments take precedence over config file values
+    #[must_use]
+    pub fn merge_with_config(mut self, config: &super::config::Config) -> Self {
+        if self.package.is_none() {
+            self.package.clone_from(&config.workspace.default_package);
+        }
+        if !self.lock_file {
+            self.lock_file = config.voyager.lock_file.unwrap_or(false);
+        }
+        if !self.test_files {
+            self.test_files = config.voyager.test_files.unwrap_or(false);
+        }
+        if !self.include_docs {
+            self.include_docs = config.voyager.include_docs.unwrap_or(false);
+        }
+        self.doc_paths.clone_from(&config.voyager.doc_paths);
+        self.size_limits = config.voyager.limits.size_limits();
+
+        self
+    }
+}
+
+#[derive(clap::Args)]
+pub struct ReproduceArgs {
+    /// Path to Scarb project directory (default: current directory)
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = project_value_parser,
+        default_value = "."
+    )]
+    pub path: Project,
+
+    /// Class hash the local build is expected to produce
+    #[arg(
+        long = "class-hash",
+        value_name = "HASH",
+        value_parser = ClassHash::new
+    )]
+    pub class_hash: ClassHash,
+
+    /// Name of the contract to build
+    #[arg(
+        long = "contract-name",
+        value_name = "NAME",
+        value_parser = contract_name_value_parser
+    )]
+    pub contract_name: String,
+
+    /// Package containing the contract (required for workspace projects)
+    #[arg(
+        long,
+        value_name = "PACKAGE_ID",
+        value_parser = package_name_value_parser
+    )]
+    pub package: Option<String>,
+
+    /// Use the existing build artifacts instead of running scarb build
+    #[arg(long, default_value_t = false)]
+    pub no_build: bool,
+
+    /// Output the report as JSON
+    #[arg(long, short, default_value_t = false)]
+    pub json: bool,
+}
+
+impl ReproduceArgs {
+    /// Merge configuration file values with CLI arguments
+    /// CLI arguments take precedence over config file values
+    #[must_use]
+    pub fn merge_with_config(mut self, config: &super::config::Config) -> Self {
+        if self.package.is_none() {
+            self.package.clone_from(&config.workspace.default_package);
+        }
+
+        self
+    }
+}
+
+#[derive(clap::Args)]
+pub struct ExplainArgs {
+    /// Error code to explain, e.g. E019
+    #[arg(value_name = "CODE")]
+    pub code: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct DiffDryrunArgs {
+    /// Payload of the earlier dry run
+    #[arg(value_name = "BEFORE", value_hint = clap::ValueHint::FilePath)]
+    pub before: Utf8PathBuf,
+
+    /// Payload of the later dry run
+    #[arg(value_name = "AFTER", value_hint = clap::ValueHint::FilePath)]
+    pub after: Utf8PathBuf,
+}
+
+#[derive(clap::Args)]
+pub struct ShareArgs {
+    /// Network the job was submitted to (mainnet, sepolia, dev). If not specified, --url is required
+    #[arg(long, value_enum)]
+    pub network: Option<NetworkKind>,
+
+    #[command(flatten)]
+    pub network_url: Network,
+
+    /// Verification job ID (UUID format)
+    #[arg(long, value_name = "UUID")]
+    pub job: String,
+}
+
+#[derive(clap::Args)]
+pub struct PackArgs {
+    /// File to write the package to (default: `<CONTRACT_NAME>.voyagerpkg`)
+    #[arg(long, short, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub output: Option<Utf8PathBuf>,
+
+    #[command(flatten)]
+    pub verify: VerifyArgs,
+}
+
+#[derive(clap::Args)]
+pub struct FlattenArgs {
+    /// Path to Scarb project directory (default: current directory)
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = project_value_parser,
+        default_value = "."
+    )]
+    pub path: Project,
+
+    /// Name of the contract to flatten
+    #[arg(
+        long = "contract-name",
+        value_name = "NAME",
+        value_parser = contract_name_value_parser
+    )]
+    pub contract_name: String,
+
+    /// Package containing the contract (default: the workspace member defining it)
+    #[arg(
+        long,
+        value_name = "PACKAGE_ID",
+        value_parser = package_name_value_parser
+    )]
+    pub package: Option<String>,
+
+    /// File to write the flattened sources to (default: stdout)
+    #[arg(long, short, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    pub output: Option<Utf8PathBuf>,
 }
 
-impl CheckArgs {
+impl FlattenArgs {
     /// Merge configuration file values with CLI arguments
     /// CLI arguments take precedence over config file values
     #[must_use]
     pub fn merge_with_config(mut self, config: &super::config::Config) -> Self {
-        // Merge network if not provided via CLI
-        if self.network.is_none() {
-            self.network = config.parse_network();
-        }
-
-        // Merge verbose flag
-        if let Some(verbose) = config.voyager.verbose {
-            if !self.verbose {
-                self.verbose = verbose;
-            }
-        }
-
-        // Merge URL if provided in config and not set via CLI or network flag
-        if self.network_url.url.as_str() == "https://placeholder.invalid/" {
-            if let Some(ref url_str) = config.voyager.url {
-                if let Ok(parsed_url) = Url::parse(url_str) {
-                    self.network_url.url = parsed_url;
-                }
-            }
+        if self.package.is_none() {
+            self.package.clone_from(&config.workspace.default_package);
         }
 
         self
     }
+}
 
-    /// Validate that all required fields are set after config merging
+#[derive(clap::Args)]
+pub struct DaemonArgs {
+    /// Network to re-verify on (default: from .voyager.toml)
+    #[arg(long, value_enum)]
+    pub network: Option<NetworkKind>,
+
+    /// API endpoint URL (default: from .voyager.toml)
+    #[arg(long, value_parser = Url::parse, conflicts_with = "network")]
+    pub url: Option<Url>,
+
+    /// Path to the project (default: current directory)
+    #[arg(long, value_name = "PATH")]
+    pub path: Option<Utf8PathBuf>,
+
+    /// Run the scheduled task once now and exit
+    #[arg(long, default_value_t = false)]
+    pub once: bool,
+
+    /// Serve Prometheus metrics at http://<ADDR>/metrics while the daemon runs (e.g. 127.0.0.1:9464)
+    #[arg(long, value_name = "ADDR")]
+    pub metrics_addr: Option<SocketAddr>,
+}
+
+#[cfg(feature = "dev-tools")]
+#[derive(clap::Args)]
+pub struct DevArgs {
+    #[command(subcommand)]
+    pub command: DevCommands,
+}
+
+#[cfg(feature = "dev-tools")]
+#[derive(clap::Subcommand)]
+pub enum DevCommands {
+    /// Create fake verification jobs on the dev network or a local mock server
     ///
-    /// # Errors
+    /// Examples:
+    ///   # Five jobs on the dev network
+    ///   voyager dev seed
     ///
-    /// Returns an error if required fields are missing or invalid
-    pub fn validate(&self) -> Result<(), String> {
-        // Check if URL is still the placeholder (means no network, no url, and no config)
-        if self.network_url.url.as_str() == "https://placeholder.invalid/" {
-            return Err(
-                "API URL is required. Provide --network, --url, or set 'network' or 'url' in .voyager.toml".to_string()
-            );
-        }
+    ///   # Jobs on a local mock server that keeps running until Ctrl-C
+    ///   voyager dev seed --mock --count 3
+    Seed(SeedArgs),
+}
 
-        Ok(())
-    }
+#[cfg(feature = "dev-tools")]
+#[derive(clap::Args)]
+pub struct SeedArgs {
+    /// API endpoint to seed (default: the dev network); mainnet and Sepolia are refused
+    #[arg(long, value_parser = Url::parse, conflicts_with = "mock")]
+    pub url: Option<Url>,
+
+    /// Seed a local mock server and keep it running instead
+    #[arg(long, default_value_t = false)]
+    pub mock: bool,
+
+    /// Number of jobs to create
+    #[arg(long, default_value_t = 5)]
+    pub count: usize,
+
+    /// Also record the jobs in local verification history
+    #[arg(long, default_value_t = false, conflicts_with = "mock")]
+    pub history: bool,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DepsFormat {
+    /// Human-readable list grouped by source
+    Text,
+
+    /// Graphviz DOT graph
+    Dot,
+
+    /// JSON for programmatic parsing
+    Json,
 }
 
 #[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
@@ -635,9 +1813,36 @@ pub enum OutputFormat {
 
     /// Table format (primarily for batch operations)
     Table,
+
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Timezone {
+    /// The system's local time zone
+    #[default]
+    Local,
+
+    /// Coordinated Universal Time
+    Utc,
 }
 
-#[derive(clap::ValueEnum, Clone)]
+/// What to do when a class is submitted again while its last job is pending
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DuplicatePolicy {
+    /// Warn and submit anyway
+    #[default]
+    Warn,
+
+    /// Refuse to submit
+    Abort,
+
+    /// Submit without looking for pending jobs
+    Allow,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug, PartialEq, Eq)]
 pub enum NetworkKind {
     /// Target the Mainnet
     Mainnet,
@@ -649,15 +1854,156 @@ pub enum NetworkKind {
     Dev,
 }
 
-#[derive(Clone)]
+/// Where the API URL of a command comes from
+///
+/// The command line takes precedence over .voyager.toml: `--url` comes first,
+/// then `--network`, then `url` and last `network` in .voyager.toml. A
+/// network's API is thus [`UrlSource::NetworkDefault`] whether it ranks above
+/// or below [`UrlSource::Config`]. `--endpoint` replaces the URL altogether
+/// and counts as configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlSource {
+    /// `--url`
+    CliFlag,
+    /// API of the network given with `--network` or `network` in .voyager.toml
+    NetworkDefault,
+    /// `url` in .voyager.toml, or the `[endpoints.<name>]` entry of `--endpoint`
+    Config,
+    /// Nothing selected an API
+    Unset,
+}
+
+impl Display for UrlSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::CliFlag => "--url",
+            Self::NetworkDefault => "network default",
+            Self::Config => ".voyager.toml",
+            Self::Unset => "unset",
+        })
+    }
+}
+
+/// Verification API a command talks to, and where it was selected
+///
+/// Parsed from `--url` and `--network`, then completed from the configuration
+/// with [`Network::merge_with_config`]. No URL is made up when nothing selects
+/// one: [`Network::url`] fails instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Network {
-    /// API endpoint URL
-    pub url: Url,
+    url: Option<Url>,
+    source: UrlSource,
+}
+
+impl Network {
+    /// The API at `url`, selected by `source`
+    #[must_use]
+    pub const fn new(url: Url, source: UrlSource) -> Self {
+        Self {
+            url: Some(url),
+            source,
+        }
+    }
+
+    /// No API selected yet
+    #[must_use]
+    pub const fn unset() -> Self {
+        Self {
+            url: None,
+            source: UrlSource::Unset,
+        }
+    }
+
+    /// The Voyager API of `network`
+    #[must_use]
+    pub fn for_network(network: &NetworkKind) -> Self {
+        Url::parse(voyager::api_url(network)).map_or_else(
+            |_| Self::unset(),
+            |url| Self::new(url, UrlSource::NetworkDefault),
+        )
+    }
+
+    /// The API URL
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::ApiUrlUnset`] if neither the command line nor the
+    /// configuration selected an API.
+    pub fn url(&self) -> Result<&Url, crate::utils::errors::CliError> {
+        self.url
+            .as_ref()
+            .ok_or(crate::utils::errors::CliError::ApiUrlUnset)
+    }
+
+    /// Where the URL was selected
+    #[must_use]
+    pub const fn source(&self) -> UrlSource {
+        self.source
+    }
+
+    /// Whether an API is selected
+    #[must_use]
+    pub const fn is_set(&self) -> bool {
+        self.url.is_some()
+    }
+
+    /// Select the API from the configuration if the command line did not
+    ///
+    /// `network` is the network after merging, so `network` in .voyager.toml
+    /// selects its API unless `url` is configured too.
+    pub fn merge_with_config(
+        &mut self,
+        network: Option<&NetworkKind>,
+        config: &super::config::Config,
+    ) {
+        if self.is_set() {
+            return;
+        }
+        let configured = config.voyager.url.as_deref().and_then(|url| {
+            Url::parse(url)
+                .map_err(|e| warn!("Ignoring invalid url '{url}' in .voyager.toml: {e}"))
+                .ok()
+        });
+        if let Some(url) = configured {
+            *self = Self::new(url, UrlSource::Config);
+        } else if let Some(network) = network {
+            *self = Self::for_network(network);
+        }
+        if let Ok(url) = self.url() {
+            debug!("Using API {url} ({})", self.source);
+        }
+    }
+
+    /// Check that an API is selected
+    ///
+    /// # Errors
+    ///
+    /// Returns the message to print if none is.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.is_set() {
+            Ok(())
+        } else {
+            Err(
+                "API URL is required. Provide --network, --url, or set 'network' or 'url' in .voyager.toml".to_string()
+            )
+        }
+    }
+
+    fn from_matches(matches: &clap::ArgMatches) -> Self {
+        if let Some(url) = matches.get_one::<Url>("url") {
+            return Self::new(url.clone(), UrlSource::CliFlag);
+        }
+        matches
+            .try_get_one::<NetworkKind>("network")
+            .ok()
+            .flatten()
+            .map_or_else(Self::unset, Self::for_network)
+    }
 }
 
 impl clap::FromArgMatches for Network {
     fn from_arg_matches(matches: &clap::ArgMatches) -> Result<Self, clap::Error> {
-        // Check if wizard mode is enabled (only present on verify command)
+        // In wizard mode the API is asked for later
         let wizard_mode = matches
             .try_get_one::<bool>("wizard")
             .ok()
@@ -666,22 +2012,9 @@ impl clap::FromArgMatches for Network {
             .unwrap_or(false);
 
         if wizard_mode {
-            // In wizard mode, provide a placeholder URL that will be replaced by the wizard
-            // SAFETY: Hardcoded URL is guaranteed to be valid
-            #[allow(clippy::unwrap_used)]
-            Ok(Self {
-                url: Url::parse("https://api.voyager.online/beta").unwrap(),
-            })
+            Ok(Self::unset())
         } else {
-            // Get URL from CLI args if provided, otherwise use a placeholder
-            // that will be replaced by config file or cause a validation error later
-            let url = matches.get_one::<Url>("url").cloned().unwrap_or_else(|| {
-                // SAFETY: Hardcoded URL is guaranteed to be valid
-                #[allow(clippy::unwrap_used)]
-                Url::parse("https://placeholder.invalid").unwrap()
-            });
-
-            Ok(Self { url })
+            Ok(Self::from_matches(matches))
         }
     }
 
@@ -698,27 +2031,16 @@ impl clap::FromArgMatches for Network {
         &mut self,
         matches: &mut clap::ArgMatches,
     ) -> Result<(), clap::Error> {
-        // Check if wizard mode is enabled (only present on verify command)
-        let wizard_mode = matches
-            .try_get_one::<bool>("wizard")
-            .ok()
-            .flatten()
-            .copied()
-            .unwrap_or(false);
-
-        if !wizard_mode {
-            // Get URL from CLI args if provided
-            if let Some(url) = matches.get_one::<Url>("url") {
-                self.url = url.clone();
-            }
-            // If not provided, keep existing URL (may be from config or placeholder)
+        let updated = Self::from_matches(matches);
+        if updated.is_set() {
+            *self = updated;
         }
-        // In wizard mode, keep the placeholder URL (will be replaced by wizard)
         Ok(())
     }
 }
 
-// Can't derive the default value logic, hence hand rolled instance
+// `--url` is the only argument; --network is read from the command it is
+// flattened into
 impl clap::Args for Network {
     fn augment_args(cmd: clap::Command) -> clap::Command {
         cmd.arg(
@@ -726,44 +2048,75 @@ impl clap::Args for Network {
                 .long("url")
                 .help("API endpoint URL (can also be set in .voyager.toml)")
                 .value_hint(clap::ValueHint::Url)
-                .value_parser(Url::parse)
-                .default_value_ifs([
-                    ("network", "mainnet", "https://api.voyager.online/beta"),
-                    (
-                        "network",
-                        "sepolia",
-                        "https://sepolia-api.voyager.online/beta",
-                    ),
-                    ("network", "dev", "https://dev-api.voyager.online/beta"),
-                ]),
+                .value_parser(Url::parse),
         )
     }
 
     fn augment_args_for_update(cmd: clap::Command) -> clap::Command {
-        cmd.arg(
-            clap::Arg::new("url")
-                .long("url")
-                .help("API endpoint URL (can also be set in .voyager.toml)")
-                .value_hint(clap::ValueHint::Url)
-                .value_parser(Url::parse)
-                .default_value_ifs([
-                    ("network", "mainnet", "https://api.voyager.online/beta"),
-                    (
-                        "network",
-                        "sepolia",
-                        "https://sepolia-api.voyager.online/beta",
-                    ),
-                    ("network", "dev", "https://dev-api.voyager.online/beta"),
-                ]),
-        )
+        Self::augment_args(cmd)
     }
 }
 
 impl VerifyArgs {
-    /// Detect if batch mode should be used based on config
+    /// Take the URL, network, API key, RPC and explorer of `endpoint`
+    ///
+    /// An RPC URL given with `--rpc-url` (or `STARKNET_RPC_URL`) and an API
+    /// key given with `--api-key` are kept.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: super::config::Endpoint) -> Self {
+        self.network_url = Network::new(endpoint.url, UrlSource::Config);
+        self.network = endpoint.network;
+        self.api_key = self.api_key.or(endpoint.api_key);
+        self.rpc_url = self.rpc_url.or(endpoint.rpc_url);
+        self.explorer_url = endpoint.explorer_url;
+        self
+    }
+
+    /// Explorer that verified classes are linked on, if known
+    #[must_use]
+    pub fn explorer(&self) -> Option<Url> {
+        self.explorer_url.clone().or_else(|| {
+            self.network_url
+                .url()
+                .ok()
+                .and_then(voyager::network_for_api_url)
+                .or_else(|| self.network.clone())
+                .and_then(|network| Url::parse(voyager::explorer_url(&network)).ok())
+        })
+    }
+
+    /// Detect if batch mode should be used based on config, `--resume`, `--all-deployed`,
+    /// `--from-dojo-manifest` or `--world`
     #[must_use]
     pub fn is_batch_mode(&self, config: &Option<super::config::Config>) -> bool {
-        config.as_ref().is_some_and(|cfg| !cfg.contracts.is_empty())
+        self.resume.is_some()
+            || self.all_deployed
+            || self.from_dojo_manifest.is_some()
+            || self.world.is_some()
+            || config.as_ref().is_some_and(|cfg| !cfg.contracts.is_empty())
+    }
+
+    /// Whether already verified classes should be skipped instead of resubmitted
+    #[must_use]
+    pub const fn should_skip_verified(&self) -> bool {
+        self.skip_if_verified && !self.force
+    }
+
+    /// Polling settings given on the command line
+    #[must_use]
+    pub fn polling(&self) -> PollingConfig {
+        PollingConfig {
+            min_delay: self.poll_interval,
+            timeout: self.poll_timeout,
+            ..PollingConfig::default()
+        }
+    }
+
+    /// Whether questions may be asked on the terminal
+    #[must_use]
+    pub fn is_interactive(&self) -> bool {
+        use std::io::IsTerminal;
+        !self.non_interactive && std::io::stdin().is_terminal()
     }
 
     /// Merge configuration file values with CLI arguments
@@ -775,51 +2128,54 @@ impl VerifyArgs {
             self.network = config.parse_network();
         }
 
-        // Merge license if not provided via CLI
-        if self.license.is_none() {
-            if let Some(ref license_str) = config.voyager.license {
-                self.license = license_value_parser(license_str).ok();
-            }
+        // Merge timezone if not provided via CLI
+        if self.timezone.is_none() {
+            self.timezone = config.parse_timezone();
         }
 
-        // Merge watch flag (only if not explicitly set via CLI)
-        // Note: clap sets default_value_t, so we need to check if it was actually provided
-        // For now, we'll use the config value if it exists
-        if let Some(watch) = config.voyager.watch {
-            if !self.watch {
-                // Only override if CLI value is false (the default)
-                self.watch = watch;
-            }
+        // Merge duplicate submission policy if not provided via CLI
+        if self.on_duplicate.is_none() {
+            self.on_duplicate = config.parse_on_duplicate();
         }
 
-        // Merge test_files flag
-        if let Some(test_files) = config.voyager.test_files {
-            if !self.test_files {
-                self.test_files = test_files;
-            }
+        // Merge prompt timeout if not provided via CLI
+        if self.prompt_timeout.is_none() {
+            self.prompt_timeout = config.parse_prompt_timeout();
         }
 
-        // Merge lock_file flag
-        if let Some(lock_file) = config.voyager.lock_file {
-            if !self.lock_file {
-                self.lock_file = lock_file;
+        // Merge license if not provided via CLI
+        if self.license.is_none() {
+            if let Some(ref license_str) = config.voyager.license {
+                self.license = license_value_parser(license_str).ok();
             }
         }
 
-        // Merge verbose flag
-        if let Some(verbose) = config.voyager.verbose {
-            if !self.verbose {
-                self.verbose = verbose;
-            }
+        // Merge state file if not provided via CLI
+        if self.state.is_none() {
+            self.state.clone_from(&config.voyager.state_file);
         }
-
-        // Merge notify flag
+        // Merge the doc paths eligible with include_docs
+        self.doc_paths.clone_from(&config.voyager.doc_paths);
+
+        // Merge flags: clap defaults them to false, so a config value only
+        // turns on a flag that was not given on the command line
+        let voyager = &config.voyager;
+        merge_flag(&mut self.watch, voyager.watch);
+        merge_flag(&mut self.test_files, voyager.test_files);
+        merge_flag(&mut self.include_docs, voyager.include_docs);
+        merge_flag(&mut self.lock_file, voyager.lock_file);
+        merge_flag(&mut self.verbose, voyager.verbose);
         #[cfg(feature = "notifications")]
-        if let Some(notify) = config.voyager.notify {
-            if !self.notify {
-                self.notify = notify;
-            }
-        }
+        merge_flag(&mut self.notify, voyager.notify);
+        merge_flag(&mut self.require_clean_git, voyager.require_clean_git);
+        merge_flag(
+            &mut self.require_license_headers,
+            voyager.require_license_headers,
+        );
+        merge_flag(&mut self.preflight, voyager.preflight);
+        merge_flag(&mut self.compress, voyager.compress);
+        merge_flag(&mut self.compare_class_hash, voyager.compare_class_hash);
+        merge_flag(&mut self.skip_if_verified, voyager.skip_if_verified);
 
         // Merge package if not provided via CLI
         if self.package.is_none() {
@@ -839,16 +2195,19 @@ impl VerifyArgs {
             }
         }
 
-        // Merge URL if provided in config and not set via CLI or network flag
-        // Check if URL is still the placeholder (means neither --url nor --network was provided)
-        if self.network_url.url.as_str() == "https://placeholder.invalid/" {
-            if let Some(ref url_str) = config.voyager.url {
-                if let Ok(parsed_url) = Url::parse(url_str) {
-                    self.network_url.url = parsed_url;
-                }
-            }
+        // History retention and size limits are only configurable in the config file
+        self.history_retention = config.history.retention();
+        self.size_limits = config.voyager.limits.size_limits();
+
+        // Take the API key from VOYAGER_API_KEY unless an endpoint set one
+        if self.api_key.is_none() {
+            self.api_key.clone_from(&config.voyager.api_key);
         }
 
+        // Select the API from config if neither --url nor --network was given
+        self.network_url
+            .merge_with_config(self.network.as_ref(), config);
+
         self
     }
 
@@ -858,18 +2217,39 @@ impl VerifyArgs {
     ///
     /// Returns an error if required fields are missing or invalid
     pub fn validate(&self) -> Result<(), String> {
-        // Check if URL is still the placeholder (means no network, no url, and no config)
-        if self.network_url.url.as_str() == "https://placeholder.invalid/" {
-            return Err(
-                "API URL is required. Provide --network, --url, or set 'network' or 'url' in .voyager.toml".to_string()
-            );
-        }
+        self.network_url.validate()?;
 
         Ok(())
     }
 }
 
+/// Set `flag` if the config file turns it on
+fn merge_flag(flag: &mut bool, config: Option<bool>) {
+    *flag |= config.unwrap_or(false);
+}
+
 impl StatusArgs {
+    /// Take the URL, network and API key of `endpoint`
+    ///
+    /// An API key given with `--api-key` is kept.
+    #[must_use]
+    pub fn with_endpoint(mut self, endpoint: super::config::Endpoint) -> Self {
+        self.network_url = Network::new(endpoint.url, UrlSource::Config);
+        self.network = endpoint.network;
+        self.api_key = self.api_key.or(endpoint.api_key);
+        self
+    }
+
+    /// Polling settings given on the command line
+    #[must_use]
+    pub fn polling(&self) -> PollingConfig {
+        PollingConfig {
+            min_delay: self.poll_interval,
+            timeout: self.poll_timeout,
+            ..PollingConfig::default()
+        }
+    }
+
     /// Merge configuration file values with CLI arguments
     /// CLI arguments take precedence over config file values
     #[must_use]
@@ -879,6 +2259,11 @@ impl StatusArgs {
             self.network = config.parse_network();
         }
 
+        // Merge timezone if not provided via CLI
+        if self.timezone.is_none() {
+            self.timezone = config.parse_timezone();
+        }
+
         // Merge verbose flag
         if let Some(verbose) = config.voyager.verbose {
             if !self.verbose {
@@ -893,22 +2278,22 @@ impl StatusArgs {
                 match format_str.to_lowercase().as_str() {
                     "json" => self.format = OutputFormat::Json,
                     "table" => self.format = OutputFormat::Table,
+                    "csv" => self.format = OutputFormat::Csv,
                     "text" => self.format = OutputFormat::Text,
                     _ => {} // Keep default if invalid format in config
                 }
             }
         }
 
-        // Merge URL if provided in config and not set via CLI or network flag
-        // Check if URL is still the placeholder (means neither --url nor --network was provided)
-        if self.network_url.url.as_str() == "https://placeholder.invalid/" {
-            if let Some(ref url_str) = config.voyager.url {
-                if let Ok(parsed_url) = Url::parse(url_str) {
-                    self.network_url.url = parsed_url;
-                }
-            }
+        // Take the API key from VOYAGER_API_KEY unless an endpoint set one
+        if self.api_key.is_none() {
+            self.api_key.clone_from(&config.voyager.api_key);
         }
 
+        // Select the API from config if neither --url nor --network was given
+        self.network_url
+            .merge_with_config(self.network.as_ref(), config);
+
         self
     }
 
@@ -918,21 +2303,39 @@ impl StatusArgs {
     ///
     /// Returns an error if required fields are missing or invalid
     pub fn validate(&self) -> Result<(), String> {
-        // Check if URL is still the placeholder (means no network, no url, and no config)
-        if self.network_url.url.as_str() == "https://placeholder.invalid/" {
-            return Err(
-                "API URL is required. Provide --network, --url, or set 'network' or 'url' in .voyager.toml".to_string()
-            );
-        }
+        self.network_url.validate()?;
 
         Ok(())
     }
 }
 
+#[derive(clap::Args)]
+pub struct ContractsArgs {
+    /// Only show this contract
+    #[arg(value_name = "NAME")]
+    pub name: Option<String>,
+
+    /// Filter by network (mainnet, sepolia, dev)
+    #[arg(long)]
+    pub network: Option<String>,
+
+    /// Output results as JSON
+    #[arg(long, short, default_value_t = false)]
+    pub json: bool,
+
+    /// Time zone for displayed timestamps (default: local)
+    #[arg(long, value_enum)]
+    pub timezone: Option<Timezone>,
+}
+
 #[derive(clap::Args)]
 pub struct HistoryArgs {
     #[command(subcommand)]
     pub command: HistoryCommands,
+
+    /// Time zone for displayed timestamps (default: local)
+    #[arg(long, value_enum, global = true)]
+    pub timezone: Option<Timezone>,
 }
 
 #[derive(clap::Subcommand)]
@@ -947,9 +2350,17 @@ pub enum HistoryCommands {
         #[arg(long)]
         network: Option<String>,
 
+        /// Only show jobs submitted by the invocation with this run id
+        #[arg(long)]
+        run_id: Option<String>,
+
         /// Limit the number of results
         #[arg(long, default_value = "20")]
         limit: usize,
+
+        /// Output format: colored text, or json, table or csv for scripts
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
     },
 
     /// Get detailed status of a verification job from history
@@ -972,6 +2383,10 @@ pub enum HistoryCommands {
         /// Show detailed error messages from the remote compiler
         #[arg(long, short = 'v', default_value_t = false)]
         verbose: bool,
+
+        /// Output format: colored text, or json, table or csv for scripts
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
     },
 
     /// Re-check status of all pending verification jobs
@@ -986,6 +2401,14 @@ pub enum HistoryCommands {
         /// Show detailed error messages from the remote compiler
         #[arg(long, short = 'v', default_value_t = false)]
         verbose: bool,
+
+        /// Mark jobs the API no longer knows (expired or purged) as Expired
+        #[arg(long, default_value_t = false)]
+        fix_orphans: bool,
+
+        /// Output format: colored text, or json, table or csv for scripts
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
     },
 
     /// Clean old verification records from history
@@ -997,8 +2420,48 @@ pub enum HistoryCommands {
         /// Delete all records (use with caution)
         #[arg(long, default_value_t = false)]
         all: bool,
+
+        /// Cancel the --all confirmation if unanswered after this long (e.g. 30s)
+        #[arg(long, value_name = "DURATION", value_parser = super::prompt::parse_timeout)]
+        prompt_timeout: Option<Duration>,
+    },
+
+    /// Check the history database for corruption and unreadable records
+    ///
+    /// Records whose timestamps or status cannot be read back are reported
+    /// with how --fix resolves them: values in another common format are
+    /// rewritten, unreadable optional timestamps cleared, and records without
+    /// a readable submission time or status moved to a quarantine table.
+    Doctor {
+        /// Repair the reported records after confirmation
+        #[arg(long, default_value_t = false)]
+        fix: bool,
+
+        /// Repair without asking for confirmation
+        #[arg(long, short = 'y', default_value_t = false, requires = "fix")]
+        yes: bool,
+
+        /// Cancel the --fix confirmation if unanswered after this long (e.g. 30s)
+        #[arg(long, value_name = "DURATION", value_parser = super::prompt::parse_timeout)]
+        prompt_timeout: Option<Duration>,
     },
 
     /// Show verification history statistics
-    Stats,
+    Stats {
+        /// Chart the size of each submitted payload over time instead of job counts
+        #[arg(long, default_value_t = false)]
+        payloads: bool,
+
+        /// Only chart the payloads of this contract
+        #[arg(long, value_name = "NAME", requires = "payloads")]
+        contract: Option<String>,
+
+        /// Number of most recent submissions to chart
+        #[arg(long, default_value = "30", requires = "payloads")]
+        limit: usize,
+
+        /// Output format: colored text, or json, table or csv for scripts
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
 }
@@ -0,0 +1,162 @@
+use crate::{
+    cli::{args::AnalyzeArgs, config::Config},
+    core::{
+        analyze::{format_size, PayloadAnalysis},
+        project::ProjectType,
+        verification::{add_docs, add_extra_sources, collect_sources},
+    },
+    filesystem::{
+        collector::build_file_map,
+        resolver::{biggest_common_prefix, dependency_closure},
+    },
+    utils::errors::CliError,
+};
+use anyhow::Result;
+use colored::Colorize;
+
+/// Number of files listed as the largest
+const LARGEST_FILES: usize = 10;
+
+/// Handles the analyze command for reporting the size of a submission
+///
+/// # Errors
+///
+/// Returns an error if the package selection is invalid or the project files
+/// cannot be collected or read.
+pub fn handle_analyze_command(args: AnalyzeArgs, config: Option<&Config>) -> Result<()> {
+    // Merge config with CLI args (CLI args take precedence)
+    let args = if let Some(cfg) = config {
+        args.merge_with_config(cfg)
+    } else {
+        args
+    };
+
+    let metadata = args.path.load()?;
+    // Dojo projects are verified with their test files, see `voyager verify`
+    let dojo = matches!(
+        args.path.detect_project_type(),
+        Ok(ProjectType::Dojo | ProjectType::Auto)
+    );
+    let test_files = args.test_files || dojo;
+
+    let (packages, mut sources) = collect_sources(
+        metadata,
+        args.package.as_deref(),
+        test_files,
+        args.size_limits.max_payload_size,
+    )?;
+    add_extra_sources(&mut sources, &args.extra_sources, args.path.root_dir()?)?;
+    if args.include_docs {
+        add_docs(&mut sources, metadata, &packages, &args.doc_paths);
+    }
+    let prefix = biggest_common_prefix(&sources, args.path.root_dir()?);
+    let files = build_file_map(
+        &sources,
+        &prefix,
+        metadata,
+        args.lock_file,
+        &args.size_limits,
+    )?;
+    let analysis = PayloadAnalysis::of_files(&files).map_err(|e| CliError::InternalError {
+        message: format!("failed to read project files: {e}"),
+    })?;
+
+    let unneeded_packages: Vec<(String, String)> = args
+        .package
+        .as_deref()
+        .map(|target| {
+            let required = dependency_closure(&packages, target);
+            packages
+                .iter()
+                .filter(|package| !required.iter().any(|r| r.name == package.name))
+                .filter_map(|package| {
+                    let dir = package.root.strip_prefix(&prefix).ok()?;
+                    Some((package.name.clone(), dir.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    display_analysis(
+        &analysis,
+        args.size_limits.max_payload_size,
+        args.test_files && !dojo,
+        &unneeded_packages,
+    );
+    Ok(())
+}
+
+fn display_analysis(
+    analysis: &PayloadAnalysis,
+    max_payload_size: u64,
+    test_files: bool,
+    unneeded_packages: &[(String, String)],
+) {
+    println!("\n{}", "Payload analysis".bold().underline());
+    println!(
+        "\n{} file(s), {} ({} limit)",
+        analysis.files.len(),
+        format_size(analysis.total_size),
+        format_size(max_payload_size)
+    );
+    if analysis.total_size > max_payload_size {
+        println!(
+            "{} The sources exceed the payload limit; the API will reject them.",
+            "!".yellow().bold()
+        );
+    }
+    let percent = (analysis.compressed_size * 100)
+        .checked_div(analysis.total_size)
+        .unwrap_or(0);
+    println!(
+        "Compressed (deflate): {} ({percent}% of the original size)",
+        format_size(analysis.compressed_size)
+    );
+
+    println!("\n{}", "By directory".bold());
+    for directory in analysis.directories() {
+        let depth = directory.path.matches('/').count() + usize::from(!directory.path.is_empty());
+        let name = if directory.path.is_empty() {
+            ".".to_string()
+        } else {
+            format!("{}/", directory.path.rsplit('/').next().unwrap_or_default())
+        };
+        println!(
+            "  {:>10}  {:>4} file(s)  {}{name}",
+            format_size(directory.size),
+            directory.files,
+            "  ".repeat(depth)
+        );
+    }
+
+    println!("\n{}", format!("Largest {LARGEST_FILES} files").bold());
+    for file in analysis.largest(LARGEST_FILES) {
+        println!(
+            "  {:>10}  {}  {}",
+            format_size(file.size),
+            file.name,
+            format!("({} compressed)", format_size(file.compressed_size)).bright_black()
+        );
+    }
+
+    let exclusions = analysis.exclusions(test_files, unneeded_packages);
+    println!("\n{}", "Suggested exclusions".bold());
+    if exclusions.is_empty() {
+        println!(
+            "  {} Every file is needed to compile the contract.",
+            "✓".green().bold()
+        );
+    }
+    for exclusion in exclusions {
+        println!(
+            "  {} {} in {} file(s): {}",
+            "-".yellow().bold(),
+            format_size(exclusion.size),
+            exclusion.files.len(),
+            exclusion.hint
+        );
+        for name in &exclusion.files {
+            println!("      {}", name.bright_black());
+        }
+    }
+}
@@ -1,14 +1,30 @@
 use crate::{
-    api::{ApiClient, ApiClientError, ClassVerificationInfo},
-    cli::{args::CheckArgs, config::Config},
-    output::status::format_timestamp,
-    utils::errors::CliError,
+    api::{ApiClient, ClassVerification},
+    cli::{
+        args::CheckArgs,
+        config::{self, Config},
+    },
+    core::verification::ensure_declared,
+    output::status::{format_duration, format_timestamp, ClassCheckOutput},
+    storage::cache::{CheckCache, DEFAULT_CHECK_CACHE_TTL_SECS},
+    utils::{errors::CliError, voyager},
 };
 use anyhow::Result;
 use colored::Colorize;
+use log::debug;
+use std::time::Duration;
+
+/// Exit code of a class that is declared but not verified
+pub const EXIT_NOT_VERIFIED: i32 = 3;
+
+/// Exit code of a class that is not declared on the network
+pub const EXIT_NOT_DECLARED: i32 = 4;
 
 /// Handles the check command for verifying if a class is already verified
 ///
+/// Exits with 0 if the class is verified, [`EXIT_NOT_VERIFIED`] if it is
+/// declared but not verified, and [`EXIT_NOT_DECLARED`] if it is not declared.
+///
 /// # Errors
 ///
 /// Returns an error if:
@@ -16,6 +32,11 @@ use colored::Colorize;
 /// - API client creation fails
 /// - Check request fails
 pub fn handle_check_command(args: CheckArgs, config: Option<&Config>) -> Result<()> {
+    let args = match config::selected_endpoint(args.endpoint.as_deref(), config)? {
+        Some(endpoint) => args.with_endpoint(endpoint),
+        None => args,
+    };
+
     // Merge config with CLI args (CLI args take precedence)
     let args = if let Some(cfg) = config {
         args.merge_with_config(cfg)
@@ -28,70 +49,135 @@ pub fn handle_check_command(args: CheckArgs, config: Option<&Config>) -> Result<
         eprintln!("Error: {err}");
         std::process::exit(1);
     }
+    let api_url = args.network_url.url()?;
 
-    let api_client = ApiClient::new(args.network_url.url.clone())?;
+    // Confirm the class exists on-chain independently of the explorer
+    if let Some(rpc_url) = &args.rpc_url {
+        let network = args
+            .network
+            .clone()
+            .or_else(|| voyager::network_for_api_url(api_url));
+        ensure_declared(rpc_url, &args.class_hash, network.as_ref())?;
+    }
 
-    match api_client.check_class_verification(&args.class_hash) {
-        Ok(info) => {
-            display_verification_info(&args, &info);
-            Ok(())
-        }
-        Err(ApiClientError::ClassNotFound(hash)) => {
-            println!(
-                "\n{} Class {} {}",
-                "!".yellow().bold(),
-                hash.cyan(),
-                "not found on-chain".yellow()
-            );
-            println!();
-            std::process::exit(1);
-        }
+    let cache = if args.no_cache {
+        None
+    } else {
+        CheckCache::open()
+    };
+    let ttl = Duration::from_secs(args.cache_ttl.unwrap_or(DEFAULT_CHECK_CACHE_TTL_SECS));
+
+    if let Some(cached) = cache
+        .as_ref()
+        .and_then(|cache| cache.get(api_url, &args.class_hash, ttl))
+    {
+        debug!("Using cached check result for {}", args.class_hash);
+        let age = cached.age_secs();
+        display_verification(&args, &ClassVerification::Verified(cached.info), Some(age));
+        return Ok(());
+    }
+
+    let api_client = ApiClient::new(api_url.clone())?;
+    let api_client = match args.api_key.as_deref() {
+        Some(api_key) => api_client.with_api_key(api_key),
+        None => api_client,
+    };
+
+    let verification = match api_client.check_class_verification(&args.class_hash) {
+        Ok(verification) => verification,
         Err(e) => {
             if args.verbose {
                 eprintln!("Error details: {e:?}");
             }
-            Err(CliError::from(e).into())
+            return Err(CliError::from(e).into());
         }
+    };
+    if let (Some(cache), Some(info)) = (&cache, verification.info()) {
+        if let Err(e) = cache.put(api_url, &args.class_hash, info) {
+            debug!("Failed to cache check result: {e}");
+        }
+    }
+    display_verification(&args, &verification, None);
+
+    match verification {
+        ClassVerification::Verified(_) => Ok(()),
+        ClassVerification::NotVerified => std::process::exit(EXIT_NOT_VERIFIED),
+        ClassVerification::NotDeclared => std::process::exit(EXIT_NOT_DECLARED),
     }
 }
 
-fn display_verification_info(args: &CheckArgs, info: &ClassVerificationInfo) {
+/// Displays the check result; `cache_age` is set when it was served from the local cache
+fn display_verification(
+    args: &CheckArgs,
+    verification: &ClassVerification,
+    cache_age: Option<u64>,
+) {
     if args.json {
-        // Output as JSON
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&info).unwrap_or_else(|_| format!("{info:?}"))
-        );
-    } else if info.verified {
-        println!(
-            "\n{} Class {} is {}",
-            "✓".green().bold(),
-            args.class_hash.to_string().cyan(),
-            "verified".green().bold()
-        );
-        if let Some(ref name) = info.name {
-            println!("  Name: {name}");
-        }
-        if let Some(ref version) = info.version {
-            println!("  Version: {version}");
-        }
-        if let Some(ref license) = info.license {
-            println!("  License: {license}");
-        }
-        if let Some(ref contract_file) = info.contract_file {
-            println!("  Contract file: {contract_file}");
+        let mut output = ClassCheckOutput::new(verification, &args.class_hash);
+        output.cache_age_secs = cache_age;
+        println!("{}", output.to_json());
+        return;
+    }
+
+    let info = match verification {
+        ClassVerification::Verified(info) => info,
+        ClassVerification::NotVerified => {
+            println!(
+                "\n{} Class {} is {}",
+                "✗".red().bold(),
+                args.class_hash.to_string().cyan(),
+                "not verified".yellow().bold()
+            );
+            println!("  Submit it with `voyager verify`");
+            println!();
+            return;
         }
-        if let Some(ts) = info.verified_timestamp {
-            println!("  Verified: {}", format_timestamp(ts));
+        ClassVerification::NotDeclared => {
+            println!(
+                "\n{} Class {} is {}",
+                "!".yellow().bold(),
+                args.class_hash.to_string().cyan(),
+                "not declared on this network".yellow().bold()
+            );
+            println!("  Declare it before verifying");
+            println!();
+            return;
         }
-        println!();
-    } else {
+    };
+
+    println!(
+        "\n{} Class {} is {}",
+        "✓".green().bold(),
+        args.class_hash.to_string().cyan(),
+        "verified".green().bold()
+    );
+    if let Some(ref name) = info.name {
+        println!("  Name: {name}");
+    }
+    if let Some(ref version) = info.version {
+        println!("  Version: {version}");
+    }
+    if let Some(ref license) = info.license {
+        println!("  License: {license}");
+    }
+    if let Some(ref contract_file) = info.contract_file {
+        println!("  Contract file: {contract_file}");
+    }
+    if let Some(ts) = info.verified_timestamp {
+        println!(
+            "  Verified: {}",
+            format_timestamp(ts, args.timezone.unwrap_or_default())
+        );
+    }
+    if let Some(age) = cache_age {
         println!(
-            "\n{} Class {} is {}",
-            "✗".red().bold(),
-            args.class_hash.to_string().cyan(),
-            "not verified".yellow().bold()
+            "  {}",
+            format!(
+                "Cached result from {} ago (use --no-cache to refresh)",
+                format_duration(age)
+            )
+            .bright_black()
         );
-        println!();
     }
+    println!();
 }
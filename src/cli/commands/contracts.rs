@@ -0,0 +1,79 @@
+use crate::{
+    cli::{
+        args::{ContractsArgs, Timezone},
+        config::Config,
+    },
+    output::status::format_datetime,
+    storage::history::{ContractEntry, HistoryDb},
+};
+use anyhow::Result;
+use colored::Colorize;
+
+/// Handles the contracts command for listing the latest verified class per contract
+///
+/// # Errors
+///
+/// Returns an error if the history database cannot be opened or read.
+pub fn handle_contracts_command(args: &ContractsArgs, config: Option<&Config>) -> Result<()> {
+    let db = HistoryDb::open_read_only().map_err(|e| {
+        eprintln!("Failed to open history database: {e}");
+        e
+    })?;
+
+    let mut entries = db.verified_contracts(args.network.as_deref())?;
+    if let Some(name) = &args.name {
+        entries.retain(|entry| &entry.contract_name == name);
+    }
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).unwrap_or_else(|_| format!("{entries:?}"))
+        );
+        return Ok(());
+    }
+
+    let timezone = args
+        .timezone
+        .or_else(|| config.and_then(Config::parse_timezone))
+        .unwrap_or_default();
+    display_contracts(&entries, timezone);
+    Ok(())
+}
+
+fn display_contracts(entries: &[ContractEntry], timezone: Timezone) {
+    if entries.is_empty() {
+        println!("\nNo verified contracts found in history.");
+        println!("Contracts appear here once a verification with 'voyager verify' succeeds.\n");
+        return;
+    }
+
+    println!("\n{}", "Verified Contracts".bold().underline());
+
+    let mut current = None;
+    for entry in entries {
+        if current != Some(&entry.contract_name) {
+            println!("\n{}", entry.contract_name.bold());
+            current = Some(&entry.contract_name);
+        }
+        println!("  {}: {}", entry.network, entry.class_hash.cyan());
+        let mut details = vec![format!(
+            "verified {}",
+            format_datetime(entry.verified_at, timezone)
+        )];
+        if let Some(ref package) = entry.package_name {
+            details.push(format!("package {package}"));
+        }
+        if let Some(ref commit) = entry.commit_hash {
+            details.push(format!("commit {}", &commit[..commit.len().min(7)]));
+        }
+        if entry.verified_classes > 1 {
+            details.push(format!(
+                "{} classes verified under this name",
+                entry.verified_classes
+            ));
+        }
+        println!("    {}", details.join(", ").bright_black());
+    }
+    println!();
+}
@@ -0,0 +1,276 @@
+//! Scheduled re-verification
+//!
+//! `voyager daemon` re-verifies the `[[contracts]]` of `.voyager.toml` at the
+//! times matched by the `cron` expression under `[schedule]`. Each run reports
+//! contracts that local history shows as verified but the API no longer does,
+//! then runs `verify --skip-if-verified --watch`, which re-submits every
+//! contract that is not verified and records the jobs in history. With
+//! `--metrics-addr`, one Prometheus endpoint serves the metrics of every run.
+
+use crate::{
+    api::{ApiClient, VerifyJobStatus},
+    cli::{
+        args::{
+            deferred_project_value_parser, DaemonArgs, Network, OutputFormat, UrlSource, VerifyArgs,
+        },
+        commands::verify,
+        config::{self, Config, ContractConfig},
+    },
+    core::{class_hash::ClassHash, project::ProjectType, verification::already_verified},
+    filesystem::collector::SizeLimits,
+    output::{
+        metrics::{Metrics, MetricsServer},
+        notifications,
+    },
+    storage::history::{HistoryDb, RetentionPolicy},
+    utils::voyager,
+};
+use anyhow::Result;
+use chrono::Utc;
+use colored::Colorize;
+use cron::Schedule;
+use log::warn;
+use std::{str::FromStr, sync::Arc, thread};
+
+/// Handles the daemon command
+///
+/// # Errors
+///
+/// Returns an error if a run started with `--once` fails. Failed scheduled
+/// runs are reported and the daemon waits for the next one.
+pub fn handle_daemon_command(args: &DaemonArgs, config: Option<&Config>) -> Result<()> {
+    let Some(config) = config.filter(|config| !config.contracts.is_empty()) else {
+        eprintln!("Error: voyager daemon re-verifies the [[contracts]] of .voyager.toml, but none are defined");
+        std::process::exit(1);
+    };
+
+    // One endpoint serves the metrics of every run
+    let metrics = match args.metrics_addr {
+        Some(addr) => {
+            let metrics = Arc::new(Metrics::default());
+            let server = MetricsServer::start(addr, Arc::clone(&metrics))?;
+            println!("Serving metrics at http://{}/metrics", server.addr());
+            Some((server, metrics))
+        }
+        None => None,
+    };
+    let metrics = metrics.as_ref().map(|(_, metrics)| metrics);
+
+    if args.once {
+        return run(args, config, metrics);
+    }
+
+    let Some(expression) = config.schedule.cron.as_deref() else {
+        eprintln!("Error: No schedule: set cron under [schedule] in .voyager.toml, or pass --once");
+        std::process::exit(1);
+    };
+    let schedule = match parse_cron(expression) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            eprintln!("Error: Invalid cron expression '{expression}' under [schedule]: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    while let Some(next) = schedule.upcoming(Utc).next() {
+        println!(
+            "Next re-verification at {}",
+            next.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+        if let Ok(wait) = (next - Utc::now()).to_std() {
+            thread::sleep(wait);
+        }
+        if let Err(e) = run(args, config, metrics) {
+            eprintln!("Error: Scheduled re-verification failed: {e:?}");
+        }
+    }
+    Ok(())
+}
+
+/// Parse a cron expression of 5 fields, or 6 to 7 with seconds and years
+///
+/// # Errors
+///
+/// Returns an error if the expression is not valid.
+pub fn parse_cron(expression: &str) -> Result<Schedule, cron::error::Error> {
+    let expression = expression.trim();
+    if expression.split_whitespace().count() == 5 {
+        Schedule::from_str(&format!("0 {expression}"))
+    } else {
+        Schedule::from_str(expression)
+    }
+}
+
+/// Report regressions, then re-submit what is not verified, counting in
+/// `metrics` if given
+fn run(args: &DaemonArgs, config: &Config, metrics: Option<&Arc<Metrics>>) -> Result<()> {
+    println!(
+        "\n{} Re-verifying {} contract(s) at {}",
+        "▶".cyan(),
+        config.contracts.len(),
+        Utc::now().format("%Y-%m-%d %H:%M:%S UTC")
+    );
+
+    let verify_args = verify_args(args)?;
+    let merged = verify_args.clone().merge_with_config(config);
+    let api_url = merged.network_url.url()?;
+    let api_client =
+        config::with_configured_polling(ApiClient::new(api_url.clone())?, Some(config));
+    let api_client = match merged.api_key.as_deref() {
+        Some(api_key) => api_client.with_api_key(api_key),
+        None => api_client,
+    };
+    let network = merged
+        .network
+        .clone()
+        .or_else(|| voyager::network_for_api_url(api_url));
+
+    for contract in regressions(
+        &api_client,
+        &config.contracts,
+        network.as_ref().map(voyager::network_name),
+    ) {
+        eprintln!(
+            "{} {} ({}) was verified before but no longer is",
+            "⚠️ ".yellow(),
+            contract.contract_name.bold(),
+            contract.class_hash
+        );
+        if config.schedule.notify_regressions.unwrap_or(false) {
+            if let Err(e) = notifications::send_regression_notification(
+                &contract.contract_name,
+                &contract.class_hash,
+            ) {
+                eprintln!("Warning: Failed to send desktop notification: {e}");
+            }
+        }
+    }
+
+    verify::verify_reporting_to(verify_args, Some(config), metrics)
+}
+
+/// Arguments of `voyager verify --skip-if-verified --watch` for the project
+///
+/// # Errors
+///
+/// Returns an error if the project path is invalid.
+fn verify_args(args: &DaemonArgs) -> Result<VerifyArgs> {
+    let network_url = match (&args.url, &args.network) {
+        (Some(url), _) => Network::new(url.clone(), UrlSource::CliFlag),
+        (None, Some(network)) => Network::for_network(network),
+        (None, None) => Network::unset(),
+    };
+    Ok(VerifyArgs {
+        network: args.network.clone(),
+        network_url,
+        endpoint: None,
+        dry_run: false,
+        format: OutputFormat::Text,
+        path: deferred_project_value_parser(args.path.as_ref().map_or(".", |path| path.as_str()))?,
+        stdin_tar: false,
+        from_pack: None,
+        all_deployed: false,
+        address_file: None,
+        from_dojo_manifest: None,
+        world: None,
+        class_hash: None,
+        rpc_url: None,
+        note: None,
+        contract_address: None,
+        from_receipt: None,
+        watch: true,
+        poll_interval: None,
+        poll_timeout: None,
+        license: None,
+        contract_name: None,
+        contract_file: None,
+        extra_sources: Vec::new(),
+        include_docs: false,
+        doc_paths: Vec::new(),
+        package: None,
+        lock_file: false,
+        test_files: false,
+        project_type: ProjectType::Auto,
+        verbose: false,
+        wizard: false,
+        non_interactive: false,
+        yes: false,
+        prompt_timeout: None,
+        #[cfg(feature = "notifications")]
+        notify: false,
+        fail_fast: false,
+        batch_delay: None,
+        jobs: 1,
+        resume: None,
+        require_clean_git: false,
+        allow_dirty: false,
+        allow_license_mismatch: false,
+        require_license_headers: false,
+        preflight: false,
+        compress: false,
+        compare_class_hash: false,
+        skip_if_verified: true,
+        force: false,
+        on_duplicate: None,
+        results: None,
+        state: None,
+        bundle: None,
+        timezone: None,
+        simulate: false,
+        targets: Vec::new(),
+        metrics_addr: None,
+        history_retention: RetentionPolicy::default(),
+        size_limits: SizeLimits::default(),
+        api_key: None,
+        explorer_url: None,
+    })
+}
+
+/// Contracts whose latest job in history succeeded but that are not verified now
+fn regressions<'a>(
+    api_client: &ApiClient,
+    contracts: &'a [ContractConfig],
+    network: Option<&str>,
+) -> Vec<&'a ContractConfig> {
+    let db = match HistoryDb::open_read_only() {
+        Ok(db) => db,
+        Err(e) => {
+            warn!("Failed to read verification history: {e}");
+            return vec![];
+        }
+    };
+
+    contracts
+        .iter()
+        .filter(|contract| {
+            db.latest_job_for_class_hash(&contract.class_hash, network)
+                .ok()
+                .flatten()
+                .is_some_and(|record| record.status == VerifyJobStatus::Success.to_string())
+        })
+        .filter(|contract| {
+            ClassHash::new(&contract.class_hash)
+                .is_ok_and(|class_hash| already_verified(api_client, &class_hash).is_none())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Timelike};
+
+    #[test]
+    fn test_parse_cron() {
+        let nightly = parse_cron("30 3 * * *").unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+        let next = nightly.after(&start).next().unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 2, 3, 30, 0).unwrap());
+
+        let every_ten_seconds = parse_cron("*/10 * * * * *").unwrap();
+        assert_eq!(every_ten_seconds.after(&start).next().unwrap().second(), 10);
+
+        assert!(parse_cron("nightly").is_err());
+    }
+}
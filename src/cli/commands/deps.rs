@@ -0,0 +1,106 @@
+use crate::{
+    cli::{
+        args::{DepsArgs, DepsFormat},
+        config::Config,
+    },
+    core::{
+        deps::{DependencyGraph, SourceKind},
+        project::select_package,
+    },
+    utils::errors::CliError,
+};
+use anyhow::Result;
+use colored::Colorize;
+
+/// Handles the deps command for showing a package's dependency graph
+///
+/// # Errors
+///
+/// Returns an error if the package is not a workspace member, or if no
+/// package is given in a workspace with several members.
+pub fn handle_deps_command(args: DepsArgs, config: Option<&Config>) -> Result<()> {
+    // Merge config with CLI args (CLI args take precedence)
+    let args = if let Some(cfg) = config {
+        args.merge_with_config(cfg)
+    } else {
+        args
+    };
+
+    let metadata = args.path.load()?;
+    let root = select_package(metadata, args.package.as_deref())?;
+
+    let graph = DependencyGraph::build(&metadata.packages, &root).ok_or_else(|| {
+        CliError::InternalError {
+            message: format!("package {root} is missing from scarb metadata"),
+        }
+    })?;
+
+    match args.format {
+        DepsFormat::Text => display_graph(&graph),
+        DepsFormat::Dot => print!("{}", graph.to_dot()),
+        DepsFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&graph).unwrap_or_else(|_| format!("{graph:?}"))
+        ),
+    }
+
+    Ok(())
+}
+
+fn display_graph(graph: &DependencyGraph) {
+    println!(
+        "\n{}",
+        format!("Dependencies of {}", graph.root).bold().underline()
+    );
+
+    for kind in [
+        SourceKind::Path,
+        SourceKind::Git,
+        SourceKind::Registry,
+        SourceKind::Builtin,
+    ] {
+        let nodes: Vec<_> = graph
+            .packages
+            .iter()
+            .filter(|node| node.kind == kind && node.name != graph.root)
+            .collect();
+        if nodes.is_empty() {
+            continue;
+        }
+
+        println!("\n{} ({})", kind.to_string().bold(), kind.note());
+        for node in nodes {
+            let dev_only = graph
+                .dependencies
+                .iter()
+                .filter(|edge| edge.to == node.name)
+                .all(|edge| edge.dev);
+            let dev = if dev_only { " (dev)" } else { "" };
+            let source = if kind == SourceKind::Builtin {
+                String::new()
+            } else {
+                format!("  {}", node.source.bright_black())
+            };
+            println!("  {} {}{dev}{source}", node.name, node.version);
+        }
+    }
+
+    let remote = graph
+        .packages
+        .iter()
+        .filter(|node| node.kind.is_remote())
+        .count();
+    println!();
+    if remote == 0 {
+        println!(
+            "{} Everything outside the compiler is uploaded with the sources.",
+            "✓".green().bold()
+        );
+    } else {
+        println!(
+            "{} {remote} package(s) must be reachable by the remote compiler.",
+            "!".yellow().bold()
+        );
+        println!("Dev dependencies are only needed when test files are included.");
+    }
+}
@@ -0,0 +1,238 @@
+//! Developer tools, built with the `dev-tools` feature
+//!
+//! `voyager dev seed` submits fake verification jobs so frontend and explorer
+//! developers can exercise status pages, and so polling and history flows can
+//! be tested end to end. Jobs go to the dev network, a custom endpoint, or an
+//! embedded mock server; the production networks are refused.
+
+use crate::{
+    api::{ApiClient, FileInfo, MockServer, ProjectMetadataInfo, DEFAULT_SIMULATION_STEP},
+    cli::{
+        args::{DevArgs, DevCommands, NetworkKind, SeedArgs},
+        config::{self, Config},
+    },
+    core::{class_hash::ClassHash, project::ProjectType},
+    storage::history::{HistoryDb, VerificationRecord},
+    utils::{errors::CliError, run_id, voyager},
+};
+use anyhow::Result;
+use itertools::Itertools;
+use reqwest::Url;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::{fs, thread};
+
+/// Compiler versions reported for seeded jobs
+const SEED_CAIRO_VERSION: semver::Version = semver::Version::new(2, 11, 4);
+const SEED_SCARB_VERSION: semver::Version = semver::Version::new(2, 11, 4);
+
+/// A fake job created by `voyager dev seed`
+#[derive(Debug, Clone)]
+pub struct SeededJob {
+    pub job_id: String,
+    pub class_hash: ClassHash,
+    pub contract_name: String,
+}
+
+/// Handles the hidden `dev` command
+///
+/// # Errors
+///
+/// Returns an error if the subcommand fails.
+pub fn handle_dev_command(args: DevArgs, config: Option<&Config>) -> Result<()> {
+    match args.command {
+        DevCommands::Seed(args) => handle_seed(&args, config),
+    }
+}
+
+fn handle_seed(args: &SeedArgs, config: Option<&Config>) -> Result<()> {
+    let mock = if args.mock {
+        Some(MockServer::start(DEFAULT_SIMULATION_STEP)?)
+    } else {
+        None
+    };
+    let url = match (&mock, &args.url) {
+        (Some(server), _) => server.url().clone(),
+        (None, Some(url)) => url.clone(),
+        (None, None) => Url::parse(voyager::api_url(&NetworkKind::Dev))?,
+    };
+    if is_production(&url) {
+        eprintln!("Error: Refusing to seed fake jobs on {url}; use the dev network, a local --url or --mock");
+        std::process::exit(1);
+    }
+
+    let api_client = config::with_configured_polling(ApiClient::new(url.clone())?, config);
+    let jobs = seed(&api_client, args.count)?;
+    println!("Seeded {} job(s) on {url}:", jobs.len());
+    for job in &jobs {
+        println!(
+            "  {}  {}  {}",
+            job.job_id, job.class_hash, job.contract_name
+        );
+    }
+
+    if args.history {
+        record_in_history(&jobs, &url)?;
+        println!("Recorded the jobs in verification history");
+    }
+
+    let (Some(server), Some(job)) = (mock, jobs.first()) else {
+        return Ok(());
+    };
+    println!(
+        "\nMock server running at {}, press Ctrl-C to stop. Try:\n  voyager status --url {} --job {} --watch",
+        server.url(),
+        server.url(),
+        job.job_id
+    );
+    loop {
+        thread::park();
+    }
+}
+
+/// Submit `count` fake verification jobs
+///
+/// Every third job submits sources that do not compile, so endpoints that
+/// compile them produce failed jobs as well. The mock server accepts them all.
+///
+/// # Errors
+///
+/// Returns an error if the fake sources cannot be written or a submission fails.
+pub fn seed(api_client: &ApiClient, count: usize) -> Result<Vec<SeededJob>> {
+    let dir = std::env::temp_dir().join(format!("voyager-seed-{}", run_id::current()));
+    fs::create_dir_all(dir.join("src"))?;
+    let result = (1..=count)
+        .map(|index| seed_job(api_client, &dir, index))
+        .collect();
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+fn seed_job(api_client: &ApiClient, dir: &std::path::Path, index: usize) -> Result<SeededJob> {
+    let package = format!("seed_{index}");
+    let contract_name = format!("SeedContract{index}");
+    let body = if index.is_multiple_of(3) {
+        "    fn broken(self: @ContractState) -> felt252 { undefined_value }\n"
+    } else {
+        ""
+    };
+    let sources = [
+        (
+            "Scarb.toml",
+            format!(
+                "[package]\nname = \"{package}\"\nversion = \"0.1.0\"\n\n[dependencies]\nstarknet = \"{SEED_CAIRO_VERSION}\"\n\n[[target.starknet-contract]]\n"
+            ),
+        ),
+        (
+            "src/lib.cairo",
+            format!("#[starknet::contract]\nmod {contract_name} {{\n    #[storage]\n    struct Storage {{}}\n{body}}}\n"),
+        ),
+    ];
+    let files = sources
+        .into_iter()
+        .map(|(name, content)| {
+            let path = dir.join(name);
+            fs::write(&path, content)?;
+            Ok(FileInfo {
+                name: name.to_string(),
+                path,
+            })
+        })
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    let class_hash = random_class_hash()?;
+    let metadata = ProjectMetadataInfo::new(
+        SEED_CAIRO_VERSION,
+        SEED_SCARB_VERSION,
+        ".".to_string(),
+        "src/lib.cairo".to_string(),
+        package,
+        ProjectType::Scarb,
+        None,
+    );
+    let job_id = api_client.verify_class(
+        &class_hash,
+        Some("MIT".to_string()),
+        &contract_name,
+        metadata,
+        &files,
+    )?;
+
+    Ok(SeededJob {
+        job_id,
+        class_hash,
+        contract_name,
+    })
+}
+
+/// A class hash of 248 random bits, so seeded classes never collide
+fn random_class_hash() -> Result<ClassHash, CliError> {
+    let mut bytes = [0u8; 31];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| CliError::InternalError {
+            message: "no randomness available for a class hash".to_string(),
+        })?;
+    let hex = bytes.iter().map(|b| format!("{b:02x}")).join("");
+    Ok(ClassHash::new(&format!("0x{hex}"))?)
+}
+
+/// Whether `url` is the API of mainnet or Sepolia
+fn is_production(url: &Url) -> bool {
+    [NetworkKind::Mainnet, NetworkKind::Sepolia]
+        .iter()
+        .filter_map(|network| Url::parse(voyager::api_url(network)).ok())
+        .any(|api| api.host_str() == url.host_str())
+}
+
+fn record_in_history(jobs: &[SeededJob], url: &Url) -> Result<()> {
+    let dev = Url::parse(voyager::api_url(&NetworkKind::Dev)).ok();
+    let network = if dev.is_some_and(|dev| dev.host_str() == url.host_str()) {
+        "dev"
+    } else {
+        "custom"
+    };
+
+    let db = HistoryDb::open()?;
+    for job in jobs {
+        let record = VerificationRecord::new(
+            job.job_id.clone(),
+            &job.class_hash,
+            job.contract_name.clone(),
+            network.to_string(),
+            crate::api::VerifyJobStatus::Submitted,
+            None,
+            SEED_SCARB_VERSION.to_string(),
+            SEED_CAIRO_VERSION.to_string(),
+            None,
+        )
+        .with_run_id(run_id::current());
+        db.insert(&record)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::api::VerifyJobStatus;
+
+    #[test]
+    fn test_seed_mock_server() {
+        let server = MockServer::start(DEFAULT_SIMULATION_STEP).unwrap();
+        let api_client = ApiClient::new(server.url().clone()).unwrap();
+
+        let jobs = seed(&api_client, 3).unwrap();
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[2].contract_name, "SeedContract3");
+        let job = api_client
+            .get_job_status_raw(jobs[0].job_id.clone())
+            .unwrap();
+        assert_eq!(*job.status(), VerifyJobStatus::Submitted);
+
+        assert!(is_production(
+            &Url::parse("https://api.voyager.online/beta").unwrap()
+        ));
+        assert!(!is_production(server.url()));
+    }
+}
@@ -0,0 +1,207 @@
+//! Comparison of two dry-run payloads
+//!
+//! `voyager diff-dryrun before.json after.json` reads two previews printed by
+//! `voyager verify --dry-run --format json` and reports what changed between
+//! them: metadata such as toolchain versions and the contract file, files that
+//! were added or removed, and files whose contents differ.
+
+use crate::{cli::args::DiffDryrunArgs, core::verification::PayloadPreview};
+use camino::Utf8Path;
+use colored::Colorize;
+use std::fs;
+
+/// Differences between two payload previews
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PayloadDiff {
+    /// Metadata fields that changed, as `(field, before, after)`
+    pub metadata: Vec<(&'static str, String, String)>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Files in both payloads whose digests differ
+    pub changed: Vec<String>,
+    /// Files in both payloads that are the same, or could not be compared
+    pub unchanged: usize,
+    /// Whether either payload has no file digests, so contents were not compared
+    pub contents_unknown: bool,
+}
+
+impl PayloadDiff {
+    /// Compare `before` with `after`
+    #[must_use]
+    pub fn new(before: &PayloadPreview, after: &PayloadPreview) -> Self {
+        let field = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+        let metadata = [
+            (
+                "compiler_version",
+                &before.compiler_version,
+                &after.compiler_version,
+            ),
+            ("scarb_version", &before.scarb_version, &after.scarb_version),
+            ("package_name", &before.package_name, &after.package_name),
+            ("name", &before.name, &after.name),
+            ("contract_file", &before.contract_file, &after.contract_file),
+            (
+                "project_dir_path",
+                &before.project_dir_path,
+                &after.project_dir_path,
+            ),
+            ("build_tool", &before.build_tool, &after.build_tool),
+            ("license", &before.license, &after.license),
+        ]
+        .into_iter()
+        .map(|(name, before, after)| (name, before.clone(), after.clone()))
+        .chain([(
+            "dojo_version",
+            field(&before.dojo_version),
+            field(&after.dojo_version),
+        )])
+        .filter(|(_, before, after)| before != after)
+        .collect();
+
+        let mut diff = Self {
+            metadata,
+            contents_unknown: before.file_hashes.is_empty() || after.file_hashes.is_empty(),
+            ..Self::default()
+        };
+        for name in &after.file_list {
+            if !before.file_list.contains(name) {
+                diff.added.push(name.clone());
+            }
+        }
+        for name in &before.file_list {
+            if !after.file_list.contains(name) {
+                diff.removed.push(name.clone());
+                continue;
+            }
+            match (before.file_hashes.get(name), after.file_hashes.get(name)) {
+                (Some(before), Some(after)) if before != after => diff.changed.push(name.clone()),
+                _ => diff.unchanged += 1,
+            }
+        }
+        diff
+    }
+
+    /// Whether the payloads are the same
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.metadata.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// Handles the diff-dryrun command
+///
+/// Exits with status 1 if either payload cannot be read.
+pub fn handle_diff_dryrun_command(args: &DiffDryrunArgs) {
+    let before = read_payload(&args.before);
+    let after = read_payload(&args.after);
+    let diff = PayloadDiff::new(&before, &after);
+
+    println!("Comparing {} with {}\n", args.before, args.after);
+    if diff.is_empty() {
+        println!("{} The payloads are the same", "✓".green());
+    }
+
+    if !diff.metadata.is_empty() {
+        println!("{}", "Metadata".bold());
+        for (name, before, after) in &diff.metadata {
+            println!("  {name}: {} → {}", before.red(), after.green());
+        }
+        println!();
+    }
+
+    if !(diff.added.is_empty() && diff.removed.is_empty() && diff.changed.is_empty()) {
+        println!("{}", "Files".bold());
+        for name in &diff.added {
+            println!("  {} {name}", "+".green());
+        }
+        for name in &diff.removed {
+            println!("  {} {name}", "-".red());
+        }
+        for name in &diff.changed {
+            println!("  {} {name}", "~".yellow());
+        }
+        println!();
+    }
+
+    println!("{} file(s) unchanged", diff.unchanged);
+    if diff.contents_unknown {
+        println!(
+            "Note: file contents were not compared, as a payload has no file hashes; \
+             re-create it with this version of voyager"
+        );
+    }
+}
+
+fn read_payload(path: &Utf8Path) -> PayloadPreview {
+    let payload = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+    match payload {
+        Ok(payload) => payload,
+        Err(e) => {
+            eprintln!("Error: {path} is not a dry-run payload: {e}");
+            eprintln!("Create one with 'voyager verify --dry-run --format json > {path}'.");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn payload(files: &[(&str, &str)], scarb_version: &str) -> PayloadPreview {
+        serde_json::from_value(serde_json::json!({
+            "compiler_version": "2.11.4",
+            "scarb_version": scarb_version,
+            "package_name": "token",
+            "name": "MyToken",
+            "contract_file": "src/lib.cairo",
+            "contract-name": "src/lib.cairo",
+            "project_dir_path": ".",
+            "build_tool": "scarb",
+            "license": "MIT",
+            "file_count": files.len(),
+            "file_list": files.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            "file_hashes": files.iter().copied().collect::<std::collections::BTreeMap<_, _>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_payload_diff() {
+        let before = payload(
+            &[
+                ("Scarb.toml", "sha256:01"),
+                ("src/lib.cairo", "sha256:02"),
+                ("src/utils.cairo", "sha256:03"),
+            ],
+            "2.11.4",
+        );
+        let after = payload(
+            &[
+                ("Scarb.toml", "sha256:01"),
+                ("src/lib.cairo", "sha256:04"),
+                ("src/token/utils.cairo", "sha256:03"),
+            ],
+            "2.12.0",
+        );
+
+        let diff = PayloadDiff::new(&before, &after);
+        assert_eq!(
+            diff.metadata,
+            [("scarb_version", "2.11.4".to_string(), "2.12.0".to_string())]
+        );
+        assert_eq!(diff.added, ["src/token/utils.cairo"]);
+        assert_eq!(diff.removed, ["src/utils.cairo"]);
+        assert_eq!(diff.changed, ["src/lib.cairo"]);
+        assert_eq!(diff.unchanged, 1);
+        assert!(!diff.contents_unknown);
+
+        assert!(PayloadDiff::new(&before, &before).is_empty());
+    }
+}
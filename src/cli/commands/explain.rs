@@ -0,0 +1,28 @@
+use crate::{cli::args::ExplainArgs, utils::explain};
+use colored::Colorize;
+
+/// Handles the explain command for printing the explanation of an error code
+///
+/// Exits with status 1 if the code has no explanation.
+pub fn handle_explain_command(args: &ExplainArgs) {
+    let Some(code) = args.code.as_deref() else {
+        list_codes();
+        return;
+    };
+
+    let Some(entry) = explain::explain(code) else {
+        eprintln!("Error: no explanation for error code '{code}'");
+        eprintln!("Run 'voyager explain' to list all error codes.");
+        std::process::exit(1);
+    };
+
+    print!("{entry}");
+}
+
+fn list_codes() {
+    println!("\n{}", "Error codes".bold().underline());
+    for entry in explain::EXPLANATIONS {
+        println!("  {}  {}", entry.code.bold(), entry.title);
+    }
+    println!("\nRun 'voyager explain <CODE>' for details.");
+}
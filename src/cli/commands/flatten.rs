@@ -0,0 +1,80 @@
+use crate::{
+    cli::{args::FlattenArgs, config::Config},
+    core::{analyze::format_size, flatten::flatten, project::select_package},
+    filesystem::collector::{package_contracts, package_defines_contract},
+    utils::errors::{CliError, MissingContract},
+};
+use anyhow::Result;
+
+/// Handles the flatten command, writing the module tree of a contract's
+/// package to one file
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The project cannot be loaded or the package cannot be selected
+/// - The package does not define the contract
+/// - A source file cannot be read
+/// - The output file cannot be written
+pub fn handle_flatten_command(args: FlattenArgs, config: Option<&Config>) -> Result<()> {
+    // Merge config with CLI args (CLI args take precedence)
+    let args = if let Some(cfg) = config {
+        args.merge_with_config(cfg)
+    } else {
+        args
+    };
+
+    let metadata = args.path.load()?;
+    let members: Vec<_> = metadata
+        .packages
+        .iter()
+        .filter(|package| metadata.workspace.members.contains(&package.id))
+        .collect();
+    // Without --package, the member defining the contract is flattened
+    let defining: Vec<_> = members
+        .iter()
+        .filter(|member| package_defines_contract(member, &args.contract_name))
+        .collect();
+    let name = match (args.package.as_deref(), defining.as_slice()) {
+        (None, [only]) => only.name.clone(),
+        (package, _) => select_package(metadata, package)?,
+    };
+    let Some(package) = members.into_iter().find(|member| member.name == name) else {
+        return Err(CliError::InternalError {
+            message: format!("package {name} is not in the workspace metadata"),
+        }
+        .into());
+    };
+
+    let contracts = package_contracts(package).map_err(CliError::from)?;
+    let Some(contract) = contracts
+        .iter()
+        .find(|contract| contract.name == args.contract_name)
+    else {
+        let available = contracts
+            .iter()
+            .map(|contract| contract.name.clone())
+            .collect();
+        return Err(CliError::from(MissingContract::new(args.contract_name, available)).into());
+    };
+
+    let flattened = flatten(package, contract).map_err(CliError::from)?;
+    for missing in &flattened.missing {
+        eprintln!(
+            "Warning: {missing} is declared as a module but does not exist; the declaration is kept"
+        );
+    }
+    let Some(output) = args.output else {
+        print!("{}", flattened.source);
+        return Ok(());
+    };
+    std::fs::write(&output, &flattened.source)?;
+
+    println!(
+        "✓ Flattened {} file(s) ({}) of {} into {output}",
+        flattened.files.len(),
+        format_size(flattened.source.len() as u64),
+        contract.name
+    );
+    Ok(())
+}
@@ -1,16 +1,29 @@
 use crate::{
-    api::ApiClient,
+    api::{ApiClient, ApiClientError, VerifyJobStatus},
     cli::{
-        args::{HistoryArgs, HistoryCommands, Network, NetworkKind},
-        config::Config,
+        args::{HistoryArgs, HistoryCommands, Network, NetworkKind, OutputFormat, Timezone},
+        config::{with_configured_api_key, with_configured_polling, Config},
+        prompt,
     },
-    core::verification::display_verbose_error,
-    storage::history::{HistoryDb, VerificationRecord},
+    core::{
+        analyze::format_size,
+        verification::{display_verbose_error, reconcile_with_explorer},
+    },
+    output::{
+        history::{
+            render, render_payload_chart, PayloadOutput, RecheckOutput, RecheckedJob, RecordOutput,
+            StatsOutput,
+        },
+        status::format_datetime,
+    },
+    storage::history::{HistoryDb, VerificationRecord, EXPIRED_STATUS},
     utils::errors::CliError,
 };
 use anyhow::Result;
+use std::time::Duration;
 
-/// Handles all history-related commands (list, status, recheck, clean, stats)
+/// Handles all history-related commands (list, status, recheck, clean, doctor,
+/// stats)
 ///
 /// # Errors
 ///
@@ -19,38 +32,99 @@ use anyhow::Result;
 /// - API requests fail when refreshing status or rechecking jobs
 /// - I/O operations fail (user input, stdout)
 pub fn handle_history_command(args: HistoryArgs, config: Option<&Config>) -> Result<()> {
+    let timezone = args
+        .timezone
+        .or_else(|| config.and_then(Config::parse_timezone))
+        .unwrap_or_default();
+
     match args.command {
         HistoryCommands::List {
             status,
             network,
+            run_id,
             limit,
-        } => handle_history_list(status.as_deref(), network.as_deref(), limit),
+            format,
+        } => handle_history_list(
+            status.as_deref(),
+            network.as_deref(),
+            run_id.as_deref(),
+            limit,
+            format,
+            timezone,
+        ),
         HistoryCommands::Status {
             job,
             network,
             network_url,
             refresh,
             verbose,
-        } => handle_history_status(&job, network, network_url, refresh, verbose, config),
+            format,
+        } => handle_history_status(
+            &job,
+            network.as_ref(),
+            network_url,
+            refresh,
+            verbose,
+            format,
+            config,
+            timezone,
+        ),
         HistoryCommands::Recheck {
             network,
             network_url,
             verbose,
-        } => handle_history_recheck(network, network_url, verbose, config),
-        HistoryCommands::Clean { older_than, all } => handle_history_clean(older_than, all),
-        HistoryCommands::Stats => handle_history_stats(),
+            fix_orphans,
+            format,
+        } => handle_history_recheck(
+            network.as_ref(),
+            network_url,
+            verbose,
+            fix_orphans,
+            format,
+            config,
+        ),
+        HistoryCommands::Clean {
+            older_than,
+            all,
+            prompt_timeout,
+        } => handle_history_clean(older_than, all, prompt_timeout),
+        HistoryCommands::Doctor {
+            fix,
+            yes,
+            prompt_timeout,
+        } => handle_history_doctor(fix, yes, prompt_timeout),
+        HistoryCommands::Stats {
+            payloads: true,
+            contract,
+            limit,
+            format,
+        } => handle_payload_stats(contract.as_deref(), limit, format, timezone),
+        HistoryCommands::Stats { format, .. } => handle_history_stats(format),
     }
 }
 
-fn handle_history_list(status: Option<&str>, network: Option<&str>, limit: usize) -> Result<()> {
+fn handle_history_list(
+    status: Option<&str>,
+    network: Option<&str>,
+    run_id: Option<&str>,
+    limit: usize,
+    format: OutputFormat,
+    timezone: Timezone,
+) -> Result<()> {
     use colored::Colorize;
 
-    let db = HistoryDb::open().map_err(|e| {
+    let db = HistoryDb::open_read_only().map_err(|e| {
         eprintln!("Failed to open history database: {e}");
         e
     })?;
 
-    let records = db.list(status, network, Some(limit))?;
+    let records = db.list(status, network, run_id, Some(limit))?;
+
+    if format != OutputFormat::Text {
+        let records: Vec<RecordOutput> = records.iter().map(RecordOutput::from).collect();
+        println!("{}", render(&records, format));
+        return Ok(());
+    }
 
     if records.is_empty() {
         println!("\nNo verification history found.");
@@ -65,27 +139,27 @@ fn handle_history_list(status: Option<&str>, network: Option<&str>, limit: usize
 
     let records_count = records.len();
     for record in records {
-        let status_colored = match record.status.as_str() {
-            "Success" => record.status.green().bold(),
-            "Fail" | "CompileFailed" => record.status.red().bold(),
-            _ => record.status.yellow(),
-        };
-
         println!("{} {}", "Job ID:".bold(), record.job_id);
         println!("  Contract: {}", record.contract_name);
         println!("  Class Hash: {}", record.class_hash);
         println!("  Network: {}", record.network);
-        println!("  Status: {status_colored}");
+        println!("  Status: {}", colored_status(&record.status));
         println!(
             "  Submitted: {}",
-            record.submitted_at.format("%Y-%m-%d %H:%M:%S UTC")
+            format_datetime(record.submitted_at, timezone)
         );
         if let Some(completed) = record.completed_at {
-            println!("  Completed: {}", completed.format("%Y-%m-%d %H:%M:%S UTC"));
+            println!("  Completed: {}", format_datetime(completed, timezone));
         }
-        if let Some(pkg) = record.package_name {
+        if let Some(ref pkg) = record.package_name {
             println!("  Package: {pkg}");
         }
+        if let Some(ref commit) = record.commit_hash {
+            println!("  Commit: {commit}");
+        }
+        if let Some(identity) = record.submitter().identity() {
+            println!("  Submitted by: {identity}");
+        }
         println!(
             "  Cairo: {}, Scarb: {}",
             record.cairo_version, record.scarb_version
@@ -102,30 +176,34 @@ fn handle_history_list(status: Option<&str>, network: Option<&str>, limit: usize
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_history_status(
     job: &str,
-    network: Option<NetworkKind>,
+    network: Option<&NetworkKind>,
     network_url: Network,
     refresh: bool,
     verbose: bool,
+    format: OutputFormat,
     config: Option<&Config>,
+    timezone: Timezone,
 ) -> Result<()> {
-    let db = HistoryDb::open()?;
+    // Only a refresh writes to the database
+    let db = if refresh {
+        HistoryDb::open()?
+    } else {
+        HistoryDb::open_read_only()?
+    };
 
     // Get record from database
     let record = db.get_by_job_id(job)?;
 
     if let Some(mut rec) = record {
         if refresh {
-            // Merge network with config
-            let _network = if network.is_none() {
-                config.as_ref().and_then(|cfg| cfg.parse_network())
-            } else {
-                network
-            };
-
-            let url = super::super::config::resolve_api_url(network_url, config)?;
-            let api_client = ApiClient::new(url)?;
+            let url = super::super::config::resolve_api_url(network_url, network, config)?;
+            let api_client = with_configured_api_key(
+                with_configured_polling(ApiClient::new(url)?, config),
+                config,
+            );
             let status = crate::api::poll_verification_status(&api_client, job).map_err(|e| {
                 let cli_error = CliError::from(e);
                 if verbose {
@@ -137,9 +215,20 @@ fn handle_history_status(
             // Update the database record
             rec.update_status(*status.status());
             db.update_status(job, &rec.status, rec.completed_at)?;
+            if *status.status() == VerifyJobStatus::Success {
+                reconcile_with_explorer(&api_client, job);
+            }
+            if format != OutputFormat::Text {
+                println!("{}", render(&RecordOutput::from(&rec), format));
+            }
+        } else if format == OutputFormat::Text {
+            display_history_record(&rec, timezone);
         } else {
-            display_history_record(&rec);
+            println!("{}", render(&RecordOutput::from(&rec), format));
         }
+    } else if format != OutputFormat::Text {
+        eprintln!("Error: Job ID not found in local history: {job}");
+        std::process::exit(1);
     } else {
         println!("\n❌ Job ID not found in local history: {job}");
         println!("\nThis job may not have been tracked, or it was cleaned from history.\n");
@@ -149,30 +238,50 @@ fn handle_history_status(
 }
 
 /// Displays a history record to the console
-fn display_history_record(rec: &VerificationRecord) {
+fn display_history_record(rec: &VerificationRecord, timezone: Timezone) {
     use colored::Colorize;
 
-    let status_colored = match rec.status.as_str() {
-        "Success" => rec.status.green().bold(),
-        "Fail" | "CompileFailed" => rec.status.red().bold(),
-        _ => rec.status.yellow(),
-    };
-
     println!("\n{} {}", "Job ID:".bold(), rec.job_id);
     println!("Contract: {}", rec.contract_name);
     println!("Class Hash: {}", rec.class_hash);
     println!("Network: {}", rec.network);
-    println!("Status: {status_colored}");
-    println!(
-        "Submitted: {}",
-        rec.submitted_at.format("%Y-%m-%d %H:%M:%S UTC")
-    );
+    println!("Status: {}", colored_status(&rec.status));
+    println!("Submitted: {}", format_datetime(rec.submitted_at, timezone));
     if let Some(completed) = rec.completed_at {
-        println!("Completed: {}", completed.format("%Y-%m-%d %H:%M:%S UTC"));
+        println!("Completed: {}", format_datetime(completed, timezone));
     }
     if let Some(ref pkg) = rec.package_name {
         println!("Package: {pkg}");
     }
+    if let Some(ref commit) = rec.commit_hash {
+        println!("Commit: {commit}");
+    }
+    if let Some(identity) = rec.submitter().identity() {
+        println!("Submitted by: {identity}");
+    }
+    if let Some(ref ci_job_url) = rec.ci_job_url {
+        println!("CI job: {ci_job_url}");
+    }
+    if let Some(ref run_id) = rec.run_id {
+        println!("Run ID: {run_id}");
+    }
+    if let Some(ref note) = rec.note {
+        println!("Note: {note}");
+    }
+    if let Some(verified_at) = rec.verified_at {
+        println!(
+            "Verified on explorer: {}",
+            format_datetime(verified_at, timezone)
+        );
+    }
+    if let Some(ref name) = rec.explorer_name {
+        if *name != rec.contract_name {
+            println!("Explorer name: {name}");
+        }
+    }
+    if let Some(ref version) = rec.explorer_version {
+        println!("Explorer version: {version}");
+    }
     println!("Cairo version: {}", rec.cairo_version);
     println!("Scarb version: {}", rec.scarb_version);
     if let Some(ref dojo) = rec.dojo_version {
@@ -181,77 +290,140 @@ fn display_history_record(rec: &VerificationRecord) {
     println!("\nUse --refresh to update status from the API.\n");
 }
 
+/// A job status colored by its outcome
+fn colored_status(status: &str) -> colored::ColoredString {
+    use colored::Colorize;
+
+    match status {
+        "Success" => status.green().bold(),
+        "Fail" | "CompileFailed" => status.red().bold(),
+        EXPIRED_STATUS => status.bright_black(),
+        _ => status.yellow(),
+    }
+}
+
 fn handle_history_recheck(
-    network: Option<NetworkKind>,
+    network: Option<&NetworkKind>,
     network_url: Network,
     verbose: bool,
+    fix_orphans: bool,
+    format: OutputFormat,
     config: Option<&Config>,
 ) -> Result<()> {
     use colored::Colorize;
 
+    let text = format == OutputFormat::Text;
     let db = HistoryDb::open()?;
 
     // Get all pending jobs
     let all_pending = get_all_pending_jobs(&db)?;
 
     if all_pending.is_empty() {
-        println!("\n✅ No pending verification jobs found.\n");
+        if text {
+            println!("\n✅ No pending verification jobs found.\n");
+        } else {
+            println!("{}", render(&RecheckOutput::default(), format));
+        }
         return Ok(());
     }
 
-    println!("\n🔄 Re-checking {} pending job(s)...\n", all_pending.len());
-
-    // Merge network with config
-    let _network = if network.is_none() {
-        config.as_ref().and_then(|cfg| cfg.parse_network())
-    } else {
-        network
-    };
+    if text {
+        println!("\n🔄 Re-checking {} pending job(s)...\n", all_pending.len());
+    }
 
-    let url = super::super::config::resolve_api_url(network_url, config)?;
-    let api_client = ApiClient::new(url)?;
+    let url = super::super::config::resolve_api_url(network_url, network, config)?;
+    let api_client = with_configured_api_key(
+        with_configured_polling(ApiClient::new(url)?, config),
+        config,
+    );
 
-    let mut updated = 0;
+    let mut output = RecheckOutput::default();
     for mut rec in all_pending {
-        print!("Checking {}... ", rec.job_id);
+        if text {
+            print!("Checking {}... ", rec.job_id);
+        }
+        let mut job = RecheckedJob {
+            job_id: rec.job_id.clone(),
+            previous_status: rec.status.clone(),
+            status: None,
+            orphaned: false,
+            error: None,
+        };
         match crate::api::poll_verification_status(&api_client, &rec.job_id) {
             Ok(status) => {
-                let old_status = rec.status.clone();
                 rec.update_status(*status.status());
+                job.status = Some(rec.status.clone());
 
-                if old_status == rec.status {
-                    println!("{}", rec.status.yellow());
+                if job.previous_status == rec.status {
+                    if text {
+                        println!("{}", rec.status.yellow());
+                    }
                 } else {
                     db.update_status(&rec.job_id, &rec.status, rec.completed_at)?;
-                    let status_colored = match rec.status.as_str() {
-                        "Success" => rec.status.green().bold(),
-                        "Fail" | "CompileFailed" => rec.status.red().bold(),
-                        _ => rec.status.yellow(),
-                    };
-                    println!("{status_colored}");
-                    updated += 1;
+                    if *status.status() == VerifyJobStatus::Success {
+                        reconcile_with_explorer(&api_client, &rec.job_id);
+                    }
+                    if text {
+                        println!("{}", colored_status(&rec.status));
+                    }
+                    output.updated += 1;
+                }
+            }
+            Err(ApiClientError::JobNotFound(_)) => {
+                output.orphans += 1;
+                job.orphaned = true;
+                if fix_orphans {
+                    rec.mark_expired();
+                    db.update_status(&rec.job_id, &rec.status, rec.completed_at)?;
+                    job.status = Some(rec.status.clone());
+                    if text {
+                        println!("{}", rec.status.bright_black());
+                    }
+                } else if text {
+                    println!("{}", "Not found".bright_black());
                 }
             }
             Err(e) => {
-                println!("{}", "Error".red());
+                let cli_error: CliError = e.into();
+                job.error = cli_error.to_string().lines().next().map(String::from);
+                if text {
+                    println!("{}", "Error".red());
+                }
                 if verbose {
-                    let cli_error: CliError = e.into();
                     display_verbose_error(&cli_error);
                 }
             }
         }
+        output.jobs.push(job);
+    }
+
+    if !text {
+        println!("{}", render(&output, format));
+        return Ok(());
     }
 
-    println!("\n✅ Updated {updated} job(s).\n");
+    let RecheckOutput {
+        updated, orphans, ..
+    } = output;
+    if fix_orphans {
+        println!("\n✅ Updated {updated} job(s), marked {orphans} as {EXPIRED_STATUS}.\n");
+    } else {
+        println!("\n✅ Updated {updated} job(s).\n");
+        if orphans > 0 {
+            println!(
+                "{orphans} job(s) are no longer known to the API. Run with --fix-orphans to mark them as {EXPIRED_STATUS}.\n"
+            );
+        }
+    }
 
     Ok(())
 }
 
 /// Gets all pending verification jobs from the database
 fn get_all_pending_jobs(db: &HistoryDb) -> Result<Vec<VerificationRecord>> {
-    let pending = db.list(Some("Submitted"), None, None)?;
-    let processing = db.list(Some("Processing"), None, None)?;
-    let compiled = db.list(Some("Compiled"), None, None)?;
+    let pending = db.list(Some("Submitted"), None, None, None)?;
+    let processing = db.list(Some("Processing"), None, None, None)?;
+    let compiled = db.list(Some("Compiled"), None, None, None)?;
 
     Ok(pending
         .into_iter()
@@ -260,7 +432,11 @@ fn get_all_pending_jobs(db: &HistoryDb) -> Result<Vec<VerificationRecord>> {
         .collect())
 }
 
-fn handle_history_clean(older_than: Option<u32>, all: bool) -> Result<()> {
+fn handle_history_clean(
+    older_than: Option<u32>,
+    all: bool,
+    prompt_timeout: Option<Duration>,
+) -> Result<()> {
     use std::io::{self, Write};
 
     let db = HistoryDb::open()?;
@@ -269,8 +445,12 @@ fn handle_history_clean(older_than: Option<u32>, all: bool) -> Result<()> {
         print!("⚠️  Are you sure you want to delete ALL verification history? (y/N): ");
         io::stdout().flush()?;
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        // Unanswered, the confirmation is cancelled
+        let input = prompt::ask(prompt_timeout, || {
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).map(|_| input)
+        })?
+        .unwrap_or_default();
 
         if input.trim().to_lowercase() == "y" {
             let deleted = db.clean_all()?;
@@ -289,42 +469,162 @@ fn handle_history_clean(older_than: Option<u32>, all: bool) -> Result<()> {
     Ok(())
 }
 
-fn handle_history_stats() -> Result<()> {
+fn handle_history_doctor(fix: bool, yes: bool, prompt_timeout: Option<Duration>) -> Result<()> {
+    use std::io::{self, Write};
+
+    let mut db = if fix {
+        HistoryDb::open()?
+    } else {
+        HistoryDb::open_read_only()?
+    };
+
+    let corruption = db.integrity_check()?;
+    if corruption.is_empty() {
+        println!("\n✅ Database integrity check passed.");
+    } else {
+        println!("\n❌ Database integrity check failed:");
+        for message in &corruption {
+            println!("  {message}");
+        }
+        println!(
+            "\nThe database cannot be repaired record by record. Back up {} and run `voyager history clean --all` to start over.",
+            HistoryDb::db_path()?.display()
+        );
+    }
+
+    let issues = db.malformed_records()?;
+    if issues.is_empty() {
+        println!("✅ All records are readable.\n");
+        return Ok(());
+    }
+
+    println!("\n⚠️  {} problem(s) in history records:\n", issues.len());
+    for issue in &issues {
+        println!(
+            "  #{} {}: {} → {}",
+            issue.id,
+            issue.job_id,
+            issue.problem,
+            issue.remedy()
+        );
+    }
+
+    if !fix {
+        println!("\nRun `voyager history doctor --fix` to repair them.\n");
+        return Ok(());
+    }
+
+    if !yes {
+        print!("\nRepair these records? (y/N): ");
+        io::stdout().flush()?;
+
+        // Unanswered, the repair is cancelled
+        let input = prompt::ask(prompt_timeout, || {
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).map(|_| input)
+        })?
+        .unwrap_or_default();
+
+        if input.trim().to_lowercase() != "y" {
+            println!("\n❌ Cancelled.\n");
+            return Ok(());
+        }
+    }
+
+    let summary = db.repair(&issues)?;
+    println!(
+        "\n✅ Rewrote {} value(s), cleared {} timestamp(s), quarantined {} record(s).",
+        summary.fixed, summary.cleared, summary.quarantined
+    );
+    if summary.quarantined > 0 {
+        println!("Quarantined records are kept in the verification_history_quarantine table.");
+    }
+    println!();
+    Ok(())
+}
+
+fn handle_history_stats(format: OutputFormat) -> Result<()> {
     use colored::Colorize;
 
-    let db = HistoryDb::open()?;
+    let db = HistoryDb::open_read_only()?;
     let stats = db.get_stats()?;
 
+    if format != OutputFormat::Text {
+        println!("{}", render(&StatsOutput::from(&stats), format));
+        return Ok(());
+    }
+
     println!("\n{}", "Verification History Statistics".bold().underline());
     println!();
     println!("Total verifications: {}", stats.total);
     println!(
         "Successful: {} ({}%)",
         stats.successful.to_string().green().bold(),
-        if stats.total > 0 {
-            stats.successful * 100 / stats.total
-        } else {
-            0
-        }
+        (stats.successful * 100)
+            .checked_div(stats.total)
+            .unwrap_or(0)
     );
     println!(
         "Failed: {} ({}%)",
         stats.failed.to_string().red().bold(),
-        if stats.total > 0 {
-            stats.failed * 100 / stats.total
-        } else {
-            0
-        }
+        (stats.failed * 100).checked_div(stats.total).unwrap_or(0)
     );
     println!(
         "Pending: {} ({}%)",
         stats.pending.to_string().yellow(),
-        if stats.total > 0 {
-            stats.pending * 100 / stats.total
-        } else {
-            0
-        }
+        (stats.pending * 100).checked_div(stats.total).unwrap_or(0)
     );
+    if stats.expired > 0 {
+        println!(
+            "Expired: {} ({}%)",
+            stats.expired.to_string().bright_black(),
+            (stats.expired * 100).checked_div(stats.total).unwrap_or(0)
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+fn handle_payload_stats(
+    contract: Option<&str>,
+    limit: usize,
+    format: OutputFormat,
+    timezone: Timezone,
+) -> Result<()> {
+    use colored::Colorize;
+
+    let db = HistoryDb::open_read_only()?;
+    let payloads = PayloadOutput::from_records(&db.payload_history(contract, Some(limit))?);
+
+    if format != OutputFormat::Text {
+        println!("{}", render(&payloads, format));
+        return Ok(());
+    }
+
+    let Some(latest) = payloads.last() else {
+        println!("No payload sizes recorded yet; they are recorded with every new submission.");
+        return Ok(());
+    };
+    println!("\n{}", "Payload Size per Submission".bold().underline());
+    println!();
+    print!("{}", render_payload_chart(&payloads, timezone));
+    println!();
+    println!(
+        "Latest: {} compressed to {}, largest file {} ({})",
+        format_size(latest.size),
+        format_size(latest.compressed_size),
+        latest.largest_file,
+        format_size(latest.largest_file_size)
+    );
+    let doubled = payloads.iter().filter(|p| p.doubled()).count();
+    if doubled > 0 {
+        println!(
+            "{}",
+            format!("{doubled} submission(s) at least doubled the payload of the previous one")
+                .yellow()
+        );
+    }
     println!();
 
     Ok(())
@@ -0,0 +1,634 @@
+use crate::{
+    cli::args::{project_value_parser, InitArgs, InitTemplate, NetworkKind, Project, ProjectError},
+    core::{
+        import::{self, ImportedClass},
+        project::ProjectType,
+    },
+    filesystem::collector::package_contracts,
+    utils::voyager,
+};
+use anyhow::Result;
+use camino::Utf8Path;
+use colored::Colorize;
+use log::debug;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Contract name written for classes imported without one
+const NAME_PLACEHOLDER: &str = "REPLACE_WITH_CONTRACT_NAME";
+
+/// A file written by `voyager init`, relative to the project directory
+struct ScaffoldFile {
+    path: &'static str,
+    contents: String,
+}
+
+/// Handles the init command, writing a verification setup for a new project
+///
+/// # Errors
+///
+/// Returns an error if the deployments file cannot be imported, or a file or
+/// directory cannot be written.
+pub fn handle_init_command(args: &InitArgs) -> Result<()> {
+    let imported = match &args.from_deployments {
+        Some(path) => Some((path.as_path(), import::read(path)?)),
+        None => None,
+    };
+
+    // Without a template, the values come from the Scarb project
+    let survey = match (args.template, project_value_parser(args.path.as_str())) {
+        (Some(_), _) => None,
+        (None, Ok(project)) => Some(survey(&project)?),
+        (None, Err(ProjectError::MissingManifest(_))) if imported.is_some() => None,
+        (None, Err(ProjectError::MissingManifest(manifest))) => {
+            eprintln!("Error: No Scarb project found at {manifest}");
+            eprintln!(
+                "Run `voyager init` in a Scarb project, or start from a template with --template."
+            );
+            std::process::exit(1);
+        }
+        (None, Err(e)) => return Err(e.into()),
+    };
+
+    let packages = survey
+        .as_ref()
+        .map(Survey::contract_packages)
+        .unwrap_or_default();
+    let contracts = imported
+        .as_ref()
+        .map(|(path, classes)| imported_contracts(path, classes, &packages));
+    let config = survey.as_ref().map_or_else(
+        || config_template(args.template, &args.network, contracts.as_deref()),
+        |survey| survey_config(survey, &args.network, contracts.as_deref()),
+    );
+    let files = scaffold_files(config, !args.no_ci);
+
+    let existing: Vec<&str> = files
+        .iter()
+        .filter(|file| args.path.join(file.path).exists())
+        .map(|file| file.path)
+        .collect();
+    if !args.force && !existing.is_empty() {
+        eprintln!("Error: The following files already exist in {}:", args.path);
+        for path in existing {
+            eprintln!("  • {path}");
+        }
+        eprintln!("Use --force to overwrite them.");
+        std::process::exit(1);
+    }
+
+    write_files(&args.path, &files)?;
+
+    println!("\n{}", "Verification setup created".bold().underline());
+    if let Some(survey) = &survey {
+        println!("\n{}", survey.describe());
+    }
+    println!();
+    for file in &files {
+        println!("  {} {}", "✓".green().bold(), file.path);
+    }
+    println!("\nNext steps:");
+    match &imported {
+        Some((path, classes)) => {
+            println!(
+                "  1. Check the {} class(es) imported from {path}",
+                classes.len()
+            );
+            let unnamed = classes.iter().filter(|class| class.name.is_none()).count();
+            if unnamed > 0 {
+                println!(
+                    "     {unnamed} of them were recorded without a name: replace {NAME_PLACEHOLDER} in .voyager.toml"
+                );
+            }
+        }
+        None if survey.is_some() => println!(
+            "  1. Declare your contracts, then uncomment their [[contracts]] in .voyager.toml and set the class hashes"
+        ),
+        None => println!(
+            "  1. Declare your contracts and replace the class-hash placeholders in .voyager.toml"
+        ),
+    }
+    println!("  2. Run 'voyager verify --dry-run' to preview the submission");
+    println!("  3. Run 'voyager verify --watch' to verify every contract\n");
+
+    Ok(())
+}
+
+fn write_files(dir: &Utf8Path, files: &[ScaffoldFile]) -> std::io::Result<()> {
+    for file in files {
+        let path = dir.join(file.path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, &file.contents)?;
+    }
+    Ok(())
+}
+
+/// The files to write for `config`, optionally including CI snippets
+fn scaffold_files(config: String, ci: bool) -> Vec<ScaffoldFile> {
+    let mut files = vec![ScaffoldFile {
+        path: ".voyager.toml",
+        contents: config,
+    }];
+
+    if ci {
+        files.push(ScaffoldFile {
+            path: ".github/workflows/voyager-verify.yml",
+            contents: GITHUB_WORKFLOW.to_string(),
+        });
+        files.push(ScaffoldFile {
+            path: ".gitlab/voyager-verify.gitlab-ci.yml",
+            contents: GITLAB_CI.to_string(),
+        });
+    }
+
+    files
+}
+
+const fn template_name(template: InitTemplate) -> &'static str {
+    match template {
+        InitTemplate::Erc20 => "erc20",
+        InitTemplate::Erc721 => "erc721",
+        InitTemplate::DojoWorld => "dojo-world",
+    }
+}
+
+fn config_template(
+    template: Option<InitTemplate>,
+    network: &NetworkKind,
+    contracts: Option<&str>,
+) -> String {
+    let (project_type, placeholders) = match template {
+        Some(InitTemplate::Erc20) => ("scarb", CONTRACTS_ERC20),
+        Some(InitTemplate::Erc721) => ("scarb", CONTRACTS_ERC721),
+        Some(InitTemplate::DojoWorld) => ("dojo", CONTRACTS_DOJO_WORLD),
+        None => ("auto", ""),
+    };
+    let contracts = contracts.unwrap_or(placeholders);
+    let command = match template {
+        Some(template) if contracts == placeholders => {
+            format!(" --template {}", template_name(template))
+        }
+        Some(template) => format!(" --template {} --from-deployments", template_name(template)),
+        None => " --from-deployments".to_string(),
+    };
+
+    render_config(
+        &command,
+        network,
+        &format!("license = \"MIT\"\nproject-type = \"{project_type}\""),
+        contracts,
+    )
+}
+
+/// A `.voyager.toml` generated by `voyager init{command}`
+///
+/// `settings` follow the network in the `[voyager]` table, before the policy
+/// defaults, and `rest` comes last.
+fn render_config(command: &str, network: &NetworkKind, settings: &str, rest: &str) -> String {
+    format!(
+        r#"# Voyager verification config, generated by `voyager init{command}`
+# See .voyager.toml.example in the voyager-verifier repository for all options.
+
+[voyager]
+network = "{network}"
+{settings}
+
+# Wait for results and include Scarb.lock for reproducible builds
+watch = true
+lock-file = true
+
+# Policy defaults: only verify committed sources, and don't resubmit
+# classes that an earlier pipeline already verified
+require-clean-git = true
+skip-if-verified = true
+{rest}"#,
+        network = voyager::network_name(network),
+    )
+}
+
+/// What `voyager init` found in the Scarb project
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Survey {
+    project_type: ProjectType,
+    /// SPDX identifier declared by every member, if they agree on one
+    license: Option<String>,
+    /// Workspace members, with the contracts each defines
+    packages: Vec<(String, Vec<String>)>,
+}
+
+/// Inspect the packages, license and project type of `project`
+fn survey(project: &Project) -> Result<Survey, ProjectError> {
+    let metadata = project.load()?;
+    let project_type = project.detect_project_type()?;
+
+    let members: Vec<_> = metadata
+        .packages
+        .iter()
+        .filter(|package| metadata.workspace.members.contains(&package.id))
+        .collect();
+    let licenses = members
+        .iter()
+        .map(|member| {
+            Ok(project
+                .get_license(Some(&member.name))?
+                .map(|license| license.name.to_string()))
+        })
+        .collect::<Result<Vec<Option<String>>, ProjectError>>()?;
+    let license = match licenses.split_first() {
+        Some((first, rest)) if rest.iter().all(|license| license == first) => first.clone(),
+        _ => None,
+    };
+
+    let packages = members
+        .iter()
+        .map(|member| {
+            let contracts = package_contracts(member).map_or_else(
+                |e| {
+                    debug!("Failed to list contracts of {}: {e}", member.name);
+                    Vec::new()
+                },
+                |contracts| {
+                    contracts
+                        .into_iter()
+                        .map(|contract| contract.name)
+                        .collect()
+                },
+            );
+            (member.name.clone(), contracts)
+        })
+        .collect();
+
+    Ok(Survey {
+        project_type,
+        license,
+        packages,
+    })
+}
+
+impl Survey {
+    /// Whether the project has several members, so contracts name their package
+    const fn is_workspace(&self) -> bool {
+        self.packages.len() > 1
+    }
+
+    /// Packages defining each contract, when a contract needs its package
+    fn contract_packages(&self) -> BTreeMap<String, Vec<String>> {
+        let mut packages: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        if self.is_workspace() {
+            for (package, contracts) in &self.packages {
+                for contract in contracts {
+                    packages
+                        .entry(contract.clone())
+                        .or_default()
+                        .push(package.clone());
+                }
+            }
+        }
+        packages
+    }
+
+    /// One line on what was found
+    fn describe(&self) -> String {
+        let contracts: usize = self
+            .packages
+            .iter()
+            .map(|(_, contracts)| contracts.len())
+            .sum();
+        let license = self.license.as_ref().map_or_else(
+            || "without a common license".to_string(),
+            |license| format!("licensed {license}"),
+        );
+        format!(
+            "Found a {} project with {} package(s) and {contracts} contract(s), {license}",
+            project_type_name(self.project_type),
+            self.packages.len(),
+        )
+    }
+}
+
+const fn project_type_name(project_type: ProjectType) -> &'static str {
+    match project_type {
+        ProjectType::Scarb => "scarb",
+        ProjectType::Dojo => "dojo",
+        ProjectType::Auto => "auto",
+    }
+}
+
+/// A `.voyager.toml` with the values found in the project
+///
+/// `contracts` replaces the commented `[[contracts]]` examples written for
+/// the contracts the project defines.
+fn survey_config(survey: &Survey, network: &NetworkKind, contracts: Option<&str>) -> String {
+    let mut settings = String::new();
+    // Writing to a String cannot fail
+    let _ = match &survey.license {
+        Some(license) => writeln!(settings, "license = {}", toml::Value::from(license.as_str())),
+        None => writeln!(
+            settings,
+            "# No license shared by every package in Scarb.toml; set an SPDX identifier\n# license = \"MIT\""
+        ),
+    };
+    let _ = write!(
+        settings,
+        "project-type = \"{}\"",
+        project_type_name(survey.project_type)
+    );
+
+    let mut rest = String::new();
+    if survey.is_workspace() {
+        let _ = write!(
+            rest,
+            "\n# Workspace members: {}\n[workspace]\n# Package verified when --package is not given\n# default-package = \"{}\"\n",
+            survey
+                .packages
+                .iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            survey.packages[0].0
+        );
+    }
+    if survey.project_type == ProjectType::Dojo {
+        rest.push_str(
+            "\n# Systems, models and events can be verified from the manifest of `sozo migrate`:\n#   voyager verify --from-dojo-manifest\n",
+        );
+    }
+    match contracts {
+        Some(contracts) => rest.push_str(contracts),
+        None => rest.push_str(&contract_examples(survey)),
+    }
+
+    render_config("", network, &settings, &rest)
+}
+
+/// Commented `[[contracts]]` entries for the contracts of the project
+fn contract_examples(survey: &Survey) -> String {
+    let mut examples = String::new();
+    let contracts: Vec<_> = survey
+        .packages
+        .iter()
+        .flat_map(|(package, contracts)| contracts.iter().map(move |name| (package, name)))
+        .collect();
+    if contracts.is_empty() {
+        examples.push_str(
+            "\n# Contracts to verify in batch, with the class hashes printed by\n# `sncast declare` / `starkli declare`:\n# [[contracts]]\n# class-hash = \"0x...\"\n# contract-name = \"MyContract\"\n",
+        );
+        return examples;
+    }
+
+    examples.push_str(
+        "\n# Contracts found in the project. Uncomment those to verify in batch and set\n# the class hashes printed by `sncast declare` / `starkli declare`.\n",
+    );
+    for (package, name) in contracts {
+        let _ = writeln!(examples, "\n# [[contracts]]");
+        let _ = writeln!(examples, "# class-hash = \"0xREPLACE_WITH_CLASS_HASH\"");
+        let _ = writeln!(examples, "# contract-name = \"{name}\"");
+        if survey.is_workspace() {
+            let _ = writeln!(examples, "# package = \"{package}\"");
+        }
+    }
+    examples
+}
+
+/// `[[contracts]]` entries for the classes imported from `path`
+///
+/// `packages` gives the packages defining each contract in a workspace; a
+/// contract defined by a single one is written with its package.
+fn imported_contracts(
+    path: &Utf8Path,
+    classes: &[ImportedClass],
+    packages: &BTreeMap<String, Vec<String>>,
+) -> String {
+    let mut contracts = format!("\n# Imported from {path}\n");
+    for class in classes {
+        // Writing to a String cannot fail
+        let _ = writeln!(contracts, "[[contracts]]");
+        let _ = writeln!(contracts, "class-hash = \"{}\"", class.class_hash);
+        let _ = match &class.name {
+            Some(name) => writeln!(
+                contracts,
+                "contract-name = {}",
+                toml::Value::from(name.as_str())
+            ),
+            None => writeln!(
+                contracts,
+                "contract-name = \"{NAME_PLACEHOLDER}\"  # not recorded by the deployment tool"
+            ),
+        };
+        let package = class
+            .name
+            .as_ref()
+            .and_then(|name| packages.get(name))
+            .and_then(|packages| match packages.as_slice() {
+                [package] => Some(package),
+                _ => None,
+            });
+        if let Some(package) = package {
+            let _ = writeln!(
+                contracts,
+                "package = {}",
+                toml::Value::from(package.as_str())
+            );
+        }
+        if !class.addresses.is_empty() {
+            let _ = writeln!(contracts, "# Deployed at {}", class.addresses.join(", "));
+        }
+        contracts.push('\n');
+    }
+    contracts.pop();
+    contracts
+}
+
+const CONTRACTS_ERC20: &str = r#"
+# Replace the class hash with the one printed by `sncast declare` / `starkli declare`
+[[contracts]]
+class-hash = "0xREPLACE_WITH_CLASS_HASH"
+contract-name = "MyToken"
+"#;
+
+const CONTRACTS_ERC721: &str = r#"
+# Replace the class hash with the one printed by `sncast declare` / `starkli declare`
+[[contracts]]
+class-hash = "0xREPLACE_WITH_CLASS_HASH"
+contract-name = "MyNFT"
+"#;
+
+const CONTRACTS_DOJO_WORLD: &str = r#"
+# One entry per system; class hashes are listed in the manifest written by `sozo migrate`
+[[contracts]]
+class-hash = "0xREPLACE_WITH_CLASS_HASH"
+contract-name = "actions"
+"#;
+
+const GITHUB_WORKFLOW: &str = r#"# Verifies contracts on Voyager, using the settings in .voyager.toml
+name: Verify contracts
+
+on:
+  workflow_dispatch:
+  push:
+    tags: ["v*"]
+
+jobs:
+  verify:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: software-mansion/setup-scarb@v1
+      - name: Install voyager-verifier
+        run: cargo install voyager-verifier
+      - name: Verify
+        run: voyager verify --results voyager-results.json
+      - uses: actions/upload-artifact@v4
+        if: always()
+        with:
+          name: voyager-results
+          path: voyager-results.json
+"#;
+
+const GITLAB_CI: &str = r#"# Verifies contracts on Voyager, using the settings in .voyager.toml
+# Include it from .gitlab-ci.yml:
+#   include:
+#     - local: .gitlab/voyager-verify.gitlab-ci.yml
+verify-contracts:
+  image: rust:latest
+  rules:
+    - if: $CI_COMMIT_TAG
+    - when: manual
+  before_script:
+    - curl --proto '=https' --tlsv1.2 -sSf https://docs.swmansion.com/scarb/install.sh | sh
+    - export PATH="$HOME/.local/bin:$PATH"
+    - cargo install voyager-verifier
+  script:
+    - voyager verify --results voyager-results.json
+  artifacts:
+    when: always
+    paths:
+      - voyager-results.json
+"#;
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::cli::config::Config;
+    use camino::Utf8PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_config_templates_parse() {
+        for template in [
+            InitTemplate::Erc20,
+            InitTemplate::Erc721,
+            InitTemplate::DojoWorld,
+        ] {
+            let config: Config = toml::from_str(&config_template(
+                Some(template),
+                &NetworkKind::Mainnet,
+                None,
+            ))
+            .unwrap();
+            assert_eq!(config.voyager.network.as_deref(), Some("mainnet"));
+            assert_eq!(config.voyager.require_clean_git, Some(true));
+            assert_eq!(config.contracts.len(), 1);
+        }
+
+        let dojo: Config = toml::from_str(&config_template(
+            Some(InitTemplate::DojoWorld),
+            &NetworkKind::Sepolia,
+            None,
+        ))
+        .unwrap();
+        assert_eq!(dojo.voyager.project_type.as_deref(), Some("dojo"));
+    }
+
+    #[test]
+    fn test_imported_contracts_replace_placeholders() {
+        let mut classes = import::parse(
+            r#"{
+                "MyToken": { "class_hash": "0x044d", "address": "0x1" },
+                "Vault": { "class_hash": "0x0def" }
+            }"#,
+        )
+        .unwrap();
+        // As sncast records declarations without a name
+        classes[1].name = None;
+        let packages = BTreeMap::from([("MyToken".to_string(), vec!["token".to_string()])]);
+        let contracts = imported_contracts(Utf8Path::new("deployments.json"), &classes, &packages);
+        assert!(contracts.contains("# Deployed at 0x1\n"));
+
+        let template = config_template(None, &NetworkKind::Sepolia, Some(&contracts));
+        assert!(template.contains("`voyager init --from-deployments`"));
+        let config: Config = toml::from_str(&template).unwrap();
+        assert_eq!(config.voyager.project_type.as_deref(), Some("auto"));
+        let names: Vec<_> = config
+            .contracts
+            .iter()
+            .map(|contract| contract.contract_name.as_str())
+            .collect();
+        assert_eq!(names, ["MyToken", NAME_PLACEHOLDER]);
+        assert_eq!(config.contracts[0].package.as_deref(), Some("token"));
+
+        let dojo = config_template(
+            Some(InitTemplate::DojoWorld),
+            &NetworkKind::Sepolia,
+            Some(&contracts),
+        );
+        let config: Config = toml::from_str(&dojo).unwrap();
+        assert_eq!(config.voyager.project_type.as_deref(), Some("dojo"));
+        assert_eq!(config.contracts.len(), 2);
+    }
+
+    #[test]
+    fn test_survey_config() {
+        let mut survey = Survey {
+            project_type: ProjectType::Scarb,
+            license: Some("Apache-2.0".to_string()),
+            packages: vec![("token".to_string(), vec!["MyToken".to_string()])],
+        };
+        let config: Config =
+            toml::from_str(&survey_config(&survey, &NetworkKind::Mainnet, None)).unwrap();
+        assert_eq!(config.voyager.license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(config.voyager.project_type.as_deref(), Some("scarb"));
+        assert!(config.contracts.is_empty());
+        assert!(survey.contract_packages().is_empty());
+
+        survey.project_type = ProjectType::Dojo;
+        survey.license = None;
+        survey
+            .packages
+            .push(("game".to_string(), vec!["actions".to_string()]));
+        let text = survey_config(&survey, &NetworkKind::Sepolia, None);
+        assert!(text.contains("# contract-name = \"actions\"\n# package = \"game\"\n"));
+        assert!(text.contains("--from-dojo-manifest"));
+        assert!(text.contains("# Workspace members: token, game\n"));
+        let config: Config = toml::from_str(&text).unwrap();
+        assert_eq!(config.voyager.license, None);
+        assert_eq!(config.voyager.project_type.as_deref(), Some("dojo"));
+        assert_eq!(
+            survey.contract_packages().get("actions"),
+            Some(&vec!["game".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_scaffold_files_ci_toggle() {
+        assert_eq!(scaffold_files(String::new(), true).len(), 3);
+        assert_eq!(scaffold_files(String::new(), false).len(), 1);
+    }
+
+    #[test]
+    fn test_write_files_creates_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = Utf8PathBuf::try_from(temp_dir.path().to_path_buf()).unwrap();
+
+        let config = config_template(Some(InitTemplate::Erc721), &NetworkKind::Sepolia, None);
+        let files = scaffold_files(config, true);
+        write_files(&dir, &files).unwrap();
+
+        assert!(dir.join(".voyager.toml").exists());
+        assert!(dir.join(".github/workflows/voyager-verify.yml").exists());
+        assert!(dir.join(".gitlab/voyager-verify.gitlab-ci.yml").exists());
+    }
+}
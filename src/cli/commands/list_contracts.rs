@@ -0,0 +1,88 @@
+use crate::{
+    cli::args::ListContractsArgs,
+    core::project::select_package,
+    filesystem::collector::{package_contracts, ContractDefinition},
+};
+use anyhow::Result;
+use camino::Utf8Path;
+use colored::Colorize;
+use serde::Serialize;
+
+/// Contracts found in one workspace package
+#[derive(Debug, Serialize)]
+struct PackageContracts {
+    package: String,
+    contracts: Vec<ContractDefinition>,
+}
+
+/// Handles the list-contracts command for showing the contracts defined in a
+/// project
+///
+/// # Errors
+///
+/// Returns an error if the project cannot be loaded, `--package` is not a
+/// workspace member, or the sources of a package cannot be collected.
+pub fn handle_list_contracts_command(args: &ListContractsArgs) -> Result<()> {
+    let metadata = args.path.load()?;
+    let package = args
+        .package
+        .as_deref()
+        .map(|name| select_package(metadata, Some(name)))
+        .transpose()?;
+
+    let mut packages = Vec::new();
+    for member in metadata
+        .packages
+        .iter()
+        .filter(|pkg| metadata.workspace.members.contains(&pkg.id))
+        .filter(|pkg| package.as_ref().is_none_or(|name| &pkg.name == name))
+    {
+        packages.push(PackageContracts {
+            package: member.name.clone(),
+            contracts: package_contracts(member)?,
+        });
+    }
+    packages.sort_by(|a, b| a.package.cmp(&b.package));
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&packages).unwrap_or_else(|_| format!("{packages:?}"))
+        );
+        return Ok(());
+    }
+
+    display_contracts(&packages, &metadata.workspace.root);
+    Ok(())
+}
+
+fn display_contracts(packages: &[PackageContracts], root: &Utf8Path) {
+    if packages.iter().all(|package| package.contracts.is_empty()) {
+        println!("\nNo #[starknet::contract] modules found in the project sources.\n");
+        return;
+    }
+
+    println!("\n{}", "Contracts".bold().underline());
+    for package in packages {
+        println!("\n{}", package.package.bold());
+        if package.contracts.is_empty() {
+            println!("  {}", "no contracts".bright_black());
+            continue;
+        }
+        let width = package
+            .contracts
+            .iter()
+            .map(|contract| contract.name.len())
+            .max()
+            .unwrap_or(0);
+        for contract in &package.contracts {
+            let file = contract.file.strip_prefix(root).unwrap_or(&contract.file);
+            println!(
+                "  {}  {}",
+                format!("{:<width$}", contract.name).cyan(),
+                file.as_str().bright_black()
+            );
+        }
+    }
+    println!("\nPass one of these names to 'voyager verify --contract-name'.\n");
+}
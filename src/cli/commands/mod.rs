@@ -1,4 +1,20 @@
+pub mod analyze;
 pub mod check;
+pub mod contracts;
+pub mod daemon;
+pub mod deps;
+#[cfg(feature = "dev-tools")]
+pub mod dev;
+pub mod diff_dryrun;
+pub mod explain;
+pub mod flatten;
 pub mod history;
+pub mod init;
+pub mod list_contracts;
+pub mod pack;
+pub mod reproduce;
+pub mod search;
+pub mod share;
 pub mod status;
 pub mod verify;
+pub mod verify_upgrade;
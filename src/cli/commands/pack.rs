@@ -0,0 +1,93 @@
+use crate::{
+    cli::{args::PackArgs, config::Config},
+    core::{
+        analyze::format_size,
+        pack::{Pack, EXTENSION},
+        piped::PipedManifest,
+        verification::collect_payload,
+    },
+    utils::{errors::CliError, license},
+};
+use anyhow::Result;
+use camino::Utf8PathBuf;
+
+/// Handles the pack command, writing the sources of a contract to a
+/// verification package
+///
+/// # Errors
+///
+/// Returns an error if the project cannot be loaded, its files cannot be
+/// collected or read, or the package cannot be written.
+pub fn handle_pack_command(args: PackArgs, config: Option<&Config>) -> Result<()> {
+    let PackArgs { output, verify } = args;
+    // Merge config with CLI args (CLI args take precedence)
+    let args = if let Some(cfg) = config {
+        verify.merge_with_config(cfg)
+    } else {
+        verify
+    };
+
+    if args.stdin_tar
+        || args.from_pack.is_some()
+        || args.all_deployed
+        || args.from_dojo_manifest.is_some()
+        || args.world.is_some()
+        || args.wizard
+    {
+        eprintln!(
+            "Error: --stdin-tar, --from-pack, --all-deployed, --from-dojo-manifest, --world and --wizard cannot be used with pack"
+        );
+        eprintln!("The sources are collected from the project at --path.");
+        std::process::exit(1);
+    }
+    let Some(class_hash) = args.class_hash.clone() else {
+        eprintln!("Error: --class-hash is required to pack a contract");
+        std::process::exit(1);
+    };
+    let Some(contract_name) = args.contract_name.clone() else {
+        eprintln!("Error: --contract-name is required to pack a contract");
+        std::process::exit(1);
+    };
+
+    let metadata = args.path.load()?;
+    let license_info = license::resolve_license_info(
+        args.license,
+        args.path.get_license(args.package.as_deref())?,
+        args.path.manifest_path()?,
+    );
+    license::warn_if_no_license(&license_info);
+    let (preview, file_infos) = collect_payload(&args, &license_info)?;
+
+    let manifest = PipedManifest {
+        cairo_version: metadata.app_version_info.cairo.version.clone(),
+        scarb_version: metadata.app_version_info.version.clone(),
+        package_name: preview.package_name,
+        contract_file: preview.contract_file,
+        project_dir_path: preview.project_dir_path,
+        // Dojo projects are built with sozo
+        build_tool: if preview.build_tool == "dojo" {
+            "sozo".to_string()
+        } else {
+            "scarb".to_string()
+        },
+        dojo_version: preview.dojo_version,
+        class_hash: Some(class_hash.to_string()),
+        contract_name: Some(contract_name.clone()),
+        license: (!license_info.is_none()).then(|| license_info.display_string().to_string()),
+    };
+    let pack = Pack::new(manifest, &file_infos).map_err(|e| CliError::InternalError {
+        message: format!("failed to read project files: {e}"),
+    })?;
+
+    let output =
+        output.unwrap_or_else(|| Utf8PathBuf::from(format!("{contract_name}.{EXTENSION}")));
+    pack.write(&output)?;
+
+    println!(
+        "✓ Packed {} file(s) ({}) of {contract_name} ({class_hash}) into {output}",
+        pack.files.len(),
+        format_size(pack.payload_size())
+    );
+    println!("Submit it with: voyager verify --network <NETWORK> --from-pack {output}");
+    Ok(())
+}
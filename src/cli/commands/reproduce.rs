@@ -0,0 +1,151 @@
+use crate::{
+    cli::{args::ReproduceArgs, config::Config},
+    core::{
+        class_hash::compute,
+        project::select_package,
+        reproduce::{self, Reproduction, Toolchain},
+    },
+    storage::history::HistoryDb,
+};
+use anyhow::Result;
+use colored::Colorize;
+use log::warn;
+
+/// Handles the reproduce command for checking a deployed class against a local build
+///
+/// Exits with status 1 when the class hashes differ.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The package cannot be selected
+/// - The active Scarb is not the one pinned in `.tool-versions`
+/// - The local build fails
+/// - The contract has no compiled Sierra class
+/// - The class hash cannot be computed
+pub fn handle_reproduce_command(args: ReproduceArgs, config: Option<&Config>) -> Result<()> {
+    // Merge config with CLI args (CLI args take precedence)
+    let args = if let Some(cfg) = config {
+        args.merge_with_config(cfg)
+    } else {
+        args
+    };
+
+    let metadata = args.path.load()?;
+    let package = select_package(metadata, args.package.as_deref())?;
+    let local = Toolchain::local(metadata);
+    let pinned = Toolchain::pinned(&metadata.workspace.root);
+
+    // Fails unless the active Scarb is the pinned one
+    if !args.no_build {
+        eprintln!("Building {package} ...");
+        reproduce::build_package(metadata, &package)?;
+    }
+
+    let sierra_path = reproduce::sierra_class_path(metadata, &package, &args.contract_name)?;
+    let computed = compute::sierra_class_hash(&sierra_path)?;
+
+    let submitted = HistoryDb::open_read_only()
+        .and_then(|db| db.latest_for_class_hash(args.class_hash.as_ref()))
+        .map_err(|e| warn!("Failed to read verification history: {e}"))
+        .ok()
+        .flatten()
+        .map(|record| Toolchain {
+            scarb: Some(record.scarb_version),
+            cairo: Some(record.cairo_version),
+        });
+
+    let report = Reproduction {
+        package,
+        contract_name: args.contract_name.clone(),
+        expected: args.class_hash.to_string(),
+        computed: computed.to_string(),
+        sierra_path,
+        local,
+        pinned,
+        submitted,
+    };
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_else(|_| format!("{report:?}"))
+        );
+    } else {
+        display_report(&report);
+    }
+
+    if !report.matches() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn display_report(report: &Reproduction) {
+    println!(
+        "\n{}",
+        format!(
+            "Reproduction of {} (package {})",
+            report.contract_name, report.package
+        )
+        .bold()
+        .underline()
+    );
+    println!();
+    println!("  Expected class hash: {}", report.expected);
+    println!("  Local class hash:    {}", report.computed);
+    println!("  Sierra class:        {}", report.sierra_path);
+
+    let scarb = |toolchain: Option<&Toolchain>| toolchain.and_then(|t| t.scarb.clone());
+    let cairo = |toolchain: Option<&Toolchain>| toolchain.and_then(|t| t.cairo.clone());
+    println!();
+    println!(
+        "  {:<10} {:<12} {:<12} Submitted",
+        "Toolchain", "Local", "Pinned"
+    );
+    for (name, versions) in [
+        (
+            "Scarb",
+            [
+                scarb(Some(&report.local)),
+                scarb(report.pinned.as_ref()),
+                scarb(report.submitted.as_ref()),
+            ],
+        ),
+        (
+            "Cairo",
+            [
+                cairo(Some(&report.local)),
+                cairo(report.pinned.as_ref()),
+                cairo(report.submitted.as_ref()),
+            ],
+        ),
+    ] {
+        let [local, pinned, submitted] = versions.map(|v| v.unwrap_or_else(|| "-".to_string()));
+        println!("  {name:<10} {local:<12} {pinned:<12} {submitted}");
+    }
+
+    println!();
+    if report.matches() {
+        println!(
+            "{} Class hash matches: these sources compile to the expected class.",
+            "✓".green().bold()
+        );
+        return;
+    }
+
+    println!("{} Class hash mismatch.", "✗".red().bold());
+    let differs = |other: Option<&Toolchain>| {
+        other.is_some_and(|other| {
+            other.scarb.is_some() && other.scarb != report.local.scarb
+                || other.cairo.is_some() && other.cairo != report.local.cairo
+        })
+    };
+    if differs(report.pinned.as_ref()) || differs(report.submitted.as_ref()) {
+        println!(
+            "The local toolchain differs from the pinned or submitted one; switch to that Scarb version and retry."
+        );
+    } else {
+        println!("Check that the sources are at the revision that was deployed.");
+    }
+}
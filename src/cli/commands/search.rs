@@ -0,0 +1,116 @@
+use crate::{
+    api::{ApiClient, ClassVerificationInfo},
+    cli::{
+        args::SearchArgs,
+        config::{self, Config},
+    },
+    output::status::format_timestamp,
+    utils::errors::CliError,
+};
+use anyhow::Result;
+use colored::Colorize;
+
+/// Handles the search command for finding class hashes by contract name
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - API client creation fails
+/// - The API does not support search
+/// - The search request fails
+pub fn handle_search_command(args: SearchArgs, config: Option<&Config>) -> Result<()> {
+    // Merge config with CLI args (CLI args take precedence)
+    let args = if let Some(cfg) = config {
+        args.merge_with_config(cfg)
+    } else {
+        args
+    };
+
+    if let Err(err) = args.validate() {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
+
+    let api_client =
+        config::with_configured_api_key(ApiClient::new(args.network_url.url()?.clone())?, config);
+    let mut classes = api_client
+        .search_classes(args.name.trim())
+        .map_err(CliError::from)?;
+
+    if args.verified_only {
+        classes.retain(|info| info.verified);
+    }
+    // Verified classes first, most recently verified on top
+    classes.sort_by(|a, b| {
+        b.verified.cmp(&a.verified).then(
+            b.verified_timestamp
+                .unwrap_or_default()
+                .total_cmp(&a.verified_timestamp.unwrap_or_default()),
+        )
+    });
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&classes).unwrap_or_else(|_| format!("{classes:?}"))
+        );
+    } else {
+        display_search_results(&args, &classes);
+    }
+
+    Ok(())
+}
+
+fn display_search_results(args: &SearchArgs, classes: &[ClassVerificationInfo]) {
+    if classes.is_empty() {
+        println!(
+            "\n{} No classes found matching '{}'",
+            "!".yellow().bold(),
+            args.name
+        );
+        println!();
+        return;
+    }
+
+    println!(
+        "\n{}",
+        format!("Classes matching '{}' ({})", args.name, classes.len())
+            .bold()
+            .underline()
+    );
+    println!();
+
+    for info in classes {
+        if info.verified {
+            println!(
+                "{} {} {}",
+                "✓".green().bold(),
+                info.class_hash.cyan(),
+                "verified".green()
+            );
+        } else {
+            println!(
+                "{} {} {}",
+                "✗".red().bold(),
+                info.class_hash.cyan(),
+                "not verified".yellow()
+            );
+        }
+        if let Some(ref name) = info.name {
+            println!("  Name: {name}");
+        }
+        if let Some(ref version) = info.version {
+            println!("  Version: {version}");
+        }
+        if let Some(ts) = info.verified_timestamp {
+            println!(
+                "  Verified: {}",
+                format_timestamp(ts, args.timezone.unwrap_or_default())
+            );
+        }
+    }
+    println!();
+    println!(
+        "Use 'voyager check --class-hash <HASH>' for details, or 'voyager verify --class-hash <HASH>' to verify an unverified class."
+    );
+}
@@ -0,0 +1,40 @@
+use crate::{
+    cli::{args::ShareArgs, config::Config},
+    output::share::JobShare,
+    storage::history::HistoryDb,
+};
+use anyhow::Result;
+use log::warn;
+
+/// Handles the share command, printing a markdown snippet for a job
+///
+/// Exits with an error if neither `--network`, `--url` nor the configuration
+/// name the API the job was submitted to.
+///
+/// # Errors
+///
+/// Returns an error if no API is selected.
+pub fn handle_share_command(args: ShareArgs, config: Option<&Config>) -> Result<()> {
+    let mut args = args;
+    if let Some(cfg) = config {
+        if args.network.is_none() {
+            args.network = cfg.parse_network();
+        }
+        args.network_url
+            .merge_with_config(args.network.as_ref(), cfg);
+    }
+    if let Err(err) = args.network_url.validate() {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
+
+    let mut share = JobShare::new(&args.job, args.network_url.url()?, args.network);
+    share.contract = HistoryDb::open_read_only()
+        .and_then(|db| db.get_by_job_id(&args.job))
+        .map_err(|e| warn!("Failed to read verification history: {e}"))
+        .ok()
+        .flatten()
+        .map(|record| (record.contract_name, record.class_hash));
+    print!("{}", share.to_markdown());
+    Ok(())
+}
@@ -1,14 +1,32 @@
 use crate::{
-    api::{ApiClient, ApiClientError},
-    cli::{args::StatusArgs, config::Config},
-    core::verification::{check, display_verbose_error},
-    utils::errors::CliError,
+    api::{ApiClient, ApiClientError, ClassVerification},
+    cli::{
+        args::{OutputFormat, StatusArgs},
+        config::{self, Config},
+    },
+    core::{
+        class_hash::ClassHash,
+        mismatch::SubmissionFacts,
+        reproduce::Toolchain,
+        verification::{check, display_verbose_error},
+    },
+    output::{mismatch, sink::SinkProgress, status::ClassCheckOutput},
+    storage::history::{HistoryDb, VerificationRecord},
+    utils::{errors::CliError, voyager},
 };
 use anyhow::Result;
-use log::info;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use log::{info, warn};
+use serde::Serialize;
+use std::sync::Arc;
 
 /// Handles the status command for checking verification job status
 ///
+/// With `--class-hash`, the latest job for the class in local history is
+/// checked. If history has none, the class's verification status is read
+/// from the API instead.
+///
 /// # Errors
 ///
 /// Returns an error if:
@@ -16,6 +34,11 @@ use log::info;
 /// - API client creation fails
 /// - Status check request fails
 pub fn handle_status_command(args: StatusArgs, config: Option<&Config>) -> Result<()> {
+    let args = match config::selected_endpoint(args.endpoint.as_deref(), config)? {
+        Some(endpoint) => args.with_endpoint(endpoint),
+        None => args,
+    };
+
     // Merge config with CLI args (CLI args take precedence)
     let args = if let Some(cfg) = config {
         args.merge_with_config(cfg)
@@ -29,24 +52,223 @@ pub fn handle_status_command(args: StatusArgs, config: Option<&Config>) -> Resul
         std::process::exit(1);
     }
 
-    let api_client = ApiClient::new(args.network_url.url.clone())?;
-    let status = check(&api_client, &args.job, &args.format).inspect_err(|e| {
+    let api_client = ApiClient::new(args.network_url.url()?.clone())?;
+    let api_client = config::with_polling(
+        match args.api_key.as_deref() {
+            Some(api_key) => api_client.with_api_key(api_key),
+            None => api_client,
+        },
+        config,
+        args.polling(),
+    )
+    .with_progress_observer(Arc::new(SinkProgress::default()));
+
+    let job_id = match (&args.job, &args.class_hash) {
+        (Some(job_id), _) => job_id.clone(),
+        (None, Some(class_hash)) => {
+            let Some(record) = latest_job(&args, class_hash) else {
+                return report_class_status(&api_client, class_hash, args.format);
+            };
+            if args.format == OutputFormat::Text {
+                println!(
+                    "Latest job for {} ({}, submitted {}): {}",
+                    class_hash.to_string().cyan(),
+                    record.contract_name,
+                    record.submitted_at.format("%Y-%m-%d %H:%M"),
+                    record.job_id
+                );
+            }
+            record.job_id
+        }
+        (None, None) => unreachable!("clap requires --job or --class-hash"),
+    };
+
+    let result = check(
+        &api_client,
+        &job_id,
+        &args.format,
+        args.timezone.unwrap_or_default(),
+        args.verbose,
+    );
+    if let Err(CliError::Api(ref error)) = result {
+        if let Some(record) = error
+            .is_unreachable()
+            .then(|| history_record(&job_id))
+            .flatten()
+        {
+            print_offline_status(&record, error, args.format);
+            return Ok(());
+        }
+    }
+    let status = result.inspect_err(|e| {
         if args.verbose {
             display_verbose_error(e);
         }
         display_error_suggestions(e);
+        if args.format == OutputFormat::Text {
+            mismatch::explain_failure(&api_client, &job_id, e, &submission_facts(&job_id));
+        }
     })?;
     info!("{status:?}");
 
     Ok(())
 }
 
+/// Last known state of a job while the API cannot be reached
+#[derive(Serialize)]
+struct OfflineStatus<'a> {
+    offline: bool,
+    as_of: DateTime<Utc>,
+    job_id: &'a str,
+    status: &'a str,
+    class_hash: &'a str,
+    contract_name: &'a str,
+    network: &'a str,
+    submitted_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+/// Prints the state of a job recorded in history, marked as offline data
+fn print_offline_status(record: &VerificationRecord, error: &ApiClientError, format: OutputFormat) {
+    // History is written on submission and whenever a job finishes
+    let as_of = record.completed_at.unwrap_or(record.submitted_at);
+    let reason = error.to_string();
+    let reason = reason.lines().next().unwrap_or_default();
+
+    if format == OutputFormat::Json {
+        let offline = OfflineStatus {
+            offline: true,
+            as_of,
+            job_id: &record.job_id,
+            status: &record.status,
+            class_hash: &record.class_hash,
+            contract_name: &record.contract_name,
+            network: &record.network,
+            submitted_at: record.submitted_at,
+            completed_at: record.completed_at,
+        };
+        match serde_json::to_string_pretty(&offline) {
+            Ok(json) => println!("{json}"),
+            Err(e) => warn!("Failed to serialize offline status: {e}"),
+        }
+        return;
+    }
+
+    eprintln!(
+        "{} Offline data as of {}: the API could not be reached ({reason})",
+        "⚠️ ".yellow(),
+        as_of.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    println!("\nJob ID:    {}", record.job_id);
+    println!("Status:    {} (last known)", record.status.bold());
+    println!("Contract:  {}", record.contract_name);
+    println!("Class:     {}", record.class_hash);
+    println!("Network:   {}", record.network);
+    println!(
+        "Submitted: {}",
+        record.submitted_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    if let Some(completed_at) = record.completed_at {
+        println!(
+            "Completed: {}",
+            completed_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+    }
+}
+
+/// The history record of a job, if history can be read
+fn history_record(job_id: &str) -> Option<VerificationRecord> {
+    HistoryDb::open_read_only()
+        .and_then(|db| db.get_by_job_id(job_id))
+        .map_err(|e| warn!("Failed to read verification history: {e}"))
+        .ok()
+        .flatten()
+}
+
+/// The toolchain a job was submitted with, from history
+fn submission_facts(job_id: &str) -> SubmissionFacts {
+    let toolchain = history_record(job_id).map(|record| Toolchain {
+        scarb: Some(record.scarb_version),
+        cairo: Some(record.cairo_version),
+    });
+    SubmissionFacts {
+        toolchain,
+        ..SubmissionFacts::default()
+    }
+}
+
+/// The latest job for `class_hash` in history, on the selected network when
+/// it is a known one
+fn latest_job(args: &StatusArgs, class_hash: &ClassHash) -> Option<VerificationRecord> {
+    let network = args.network.clone().or_else(|| {
+        args.network_url
+            .url()
+            .ok()
+            .and_then(voyager::network_for_api_url)
+    });
+
+    HistoryDb::open_read_only()
+        .and_then(|db| {
+            db.latest_job_for_class_hash(
+                class_hash.as_ref(),
+                network.as_ref().map(voyager::network_name),
+            )
+        })
+        .map_err(|e| warn!("Failed to read verification history: {e}"))
+        .ok()
+        .flatten()
+}
+
+/// Reports whether a class without a job in local history is verified
+fn report_class_status(
+    api_client: &ApiClient,
+    class_hash: &ClassHash,
+    format: OutputFormat,
+) -> Result<()> {
+    let verification = api_client
+        .check_class_verification(class_hash)
+        .map_err(CliError::from)?;
+
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            ClassCheckOutput::new(&verification, class_hash).to_json()
+        );
+        return Ok(());
+    }
+
+    println!("No verification job for {class_hash} in local history.");
+    match verification {
+        ClassVerification::Verified(info) => println!(
+            "{} The class is {} on Voyager{}",
+            "✓".green().bold(),
+            "verified".green().bold(),
+            info.name
+                .as_deref()
+                .map(|name| format!(" as {name}"))
+                .unwrap_or_default()
+        ),
+        ClassVerification::NotVerified => println!(
+            "{} The class is {} on Voyager; submit it with `voyager verify`",
+            "✗".red().bold(),
+            "not verified".yellow().bold()
+        ),
+        ClassVerification::NotDeclared => println!(
+            "{} The class is {}; declare it before verifying",
+            "!".yellow().bold(),
+            "not declared on this network".yellow().bold()
+        ),
+    }
+
+    Ok(())
+}
+
 /// Displays error suggestions based on the error type
 fn display_error_suggestions(error: &CliError) {
     if let CliError::Api(ApiClientError::Verify(ref verification_error)) = error {
         eprintln!("\nSuggestions:");
         for suggestion in verification_error.suggestions() {
-            eprintln!("  • {suggestion}");
+            eprintln!("  • {}", suggestion.text);
         }
     }
     // RequestFailure errors already include suggestions in their display
@@ -1,15 +1,51 @@
 use crate::{
-    api::{ApiClient, ApiClientError},
+    api::{
+        ApiClient, ApiClientError, MockServer, PollingConfig, RpcClient, DEFAULT_SIMULATION_STEP,
+    },
     cli::{
-        args::{OutputFormat, VerifyArgs},
-        config::Config,
-        wizard,
+        args::{Network, OutputFormat, UrlSource, VerifyArgs},
+        config::{self, Config},
+        prompt, wizard,
+    },
+    core::{
+        class_hash::ClassHash,
+        deployments,
+        mismatch::{ProjectFacts, SubmissionFacts},
+        pack::Pack,
+        piped::{submit_piped, submit_sources, SourceOrigin},
+        receipt,
+        reproduce::Toolchain,
+        verification::{
+            already_verified, check, display_verbose_error, display_verification_job_id,
+            infer_contract_name, preview_payload, record_verified_state, submit, submit_to_targets,
+            BatchVerificationSummary, Submission, Target, TargetJob,
+        },
+        world::world_resources,
+    },
+    output::{
+        metrics::{Metrics, MetricsServer},
+        mismatch, results,
+        share::JobShare,
+        sink::SinkProgress,
+        triage,
     },
-    core::verification::{check, display_verbose_error, display_verification_job_id, submit},
-    utils::{errors::CliError, license},
+    storage::{
+        batch::{BatchPlan, BatchStore},
+        preflight,
+        state::WorkspaceState,
+    },
+    utils::{errors::CliError, git, license, voyager},
 };
 use anyhow::Result;
-use log::info;
+use camino::{Utf8Path, Utf8PathBuf};
+use dialoguer::Confirm;
+use itertools::Itertools;
+use log::{debug, info, warn};
+use std::{
+    io::{self, IsTerminal},
+    sync::Arc,
+};
+use url::Url;
 
 /// Handles the verify command with both batch and single verification modes
 ///
@@ -21,29 +57,88 @@ use log::info;
 /// - Verification submission fails
 /// - Polling for verification status fails
 pub fn handle_verify_command(args: VerifyArgs, config: Option<&Config>) -> Result<()> {
+    verify_reporting_to(args, config, None)
+}
+
+/// Handles the verify command, counting in `shared_metrics` if given instead
+/// of serving metrics of its own
+///
+/// `voyager daemon` serves a single metrics endpoint across its runs.
+///
+/// # Errors
+///
+/// Returns an error under the conditions of [`handle_verify_command`].
+pub fn verify_reporting_to(
+    mut args: VerifyArgs,
+    config: Option<&Config>,
+    shared_metrics: Option<&Arc<Metrics>>,
+) -> Result<()> {
+    // The receipt's network takes precedence over the config file, so apply it first
+    if let Some(path) = args.from_receipt.clone() {
+        apply_receipt(&mut args, &path)?;
+    }
+
+    // An endpoint stands in for --network and --url, so the config file must not fill them
+    if let Some(endpoint) = config::selected_endpoint(args.endpoint.as_deref(), config)? {
+        args = args.with_endpoint(endpoint);
+    }
+
     // Merge config with CLI args (CLI args take precedence)
-    let args = if let Some(cfg) = config {
+    let mut args = if let Some(cfg) = config {
         args.merge_with_config(cfg)
     } else {
         args
     };
 
+    // The mock server must outlive submission and watching
+    let _simulation = if args.simulate {
+        let server = MockServer::start(DEFAULT_SIMULATION_STEP)?;
+        args.network_url = Network::new(server.url().clone(), UrlSource::CliFlag);
+        println!(
+            "Simulation mode: submitting to a local mock server at {}; nothing is sent to Voyager\n",
+            server.url()
+        );
+        Some(server)
+    } else {
+        None
+    };
+
+    // Like the mock server, the metrics server runs until the verifier exits
+    let metrics = match args.metrics_addr.filter(|_| shared_metrics.is_none()) {
+        Some(addr) => {
+            if !args.watch {
+                eprintln!("Error: --metrics-addr requires --watch");
+                std::process::exit(1);
+            }
+            let metrics = Arc::new(Metrics::default());
+            let server = MetricsServer::start(addr, Arc::clone(&metrics))?;
+            println!("Serving metrics at http://{}/metrics\n", server.addr());
+            Some((server, metrics))
+        }
+        None => None,
+    };
+    let metrics = shared_metrics.or_else(|| metrics.as_ref().map(|(_, metrics)| metrics));
+
+    // Piped sources need no project, and carry what single mode would require
+    if args.stdin_tar {
+        return handle_piped_verification(&args, config, metrics);
+    }
+    if let Some(path) = args.from_pack.clone() {
+        return handle_pack_verification(&args, &path, config, metrics);
+    }
+
     // Detect batch mode - convert Option<&Config> to &Option<Config>
-    let config_owned = config.cloned();
+    let mut config_owned = config.cloned();
     let is_batch = args.is_batch_mode(&config_owned);
 
     // Validate based on mode
     if !is_batch && !args.wizard {
-        // Single verification mode requires class_hash and contract_name
-        if args.class_hash.is_none() {
-            eprintln!("Error: --class-hash is required for single contract verification");
+        // Single verification mode requires class_hash; contract_name is
+        // inferred when the package defines a single contract
+        if args.class_hash.is_none() && args.contract_address.is_none() {
             eprintln!(
-                "Tip: Use --wizard for interactive mode or add [[contracts]] to .voyager.toml for batch mode"
+                "Error: --class-hash or --contract-address is required for single contract verification"
             );
-            std::process::exit(1);
-        }
-        if args.contract_name.is_none() {
-            eprintln!("Error: --contract-name is required for single contract verification");
             eprintln!(
                 "Tip: Use --wizard for interactive mode or add [[contracts]] to .voyager.toml for batch mode"
             );
@@ -51,15 +146,283 @@ pub fn handle_verify_command(args: VerifyArgs, config: Option<&Config>) -> Resul
         }
     }
 
+    // A custom API that cannot take the job fails before the project is read
+    check_custom_api(&args, config)?;
+
+    // Only read the project once the arguments are known to be usable
+    args.path.load()?;
+
+    if args.world.is_some() {
+        // Only what is not verified yet is submitted
+        args.skip_if_verified = true;
+    }
+    if args.all_deployed || args.from_dojo_manifest.is_some() || args.world.is_some() {
+        add_deployed_contracts(&args, &mut config_owned)?;
+    }
+
     if is_batch {
-        handle_batch_verification(&args, config_owned.as_ref())?;
+        handle_batch_verification(&args, config_owned.as_ref(), metrics)?;
+    } else {
+        handle_single_verification(args, config, metrics)?;
+    }
+
+    Ok(())
+}
+
+/// Check that an API selected with `--url` or the config file answers like
+/// the verifier API
+///
+/// The public APIs selected by network, simulations and dry runs are not
+/// checked.
+fn check_custom_api(args: &VerifyArgs, config: Option<&Config>) -> Result<()> {
+    if args.simulate || args.dry_run {
+        return Ok(());
+    }
+    if !matches!(
+        args.network_url.source(),
+        UrlSource::CliFlag | UrlSource::Config
+    ) {
+        return Ok(());
+    }
+    let url = args.network_url.url()?;
+    debug!("Checking the API at {url}");
+    api_client(
+        url,
+        args.api_key.as_deref(),
+        args.polling(),
+        args.compress,
+        config,
+        None,
+    )?
+    .health_check()
+    .map_err(CliError::from)?;
+    Ok(())
+}
+
+/// API client with the configured polling and payload transforms, compressing
+/// verification requests if `compress`, reporting to `metrics` if given
+fn api_client(
+    url: &Url,
+    api_key: Option<&str>,
+    polling: PollingConfig,
+    compress: bool,
+    config: Option<&Config>,
+    metrics: Option<&Arc<Metrics>>,
+) -> Result<ApiClient> {
+    let api_client = config::with_configured_transforms(
+        config::with_polling(ApiClient::new(url.clone())?, config, polling),
+        config,
+    )
+    .with_compression(compress);
+    let api_client = match api_key {
+        Some(api_key) => api_client.with_api_key(api_key),
+        None => api_client,
+    };
+    Ok(match metrics {
+        Some(metrics) => api_client.with_progress_observer(Arc::clone(metrics) as _),
+        None => api_client.with_progress_observer(Arc::new(SinkProgress::default())),
+    })
+}
+
+/// Take the class hash and network to verify from a deployment receipt
+///
+/// A network given on the command line must match the receipt's.
+///
+/// # Errors
+///
+/// Returns an error if the receipt cannot be read, has no unique class hash,
+/// or is from another network than the one selected.
+fn apply_receipt(args: &mut VerifyArgs, path: &Utf8Path) -> Result<(), CliError> {
+    let info = receipt::load(path)?;
+    println!("Using class hash {} from {path}", info.class_hash);
+    args.class_hash = Some(info.class_hash);
+
+    let Some(network) = info.network else {
+        return Ok(());
+    };
+    match &args.network {
+        Some(selected) if *selected != network => {
+            return Err(CliError::ReceiptNetworkMismatch {
+                path: path.to_path_buf(),
+                receipt: voyager::network_name(&network).to_string(),
+                selected: voyager::network_name(selected).to_string(),
+            });
+        }
+        None if !args.network_url.is_set() => {
+            println!(
+                "Using network {} from {path}",
+                voyager::network_name(&network)
+            );
+            args.network_url = Network::for_network(&network);
+            args.network = Some(network);
+        }
+        // The same network was given, or an explicit --url wins
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Fill the batch with the contracts of `--address-file` that the workspace
+/// defines, or the classes of `--from-dojo-manifest` or `--world`
+///
+/// Exits with an error if .voyager.toml lists `[[contracts]]` too, or if
+/// nothing is left to verify.
+///
+/// # Errors
+///
+/// Returns an error if the deployments file or manifest is invalid, or the
+/// world cannot be read from the node.
+fn add_deployed_contracts(args: &VerifyArgs, config: &mut Option<Config>) -> Result<()> {
+    let flag = if args.all_deployed {
+        "--all-deployed"
+    } else if args.world.is_some() {
+        "--world"
     } else {
-        handle_single_verification(args)?;
+        "--from-dojo-manifest"
+    };
+    let cfg = config.get_or_insert_with(Config::default);
+    if !cfg.contracts.is_empty() {
+        eprintln!("Error: Cannot use {flag} with [[contracts]] in .voyager.toml.");
+        eprintln!("Remove [[contracts]] from .voyager.toml or remove {flag} flag.");
+        std::process::exit(1);
     }
 
+    let (source, matched) = if let Some(world) = &args.world {
+        let matched = deployments::dojo_batch(registered_resources(args, world)?);
+        (format!("world {world}"), matched)
+    } else if let Some(manifest) = &args.from_dojo_manifest {
+        let path = match manifest {
+            Some(path) => path.clone(),
+            None => dojo_manifest_path(args.path.root_dir()?),
+        };
+        let matched = deployments::dojo_batch(deployments::read_dojo_manifest(&path)?);
+        (path.to_string(), matched)
+    } else {
+        // clap requires --address-file with --all-deployed
+        let Some(path) = args.address_file.clone() else {
+            return Ok(());
+        };
+        let metadata = args.path.load()?;
+        let members: Vec<_> = metadata
+            .packages
+            .iter()
+            .filter(|package| metadata.workspace.members.contains(&package.id))
+            .collect();
+        let matched = deployments::match_workspace(
+            deployments::read(&path)?,
+            &deployments::contract_packages(&members),
+        );
+        (path.to_string(), matched)
+    };
+    for (deployment, reason) in &matched.skipped {
+        let address = deployment
+            .address
+            .as_ref()
+            .map_or_else(String::new, |address| format!(" at {address}"));
+        println!("Skipping {}{address}: {reason}", deployment.name);
+    }
+    if matched.contracts.is_empty() && args.all_deployed {
+        eprintln!("Error: The workspace defines none of the contracts in {source}");
+        eprintln!("Run voyager list-contracts to see the contracts of each package.");
+        std::process::exit(1);
+    }
+    if matched.contracts.is_empty() {
+        eprintln!("Error: {source} has no contracts, models or events to verify");
+        eprintln!("Run sozo migrate to deploy the world before verifying it.");
+        std::process::exit(1);
+    }
+    println!(
+        "Verifying {} deployed contract(s) from {source}\n",
+        matched.contracts.len()
+    );
+    cfg.contracts = matched.contracts;
     Ok(())
 }
 
+/// The world class and the resources registered with the Dojo world at
+/// `world`, read from `--rpc-url`
+///
+/// Exits with an error if no RPC URL is given.
+///
+/// # Errors
+///
+/// Returns an error if the node is on another network, nothing is deployed
+/// at `world`, or a request fails.
+fn registered_resources(
+    args: &VerifyArgs,
+    world: &str,
+) -> Result<Vec<(deployments::DojoResource, deployments::Deployment)>> {
+    let Some(rpc_url) = args.rpc_url.clone() else {
+        eprintln!("Error: --rpc-url (or STARKNET_RPC_URL) is required for --world.");
+        eprintln!("The world's systems, models and events are read from a Starknet node.");
+        std::process::exit(1);
+    };
+    let rpc = RpcClient::new(rpc_url);
+    let network = args.network.clone().or_else(|| {
+        args.network_url
+            .url()
+            .ok()
+            .and_then(voyager::network_for_api_url)
+    });
+    if let Some(expected) = network.as_ref().and_then(voyager::chain_id) {
+        rpc.ensure_chain_id(expected).map_err(CliError::from)?;
+    }
+    println!("Reading the resources registered with world {world} ...");
+    Ok(world_resources(&rpc, world).map_err(CliError::from)?)
+}
+
+/// The only Dojo manifest of the project at `root`
+///
+/// Exits with an error if the project has none, or several to choose from.
+fn dojo_manifest_path(root: &Utf8Path) -> Utf8PathBuf {
+    match deployments::find_dojo_manifests(root).as_slice() {
+        [path] => {
+            println!("Using Dojo manifest {path}");
+            path.clone()
+        }
+        [] => {
+            eprintln!("Error: No Dojo manifest found in {root}");
+            eprintln!("Run sozo migrate first, or pass the manifest: --from-dojo-manifest <FILE>");
+            std::process::exit(1);
+        }
+        several => {
+            eprintln!("Error: Several Dojo manifests found in {root}:");
+            for path in several {
+                eprintln!("  {path}");
+            }
+            eprintln!("Select one with --from-dojo-manifest <FILE>");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Take the class hash to verify from the contract deployed at `address`
+///
+/// # Errors
+///
+/// Returns an error if the explorer does not know the contract or its class
+/// hash is invalid.
+fn resolve_contract_address(
+    mut args: VerifyArgs,
+    address: &str,
+    config: Option<&Config>,
+) -> Result<VerifyArgs> {
+    let class_hash = api_client(
+        args.network_url.url()?,
+        args.api_key.as_deref(),
+        args.polling(),
+        args.compress,
+        config,
+        None,
+    )?
+    .get_contract_class_hash(address)
+    .map_err(CliError::from)?;
+    let class_hash = ClassHash::new(&class_hash).map_err(CliError::from)?;
+    println!("Using class hash {class_hash} of contract {address}");
+    args.class_hash = Some(class_hash);
+    Ok(args)
+}
+
 /// Handles batch verification mode for multiple contracts
 ///
 /// # Errors
@@ -69,69 +432,288 @@ pub fn handle_verify_command(args: VerifyArgs, config: Option<&Config>) -> Resul
 /// - API client creation fails
 /// - Batch submission fails
 /// - Watch mode polling fails
-fn handle_batch_verification(args: &VerifyArgs, config: Option<&Config>) -> Result<()> {
-    // SAFETY: is_batch is only true when config contains [[contracts]], so config must be Some
-    let cfg = config.unwrap_or_else(|| {
-        unreachable!("Config must exist for batch mode - is_batch_mode() guarantees this")
-    });
+fn handle_batch_verification(
+    args: &VerifyArgs,
+    config: Option<&Config>,
+    metrics: Option<&Arc<Metrics>>,
+) -> Result<()> {
+    // Without --resume, is_batch is only true when config contains [[contracts]];
+    // a resumed batch takes its contracts from the batch plan instead
+    let mut cfg = config.cloned().unwrap_or_default();
 
-    // Validate: can't specify --class-hash in batch mode
-    if args.class_hash.is_some() {
-        eprintln!("Error: Cannot use --class-hash with batch verification.");
-        eprintln!("Remove [[contracts]] from .voyager.toml or remove --class-hash flag.");
-        std::process::exit(1);
+    reject_single_contract_options(args)?;
+
+    // Validate URL is set
+    args.validate().map_err(|_| CliError::ApiUrlUnset)?;
+    let api_url = args.network_url.url()?;
+
+    if args.bundle.is_some() {
+        eprintln!(
+            "Warning: --bundle is only written for single contract verification and will be ignored"
+        );
     }
+    ensure_clean_working_tree(args)?;
 
-    // Validate: can't use wizard mode with batch
-    if args.wizard {
-        eprintln!("Error: Cannot use --wizard with batch verification.");
-        eprintln!("Remove [[contracts]] from .voyager.toml or remove --wizard flag.");
-        std::process::exit(1);
+    // History is written after each submission, so surface storage problems first
+    if !args.dry_run && !args.simulate {
+        preflight::warn_on_state_issues();
     }
 
-    // Validate URL is set
-    if let Err(err) = args.validate() {
-        eprintln!("Error: {err}");
-        std::process::exit(1);
+    let api_client = api_client(
+        api_url,
+        args.api_key.as_deref(),
+        args.polling(),
+        args.compress,
+        config,
+        metrics,
+    )?;
+
+    // Licenses are resolved per contract package; only a single --license
+    // applied to every package can conflict with what members declare
+    if let Some(cli_license) = args.license {
+        confirm_workspace_licenses(args, &license::LicenseInfo::Cli(cli_license))?;
     }
 
-    let api_client = ApiClient::new(args.network_url.url.clone())?;
+    crate::core::verification::resolve_contract_packages(
+        &mut cfg.contracts,
+        args.path.load()?,
+        &cfg.workspace.members_filter,
+    )?;
 
-    let license_info = license::resolve_license_info(
-        args.license,
-        args.path.get_license(),
-        args.path.manifest_path(),
-    );
-    license::warn_if_no_license(&license_info);
+    // Dry runs and simulations submit nothing that would need resuming
+    let mut plan = if args.dry_run || args.simulate {
+        None
+    } else {
+        batch_plan(args, args.path.root_dir()?, api_url, &mut cfg)?
+    };
+
+    let mut state = args
+        .state
+        .as_ref()
+        .map(|path| {
+            WorkspaceState::load(path, api_url).map_err(|source| CliError::StateUnreadable {
+                path: path.clone(),
+                source,
+            })
+        })
+        .transpose()?;
 
     // Submit batch
-    let summary = crate::core::verification::submit_batch(&api_client, args, cfg, &license_info)
-        .inspect_err(|e| {
-            if args.verbose {
-                display_verbose_error(e);
-            }
-        })?;
+    let summary = crate::core::verification::submit_batch(
+        &api_client,
+        args,
+        &cfg,
+        plan.as_mut(),
+        state.as_ref(),
+    )
+    .inspect_err(|e| {
+        if args.verbose {
+            display_verbose_error(e);
+        }
+    })?;
 
-    // Display summary
-    crate::core::verification::display_batch_summary(&summary);
+    // Tables, CSV and JSON are only printed for the final state of a watched batch
+    let watching = args.watch && summary.submitted > 0;
+    if !watching || args.format == OutputFormat::Text {
+        print_batch_summary(args, api_url, &summary);
+    }
+
+    if let Some(ref plan) = plan {
+        finish_batch_plan(plan);
+    }
 
     // Watch mode
-    if args.watch && summary.submitted > 0 {
+    let summary = if watching {
         let final_summary =
-            crate::core::verification::watch_batch(&api_client, &summary, &OutputFormat::Text)
+            crate::core::verification::watch_batch(&api_client, &summary, &args.format)
                 .inspect_err(|e| {
                     if args.verbose {
                         display_verbose_error(e);
                     }
                 })?;
 
-        println!("\n=== Final Summary ===");
-        crate::core::verification::display_batch_summary(&final_summary);
+        if args.format == OutputFormat::Text {
+            println!("\n=== Final Summary ===");
+        }
+        print_batch_summary(args, api_url, &final_summary);
+        final_summary
+    } else {
+        summary
+    };
+
+    if let Some(ref path) = args.results {
+        results::write_batch_results(
+            path,
+            &summary,
+            args.network_url.url()?,
+            args.explorer().as_ref(),
+        )?;
+        println!("Results written to {path}");
+    }
+
+    // Dry runs and simulations verify nothing that could be recorded
+    if let Some(state) = state.as_mut().filter(|_| !args.dry_run && !args.simulate) {
+        save_verified_state(args, state, &summary, watching)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that `args` has no options that only apply to a single contract
+///
+/// # Errors
+///
+/// Returns [`CliError::BatchOptionConflict`] for the first such option.
+fn reject_single_contract_options(args: &VerifyArgs) -> Result<(), CliError> {
+    let conflict = if args.class_hash.is_some() {
+        Some((
+            "--class-hash",
+            "Remove [[contracts]] from .voyager.toml or remove --class-hash flag",
+        ))
+    } else if args.contract_file.is_some() {
+        // The contract file is set per contract in batch mode
+        Some((
+            "--contract-file",
+            "Set contract-file on the [[contracts]] entries in .voyager.toml instead",
+        ))
+    } else if args.contract_address.is_some() {
+        Some((
+            "--contract-address",
+            "Set class-hash on the [[contracts]] entries in .voyager.toml instead",
+        ))
+    } else if !args.targets.is_empty() {
+        Some((
+            "--targets",
+            "Verify the contracts on one explorer at a time with --network or --url",
+        ))
+    } else if args.wizard {
+        Some((
+            "--wizard",
+            "Remove [[contracts]] from .voyager.toml or remove --wizard flag",
+        ))
+    } else {
+        None
+    };
+    conflict.map_or(Ok(()), |(option, fix)| {
+        Err(CliError::BatchOptionConflict { option, fix })
+    })
+}
+
+/// Removes the plan of a batch whose contracts were all submitted, or tells
+/// how to resume it
+fn finish_batch_plan(plan: &BatchPlan) {
+    if plan.remaining() == 0 {
+        if let Err(e) = plan.remove() {
+            warn!("Failed to remove plan of finished batch {}: {e}", plan.id);
+        }
+    } else {
+        println!(
+            "\n{} contract(s) were not submitted; retry them with: voyager verify --resume {}",
+            plan.remaining(),
+            plan.id
+        );
     }
+}
 
+/// Records the contracts of `summary` verified in `state` and writes it
+///
+/// # Errors
+///
+/// Returns an error if the state file cannot be written.
+fn save_verified_state(
+    args: &VerifyArgs,
+    state: &mut WorkspaceState,
+    summary: &BatchVerificationSummary,
+    watching: bool,
+) -> Result<()> {
+    record_verified_state(state, summary);
+    state.save()?;
+    if let Some(path) = &args.state {
+        println!("Verified state written to {path}");
+    }
+    if !watching && summary.submitted > 0 {
+        println!(
+            "Contracts just submitted are recorded once verified; use --watch to record them in this run"
+        );
+    }
     Ok(())
 }
 
+/// Loads the batch plan to resume, or starts a new one for the contracts in `cfg`
+///
+/// When resuming, the plan's contracts replace those in `cfg`. Returns `None`
+/// if plans cannot be stored, in which case the batch cannot be resumed.
+///
+/// # Errors
+///
+/// Returns [`CliError::BatchNotResumable`] if the batch to resume is not
+/// found or was submitted to another API.
+fn batch_plan(
+    args: &VerifyArgs,
+    project: &Utf8Path,
+    api_url: &Url,
+    cfg: &mut Config,
+) -> Result<Option<BatchPlan>, CliError> {
+    let Some(store) = BatchStore::open() else {
+        if args.resume.is_some() {
+            return Err(CliError::BatchNotResumable {
+                reason: "the home directory could not be determined".to_string(),
+            });
+        }
+        warn!("Home directory not found; this batch cannot be resumed if interrupted");
+        return Ok(None);
+    };
+    let project = project.to_path_buf();
+
+    let Some(ref id) = args.resume else {
+        let plan = store.create(project, api_url, cfg.contracts.clone());
+        if let Err(e) = plan.save() {
+            warn!("Failed to save plan of batch {}: {e}", plan.id);
+            return Ok(None);
+        }
+        println!(
+            "Batch ID: {} (if interrupted, continue with: voyager verify --resume)",
+            plan.id
+        );
+        return Ok(Some(plan));
+    };
+
+    let plan = if id == "latest" {
+        store.latest(&project)
+    } else {
+        store
+            .load(id)
+            .map_err(|e| debug!("Cannot load batch plan {id}: {e}"))
+            .ok()
+    };
+    let Some(plan) = plan else {
+        let reason = if id == "latest" {
+            format!("no interrupted batch verification found for {project}")
+        } else {
+            format!("no batch verification with ID '{id}' was found")
+        };
+        return Err(CliError::BatchNotResumable { reason });
+    };
+
+    if plan.api_url != api_url.as_str() {
+        return Err(CliError::BatchNotResumable {
+            reason: format!(
+                "batch {} was submitted to {}, not {api_url}",
+                plan.id, plan.api_url
+            ),
+        });
+    }
+
+    println!(
+        "Resuming batch {}: {} of {} contract(s) left",
+        plan.id,
+        plan.remaining(),
+        plan.contracts.len()
+    );
+    cfg.contracts.clone_from(&plan.contracts);
+    Ok(Some(plan))
+}
+
 /// Handles single contract verification mode
 ///
 /// # Errors
@@ -143,76 +725,615 @@ fn handle_batch_verification(args: &VerifyArgs, config: Option<&Config>) -> Resu
 /// - Verification submission fails
 /// - Watch mode polling fails
 /// - Desktop notification fails (non-fatal, logged as warning)
-fn handle_single_verification(args: VerifyArgs) -> Result<()> {
+fn handle_single_verification(
+    args: VerifyArgs,
+    config: Option<&Config>,
+    metrics: Option<&Arc<Metrics>>,
+) -> Result<()> {
     // Validate network URL
-    if let Err(err) = args.validate() {
-        eprintln!("Error: {err}");
-        std::process::exit(1);
-    }
+    args.validate().map_err(|_| CliError::ApiUrlUnset)?;
 
     // Check if wizard mode is enabled
     let args = if args.wizard {
-        // Run the wizard with the already-loaded project
-        wizard::run_wizard(args.path)?
+        // Run the wizard with the already-loaded project, keeping config-only settings
+        let history_retention = args.history_retention;
+        VerifyArgs {
+            history_retention,
+            ..wizard::run_wizard(args.path, args.prompt_timeout)?
+        }
     } else {
         args
     };
 
-    let api_client = ApiClient::new(args.network_url.url.clone())?;
+    let mut args = match args.contract_address.clone() {
+        Some(address) => resolve_contract_address(args, &address, config)?,
+        None => args,
+    };
+    if args.contract_name.is_none() {
+        args.contract_name = Some(infer_contract_name(&args)?);
+    }
+
+    warn_on_batch_options(&args);
+
+    // A JSON preview only needs the file list: no API, git or license checks
+    if args.dry_run && args.format == OutputFormat::Json {
+        return print_payload_json(&args);
+    }
+
+    ensure_clean_working_tree(&args)?;
+
+    // History is written after submission, so surface storage problems first
+    if !args.dry_run && !args.simulate {
+        preflight::warn_on_state_issues();
+    }
+
+    let api_client = api_client(
+        args.network_url.url()?,
+        args.api_key.as_deref(),
+        args.polling(),
+        args.compress,
+        config,
+        metrics,
+    )?;
+
+    if args.should_skip_verified() && is_verified(&args, &api_client) {
+        return Ok(());
+    }
 
     let license_info = license::resolve_license_info(
         args.license,
-        args.path.get_license(),
-        args.path.manifest_path(),
+        args.path.get_license(args.package.as_deref())?,
+        args.path.manifest_path()?,
     );
 
     license::warn_if_no_license(&license_info);
+    confirm_workspace_licenses(&args, &license_info)?;
 
-    let job_id = submit(&api_client, &args, &license_info).inspect_err(|e| {
+    let clients = target_clients(&args, config, metrics)?;
+    let targets = targets(&args, &clients, &api_client);
+
+    let submission = if targets.is_empty() {
+        submit(&api_client, &args, &license_info)
+    } else {
+        submit_to_targets(&targets, &args, &license_info)
+    }
+    .inspect_err(|e| {
         if args.verbose {
             display_verbose_error(e);
         }
         display_error_suggestions(e);
+        report_failure(&args, config, &license_info, &api_client, None, e);
     })?;
 
-    if job_id != "dry-run" {
+    if !targets.is_empty() {
+        report_targets(&args, &targets, &submission.target_jobs)?;
+        return Ok(());
+    }
+
+    if let Some(job_id) = submission.job_id {
         display_verification_job_id(&job_id);
+        display_share_hints(&args, &job_id);
 
         // If --watch flag is enabled, poll for verification result
         if args.watch {
-            let status = check(&api_client, &job_id, &OutputFormat::Text).inspect_err(|e| {
+            let status = check(
+                &api_client,
+                &job_id,
+                &OutputFormat::Text,
+                args.timezone.unwrap_or_default(),
+                args.verbose,
+            )
+            .inspect_err(|e| {
                 if args.verbose {
                     display_verbose_error(e);
                 }
                 display_error_suggestions(e);
+                mismatch::explain_failure(&api_client, &job_id, e, &submission_facts(&args));
+                report_failure(&args, config, &license_info, &api_client, Some(&job_id), e);
             })?;
             info!("{status:?}");
 
             // Send desktop notification if enabled
             #[cfg(feature = "notifications")]
             if args.notify {
-                if let Some(ref contract_name) = args.contract_name {
-                    if let Err(e) = crate::output::notifications::send_verification_notification(
-                        contract_name,
-                        *status.status(),
-                        &job_id,
-                    ) {
-                        eprintln!("Warning: Failed to send desktop notification: {e}");
-                    }
-                }
+                notify(&args, *status.status(), &job_id);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Warns about options given for a single contract that only apply to
+/// batches or dry runs
+fn warn_on_batch_options(args: &VerifyArgs) {
+    if args.results.is_some() {
+        eprintln!("Warning: --results is only written in batch mode and will be ignored");
+    }
+    match args.format {
+        OutputFormat::Table | OutputFormat::Csv => eprintln!(
+            "Warning: --format table and csv only apply to batch verification and will be ignored"
+        ),
+        OutputFormat::Json if !args.dry_run => {
+            eprintln!("Warning: --format json only applies to dry runs and will be ignored");
+        }
+        _ => {}
+    }
+}
+
+/// Whether the class given with `--class-hash` is verified already, which is
+/// reported
+fn is_verified(args: &VerifyArgs, api_client: &ApiClient) -> bool {
+    let Some(info) = args
+        .class_hash
+        .as_ref()
+        .and_then(|class_hash| already_verified(api_client, class_hash))
+    else {
+        return false;
+    };
+    println!(
+        "✓ Class {} is already verified{}, skipping (use --force to re-verify)",
+        info.class_hash,
+        info.name
+            .as_deref()
+            .map(|name| format!(" as {name}"))
+            .unwrap_or_default()
+    );
+    true
+}
+
+/// The explorers named with `--targets`, with `clients` from
+/// [`target_clients`]; `voyager` is submitted to with `api_client`
+fn targets<'a>(
+    args: &'a VerifyArgs,
+    clients: &'a [(String, ApiClient)],
+    api_client: &'a ApiClient,
+) -> Vec<Target<'a>> {
+    args.targets
+        .iter()
+        .unique()
+        .map(|name| {
+            clients
+                .iter()
+                .find(|(configured, _)| configured == name)
+                .map_or(
+                    Target {
+                        name: VOYAGER_TARGET,
+                        client: api_client,
+                        record_history: true,
+                    },
+                    |(name, client)| Target {
+                        name,
+                        client,
+                        record_history: false,
+                    },
+                )
+        })
+        .collect()
+}
+
+/// Sends a desktop notification that the job of the contract finished with
+/// `status`
+#[cfg(feature = "notifications")]
+fn notify(args: &VerifyArgs, status: crate::api::VerifyJobStatus, job_id: &str) {
+    let Some(ref contract_name) = args.contract_name else {
+        return;
+    };
+    if let Err(e) =
+        crate::output::notifications::send_verification_notification(contract_name, status, job_id)
+    {
+        eprintln!("Warning: Failed to send desktop notification: {e}");
+    }
+}
+
+/// Prints the command that checks a submitted job and, if the explorer is
+/// known, the link to share it
+fn display_share_hints(args: &VerifyArgs, job_id: &str) {
+    // Simulated jobs exist nowhere but in this process
+    if args.simulate {
+        return;
+    }
+    let Ok(api_url) = args.network_url.url() else {
+        return;
+    };
+    let mut share = JobShare::new(job_id, api_url, args.network.clone());
+    share.explorer = args.explorer();
+    println!("Check status: {}", share.status_command());
+    if let Some(link) = share.job_link() {
+        println!("Share:        {link}");
+    }
+    println!();
+}
+
+/// Name of the Voyager API selected with `--network` or `--url` in `--targets`
+const VOYAGER_TARGET: &str = "voyager";
+
+/// API clients of the explorers named with `--targets`, other than Voyager
+///
+/// Exits with an error if a target is neither `voyager` nor configured as
+/// `[targets.<name>]`, or its URL is invalid.
+///
+/// # Errors
+///
+/// Returns an error if an API client cannot be created.
+fn target_clients(
+    args: &VerifyArgs,
+    config: Option<&Config>,
+    metrics: Option<&Arc<Metrics>>,
+) -> Result<Vec<(String, ApiClient)>> {
+    args.targets
+        .iter()
+        .filter(|name| name.as_str() != VOYAGER_TARGET)
+        .unique()
+        .map(|name| {
+            let Some(target) = config.and_then(|config| config.targets.get(name)) else {
+                eprintln!("Error: Unknown verification target '{name}'");
+                eprintln!(
+                    "Use '{VOYAGER_TARGET}', or add [targets.{name}] with the url of its verification API to .voyager.toml"
+                );
+                std::process::exit(1);
+            };
+            let url = Url::parse(&target.url).unwrap_or_else(|e| {
+                eprintln!("Error: Invalid url '{}' of target '{name}': {e}", target.url);
+                std::process::exit(1);
+            });
+            Ok((name.clone(), api_client(
+                    &url,
+                    None,
+                    args.polling(),
+                    args.compress,
+                    config,
+                    metrics,
+                )?))
+        })
+        .collect()
+}
+
+/// Shows the job of each target of a `--targets` submission and, with
+/// `--watch`, waits for each of them in turn
+///
+/// # Errors
+///
+/// Returns [`CliError::TargetsFailed`] if a target did not accept the
+/// submission or its verification failed.
+fn report_targets(
+    args: &VerifyArgs,
+    targets: &[Target<'_>],
+    jobs: &[TargetJob],
+) -> Result<(), CliError> {
+    if jobs.is_empty() {
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    let mut rows = Vec::with_capacity(jobs.len());
+    for (target, job) in targets.iter().zip(jobs) {
+        let (job_id, status) = match &job.outcome {
+            Ok(job_id) if args.watch => {
+                println!("\nWatching job {job_id} on {}", target.name);
+                let status = check(
+                    target.client,
+                    job_id,
+                    &OutputFormat::Text,
+                    args.timezone.unwrap_or_default(),
+                    args.verbose,
+                )
+                .map_or_else(
+                    |e| {
+                        failed.push(job.target.clone());
+                        let reason = e.to_string();
+                        format!("Failed: {}", reason.lines().next().unwrap_or_default())
+                    },
+                    |status| status.status().to_string(),
+                );
+                (job_id.clone(), status)
+            }
+            Ok(job_id) => (job_id.clone(), "Submitted".to_string()),
+            Err(reason) => {
+                failed.push(job.target.clone());
+                ("-".to_string(), format!("Not submitted: {reason}"))
+            }
+        };
+        rows.push(vec![job.target.clone(), job_id, status]);
+    }
+
+    println!("\nVerification jobs by target:");
+    print!(
+        "{}",
+        results::render_table(&["Target", "Job ID", "Status"], &rows)
+    );
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::TargetsFailed { targets: failed })
+    }
+}
+
+/// Verifies the sources of a tar archive piped to standard input
+///
+/// # Errors
+///
+/// Returns an error if the archive is invalid, or submitting or watching the
+/// verification fails.
+fn handle_piped_verification(
+    args: &VerifyArgs,
+    config: Option<&Config>,
+    metrics: Option<&Arc<Metrics>>,
+) -> Result<()> {
+    args.validate().map_err(|_| CliError::ApiUrlUnset)?;
+    if io::stdin().is_terminal() {
+        eprintln!(
+            "Error: --stdin-tar reads a tar archive from standard input, which is a terminal"
+        );
+        eprintln!("Tip: Pipe the sources in, e.g. 'tar -cf - -C build . | voyager verify --stdin-tar ...'");
+        std::process::exit(1);
+    }
+    submit_without_project(args, config, metrics, |api_client| {
+        submit_piped(api_client, args, io::stdin().lock())
+    })
+}
+
+/// Verifies the sources of a package written by `voyager pack`
+///
+/// # Errors
+///
+/// Returns an error if the package is invalid, or submitting or watching the
+/// verification fails.
+fn handle_pack_verification(
+    args: &VerifyArgs,
+    path: &Utf8Path,
+    config: Option<&Config>,
+    metrics: Option<&Arc<Metrics>>,
+) -> Result<()> {
+    args.validate().map_err(|_| CliError::ApiUrlUnset)?;
+    let pack = Pack::read(path)?;
+    info!(
+        "Read {} file(s) packed by voyager {} at {}",
+        pack.files.len(),
+        pack.manifest.voyager_version,
+        pack.manifest.created_at
+    );
+
+    submit_without_project(args, config, metrics, |api_client| {
+        submit_sources(
+            api_client,
+            args,
+            pack.into_sources(),
+            &SourceOrigin::Pack(path),
+        )
+    })
+}
+
+/// Submits sources that come with their own manifest instead of a project,
+/// and watches the job if requested
+///
+/// # Errors
+///
+/// Returns an error if submitting or watching the verification fails.
+fn submit_without_project(
+    args: &VerifyArgs,
+    config: Option<&Config>,
+    metrics: Option<&Arc<Metrics>>,
+    submit: impl FnOnce(&ApiClient) -> Result<Submission, CliError>,
+) -> Result<()> {
+    if args.results.is_some() || args.state.is_some() {
+        eprintln!("Warning: --results and --state are only used in batch mode and will be ignored");
+    }
+
+    // History is written after submission, so surface storage problems first
+    if !args.dry_run && !args.simulate {
+        preflight::warn_on_state_issues();
+    }
+
+    let api_client = api_client(
+        args.network_url.url()?,
+        args.api_key.as_deref(),
+        args.polling(),
+        args.compress,
+        config,
+        metrics,
+    )?;
+    let submission = submit(&api_client).inspect_err(|e| {
+        if args.verbose {
+            display_verbose_error(e);
+        }
+        display_error_suggestions(e);
+    })?;
+    let Some(job_id) = submission.job_id else {
+        return Ok(());
+    };
+
+    display_verification_job_id(&job_id);
+    display_share_hints(args, &job_id);
+    if args.watch {
+        let status = check(
+            &api_client,
+            &job_id,
+            &OutputFormat::Text,
+            args.timezone.unwrap_or_default(),
+            args.verbose,
+        )
+        .inspect_err(|e| {
+            if args.verbose {
+                display_verbose_error(e);
+            }
+            display_error_suggestions(e);
+        })?;
+        info!("{status:?}");
+    }
+
+    Ok(())
+}
+
+/// Prints the summary of a batch in the format selected with `--format`
+fn print_batch_summary(args: &VerifyArgs, api_url: &Url, summary: &BatchVerificationSummary) {
+    match args.format {
+        OutputFormat::Table => print!(
+            "\n{}",
+            results::format_table(summary, args.explorer().as_ref())
+        ),
+        OutputFormat::Csv => print!("{}", results::format_csv(summary, args.explorer().as_ref())),
+        OutputFormat::Json => {
+            match results::format_json(summary, api_url, args.explorer().as_ref()) {
+                Ok(json) => println!("{json}"),
+                Err(e) => warn!("Failed to serialize the batch summary: {e}"),
             }
         }
+        OutputFormat::Text => crate::core::verification::display_batch_summary(summary),
     }
+}
 
+/// Prints the payload a dry run would submit as JSON, and nothing else
+///
+/// # Errors
+///
+/// Returns an error if the project files cannot be collected.
+fn print_payload_json(args: &VerifyArgs) -> Result<()> {
+    let license_info = license::resolve_license_info(
+        args.license,
+        args.path.get_license(args.package.as_deref())?,
+        args.path.manifest_path()?,
+    );
+    let payload = preview_payload(args, &license_info)?;
+    println!("{}", serde_json::to_string_pretty(&payload)?);
     Ok(())
 }
 
+/// What the local project says about a submission, for mismatch reports
+fn submission_facts(args: &VerifyArgs) -> SubmissionFacts {
+    let Ok(metadata) = args.path.load() else {
+        return SubmissionFacts::default();
+    };
+    SubmissionFacts {
+        toolchain: Some(Toolchain::local(metadata)),
+        pinned: Toolchain::pinned(&metadata.workspace.root),
+        project: ProjectFacts::read(metadata, args.package.as_deref(), args.lock_file),
+    }
+}
+
+/// Writes a triage bundle for a failed verification when `--bundle` was given,
+/// and points at the flag otherwise
+fn report_failure(
+    args: &VerifyArgs,
+    config: Option<&Config>,
+    license_info: &license::LicenseInfo,
+    api_client: &ApiClient,
+    job_id: Option<&str>,
+    error: &CliError,
+) {
+    let Some(ref path) = args.bundle else {
+        eprintln!(
+            "\nTip: re-run with --bundle triage.zip to collect diagnostics for a support ticket"
+        );
+        return;
+    };
+
+    let bundle = triage::verification_bundle(args, config, license_info, api_client, job_id, error);
+    match bundle.write(path) {
+        Ok(()) => eprintln!(
+            "\nTriage bundle written to {path} ({})",
+            bundle.file_names().collect::<Vec<_>>().join(", ")
+        ),
+        Err(e) => eprintln!("\nWarning: {e}"),
+    }
+}
+
+/// Enforces the `--require-clean-git` policy before anything is submitted
+///
+/// # Errors
+///
+/// Returns an error if the project has uncommitted changes or its git status
+/// cannot be determined, unless `--allow-dirty` was given.
+fn ensure_clean_working_tree(args: &VerifyArgs) -> Result<(), CliError> {
+    if !args.require_clean_git {
+        return Ok(());
+    }
+
+    let root = args.path.root_dir()?;
+    let changes = git::uncommitted_changes(root);
+
+    if args.allow_dirty {
+        if changes.is_some_and(|files| !files.is_empty()) {
+            eprintln!(
+                "Warning: Submitting with uncommitted changes because --allow-dirty was given"
+            );
+        }
+        return Ok(());
+    }
+
+    match changes {
+        Some(files) if files.is_empty() => Ok(()),
+        Some(files) => Err(CliError::DirtyWorkingTree {
+            path: root.clone(),
+            files,
+        }),
+        None => Err(CliError::GitStatusUnavailable { path: root.clone() }),
+    }
+}
+
+/// Warns when workspace members declare licenses that differ from the one being
+/// submitted, and asks the user to confirm before continuing
+///
+/// Non-interactive sessions cannot confirm, so they fail unless
+/// `--allow-license-mismatch` was given.
+///
+/// # Errors
+///
+/// Returns an error if the licenses conflict and the submission was not confirmed.
+fn confirm_workspace_licenses(
+    args: &VerifyArgs,
+    license_info: &license::LicenseInfo,
+) -> Result<(), CliError> {
+    let members = license::workspace_member_licenses(args.path.load()?);
+    if !license::conflicts_with(&members, license_info) {
+        return Ok(());
+    }
+
+    license::print_license_conflicts(&members, license_info);
+
+    if args.allow_license_mismatch {
+        return Ok(());
+    }
+
+    let question = format!(
+        "Submit all sources under {}?",
+        license_info.display_string()
+    );
+    let confirmed = args.is_interactive()
+        && prompt::ask(args.prompt_timeout, move || {
+            Confirm::new()
+                .with_prompt(question)
+                .default(false)
+                .interact()
+        })?
+        .unwrap_or(false);
+
+    if confirmed {
+        Ok(())
+    } else {
+        Err(CliError::LicenseConflict {
+            license: license_info.display_string().to_string(),
+            packages: members
+                .iter()
+                .filter(|member| member.differs_from(license_info))
+                .map(|member| {
+                    format!(
+                        "{} ({})",
+                        member.package,
+                        member.license.as_deref().unwrap_or_default()
+                    )
+                })
+                .collect(),
+        })
+    }
+}
+
 /// Displays error suggestions based on the error type
 fn display_error_suggestions(error: &CliError) {
     if let CliError::Api(ApiClientError::Verify(ref verification_error)) = error {
         eprintln!("\nSuggestions:");
         for suggestion in verification_error.suggestions() {
-            eprintln!("  • {suggestion}");
+            eprintln!("  • {}", suggestion.text);
         }
     }
     // RequestFailure errors already include suggestions in their display
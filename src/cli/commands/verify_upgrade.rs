@@ -0,0 +1,209 @@
+use crate::{
+    api::{ApiClient, ClassChange, ClassHistory, ClassVerificationInfo, RpcClient},
+    cli::{
+        args::{VerifyArgs, VerifyUpgradeArgs},
+        config::{self, Config, ContractConfig},
+    },
+    core::{class_hash::ClassHash, verification::already_verified},
+    utils::{errors::CliError, voyager},
+};
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::Confirm;
+use std::io::IsTerminal;
+
+/// Handles the verify-upgrade command for upgradeable contracts
+///
+/// Unverified classes are submitted through the regular batch verification
+/// flow, so every verify option (license, lock file, --watch, --results, ...)
+/// applies to them.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The class history cannot be read from the RPC node
+/// - API client creation fails
+/// - Batch verification fails
+pub fn handle_verify_upgrade_command(
+    args: VerifyUpgradeArgs,
+    config: Option<&Config>,
+) -> Result<()> {
+    if args.verify.class_hash.is_some()
+        || args.verify.contract_address.is_some()
+        || args.verify.all_deployed
+        || args.verify.from_dojo_manifest.is_some()
+        || args.verify.world.is_some()
+        || args.verify.wizard
+    {
+        eprintln!(
+            "Error: --class-hash, --contract-address, --all-deployed, --from-dojo-manifest, --world and --wizard cannot be used with verify-upgrade."
+        );
+        eprintln!("The class hashes are read from the contract's history.");
+        std::process::exit(1);
+    }
+
+    let mut verify_args = args.verify;
+    if let Some(endpoint) = config::selected_endpoint(verify_args.endpoint.as_deref(), config)? {
+        verify_args = verify_args.with_endpoint(endpoint);
+    }
+    let mut verify_args = if let Some(cfg) = config {
+        verify_args.merge_with_config(cfg)
+    } else {
+        verify_args
+    };
+
+    if let Err(err) = verify_args.validate() {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
+
+    let history = class_history(&verify_args, &args.address)?;
+
+    let api_client = ApiClient::new(verify_args.network_url.url()?.clone())?;
+    let api_client = config::with_configured_polling(
+        match verify_args.api_key.as_deref() {
+            Some(api_key) => api_client.with_api_key(api_key),
+            None => api_client,
+        },
+        config,
+    );
+    let classes = history
+        .changes
+        .into_iter()
+        .map(|change| {
+            let class_hash = ClassHash::new(&change.class_hash)?;
+            let info = already_verified(&api_client, &class_hash);
+            Ok((change, class_hash, info))
+        })
+        .collect::<Result<Vec<_>, CliError>>()?;
+
+    display_class_history(&args.address, &classes);
+    if !history.complete {
+        println!(
+            "\n{} The contract emitted no Upgraded event for some of its upgrades, so the history was",
+            "!".yellow().bold()
+        );
+        println!("searched block by block and may miss classes that were replaced and restored.");
+    }
+
+    let unverified: Vec<&ClassHash> = classes
+        .iter()
+        .filter(|(_, _, info)| info.is_none())
+        .map(|(_, class_hash, _)| class_hash)
+        .collect();
+
+    if unverified.is_empty() {
+        println!("\n{} All classes are verified.", "✓".green().bold());
+        return Ok(());
+    }
+
+    let Some(contract_name) = verify_args.contract_name.clone() else {
+        println!(
+            "\n{} unverified class(es). Pass --contract-name to verify them with the sources at {}.",
+            unverified.len(),
+            verify_args.path
+        );
+        return Ok(());
+    };
+
+    println!(
+        "\nEach class only verifies if the sources at {} compile to it; check out the matching",
+        verify_args.path
+    );
+    println!("revision first for older implementations.");
+
+    let confirmed = verify_args.yes
+        || (std::io::stdin().is_terminal()
+            && Confirm::new()
+                .with_prompt(format!(
+                    "Verify {} unverified class(es) as {contract_name}?",
+                    unverified.len()
+                ))
+                .default(false)
+                .interact()?);
+
+    if !confirmed {
+        println!("No classes were submitted (use --yes to skip the prompt).");
+        return Ok(());
+    }
+
+    let contract_file = verify_args.contract_file.take();
+    let mut batch_config = config.cloned().unwrap_or_default();
+    batch_config.contracts = unverified
+        .into_iter()
+        .map(|class_hash| ContractConfig {
+            class_hash: class_hash.to_string(),
+            contract_name: contract_name.clone(),
+            package: verify_args.package.clone(),
+            contract_file: contract_file.clone(),
+            depends_on: vec![],
+        })
+        .collect();
+
+    super::verify::handle_verify_command(verify_args, Some(&batch_config))
+}
+
+/// The classes of the contract at `address`, read from `--rpc-url`
+///
+/// Exits with an error if no RPC URL is given.
+///
+/// # Errors
+///
+/// Returns an error if the node is on another network or a request fails.
+fn class_history(verify_args: &VerifyArgs, address: &str) -> Result<ClassHistory> {
+    let Some(rpc_url) = verify_args.rpc_url.clone() else {
+        eprintln!("Error: --rpc-url (or STARKNET_RPC_URL) is required for verify-upgrade.");
+        eprintln!("The class history is read from a Starknet node.");
+        std::process::exit(1);
+    };
+    let rpc = RpcClient::new(rpc_url);
+    let network = verify_args.network.clone().or_else(|| {
+        verify_args
+            .network_url
+            .url()
+            .ok()
+            .and_then(voyager::network_for_api_url)
+    });
+    if let Some(expected) = network.as_ref().and_then(voyager::chain_id) {
+        rpc.ensure_chain_id(expected).map_err(CliError::from)?;
+    }
+
+    println!("Reading class history of {address} ...");
+    Ok(rpc.class_history(address).map_err(CliError::from)?)
+}
+
+fn display_class_history(
+    address: &str,
+    classes: &[(ClassChange, ClassHash, Option<ClassVerificationInfo>)],
+) {
+    println!(
+        "\n{}",
+        format!("Class history of {address} ({} class(es))", classes.len())
+            .bold()
+            .underline()
+    );
+    println!();
+    println!("  {:<12} {:<68} Status", "Since block", "Class hash");
+
+    for (index, (change, class_hash, info)) in classes.iter().enumerate() {
+        let current = if index == 0 { " (current)" } else { "" };
+        let status = info.as_ref().map_or_else(
+            || format!("{} Not verified", "✗".red().bold()),
+            |info| {
+                format!(
+                    "{} Verified{}",
+                    "✓".green().bold(),
+                    info.name
+                        .as_deref()
+                        .map(|name| format!(" as {name}"))
+                        .unwrap_or_default()
+                )
+            },
+        );
+        println!(
+            "  {:<12} {:<68} {status}",
+            change.since_block,
+            format!("{class_hash}{current}")
+        );
+    }
+}
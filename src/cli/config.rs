@@ -13,9 +13,16 @@
 //!
 //! Configuration values are merged with the following priority:
 //! - CLI arguments (highest priority)
+//! - `VOYAGER_*` environment variables
 //! - Config file values
 //! - Default values (lowest priority)
 //!
+//! Each `[voyager]` setting is read from the variable named after it, e.g.
+//! `VOYAGER_NETWORK` for `network`, `VOYAGER_CHECK_CACHE_TTL` for
+//! `check-cache-ttl` and `VOYAGER_LIMITS_MAX_FILE_SIZE` for
+//! `limits.max-file-size`. `VOYAGER_API_KEY` holds the API key; the config
+//! file can only name the variable holding it, with `api-key-env`.
+//!
 //! ## Example Configuration
 //!
 //! ```toml
@@ -31,26 +38,95 @@
 //! default_package = "my_contract"
 //! ```
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
+use log::debug;
 use serde::{Deserialize, Serialize};
-use std::{env, fs, io};
+use std::{collections::BTreeMap, env, fs, io, time::Duration};
 use thiserror::Error;
 
-use super::args::NetworkKind;
+use super::args::{DuplicatePolicy, NetworkKind, Timezone};
+use super::policy::Policy;
+use crate::api::{ApiClient, PayloadTransformKind, PollStrategyKind, PollingConfig};
+use crate::filesystem::collector::SizeLimits;
+use crate::storage::history::RetentionPolicy;
+use crate::utils::suggestion::{Suggestion, SuggestionList};
+use url::Url;
 
 /// Configuration file name
 pub const CONFIG_FILE_NAME: &str = ".voyager.toml";
 
+/// Environment variable holding the API key sent with every request
+pub const API_KEY_ENV: &str = "VOYAGER_API_KEY";
+
+/// Type of a setting read from the environment
+#[derive(Debug, Clone, Copy)]
+enum EnvValue {
+    String,
+    Bool,
+    Integer,
+    /// Comma-separated list
+    List,
+}
+
+/// `[voyager]` settings that environment variables override
+const ENV_SETTINGS: &[(&str, EnvValue)] = &[
+    ("network", EnvValue::String),
+    ("url", EnvValue::String),
+    ("license", EnvValue::String),
+    ("watch", EnvValue::Bool),
+    ("test-files", EnvValue::Bool),
+    ("include-docs", EnvValue::Bool),
+    ("doc-paths", EnvValue::List),
+    ("lock-file", EnvValue::Bool),
+    ("verbose", EnvValue::Bool),
+    ("project-type", EnvValue::String),
+    ("notify", EnvValue::Bool),
+    ("format", EnvValue::String),
+    ("require-clean-git", EnvValue::Bool),
+    ("require-license-headers", EnvValue::Bool),
+    ("preflight", EnvValue::Bool),
+    ("compress", EnvValue::Bool),
+    ("compare-class-hash", EnvValue::Bool),
+    ("skip-if-verified", EnvValue::Bool),
+    ("on-duplicate", EnvValue::String),
+    ("state-file", EnvValue::String),
+    ("check-cache-ttl", EnvValue::Integer),
+    ("timezone", EnvValue::String),
+    ("poll-strategy", EnvValue::String),
+    ("poll-interval", EnvValue::String),
+    ("poll-max-delay", EnvValue::String),
+    ("poll-max-attempts", EnvValue::Integer),
+    ("poll-timeout", EnvValue::String),
+    ("prompt-timeout", EnvValue::String),
+    ("payload-transforms", EnvValue::List),
+    ("limits.max-file-size", EnvValue::Integer),
+    ("limits.max-payload-size", EnvValue::Integer),
+];
+
+/// Environment variable overriding the `[voyager]` setting `key`
+fn env_var_name(key: &str) -> String {
+    format!("VOYAGER_{}", key.replace(['-', '.'], "_").to_uppercase())
+}
+
 #[derive(Error, Debug)]
 pub enum ConfigError {
-    #[error("[E030] Failed to read config file: {0}\n\nSuggestions:\n  • Check file permissions\n  • Verify the file exists and is accessible\n  • Ensure you have read access to the file")]
+    #[error("[E030] Failed to read config file: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Io(#[from] io::Error),
 
-    #[error("[E031] Failed to parse config file: {0}\n\nSuggestions:\n  • Check that .voyager.toml is valid TOML format\n  • Verify all field names are spelled correctly\n  • Ensure values match expected types (e.g., boolean, string)\n  • Run a TOML validator on your config file")]
+    #[error("[E031] Failed to parse config file: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Parse(#[from] toml::de::Error),
 
-    #[error("[E032] Invalid UTF-8 path: {0}\n\nSuggestions:\n  • Use only ASCII characters in file paths\n  • Avoid special characters in directory names")]
+    #[error("[E032] Invalid UTF-8 path: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Utf8(#[from] camino::FromPathBufError),
+
+    #[error("[E033] Organization policy '{path}' cannot be applied: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    Policy { path: Utf8PathBuf, reason: String },
+
+    #[error("[E076] Endpoint '{name}' cannot be used: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    Endpoint { name: String, reason: String },
+
+    #[error("[E084] Environment variable {var} is invalid: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    Env { var: String, reason: String },
 }
 
 impl ConfigError {
@@ -60,8 +136,41 @@ impl ConfigError {
             Self::Io(_) => "E030",
             Self::Parse(_) => "E031",
             Self::Utf8(_) => "E032",
+            Self::Policy { .. } => "E033",
+            Self::Endpoint { .. } => "E076",
+            Self::Env { .. } => "E084",
         }
     }
+
+    /// Next steps for fixing the configuration
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        match self {
+            Self::Endpoint { name, .. } => {
+                Suggestion::for_code(self.error_code(), &[("name", name)])
+            }
+            Self::Io(_)
+            | Self::Parse(_)
+            | Self::Utf8(_)
+            | Self::Policy { .. }
+            | Self::Env { .. } => Suggestion::for_code(self.error_code(), &[]),
+        }
+    }
+}
+
+/// `error` loading the config file, as [`ConfigError::Policy`] if a `policy`
+/// is pinned
+fn enforced(error: ConfigError, policy: Option<&Policy>) -> ConfigError {
+    let reason = match &error {
+        ConfigError::Io(e) => e.to_string(),
+        ConfigError::Parse(e) => e.to_string(),
+        ConfigError::Utf8(e) => e.to_string(),
+        _ => return error,
+    };
+    policy.map_or(error, |policy| ConfigError::Policy {
+        path: policy.path.clone(),
+        reason: format!("{CONFIG_FILE_NAME} cannot be loaded: {reason}"),
+    })
 }
 
 /// Configuration for a single contract in batch verification
@@ -74,9 +183,18 @@ pub struct ContractConfig {
     /// Name of the contract for verification
     pub contract_name: String,
 
-    /// Optional package name (for workspace projects)
-    /// If not specified, will use `workspace.default_package` or auto-detect
+    /// Optional package name (for workspace projects), or a glob such as
+    /// `contracts/*` matched against member names and directories
+    /// If not specified, will use `workspace.members_filter`, `workspace.default_package` or auto-detect
     pub package: Option<String>,
+
+    /// Optional path of the file defining the contract, relative to the project root
+    /// If not specified, the file is detected from the contract name
+    pub contract_file: Option<Utf8PathBuf>,
+
+    /// Contract names that must verify successfully before this one is submitted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
 }
 
 /// Top-level configuration structure
@@ -91,10 +209,65 @@ pub struct Config {
     #[serde(default)]
     pub workspace: WorkspaceConfig,
 
+    /// Verification history settings
+    #[serde(default)]
+    pub history: HistoryConfig,
+
+    /// Scheduled re-verification, run by `voyager daemon`
+    #[serde(default)]
+    pub schedule: ScheduleConfig,
+
     /// Batch verification contracts
     /// When this array is non-empty, the verifier runs in batch mode
     #[serde(default)]
     pub contracts: Vec<ContractConfig>,
+
+    /// Explorers `voyager verify --targets` can submit to, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets: BTreeMap<String, TargetConfig>,
+
+    /// API endpoints selected with `--endpoint`, by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub endpoints: BTreeMap<String, EndpointConfig>,
+}
+
+/// A verification API and the settings that go with it, configured as
+/// `[endpoints.<name>]`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct EndpointConfig {
+    /// Base URL of the verification API
+    pub url: String,
+
+    /// Network the API verifies classes of (mainnet, sepolia, dev)
+    pub network: Option<String>,
+
+    /// Environment variable holding the API key sent with every request
+    pub api_key_env: Option<String>,
+
+    /// Starknet JSON-RPC endpoint, used as the default `--rpc-url`
+    pub rpc_url: Option<String>,
+
+    /// Explorer linked to for verified classes, e.g. `https://voyager.online`
+    pub explorer_url: Option<String>,
+}
+
+/// An endpoint of `[endpoints]` with its URLs parsed and API key read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    pub url: Url,
+    pub network: Option<NetworkKind>,
+    pub api_key: Option<String>,
+    pub rpc_url: Option<Url>,
+    pub explorer_url: Option<Url>,
+}
+
+/// An explorer verification API, configured as `[targets.<name>]`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TargetConfig {
+    /// Base URL of the explorer's verification API
+    pub url: String,
 }
 
 /// Voyager verification configuration
@@ -115,6 +288,15 @@ pub struct VoyagerConfig {
     #[serde(default)]
     pub test_files: Option<bool>,
 
+    /// Submit documentation files with the sources
+    #[serde(default)]
+    pub include_docs: Option<bool>,
+
+    /// Documentation globs eligible for `include-docs`, relative to the
+    /// workspace root and each package root
+    #[serde(default)]
+    pub doc_paths: Vec<String>,
+
     /// Include Scarb.lock file in verification submission
     #[serde(default)]
     pub lock_file: Option<bool>,
@@ -134,9 +316,115 @@ pub struct VoyagerConfig {
     #[serde(default)]
     pub notify: Option<bool>,
 
-    /// Output format for status information (text, json, table)
+    /// Output format for status information (text, json, table, csv)
     #[serde(default)]
     pub format: Option<String>,
+
+    /// Refuse to submit when the project has uncommitted git changes
+    #[serde(default)]
+    pub require_clean_git: Option<bool>,
+
+    /// Refuse to submit Cairo files without an SPDX header naming the license
+    #[serde(default)]
+    pub require_license_headers: Option<bool>,
+
+    /// Build the files to submit locally before submitting them
+    #[serde(default)]
+    pub preflight: Option<bool>,
+
+    /// Send verification requests gzip-compressed
+    #[serde(default)]
+    pub compress: Option<bool>,
+
+    /// Compare the class hash of a local build with the one to verify
+    #[serde(default)]
+    pub compare_class_hash: Option<bool>,
+
+    /// Skip submission when the class is already verified
+    #[serde(default)]
+    pub skip_if_verified: Option<bool>,
+
+    /// What to do when history shows a pending job for the class ("warn",
+    /// "abort" or "allow")
+    #[serde(default)]
+    pub on_duplicate: Option<String>,
+
+    /// Verified state file for incremental batch verification
+    #[serde(default)]
+    pub state_file: Option<Utf8PathBuf>,
+
+    /// How long cached `check` results stay valid, in seconds (0 disables the cache)
+    #[serde(default)]
+    pub check_cache_ttl: Option<u64>,
+
+    /// Time zone for displayed timestamps ("local" or "utc")
+    #[serde(default)]
+    pub timezone: Option<String>,
+
+    /// How verification jobs are polled ("fixed", "exponential" or "server-hinted")
+    #[serde(default)]
+    pub poll_strategy: Option<String>,
+
+    /// Delay between status checks, or the first delay of "exponential" (e.g. "5s")
+    #[serde(default)]
+    pub poll_interval: Option<String>,
+
+    /// Longest delay between status checks of "exponential" and "server-hinted"
+    #[serde(default)]
+    pub poll_max_delay: Option<String>,
+
+    /// Most status checks before giving up
+    #[serde(default)]
+    pub poll_max_attempts: Option<u32>,
+
+    /// Give up waiting for a job after this long (e.g. "30m")
+    #[serde(default)]
+    pub poll_timeout: Option<String>,
+
+    /// How long prompts wait for an answer (e.g. "30s", "2m")
+    #[serde(default)]
+    pub prompt_timeout: Option<String>,
+
+    /// Transforms applied in order to submitted files ("dev-dependencies",
+    /// "line-endings", "secret-scan", "generated-files")
+    #[serde(default)]
+    pub payload_transforms: Option<Vec<String>>,
+
+    /// Size limits of submitted files (`[voyager.limits]`)
+    #[serde(default)]
+    pub limits: LimitsConfig,
+
+    /// Environment variable holding the API key, read when
+    /// [`API_KEY_ENV`] is not set
+    #[serde(default)]
+    pub api_key_env: Option<String>,
+
+    /// API key sent with every request, read from the environment
+    #[serde(skip)]
+    pub api_key: Option<String>,
+}
+
+/// Size limits of submitted files, in bytes
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct LimitsConfig {
+    /// Largest single file
+    pub max_file_size: Option<u64>,
+
+    /// Largest combined size of all submitted files
+    pub max_payload_size: Option<u64>,
+}
+
+impl LimitsConfig {
+    /// Limits enforced before uploading, before narrowing to the API's own
+    #[must_use]
+    pub fn size_limits(&self) -> SizeLimits {
+        let defaults = SizeLimits::default();
+        SizeLimits {
+            max_file_size: self.max_file_size.unwrap_or(defaults.max_file_size),
+            max_payload_size: self.max_payload_size.unwrap_or(defaults.max_payload_size),
+        }
+    }
 }
 
 /// Workspace-specific configuration
@@ -145,6 +433,43 @@ pub struct VoyagerConfig {
 pub struct WorkspaceConfig {
     /// Default package for verification in workspace projects
     pub default_package: Option<String>,
+
+    /// Globs of the members that define the batch contracts without a `package`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members_filter: Vec<String>,
+}
+
+/// Verification history configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryConfig {
+    /// Keep at most this many of the most recent records
+    pub max_records: Option<usize>,
+
+    /// Delete records older than this many days
+    pub max_age_days: Option<u32>,
+}
+
+/// Scheduled re-verification configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ScheduleConfig {
+    /// When to re-verify the contracts, as a cron expression in UTC
+    pub cron: Option<String>,
+
+    /// Send a desktop notification when a contract is no longer verified
+    pub notify_regressions: Option<bool>,
+}
+
+impl HistoryConfig {
+    /// Retention policy applied whenever a record is added to history
+    #[must_use]
+    pub const fn retention(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            max_records: self.max_records,
+            max_age_days: self.max_age_days,
+        }
+    }
 }
 
 impl Config {
@@ -161,17 +486,72 @@ impl Config {
 
     /// Find and load configuration file by searching current and parent directories
     ///
-    /// Returns None if no config file is found (which is not an error)
+    /// `VOYAGER_*` environment variables override the settings of the config
+    /// file, and the settings of the organization policy, if one is pinned
+    /// (see [`Policy`]), replace both. Returns None if there is neither a
+    /// config file, a variable nor a policy (which is not an error)
     ///
     /// # Errors
     ///
-    /// Returns an error if a config file is found but cannot be read or parsed
+    /// Returns an error if a config file is found but cannot be read or parsed,
+    /// [`ConfigError::Env`] if a variable has an invalid value, or
+    /// [`ConfigError::Policy`] if the pinned policy cannot be applied,
+    /// including because the config file cannot be loaded
     pub fn find_and_load() -> Result<Option<Self>, ConfigError> {
-        if let Some(config_path) = Self::find_config_file()? {
-            Ok(Some(Self::from_file(&config_path)?))
-        } else {
-            Ok(None)
-        }
+        let policy = Policy::find_and_load()?;
+        let project = Self::find_config_file().map_err(|e| enforced(e, policy.as_ref()))?;
+        let var = |name: &str| env::var(name).ok().filter(|value| !value.is_empty());
+        Self::load(project.as_deref(), policy.as_ref(), var)
+    }
+
+    /// Load the config file `project`, with the settings of `policy` and of
+    /// the environment variables read by `var`
+    ///
+    /// With a `policy`, a config file that cannot be loaded fails as
+    /// [`ConfigError::Policy`], so that the policy is not skipped with it.
+    ///
+    /// # Errors
+    ///
+    /// See [`Config::find_and_load`].
+    pub(super) fn load(
+        project: Option<&Utf8Path>,
+        policy: Option<&Policy>,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, ConfigError> {
+        let read = |path: &Utf8Path| -> Result<toml::Table, ConfigError> {
+            Ok(fs::read_to_string(path)?.parse()?)
+        };
+        let project = project
+            .map(read)
+            .transpose()
+            .map_err(|e| enforced(e, policy))?;
+        let project = with_env_settings(project, &var)?;
+        let api_key = var(API_KEY_ENV);
+        let project = match (project, &api_key) {
+            (None, Some(_)) => Some(toml::Table::new()),
+            (project, _) => project,
+        };
+
+        let settings = match policy {
+            Some(policy) => {
+                let (settings, overridden) = policy.apply(project.unwrap_or_default());
+                for key in overridden {
+                    eprintln!(
+                        "Warning: '{key}' in {CONFIG_FILE_NAME} is overridden by the organization policy {}",
+                        policy.path
+                    );
+                }
+                settings
+            }
+            None => match project {
+                Some(project) => project,
+                None => return Ok(None),
+            },
+        };
+        let mut config = Self::deserialize(settings).map_err(|e| enforced(e.into(), policy))?;
+        config.voyager.api_key =
+            api_key.or_else(|| config.voyager.api_key_env.as_deref().and_then(&var));
+        Ok(Some(config))
     }
 
     /// Find the config file by searching current and parent directories
@@ -205,32 +585,339 @@ impl Config {
                 _ => None,
             })
     }
+
+    /// The endpoint configured as `[endpoints.<name>]`
+    ///
+    /// The API key is read from the environment variable named by
+    /// `api-key-env`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Endpoint`] if no such endpoint is configured, one
+    /// of its URLs or its network is invalid, or its API key variable is not
+    /// set.
+    pub fn endpoint(&self, name: &str) -> Result<Endpoint, ConfigError> {
+        self.endpoint_with(name, |var| env::var(var).ok())
+    }
+
+    /// [`Self::endpoint`], reading environment variables with `var`
+    fn endpoint_with(
+        &self,
+        name: &str,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<Endpoint, ConfigError> {
+        let invalid = |reason: String| ConfigError::Endpoint {
+            name: name.to_string(),
+            reason,
+        };
+        let Some(endpoint) = self.endpoints.get(name) else {
+            let reason = if self.endpoints.is_empty() {
+                "no endpoints are configured".to_string()
+            } else {
+                format!(
+                    "it is not configured (available: {})",
+                    self.endpoints
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            return Err(invalid(reason));
+        };
+        let parse_url = |field: &str, url: &str| {
+            Url::parse(url).map_err(|e| invalid(format!("invalid {field} '{url}': {e}")))
+        };
+
+        let network = endpoint
+            .network
+            .as_deref()
+            .map(|network| match network.to_lowercase().as_str() {
+                "mainnet" => Ok(NetworkKind::Mainnet),
+                "sepolia" => Ok(NetworkKind::Sepolia),
+                "dev" => Ok(NetworkKind::Dev),
+                _ => Err(invalid(format!(
+                    "unknown network '{network}' (expected mainnet, sepolia or dev)"
+                ))),
+            })
+            .transpose()?;
+        let api_key = endpoint
+            .api_key_env
+            .as_deref()
+            .map(|name| {
+                var(name)
+                    .filter(|key| !key.trim().is_empty())
+                    .ok_or_else(|| invalid(format!("its API key variable {name} is not set")))
+            })
+            .transpose()?;
+
+        Ok(Endpoint {
+            url: parse_url("url", &endpoint.url)?,
+            network,
+            api_key,
+            rpc_url: endpoint
+                .rpc_url
+                .as_deref()
+                .map(|url| parse_url("rpc-url", url))
+                .transpose()?,
+            explorer_url: endpoint
+                .explorer_url
+                .as_deref()
+                .map(|url| parse_url("explorer-url", url))
+                .transpose()?,
+        })
+    }
+
+    /// Convert timezone string to `Timezone` enum
+    #[must_use]
+    pub fn parse_timezone(&self) -> Option<Timezone> {
+        self.voyager
+            .timezone
+            .as_ref()
+            .and_then(|tz| match tz.to_lowercase().as_str() {
+                "local" => Some(Timezone::Local),
+                "utc" => Some(Timezone::Utc),
+                _ => None,
+            })
+    }
+
+    /// Convert the duplicate submission policy string to `DuplicatePolicy`
+    #[must_use]
+    pub fn parse_on_duplicate(&self) -> Option<DuplicatePolicy> {
+        self.voyager
+            .on_duplicate
+            .as_ref()
+            .and_then(|policy| match policy.to_lowercase().as_str() {
+                "warn" => Some(DuplicatePolicy::Warn),
+                "abort" => Some(DuplicatePolicy::Abort),
+                "allow" => Some(DuplicatePolicy::Allow),
+                _ => None,
+            })
+    }
+
+    /// Convert the prompt timeout string to a `Duration`
+    #[must_use]
+    pub fn parse_prompt_timeout(&self) -> Option<Duration> {
+        self.voyager
+            .prompt_timeout
+            .as_deref()
+            .and_then(|timeout| super::prompt::parse_timeout(timeout).ok())
+    }
+
+    /// Polling settings of `[voyager]`
+    ///
+    /// Durations that cannot be parsed are ignored.
+    #[must_use]
+    pub fn polling(&self) -> PollingConfig {
+        let duration = |raw: &Option<String>| {
+            raw.as_deref()
+                .and_then(|raw| super::prompt::parse_timeout(raw).ok())
+        };
+        PollingConfig {
+            max_attempts: self.voyager.poll_max_attempts,
+            min_delay: duration(&self.voyager.poll_interval),
+            max_delay: duration(&self.voyager.poll_max_delay),
+            timeout: duration(&self.voyager.poll_timeout),
+        }
+    }
+
+    /// Convert poll strategy string to `PollStrategyKind` enum
+    #[must_use]
+    pub fn parse_poll_strategy(&self) -> Option<PollStrategyKind> {
+        self.voyager
+            .poll_strategy
+            .as_ref()
+            .and_then(|s| match s.to_lowercase().as_str() {
+                "fixed" => Some(PollStrategyKind::Fixed),
+                "exponential" => Some(PollStrategyKind::Exponential),
+                "server-hinted" => Some(PollStrategyKind::ServerHinted),
+                _ => None,
+            })
+    }
+}
+
+/// `settings` of a config file with the `[voyager]` settings set by the
+/// environment variables `var` reads
+///
+/// Returns None if there is no config file and no variable is set. A network
+/// set by the environment replaces a `url` of the config file, as the URL
+/// would otherwise select the API.
+///
+/// # Errors
+///
+/// Returns [`ConfigError::Env`] if a variable cannot be read as the type of
+/// its setting.
+fn with_env_settings(
+    settings: Option<toml::Table>,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<Option<toml::Table>, ConfigError> {
+    let overrides: Vec<(&str, toml::Value)> = ENV_SETTINGS
+        .iter()
+        .filter_map(|&(key, kind)| {
+            let name = env_var_name(key);
+            let value = var(&name)?;
+            Some(
+                env_value(&value, kind)
+                    .map(|value| (key, value))
+                    .map_err(|reason| ConfigError::Env { var: name, reason }),
+            )
+        })
+        .collect::<Result<_, _>>()?;
+    if overrides.is_empty() {
+        return Ok(settings);
+    }
+
+    let mut settings = settings.unwrap_or_default();
+    let voyager = settings
+        .entry("voyager")
+        .or_insert_with(|| toml::Table::new().into());
+    let toml::Value::Table(voyager) = voyager else {
+        return Ok(Some(settings));
+    };
+    let sets = |key: &str| overrides.iter().any(|(set, _)| *set == key);
+    if sets("network") && !sets("url") {
+        voyager.remove("url");
+    }
+    for (key, value) in overrides {
+        debug!("Using {} for '{key}'", env_var_name(key));
+        match key.split_once('.') {
+            Some((section, key)) => {
+                let section = voyager
+                    .entry(section)
+                    .or_insert_with(|| toml::Table::new().into());
+                if let toml::Value::Table(section) = section {
+                    section.insert(key.to_string(), value);
+                }
+            }
+            None => {
+                voyager.insert(key.to_string(), value);
+            }
+        }
+    }
+    Ok(Some(settings))
+}
+
+/// `value` of an environment variable as a setting of type `kind`
+fn env_value(value: &str, kind: EnvValue) -> Result<toml::Value, String> {
+    match kind {
+        EnvValue::String => Ok(value.into()),
+        EnvValue::Bool => match value.to_lowercase().as_str() {
+            "true" | "1" => Ok(true.into()),
+            "false" | "0" => Ok(false.into()),
+            _ => Err(format!("expected true or false, got '{value}'")),
+        },
+        EnvValue::Integer => value
+            .parse::<i64>()
+            .ok()
+            .filter(|value| *value >= 0)
+            .map(toml::Value::from)
+            .ok_or_else(|| format!("expected a whole number, got '{value}'")),
+        EnvValue::List => Ok(value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(toml::Value::from)
+            .collect::<Vec<_>>()
+            .into()),
+    }
+}
+
+/// Authenticates `api_client` with the API key of the environment, if any
+#[must_use]
+pub fn with_configured_api_key(api_client: ApiClient, config: Option<&Config>) -> ApiClient {
+    match config.and_then(|cfg| cfg.voyager.api_key.as_deref()) {
+        Some(api_key) => api_client.with_api_key(api_key),
+        None => api_client,
+    }
+}
+
+/// Applies the configured payload transforms, if any, to `api_client`
+///
+/// Unknown transform names are skipped with a warning.
+#[must_use]
+pub fn with_configured_transforms(api_client: ApiClient, config: Option<&Config>) -> ApiClient {
+    let Some(names) = config.and_then(|config| config.voyager.payload_transforms.as_ref()) else {
+        return api_client;
+    };
+    let transforms = names
+        .iter()
+        .filter_map(|name| {
+            let kind = PayloadTransformKind::from_name(name);
+            if kind.is_none() {
+                eprintln!(
+                    "Warning: Unknown payload transform '{name}' in .voyager.toml will be ignored"
+                );
+            }
+            kind
+        })
+        .map(PayloadTransformKind::build)
+        .collect();
+    api_client.with_transforms(transforms)
+}
+
+/// The endpoint selected with `--endpoint`, if one was
+///
+/// # Errors
+///
+/// Returns [`ConfigError::Endpoint`] if there is no config file or the endpoint
+/// cannot be used (see [`Config::endpoint`]).
+pub fn selected_endpoint(
+    name: Option<&str>,
+    config: Option<&Config>,
+) -> Result<Option<Endpoint>, ConfigError> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let config = config.ok_or_else(|| ConfigError::Endpoint {
+        name: name.to_string(),
+        reason: format!("no {CONFIG_FILE_NAME} was found"),
+    })?;
+    config.endpoint(name).map(Some)
+}
+
+/// Applies the configured poll strategy, if any, to `api_client`
+#[must_use]
+pub fn with_configured_polling(api_client: ApiClient, config: Option<&Config>) -> ApiClient {
+    with_polling(api_client, config, PollingConfig::default())
+}
+
+/// Applies the configured polling strategy to `api_client`, with the
+/// settings of `polling` replacing those of the config
+#[must_use]
+pub fn with_polling(
+    api_client: ApiClient,
+    config: Option<&Config>,
+    polling: PollingConfig,
+) -> ApiClient {
+    let kind = config.and_then(Config::parse_poll_strategy);
+    let polling = polling.or(config.map(Config::polling).unwrap_or_default());
+    match (kind, polling == PollingConfig::default()) {
+        (None, true) => api_client,
+        (kind, _) => {
+            api_client.with_poll_strategy(polling.build(kind.unwrap_or(PollStrategyKind::Fixed)))
+        }
+    }
 }
 
 /// Resolves the API URL from CLI args and config
 ///
+/// `network` is the network given with `--network`; `network` in the config
+/// selects its API when neither `--url` nor `url` in the config is set.
+///
 /// # Errors
 ///
-/// Returns an error if the URL cannot be parsed
+/// Returns [`CliError::ApiUrlUnset`](crate::utils::errors::CliError::ApiUrlUnset)
+/// if nothing selects an API.
 pub fn resolve_api_url(
-    network_url: super::args::Network,
+    mut network_url: super::args::Network,
+    network: Option<&NetworkKind>,
     config: Option<&Config>,
-) -> anyhow::Result<reqwest::Url> {
-    if network_url.url.as_str() == "https://placeholder.invalid/" {
-        if let Some(cfg) = config {
-            if let Some(ref url_str) = cfg.voyager.url {
-                Ok(reqwest::Url::parse(url_str)?)
-            } else {
-                eprintln!("Error: API URL is required. Provide --network, --url, or set 'network' or 'url' in .voyager.toml");
-                std::process::exit(1);
-            }
-        } else {
-            eprintln!("Error: API URL is required. Provide --network, --url, or set 'network' or 'url' in .voyager.toml");
-            std::process::exit(1);
-        }
-    } else {
-        Ok(network_url.url)
+) -> Result<reqwest::Url, crate::utils::errors::CliError> {
+    if let Some(cfg) = config {
+        let network = network.cloned().or_else(|| cfg.parse_network());
+        network_url.merge_with_config(network.as_ref(), cfg);
     }
+    network_url.url().cloned()
 }
 
 #[cfg(test)]
@@ -252,6 +939,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_api_url_precedence() -> Result<(), Box<dyn std::error::Error>> {
+        use super::super::args::{Network, UrlSource};
+
+        let by_network: Config = toml::from_str("[voyager]\nnetwork = \"sepolia\"")?;
+        let by_url: Config = toml::from_str(
+            "[voyager]\nnetwork = \"sepolia\"\nurl = \"https://verifier.example.com/api\"",
+        )?;
+        let network = by_network.parse_network();
+
+        let mut api = Network::unset();
+        assert!(api.url().is_err());
+        api.merge_with_config(network.as_ref(), &by_network);
+        assert_eq!(api.source(), UrlSource::NetworkDefault);
+        assert_eq!(
+            api.url()?.as_str(),
+            "https://sepolia-api.voyager.online/beta"
+        );
+
+        let mut api = Network::unset();
+        api.merge_with_config(network.as_ref(), &by_url);
+        assert_eq!(api.source(), UrlSource::Config);
+        assert_eq!(api.url()?.as_str(), "https://verifier.example.com/api");
+
+        let flag = url::Url::parse("http://localhost:8080/")?;
+        let mut api = Network::new(flag.clone(), UrlSource::CliFlag);
+        api.merge_with_config(network.as_ref(), &by_url);
+        assert_eq!(api.source(), UrlSource::CliFlag);
+        assert_eq!(api.url()?, &flag);
+        Ok(())
+    }
+
     #[test]
     fn test_parse_full_config() -> Result<(), Box<dyn std::error::Error>> {
         let toml = r#"
@@ -344,6 +1063,233 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_require_clean_git() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r"
+            [voyager]
+            require-clean-git = true
+        ";
+
+        let config: Config = toml::from_str(toml)?;
+        assert_eq!(config.voyager.require_clean_git, Some(true));
+        assert_eq!(Config::default().voyager.require_clean_git, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_skip_if_verified() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r"
+            [voyager]
+            skip-if-verified = true
+        ";
+
+        let config: Config = toml::from_str(toml)?;
+        assert_eq!(config.voyager.skip_if_verified, Some(true));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_timezone() -> Result<(), Box<dyn std::error::Error>> {
+        let config: Config = toml::from_str("[voyager]\ntimezone = \"UTC\"")?;
+        assert_eq!(config.parse_timezone(), Some(Timezone::Utc));
+
+        let config: Config = toml::from_str("[voyager]\ntimezone = \"mars\"")?;
+        assert_eq!(config.parse_timezone(), None);
+        assert_eq!(Config::default().parse_timezone(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_on_duplicate() -> Result<(), Box<dyn std::error::Error>> {
+        let config: Config = toml::from_str(
+            "[voyager]
+on-duplicate = \"Abort\"",
+        )?;
+        assert_eq!(config.parse_on_duplicate(), Some(DuplicatePolicy::Abort));
+
+        let config: Config = toml::from_str(
+            "[voyager]
+on-duplicate = \"ignore\"",
+        )?;
+        assert_eq!(config.parse_on_duplicate(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_poll_strategy() -> Result<(), Box<dyn std::error::Error>> {
+        let config: Config = toml::from_str("[voyager]\npoll-strategy = \"server-hinted\"")?;
+        assert_eq!(
+            config.parse_poll_strategy(),
+            Some(PollStrategyKind::ServerHinted)
+        );
+
+        let config: Config = toml::from_str("[voyager]\npoll-strategy = \"sometimes\"")?;
+        assert_eq!(config.parse_poll_strategy(), None);
+        assert_eq!(Config::default().parse_poll_strategy(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_check_cache_ttl() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r"
+            [voyager]
+            check-cache-ttl = 0
+        ";
+
+        let config: Config = toml::from_str(toml)?;
+        assert_eq!(config.voyager.check_cache_ttl, Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_history_retention() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r"
+            [history]
+            max-records = 5000
+            max-age-days = 180
+        ";
+
+        let config: Config = toml::from_str(toml)?;
+        let retention = config.history.retention();
+        assert_eq!(retention.max_records, Some(5000));
+        assert_eq!(retention.max_age_days, Some(180));
+        assert!(Config::default().history.retention().is_unlimited());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_limits() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r"
+            [voyager.limits]
+            max-payload-size = 5242880
+        ";
+
+        let config: Config = toml::from_str(toml)?;
+        let limits = config.voyager.limits.size_limits();
+        assert_eq!(limits.max_payload_size, 5_242_880);
+        assert_eq!(limits.max_file_size, SizeLimits::default().max_file_size);
+        assert_eq!(limits.file_limit(), 5_242_880);
+        assert_eq!(
+            Config::default().voyager.limits.size_limits(),
+            SizeLimits::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_targets() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+            [targets.starkscan]
+            url = "https://verify.starkscan.example/api"
+
+            [targets.staging]
+            url = "https://staging.verifier.example/api"
+        "#;
+
+        let config: Config = toml::from_str(toml)?;
+        assert_eq!(
+            config.targets.keys().collect::<Vec<_>>(),
+            ["staging", "starkscan"]
+        );
+        assert_eq!(
+            config.targets["starkscan"].url,
+            "https://verify.starkscan.example/api"
+        );
+        assert!(Config::default().targets.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_endpoint() -> Result<(), Box<dyn std::error::Error>> {
+        let toml = r#"
+            [endpoints.mainnet-internal]
+            url = "https://verifier.internal.example/beta"
+            network = "mainnet"
+            api-key-env = "VOYAGER_TEST_ENDPOINT_KEY"
+            rpc-url = "https://rpc.internal.example"
+
+            [endpoints.local]
+            url = "http://localhost:3000"
+            api-key-env = "VOYAGER_TEST_UNSET_KEY"
+        "#;
+
+        let config: Config = toml::from_str(toml)?;
+        let var = |name: &str| (name == "VOYAGER_TEST_ENDPOINT_KEY").then(|| "secret".to_string());
+        let endpoint = config.endpoint_with("mainnet-internal", var)?;
+        assert_eq!(
+            endpoint.url.as_str(),
+            "https://verifier.internal.example/beta"
+        );
+        assert_eq!(endpoint.network, Some(NetworkKind::Mainnet));
+        assert_eq!(endpoint.api_key.as_deref(), Some("secret"));
+        assert!(endpoint.rpc_url.is_some());
+        assert!(endpoint.explorer_url.is_none());
+
+        let err = config
+            .endpoint_with("local", var)
+            .err()
+            .ok_or("expected an error")?;
+        assert_eq!(err.error_code(), "E076");
+        assert!(err
+            .to_string()
+            .contains("VOYAGER_TEST_UNSET_KEY is not set"));
+
+        let err = config
+            .endpoint_with("staging", var)
+            .err()
+            .ok_or("expected an error")?;
+        assert!(err
+            .to_string()
+            .contains("it is not configured (available: local, mainnet-internal)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_env_settings() -> Result<(), Box<dyn std::error::Error>> {
+        let file: toml::Table = toml::from_str(
+            r#"
+            [voyager]
+            url = "https://verifier.example.com"
+            license = "MIT"
+            watch = false
+        "#,
+        )?;
+        let vars = BTreeMap::from([
+            ("VOYAGER_NETWORK", "sepolia"),
+            ("VOYAGER_WATCH", "1"),
+            ("VOYAGER_CHECK_CACHE_TTL", "60"),
+            ("VOYAGER_PAYLOAD_TRANSFORMS", "line-endings, secret-scan"),
+            ("VOYAGER_LIMITS_MAX_FILE_SIZE", "1024"),
+        ]);
+        let var = |name: &str| vars.get(name).map(ToString::to_string);
+
+        let settings = with_env_settings(Some(file), var)?.ok_or("expected settings")?;
+        let config = Config::deserialize(settings)?;
+        assert_eq!(config.parse_network(), Some(NetworkKind::Sepolia));
+        // The network of the environment selects the API over the file's URL
+        assert_eq!(config.voyager.url, None);
+        assert_eq!(config.voyager.license.as_deref(), Some("MIT"));
+        assert_eq!(config.voyager.watch, Some(true));
+        assert_eq!(config.voyager.check_cache_ttl, Some(60));
+        assert_eq!(
+            config.voyager.payload_transforms,
+            Some(vec!["line-endings".to_string(), "secret-scan".to_string()])
+        );
+        assert_eq!(config.voyager.limits.max_file_size, Some(1024));
+
+        assert_eq!(with_env_settings(None, |_| None)?, None);
+        let err = with_env_settings(None, |name| {
+            (name == "VOYAGER_LOCK_FILE").then(|| "maybe".to_string())
+        })
+        .err()
+        .ok_or("expected an error")?;
+        assert_eq!(err.error_code(), "E084");
+        assert!(err
+            .to_string()
+            .contains("VOYAGER_LOCK_FILE is invalid: expected true or false, got 'maybe'"));
+        Ok(())
+    }
+
     #[test]
     fn test_invalid_toml() {
         let toml = r#"
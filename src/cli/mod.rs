@@ -1,4 +1,7 @@
 pub mod args;
 pub mod commands;
 pub mod config;
+pub mod policy;
+pub mod prompt;
+pub mod terminal;
 pub mod wizard;
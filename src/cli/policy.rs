@@ -0,0 +1,303 @@
+//! Signed organization policy
+//!
+//! An organization can pin settings that project `.voyager.toml` files must
+//! not change, such as the API endpoint, `require-clean-git` or notification
+//! settings. The policy is a TOML file with the same layout as
+//! `.voyager.toml`, signed with an Ed25519 key. Its public key is pinned in
+//! the user configuration, `~/.voyager/config.toml`:
+//!
+//! ```toml
+//! [policy]
+//! public-key = "<hex-encoded Ed25519 public key>"
+//! path = "/etc/voyager/policy.toml"  # default: ~/.voyager/policy.toml
+//! ```
+//!
+//! The signature is read from the file next to the policy with `.sig`
+//! appended (`policy.toml.sig`), as the hex-encoded signature of the policy
+//! file's exact bytes. Once a key is pinned, a missing policy, a missing or
+//! invalid signature, or an invalid policy is an error, so the policy cannot
+//! be bypassed by removing or editing it. So is a project file that cannot be
+//! loaded, which would otherwise be skipped along with the policy.
+//!
+//! Every setting in the policy replaces the value from the project file.
+//! Command-line options still take precedence, as they do over the project
+//! file.
+
+use super::config::{Config, ConfigError};
+use camino::{Utf8Path, Utf8PathBuf};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use serde::Deserialize;
+use std::fs;
+use toml::{Table, Value};
+
+/// User configuration file, in `~/.voyager`
+pub const USER_CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Policy file used when the user configuration does not name one, in `~/.voyager`
+pub const DEFAULT_POLICY_FILE_NAME: &str = "policy.toml";
+
+/// Settings of the user configuration (`~/.voyager/config.toml`)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UserConfig {
+    /// Organization policy to enforce
+    pub policy: Option<PolicyPin>,
+}
+
+/// Where the organization policy is and which key must have signed it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PolicyPin {
+    /// Hex-encoded Ed25519 public key
+    pub public_key: String,
+    /// Policy file; `~/.voyager/policy.toml` if not set
+    pub path: Option<Utf8PathBuf>,
+}
+
+/// A policy whose signature was verified
+#[derive(Debug, Clone)]
+pub struct Policy {
+    pub path: Utf8PathBuf,
+    settings: Table,
+}
+
+impl UserConfig {
+    /// Load `~/.voyager/config.toml`; a missing file is an empty configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load() -> Result<Self, ConfigError> {
+        let Some(dir) = voyager_dir() else {
+            return Ok(Self::default());
+        };
+        let path = dir.join(USER_CONFIG_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+impl Policy {
+    /// Load the policy pinned in the user configuration, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the user configuration cannot be read, or
+    /// [`ConfigError::Policy`] if a policy is pinned but cannot be loaded or
+    /// verified.
+    pub fn find_and_load() -> Result<Option<Self>, ConfigError> {
+        let Some(pin) = UserConfig::load()?.policy else {
+            return Ok(None);
+        };
+        let path = match pin.path {
+            Some(path) => path,
+            None => voyager_dir()
+                .map(|dir| dir.join(DEFAULT_POLICY_FILE_NAME))
+                .ok_or_else(|| ConfigError::Policy {
+                    path: Utf8PathBuf::from(DEFAULT_POLICY_FILE_NAME),
+                    reason: "the home directory cannot be determined".to_string(),
+                })?,
+        };
+        Self::load(&path, &pin.public_key).map(Some)
+    }
+
+    /// Load the policy at `path` and check it against `public_key`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Policy`] if the policy or its signature cannot
+    /// be read, the signature does not match, or the policy is not a valid
+    /// configuration.
+    pub fn load(path: &Utf8Path, public_key: &str) -> Result<Self, ConfigError> {
+        let rejected = |reason: String| ConfigError::Policy {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let content = fs::read(path).map_err(|e| rejected(e.to_string()))?;
+        let signature_path = Utf8PathBuf::from(format!("{path}.sig"));
+        let signature = fs::read_to_string(&signature_path)
+            .map_err(|e| rejected(format!("cannot read signature {signature_path}: {e}")))?;
+        verify_signature(public_key, &content, &signature).map_err(rejected)?;
+
+        let content = String::from_utf8(content).map_err(|e| rejected(e.to_string()))?;
+        let settings: Table = toml::from_str(&content).map_err(|e| rejected(e.to_string()))?;
+        if settings.contains_key("contracts") {
+            return Err(rejected("a policy cannot define [[contracts]]".to_string()));
+        }
+        Config::deserialize(settings.clone()).map_err(|e| rejected(e.to_string()))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            settings,
+        })
+    }
+
+    /// Replace the settings of a project configuration with the policy's
+    ///
+    /// Returns the merged configuration and the settings of the project file
+    /// that were overridden, as dotted keys such as `voyager.url`.
+    #[must_use]
+    pub fn apply(&self, mut project: Table) -> (Table, Vec<String>) {
+        let mut overridden = vec![];
+        merge(&mut project, &self.settings, "", &mut overridden);
+        (project, overridden)
+    }
+}
+
+/// Check the hex-encoded Ed25519 `signature` of `content`
+///
+/// # Errors
+///
+/// Returns the reason if the key or signature is malformed or the signature
+/// does not match.
+pub fn verify_signature(public_key: &str, content: &[u8], signature: &str) -> Result<(), String> {
+    let public_key = decode_hex(public_key).ok_or("the pinned public key is not valid hex")?;
+    let signature = decode_hex(signature).ok_or("the signature is not valid hex")?;
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(content, &signature)
+        .map_err(|_| "the signature does not match the pinned public key".to_string())
+}
+
+/// Copy `policy` into `target`, recording the keys whose value changed
+fn merge(target: &mut Table, policy: &Table, prefix: &str, overridden: &mut Vec<String>) {
+    for (key, value) in policy {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match (target.get_mut(key), value) {
+            (Some(Value::Table(existing)), Value::Table(settings)) => {
+                merge(existing, settings, &path, overridden);
+            }
+            (existing, _) => {
+                if existing.is_some_and(|existing| existing != value) {
+                    overridden.push(path);
+                }
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim();
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn voyager_dir() -> Option<Utf8PathBuf> {
+    let home = Utf8PathBuf::try_from(dirs::home_dir()?).ok()?;
+    Some(home.join(".voyager"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use ring::{
+        rand::SystemRandom,
+        signature::{Ed25519KeyPair, KeyPair},
+    };
+    use std::fmt::Write as _;
+    use tempfile::TempDir;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().fold(String::new(), |mut out, byte| {
+            write!(out, "{byte:02x}").unwrap();
+            out
+        })
+    }
+
+    #[test]
+    fn test_signed_policy_overrides_project() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public_key = hex(key.public_key().as_ref());
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(temp_dir.path().join("policy.toml")).unwrap();
+        let policy =
+            "[voyager]\nurl = \"https://voyager.internal/api\"\nrequire-clean-git = true\n";
+        fs::write(&path, policy).unwrap();
+        let signature_path = format!("{path}.sig");
+
+        // Unsigned
+        assert!(matches!(
+            Policy::load(&path, &public_key),
+            Err(ConfigError::Policy { .. })
+        ));
+
+        fs::write(&signature_path, hex(key.sign(policy.as_bytes()).as_ref())).unwrap();
+        let loaded = Policy::load(&path, &public_key).unwrap();
+
+        let project: Table = toml::from_str(
+            "[voyager]\nnetwork = \"sepolia\"\nurl = \"https://example.com\"\n\n[workspace]\ndefault-package = \"token\"\n",
+        )
+        .unwrap();
+        let (merged, overridden) = loaded.apply(project);
+        assert_eq!(overridden, ["voyager.url"]);
+        let config = Config::deserialize(merged).unwrap();
+        assert_eq!(
+            config.voyager.url.as_deref(),
+            Some("https://voyager.internal/api")
+        );
+        assert_eq!(config.voyager.require_clean_git, Some(true));
+        assert_eq!(config.voyager.network.as_deref(), Some("sepolia"));
+        assert_eq!(config.workspace.default_package.as_deref(), Some("token"));
+
+        // Edited after signing
+        fs::write(&path, policy.replace("true", "false")).unwrap();
+        let Err(ConfigError::Policy { reason, .. }) = Policy::load(&path, &public_key) else {
+            panic!("tampered policy was accepted");
+        };
+        assert_eq!(reason, "the signature does not match the pinned public key");
+    }
+
+    #[test]
+    fn test_broken_project_file_does_not_skip_policy() {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let key = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(temp_dir.path().join("policy.toml")).unwrap();
+        let policy = "[voyager]\nrequire-clean-git = true\n";
+        fs::write(&path, policy).unwrap();
+        fs::write(
+            format!("{path}.sig"),
+            hex(key.sign(policy.as_bytes()).as_ref()),
+        )
+        .unwrap();
+        let policy = Policy::load(&path, &hex(key.public_key().as_ref())).unwrap();
+
+        let project = Utf8PathBuf::try_from(temp_dir.path().join(".voyager.toml")).unwrap();
+        for broken in ["[voyager\n", "[voyager]\nnetwork = 5\n"] {
+            fs::write(&project, broken).unwrap();
+            assert!(matches!(
+                Config::load(Some(&project), Some(&policy), |_| None),
+                Err(ConfigError::Policy { .. })
+            ));
+            // Without a policy, it is only the project file that fails
+            assert!(matches!(
+                Config::load(Some(&project), None, |_| None),
+                Err(ConfigError::Parse(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex("0x00ff10"), Some(vec![0x00, 0xff, 0x10]));
+        assert_eq!(decode_hex(" abcd\n"), Some(vec![0xab, 0xcd]));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+}
@@ -0,0 +1,153 @@
+//! Prompts that give up after a while
+//!
+//! A forgotten `--wizard`, or a prompt reached in a job whose stdin is an open
+//! pipe, would otherwise wait for an answer forever. With `--prompt-timeout`
+//! each question gets that long to be answered, after which the caller falls
+//! back to the safe choice: the default answer, or cancelling.
+
+use crate::output::status::format_duration;
+use dialoguer::console::Term;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Parse a prompt timeout such as `30s`, `2m` or `1h`; plain numbers are
+/// seconds
+///
+/// # Errors
+///
+/// Returns an error if `raw` is not a number with an optional `s`, `m` or `h`
+/// unit.
+pub fn parse_timeout(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim();
+    let (amount, unit) = raw
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or((raw, ""), |index| raw.split_at(index));
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid timeout '{raw}': expected e.g. 30s, 2m or 1h"))?;
+    let seconds = match unit {
+        "" | "s" => amount,
+        "m" => amount.saturating_mul(60),
+        "h" => amount.saturating_mul(3_600),
+        _ => return Err(format!("invalid timeout unit '{unit}': expected s, m or h")),
+    };
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Ask `prompt`, giving up after `timeout`
+///
+/// Returns `Ok(None)` if no answer came in time, with the terminal put back in
+/// the mode it was in before the prompt. The prompt keeps waiting for input in
+/// the background, so the caller should not ask anything else that the user
+/// could be typing into. Without a timeout, `prompt` is asked directly.
+///
+/// # Errors
+///
+/// Returns the error of `prompt`.
+pub fn ask<T, E, F>(timeout: Option<Duration>, prompt: F) -> Result<Option<T>, E>
+where
+    T: Send + 'static,
+    E: Send + 'static,
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+{
+    let Some(timeout) = timeout else {
+        return prompt().map(Some);
+    };
+
+    // Selection prompts switch the terminal to raw mode while they wait for a
+    // key, and only switch it back once one is read
+    let mode = TerminalMode::save();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone once the prompt timed out
+        let _ = sender.send(prompt());
+    });
+
+    receiver.recv_timeout(timeout).map_or_else(
+        |_| {
+            if let Some(mode) = &mode {
+                mode.restore();
+            }
+            // Selection prompts hide the cursor while they wait
+            let _ = Term::stderr().show_cursor();
+            eprintln!("\nNo answer within {}", format_duration(timeout.as_secs()));
+            Ok(None)
+        },
+        |answer| answer.map(Some),
+    )
+}
+
+/// Settings of the controlling terminal
+#[cfg(unix)]
+struct TerminalMode {
+    tty: std::fs::File,
+    termios: rustix::termios::Termios,
+}
+
+#[cfg(unix)]
+impl TerminalMode {
+    /// The current settings, if there is a controlling terminal
+    fn save() -> Option<Self> {
+        let tty = std::fs::File::options()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")
+            .ok()?;
+        let termios = rustix::termios::tcgetattr(&tty).ok()?;
+        Some(Self { tty, termios })
+    }
+
+    /// Put the terminal back into the saved mode
+    fn restore(&self) {
+        let _ = rustix::termios::tcsetattr(
+            &self.tty,
+            rustix::termios::OptionalActions::Now,
+            &self.termios,
+        );
+    }
+}
+
+/// Settings of the controlling terminal; only kept on Unix
+#[cfg(not(unix))]
+struct TerminalMode;
+
+#[cfg(not(unix))]
+impl TerminalMode {
+    const fn save() -> Option<Self> {
+        None
+    }
+
+    const fn restore(&self) {}
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("30").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_timeout("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_timeout("2m").unwrap(), Duration::from_mins(2));
+        assert_eq!(parse_timeout("1h").unwrap(), Duration::from_hours(1));
+        assert!(parse_timeout("soon").is_err());
+        assert!(parse_timeout("10d").is_err());
+        assert!(parse_timeout("").is_err());
+    }
+
+    #[test]
+    fn test_ask_times_out() {
+        let answer: Result<Option<bool>, ()> = ask(Some(Duration::from_millis(10)), || {
+            thread::sleep(Duration::from_secs(5));
+            Ok(true)
+        });
+        assert_eq!(answer, Ok(None));
+        assert_eq!(
+            ask(Some(Duration::from_secs(5)), || Ok::<_, ()>(1)),
+            Ok(Some(1))
+        );
+        assert_eq!(ask(None, || Err::<u8, _>("failed")), Err("failed"));
+    }
+}
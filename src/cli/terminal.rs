@@ -0,0 +1,60 @@
+//! Terminal output of the CLI
+//!
+//! The verification flow writes through [`crate::output::sink`]; the CLI
+//! installs [`Terminal`] at startup so that its lines reach standard output
+//! and standard error, colored unless `NO_COLOR` is set or the output is not a
+//! terminal. Progress lines are drawn on standard error when it is a terminal.
+
+use crate::output::sink::OutputSink;
+use std::io::{IsTerminal, Write};
+
+/// Sink printing regular output to stdout and warnings to stderr
+#[derive(Debug, Clone, Copy)]
+pub struct Terminal {
+    colors: bool,
+    /// Whether stderr is a terminal, where progress lines can be rewritten
+    progress: bool,
+}
+
+impl Terminal {
+    /// Colors as decided by `NO_COLOR`, `CLICOLOR` and whether stdout is a
+    /// terminal
+    #[must_use]
+    pub fn detect() -> Self {
+        Self {
+            colors: colored::control::SHOULD_COLORIZE.should_colorize(),
+            progress: std::io::stderr().is_terminal(),
+        }
+    }
+}
+
+impl OutputSink for Terminal {
+    fn line(&self, text: &str) {
+        println!("{text}");
+    }
+
+    fn warning(&self, text: &str) {
+        eprintln!("{text}");
+    }
+
+    fn progress(&self, text: Option<&str>) {
+        if !self.progress {
+            return;
+        }
+        // Clear the line, then draw the new one if any
+        eprint!("\r\x1B[2K{}", text.unwrap_or_default());
+        std::io::stderr().flush().ok();
+    }
+
+    fn shows_progress(&self) -> bool {
+        self.progress
+    }
+
+    fn colors(&self) -> bool {
+        self.colors
+    }
+
+    fn emoji(&self) -> bool {
+        true
+    }
+}
@@ -4,14 +4,18 @@
 //! interactive prompts over CLI flags.
 
 use super::args::{
-    contract_name_value_parser, license_value_parser, Network, NetworkKind, Project, VerifyArgs,
+    contract_name_value_parser, Network, NetworkKind, OutputFormat, Project, UrlSource, VerifyArgs,
 };
+use super::prompt;
 use crate::core::{class_hash::ClassHash, project::ProjectType};
-use crate::utils::errors::CliError;
+use crate::filesystem::collector::SizeLimits;
+use crate::storage::history::RetentionPolicy;
+use crate::utils::{errors::CliError, license};
 use dialoguer::{Confirm, Input, Select};
 use reqwest::Url;
 use scarb_metadata::PackageMetadata;
 use spdx::LicenseId;
+use std::time::Duration;
 
 /// Summary of verification parameters for display
 #[allow(clippy::struct_excessive_bools)]
@@ -36,6 +40,8 @@ struct VerificationSummary<'a> {
 /// # Arguments
 ///
 /// * `project` - Already-loaded Scarb project
+/// * `timeout` - How long each prompt waits for an answer before the wizard
+///   is cancelled, or `None` to wait indefinitely
 ///
 /// # Returns
 ///
@@ -45,32 +51,32 @@ struct VerificationSummary<'a> {
 ///
 /// Returns a `CliError` if:
 /// - User cancels the wizard
-/// - Interactive prompts fail (non-TTY environment)
+/// - Interactive prompts fail (non-TTY environment) or time out
 /// - Invalid input is provided and validation fails
-pub fn run_wizard(project: Project) -> Result<VerifyArgs, CliError> {
+pub fn run_wizard(project: Project, timeout: Option<Duration>) -> Result<VerifyArgs, CliError> {
     println!("\n🧙 Interactive Verification Wizard\n");
     println!("This wizard will guide you through verifying your contract on Voyager.\n");
 
     // 1. Network selection
-    let (network, network_url) = prompt_network()?;
+    let (network, network_url) = prompt_network(timeout)?;
 
     // 2. Class hash input
-    let class_hash = prompt_class_hash()?;
+    let class_hash = prompt_class_hash(timeout)?;
 
     // 3. Package selection (if workspace)
-    let package = prompt_package(&project)?;
+    let package = prompt_package(&project, timeout)?;
 
     // 4. Contract name
-    let contract_name = prompt_contract_name()?;
+    let contract_name = prompt_contract_name(timeout)?;
 
     // 5. License selection
-    let license = prompt_license(&project)?;
+    let license = prompt_license(&project, package.as_deref(), timeout)?;
 
     // 6. Optional features
-    let lock_file = prompt_lock_file()?;
-    let test_files = prompt_test_files()?;
-    let watch = prompt_watch()?;
-    let verbose = prompt_verbose()?;
+    let lock_file = prompt_lock_file(timeout)?;
+    let test_files = prompt_test_files(timeout)?;
+    let watch = prompt_watch(timeout)?;
+    let verbose = prompt_verbose(timeout)?;
 
     // 7. Show summary
     let summary = VerificationSummary {
@@ -88,7 +94,7 @@ pub fn run_wizard(project: Project) -> Result<VerifyArgs, CliError> {
     show_summary(&summary);
 
     // 8. Final confirmation
-    if !confirm_proceed()? {
+    if !confirm_proceed(timeout)? {
         println!("\n❌ Verification cancelled by user.");
         std::process::exit(0);
     }
@@ -98,11 +104,21 @@ pub fn run_wizard(project: Project) -> Result<VerifyArgs, CliError> {
         network,
         network_url,
         dry_run: false,
+        format: OutputFormat::Text,
         path: project,
         class_hash: Some(class_hash),
+        rpc_url: None,
+        contract_address: None,
+        from_receipt: None,
         watch,
+        poll_interval: None,
+        poll_timeout: None,
         license,
         contract_name: Some(contract_name),
+        contract_file: None,
+        extra_sources: vec![],
+        include_docs: false,
+        doc_paths: vec![],
         package,
         lock_file,
         test_files,
@@ -111,69 +127,94 @@ pub fn run_wizard(project: Project) -> Result<VerifyArgs, CliError> {
         notify: false,
         verbose,
         wizard: true, // Mark as wizard mode
+        non_interactive: false,
+        // The wizard asked for confirmation already
+        yes: true,
+        stdin_tar: false,
+        from_pack: None,
+        all_deployed: false,
+        address_file: None,
+        from_dojo_manifest: None,
+        world: None,
         fail_fast: false,
         batch_delay: None,
+        jobs: 1,
+        on_duplicate: None,
+        resume: None,
+        require_clean_git: false,
+        allow_dirty: false,
+        allow_license_mismatch: false,
+        require_license_headers: false,
+        preflight: false,
+        compress: false,
+        compare_class_hash: false,
+        skip_if_verified: false,
+        force: false,
+        results: None,
+        state: None,
+        bundle: None,
+        timezone: None,
+        simulate: false,
+        targets: Vec::new(),
+        note: None,
+        endpoint: None,
+        api_key: None,
+        explorer_url: None,
+        metrics_addr: None,
+        prompt_timeout: timeout,
+        history_retention: RetentionPolicy::default(),
+        size_limits: SizeLimits::default(),
     })
 }
 
 /// Prompt for network selection
-fn prompt_network() -> Result<(Option<NetworkKind>, Network), CliError> {
-    let options = vec![
+fn prompt_network(timeout: Option<Duration>) -> Result<(Option<NetworkKind>, Network), CliError> {
+    let options = [
         "Mainnet (api.voyager.online)",
         "Sepolia (sepolia-api.voyager.online)",
         "Dev (dev-api.voyager.online)",
         "Custom URL",
     ];
 
-    let selection = Select::new()
-        .with_prompt("Select network")
-        .items(&options)
-        .default(0)
-        .interact()?;
+    let selection = answer(timeout, move || {
+        Select::new()
+            .with_prompt("Select network")
+            .items(&options)
+            .default(0)
+            .interact()
+    })?;
 
     match selection {
         0 => Ok((
             Some(NetworkKind::Mainnet),
-            Network {
-                // SAFETY: Hardcoded URL is guaranteed to be valid
-                #[allow(clippy::unwrap_used)]
-                url: Url::parse("https://api.voyager.online/beta").unwrap(),
-            },
+            Network::for_network(&NetworkKind::Mainnet),
         )),
         1 => Ok((
             Some(NetworkKind::Sepolia),
-            Network {
-                // SAFETY: Hardcoded URL is guaranteed to be valid
-                #[allow(clippy::unwrap_used)]
-                url: Url::parse("https://sepolia-api.voyager.online/beta").unwrap(),
-            },
+            Network::for_network(&NetworkKind::Sepolia),
         )),
         2 => Ok((
             Some(NetworkKind::Dev),
-            Network {
-                // SAFETY: Hardcoded URL is guaranteed to be valid
-                #[allow(clippy::unwrap_used)]
-                url: Url::parse("https://dev-api.voyager.online/beta").unwrap(),
-            },
+            Network::for_network(&NetworkKind::Dev),
         )),
         3 => {
             // Custom URL input
-            let url: String = Input::new()
-                .with_prompt("Enter custom network URL")
-                .validate_with(|input: &String| -> Result<(), &str> {
-                    Url::parse(input)
-                        .map(|_| ())
-                        .map_err(|_| "Invalid URL format. Please enter a valid HTTP/HTTPS URL")
-                })
-                .interact_text()?;
+            let url: String = answer(timeout, || {
+                Input::new()
+                    .with_prompt("Enter custom network URL")
+                    .validate_with(|input: &String| -> Result<(), &str> {
+                        Url::parse(input)
+                            .map(|_| ())
+                            .map_err(|_| "Invalid URL format. Please enter a valid HTTP/HTTPS URL")
+                    })
+                    .interact_text()
+            })?;
 
             // SAFETY: URL was validated in the input above
             #[allow(clippy::unwrap_used)]
             Ok((
                 None,
-                Network {
-                    url: Url::parse(&url).unwrap(),
-                },
+                Network::new(Url::parse(&url).unwrap(), UrlSource::CliFlag),
             ))
         }
         _ => unreachable!(),
@@ -181,14 +222,16 @@ fn prompt_network() -> Result<(Option<NetworkKind>, Network), CliError> {
 }
 
 /// Prompt for class hash input
-fn prompt_class_hash() -> Result<ClassHash, CliError> {
-    let hash_str: String = Input::new()
-        .with_prompt("Enter class hash")
-        .validate_with(|input: &String| -> Result<(), String> {
-            // Validate using the ClassHash constructor
-            ClassHash::new(input).map(|_| ()).map_err(|e| e.to_string())
-        })
-        .interact_text()?;
+fn prompt_class_hash(timeout: Option<Duration>) -> Result<ClassHash, CliError> {
+    let hash_str: String = answer(timeout, || {
+        Input::new()
+            .with_prompt("Enter class hash")
+            .validate_with(|input: &String| -> Result<(), String> {
+                // Validate using the ClassHash constructor
+                ClassHash::new(input).map(|_| ()).map_err(|e| e.to_string())
+            })
+            .interact_text()
+    })?;
 
     // This should never fail because we validated above, but handle it just in case
     ClassHash::new(&hash_str).map_err(|e| {
@@ -200,8 +243,11 @@ fn prompt_class_hash() -> Result<ClassHash, CliError> {
 }
 
 /// Prompt for package selection (only for workspaces)
-fn prompt_package(project: &Project) -> Result<Option<String>, CliError> {
-    let metadata = project.metadata();
+fn prompt_package(
+    project: &Project,
+    timeout: Option<Duration>,
+) -> Result<Option<String>, CliError> {
+    let metadata = project.load()?;
 
     // Gather packages
     let packages: Vec<&PackageMetadata> = metadata
@@ -241,132 +287,181 @@ fn prompt_package(project: &Project) -> Result<Option<String>, CliError> {
         return Ok(Some(package_names[0].clone()));
     }
 
-    let selection = Select::new()
-        .with_prompt("Select package to verify")
-        .items(&package_names)
-        .default(0)
-        .interact()?;
+    let items = package_names.clone();
+    let selection = answer(timeout, move || {
+        Select::new()
+            .with_prompt("Select package to verify")
+            .items(&items)
+            .default(0)
+            .interact()
+    })?;
 
     Ok(Some(package_names[selection].clone()))
 }
 
 /// Prompt for contract name
-fn prompt_contract_name() -> Result<String, CliError> {
-    let name: String = Input::new()
-        .with_prompt("Enter contract name")
-        .validate_with(|input: &String| -> Result<(), String> {
-            contract_name_value_parser(input).map(|_| ())
-        })
-        .interact_text()?;
+fn prompt_contract_name(timeout: Option<Duration>) -> Result<String, CliError> {
+    let name: String = answer(timeout, || {
+        Input::new()
+            .with_prompt("Enter contract name")
+            .validate_with(|input: &String| -> Result<(), String> {
+                contract_name_value_parser(input).map(|_| ())
+            })
+            .interact_text()
+    })?;
 
     Ok(name)
 }
 
-/// Prompt for license selection
-fn prompt_license(project: &Project) -> Result<Option<LicenseId>, CliError> {
-    // Try to detect license from Scarb.toml
-    let detected_license = project.get_license();
+/// Number of search results offered at once in the license picker
+const LICENSE_SEARCH_RESULTS: usize = 15;
 
+/// `MIT — MIT License`
+fn license_label(id: LicenseId) -> String {
+    format!("{} — {}", id.name, id.full_name)
+}
+
+/// Prompt for license selection
+///
+/// Offers the detected and most common licenses, and a search over the full
+/// SPDX license list by identifier or name.
+fn prompt_license(
+    project: &Project,
+    package: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<Option<LicenseId>, CliError> {
+    // Try to detect license from the selected package's Scarb.toml
+    let detected_license = project.get_license(package)?;
+
+    let mut choices: Vec<LicenseId> = vec![];
     let mut options = vec![];
 
     // Add detected license first if found
-    if let Some(detected) = &detected_license {
-        options.push(format!("{} (detected in Scarb.toml)", detected.name));
+    if let Some(detected) = detected_license {
+        choices.push(detected);
+        options.push(format!(
+            "{} (detected in Scarb.toml)",
+            license_label(detected)
+        ));
     }
 
-    // Default to first option
-    let default_idx = 0;
-
     // Add common licenses
-    let common_licenses = vec!["MIT", "Apache-2.0", "GPL-3.0", "BSD-3-Clause", "ISC"];
-
-    for lic in &common_licenses {
+    for lic in ["MIT", "Apache-2.0", "GPL-3.0-only", "BSD-3-Clause", "ISC"] {
+        let Some(id) = spdx::license_id(lic) else {
+            continue;
+        };
         // Skip if already added as detected
-        if detected_license
-            .as_ref()
-            .is_some_and(|d| d.name.contains(lic) || lic.contains(d.name))
-        {
+        if detected_license == Some(id) {
             continue;
         }
-        options.push((*lic).to_string());
+        choices.push(id);
+        options.push(license_label(id));
     }
 
+    options.push("Search all SPDX licenses...".to_string());
     options.push("None (no license)".to_string());
-    options.push("Custom SPDX identifier...".to_string());
 
-    let selection = Select::new()
-        .with_prompt("Select license")
-        .items(&options)
-        .default(default_idx)
-        .interact()?;
-
-    // Handle selection
-    if selection == options.len() - 2 {
+    let selection = answer(timeout, move || {
+        Select::new()
+            .with_prompt("Select license")
+            .items(&options)
+            .default(0)
+            .interact()
+    })?;
+
+    match choices.get(selection) {
+        Some(id) => Ok(Some(*id)),
+        None if selection == choices.len() => prompt_license_search(timeout).map(Some),
         // "None" selected
-        Ok(None)
-    } else if selection == options.len() - 1 {
-        // "Custom" selected
-        let custom: String = Input::new()
-            .with_prompt("Enter SPDX license identifier (e.g., MIT, Apache-2.0)")
-            .validate_with(|input: &String| -> Result<(), String> {
-                if input.is_empty() {
-                    return Err("License identifier cannot be empty".to_string());
-                }
-                license_value_parser(input).map(|_| ())
-            })
-            .interact_text()?;
-
-        Ok(Some(license_value_parser(&custom).map_err(|e| {
-            CliError::InteractivePromptFailed(dialoguer::Error::IO(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!("Invalid license identifier: {e}"),
-            )))
-        })?))
-    } else if selection == 0 && detected_license.is_some() {
-        // Detected license selected
-        Ok(detected_license)
-    } else {
-        // One of the common licenses selected
-        let selected_name = &options[selection];
-        Ok(Some(license_value_parser(selected_name).map_err(|e| {
-            CliError::InteractivePromptFailed(dialoguer::Error::IO(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!("Invalid license identifier: {e}"),
-            )))
-        })?))
+        None => Ok(None),
+    }
+}
+
+/// Search the SPDX license list until a license is picked
+fn prompt_license_search(timeout: Option<Duration>) -> Result<LicenseId, CliError> {
+    loop {
+        let query: String = answer(timeout, || {
+            Input::new()
+                .with_prompt("Search licenses by identifier or name (e.g., apache, GPL, Mozilla)")
+                .interact_text()
+        })?;
+
+        let matches = license::search_licenses(&query, LICENSE_SEARCH_RESULTS);
+        if matches.is_empty() {
+            println!("No SPDX license matches '{query}', try another search.");
+            continue;
+        }
+
+        let mut options: Vec<String> = matches.iter().map(|id| license_label(*id)).collect();
+        options.push("Search again...".to_string());
+
+        let heading = format!("Licenses matching '{query}'");
+        let selection = answer(timeout, move || {
+            Select::new()
+                .with_prompt(heading)
+                .items(&options)
+                .default(0)
+                .max_length(LICENSE_SEARCH_RESULTS + 1)
+                .interact()
+        })?;
+
+        if let Some(id) = matches.get(selection) {
+            return Ok(*id);
+        }
     }
 }
 
 /// Prompt for Scarb.lock file inclusion
-fn prompt_lock_file() -> Result<bool, CliError> {
-    Ok(Confirm::new()
-        .with_prompt("Include Scarb.lock file? (recommended for reproducible builds)")
-        .default(true)
-        .interact()?)
+fn prompt_lock_file(timeout: Option<Duration>) -> Result<bool, CliError> {
+    answer(timeout, || {
+        Confirm::new()
+            .with_prompt("Include Scarb.lock file? (recommended for reproducible builds)")
+            .default(true)
+            .interact()
+    })
 }
 
 /// Prompt for test files inclusion
-fn prompt_test_files() -> Result<bool, CliError> {
-    Ok(Confirm::new()
-        .with_prompt("Include test files from src/ directory?")
-        .default(false)
-        .interact()?)
+fn prompt_test_files(timeout: Option<Duration>) -> Result<bool, CliError> {
+    answer(timeout, || {
+        Confirm::new()
+            .with_prompt("Include test files from src/ directory?")
+            .default(false)
+            .interact()
+    })
 }
 
 /// Prompt for watch mode
-fn prompt_watch() -> Result<bool, CliError> {
-    Ok(Confirm::new()
-        .with_prompt("Watch for verification completion? (poll until done)")
-        .default(true)
-        .interact()?)
+fn prompt_watch(timeout: Option<Duration>) -> Result<bool, CliError> {
+    answer(timeout, || {
+        Confirm::new()
+            .with_prompt("Watch for verification completion? (poll until done)")
+            .default(true)
+            .interact()
+    })
 }
 
 /// Prompt for verbose output
-fn prompt_verbose() -> Result<bool, CliError> {
-    Ok(Confirm::new()
-        .with_prompt("Enable verbose output? (show detailed debug information)")
-        .default(false)
-        .interact()?)
+fn prompt_verbose(timeout: Option<Duration>) -> Result<bool, CliError> {
+    answer(timeout, || {
+        Confirm::new()
+            .with_prompt("Enable verbose output? (show detailed debug information)")
+            .default(false)
+            .interact()
+    })
+}
+
+/// Ask `prompt`, cancelling the wizard if it is not answered within `timeout`
+fn answer<T: Send + 'static>(
+    timeout: Option<Duration>,
+    prompt: impl FnOnce() -> dialoguer::Result<T> + Send + 'static,
+) -> Result<T, CliError> {
+    prompt::ask(timeout, prompt)?.ok_or_else(|| {
+        CliError::InteractivePromptFailed(dialoguer::Error::IO(std::io::Error::new(
+            std::io::ErrorKind::TimedOut,
+            "the wizard was not answered before --prompt-timeout",
+        )))
+    })
 }
 
 /// Display verification summary
@@ -381,10 +476,10 @@ fn show_summary(summary: &VerificationSummary) {
         Some(NetworkKind::Dev) => "Dev",
         None => "Custom",
     };
-    println!(
-        "   Network:      {} ({})",
-        network_display, summary.network_url.url
-    );
+    match summary.network_url.url() {
+        Ok(url) => println!("   Network:      {network_display} ({url})"),
+        Err(_) => println!("   Network:      {network_display}"),
+    }
 
     // Class hash (truncated for display)
     let hash_str = summary.class_hash.to_string();
@@ -432,16 +527,19 @@ fn show_summary(summary: &VerificationSummary) {
 }
 
 /// Confirm proceed with verification
-fn confirm_proceed() -> Result<bool, CliError> {
-    Ok(Confirm::new()
-        .with_prompt("Proceed with verification?")
-        .default(true)
-        .interact()?)
+fn confirm_proceed(timeout: Option<Duration>) -> Result<bool, CliError> {
+    answer(timeout, || {
+        Confirm::new()
+            .with_prompt("Proceed with verification?")
+            .default(true)
+            .interact()
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::args::license_value_parser;
 
     #[test]
     fn test_contract_name_validation() {
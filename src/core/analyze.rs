@@ -0,0 +1,292 @@
+//! Size analysis of a verification payload
+//!
+//! Backs `voyager analyze`: given the files the resolver would submit, it
+//! totals their size per directory, finds the largest files, estimates how
+//! well they compress, and points out files that could be left out.
+
+use crate::storage::history::PayloadStats;
+use camino::Utf8PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, Cursor, Write},
+};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+/// Files that are not compiled are worth a suggestion from this size on
+const LARGE_FILE_SIZE: u64 = 64 * 1024;
+
+/// A submitted file and its size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyzedFile {
+    /// Path as submitted, relative to the project root
+    pub name: String,
+    pub size: u64,
+    /// Size after deflate compression
+    pub compressed_size: u64,
+}
+
+/// Total size of the files in a directory, including subdirectories
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryTotal {
+    /// Directory relative to the project root; empty for the root itself
+    pub path: String,
+    pub files: usize,
+    pub size: u64,
+}
+
+/// Files that could be left out of the submission, and how
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exclusion {
+    pub files: Vec<String>,
+    pub size: u64,
+    pub hint: String,
+}
+
+/// Sizes of the files in a payload
+#[derive(Debug, Clone)]
+pub struct PayloadAnalysis {
+    /// Files by decreasing size
+    pub files: Vec<AnalyzedFile>,
+    pub total_size: u64,
+    pub compressed_size: u64,
+}
+
+impl PayloadAnalysis {
+    /// Read and compress the files of a payload, given as submitted name to path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read.
+    pub fn of_files<S: std::hash::BuildHasher>(
+        files: &HashMap<String, Utf8PathBuf, S>,
+    ) -> io::Result<Self> {
+        let mut names: Vec<&String> = files.keys().collect();
+        names.sort();
+
+        let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        for name in &names {
+            archive.start_file(name.as_str(), options)?;
+            archive.write_all(&fs::read(&files[*name])?)?;
+        }
+        let archive = archive.finish()?.into_inner();
+
+        let mut archive = ZipArchive::new(Cursor::new(archive))?;
+        let mut analyzed = Vec::with_capacity(names.len());
+        for index in 0..archive.len() {
+            let entry = archive.by_index(index)?;
+            analyzed.push(AnalyzedFile {
+                name: entry.name().to_string(),
+                size: entry.size(),
+                compressed_size: entry.compressed_size(),
+            });
+        }
+        analyzed.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+
+        Ok(Self {
+            total_size: analyzed.iter().map(|file| file.size).sum(),
+            compressed_size: analyzed.iter().map(|file| file.compressed_size).sum(),
+            files: analyzed,
+        })
+    }
+
+    /// Sizes recorded in history for a submission of these files
+    #[must_use]
+    pub fn stats(&self) -> PayloadStats {
+        let largest = self.files.first();
+        PayloadStats {
+            size: self.total_size,
+            files: self.files.len(),
+            compressed_size: self.compressed_size,
+            largest_file: largest.map(|file| file.name.clone()).unwrap_or_default(),
+            largest_file_size: largest.map_or(0, |file| file.size),
+        }
+    }
+
+    /// The `count` largest files
+    #[must_use]
+    pub fn largest(&self, count: usize) -> &[AnalyzedFile] {
+        &self.files[..count.min(self.files.len())]
+    }
+
+    /// Totals of every directory that contains files, sorted by path
+    #[must_use]
+    pub fn directories(&self) -> Vec<DirectoryTotal> {
+        let mut totals: BTreeMap<&str, (usize, u64)> = BTreeMap::new();
+        for file in &self.files {
+            let mut path = file.name.as_str();
+            loop {
+                path = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+                let total = totals.entry(path).or_default();
+                total.0 += 1;
+                total.1 += file.size;
+                if path.is_empty() {
+                    break;
+                }
+            }
+        }
+        totals
+            .into_iter()
+            .map(|(path, (files, size))| DirectoryTotal {
+                path: path.to_string(),
+                files,
+                size,
+            })
+            .collect()
+    }
+
+    /// Files that are not needed to compile the contract
+    ///
+    /// `test_files` is whether test files were included on request, and
+    /// `unneeded_packages` lists workspace members (name and directory
+    /// relative to the project root) that the verified package does not
+    /// depend on.
+    #[must_use]
+    pub fn exclusions(
+        &self,
+        test_files: bool,
+        unneeded_packages: &[(String, String)],
+    ) -> Vec<Exclusion> {
+        let mut exclusions = vec![];
+        let mut add = |files: Vec<&AnalyzedFile>, hint: String| {
+            if !files.is_empty() {
+                exclusions.push(Exclusion {
+                    size: files.iter().map(|file| file.size).sum(),
+                    files: files.iter().map(|file| file.name.clone()).collect(),
+                    hint,
+                });
+            }
+        };
+
+        for (package, dir) in unneeded_packages {
+            let prefix = format!("{dir}/");
+            add(
+                self.files
+                    .iter()
+                    .filter(|file| file.name.starts_with(&prefix))
+                    .collect(),
+                format!("package {package} is not a dependency of the verified package"),
+            );
+        }
+        if test_files {
+            add(
+                self.files
+                    .iter()
+                    .filter(|file| is_test_file(&file.name))
+                    .collect(),
+                "test files are only needed with --test-files".to_string(),
+            );
+        }
+        add(
+            self.files
+                .iter()
+                .filter(|file| file.size >= LARGE_FILE_SIZE && !is_compiled(&file.name))
+                .collect(),
+            "large files that are not compiled; check the readme and license-file entries of Scarb.toml"
+                .to_string(),
+        );
+
+        exclusions
+    }
+}
+
+/// Whether the file is a test module, as the resolver recognizes them
+fn is_test_file(name: &str) -> bool {
+    let name = format!("/{name}");
+    name.contains("/test")
+}
+
+/// Whether the compiler needs the file: Cairo and Rust sources and manifests
+fn is_compiled(name: &str) -> bool {
+    let file = name.rsplit('/').next().unwrap_or(name);
+    [".cairo", ".rs"].iter().any(|ext| file.ends_with(ext))
+        || ["Scarb.toml", "Scarb.lock", "Cargo.toml"].contains(&file)
+}
+
+/// Human-readable size, e.g. `1.5 KiB`
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_analyze_payload() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(temp_dir.path().to_path_buf()).unwrap();
+        let contents = [
+            ("Scarb.toml", "[package]\nname = \"token\"\n".to_string()),
+            ("token/src/lib.cairo", "mod token;\n".repeat(100)),
+            (
+                "token/src/tests/test_token.cairo",
+                "fn test() {}\n".repeat(10),
+            ),
+            ("token/README.md", "# Token\n".repeat(10_000)),
+            ("tools/src/lib.cairo", "fn helper() {}\n".to_string()),
+        ];
+        let mut files = HashMap::new();
+        for (name, content) in &contents {
+            let path = root.join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, content).unwrap();
+            files.insert((*name).to_string(), path);
+        }
+
+        let analysis = PayloadAnalysis::of_files(&files).unwrap();
+        let total: u64 = contents.iter().map(|(_, c)| c.len() as u64).sum();
+        assert_eq!(analysis.total_size, total);
+        assert!(analysis.compressed_size < analysis.total_size / 10);
+        assert_eq!(analysis.largest(2)[0].name, "token/README.md");
+        assert_eq!(analysis.largest(2)[1].name, "token/src/lib.cairo");
+        assert_eq!(analysis.largest(10).len(), 5);
+
+        let directories = analysis.directories();
+        let paths: Vec<_> = directories.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "",
+                "token",
+                "token/src",
+                "token/src/tests",
+                "tools",
+                "tools/src"
+            ]
+        );
+        assert_eq!(directories[0].size, total);
+        assert_eq!(directories[1].files, 3);
+
+        let exclusions = analysis.exclusions(true, &[("tools".to_string(), "tools".to_string())]);
+        let files: Vec<_> = exclusions.iter().map(|e| e.files.clone()).collect();
+        assert_eq!(
+            files,
+            [
+                vec!["tools/src/lib.cairo".to_string()],
+                vec!["token/src/tests/test_token.cairo".to_string()],
+                vec!["token/README.md".to_string()],
+            ]
+        );
+        assert_eq!(analysis.exclusions(false, &[]).len(), 1);
+
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(10 * 1024 * 1024), "10.0 MiB");
+    }
+}
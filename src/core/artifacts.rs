@@ -0,0 +1,229 @@
+//! Locally built Starknet contract artifacts
+//!
+//! `scarb build` writes `<package>.starknet_artifacts.json` to the profile
+//! directory under `target/`, listing every contract in the package together
+//! with its Sierra class file. When these artifacts exist they are used to
+//! check `--contract-name` against the contracts that were actually compiled,
+//! so a misspelled or wrongly cased name fails before anything is uploaded.
+
+use crate::utils::errors::ContractNotInArtifacts;
+use camino::Utf8PathBuf;
+use log::debug;
+use serde::Deserialize;
+use std::fs;
+
+#[derive(Deserialize)]
+struct ArtifactsFile {
+    contracts: Vec<ArtifactEntry>,
+}
+
+#[derive(Deserialize)]
+struct ArtifactEntry {
+    contract_name: String,
+    module_path: String,
+    artifacts: ArtifactPaths,
+}
+
+#[derive(Deserialize)]
+struct ArtifactPaths {
+    sierra: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SierraClass {
+    #[serde(default)]
+    sierra_program: Vec<String>,
+    #[serde(default)]
+    abi: Vec<serde_json::Value>,
+    entry_points_by_type: EntryPointsByType,
+}
+
+#[derive(Deserialize)]
+struct EntryPointsByType {
+    #[serde(rename = "EXTERNAL", default)]
+    external: Vec<serde_json::Value>,
+    #[serde(rename = "L1_HANDLER", default)]
+    l1_handler: Vec<serde_json::Value>,
+    #[serde(rename = "CONSTRUCTOR", default)]
+    constructor: Vec<serde_json::Value>,
+}
+
+/// A contract listed in a package's build artifacts
+#[derive(Debug, Clone)]
+pub struct ContractArtifact {
+    pub contract_name: String,
+    pub module_path: String,
+    /// Path of the compiled Sierra class, if it was built
+    pub sierra_path: Option<Utf8PathBuf>,
+}
+
+/// Entry point and ABI sizes of a compiled contract class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassSummary {
+    pub external: usize,
+    pub l1_handler: usize,
+    pub constructor: usize,
+    pub abi_items: usize,
+    /// Sierra version the class was compiled to
+    pub sierra_version: Option<semver::Version>,
+}
+
+impl ClassSummary {
+    #[must_use]
+    pub const fn entry_points(&self) -> usize {
+        self.external + self.l1_handler + self.constructor
+    }
+}
+
+impl ContractArtifact {
+    /// Read entry point counts from the Sierra class file
+    ///
+    /// Returns `None` if the class was not built or cannot be parsed.
+    #[must_use]
+    pub fn class_summary(&self) -> Option<ClassSummary> {
+        let path = self.sierra_path.as_ref()?;
+        let content = fs::read_to_string(path)
+            .map_err(|e| debug!("Failed to read Sierra class {path}: {e}"))
+            .ok()?;
+        let class: SierraClass = serde_json::from_str(&content)
+            .map_err(|e| debug!("Failed to parse Sierra class {path}: {e}"))
+            .ok()?;
+
+        Some(ClassSummary {
+            external: class.entry_points_by_type.external.len(),
+            l1_handler: class.entry_points_by_type.l1_handler.len(),
+            constructor: class.entry_points_by_type.constructor.len(),
+            abi_items: class.abi.len(),
+            sierra_version: sierra_version(&class.sierra_program),
+        })
+    }
+}
+
+/// Sierra version a program was compiled to
+///
+/// The first three felts of a compiled Sierra program are the major, minor
+/// and patch version of Sierra.
+fn sierra_version(program: &[String]) -> Option<semver::Version> {
+    let felt = |index: usize| {
+        let felt = program.get(index)?;
+        u64::from_str_radix(felt.trim_start_matches("0x"), 16).ok()
+    };
+    Some(semver::Version::new(felt(0)?, felt(1)?, felt(2)?))
+}
+
+/// Contracts built for `package` in the current profile
+///
+/// Returns `None` if the package has not been built, in which case no
+/// validation is possible.
+#[must_use]
+pub fn package_artifacts(
+    metadata: &scarb_metadata::Metadata,
+    package: &str,
+) -> Option<Vec<ContractArtifact>> {
+    let profile_dir = metadata
+        .target_dir
+        .clone()
+        .unwrap_or_else(|| metadata.workspace.root.join("target"))
+        .join(&metadata.current_profile);
+    let path = profile_dir.join(format!("{package}.starknet_artifacts.json"));
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| debug!("No build artifacts at {path}: {e}"))
+        .ok()?;
+    let file: ArtifactsFile = serde_json::from_str(&content)
+        .map_err(|e| debug!("Failed to parse build artifacts {path}: {e}"))
+        .ok()?;
+
+    Some(
+        file.contracts
+            .into_iter()
+            .map(|entry| ContractArtifact {
+                contract_name: entry.contract_name,
+                module_path: entry.module_path,
+                sierra_path: entry
+                    .artifacts
+                    .sierra
+                    .map(|sierra| profile_dir.join(sierra)),
+            })
+            .collect(),
+    )
+}
+
+/// Find `contract_name` among the built contracts of a package
+///
+/// # Errors
+///
+/// Returns [`ContractNotInArtifacts`] if the artifacts do not contain a
+/// contract with exactly this name.
+pub fn find_contract(
+    artifacts: &[ContractArtifact],
+    package: &str,
+    contract_name: &str,
+) -> Result<ContractArtifact, ContractNotInArtifacts> {
+    artifacts
+        .iter()
+        .find(|artifact| artifact.contract_name == contract_name)
+        .cloned()
+        .ok_or_else(|| ContractNotInArtifacts {
+            name: contract_name.to_string(),
+            package: package.to_string(),
+            available: artifacts
+                .iter()
+                .map(|artifact| artifact.contract_name.clone())
+                .collect(),
+        })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn artifact(name: &str, sierra_path: Option<Utf8PathBuf>) -> ContractArtifact {
+        ContractArtifact {
+            contract_name: name.to_string(),
+            module_path: format!("my_package::{name}"),
+            sierra_path,
+        }
+    }
+
+    #[test]
+    fn test_find_contract_is_case_sensitive() {
+        let artifacts = [artifact("MyToken", None), artifact("Vault", None)];
+
+        assert!(find_contract(&artifacts, "my_package", "MyToken").is_ok());
+
+        let err = find_contract(&artifacts, "my_package", "mytoken").unwrap_err();
+        assert_eq!(err.available, ["MyToken", "Vault"]);
+        assert!(err.to_string().contains("Did you mean 'MyToken'?"));
+    }
+
+    #[test]
+    fn test_class_summary_counts_entry_points() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(temp_dir.path().join("class.json")).unwrap();
+        fs::write(
+            &path,
+            r#"{
+                "sierra_program": ["0x1", "0x7", "0x0", "0x2", "0xb", "0x4"],
+                "entry_points_by_type": {
+                    "EXTERNAL": [{"selector": "0x1", "function_idx": 0}, {"selector": "0x2", "function_idx": 1}],
+                    "L1_HANDLER": [],
+                    "CONSTRUCTOR": [{"selector": "0x3", "function_idx": 2}]
+                },
+                "abi": [{"type": "function"}, {"type": "constructor"}, {"type": "event"}]
+            }"#,
+        )
+        .unwrap();
+
+        let summary = artifact("MyToken", Some(path)).class_summary().unwrap();
+        assert_eq!(summary.external, 2);
+        assert_eq!(summary.constructor, 1);
+        assert_eq!(summary.entry_points(), 3);
+        assert_eq!(summary.abi_items, 3);
+        assert_eq!(summary.sierra_version, Some(semver::Version::new(1, 7, 0)));
+
+        assert!(artifact("MyToken", None).class_summary().is_none());
+    }
+}
@@ -25,10 +25,14 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+pub mod compute;
+
 use regex::Regex;
 use std::{fmt, sync::LazyLock};
 use thiserror::Error;
 
+use crate::utils::suggestion::{Suggestion, SuggestionList};
+
 static CLASS_HASH_REGEX: LazyLock<Result<Regex, regex::Error>> =
     LazyLock::new(|| Regex::new(r"^0x[a-fA-F0-9]+$"));
 
@@ -77,7 +81,7 @@ pub struct ClassHash(String);
 /// and includes actionable suggestions for fixing the issue.
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum ClassHashError {
-    #[error("[E010] Invalid class hash format: '{0}'\n\nExpected format: 0x followed by up to 64 hexadecimal characters\nExample: 0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18\n\nSuggestions:\n  • Check that the hash starts with '0x'\n  • Verify all characters are hexadecimal (0-9, a-f, A-F)\n  • Ensure the hash is not longer than 66 characters total")]
+    #[error("[E010] Invalid class hash format: '{0}'\n\nExpected format: 0x followed by up to 64 hexadecimal characters\nExample: 0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Match(String),
     #[error("[E011] Internal regex compilation error\n\nThis is an internal error. Please report this issue.")]
     RegexError,
@@ -91,6 +95,15 @@ impl ClassHashError {
             Self::RegexError => "E011",
         }
     }
+
+    /// Next steps for correcting the class hash; internal errors have none
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        match self {
+            Self::Match(_) => Suggestion::for_code(self.error_code(), &[]),
+            Self::RegexError => Vec::new(),
+        }
+    }
 }
 
 impl ClassHash {
@@ -139,6 +152,21 @@ impl ClassHash {
             Err(ClassHashError::Match(raw.to_string()))
         }
     }
+
+    /// Whether both hashes denote the same value, ignoring case and leading zeros
+    ///
+    /// ```rust
+    /// use verifier::core::class_hash::ClassHash;
+    ///
+    /// let padded = ClassHash::new("0x00AB12")?;
+    /// assert!(padded.same_value(&ClassHash::new("0xab12")?));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[must_use]
+    pub fn same_value(&self, other: &Self) -> bool {
+        let digits = |hash: &Self| hash.0[2..].trim_start_matches('0').to_ascii_lowercase();
+        digits(self) == digits(other)
+    }
 }
 
 impl fmt::Display for ClassHash {
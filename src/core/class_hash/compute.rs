@@ -0,0 +1,74 @@
+//! Class hashes of locally compiled classes
+//!
+//! The class hash is computed from the compiled Sierra class by
+//! `starkli class-hash`, so starkli has to be installed. `voyager reproduce`
+//! and `voyager verify --compare-class-hash` compare it with the class hash
+//! of the declared class: when they differ, the sources cannot compile to the
+//! declared class with the local toolchain.
+
+use super::ClassHash;
+use crate::core::reproduce::{build_package, sierra_class_path};
+use crate::utils::errors::CliError;
+use camino::Utf8Path;
+use scarb_metadata::Metadata;
+use std::process::Command;
+
+/// Compute the class hash of a Sierra class file with `starkli class-hash`
+///
+/// # Errors
+///
+/// Returns [`CliError::ClassHashComputationFailed`] if starkli is not
+/// installed, fails, or prints something that is not a class hash.
+pub fn sierra_class_hash(sierra_path: &Utf8Path) -> Result<ClassHash, CliError> {
+    let failed = |reason: String| CliError::ClassHashComputationFailed {
+        path: sierra_path.to_path_buf(),
+        reason,
+    };
+
+    let output = Command::new("starkli")
+        .arg("class-hash")
+        .arg(sierra_path)
+        .output()
+        .map_err(|e| failed(format!("could not run starkli: {e}")))?;
+
+    if !output.status.success() {
+        return Err(failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    ClassHash::new(stdout.trim())
+        .map_err(|_| failed(format!("unexpected output '{}'", stdout.trim())))
+}
+
+/// Build `package` with `scarb build` and compute the class hash of
+/// `contract_name`
+///
+/// # Errors
+///
+/// Returns an error if the build fails, the contract has no compiled Sierra
+/// class, or the class hash cannot be computed.
+pub fn local_class_hash(
+    metadata: &Metadata,
+    package: &str,
+    contract_name: &str,
+) -> Result<ClassHash, CliError> {
+    build_package(metadata, package)?;
+    let sierra_path = sierra_class_path(metadata, package, contract_name)?;
+    sierra_class_hash(&sierra_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sierra_class_hash_of_missing_file() {
+        let path = Utf8Path::new("/nonexistent/token_MyToken.contract_class.json");
+        assert!(matches!(
+            sierra_class_hash(path),
+            Err(CliError::ClassHashComputationFailed { path: failed, .. }) if failed == path
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+//! Toolchain versions the verification API can build with
+//!
+//! The remote compiler only runs the Cairo and Scarb releases installed on the
+//! server, so a project built with a newer (or much older) toolchain is
+//! accepted, queued, and fails minutes later with `[E004] Compilation failed`.
+//! The table embedded here mirrors the supported versions at release time;
+//! every range the capabilities endpoint reports replaces the embedded one, so
+//! a server that gained a release needs no client update.
+
+use crate::api::{Capabilities, VersionRange};
+use semver::Version;
+use std::fmt;
+
+/// Error code of the job once the API fails to build with an unsupported
+/// toolchain
+pub const FAILURE_CODE: &str = "E004";
+
+/// Supported Cairo, Scarb and Sierra versions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Compatibility {
+    pub cairo: VersionRange,
+    pub scarb: VersionRange,
+    pub sierra: VersionRange,
+}
+
+impl Default for Compatibility {
+    fn default() -> Self {
+        Self {
+            cairo: VersionRange::new(Version::new(1, 0, 0), Version::new(2, 13, 1)),
+            scarb: VersionRange::new(Version::new(0, 4, 0), Version::new(2, 13, 1)),
+            sierra: VersionRange::new(Version::new(1, 1, 0), Version::new(1, 7, 0)),
+        }
+    }
+}
+
+/// A toolchain component outside the supported range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    /// `Cairo`, `Scarb` or `Sierra`
+    pub component: &'static str,
+    pub version: Version,
+    pub supported: VersionRange,
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} is not supported by the API (supported: {})",
+            self.component, self.version, self.supported
+        )
+    }
+}
+
+impl Compatibility {
+    /// The embedded table, with the ranges reported by the API taking
+    /// precedence
+    #[must_use]
+    pub fn with_server(self, capabilities: &Capabilities) -> Self {
+        Self {
+            cairo: capabilities.cairo.clone().unwrap_or(self.cairo),
+            scarb: capabilities.scarb.clone().unwrap_or(self.scarb),
+            sierra: capabilities.sierra.clone().unwrap_or(self.sierra),
+        }
+    }
+
+    /// Components of a toolchain the API cannot build with
+    ///
+    /// The Sierra version is only known once the contract has been built
+    /// locally, so it is skipped when `None`.
+    #[must_use]
+    pub fn check(
+        &self,
+        cairo: &Version,
+        scarb: &Version,
+        sierra: Option<&Version>,
+    ) -> Vec<Incompatibility> {
+        [
+            ("Cairo", Some(cairo), &self.cairo),
+            ("Scarb", Some(scarb), &self.scarb),
+            ("Sierra", sierra, &self.sierra),
+        ]
+        .into_iter()
+        .filter_map(|(component, version, supported)| {
+            let version = version?;
+            (!supported.contains(version)).then(|| Incompatibility {
+                component,
+                version: version.clone(),
+                supported: supported.clone(),
+            })
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_against_embedded_and_server_ranges() {
+        let compat = Compatibility::default();
+        let supported = Version::new(2, 11, 4);
+        assert!(compat
+            .check(&supported, &supported, Some(&Version::new(1, 7, 0)))
+            .is_empty());
+
+        let newer = Version::parse("2.14.0-rc.1").unwrap();
+        let found = compat.check(&newer, &newer, Some(&Version::new(1, 8, 0)));
+        assert_eq!(
+            found.iter().map(|i| i.component).collect::<Vec<_>>(),
+            ["Cairo", "Scarb", "Sierra"]
+        );
+        assert_eq!(
+            found[0].to_string(),
+            "Cairo 2.14.0-rc.1 is not supported by the API (supported: 1.0.0 to 2.13.1)"
+        );
+
+        let server: Capabilities = serde_json::from_str(
+            r#"{"cairo": {"min": "2.0.0", "max": "2.14.0"}, "max_payload_size": 1024}"#,
+        )
+        .unwrap();
+        let compat = compat.with_server(&server);
+        let found = compat.check(&newer, &newer, None);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].component, "Scarb");
+        assert_eq!(found[0].supported.max, Version::new(2, 13, 1));
+    }
+}
@@ -0,0 +1,507 @@
+//! Batches built from a deployments file
+//!
+//! `voyager verify --all-deployed --address-file <FILE>` reads the contracts
+//! a deployment script recorded, matches each to the workspace member whose
+//! sources define it, and verifies them as a batch, as if they were listed as
+//! `[[contracts]]` in .voyager.toml.
+//!
+//! The file is a JSON list of contracts, or an object with a `contracts`
+//! list, such as a Dojo `manifest_<profile>.json`:
+//!
+//! ```json
+//! {
+//!   "contracts": [
+//!     { "name": "MyToken", "class_hash": "0x044d…", "address": "0x0123…" },
+//!     { "tag": "game-actions", "class_hash": "0x07a1…", "address": "0x0456…" }
+//!   ]
+//! }
+//! ```
+//!
+//! A contract is named by `name` (or `contract_name`), or by a Dojo `tag`
+//! whose part after the namespace is the module name. Only the class hash is
+//! submitted; the address is shown to tell deployments apart.
+//!
+//! `voyager verify --from-dojo-manifest` reads the manifest `sozo migrate`
+//! writes instead: the systems, models and events of its `contracts`,
+//! `models` and `events` lists are verified as one batch of the Dojo package.
+
+use super::class_hash::ClassHash;
+use crate::cli::config::ContractConfig;
+use crate::filesystem::collector::package_contracts;
+use crate::utils::errors::CliError;
+use camino::{Utf8Path, Utf8PathBuf};
+use log::debug;
+use scarb_metadata::PackageMetadata;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// A contract recorded in a deployments file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployment {
+    /// Module name of the contract
+    pub name: String,
+    pub class_hash: ClassHash,
+    pub address: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Entry {
+    #[serde(default, alias = "contract_name")]
+    name: Option<String>,
+    #[serde(default)]
+    tag: Option<String>,
+    #[serde(default, alias = "classHash")]
+    class_hash: Option<String>,
+    #[serde(default, alias = "contract_address")]
+    address: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Manifest {
+    List(Vec<Entry>),
+    Contracts { contracts: Vec<Entry> },
+}
+
+/// Read the deployments file at `path`
+///
+/// # Errors
+///
+/// Returns [`CliError::InvalidDeployments`] if the file cannot be read, is
+/// not a deployments file, or a contract has no name or a malformed class
+/// hash.
+pub fn read(path: &Utf8Path) -> Result<Vec<Deployment>, CliError> {
+    let content = std::fs::read_to_string(path).map_err(|e| CliError::InvalidDeployments {
+        path: path.to_path_buf(),
+        reason: format!("cannot be read: {e}"),
+    })?;
+    parse(&content).map_err(|reason| CliError::InvalidDeployments {
+        path: path.to_path_buf(),
+        reason,
+    })
+}
+
+/// Parse the contents of a deployments file
+///
+/// # Errors
+///
+/// Returns the reason the contents are not a deployments file.
+pub fn parse(content: &str) -> Result<Vec<Deployment>, String> {
+    let manifest: Manifest = serde_json::from_str(content).map_err(|_| {
+        "expected a list of contracts or an object with a 'contracts' list".to_string()
+    })?;
+    let (Manifest::List(entries) | Manifest::Contracts { contracts: entries }) = manifest;
+    if entries.is_empty() {
+        return Err("it lists no contracts".to_string());
+    }
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let name = entry
+                .name
+                .clone()
+                .or_else(|| entry.tag_name())
+                .ok_or_else(|| format!("contract {} has no name or tag", index + 1))?;
+            entry.into_deployment(name)
+        })
+        .collect()
+}
+
+impl Entry {
+    /// Module name in a Dojo tag, which reads `<namespace>-<module>`
+    fn tag_name(&self) -> Option<String> {
+        self.tag
+            .as_deref()
+            .map(|tag| tag.split_once('-').map_or(tag, |(_, name)| name))
+            .map(str::to_string)
+    }
+
+    fn into_deployment(self, name: String) -> Result<Deployment, String> {
+        let class_hash = self
+            .class_hash
+            .ok_or_else(|| format!("{name} has no class_hash"))?;
+        let class_hash = ClassHash::new(&class_hash)
+            .map_err(|_| format!("{name} has an invalid class_hash '{class_hash}'"))?;
+        Ok(Deployment {
+            name,
+            class_hash,
+            address: self.address,
+        })
+    }
+}
+
+/// What a class of a Dojo manifest implements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DojoResource {
+    World,
+    /// A system, deployed as a contract of the world
+    Contract,
+    Model,
+    Event,
+}
+
+impl std::fmt::Display for DojoResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::World => "world",
+            Self::Contract => "contract",
+            Self::Model => "model",
+            Self::Event => "event",
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct DojoManifest {
+    #[serde(default)]
+    world: Option<Entry>,
+    #[serde(default)]
+    contracts: Vec<Entry>,
+    #[serde(default)]
+    models: Vec<Entry>,
+    #[serde(default)]
+    events: Vec<Entry>,
+}
+
+/// Read the Dojo manifest at `path`
+///
+/// # Errors
+///
+/// Returns [`CliError::InvalidDeployments`] if the file cannot be read, is
+/// not a Dojo manifest, or a class has no name or a malformed class hash.
+pub fn read_dojo_manifest(path: &Utf8Path) -> Result<Vec<(DojoResource, Deployment)>, CliError> {
+    let content = std::fs::read_to_string(path).map_err(|e| CliError::InvalidDeployments {
+        path: path.to_path_buf(),
+        reason: format!("cannot be read: {e}"),
+    })?;
+    parse_dojo_manifest(&content).map_err(|reason| CliError::InvalidDeployments {
+        path: path.to_path_buf(),
+        reason,
+    })
+}
+
+/// Parse the contents of a manifest written by `sozo migrate`
+///
+/// Classes are named by the module part of their tag, or by the last segment
+/// of their name in manifests without tags.
+///
+/// # Errors
+///
+/// Returns the reason the contents are not a Dojo manifest.
+pub fn parse_dojo_manifest(content: &str) -> Result<Vec<(DojoResource, Deployment)>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("not a Dojo manifest: {e}"))?;
+    // A list would be taken for the fields of a manifest in order
+    if !value.is_object() {
+        return Err("not a Dojo manifest: expected an object with a world".to_string());
+    }
+    let manifest: DojoManifest =
+        serde_json::from_value(value).map_err(|e| format!("not a Dojo manifest: {e}"))?;
+    if manifest.world.is_none() {
+        return Err("not a Dojo manifest: it has no world".to_string());
+    }
+
+    let world = manifest.world.map(|entry| (DojoResource::World, entry));
+    let resources = [
+        (DojoResource::Contract, manifest.contracts),
+        (DojoResource::Model, manifest.models),
+        (DojoResource::Event, manifest.events),
+    ];
+    world
+        .into_iter()
+        .chain(
+            resources
+                .into_iter()
+                .flat_map(|(resource, entries)| entries.into_iter().map(move |e| (resource, e))),
+        )
+        .map(|(resource, entry)| {
+            let name = entry
+                .tag_name()
+                .or_else(|| {
+                    entry
+                        .name
+                        .as_deref()
+                        .map(|name| name.rsplit("::").next().unwrap_or(name).to_string())
+                })
+                .or_else(|| (resource == DojoResource::World).then(|| "world".to_string()))
+                .ok_or_else(|| format!("a {resource} has no tag or name"))?;
+            Ok((resource, entry.into_deployment(name)?))
+        })
+        .collect()
+}
+
+/// Dojo manifests written by `sozo migrate` in the project at `root`
+///
+/// Looks for the `manifest_<profile>.json` files of Dojo 1 and the
+/// `manifests/<profile>/deployment/manifest.json` files of earlier releases.
+#[must_use]
+pub fn find_dojo_manifests(root: &Utf8Path) -> Vec<Utf8PathBuf> {
+    let list = |dir: &Utf8Path| -> Vec<Utf8PathBuf> {
+        dir.read_dir_utf8()
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(camino::Utf8DirEntry::into_path)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut manifests: Vec<Utf8PathBuf> = list(root)
+        .into_iter()
+        .filter(|path| {
+            path.extension() == Some("json")
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.starts_with("manifest_"))
+        })
+        .chain(
+            list(&root.join("manifests"))
+                .into_iter()
+                .map(|profile| profile.join("deployment").join("manifest.json")),
+        )
+        .filter(|path| path.is_file())
+        .collect();
+    manifests.sort();
+    manifests
+}
+
+/// Deployments matched to the workspace
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct WorkspaceDeployments {
+    /// Batch entries, one per class, in the order of the file
+    pub contracts: Vec<ContractConfig>,
+    /// Deployments left out, with the reason
+    pub skipped: Vec<(Deployment, String)>,
+}
+
+/// Packages defining each contract, by contract name, among `members`
+#[must_use]
+pub fn contract_packages(members: &[&PackageMetadata]) -> BTreeMap<String, Vec<String>> {
+    let mut packages: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for member in members {
+        match package_contracts(member) {
+            Ok(contracts) => {
+                for contract in contracts {
+                    let names = packages.entry(contract.name).or_default();
+                    if !names.contains(&member.name) {
+                        names.push(member.name.clone());
+                    }
+                }
+            }
+            Err(e) => debug!("Failed to list contracts of {}: {e}", member.name),
+        }
+    }
+    packages
+}
+
+/// Batch entries for the `deployments` the workspace defines
+///
+/// `packages` gives the packages defining each contract, as returned by
+/// [`contract_packages`]. A class deployed several times is verified once.
+#[must_use]
+pub fn match_workspace(
+    deployments: Vec<Deployment>,
+    packages: &BTreeMap<String, Vec<String>>,
+) -> WorkspaceDeployments {
+    let mut matched = WorkspaceDeployments::default();
+    for deployment in deployments {
+        let package = match packages.get(&deployment.name).map(Vec::as_slice) {
+            Some([package]) => package.clone(),
+            Some(several) if several.len() > 1 => {
+                let reason = format!("defined by several packages ({})", several.join(", "));
+                matched.skipped.push((deployment, reason));
+                continue;
+            }
+            _ => {
+                let reason = "not defined in the workspace".to_string();
+                matched.skipped.push((deployment, reason));
+                continue;
+            }
+        };
+        matched.push(deployment, Some(package));
+    }
+    matched
+}
+
+/// Batch entries for the classes of a Dojo manifest
+///
+/// Systems, models and events are verified as part of the Dojo package; the
+/// package is resolved as for a contract without `package`. The world class
+/// is built from the `dojo` dependency rather than the project, so it is left
+/// out.
+#[must_use]
+pub fn dojo_batch(resources: Vec<(DojoResource, Deployment)>) -> WorkspaceDeployments {
+    let mut matched = WorkspaceDeployments::default();
+    for (resource, deployment) in resources {
+        if resource == DojoResource::World {
+            let reason = "the world is built from the dojo dependency, not the project".to_string();
+            matched.skipped.push((deployment, reason));
+        } else {
+            matched.push(deployment, None);
+        }
+    }
+    matched
+}
+
+impl WorkspaceDeployments {
+    /// Add `deployment` to the batch, unless its class is in it already
+    fn push(&mut self, deployment: Deployment, package: Option<String>) {
+        let known = self.contracts.iter().any(|contract| {
+            ClassHash::new(&contract.class_hash)
+                .is_ok_and(|hash| hash.same_value(&deployment.class_hash))
+        });
+        if known {
+            let reason = "its class is already verified with another deployment".to_string();
+            self.skipped.push((deployment, reason));
+            return;
+        }
+        self.contracts.push(ContractConfig {
+            class_hash: deployment.class_hash.to_string(),
+            contract_name: deployment.name,
+            package,
+            contract_file: None,
+            depends_on: Vec::new(),
+        });
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18";
+    const ACTIONS: &str = "0x07a1";
+
+    #[test]
+    fn test_parse_and_match_deployments() {
+        let list = format!(
+            r#"[{{"contract_name": "MyToken", "class_hash": "{TOKEN}", "address": "0x1"}}]"#
+        );
+        assert_eq!(parse(&list).unwrap()[0].name, "MyToken");
+
+        let dojo = format!(
+            r#"{{
+                "world": {{ "class_hash": "0x0abc", "address": "0x2" }},
+                "contracts": [
+                    {{ "tag": "game-actions", "class_hash": "{ACTIONS}", "address": "0x3" }},
+                    {{ "name": "MyToken", "class_hash": "{TOKEN}", "address": "0x4" }},
+                    {{ "name": "MyToken", "class_hash": "0x44DC2B3239382230D8B1E943DF23B96F52EEBCAC93EFE6E8BDE92F9A2F1DA18", "address": "0x5" }},
+                    {{ "name": "ERC20", "class_hash": "0x0def", "address": "0x6" }}
+                ]
+            }}"#
+        );
+        let deployments = parse(&dojo).unwrap();
+        assert_eq!(deployments[0].name, "actions");
+        assert_eq!(deployments[0].address.as_deref(), Some("0x3"));
+
+        let packages = BTreeMap::from([
+            ("actions".to_string(), vec!["game".to_string()]),
+            ("MyToken".to_string(), vec!["token".to_string()]),
+        ]);
+        let matched = match_workspace(deployments, &packages);
+        let names: Vec<_> = matched
+            .contracts
+            .iter()
+            .map(|contract| (contract.contract_name.as_str(), contract.package.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            [("actions", Some("game")), ("MyToken", Some("token"))]
+        );
+        let skipped: Vec<_> = matched
+            .skipped
+            .iter()
+            .map(|(deployment, reason)| (deployment.address.as_deref(), reason.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            [
+                (
+                    Some("0x5"),
+                    "its class is already verified with another deployment"
+                ),
+                (Some("0x6"), "not defined in the workspace"),
+            ]
+        );
+
+        assert!(parse("[]").unwrap_err().contains("no contracts"));
+        assert!(parse(r#"{"models": []}"#).is_err());
+        assert!(parse(r#"[{"class_hash": "0x1"}]"#)
+            .unwrap_err()
+            .contains("no name"));
+        assert!(
+            parse(r#"{"contracts": [{"name": "A", "class_hash": "xyz"}]}"#)
+                .unwrap_err()
+                .contains("invalid class_hash")
+        );
+    }
+
+    #[test]
+    fn test_dojo_manifest_batch() {
+        let manifest = format!(
+            r#"{{
+                "world": {{ "class_hash": "0x0abc", "address": "0x2", "seed": "game" }},
+                "contracts": [
+                    {{ "tag": "game-actions", "class_hash": "{ACTIONS}", "address": "0x3" }}
+                ],
+                "models": [
+                    {{ "tag": "game-Position", "class_hash": "0x0b01" }},
+                    {{ "name": "game::models::moves", "class_hash": "0x0b02" }}
+                ],
+                "events": [{{ "tag": "game-Moved", "class_hash": "0x0b03" }}]
+            }}"#
+        );
+        let resources = parse_dojo_manifest(&manifest).unwrap();
+        let names: Vec<_> = resources
+            .iter()
+            .map(|(resource, deployment)| (*resource, deployment.name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (DojoResource::World, "world"),
+                (DojoResource::Contract, "actions"),
+                (DojoResource::Model, "Position"),
+                (DojoResource::Model, "moves"),
+                (DojoResource::Event, "Moved"),
+            ]
+        );
+
+        let batch = dojo_batch(resources);
+        assert_eq!(batch.contracts.len(), 4);
+        assert!(batch
+            .contracts
+            .iter()
+            .all(|contract| contract.package.is_none()));
+        assert_eq!(batch.skipped.len(), 1);
+        assert_eq!(batch.skipped[0].0.name, "world");
+
+        // A generic deployments file is no Dojo manifest
+        assert!(parse_dojo_manifest(r#"[{"name": "A", "class_hash": "0x1"}]"#).is_err());
+        assert!(parse_dojo_manifest(r#"{"contracts": []}"#)
+            .unwrap_err()
+            .contains("no world"));
+    }
+
+    #[test]
+    fn test_find_dojo_manifests() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        std::fs::write(root.join("manifest_sepolia.json"), "{}").unwrap();
+        std::fs::write(root.join("Scarb.toml"), "").unwrap();
+        std::fs::create_dir_all(root.join("manifests/dev/deployment")).unwrap();
+        std::fs::write(root.join("manifests/dev/deployment/manifest.json"), "{}").unwrap();
+        std::fs::create_dir_all(root.join("manifests/release")).unwrap();
+
+        assert_eq!(
+            find_dojo_manifests(&root),
+            [
+                root.join("manifest_sepolia.json"),
+                root.join("manifests/dev/deployment/manifest.json"),
+            ]
+        );
+    }
+}
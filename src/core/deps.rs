@@ -0,0 +1,281 @@
+//! Resolved dependency graph of a package
+//!
+//! Built from `scarb metadata`, which lists every package Scarb resolved for
+//! the workspace. Path dependencies are uploaded with the sources, while git
+//! and registry dependencies have to be fetched by the remote compiler, so the
+//! graph shows up front what a verification depends on besides the upload.
+
+use scarb_metadata::PackageMetadata;
+use serde::Serialize;
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write as _},
+};
+
+/// Where a package in the graph comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceKind {
+    /// Local directory; uploaded with the sources
+    Path,
+    /// Git repository; fetched by the remote compiler
+    Git,
+    /// Package registry; fetched by the remote compiler
+    Registry,
+    /// Shipped with the compiler (`core`, `starknet`)
+    Builtin,
+}
+
+impl SourceKind {
+    /// Classify a Scarb source id such as `path+file:///...` or `git+https://...`
+    #[must_use]
+    pub fn from_repr(repr: &str) -> Self {
+        match repr.split_once('+').map(|(scheme, _)| scheme) {
+            Some("path") => Self::Path,
+            Some("git") => Self::Git,
+            Some("registry") => Self::Registry,
+            _ => Self::Builtin,
+        }
+    }
+
+    /// Whether the remote compiler has to download the package
+    #[must_use]
+    pub const fn is_remote(self) -> bool {
+        matches!(self, Self::Git | Self::Registry)
+    }
+
+    /// What verification does with packages from this source
+    #[must_use]
+    pub const fn note(self) -> &'static str {
+        match self {
+            Self::Path => "uploaded with the sources",
+            Self::Git => "fetched from git by the remote compiler",
+            Self::Registry => "fetched from the registry by the remote compiler",
+            Self::Builtin => "bundled with the compiler",
+        }
+    }
+}
+
+impl fmt::Display for SourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Path => "path",
+            Self::Git => "git",
+            Self::Registry => "registry",
+            Self::Builtin => "builtin",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A package reachable from the root package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyNode {
+    pub name: String,
+    pub version: String,
+    pub kind: SourceKind,
+    /// Scarb source id, e.g. `registry+https://scarbs.xyz/`
+    pub source: String,
+}
+
+/// A dependency of one package on another
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyEdge {
+    pub from: String,
+    pub to: String,
+    pub version_req: String,
+    /// Only needed to build tests
+    pub dev: bool,
+}
+
+/// Packages reachable from `root`, in breadth-first order
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyGraph {
+    pub root: String,
+    pub packages: Vec<DependencyNode>,
+    pub dependencies: Vec<DependencyEdge>,
+}
+
+impl DependencyGraph {
+    /// Build the graph of `root` from the packages resolved by Scarb
+    ///
+    /// Returns `None` if no package is named `root`. Dependencies Scarb did
+    /// not resolve (usually optional dev dependencies) are skipped.
+    #[must_use]
+    pub fn build(packages: &[PackageMetadata], root: &str) -> Option<Self> {
+        let root_package = packages.iter().find(|package| package.name == root)?;
+
+        let mut graph = Self {
+            root: root.to_string(),
+            packages: vec![],
+            dependencies: vec![],
+        };
+        let mut visited = vec![&root_package.id];
+        let mut queue = VecDeque::from([root_package]);
+
+        while let Some(package) = queue.pop_front() {
+            graph.packages.push(DependencyNode {
+                name: package.name.clone(),
+                version: package.version.to_string(),
+                kind: SourceKind::from_repr(&package.source.repr),
+                source: package.source.repr.clone(),
+            });
+
+            for dependency in &package.dependencies {
+                // Prefer the exact source, as the same name can resolve twice
+                let Some(resolved) = packages
+                    .iter()
+                    .find(|p| p.name == dependency.name && p.source == dependency.source)
+                    .or_else(|| packages.iter().find(|p| p.name == dependency.name))
+                else {
+                    continue;
+                };
+
+                graph.dependencies.push(DependencyEdge {
+                    from: package.name.clone(),
+                    to: resolved.name.clone(),
+                    version_req: dependency.version_req.to_string(),
+                    dev: dependency.kind.is_some(),
+                });
+                if !visited.contains(&&resolved.id) {
+                    visited.push(&resolved.id);
+                    queue.push_back(resolved);
+                }
+            }
+        }
+
+        Some(graph)
+    }
+
+    /// Number of packages from each source kind
+    #[must_use]
+    pub fn count(&self, kind: SourceKind) -> usize {
+        self.packages
+            .iter()
+            .filter(|node| node.kind == kind)
+            .count()
+    }
+
+    /// Render the graph in Graphviz DOT format
+    ///
+    /// Packages the remote compiler has to fetch are highlighted and dev
+    /// dependencies are drawn dashed.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dependencies {\n    node [shape=box];\n");
+
+        for node in &self.packages {
+            let style = match node.kind {
+                SourceKind::Path => "",
+                SourceKind::Git => ", style=filled, fillcolor=\"#fde2b8\"",
+                SourceKind::Registry => ", style=filled, fillcolor=\"#cfe2f3\"",
+                SourceKind::Builtin => ", color=gray, fontcolor=gray",
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{} {}\\n({})\"{style}];",
+                node.name, node.name, node.version, node.kind
+            );
+        }
+
+        for edge in &self.dependencies {
+            let style = if edge.dev { " [style=dashed]" } else { "" };
+            let _ = writeln!(dot, "    \"{}\" -> \"{}\"{style};", edge.from, edge.to);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn package(name: &str, source: &str, dependencies: &[(&str, &str, bool)]) -> PackageMetadata {
+        let dependencies: Vec<_> = dependencies
+            .iter()
+            .map(|(dep, dep_source, dev)| {
+                json!({
+                    "name": dep,
+                    "version_req": "*",
+                    "source": dep_source,
+                    "kind": if *dev { json!("dev") } else { json!(null) },
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "id": format!("{name} 1.0.0 ({source})"),
+            "name": name,
+            "version": "1.0.0",
+            "source": source,
+            "manifest_path": format!("/workspace/{name}/Scarb.toml"),
+            "root": format!("/workspace/{name}"),
+            "dependencies": dependencies,
+            "targets": [],
+        }))
+        .unwrap()
+    }
+
+    const PATH: &str = "path+file:///workspace/utils/Scarb.toml";
+    const GIT: &str = "git+https://github.com/OpenZeppelin/cairo-contracts.git?tag=v1.0.0";
+    const REGISTRY: &str = "registry+https://scarbs.xyz/";
+
+    fn packages() -> Vec<PackageMetadata> {
+        vec![
+            package(
+                "token",
+                "path+file:///workspace/token/Scarb.toml",
+                &[
+                    ("utils", PATH, false),
+                    ("openzeppelin", GIT, false),
+                    ("core", "std", false),
+                    ("snforge_std", REGISTRY, true),
+                ],
+            ),
+            package("utils", PATH, &[("core", "std", false)]),
+            package("openzeppelin", GIT, &[]),
+            package("snforge_std", REGISTRY, &[]),
+            package("core", "std", &[]),
+            package("unrelated", "path+file:///workspace/other/Scarb.toml", &[]),
+        ]
+    }
+
+    #[test]
+    fn test_source_kind_from_repr() {
+        assert_eq!(SourceKind::from_repr(PATH), SourceKind::Path);
+        assert_eq!(SourceKind::from_repr(GIT), SourceKind::Git);
+        assert_eq!(SourceKind::from_repr(REGISTRY), SourceKind::Registry);
+        assert_eq!(SourceKind::from_repr("std"), SourceKind::Builtin);
+    }
+
+    #[test]
+    fn test_build_graph() {
+        let graph = DependencyGraph::build(&packages(), "token").unwrap();
+
+        let names: Vec<&str> = graph.packages.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["token", "utils", "openzeppelin", "core", "snforge_std"]
+        );
+        assert_eq!(graph.count(SourceKind::Path), 2);
+        assert_eq!(graph.count(SourceKind::Git), 1);
+        // `core` is shared but listed once, with an edge from each dependent
+        assert_eq!(
+            graph.dependencies.iter().filter(|e| e.to == "core").count(),
+            2
+        );
+        assert!(graph
+            .dependencies
+            .iter()
+            .any(|e| e.to == "snforge_std" && e.dev));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"token\" -> \"openzeppelin\";"));
+        assert!(dot.contains("\"token\" -> \"snforge_std\" [style=dashed];"));
+
+        assert!(DependencyGraph::build(&packages(), "missing").is_none());
+    }
+}
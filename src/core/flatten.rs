@@ -0,0 +1,330 @@
+//! Single-file sources of a contract
+//!
+//! `voyager flatten --contract-name <NAME>` follows the `mod <name>;`
+//! declarations of a package from its crate root and replaces each with an
+//! inline `mod <name> { ... }` block holding the file it names, so the module
+//! tree reads as one file. Each inlined file is marked with its path.
+//! Dependencies are not inlined; they are listed in the header of the file,
+//! with their version requirement and source, to be resolved by the reader.
+//!
+//! A declared module whose file does not exist is left as a declaration and
+//! noted in a comment.
+
+use super::deps::SourceKind;
+use crate::filesystem::{collector::ContractDefinition, resolver};
+use camino::{Utf8Path, Utf8PathBuf};
+use scarb_metadata::PackageMetadata;
+use std::fmt::Write;
+
+/// Indentation added per inlined module
+const INDENT: &str = "    ";
+
+/// The module tree of a package as one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Flattened {
+    pub source: String,
+    /// Files inlined, relative to the package root, in the order they appear
+    pub files: Vec<Utf8PathBuf>,
+    /// Files of declared modules that do not exist, relative to the package root
+    pub missing: Vec<Utf8PathBuf>,
+}
+
+/// Flatten the module tree of `package`, which defines `contract`
+///
+/// # Errors
+///
+/// Returns [`resolver::Error::SourceUnreadable`] if a source file cannot be
+/// read.
+pub fn flatten(
+    package: &PackageMetadata,
+    contract: &ContractDefinition,
+) -> Result<Flattened, resolver::Error> {
+    let crate_root = package.targets.first().map_or_else(
+        || package.root.join("src/lib.cairo"),
+        |target| target.source_path.clone(),
+    );
+
+    let mut flattener = Flattener::new(&package.name, &package.root);
+    let body = flattener.crate_root(&crate_root)?;
+
+    let dependencies: Vec<(String, String)> = package
+        .dependencies
+        .iter()
+        .filter(|dependency| dependency.kind.is_none())
+        .filter_map(|dependency| {
+            let kind = SourceKind::from_repr(&dependency.source.repr);
+            (kind != SourceKind::Builtin).then(|| {
+                (
+                    dependency.name.clone(),
+                    format!("{} ({kind})", dependency.version_req),
+                )
+            })
+        })
+        .collect();
+    let file = contract
+        .file
+        .strip_prefix(&package.root)
+        .unwrap_or(&contract.file);
+    let header = header(
+        &format!("{} {}", package.name, package.version),
+        &contract.name,
+        file,
+        &dependencies,
+    );
+
+    Ok(Flattened {
+        source: header + &body,
+        files: flattener.files,
+        missing: flattener.missing,
+    })
+}
+
+/// Comment opening a flattened file
+fn header(
+    package: &str,
+    contract: &str,
+    file: &Utf8Path,
+    dependencies: &[(String, String)],
+) -> String {
+    let mut header = format!(
+        "// {contract} from {package}, flattened by `voyager flatten`\n\
+         // The contract is defined in {file}.\n"
+    );
+    // Writing to a String cannot fail
+    if dependencies.is_empty() {
+        header.push_str("// Dependencies: none\n");
+    } else {
+        header.push_str("// Dependencies, not inlined:\n");
+        for (name, requirement) in dependencies {
+            let _ = writeln!(header, "//   {name} {requirement}");
+        }
+    }
+    header.push('\n');
+    header
+}
+
+struct Flattener<'a> {
+    package: &'a str,
+    root: &'a Utf8Path,
+    files: Vec<Utf8PathBuf>,
+    missing: Vec<Utf8PathBuf>,
+}
+
+impl<'a> Flattener<'a> {
+    const fn new(package: &'a str, root: &'a Utf8Path) -> Self {
+        Self {
+            package,
+            root,
+            files: Vec::new(),
+            missing: Vec::new(),
+        }
+    }
+
+    /// The crate root at `path` with its modules inlined
+    ///
+    /// Modules of the crate root are next to it: `mod token;` in
+    /// `src/lib.cairo` is `src/token.cairo`.
+    fn crate_root(&mut self, path: &Utf8Path) -> Result<String, resolver::Error> {
+        let dir = path.parent().unwrap_or(self.root).to_path_buf();
+        self.file(path, &dir)
+    }
+
+    /// The file at `path` with its modules inlined, looking for the files of
+    /// its modules in `dir`
+    fn file(&mut self, path: &Utf8Path, dir: &Utf8Path) -> Result<String, resolver::Error> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| resolver::Error::SourceUnreadable {
+                package: self.package.to_string(),
+                path: path.to_string(),
+                operation: "read",
+                error: e.to_string(),
+            })?;
+        self.files.push(self.relative(path));
+
+        let mut output = String::with_capacity(content.len());
+        // Inline modules enclosing the current line, with the brace depth
+        // they were opened at
+        let mut inline: Vec<(&str, usize)> = Vec::new();
+        let mut depth = 0;
+        for line in content.lines() {
+            let declaration = declared_module(line);
+            if let Some((name, '{', _)) = declaration {
+                inline.push((name, depth));
+            }
+            let code = line.split("//").next().unwrap_or_default();
+            depth = (depth + code.matches('{').count()).saturating_sub(code.matches('}').count());
+            while inline.last().is_some_and(|(_, opened)| *opened >= depth) {
+                inline.pop();
+            }
+
+            let Some((name, ';', end)) = declaration else {
+                output.push_str(line);
+                output.push('\n');
+                continue;
+            };
+            let module_dir = inline
+                .iter()
+                .fold(dir.to_path_buf(), |dir, (module, _)| dir.join(module));
+            let module_path = module_dir.join(format!("{name}.cairo"));
+            if !module_path.is_file() {
+                let relative = self.relative(&module_path);
+                let _ = writeln!(output, "{line} // not flattened: {relative} does not exist");
+                self.missing.push(relative);
+                continue;
+            }
+
+            let module = self.file(&module_path, &module_dir.join(name))?;
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let _ = writeln!(output, "{} {{", line[..end].trim_end());
+            let _ = writeln!(
+                output,
+                "{indent}{INDENT}// ---- {} ----",
+                self.relative(&module_path)
+            );
+            for module_line in module.lines() {
+                if module_line.is_empty() {
+                    output.push('\n');
+                } else {
+                    let _ = writeln!(output, "{indent}{INDENT}{module_line}");
+                }
+            }
+            let _ = writeln!(output, "{indent}}}{}", &line[end + 1..]);
+        }
+        Ok(output)
+    }
+
+    fn relative(&self, path: &Utf8Path) -> Utf8PathBuf {
+        path.strip_prefix(self.root).unwrap_or(path).to_path_buf()
+    }
+}
+
+/// Module declared by `line`, as `[pub[(...)]] mod <name>` followed by `;` or
+/// `{`
+///
+/// Returns the name, the character following it and the position of that
+/// character in `line`.
+fn declared_module(line: &str) -> Option<(&str, char, usize)> {
+    let trimmed = line.trim_start();
+    let mut rest = trimmed;
+    if let Some(after) = rest.strip_prefix("pub") {
+        rest = match after.strip_prefix('(') {
+            Some(scope) => &scope[scope.find(')')? + 1..],
+            None => after,
+        };
+        if rest.len() == after.len() && !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+        rest = rest.trim_start();
+    }
+    let rest = rest.strip_prefix("mod")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let rest = rest.trim_start();
+    let name_len = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(rest.len());
+    let name = &rest[..name_len];
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let after_name = &rest[name_len..];
+    let terminator = after_name.trim_start().chars().next()?;
+    if terminator != ';' && terminator != '{' {
+        return None;
+    }
+    let position = line.len() - after_name.trim_start().len();
+    Some((name, terminator, position))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_module() {
+        assert_eq!(declared_module("mod token;"), Some(("token", ';', 9)));
+        assert_eq!(
+            declared_module("    pub(crate) mod utils ; // helpers"),
+            Some(("utils", ';', 25))
+        );
+        assert_eq!(
+            declared_module("pub mod MyToken {"),
+            Some(("MyToken", '{', 16))
+        );
+        assert_eq!(declared_module("use token::module;"), None);
+        assert_eq!(declared_module("public mod token;"), None);
+        assert_eq!(declared_module("// mod token;"), None);
+    }
+
+    #[test]
+    fn test_flatten_module_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        for (name, content) in [
+            (
+                "src/lib.cairo",
+                "pub mod token;\nmod utils {\n    pub mod math;\n}\nmod tests;\n",
+            ),
+            (
+                "src/token.cairo",
+                "#[starknet::contract]\npub mod MyToken {\n    #[storage]\n    struct Storage {}\n}\n",
+            ),
+            ("src/utils/math.cairo", "pub fn double(x: u8) -> u8 {\n    x * 2\n}\n"),
+        ] {
+            let path = root.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let mut flattener = Flattener::new("token", &root);
+        let source = flattener.crate_root(&root.join("src/lib.cairo")).unwrap();
+        assert_eq!(
+            source,
+            "pub mod token {\n\
+             \x20   // ---- src/token.cairo ----\n\
+             \x20   #[starknet::contract]\n\
+             \x20   pub mod MyToken {\n\
+             \x20       #[storage]\n\
+             \x20       struct Storage {}\n\
+             \x20   }\n\
+             }\n\
+             mod utils {\n\
+             \x20   pub mod math {\n\
+             \x20       // ---- src/utils/math.cairo ----\n\
+             \x20       pub fn double(x: u8) -> u8 {\n\
+             \x20           x * 2\n\
+             \x20       }\n\
+             \x20   }\n\
+             }\n\
+             mod tests; // not flattened: src/tests.cairo does not exist\n"
+        );
+        assert_eq!(
+            flattener.files,
+            ["src/lib.cairo", "src/token.cairo", "src/utils/math.cairo"]
+        );
+        assert_eq!(flattener.missing, ["src/tests.cairo"]);
+
+        let err = Flattener::new("token", &root)
+            .crate_root(&root.join("src/main.cairo"))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            resolver::Error::SourceUnreadable {
+                operation: "read",
+                ..
+            }
+        ));
+
+        let header = header(
+            "token 0.1.0",
+            "MyToken",
+            Utf8Path::new("src/token.cairo"),
+            &[("openzeppelin".to_string(), "^0.20.0 (registry)".to_string())],
+        );
+        assert!(header.starts_with("// MyToken from token 0.1.0, flattened by `voyager flatten`\n"));
+        assert!(header
+            .contains("// Dependencies, not inlined:\n//   openzeppelin ^0.20.0 (registry)\n"));
+    }
+}
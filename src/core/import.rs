@@ -0,0 +1,281 @@
+//! Classes recorded by deployment tooling
+//!
+//! `voyager init --from-deployments <FILE>` writes the `[[contracts]]` of
+//! .voyager.toml from the classes a deployment tool recorded instead of
+//! placeholders. It reads:
+//!
+//! - the state file `sncast script run` keeps next to a deployment script
+//!   (`<script>_<network>_state.json`), whose successful `declare`
+//!   transactions give the class hashes;
+//! - deployments files written by scripts around `starkli`, in the formats
+//!   of [`super::deployments`] or as an object of contracts by name:
+//!
+//! ```json
+//! {
+//!   "MyToken": { "class_hash": "0x044d…", "address": "0x0123…" }
+//! }
+//! ```
+//!
+//! sncast does not record the name of a declared contract in all versions;
+//! such classes are imported without a name, to be filled in by hand.
+
+use super::{class_hash::ClassHash, deployments};
+use crate::utils::errors::CliError;
+use camino::Utf8Path;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// A class to verify, with the addresses it was deployed at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedClass {
+    /// Module name of the contract, if the tool recorded it
+    pub name: Option<String>,
+    pub class_hash: ClassHash,
+    pub addresses: Vec<String>,
+}
+
+/// Read the deployments recorded in the file at `path`
+///
+/// # Errors
+///
+/// Returns [`CliError::InvalidDeployments`] if the file cannot be read, is
+/// in none of the supported formats, or records no class.
+pub fn read(path: &Utf8Path) -> Result<Vec<ImportedClass>, CliError> {
+    let content = std::fs::read_to_string(path).map_err(|e| CliError::InvalidDeployments {
+        path: path.to_path_buf(),
+        reason: format!("cannot be read: {e}"),
+    })?;
+    parse(&content).map_err(|reason| CliError::InvalidDeployments {
+        path: path.to_path_buf(),
+        reason,
+    })
+}
+
+/// Parse the contents of an sncast state file or a deployments file
+///
+/// A class deployed several times is imported once, with every address.
+///
+/// # Errors
+///
+/// Returns the reason the contents cannot be imported.
+pub fn parse(content: &str) -> Result<Vec<ImportedClass>, String> {
+    let value: Value = serde_json::from_str(content).map_err(|e| format!("not JSON: {e}"))?;
+    let recorded = match &value {
+        Value::Array(_) => from_deployments(content)?,
+        Value::Object(object) if object.contains_key("contracts") => from_deployments(content)?,
+        Value::Object(object) if object.contains_key("transactions") => sncast_declarations(value)?,
+        Value::Object(object) => by_name(object)?,
+        _ => return Err(
+            "expected an sncast state file, a list of contracts or an object of contracts by name"
+                .to_string(),
+        ),
+    };
+
+    let mut classes: Vec<ImportedClass> = Vec::new();
+    for (name, class_hash, address) in recorded {
+        let known = classes
+            .iter_mut()
+            .find(|class| class.class_hash.same_value(&class_hash));
+        match known {
+            Some(class) => {
+                class.name = class.name.take().or(name);
+                if let Some(address) = address.filter(|a| !class.addresses.contains(a)) {
+                    class.addresses.push(address);
+                }
+            }
+            None => classes.push(ImportedClass {
+                name,
+                class_hash,
+                addresses: address.into_iter().collect(),
+            }),
+        }
+    }
+    if classes.is_empty() {
+        return Err("it records no declared class".to_string());
+    }
+    Ok(classes)
+}
+
+/// Name, class hash and address of a recorded contract
+type Recorded = (Option<String>, ClassHash, Option<String>);
+
+fn from_deployments(content: &str) -> Result<Vec<Recorded>, String> {
+    Ok(deployments::parse(content)?
+        .into_iter()
+        .map(|deployment| {
+            (
+                Some(deployment.name),
+                deployment.class_hash,
+                deployment.address,
+            )
+        })
+        .collect())
+}
+
+#[derive(Deserialize)]
+struct SncastState {
+    /// `null` until the script sends a transaction
+    #[serde(default)]
+    transactions: Option<SncastTransactions>,
+}
+
+#[derive(Deserialize)]
+struct SncastTransactions {
+    transactions: BTreeMap<String, SncastTransaction>,
+}
+
+#[derive(Deserialize)]
+struct SncastTransaction {
+    name: String,
+    output: Value,
+    status: String,
+    #[serde(default)]
+    timestamp: u64,
+    #[serde(default)]
+    misc: Option<Map<String, Value>>,
+}
+
+/// Classes declared by the successful transactions of an sncast state file,
+/// in the order they were sent
+fn sncast_declarations(value: Value) -> Result<Vec<Recorded>, String> {
+    let state: SncastState =
+        serde_json::from_value(value).map_err(|e| format!("not an sncast state file: {e}"))?;
+    let mut transactions: Vec<SncastTransaction> = state
+        .transactions
+        .map(|transactions| transactions.transactions.into_values().collect())
+        .unwrap_or_default();
+    transactions.sort_by_key(|transaction| transaction.timestamp);
+
+    transactions
+        .into_iter()
+        .filter(|transaction| transaction.name == "declare" && transaction.status == "Success")
+        .filter_map(|transaction| {
+            let class_hash = transaction.output.get("class_hash")?.as_str()?.to_string();
+            let name = transaction
+                .misc
+                .as_ref()
+                .and_then(|misc| misc.get("contract_name"))
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            Some((name, class_hash))
+        })
+        .map(|(name, class_hash)| {
+            let class_hash = ClassHash::new(&class_hash)
+                .map_err(|_| format!("a declaration has an invalid class_hash '{class_hash}'"))?;
+            Ok((name, class_hash, None))
+        })
+        .collect()
+}
+
+/// Contracts of an object keyed by contract name
+fn by_name(object: &Map<String, Value>) -> Result<Vec<Recorded>, String> {
+    let field = |contract: &Value, keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| contract.get(key).and_then(Value::as_str))
+            .map(str::to_string)
+    };
+    object
+        .iter()
+        .map(|(name, contract)| {
+            if !contract.is_object() {
+                return Err(format!(
+                    "expected an sncast state file, a list of contracts or an object of contracts by name, but {name} is not a contract"
+                ));
+            }
+            let class_hash = field(contract, &["class_hash", "classHash"])
+                .ok_or_else(|| format!("{name} has no class_hash"))?;
+            let class_hash = ClassHash::new(&class_hash)
+                .map_err(|_| format!("{name} has an invalid class_hash '{class_hash}'"))?;
+            let address = field(contract, &["address", "contract_address"]);
+            Ok((Some(name.clone()), class_hash, address))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18";
+
+    #[test]
+    fn test_parse_sncast_state() {
+        let state = format!(
+            r#"{{
+                "version": 1,
+                "transactions": {{
+                    "transactions": {{
+                        "b2": {{
+                            "name": "declare",
+                            "output": {{ "type": "DeclareResponse", "class_hash": "0x0abc", "transaction_hash": "0x2" }},
+                            "status": "Success",
+                            "timestamp": 20
+                        }},
+                        "a1": {{
+                            "name": "declare",
+                            "output": {{ "type": "DeclareResponse", "class_hash": "{TOKEN}", "transaction_hash": "0x1" }},
+                            "status": "Success",
+                            "timestamp": 10,
+                            "misc": {{ "contract_name": "MyToken" }}
+                        }},
+                        "c3": {{
+                            "name": "deploy",
+                            "output": {{ "type": "DeployResponse", "contract_address": "0x5", "transaction_hash": "0x3" }},
+                            "status": "Success",
+                            "timestamp": 30
+                        }},
+                        "d4": {{
+                            "name": "declare",
+                            "output": {{ "type": "ErrorResponse", "message": "class already declared" }},
+                            "status": "Error",
+                            "timestamp": 40
+                        }}
+                    }}
+                }}
+            }}"#
+        );
+        let classes = parse(&state).unwrap();
+        let found: Vec<_> = classes
+            .iter()
+            .map(|class| (class.name.as_deref(), class.class_hash.to_string()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (Some("MyToken"), TOKEN.to_string()),
+                (None, "0x0abc".to_string())
+            ]
+        );
+
+        let empty = r#"{ "version": 1, "transactions": null }"#;
+        assert_eq!(parse(empty).unwrap_err(), "it records no declared class");
+    }
+
+    #[test]
+    fn test_parse_deployments_by_name() {
+        let deployments = format!(
+            r#"{{
+                "MyToken": {{ "class_hash": "{TOKEN}", "address": "0x1" }},
+                "MyTokenV2": {{ "classHash": "{TOKEN}", "contract_address": "0x2" }},
+                "Vault": {{ "class_hash": "0x0def" }}
+            }}"#
+        );
+        let classes = parse(&deployments).unwrap();
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].name.as_deref(), Some("MyToken"));
+        assert_eq!(classes[0].addresses, ["0x1", "0x2"]);
+        assert!(classes[1].addresses.is_empty());
+
+        let list = format!(r#"[{{ "name": "MyToken", "class_hash": "{TOKEN}" }}]"#);
+        assert_eq!(parse(&list).unwrap()[0].name.as_deref(), Some("MyToken"));
+
+        assert!(parse(r#"{ "MyToken": "0x1" }"#)
+            .unwrap_err()
+            .contains("MyToken is not a contract"));
+        assert!(parse(r#"{ "MyToken": { "class_hash": "nope" } }"#)
+            .unwrap_err()
+            .contains("invalid class_hash"));
+    }
+}
@@ -0,0 +1,284 @@
+//! Explanation of class hash mismatches
+//!
+//! A verification fails when the class compiled by the API does not hash to
+//! the declared class hash. The usual causes are a different toolchain, the
+//! `sierra-replace-ids` setting, enabled features, and dependencies resolved to
+//! other versions. This module turns what the client knows about the
+//! submission into a checklist of those causes, flagging the likely ones.
+
+use super::reproduce::Toolchain;
+use camino::Utf8Path;
+use log::debug;
+use scarb_metadata::Metadata;
+use std::fs;
+use toml::{Table, Value};
+
+/// Whether `Scarb.lock` went into the submission
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockFile {
+    Submitted,
+    /// The workspace has one, but `--lock-file` was not given
+    NotSubmitted,
+    Missing,
+}
+
+/// Settings of the submitted package that change the compiled class
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectFacts {
+    /// `sierra-replace-ids` under `[profile.release.cairo]` or `[cairo]`, if set
+    pub sierra_replace_ids: Option<bool>,
+    /// Features declared under `[features]`, except `default`
+    pub features: Vec<String>,
+    /// Features enabled by default
+    pub default_features: Vec<String>,
+    pub lock_file: LockFile,
+}
+
+impl ProjectFacts {
+    /// Read the settings of `package` (the only member if `None`) in `metadata`
+    #[must_use]
+    pub fn read(metadata: &Metadata, package: Option<&str>, lock_file: bool) -> Option<Self> {
+        let member = metadata
+            .packages
+            .iter()
+            .filter(|p| metadata.workspace.members.contains(&p.id))
+            .find(|p| package.is_none_or(|name| p.name == name))?;
+        let manifest = read_manifest(&member.manifest_path)?;
+        let workspace = read_manifest(&metadata.workspace.manifest_path).unwrap_or_default();
+        let lock_file = if lock_file {
+            LockFile::Submitted
+        } else if metadata.workspace.root.join("Scarb.lock").exists() {
+            LockFile::NotSubmitted
+        } else {
+            LockFile::Missing
+        };
+        Some(Self::from_manifests(&manifest, &workspace, lock_file))
+    }
+
+    /// Settings from the package manifest, falling back to the workspace's
+    #[must_use]
+    pub fn from_manifests(package: &Table, workspace: &Table, lock_file: LockFile) -> Self {
+        let sierra_replace_ids = [package, workspace]
+            .into_iter()
+            .find_map(sierra_replace_ids);
+        let features = package.get("features").and_then(Value::as_table);
+        let names = |values: Option<&Value>| -> Vec<String> {
+            values
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|value| value.as_str().map(ToString::to_string))
+                .collect()
+        };
+        Self {
+            sierra_replace_ids,
+            features: features
+                .into_iter()
+                .flat_map(|table| table.keys())
+                .filter(|name| *name != "default")
+                .cloned()
+                .collect(),
+            default_features: names(features.and_then(|table| table.get("default"))),
+            lock_file,
+        }
+    }
+}
+
+/// What is known about a submission that failed with a mismatch
+#[derive(Debug, Clone, Default)]
+pub struct SubmissionFacts {
+    /// Toolchain the sources were submitted with
+    pub toolchain: Option<Toolchain>,
+    /// Toolchain pinned in `.tool-versions`
+    pub pinned: Option<Toolchain>,
+    /// Settings of the package, when the project is at hand
+    pub project: Option<ProjectFacts>,
+}
+
+/// A possible cause of a mismatch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cause {
+    pub title: &'static str,
+    pub detail: String,
+    /// Whether the known facts point at this cause
+    pub likely: bool,
+}
+
+impl SubmissionFacts {
+    /// The usual causes of a mismatch, tailored to the known facts
+    #[must_use]
+    pub fn causes(&self) -> Vec<Cause> {
+        let mut causes = vec![self.toolchain_cause()];
+        let project = self.project.as_ref();
+
+        causes.push(match project.map(|p| p.sierra_replace_ids) {
+            Some(Some(enabled)) => Cause {
+                title: "sierra-replace-ids",
+                detail: format!(
+                    "Scarb.toml sets sierra-replace-ids = {enabled}; the declared class must have been built with the same setting"
+                ),
+                likely: false,
+            },
+            Some(None) => Cause {
+                title: "sierra-replace-ids",
+                detail: "Scarb.toml does not set sierra-replace-ids; a class declared from a build that enabled it compiles differently".to_string(),
+                likely: false,
+            },
+            None => Cause {
+                title: "sierra-replace-ids",
+                detail: "The [cairo] sierra-replace-ids setting must match the build the class was declared from".to_string(),
+                likely: false,
+            },
+        });
+
+        match project {
+            Some(project) if !project.features.is_empty() => causes.push(Cause {
+                title: "Features",
+                detail: format!(
+                    "The package declares features ({}); the remote build only enables the default ones ({}), so a class declared with --features differs",
+                    project.features.join(", "),
+                    if project.default_features.is_empty() {
+                        "none".to_string()
+                    } else {
+                        project.default_features.join(", ")
+                    }
+                ),
+                likely: true,
+            }),
+            Some(_) => {}
+            None => causes.push(Cause {
+                title: "Features",
+                detail: "The class must have been declared from a build with the default features only".to_string(),
+                likely: false,
+            }),
+        }
+
+        causes.push(match project.map(|p| p.lock_file) {
+            Some(LockFile::NotSubmitted) => Cause {
+                title: "Dependency drift",
+                detail: "Scarb.lock was not submitted, so the remote build resolves dependency versions anew; retry with --lock-file".to_string(),
+                likely: true,
+            },
+            Some(LockFile::Missing) => Cause {
+                title: "Dependency drift",
+                detail: "The project has no Scarb.lock; dependencies may resolve to other versions than when the class was declared".to_string(),
+                likely: true,
+            },
+            Some(LockFile::Submitted) => Cause {
+                title: "Dependency drift",
+                detail: "Scarb.lock was submitted; check that it is the lock file of the deployed revision".to_string(),
+                likely: false,
+            },
+            None => Cause {
+                title: "Dependency drift",
+                detail: "Dependencies must resolve to the versions used for the declared build; submit Scarb.lock with --lock-file".to_string(),
+                likely: false,
+            },
+        });
+
+        causes
+    }
+
+    fn toolchain_cause(&self) -> Cause {
+        let title = "Compiler version";
+        let Some(toolchain) = &self.toolchain else {
+            return Cause {
+                title,
+                detail: "The class must have been declared from a build with the same Scarb and Cairo versions".to_string(),
+                likely: false,
+            };
+        };
+        let submitted = format!(
+            "Scarb {} (Cairo {})",
+            toolchain.scarb.as_deref().unwrap_or("?"),
+            toolchain.cairo.as_deref().unwrap_or("?")
+        );
+        self.pinned
+            .as_ref()
+            .and_then(|pinned| pinned.scarb.as_ref())
+            .filter(|pinned| toolchain.scarb.as_ref() != Some(*pinned))
+            .map_or_else(
+                || Cause {
+                    title,
+                    detail: format!(
+                        "Submitted with {submitted}; the class must have been declared from a build with the same versions"
+                    ),
+                    likely: false,
+                },
+                |pinned| Cause {
+                    title,
+                    detail: format!(
+                        "Submitted with {submitted}, but .tool-versions pins Scarb {pinned}; retry with the pinned version"
+                    ),
+                    likely: true,
+                },
+            )
+    }
+}
+
+/// `sierra-replace-ids` of the release profile, which the remote build uses
+fn sierra_replace_ids(manifest: &Table) -> Option<bool> {
+    let release = manifest
+        .get("profile")
+        .and_then(|profile| profile.get("release"))
+        .and_then(Value::as_table);
+    [release, Some(manifest)]
+        .into_iter()
+        .flatten()
+        .find_map(|settings| settings.get("cairo")?.get("sierra-replace-ids")?.as_bool())
+}
+
+fn read_manifest(path: &Utf8Path) -> Option<Table> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| debug!("Cannot read {path}: {e}"))
+        .ok()?;
+    toml::from_str(&content)
+        .map_err(|e| debug!("Cannot parse {path}: {e}"))
+        .ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_causes_follow_known_facts() {
+        let package: Table = toml::from_str(
+            "[package]\nname = \"token\"\n\n[features]\ndefault = [\"erc20\"]\nerc20 = []\nmock = []\n",
+        )
+        .unwrap();
+        let workspace: Table = toml::from_str(
+            "[cairo]\nsierra-replace-ids = false\n\n[profile.release.cairo]\nsierra-replace-ids = true\n",
+        )
+        .unwrap();
+        let project = ProjectFacts::from_manifests(&package, &workspace, LockFile::NotSubmitted);
+        assert_eq!(project.sierra_replace_ids, Some(true));
+        assert_eq!(project.features, ["erc20", "mock"]);
+        assert_eq!(project.default_features, ["erc20"]);
+
+        let facts = SubmissionFacts {
+            toolchain: Some(Toolchain {
+                scarb: Some("2.8.4".to_string()),
+                cairo: Some("2.8.4".to_string()),
+            }),
+            pinned: Some(Toolchain {
+                scarb: Some("2.9.1".to_string()),
+                cairo: None,
+            }),
+            project: Some(project),
+        };
+        let likely: Vec<_> = facts
+            .causes()
+            .into_iter()
+            .filter(|cause| cause.likely)
+            .map(|cause| cause.title)
+            .collect();
+        assert_eq!(likely, ["Compiler version", "Features", "Dependency drift"]);
+
+        // Nothing known: every cause is listed, none is flagged
+        let causes = SubmissionFacts::default().causes();
+        assert_eq!(causes.len(), 4);
+        assert!(causes.iter().all(|cause| !cause.likely));
+    }
+}
@@ -1,3 +1,16 @@
+pub mod analyze;
+pub mod artifacts;
 pub mod class_hash;
+pub mod compat;
+pub mod deployments;
+pub mod deps;
+pub mod flatten;
+pub mod import;
+pub mod mismatch;
+pub mod pack;
+pub mod piped;
 pub mod project;
+pub mod receipt;
+pub mod reproduce;
 pub mod verification;
+pub mod world;
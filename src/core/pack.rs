@@ -0,0 +1,356 @@
+//! Self-contained verification packages
+//!
+//! `voyager pack` collects the files of a contract the way `voyager verify`
+//! does and writes them, with what the API needs to know about them, to a
+//! single `.voyagerpkg` file. `voyager verify --from-pack` submits that file
+//! later without the project, so the sources can be collected by a developer
+//! and submitted by whoever holds the API credentials.
+//!
+//! A package is a zip archive with the sources under [`PAYLOAD_DIR`] and a
+//! [`MANIFEST_NAME`] at its root:
+//!
+//! ```json
+//! {
+//!   "format_version": 1,
+//!   "created_at": "2025-11-20T10:15:00Z",
+//!   "voyager_version": "2.1.0",
+//!   "metadata": {
+//!     "cairo_version": "2.11.4",
+//!     "scarb_version": "2.11.4",
+//!     "package_name": "token",
+//!     "contract_file": "src/lib.cairo",
+//!     "project_dir_path": ".",
+//!     "build_tool": "scarb",
+//!     "class_hash": "0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18",
+//!     "contract_name": "MyToken",
+//!     "license": "MIT"
+//!   },
+//!   "files": {
+//!     "Scarb.toml": "sha256:…",
+//!     "src/lib.cairo": "sha256:…"
+//!   }
+//! }
+//! ```
+//!
+//! The digest of `manifest.json` itself is stored next to it in
+//! [`MANIFEST_DIGEST_NAME`]. The manifest is checked against it, and every
+//! file against its digest, before anything is submitted. The digests are
+//! stored in the package they cover, so they detect corruption, such as a
+//! truncated transfer, but not deliberate edits.
+
+use super::piped::{PipedManifest, PipedSources};
+use crate::api::FileInfo;
+use crate::filesystem::{collector::content_digest, tar::TarEntry};
+use crate::utils::errors::CliError;
+use camino::Utf8Path;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Read, Write},
+};
+use zip::{result::ZipError, write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+/// File extension of verification packages
+pub const EXTENSION: &str = "voyagerpkg";
+
+/// Name of the manifest at the root of a package
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// Name of the entry holding the digest of [`MANIFEST_NAME`]
+pub const MANIFEST_DIGEST_NAME: &str = "manifest.json.sha256";
+
+/// Directory of the package holding the sources
+pub const PAYLOAD_DIR: &str = "payload/";
+
+/// Version of the package layout written by this release
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Contents of [`MANIFEST_NAME`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Version of voyager that wrote the package
+    pub voyager_version: String,
+    /// What would otherwise be read from the project
+    pub metadata: PipedManifest,
+    /// Digest of each source, by name
+    pub files: BTreeMap<String, String>,
+}
+
+/// A verification package
+#[derive(Debug)]
+pub struct Pack {
+    pub manifest: PackManifest,
+    pub files: Vec<TarEntry>,
+}
+
+impl Pack {
+    /// Package the files in `file_infos`, described by `metadata`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if one of the files cannot be read.
+    pub fn new(metadata: PipedManifest, file_infos: &[FileInfo]) -> std::io::Result<Self> {
+        let files = file_infos
+            .iter()
+            .map(|info| {
+                Ok(TarEntry {
+                    path: info.name.clone(),
+                    contents: std::fs::read(&info.path)?,
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let manifest = PackManifest {
+            format_version: FORMAT_VERSION,
+            created_at: Utc::now(),
+            voyager_version: env!("CARGO_PKG_VERSION").to_string(),
+            metadata,
+            files: files
+                .iter()
+                .map(|entry| (entry.path.clone(), content_digest(&entry.contents)))
+                .collect(),
+        };
+        Ok(Self { manifest, files })
+    }
+
+    /// Total size of the sources, in bytes
+    #[must_use]
+    pub fn payload_size(&self) -> u64 {
+        self.files
+            .iter()
+            .map(|entry| entry.contents.len() as u64)
+            .sum()
+    }
+
+    /// Write the package as a zip archive to `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::PackWriteFailed`] if the archive cannot be written.
+    pub fn write(&self, path: &Utf8Path) -> Result<(), CliError> {
+        let to_error = |source| CliError::PackWriteFailed {
+            path: path.to_path_buf(),
+            source,
+        };
+        let manifest = serde_json::to_vec_pretty(&self.manifest).map_err(|e| to_error(e.into()))?;
+
+        let manifest_digest = content_digest(&manifest);
+
+        let mut zip = ZipWriter::new(File::create(path).map_err(to_error)?);
+        let entries = [
+            (MANIFEST_NAME.to_string(), manifest.as_slice()),
+            (MANIFEST_DIGEST_NAME.to_string(), manifest_digest.as_bytes()),
+        ]
+        .into_iter()
+        .chain(self.files.iter().map(|entry| {
+            (
+                format!("{PAYLOAD_DIR}{}", entry.path),
+                entry.contents.as_slice(),
+            )
+        }));
+        for (name, contents) in entries {
+            zip.start_file(name, SimpleFileOptions::default())
+                .map_err(|e| to_error(e.into()))?;
+            zip.write_all(contents).map_err(to_error)?;
+        }
+        zip.finish().map_err(|e| to_error(e.into()))?;
+        Ok(())
+    }
+
+    /// Read the package at `path`, checking the manifest and each source
+    /// against its digest
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::InvalidPack`] if the file is not a package, its
+    /// manifest does not match its digest, it was written by a newer format,
+    /// has no sources, or a source is missing, unlisted or does not match its
+    /// digest.
+    pub fn read(path: &Utf8Path) -> Result<Self, CliError> {
+        let invalid = |reason: String| CliError::InvalidPack {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let file = File::open(path).map_err(|e| invalid(format!("cannot be opened: {e}")))?;
+        let mut archive =
+            ZipArchive::new(file).map_err(|e| invalid(format!("not a zip archive: {e}")))?;
+
+        let manifest = read_entry(&mut archive, MANIFEST_NAME)
+            .map_err(|e| invalid(format!("cannot read {MANIFEST_NAME}: {e}")))?;
+        match read_entry(&mut archive, MANIFEST_DIGEST_NAME) {
+            Ok(digest) if digest.trim_ascii() == content_digest(&manifest).as_bytes() => {}
+            Ok(_) => {
+                return Err(invalid(format!(
+                    "{MANIFEST_NAME} does not match its digest in {MANIFEST_DIGEST_NAME}"
+                )))
+            }
+            Err(ZipError::FileNotFound) => {
+                return Err(invalid(format!("it has no {MANIFEST_DIGEST_NAME}")))
+            }
+            Err(e) => return Err(invalid(format!("cannot read {MANIFEST_DIGEST_NAME}: {e}"))),
+        }
+        let manifest: PackManifest = serde_json::from_slice(&manifest)
+            .map_err(|e| invalid(format!("{MANIFEST_NAME} is invalid: {e}")))?;
+        if manifest.format_version > FORMAT_VERSION {
+            return Err(invalid(format!(
+                "format version {} is newer than the supported version {FORMAT_VERSION}",
+                manifest.format_version
+            )));
+        }
+        manifest.metadata.check_build_tool().map_err(invalid)?;
+        if manifest.files.is_empty() {
+            return Err(invalid("it has no sources".to_string()));
+        }
+
+        let unlisted = archive.file_names().find(|name| {
+            name.strip_prefix(PAYLOAD_DIR).is_some_and(|source| {
+                !source.is_empty() && !source.ends_with('/') && !manifest.files.contains_key(source)
+            })
+        });
+        if let Some(name) = unlisted {
+            return Err(invalid(format!("{name} is not listed in {MANIFEST_NAME}")));
+        }
+
+        let mut files = Vec::with_capacity(manifest.files.len());
+        for (name, digest) in &manifest.files {
+            if name.starts_with('/') || name.split('/').any(|part| part == "..") {
+                return Err(invalid(format!("{name} is outside of the package")));
+            }
+            let contents = read_entry(&mut archive, &format!("{PAYLOAD_DIR}{name}"))
+                .map_err(|e| invalid(format!("cannot read {name}: {e}")))?;
+            if content_digest(&contents) != *digest {
+                return Err(invalid(format!(
+                    "{name} does not match its digest in {MANIFEST_NAME}"
+                )));
+            }
+            files.push(TarEntry {
+                path: name.clone(),
+                contents,
+            });
+        }
+        Ok(Self { manifest, files })
+    }
+
+    /// The sources and their metadata, to be submitted like piped sources
+    #[must_use]
+    pub fn into_sources(self) -> PipedSources {
+        PipedSources {
+            manifest: self.manifest.metadata,
+            files: self.files,
+        }
+    }
+}
+
+/// Contents of the entry `name` of `archive`
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> Result<Vec<u8>, ZipError> {
+    let mut entry = archive.by_name(name)?;
+    let mut contents = Vec::new();
+    entry.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+
+    fn metadata() -> PipedManifest {
+        serde_json::from_str(
+            r#"{
+                "cairo_version": "2.11.4",
+                "scarb_version": "2.11.4",
+                "package_name": "token",
+                "contract_file": "src/lib.cairo",
+                "contract_name": "MyToken"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pack_round_trip_checks_digests() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        let source = dir.join("lib.cairo");
+        std::fs::write(&source, "mod token;\n").unwrap();
+        let pack = Pack::new(
+            metadata(),
+            &[FileInfo {
+                name: "src/lib.cairo".to_string(),
+                path: source.into_std_path_buf(),
+            }],
+        )
+        .unwrap();
+        assert_eq!(pack.payload_size(), 11);
+
+        let path = dir.join(format!("token.{EXTENSION}"));
+        pack.write(&path).unwrap();
+        let read = Pack::read(&path).unwrap();
+        assert_eq!(read.manifest, pack.manifest);
+        let sources = read.into_sources();
+        assert_eq!(sources.manifest.package_name, "token");
+        assert_eq!(sources.files, pack.files);
+
+        let mut corrupted = Pack {
+            manifest: pack.manifest.clone(),
+            files: vec![TarEntry {
+                path: "src/lib.cairo".to_string(),
+                contents: b"mod tok".to_vec(),
+            }],
+        };
+        corrupted.write(&path).unwrap();
+        assert!(matches!(
+            Pack::read(&path),
+            Err(CliError::InvalidPack { ref reason, .. }) if reason.contains("digest")
+        ));
+
+        let write_zip = |entries: &[(&str, Vec<u8>)]| {
+            let mut zip = ZipWriter::new(File::create(&path).unwrap());
+            for (name, contents) in entries {
+                zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+                zip.write_all(contents).unwrap();
+            }
+            zip.finish().unwrap();
+        };
+        let manifest_json = serde_json::to_vec_pretty(&pack.manifest).unwrap();
+        let source = ("payload/src/lib.cairo", b"mod token;\n".to_vec());
+
+        // The manifest is covered by its own digest
+        let mut manifest = serde_json::to_value(&pack.manifest).unwrap();
+        manifest["metadata"]["contract_name"] = "MyTokem".into();
+        write_zip(&[
+            (MANIFEST_NAME, manifest.to_string().into_bytes()),
+            (
+                MANIFEST_DIGEST_NAME,
+                content_digest(&manifest_json).into_bytes(),
+            ),
+            source.clone(),
+        ]);
+        assert!(matches!(
+            Pack::read(&path),
+            Err(CliError::InvalidPack { ref reason, .. }) if reason.contains(MANIFEST_DIGEST_NAME)
+        ));
+
+        // ... which cannot be left out
+        write_zip(&[(MANIFEST_NAME, manifest_json), source]);
+        assert!(matches!(
+            Pack::read(&path),
+            Err(CliError::InvalidPack { ref reason, .. }) if reason.contains(MANIFEST_DIGEST_NAME)
+        ));
+
+        corrupted.files = pack.files;
+        corrupted.manifest.format_version = FORMAT_VERSION + 1;
+        corrupted.write(&path).unwrap();
+        assert!(Pack::read(&path).is_err());
+
+        std::fs::write(&path, "not a zip").unwrap();
+        assert!(matches!(
+            Pack::read(&path),
+            Err(CliError::InvalidPack { ref reason, .. }) if reason.contains("zip")
+        ));
+    }
+}
@@ -0,0 +1,610 @@
+//! Verification of sources piped in as a tar stream
+//!
+//! Build systems such as Bazel and Nix keep sources outside of a Scarb
+//! working tree, so `scarb metadata` cannot describe them. With
+//! `voyager verify --stdin-tar` the files to submit arrive as a tar archive on
+//! standard input instead, along with a [`MANIFEST_NAME`] file at its root
+//! stating what would otherwise be read from the project:
+//!
+//! ```json
+//! {
+//!   "cairo_version": "2.11.4",
+//!   "scarb_version": "2.11.4",
+//!   "package_name": "token",
+//!   "contract_file": "src/lib.cairo",
+//!   "class_hash": "0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18",
+//!   "contract_name": "MyToken",
+//!   "license": "MIT"
+//! }
+//! ```
+//!
+//! `--class-hash`, `--contract-name`, `--license`, `--package`,
+//! `--contract-file` and `--project-type` take precedence over the manifest.
+
+use super::{
+    class_hash::ClassHash,
+    pack,
+    project::ProjectType,
+    verification::{
+        cache_submission, check_upload, confirm_submission, ensure_class_declared,
+        guard_duplicate_submission, network_label, payload_stats, request_digest,
+        reuse_cached_submission, save_to_history, warn_unsupported_toolchain, CheckedUpload,
+        HistoryParams, Submission, Upload,
+    },
+};
+use crate::api::{ApiClient, FileInfo, ProjectMetadataInfo};
+use crate::cli::args::VerifyArgs;
+use crate::filesystem::{
+    collector::{source_fingerprint, ScratchDir},
+    tar::{read_tar, TarEntry},
+};
+use crate::output::{sink, summary::SubmissionSummary};
+use crate::utils::{errors::CliError, license::LicenseInfo};
+use camino::Utf8Path;
+use log::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::{fmt, io::Read};
+
+/// Name of the manifest at the root of a piped archive
+pub const MANIFEST_NAME: &str = "voyager-manifest.json";
+
+/// What `scarb metadata` would tell about piped sources
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PipedManifest {
+    pub cairo_version: semver::Version,
+    pub scarb_version: semver::Version,
+    pub package_name: String,
+    /// File defining the contract, as named in the archive
+    pub contract_file: String,
+    /// Directory of the package in the archive
+    #[serde(default = "default_project_dir_path")]
+    pub project_dir_path: String,
+    /// `scarb` or `sozo`
+    #[serde(default = "default_build_tool")]
+    pub build_tool: String,
+    #[serde(default)]
+    pub dojo_version: Option<String>,
+    #[serde(default)]
+    pub class_hash: Option<String>,
+    #[serde(default)]
+    pub contract_name: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+}
+
+impl PipedManifest {
+    /// Reject a build tool the API does not know
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason if `build_tool` is neither `scarb` nor `sozo`.
+    pub fn check_build_tool(&self) -> Result<(), String> {
+        if matches!(self.build_tool.as_str(), "scarb" | "sozo") {
+            Ok(())
+        } else {
+            Err(format!(
+                "build_tool must be 'scarb' or 'sozo', not '{}'",
+                self.build_tool
+            ))
+        }
+    }
+}
+
+fn default_project_dir_path() -> String {
+    ".".to_string()
+}
+
+fn default_build_tool() -> String {
+    "scarb".to_string()
+}
+
+/// Sources of a piped archive, with its manifest
+#[derive(Debug)]
+pub struct PipedSources {
+    pub manifest: PipedManifest,
+    /// Every other file of the archive
+    pub files: Vec<TarEntry>,
+}
+
+impl PipedSources {
+    /// Read the archive in `reader`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::InvalidPipedSources`] if the stream is not a tar
+    /// archive, has no sources, or its manifest is missing or invalid.
+    pub fn read(reader: impl Read) -> Result<Self, CliError> {
+        let invalid = |reason: String| CliError::InvalidPipedSources { reason };
+        let mut files = read_tar(reader).map_err(|e| invalid(e.to_string()))?;
+
+        let position = files
+            .iter()
+            .position(|entry| entry.path == MANIFEST_NAME)
+            .ok_or_else(|| invalid(format!("the archive has no {MANIFEST_NAME}")))?;
+        let manifest: PipedManifest = serde_json::from_slice(&files.remove(position).contents)
+            .map_err(|e| invalid(format!("{MANIFEST_NAME} is invalid: {e}")))?;
+
+        manifest.check_build_tool().map_err(invalid)?;
+        if files.is_empty() {
+            return Err(invalid("the archive has no sources".to_string()));
+        }
+        Ok(Self { manifest, files })
+    }
+}
+
+/// Submit the sources of the archive in `reader`
+///
+/// The files are extracted to a temporary directory so that they pass the
+/// same size limits and payload transforms as collected sources. In dry-run
+/// mode, they are listed instead of submitted.
+///
+/// # Errors
+///
+/// Returns a `CliError` if the archive is invalid, the class hash or contract
+/// name is neither given nor in the manifest, the files exceed the size
+/// limits, or the submission fails.
+pub fn submit_piped(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    reader: impl Read,
+) -> Result<Submission, CliError> {
+    let sources = PipedSources::read(reader)?;
+    submit_sources(api_client, args, sources, &SourceOrigin::Stdin)
+}
+
+/// Where sources submitted without a project were read from
+#[derive(Debug, Clone, Copy)]
+pub enum SourceOrigin<'a> {
+    /// A tar archive piped to `--stdin-tar`
+    Stdin,
+    /// A `.voyagerpkg` file written by `voyager pack`
+    Pack(&'a Utf8Path),
+}
+
+impl SourceOrigin<'_> {
+    /// Error reporting invalid sources from this origin
+    fn invalid(&self, reason: String) -> CliError {
+        match self {
+            Self::Stdin => CliError::InvalidPipedSources { reason },
+            Self::Pack(path) => CliError::InvalidPack {
+                path: path.to_path_buf(),
+                reason,
+            },
+        }
+    }
+
+    /// File describing the sources
+    const fn manifest_name(&self) -> &'static str {
+        match self {
+            Self::Stdin => MANIFEST_NAME,
+            Self::Pack(_) => pack::MANIFEST_NAME,
+        }
+    }
+}
+
+impl fmt::Display for SourceOrigin<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdin => write!(f, "standard input"),
+            Self::Pack(path) => write!(f, "{path}"),
+        }
+    }
+}
+
+/// What a piped submission is for, from the arguments or else the manifest
+struct Target {
+    class_hash: ClassHash,
+    contract_name: String,
+    contract_file: String,
+    package_name: String,
+    project_type: ProjectType,
+    license_info: LicenseInfo,
+}
+
+impl Target {
+    /// Resolve the target of `manifest`, letting `args` override it
+    fn resolve(
+        args: &VerifyArgs,
+        manifest: &PipedManifest,
+        files: &[TarEntry],
+        origin: &SourceOrigin<'_>,
+    ) -> Result<Self, CliError> {
+        let invalid = |reason: String| origin.invalid(reason);
+        let manifest_name = origin.manifest_name();
+
+        let class_hash = match (&args.class_hash, &manifest.class_hash) {
+            (Some(class_hash), _) => class_hash.clone(),
+            (None, Some(raw)) => ClassHash::new(raw).map_err(|e| invalid(e.to_string()))?,
+            (None, None) => {
+                return Err(invalid(format!(
+                    "no class hash: pass --class-hash or set class_hash in {manifest_name}"
+                )))
+            }
+        };
+        let contract_name = args
+            .contract_name
+            .clone()
+            .or_else(|| manifest.contract_name.clone())
+            .ok_or_else(|| {
+                invalid(format!(
+                    "no contract name: pass --contract-name or set contract_name in {manifest_name}"
+                ))
+            })?;
+        let contract_file = args
+            .contract_file
+            .as_ref()
+            .map_or_else(|| manifest.contract_file.clone(), ToString::to_string);
+        if !files.iter().any(|entry| entry.path == contract_file) {
+            return Err(invalid(format!(
+                "the contract file {contract_file} is not among the sources"
+            )));
+        }
+        let package_name = args
+            .package
+            .clone()
+            .unwrap_or_else(|| manifest.package_name.clone());
+        let project_type = match args.project_type {
+            ProjectType::Auto if manifest.build_tool == "sozo" => ProjectType::Dojo,
+            ProjectType::Auto => ProjectType::Scarb,
+            project_type => project_type,
+        };
+        let license_info = match (args.license, &manifest.license) {
+            (Some(license), _) => LicenseInfo::Cli(license),
+            (None, Some(license)) => LicenseInfo::Manifest(license.clone()),
+            (None, None) => LicenseInfo::None,
+        };
+
+        Ok(Self {
+            class_hash,
+            contract_name,
+            contract_file,
+            package_name,
+            project_type,
+            license_info,
+        })
+    }
+
+    /// Project metadata sent with the sources
+    fn project_metadata(&self, args: &VerifyArgs, manifest: &PipedManifest) -> ProjectMetadataInfo {
+        ProjectMetadataInfo::new(
+            manifest.cairo_version.clone(),
+            manifest.scarb_version.clone(),
+            manifest.project_dir_path.clone(),
+            self.contract_file.clone(),
+            self.package_name.clone(),
+            self.project_type,
+            manifest.dojo_version.clone(),
+        )
+        .with_note(args.note.clone())
+    }
+
+    /// Summary of the submission of `files` files of `payload_size` bytes
+    fn summary(
+        &self,
+        args: &VerifyArgs,
+        manifest: &PipedManifest,
+        files: usize,
+        payload_size: u64,
+        fingerprint: Option<String>,
+    ) -> Result<SubmissionSummary, CliError> {
+        Ok(SubmissionSummary {
+            contract_name: self.contract_name.clone(),
+            class_hash: self.class_hash.to_string(),
+            package: self.package_name.clone(),
+            contract_file: self.contract_file.clone(),
+            files,
+            payload_size,
+            cairo_version: manifest.cairo_version.to_string(),
+            scarb_version: manifest.scarb_version.to_string(),
+            build_tool: if self.project_type == ProjectType::Dojo {
+                "sozo"
+            } else {
+                "scarb"
+            },
+            license: self.license_info.display_string().to_string(),
+            network: format!("{} ({})", network_label(args), args.network_url.url()?),
+            fingerprint,
+            note: args.note.clone(),
+        })
+    }
+
+    /// Add the job `job_id` submitting `file_infos` to history
+    fn record(
+        &self,
+        args: &VerifyArgs,
+        manifest: &PipedManifest,
+        job_id: &str,
+        file_infos: &[FileInfo],
+    ) {
+        if let Err(e) = save_to_history(&HistoryParams {
+            job_id,
+            class_hash: &self.class_hash,
+            contract_name: &self.contract_name,
+            network: network_label(args),
+            cairo_version: &manifest.cairo_version.to_string(),
+            scarb_version: &manifest.scarb_version.to_string(),
+            dojo_version: manifest.dojo_version.as_deref(),
+            package_name: &self.package_name,
+            commit_hash: None,
+            note: args.note.as_deref(),
+            payload: payload_stats(file_infos),
+            retention: args.history_retention,
+        }) {
+            warn!("Failed to save verification to history: {e}");
+        }
+    }
+
+    /// List what would be submitted, for `--dry-run`
+    fn print_dry_run(&self, files: &[TarEntry], origin: &SourceOrigin<'_>) {
+        sink::line(format!(
+            "Dry run: would submit {} ({}) from {origin}",
+            self.contract_name, self.class_hash
+        ));
+        sink::line(format!(
+            "  package: {}, contract file: {}",
+            self.package_name, self.contract_file
+        ));
+        sink::line(format!("  license: {}", self.license_info.display_string()));
+        sink::line(format!("  {} file(s):", files.len()));
+        for entry in files {
+            sink::line(format!(
+                "    {} ({} bytes)",
+                entry.path,
+                entry.contents.len()
+            ));
+        }
+    }
+}
+
+/// Submit sources read from `origin`
+///
+/// The sources pass the same checks as the sources of a project before they
+/// are uploaded (see [`check_upload`]), except for the ones that need a local
+/// build of the project.
+///
+/// # Errors
+///
+/// Returns a `CliError` if the class hash or contract name is neither given
+/// nor in the manifest, the contract file is not among the sources, a check
+/// before the upload fails, or the submission fails.
+pub fn submit_sources(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    sources: PipedSources,
+    origin: &SourceOrigin<'_>,
+) -> Result<Submission, CliError> {
+    let PipedSources { manifest, files } = sources;
+    let target = Target::resolve(args, &manifest, &files, origin)?;
+    // The checks shared with project submissions read these from the arguments
+    let args = &VerifyArgs {
+        class_hash: Some(target.class_hash.clone()),
+        contract_name: Some(target.contract_name.clone()),
+        ..args.clone()
+    };
+    ensure_class_declared(args, &target.class_hash)?;
+
+    let (_extraction, file_infos) = extract(&files, origin)?;
+    let CheckedUpload {
+        capabilities,
+        payload_size,
+        ..
+    } = check_upload(
+        api_client,
+        args,
+        &Upload {
+            file_infos: &file_infos,
+            license_info: &target.license_info,
+            project_dir_path: &manifest.project_dir_path,
+            package_name: &target.package_name,
+            project_type: target.project_type,
+        },
+    )?;
+    warn_unsupported_toolchain(
+        &manifest.cairo_version,
+        &manifest.scarb_version,
+        None,
+        capabilities.as_ref(),
+    );
+    let fingerprint = source_fingerprint(&file_infos)
+        .inspect_err(|e| warn!("Failed to fingerprint the sources from {origin}: {e}"))
+        .ok();
+    let not_submitted = |fingerprint| Submission {
+        job_id: None,
+        source_fingerprint: fingerprint,
+        target_jobs: Vec::new(),
+    };
+
+    if args.dry_run {
+        target.print_dry_run(&files, origin);
+        return Ok(not_submitted(fingerprint));
+    }
+
+    let project_meta = target.project_metadata(args, &manifest);
+    let digest = request_digest(
+        api_client,
+        args,
+        project_meta.clone(),
+        &target.license_info,
+        &file_infos,
+    );
+    if let Some(submission) = reuse_cached_submission(
+        api_client,
+        args,
+        "voyager",
+        digest.as_deref(),
+        fingerprint.as_deref(),
+    ) {
+        return Ok(submission);
+    }
+    guard_duplicate_submission(api_client, args, &target.class_hash)?;
+
+    let summary = target.summary(
+        args,
+        &manifest,
+        file_infos.len(),
+        payload_size,
+        fingerprint.clone(),
+    )?;
+    sink::line(format!("\n{}", summary.render()));
+    if !confirm_submission(args, &target.contract_name)? {
+        sink::line("No verification was submitted.");
+        return Ok(not_submitted(fingerprint));
+    }
+
+    info!(
+        "Submitting {} file(s) from {origin} for {}",
+        file_infos.len(),
+        target.contract_name
+    );
+    let job_id = api_client
+        .verify_class(
+            &target.class_hash,
+            Some(target.license_info.display_string().to_string()),
+            &target.contract_name,
+            project_meta,
+            &file_infos,
+        )
+        .map_err(CliError::from)?;
+    if let Some(digest) = &digest {
+        cache_submission(args, &job_id, digest);
+    }
+
+    if args.simulate {
+        debug!("Not recording simulated job {job_id} in history");
+    } else {
+        target.record(args, &manifest, &job_id, &file_infos);
+    }
+
+    Ok(Submission {
+        job_id: Some(job_id),
+        source_fingerprint: fingerprint,
+        target_jobs: Vec::new(),
+    })
+}
+
+/// Extract `files` to a temporary directory, removed when it is dropped
+fn extract(
+    files: &[TarEntry],
+    origin: &SourceOrigin<'_>,
+) -> Result<(ScratchDir, Vec<FileInfo>), CliError> {
+    let extraction = ScratchDir::new("sources")
+        .map_err(|e| origin.invalid(format!("failed to create a temporary directory: {e}")))?;
+    let mut file_infos = Vec::with_capacity(files.len());
+    for entry in files {
+        let path = extraction
+            .write(&entry.path, &entry.contents)
+            .map_err(|e| origin.invalid(format!("failed to extract {}: {e}", entry.path)))?;
+        file_infos.push(FileInfo {
+            name: entry.path.clone(),
+            path: path.into_std_path_buf(),
+        });
+    }
+    Ok((extraction, file_infos))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    /// A one-entry-per-file ustar archive
+    fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut archive = Vec::new();
+        for (name, contents) in files {
+            let mut header = [0u8; 512];
+            header[..name.len()].copy_from_slice(name.as_bytes());
+            header[124..135].copy_from_slice(format!("{:011o}", contents.len()).as_bytes());
+            header[156] = b'0';
+            header[148..156].fill(b' ');
+            let checksum: u64 = header.iter().map(|&b| u64::from(b)).sum();
+            header[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+            archive.extend(header);
+            archive.extend(contents.as_bytes());
+            archive.resize(archive.len().div_ceil(512) * 512, 0);
+        }
+        archive
+    }
+
+    #[test]
+    fn test_read_piped_sources() {
+        let manifest = r#"{
+            "cairo_version": "2.11.4",
+            "scarb_version": "2.11.4",
+            "package_name": "token",
+            "contract_file": "src/lib.cairo",
+            "build_tool": "sozo",
+            "contract_name": "MyToken"
+        }"#;
+        let sources = PipedSources::read(
+            archive(&[
+                ("Scarb.toml", "[package]\nname = \"token\"\n"),
+                (MANIFEST_NAME, manifest),
+                ("src/lib.cairo", "mod token;\n"),
+            ])
+            .as_slice(),
+        )
+        .unwrap();
+        assert_eq!(sources.manifest.package_name, "token");
+        assert_eq!(sources.manifest.project_dir_path, ".");
+        assert_eq!(sources.manifest.contract_name.as_deref(), Some("MyToken"));
+        assert_eq!(sources.manifest.class_hash, None);
+        let paths: Vec<_> = sources
+            .files
+            .iter()
+            .map(|entry| entry.path.as_str())
+            .collect();
+        assert_eq!(paths, ["Scarb.toml", "src/lib.cairo"]);
+
+        let missing = PipedSources::read(archive(&[("src/lib.cairo", "")]).as_slice());
+        assert!(matches!(
+            missing,
+            Err(CliError::InvalidPipedSources { ref reason }) if reason.contains(MANIFEST_NAME)
+        ));
+        let bazel = manifest.replace("sozo", "bazel");
+        let wrong_tool = PipedSources::read(
+            archive(&[(MANIFEST_NAME, &bazel), ("src/lib.cairo", "")]).as_slice(),
+        );
+        assert!(wrong_tool.is_err());
+    }
+
+    #[test]
+    fn test_piped_sources_pass_the_submission_checks() {
+        use crate::cli::args::{Args, Commands};
+        use clap::Parser;
+
+        let parse = |extra: &[&str]| {
+            let base = [
+                "voyager",
+                "verify",
+                "--stdin-tar",
+                "--url",
+                "http://127.0.0.1:1",
+            ];
+            Args::try_parse_from(base.iter().chain(extra)).map(|args| match args.command {
+                Commands::Verify(args) => args,
+                _ => unreachable!("parsed a verify command"),
+            })
+        };
+        assert!(parse(&["--compare-class-hash"]).is_err());
+
+        let args = parse(&["--license", "MIT", "--require-license-headers", "--dry-run"]).unwrap();
+        let manifest = r#"{
+            "cairo_version": "2.11.4",
+            "scarb_version": "2.11.4",
+            "package_name": "token",
+            "contract_file": "src/lib.cairo",
+            "class_hash": "0x1234",
+            "contract_name": "MyToken"
+        }"#;
+        let archive = archive(&[
+            ("Scarb.toml", "[package]\nname = \"token\"\n"),
+            (MANIFEST_NAME, manifest),
+            ("src/lib.cairo", "mod token;\n"),
+        ]);
+        let api_client = ApiClient::new(args.network_url.url().unwrap().clone()).unwrap();
+        assert!(matches!(
+            submit_piped(&api_client, &args, archive.as_slice()),
+            Err(CliError::LicenseHeaderMismatch { .. })
+        ));
+    }
+}
@@ -71,8 +71,11 @@ impl std::fmt::Display for ProjectType {
     }
 }
 
-use crate::cli::args::{Project, VerifyArgs};
-use crate::utils::errors::CliError;
+use crate::cli::{
+    args::{Project, VerifyArgs},
+    prompt,
+};
+use crate::utils::errors::{self, CliError};
 use dialoguer::Select;
 use log::{debug, info, warn};
 use std::fs;
@@ -82,7 +85,8 @@ use std::fs;
 /// This function resolves the project type using the following priority:
 /// 1. If explicitly set to Scarb or Dojo, uses that and validates
 /// 2. If set to Auto, attempts automatic detection
-/// 3. Falls back to interactive prompt if detection fails
+/// 3. If detection is inconclusive, asks on the terminal, or assumes Dojo when
+///    questions cannot be asked (`--non-interactive` or no terminal)
 ///
 /// # Arguments
 ///
@@ -116,18 +120,28 @@ pub fn determine_project_type(args: &VerifyArgs) -> Result<ProjectType, CliError
                     info!("Detected Scarb project automatically");
                     Ok(ProjectType::Scarb)
                 }
+                ProjectType::Auto if !args.is_interactive() => {
+                    // Dojo imports are the only indicator left
+                    warn!("Sources use Dojo but no Dojo dependency, profile or manifest was found; assuming a Dojo project (set --project-type to override)");
+                    validate_dojo_project(&args.path)?;
+                    Ok(ProjectType::Dojo)
+                }
                 ProjectType::Auto => {
                     // Fallback to interactive prompt
-                    let options = vec![
+                    let options = [
                         "Regular Scarb project (uses scarb build)",
                         "Dojo project (uses sozo build)",
                     ];
 
-                    let selection = Select::new()
-                        .with_prompt("What type of project are you verifying?")
-                        .items(&options)
-                        .default(0)
-                        .interact()?;
+                    // Unanswered, the prompt takes its default like --non-interactive
+                    let selection = prompt::ask(args.prompt_timeout, move || {
+                        Select::new()
+                            .with_prompt("Sources use Dojo, but no Dojo dependency was found. What type of project are you verifying?")
+                            .items(&options)
+                            .default(1)
+                            .interact()
+                    })?
+                    .unwrap_or(1);
 
                     match selection {
                         0 => Ok(ProjectType::Scarb),
@@ -172,7 +186,7 @@ pub fn validate_dojo_project(project: &Project) -> Result<(), CliError> {
     }
 
     // Validate project has Dojo dependencies
-    if project.detect_project_type()? != ProjectType::Dojo {
+    if project.detect_project_type()? == ProjectType::Scarb {
         return Err(CliError::InvalidProjectType {
             specified: "dojo".to_string(),
             detected: "scarb".to_string(),
@@ -186,6 +200,75 @@ pub fn validate_dojo_project(project: &Project) -> Result<(), CliError> {
     Ok(())
 }
 
+/// Whether a dependency called `name` from `source` is part of Dojo
+///
+/// `source` is the dependency's source ID in Scarb metadata, e.g.
+/// `git+https://github.com/dojoengine/dojo?tag=v1.0.0`, so Dojo packages
+/// imported under another name are recognized by their repository.
+#[must_use]
+pub fn is_dojo_dependency(name: &str, source: &str) -> bool {
+    name == "dojo"
+        || name.starts_with("dojo_")
+        || name.starts_with("dojo-")
+        || source.to_lowercase().contains("github.com/dojoengine/")
+}
+
+/// Whether `dir` holds the profiles or manifests of a Dojo project
+///
+/// Dojo profiles are `dojo_<profile>.toml` files next to `Scarb.toml`, and
+/// sozo writes `manifest_<profile>.json` (Dojo 1.x) or `manifests/<profile>/`
+/// (earlier versions) there as well.
+#[must_use]
+pub fn has_dojo_files(dir: &camino::Utf8Path) -> bool {
+    if dir.join("manifests").join("dev").is_dir() {
+        return true;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_file())
+        .any(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            (name.starts_with("dojo_") && name.ends_with(".toml"))
+                || (name.starts_with("manifest_") && name.ends_with(".json"))
+        })
+}
+
+/// Name of the workspace member a command operates on
+///
+/// `package` is the `--package` selection and must be a workspace member;
+/// without it the workspace's only member is used.
+///
+/// # Errors
+///
+/// Returns a `MissingContract` error listing the members if `package` is not
+/// a member, or if none is given in a workspace with several members.
+pub fn select_package(
+    metadata: &scarb_metadata::Metadata,
+    package: Option<&str>,
+) -> Result<String, CliError> {
+    let members: Vec<String> = metadata
+        .packages
+        .iter()
+        .filter(|pkg| metadata.workspace.members.contains(&pkg.id))
+        .map(|pkg| pkg.name.clone())
+        .collect();
+
+    match (package, members.as_slice()) {
+        (Some(name), _) if members.iter().any(|member| member == name) => Ok(name.to_string()),
+        (Some(name), _) => Err(errors::MissingContract::new(name.to_string(), members).into()),
+        (None, [only]) => Ok(only.clone()),
+        (None, _) => Err(errors::MissingContract::new(
+            "Workspace project detected - use --package argument".to_string(),
+            members,
+        )
+        .into()),
+    }
+}
+
 /// Clean a version string by removing semver constraint prefixes.
 ///
 /// Scarb.toml may contain version constraints like `"=1.7.1"`, `">=1.0.0"`, `"^2.0.0"`, etc.
@@ -722,4 +805,32 @@ dojo = ">=2.0.0"
         let result = extract_dojo_version(project_path, None);
         assert_eq!(result, Some("2.0.0".to_string()));
     }
+
+    #[test]
+    fn test_dojo_detection_signals() {
+        assert!(is_dojo_dependency(
+            "dojo_cairo_test",
+            "registry+https://scarbs.xyz/"
+        ));
+        assert!(is_dojo_dependency(
+            "world",
+            "git+https://github.com/dojoengine/dojo?tag=v1.0.0#1234"
+        ));
+        assert!(!is_dojo_dependency(
+            "openzeppelin",
+            "registry+https://scarbs.xyz/"
+        ));
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir = camino::Utf8Path::from_path(temp_dir.path()).unwrap();
+        fs::write(dir.join("Scarb.toml"), "[package]\nname = \"game\"\n").unwrap();
+        assert!(!has_dojo_files(dir));
+
+        fs::write(dir.join("dojo_dev.toml"), "[world]\n").unwrap();
+        assert!(has_dojo_files(dir));
+
+        fs::remove_file(dir.join("dojo_dev.toml")).unwrap();
+        fs::create_dir_all(dir.join("manifests").join("dev")).unwrap();
+        assert!(has_dojo_files(dir));
+    }
 }
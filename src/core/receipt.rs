@@ -0,0 +1,202 @@
+//! Class hash and network from deployment tool output
+//!
+//! Backs `voyager verify --from-receipt <FILE>`, so a pipeline can verify a
+//! class right after declaring or deploying it without copying values between
+//! tools. The file is the JSON printed by the deployment tooling:
+//!
+//! - declare transactions (`starkli transaction`, `starknet_getTransactionByHash`)
+//!   and `sncast --json declare`/`deploy` output, which carry a `class_hash`
+//! - receipts of deployments through the Universal Deployer Contract
+//!   (`starkli receipt` after `starkli deploy`), whose `ContractDeployed`
+//!   event holds the class hash
+//!
+//! Receipts do not say which chain they come from, so the network is only
+//! taken from the file if it has a `chain_id` or `network` field.
+
+use super::class_hash::ClassHash;
+use crate::{
+    cli::args::NetworkKind,
+    utils::{errors::CliError, voyager},
+};
+use camino::Utf8Path;
+use serde_json::Value;
+use std::fs;
+
+/// Address of the Universal Deployer Contract, the same on every network
+const UDC_ADDRESS: &str = "0x41a78e741e5af2fec34b695679bc6891742439f7afb8484ecd7766661ad02bf";
+
+/// Values to verify, as found in a receipt
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptInfo {
+    pub class_hash: ClassHash,
+    pub network: Option<NetworkKind>,
+}
+
+/// Read the receipt at `path`
+///
+/// # Errors
+///
+/// Returns [`CliError::InvalidReceipt`] if the file cannot be read, is not
+/// JSON, or does not contain exactly one class hash.
+pub fn load(path: &Utf8Path) -> Result<ReceiptInfo, CliError> {
+    let invalid = |reason: String| CliError::InvalidReceipt {
+        path: path.to_path_buf(),
+        reason,
+    };
+    let content = fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+    let value: Value =
+        serde_json::from_str(&content).map_err(|e| invalid(format!("not valid JSON: {e}")))?;
+    parse(&value).map_err(invalid)
+}
+
+/// Extract the class hash and network from receipt JSON
+///
+/// # Errors
+///
+/// Returns the reason if the JSON contains no class hash or several
+/// different ones.
+pub fn parse(receipt: &Value) -> Result<ReceiptInfo, String> {
+    let mut hashes: Vec<ClassHash> = vec![];
+    let mut candidates = vec![];
+    collect_strings(receipt, "class_hash", &mut candidates);
+    candidates.extend(udc_deployed_classes(receipt));
+    for candidate in candidates {
+        let hash =
+            ClassHash::new(candidate).map_err(|_| format!("'{candidate}' is not a class hash"))?;
+        if !hashes.iter().any(|known| known.same_value(&hash)) {
+            hashes.push(hash);
+        }
+    }
+
+    let class_hash = match hashes.len() {
+        0 => return Err("no class hash found".to_string()),
+        1 => hashes.remove(0),
+        _ => {
+            let listed = hashes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(format!("several class hashes found ({listed})"));
+        }
+    };
+
+    let mut chain_ids = vec![];
+    collect_strings(receipt, "chain_id", &mut chain_ids);
+    let mut names = vec![];
+    collect_strings(receipt, "network", &mut names);
+    let network = chain_ids
+        .into_iter()
+        .find_map(voyager::network_for_chain_id)
+        .or_else(|| {
+            names
+                .into_iter()
+                .find_map(|name| match name.to_lowercase().as_str() {
+                    "mainnet" => Some(NetworkKind::Mainnet),
+                    "sepolia" => Some(NetworkKind::Sepolia),
+                    "dev" => Some(NetworkKind::Dev),
+                    _ => None,
+                })
+        });
+
+    Ok(ReceiptInfo {
+        class_hash,
+        network,
+    })
+}
+
+/// All string values of `key` anywhere in `value`
+fn collect_strings<'a>(value: &'a Value, key: &str, found: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            for (name, child) in map {
+                match child {
+                    Value::String(text) if name == key => found.push(text),
+                    _ => collect_strings(child, key, found),
+                }
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect_strings(v, key, found)),
+        _ => {}
+    }
+}
+
+/// Class hashes of the contracts deployed through the UDC in a receipt
+///
+/// The `ContractDeployed` event data is `[address, deployer, unique,
+/// class_hash, calldata_len, calldata...]`.
+fn udc_deployed_classes(receipt: &Value) -> Vec<&str> {
+    let Ok(udc) = ClassHash::new(UDC_ADDRESS) else {
+        return vec![];
+    };
+    let from_udc = |event: &&Value| {
+        event
+            .get("from_address")
+            .and_then(Value::as_str)
+            .and_then(|address| ClassHash::new(address).ok())
+            .is_some_and(|address| address.same_value(&udc))
+    };
+
+    receipt
+        .get("events")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(from_udc)
+        .filter_map(|event| event.get("data")?.get(3)?.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CLASS_HASH: &str = "0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18";
+
+    #[test]
+    fn test_parse_declare_output() {
+        let sncast = json!({
+            "command": "declare",
+            "class_hash": CLASS_HASH,
+            "transaction_hash": "0x1",
+        });
+        let info = parse(&sncast).unwrap();
+        assert_eq!(info.class_hash.to_string(), CLASS_HASH);
+        assert_eq!(info.network, None);
+
+        let transaction = json!({
+            "transaction": { "type": "DECLARE", "class_hash": CLASS_HASH },
+            "chain_id": "0x534e5f5345504f4c4941",
+        });
+        assert_eq!(
+            parse(&transaction).unwrap().network,
+            Some(NetworkKind::Sepolia)
+        );
+    }
+
+    #[test]
+    fn test_parse_udc_deploy_receipt() {
+        let receipt = json!({
+            "type": "INVOKE",
+            "execution_status": "SUCCEEDED",
+            "events": [
+                { "from_address": "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7", "data": ["0x1", "0x2", "0x3", "0x4"] },
+                { "from_address": UDC_ADDRESS.replace("0x", "0x0"), "data": ["0xa", "0xb", "0x0", CLASS_HASH, "0x0"] },
+            ],
+        });
+        assert_eq!(parse(&receipt).unwrap().class_hash.to_string(), CLASS_HASH);
+
+        assert_eq!(
+            parse(&json!({ "events": [] })).unwrap_err(),
+            "no class hash found"
+        );
+        let several =
+            json!([{ "class_hash": "0x1" }, { "class_hash": "0x01" }, { "class_hash": "0x2" }]);
+        assert_eq!(
+            parse(&several).unwrap_err(),
+            "several class hashes found (0x1, 0x2)"
+        );
+    }
+}
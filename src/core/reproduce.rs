@@ -0,0 +1,207 @@
+//! Local reproduction of a deployed class
+//!
+//! Backs `voyager reproduce`: the package is built with the local toolchain,
+//! which has to be the Scarb version pinned in `.tool-versions` if any, the
+//! class hash of the compiled Sierra class is computed, and the result is
+//! compared with a class hash from the chain. A match shows that the sources
+//! compile to the deployed class without relying on the explorer's verdict.
+//!
+//! Class hashes are computed by [`compute`](super::class_hash::compute), so
+//! starkli has to be installed.
+
+use super::{artifacts, class_hash::ClassHash};
+use crate::utils::errors::CliError;
+use camino::{Utf8Path, Utf8PathBuf};
+use log::debug;
+use scarb_metadata::Metadata;
+use serde::Serialize;
+use std::{fs, process::Command};
+
+/// Scarb and Cairo versions used for a build
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Toolchain {
+    pub scarb: Option<String>,
+    pub cairo: Option<String>,
+}
+
+impl Toolchain {
+    /// The toolchain that produced `metadata`, i.e. the one `scarb build` uses
+    #[must_use]
+    pub fn local(metadata: &Metadata) -> Self {
+        Self {
+            scarb: Some(metadata.app_version_info.version.to_string()),
+            cairo: Some(metadata.app_version_info.cairo.version.to_string()),
+        }
+    }
+
+    /// Versions pinned in the `.tool-versions` file at `root`, if any
+    ///
+    /// asdf selects these automatically inside the project, so they are what
+    /// a fresh checkout builds with. Only Scarb can be pinned there; the Cairo
+    /// version follows from it.
+    #[must_use]
+    pub fn pinned(root: &Utf8Path) -> Option<Self> {
+        let path = root.join(".tool-versions");
+        let content = fs::read_to_string(&path)
+            .map_err(|e| debug!("No {path}: {e}"))
+            .ok()?;
+        let scarb = tool_version(&content, "scarb")?;
+        Some(Self {
+            scarb: Some(scarb),
+            cairo: None,
+        })
+    }
+
+    /// Check that this toolchain runs the Scarb version `pinned` pins, if any
+    ///
+    /// Another Scarb version may compile the same sources to another class,
+    /// which would only show up as a class hash mismatch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::ToolchainMismatch`] if the Scarb versions differ.
+    pub fn ensure_pinned(&self, pinned: Option<&Self>) -> Result<(), CliError> {
+        match pinned.and_then(|pinned| pinned.scarb.as_ref()) {
+            Some(pinned) if self.scarb.as_ref() != Some(pinned) => {
+                Err(CliError::ToolchainMismatch {
+                    pinned: pinned.clone(),
+                    active: self.scarb.clone().unwrap_or_else(|| "?".to_string()),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Version of `tool` in `.tool-versions` content
+fn tool_version(content: &str, tool: &str) -> Option<String> {
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .find_map(|line| {
+            let mut fields = line.split_whitespace();
+            (fields.next() == Some(tool))
+                .then(|| fields.next().map(ToString::to_string))
+                .flatten()
+        })
+}
+
+/// Outcome of a local reproduction
+#[derive(Debug, Clone, Serialize)]
+pub struct Reproduction {
+    pub package: String,
+    pub contract_name: String,
+    pub expected: String,
+    pub computed: String,
+    pub sierra_path: Utf8PathBuf,
+    pub local: Toolchain,
+    pub pinned: Option<Toolchain>,
+    /// Toolchain of the last verification of this class submitted from here
+    pub submitted: Option<Toolchain>,
+}
+
+impl Reproduction {
+    /// Whether the locally built class is the expected one
+    #[must_use]
+    pub fn matches(&self) -> bool {
+        match (
+            ClassHash::new(&self.expected),
+            ClassHash::new(&self.computed),
+        ) {
+            (Ok(expected), Ok(computed)) => expected.same_value(&computed),
+            _ => false,
+        }
+    }
+}
+
+/// Build `package` with `scarb build` in the workspace root
+///
+/// Build output goes to stderr, so stdout stays free for the report.
+///
+/// # Errors
+///
+/// Returns [`CliError::ToolchainMismatch`] if the active Scarb is not the
+/// one pinned in `.tool-versions`, or [`CliError::LocalBuildFailed`] if scarb
+/// cannot be run or the build fails.
+pub fn build_package(metadata: &Metadata, package: &str) -> Result<(), CliError> {
+    Toolchain::local(metadata)
+        .ensure_pinned(Toolchain::pinned(&metadata.workspace.root).as_ref())?;
+    let command = format!("scarb build --package {package}");
+    let status = Command::new("scarb")
+        .args(["build", "--package", package])
+        .current_dir(&metadata.workspace.root)
+        .stdout(std::io::stderr())
+        .status()
+        .map_err(|e| CliError::LocalBuildFailed {
+            command: command.clone(),
+            status: e.to_string(),
+        })?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(CliError::LocalBuildFailed {
+            command,
+            status: status.to_string(),
+        })
+    }
+}
+
+/// Path of the compiled Sierra class of `contract_name`
+///
+/// # Errors
+///
+/// Returns an error if the package has no build artifacts, does not contain
+/// the contract, or the contract was built without a Sierra class.
+pub fn sierra_class_path(
+    metadata: &Metadata,
+    package: &str,
+    contract_name: &str,
+) -> Result<Utf8PathBuf, CliError> {
+    let missing = || CliError::SierraClassMissing {
+        package: package.to_string(),
+        contract_name: contract_name.to_string(),
+    };
+
+    let built = artifacts::package_artifacts(metadata, package).ok_or_else(missing)?;
+    let artifact = artifacts::find_contract(&built, package, contract_name)?;
+    artifact.sierra_path.ok_or_else(missing)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_version() {
+        let content =
+            "# toolchain\nstarknet-foundry 0.35.1\nscarb 2.9.2 # pinned for verification\n";
+        assert_eq!(tool_version(content, "scarb").as_deref(), Some("2.9.2"));
+        assert_eq!(
+            tool_version(content, "starknet-foundry").as_deref(),
+            Some("0.35.1")
+        );
+        assert_eq!(tool_version(content, "sozo"), None);
+        assert_eq!(tool_version("scarb\n", "scarb"), None);
+    }
+
+    #[test]
+    fn test_ensure_pinned() {
+        let toolchain = |scarb: &str| Toolchain {
+            scarb: Some(scarb.to_string()),
+            cairo: None,
+        };
+        let active = toolchain("2.11.4");
+
+        assert!(active.ensure_pinned(None).is_ok());
+        assert!(active.ensure_pinned(Some(&toolchain("2.11.4"))).is_ok());
+        assert!(active.ensure_pinned(Some(&Toolchain::default())).is_ok());
+
+        let err = active.ensure_pinned(Some(&toolchain("2.9.2"))).unwrap_err();
+        assert_eq!(err.error_code(), "E090");
+        assert!(err
+            .to_string()
+            .contains("pins scarb 2.9.2, but scarb 2.11.4"));
+    }
+}
@@ -8,21 +8,59 @@
 //! - Polling and checking verification job status
 //! - Managing the verification lifecycle from submission to completion
 
-use super::project::{determine_project_type, extract_dojo_version, ProjectType};
+pub mod preflight;
+
+use super::analyze::{format_size, PayloadAnalysis};
+use super::artifacts::{self, ClassSummary};
+use super::class_hash::compute;
+use super::compat::{self, Compatibility};
+use super::project::{determine_project_type, extract_dojo_version, select_package, ProjectType};
 use crate::api::{
-    ApiClient, ApiClientError, FileInfo, ProjectMetadataInfo, VerificationError, VerificationJob,
+    ApiClient, ApiClientError, Capabilities, ClassVerification, ClassVerificationInfo, FileInfo,
+    FixedInterval, ProjectMetadataInfo, RpcClient, VerificationError, VerificationJob,
     VerifyJobStatus,
 };
-use crate::cli::args::VerifyArgs;
+use crate::cli::{
+    args::{DuplicatePolicy, NetworkKind, VerifyArgs},
+    config::ContractConfig,
+    prompt,
+};
 use crate::filesystem::{
-    collector::{log_verification_info, prepare_project_for_verification},
-    resolver::{collect_source_files, gather_packages_and_validate},
+    collector::{
+        collect_docs, collect_extra_sources, content_digest, estimate_payload_size, file_digest,
+        log_verification_info, package_contracts, package_defines_contract,
+        prepare_project_for_verification, source_fingerprint, sources_size, PayloadEstimate,
+        SizeLimits, DEFAULT_DOC_PATHS,
+    },
+    resolver::{
+        collect_source_files, dependency_closure, gather_packages_and_validate, is_package_glob,
+        members_matching,
+    },
+};
+use crate::output::{
+    live::LiveStatus,
+    sink::{self, Marker, Tone},
+    status::format_datetime,
+    summary::SubmissionSummary,
+};
+use crate::storage::{
+    batch::{BatchPlan, CompletedContract},
+    cache::{CachedSubmission, SubmissionCache},
+    history::{HistoryDb, PayloadStats, RetentionPolicy, VerificationRecord},
+    state::WorkspaceState,
 };
-use crate::storage::history::{HistoryDb, VerificationRecord};
-use crate::utils::{errors::CliError, license};
-use colored::Colorize;
+use crate::utils::{
+    errors::CliError, git, license, run_id, submitter::Submitter, suggestion::Suggestion, voyager,
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Utc};
+use dialoguer::Confirm;
+use itertools::Itertools;
 use log::{debug, info, warn};
 use scarb_metadata::PackageMetadata;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+use url::Url;
 
 /// Context information for a verification job
 ///
@@ -42,6 +80,37 @@ pub struct VerificationContext {
     pub file_infos: Vec<FileInfo>,
 }
 
+/// Outcome of a verification submission
+#[derive(Debug, Clone)]
+pub struct Submission {
+    /// The job ID assigned by the API, or `None` in dry-run mode or when the
+    /// submission was not confirmed
+    pub job_id: Option<String>,
+    /// Fingerprint of the submitted sources (see [`source_fingerprint`])
+    pub source_fingerprint: Option<String>,
+    /// Outcome on each target, in target order; empty without submitting
+    pub target_jobs: Vec<TargetJob>,
+}
+
+/// An explorer verification API a submission is sent to
+#[derive(Clone, Copy)]
+pub struct Target<'a> {
+    /// Name given to `--targets`
+    pub name: &'a str,
+    pub client: &'a ApiClient,
+    /// Whether to record the job in verification history, which refreshes
+    /// job statuses from Voyager
+    pub record_history: bool,
+}
+
+/// Job of one target of a `--targets` submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetJob {
+    pub target: String,
+    /// The job ID, or why the target did not accept the submission
+    pub outcome: Result<String, String>,
+}
+
 /// Submit a verification job
 ///
 /// This function orchestrates the entire verification submission process:
@@ -49,7 +118,7 @@ pub struct VerificationContext {
 /// 2. Gathers packages and validates the selection
 /// 3. Collects source files
 /// 4. Prepares the project structure for verification
-/// 5. Logs verification information
+/// 5. Shows a summary and asks to confirm it (see [`VerifyArgs::yes`])
 /// 6. Executes the verification (unless in dry-run mode)
 ///
 /// # Arguments
@@ -60,38 +129,103 @@ pub struct VerificationContext {
 ///
 /// # Returns
 ///
-/// Returns the job ID as a String if successful, or "dry-run" if in dry-run mode.
+/// Returns the submission outcome; its job ID is `None` in dry-run mode or
+/// when the submission was not confirmed.
 ///
 /// # Errors
 ///
 /// Returns a `CliError` if any step of the verification preparation or submission fails.
-#[allow(clippy::too_many_lines)]
+/// Without a contract name, see [`infer_contract_name`].
 pub fn submit(
     api_client: &ApiClient,
     args: &VerifyArgs,
     license_info: &license::LicenseInfo,
-) -> Result<String, CliError> {
-    // Define the DryRunPayload structure for dry-run mode
-    #[derive(serde::Serialize)]
-    struct DryRunPayload {
-        compiler_version: String,
-        scarb_version: String,
-        package_name: String,
-        name: String,
-        contract_file: String,
-        #[serde(rename = "contract-name")]
-        contract_name: String,
-        project_dir_path: String,
-        build_tool: String,
-        license: String,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        dojo_version: Option<String>,
-        file_count: usize,
-        file_list: Vec<String>,
+) -> Result<Submission, CliError> {
+    let target = Target {
+        name: "voyager",
+        client: api_client,
+        record_history: true,
+    };
+    submit_to_targets(&[target], args, license_info)
+}
+
+/// The contract to verify when `--contract-name` is omitted
+///
+/// Scans the sources of the selected package for `#[starknet::contract]`
+/// modules, like `voyager list-contracts`. If there is exactly one, it is the
+/// contract to verify.
+///
+/// # Errors
+///
+/// Returns [`CliError::NoTarget`] if the package defines no contract, or
+/// [`CliError::MultipleContracts`] listing them if it defines several. Also
+/// fails if no package can be selected.
+pub fn infer_contract_name(args: &VerifyArgs) -> Result<String, CliError> {
+    let metadata = args.path.load()?;
+    let package = select_package(metadata, args.package.as_deref())?;
+    let package_meta = metadata
+        .packages
+        .iter()
+        .find(|pkg| pkg.name == package && metadata.workspace.members.contains(&pkg.id))
+        .ok_or(CliError::NoTarget)?;
+
+    let mut contracts = package_contracts(package_meta)?;
+    match contracts.len() {
+        0 => Err(CliError::NoTarget),
+        1 => {
+            let contract = contracts.remove(0);
+            info!(
+                "Verifying {}, the only contract of package {package} (in {})",
+                contract.name, contract.file
+            );
+            Ok(contract.name)
+        }
+        _ => Err(CliError::MultipleContracts {
+            candidates: contracts
+                .iter()
+                .map(|contract| format!("{} ({})", contract.name, contract.file))
+                .collect(),
+        }),
     }
+}
 
+/// Submit the same sources to each of `targets`
+///
+/// The sources are collected, checked and confirmed once, against the first
+/// target, and then sent to every target in turn. A target that rejects the
+/// submission does not stop the others; its error is kept in
+/// [`Submission::target_jobs`]. The job ID of the submission is the first
+/// target's.
+///
+/// # Errors
+///
+/// Returns a `CliError` if any step of the preparation fails, or the error of
+/// the first target if no target accepted the submission.
+#[allow(clippy::too_many_lines)]
+pub fn submit_to_targets(
+    targets: &[Target<'_>],
+    args: &VerifyArgs,
+    license_info: &license::LicenseInfo,
+) -> Result<Submission, CliError> {
+    let api_client = targets
+        .first()
+        .ok_or_else(|| CliError::InternalError {
+            message: "at least one target to submit to".to_string(),
+        })?
+        .client;
     info!("🚀 Starting verification for project at: {}", args.path);
 
+    let inferred;
+    let args = if args.contract_name.is_none() {
+        inferred = VerifyArgs {
+            contract_name: Some(infer_contract_name(args)?),
+            ..args.clone()
+        };
+        &inferred
+    } else {
+        args
+    };
+
     // Validate required fields are present (they should be if not in wizard mode, or populated by wizard)
     let class_hash = args
         .class_hash
@@ -106,6 +240,9 @@ pub fn submit(
             message: "contract_name should be present - either from CLI args or wizard".to_string(),
         })?;
 
+    // Nothing is worth collecting for a class that was never declared
+    ensure_class_declared(args, class_hash)?;
+
     // Determine project type early in the process
     let project_type = determine_project_type(args)?;
 
@@ -116,101 +253,929 @@ pub fn submit(
         ProjectType::Auto => unreachable!("Auto should be resolved by now"),
     }
 
-    let metadata = args.path.metadata();
+    let metadata = args.path.load()?;
 
-    // Determine test_files setting - default to true for Dojo projects
-    let include_test_files = match project_type {
-        ProjectType::Dojo => {
-            if !args.test_files {
-                info!("🧪 Including test files by default for Dojo project");
-            }
-            true
-        }
-        _ => args.test_files,
-    };
+    api_client.progress().files_collection_started();
+    let (file_infos, package_meta, contract_file, project_dir_path) =
+        collect_project_files(args, project_type)?;
+
+    let CheckedUpload {
+        capabilities,
+        limits,
+        estimate,
+        payload_size,
+    } = check_upload(
+        api_client,
+        args,
+        &Upload {
+            file_infos: &file_infos,
+            license_info,
+            project_dir_path: &project_dir_path,
+            package_name: &package_meta.name,
+            project_type,
+        },
+    )?;
+    api_client
+        .progress()
+        .files_collected(file_infos.len(), payload_size);
+
+    if args.compare_class_hash {
+        compare_with_local_build(
+            metadata,
+            &package_meta.name,
+            contract_name,
+            class_hash,
+            project_type,
+        );
+    }
 
-    // Gather packages and sources
-    let packages = gather_packages_and_validate(metadata, args)?;
-    let sources = collect_source_files(metadata, &packages, include_test_files)?;
+    // Catch contract name mismatches against the local build before uploading
+    let class_summary = validate_against_artifacts(metadata, &package_meta.name, contract_name)?;
+    if let Some(summary) = &class_summary {
+        info!(
+            "Built class has {} entry point(s) and {} ABI item(s)",
+            summary.entry_points(),
+            summary.abi_items
+        );
+    }
 
-    // Prepare project structure
-    let (file_infos, package_meta, contract_file, project_dir_path) =
-        prepare_project_for_verification(args, metadata, &packages, &sources)?;
+    warn_unsupported_toolchain(
+        &metadata.app_version_info.cairo.version,
+        &metadata.app_version_info.version,
+        class_summary
+            .as_ref()
+            .and_then(|summary| summary.sierra_version.as_ref()),
+        capabilities.as_ref(),
+    );
 
     // Log verification info
     log_verification_info(args, metadata, &file_infos, &contract_file, license_info);
 
+    let fingerprint = source_fingerprint(&file_infos)
+        .inspect(|fingerprint| info!("Source fingerprint: {fingerprint}"))
+        .map_err(|e| warn!("Failed to fingerprint sources: {e}"))
+        .ok();
+
+    let context = VerificationContext {
+        project_type,
+        project_dir_path,
+        contract_file,
+        package_meta,
+        file_infos,
+    };
+
+    // An identical request need not be uploaded again; --force resubmits it
+    let digest = match targets {
+        [target] if !args.dry_run => {
+            project_metadata_info(args, &context)
+                .ok()
+                .and_then(|project_meta| {
+                    request_digest(
+                        target.client,
+                        args,
+                        project_meta,
+                        license_info,
+                        &context.file_infos,
+                    )
+                })
+        }
+        _ => None,
+    };
+    if let Some(submission) = reuse_cached_submission(
+        api_client,
+        args,
+        targets[0].name,
+        digest.as_deref(),
+        fingerprint.as_deref(),
+    ) {
+        return Ok(submission);
+    }
+
+    if !args.dry_run {
+        guard_duplicate_submission(api_client, args, class_hash)?;
+    }
+
+    let summary = SubmissionSummary {
+        contract_name: contract_name.clone(),
+        class_hash: class_hash.to_string(),
+        package: context.package_meta.name.clone(),
+        contract_file: context.contract_file.clone(),
+        files: context.file_infos.len(),
+        payload_size,
+        cairo_version: metadata.app_version_info.cairo.version.to_string(),
+        scarb_version: metadata.app_version_info.version.to_string(),
+        build_tool: if project_type == ProjectType::Dojo {
+            "sozo"
+        } else {
+            "scarb"
+        },
+        license: license_info.display_string().to_string(),
+        network: if targets.len() > 1 {
+            format!(
+                "{} on {}",
+                network_label(args),
+                targets.iter().map(|target| target.name).join(", ")
+            )
+        } else {
+            format!("{} ({})", network_label(args), args.network_url.url()?)
+        },
+        fingerprint: fingerprint.clone(),
+        note: args.note.clone(),
+    };
+    sink::line(format!("\n{}", summary.render()));
+
     // Execute verification unless dry run is requested
     if !args.dry_run {
-        let context = VerificationContext {
-            project_type,
-            project_dir_path,
-            contract_file,
-            package_meta,
-            file_infos,
-        };
-        return execute_verification(api_client, args, context, license_info);
+        if !confirm_submission(args, contract_name)? {
+            sink::line("No verification was submitted.");
+            return Ok(Submission {
+                job_id: None,
+                source_fingerprint: fingerprint,
+                target_jobs: Vec::new(),
+            });
+        }
+        let target_jobs = submit_to_each(targets, args, &context, license_info)?;
+        if let (
+            Some(digest),
+            [TargetJob {
+                outcome: Ok(job_id),
+                ..
+            }],
+        ) = (&digest, target_jobs.as_slice())
+        {
+            cache_submission(args, job_id, digest);
+        }
+        return Ok(Submission {
+            job_id: target_jobs.first().and_then(|job| job.outcome.clone().ok()),
+            source_fingerprint: fingerprint,
+            target_jobs,
+        });
     }
 
     // Dry run: Build and display the full payload that would be sent
-    println!("\n✅ Dry run completed successfully!");
-    println!("Collected {} file(s) for verification", file_infos.len());
-    println!("Contract: {contract_name}");
-    println!("Class hash: {class_hash}");
+    sink::line(format!(
+        "\n{} Dry run completed successfully!",
+        sink::marker(Marker::Done)
+    ));
+    if let Some(summary) = &class_summary {
+        sink::line(format!(
+            "Entry points: {} ({} external, {} L1 handler, {} constructor)",
+            summary.entry_points(),
+            summary.external,
+            summary.l1_handler,
+            summary.constructor
+        ));
+    }
 
-    // Build the complete payload
-    let cairo_version = metadata.app_version_info.cairo.version.clone();
-    let scarb_version = metadata.app_version_info.version.clone();
+    let payload = PayloadPreview::new(
+        args,
+        project_type,
+        &context.package_meta,
+        contract_name,
+        context.contract_file,
+        context.project_dir_path,
+        &context.file_infos,
+        license_info,
+    )?;
 
-    // Extract Dojo version if it's a Dojo project (same logic as execute_verification)
-    let dojo_version = if project_type == ProjectType::Dojo {
-        let workspace_root = args.path.root_dir().to_string();
-        let package_root = package_meta.root.to_string();
-        let package_root_opt = if package_root == workspace_root {
-            None
-        } else {
-            Some(package_root.as_str())
-        };
-        extract_dojo_version(&workspace_root, package_root_opt)
-    } else {
+    // Display the payload as pretty-printed JSON
+    sink::line(format!(
+        "\n{}",
+        sink::paint("=== API Request Payload ===", Tone::Heading)
+    ));
+    match serde_json::to_string_pretty(&payload) {
+        Ok(json) => sink::line(json),
+        Err(e) => warn!("Failed to serialize payload to JSON: {e}"),
+    }
+    sink::line(format!(
+        "{}\n",
+        sink::paint("=== End Payload ===", Tone::Heading)
+    ));
+
+    sink::line(sink::paint("=== Request Size ===", Tone::Heading));
+    for (name, size) in &estimate.files {
+        sink::line(format!("{:>10}  {name}", format_size(*size)));
+    }
+    match estimate.compressed {
+        Some(compressed) => sink::line(format!(
+            "Total: {}, compressed to {} of the {} limit, metadata included",
+            format_size(estimate.total),
+            format_size(compressed),
+            format_size(limits.max_payload_size)
+        )),
+        None => sink::line(format!(
+            "Total: {} of the {} limit, metadata included",
+            format_size(estimate.total),
+            format_size(limits.max_payload_size)
+        )),
+    }
+
+    sink::line(format!(
+        "\n{} No verification was submitted due to --dry-run flag",
+        sink::marker(Marker::Warning)
+    ));
+    sink::line("Remove --dry-run to submit for actual verification.\n");
+    Ok(Submission {
+        job_id: None,
+        source_fingerprint: fingerprint,
+        target_jobs: Vec::new(),
+    })
+}
+
+/// Files about to be uploaded, as the checks before the upload see them
+pub(super) struct Upload<'a> {
+    pub file_infos: &'a [FileInfo],
+    pub license_info: &'a license::LicenseInfo,
+    /// Directory of the package, relative to the files
+    pub project_dir_path: &'a str,
+    pub package_name: &'a str,
+    pub project_type: ProjectType,
+}
+
+/// What [`check_upload`] found out about an upload
+pub(super) struct CheckedUpload {
+    /// Limits and toolchain ranges the API reports; never fetched in dry-run
+    /// mode
+    pub capabilities: Option<Capabilities>,
+    /// Size limits in effect
+    pub limits: SizeLimits,
+    pub estimate: PayloadEstimate,
+    /// Total size of the files on disk, in bytes
+    pub payload_size: u64,
+}
+
+/// Checks every submission passes before it is uploaded, with or without a
+/// project
+///
+/// Enforces the size limits, the estimated request size against the
+/// payload limit, `--require-license-headers` and `--preflight`.
+///
+/// # Errors
+///
+/// Returns the error of the first check that fails.
+pub(super) fn check_upload(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    upload: &Upload<'_>,
+) -> Result<CheckedUpload, CliError> {
+    // Fail before uploading rather than have the API reject the payload
+    let capabilities = if args.dry_run {
         None
+    } else {
+        server_capabilities(api_client)
     };
-
-    // Prepare license value (same logic as in API client)
-    let license_str = license_info.display_string().to_string();
-    let license_value = if license_str == "MIT" {
-        "MIT".to_string()
+    let limits = capabilities
+        .as_ref()
+        .map_or(args.size_limits, |capabilities| {
+            args.size_limits.with_server(capabilities)
+        });
+    // Compressed requests are held to the payload limit once compressed
+    if args.compress {
+        limits.check_files(upload.file_infos)?;
     } else {
-        license_str
+        limits.check(upload.file_infos)?;
+    }
+    // Escaping and the payload transforms change what is actually sent
+    let payload = api_client.payload_files(upload.file_infos)?;
+    let estimate = estimate_payload_size(&payload, args.compress);
+    debug!(
+        "Estimated request size: {} bytes ({:?} compressed)",
+        estimate.total, estimate.compressed
+    );
+    estimate.check(limits.max_payload_size)?;
+    ensure_license_headers(args, upload.license_info, upload.file_infos)?;
+    if args.preflight {
+        preflight::build(
+            &payload,
+            upload.project_dir_path,
+            upload.package_name,
+            upload.project_type,
+        )?;
+    }
+    let payload_size = upload
+        .file_infos
+        .iter()
+        .filter_map(|file| std::fs::metadata(&file.path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    Ok(CheckedUpload {
+        capabilities,
+        limits,
+        estimate,
+        payload_size,
+    })
+}
+
+/// Confirm with `--rpc-url`, if given, that the class was declared
+///
+/// # Errors
+///
+/// Returns the error of [`ensure_declared`].
+pub(super) fn ensure_class_declared(
+    args: &VerifyArgs,
+    class_hash: &super::class_hash::ClassHash,
+) -> Result<(), CliError> {
+    let Some(rpc_url) = &args.rpc_url else {
+        return Ok(());
     };
+    let network = args.network.clone().or_else(|| {
+        args.network_url
+            .url()
+            .ok()
+            .and_then(voyager::network_for_api_url)
+    });
+    ensure_declared(rpc_url, class_hash, network.as_ref())
+}
+
+/// Digest of the request body `client` would send for `project_meta` and
+/// `files`
+///
+/// The body holds the files after the payload transforms as well as the
+/// license, toolchain versions, package, contract file and build tool, so two
+/// submissions share a digest only if they would send the same request.
+pub(super) fn request_digest(
+    client: &ApiClient,
+    args: &VerifyArgs,
+    project_meta: ProjectMetadataInfo,
+    license_info: &license::LicenseInfo,
+    files: &[FileInfo],
+) -> Option<String> {
+    let body = client
+        .verification_request_body(
+            args.class_hash.as_ref()?,
+            Some(license_info.display_string().to_string()),
+            args.contract_name.as_deref()?,
+            project_meta,
+            files,
+        )
+        .map_err(|e| debug!("Cannot build the request body to look up cached submissions: {e}"))
+        .ok()?;
+    Some(content_digest(&body))
+}
+
+/// The submission of the job an identical request to `target` already
+/// created, unless `--force` is given
+///
+/// The job is announced, along with how to submit anyway.
+pub(super) fn reuse_cached_submission(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    target: &str,
+    digest: Option<&str>,
+    fingerprint: Option<&str>,
+) -> Option<Submission> {
+    let cached = digest
+        .filter(|_| !args.force)
+        .and_then(|digest| cached_submission(api_client, args, digest))?;
+    let submitted_at =
+        DateTime::from_timestamp(cached.submitted_at, 0).map_or_else(String::new, |at| {
+            format!(
+                " on {}",
+                format_datetime(at, args.timezone.unwrap_or_default())
+            )
+        });
+    sink::line(format!(
+        "An identical request was already submitted as job {}{submitted_at}; not uploading it again.",
+        sink::paint(&cached.job_id, Tone::Success)
+    ));
+    sink::line("Use --force to submit them anyway.");
+    Some(Submission {
+        job_id: Some(cached.job_id.clone()),
+        source_fingerprint: fingerprint.map(ToString::to_string),
+        target_jobs: vec![TargetJob {
+            target: target.to_string(),
+            outcome: Ok(cached.job_id),
+        }],
+    })
+}
+
+/// The job already submitted with the same request, unless it failed
+///
+/// The cached job is looked up on the API, so requests whose job failed or is
+/// no longer known are submitted again.
+fn cached_submission(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    digest: &str,
+) -> Option<CachedSubmission> {
+    let cached = SubmissionCache::open()?.get(
+        args.network_url.url().ok()?,
+        args.class_hash.as_ref()?,
+        digest,
+    )?;
+    match api_client.get_job_status_raw(&cached.job_id) {
+        Ok(job)
+            if !matches!(
+                job.status(),
+                VerifyJobStatus::Fail | VerifyJobStatus::CompileFailed
+            ) =>
+        {
+            Some(cached)
+        }
+        Ok(_) => {
+            debug!("Cached job {} failed, submitting again", cached.job_id);
+            None
+        }
+        Err(e) => {
+            debug!("Cached job {} cannot be reused: {e}", cached.job_id);
+            None
+        }
+    }
+}
+
+/// Warn about, or refuse, submitting a class whose last job is still pending
+///
+/// Users re-running a slow first attempt would otherwise queue the same
+/// verification twice. History only knows the status it last saw, so the job
+/// is looked up on the API; a job that finished since, or that the API no
+/// longer knows, is no duplicate.
+///
+/// # Errors
+///
+/// Returns [`CliError::DuplicateSubmission`] with `--on-duplicate abort`.
+pub(super) fn guard_duplicate_submission(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    class_hash: &super::class_hash::ClassHash,
+) -> Result<(), CliError> {
+    let policy = args.on_duplicate.unwrap_or_default();
+    if policy == DuplicatePolicy::Allow || args.force {
+        return Ok(());
+    }
+
+    let Some(record) = HistoryDb::open_read_only()
+        .and_then(|db| db.latest_job_for_class_hash(class_hash.as_ref(), Some(network_label(args))))
+        .map_err(|e| debug!("Cannot look for pending jobs in history: {e}"))
+        .ok()
+        .flatten()
+    else {
+        return Ok(());
+    };
+    let pending = [
+        VerifyJobStatus::Submitted,
+        VerifyJobStatus::Compiled,
+        VerifyJobStatus::Processing,
+    ];
+    if !pending
+        .iter()
+        .any(|status| status.to_string() == record.status)
+    {
+        return Ok(());
+    }
+    let job = match api_client.get_job_status_raw(&record.job_id) {
+        Ok(job) if !job.is_completed() => job,
+        Ok(_) => return Ok(()),
+        Err(e) => {
+            debug!("Cannot look up pending job {}: {e}", record.job_id);
+            return Ok(());
+        }
+    };
+
+    let duplicate = CliError::DuplicateSubmission {
+        class_hash: class_hash.to_string(),
+        job_id: record.job_id,
+        status: job.status().to_string(),
+    };
+    if policy == DuplicatePolicy::Abort {
+        return Err(duplicate);
+    }
+    sink::warning(format!(
+        "Warning: {}",
+        duplicate.to_string().lines().next().unwrap_or_default()
+    ));
+    sink::warning("Submitting anyway; use --on-duplicate abort to refuse duplicate submissions.");
+    Ok(())
+}
+
+/// Remember the job of the submitted request for [`cached_submission`]
+pub(super) fn cache_submission(args: &VerifyArgs, job_id: &str, digest: &str) {
+    let (Some(cache), Ok(api_url), Some(class_hash)) = (
+        SubmissionCache::open(),
+        args.network_url.url(),
+        args.class_hash.as_ref(),
+    ) else {
+        return;
+    };
+    let submission = CachedSubmission {
+        job_id: job_id.to_string(),
+        request_digest: digest.to_string(),
+        submitted_at: Utc::now().timestamp(),
+    };
+    if let Err(e) = cache.put(api_url, class_hash, &submission) {
+        debug!("Failed to cache submission: {e}");
+    }
+}
+
+/// Whether to go ahead with the submission shown in the summary
+///
+/// Sessions that cannot be asked (`--non-interactive`, or no terminal) submit
+/// without confirmation; a prompt left unanswered past `--prompt-timeout` does
+/// not submit.
+pub(super) fn confirm_submission(args: &VerifyArgs, contract_name: &str) -> Result<bool, CliError> {
+    if args.yes || !args.is_interactive() {
+        return Ok(true);
+    }
+    let question = format!("Submit {contract_name} for verification?");
+    Ok(prompt::ask(args.prompt_timeout, move || {
+        Confirm::new()
+            .with_prompt(question)
+            .default(true)
+            .interact()
+    })?
+    .unwrap_or(false))
+}
+
+/// Warns when the class built locally has another class hash than the one
+/// being verified
+///
+/// The remote compiler would most likely produce the same class as the local
+/// build, so verification is then bound to fail. The comparison never stops
+/// the submission.
+fn compare_with_local_build(
+    metadata: &scarb_metadata::Metadata,
+    package: &str,
+    contract_name: &str,
+    class_hash: &super::class_hash::ClassHash,
+    project_type: ProjectType,
+) {
+    if project_type == ProjectType::Dojo {
+        sink::warning(
+            "Warning: --compare-class-hash builds with scarb and is skipped for Dojo projects",
+        );
+        return;
+    }
+
+    sink::warning(format!("Building {package} to compare its class hash ..."));
+    match compute::local_class_hash(metadata, package, contract_name) {
+        Ok(computed) if computed.same_value(class_hash) => {
+            sink::warning(format!(
+                "{} The local build of {contract_name} has class hash {class_hash}",
+                sink::marker(Marker::Success)
+            ));
+        }
+        Ok(computed) => {
+            sink::warning(format!(
+                "Warning: The local build of {contract_name} has class hash {computed}, not {class_hash}."
+            ));
+            sink::warning("The sources or toolchain differ from the ones the class was declared with, so verification will most likely fail.");
+            sink::warning("Run 'voyager reproduce' to compare toolchains.");
+        }
+        Err(e) => {
+            let reason = e.to_string();
+            sink::warning(format!(
+                "Warning: Could not compare the class hash with a local build: {}",
+                reason.lines().next().unwrap_or_default()
+            ));
+        }
+    }
+}
+
+/// Limits and toolchain ranges the API reports, when it reports any
+fn server_capabilities(api_client: &ApiClient) -> Option<Capabilities> {
+    match api_client.get_capabilities() {
+        Ok(capabilities) => {
+            debug!("API capabilities: {capabilities:?}");
+            capabilities
+        }
+        Err(e) => {
+            debug!("Failed to fetch API capabilities, using the configured limits: {e}");
+            None
+        }
+    }
+}
+
+/// Warns when the toolchain is outside the versions the API builds with
+///
+/// The API accepts such a submission and fails it only once the remote build
+/// ran, so the warning names the error the job would end with. It never stops
+/// the submission, as the embedded table may be older than the server.
+pub(super) fn warn_unsupported_toolchain(
+    cairo_version: &semver::Version,
+    scarb_version: &semver::Version,
+    sierra_version: Option<&semver::Version>,
+    capabilities: Option<&Capabilities>,
+) {
+    let compatibility = capabilities.map_or_else(Compatibility::default, |capabilities| {
+        Compatibility::default().with_server(capabilities)
+    });
+    let unsupported = compatibility.check(cairo_version, scarb_version, sierra_version);
+    if unsupported.is_empty() {
+        return;
+    }
+
+    for incompatibility in &unsupported {
+        sink::warning(format!("Warning: {incompatibility}"));
+    }
+    sink::warning(format!(
+        "The job will most likely fail with [{}] Compilation failed. Build with a supported toolchain, or run 'voyager explain {}'.",
+        compat::FAILURE_CODE,
+        compat::FAILURE_CODE
+    ));
+}
+
+/// Enforces the `--require-license-headers` policy before anything is submitted
+///
+/// # Errors
+///
+/// Returns an error listing the fixes if a Cairo file lacks the SPDX header of
+/// the submitted license.
+fn ensure_license_headers(
+    args: &VerifyArgs,
+    license_info: &license::LicenseInfo,
+    files: &[FileInfo],
+) -> Result<(), CliError> {
+    if !args.require_license_headers {
+        return Ok(());
+    }
+    if license_info.is_none() {
+        warn!("No license to check the SPDX headers of the sources against");
+        return Ok(());
+    }
+
+    let license = license_info.display_string();
+    let mismatches = license::check_license_headers(files, license);
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::LicenseHeaderMismatch {
+        license: license.to_string(),
+        fixes: mismatches
+            .iter()
+            .map(|mismatch| mismatch.fix(license))
+            .collect(),
+    })
+}
+
+/// The request payload that would be sent, without file contents
+///
+/// Printed by `--dry-run --format json` and read back by `voyager diff-dryrun`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PayloadPreview {
+    pub compiler_version: String,
+    pub scarb_version: String,
+    pub package_name: String,
+    pub name: String,
+    pub contract_file: String,
+    #[serde(rename = "contract-name")]
+    pub contract_name: String,
+    pub project_dir_path: String,
+    pub build_tool: String,
+    pub license: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dojo_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub file_count: usize,
+    pub file_list: Vec<String>,
+    /// Content digest of each file, by name; empty in older previews
+    #[serde(default)]
+    pub file_hashes: std::collections::BTreeMap<String, String>,
+}
+
+impl PayloadPreview {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        args: &VerifyArgs,
+        project_type: ProjectType,
+        package_meta: &PackageMetadata,
+        contract_name: &str,
+        contract_file: String,
+        project_dir_path: String,
+        file_infos: &[FileInfo],
+        license_info: &license::LicenseInfo,
+    ) -> Result<Self, CliError> {
+        let metadata = args.path.load()?;
+
+        // Extract Dojo version if it's a Dojo project (same logic as execute_verification)
+        let dojo_version = if project_type == ProjectType::Dojo {
+            let workspace_root = args.path.root_dir()?.to_string();
+            let package_root = package_meta.root.to_string();
+            let package_root_opt = if package_root == workspace_root {
+                None
+            } else {
+                Some(package_root.as_str())
+            };
+            extract_dojo_version(&workspace_root, package_root_opt)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            compiler_version: metadata.app_version_info.cairo.version.to_string(),
+            scarb_version: metadata.app_version_info.version.to_string(),
+            package_name: package_meta.name.clone(),
+            name: contract_name.to_string(),
+            contract_file: contract_file.clone(),
+            contract_name: contract_file,
+            project_dir_path,
+            build_tool: project_type.to_string(),
+            license: license_info.display_string().to_string(),
+            dojo_version,
+            note: args.note.clone(),
+            file_count: file_infos.len(),
+            file_list: file_infos.iter().map(|f| f.name.clone()).collect(),
+            file_hashes: file_infos
+                .iter()
+                .filter_map(|f| Some((f.name.clone(), file_digest(&f.path).ok()?)))
+                .collect(),
+        })
+    }
+}
+
+/// Build the payload preview for `args` without printing or submitting anything
+///
+/// # Errors
+///
+/// Returns a `CliError` if the project files cannot be collected.
+pub fn preview_payload(
+    args: &VerifyArgs,
+    license_info: &license::LicenseInfo,
+) -> Result<PayloadPreview, CliError> {
+    collect_payload(args, license_info).map(|(preview, _)| preview)
+}
 
-    // Build the request payload structure (without file contents for brevity)
-    let payload = DryRunPayload {
-        compiler_version: cairo_version.to_string(),
-        scarb_version: scarb_version.to_string(),
-        package_name: package_meta.name,
-        name: contract_name.clone(),
-        contract_file: contract_file.clone(),
-        contract_name: contract_file,
+/// Collect the files `args` would submit, along with their payload preview
+///
+/// # Errors
+///
+/// Returns a `CliError` if the project files cannot be collected.
+pub fn collect_payload(
+    args: &VerifyArgs,
+    license_info: &license::LicenseInfo,
+) -> Result<(PayloadPreview, Vec<FileInfo>), CliError> {
+    let contract_name = args
+        .contract_name
+        .as_ref()
+        .ok_or_else(|| CliError::InternalError {
+            message: "contract_name should be present".to_string(),
+        })?;
+    let project_type = determine_project_type(args)?;
+    let (file_infos, package_meta, contract_file, project_dir_path) =
+        collect_project_files(args, project_type)?;
+
+    let preview = PayloadPreview::new(
+        args,
+        project_type,
+        &package_meta,
+        contract_name,
+        contract_file,
         project_dir_path,
-        build_tool: project_type.to_string(),
-        license: license_value,
-        dojo_version,
-        file_count: file_infos.len(),
-        file_list: file_infos.iter().map(|f| f.name.clone()).collect(),
+        &file_infos,
+        license_info,
+    )?;
+    Ok((preview, file_infos))
+}
+
+/// Collect the files to submit and resolve the contract file and project directory
+fn collect_project_files(
+    args: &VerifyArgs,
+    project_type: ProjectType,
+) -> Result<(Vec<FileInfo>, PackageMetadata, String, String), CliError> {
+    let metadata = args.path.load()?;
+
+    // Determine test_files setting - default to true for Dojo projects
+    let include_test_files = match project_type {
+        ProjectType::Dojo => {
+            if !args.test_files {
+                info!("🧪 Including test files by default for Dojo project");
+            }
+            true
+        }
+        _ => args.test_files,
     };
 
-    // Display the payload as pretty-printed JSON
-    println!("\n{}", "=== API Request Payload ===".bright_cyan().bold());
-    match serde_json::to_string_pretty(&payload) {
-        Ok(json) => println!("{json}"),
-        Err(e) => warn!("Failed to serialize payload to JSON: {e}"),
+    let (packages, mut sources) = collect_sources(
+        metadata,
+        args.package.as_deref(),
+        include_test_files,
+        args.size_limits.max_payload_size,
+    )?;
+    add_extra_sources(&mut sources, &args.extra_sources, args.path.root_dir()?)?;
+    if args.include_docs {
+        add_docs(&mut sources, metadata, &packages, &args.doc_paths);
+    }
+
+    // Prepare project structure
+    prepare_project_for_verification(args, metadata, &packages, &sources)
+}
+
+/// Gather the packages to upload and collect their source files
+///
+/// Uploading every workspace member can exceed `max_payload_size`; if it
+/// would, only the `package` being verified and the members it depends on are
+/// kept, since that is all it needs to compile, and the others are dropped
+/// from the workspace manifest when the request is built. Without a
+/// `package`, the sources are kept whole and the excess is reported.
+///
+/// # Errors
+///
+/// Returns a `CliError` if the package selection is invalid or the sources
+/// cannot be read.
+pub fn collect_sources(
+    metadata: &scarb_metadata::Metadata,
+    package: Option<&str>,
+    include_test_files: bool,
+    max_payload_size: u64,
+) -> Result<(Vec<PackageMetadata>, Vec<Utf8PathBuf>), CliError> {
+    let mut packages = gather_packages_and_validate(metadata, package)?;
+    let mut sources = collect_source_files(metadata, &packages, include_test_files)?;
+
+    let size = sources_size(&sources);
+    if size <= max_payload_size {
+        return Ok((packages, sources));
+    }
+    sink::warning(format!(
+        "Warning: Sources of all packages ({size} bytes) exceed the {max_payload_size} byte payload limit."
+    ));
+    let Some(target) = package else {
+        sink::warning(
+            "Pass --package to only upload the package being verified and its dependencies.",
+        );
+        return Ok((packages, sources));
+    };
+    let required = dependency_closure(&packages, target);
+    if required.len() < packages.len() {
+        let pruned = packages
+            .iter()
+            .filter(|package| !required.contains(package))
+            .map(|package| package.name.as_str())
+            .join(", ");
+        sink::warning(format!(
+            "Only uploading {target} and its dependencies; pruned packages not needed by it: {pruned}"
+        ));
+        packages = required;
+        sources = collect_source_files(metadata, &packages, include_test_files)?;
+        debug!("Pruned sources total {} bytes", sources_size(&sources));
+    }
+
+    Ok((packages, sources))
+}
+
+/// Add the files of the `--extra-source` directories not collected already
+///
+/// # Errors
+///
+/// Returns a `CliError` if a directory cannot be read or holds a file of an
+/// unsupported type.
+pub fn add_extra_sources(
+    sources: &mut Vec<Utf8PathBuf>,
+    dirs: &[Utf8PathBuf],
+    project_root: &Utf8Path,
+) -> Result<(), CliError> {
+    for path in collect_extra_sources(dirs, project_root)? {
+        if !sources.contains(&path) {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Add the documentation of the workspace and `packages` for `--include-docs`
+///
+/// `doc_paths` are the eligible globs; [`DEFAULT_DOC_PATHS`] if empty.
+pub fn add_docs(
+    sources: &mut Vec<Utf8PathBuf>,
+    metadata: &scarb_metadata::Metadata,
+    packages: &[PackageMetadata],
+    doc_paths: &[String],
+) {
+    let patterns = if doc_paths.is_empty() {
+        DEFAULT_DOC_PATHS.map(String::from).to_vec()
+    } else {
+        doc_paths.to_vec()
+    };
+    let mut roots = vec![metadata.workspace.root.as_path()];
+    roots.extend(packages.iter().map(|package| package.root.as_path()));
+
+    let docs = collect_docs(&roots, &patterns);
+    info!("📚 Including {} documentation file(s)", docs.len());
+    for path in docs {
+        if !sources.contains(&path) {
+            sources.push(path);
+        }
     }
-    println!("{}\n", "=== End Payload ===".bright_cyan().bold());
+}
 
-    println!("\n⚠️  No verification was submitted due to --dry-run flag");
-    println!("Remove --dry-run to submit for actual verification.\n");
-    Ok("dry-run".to_string())
+/// Check `contract_name` against the package's build artifacts, if it was built
+///
+/// Returns the entry point counts of the built class when available.
+fn validate_against_artifacts(
+    metadata: &scarb_metadata::Metadata,
+    package: &str,
+    contract_name: &str,
+) -> Result<Option<ClassSummary>, CliError> {
+    let Some(built) = artifacts::package_artifacts(metadata, package) else {
+        debug!("No build artifacts for {package}, skipping contract name validation");
+        return Ok(None);
+    };
+
+    let artifact = artifacts::find_contract(&built, package, contract_name)?;
+    debug!(
+        "Found {contract_name} in build artifacts as {}",
+        artifact.module_path
+    );
+    Ok(artifact.class_summary())
 }
 
 /// Execute the verification request
@@ -227,6 +1192,7 @@ pub fn submit(
 /// * `args` - Command-line arguments containing verification parameters
 /// * `context` - The verification context with all prepared data
 /// * `license_info` - License information for the contract
+/// * `record_history` - Whether to record the job in verification history
 ///
 /// # Returns
 ///
@@ -239,8 +1205,9 @@ pub fn submit(
 pub fn execute_verification(
     api_client: &ApiClient,
     args: &VerifyArgs,
-    context: VerificationContext,
+    context: &VerificationContext,
     license_info: &license::LicenseInfo,
+    record_history: bool,
 ) -> Result<String, CliError> {
     // Extract required fields
     let class_hash = args
@@ -256,14 +1223,68 @@ pub fn execute_verification(
             message: "contract_name should be present".to_string(),
         })?;
 
-    let metadata = args.path.metadata();
+    let project_meta = project_metadata_info(args, context)?;
+    // Save version strings for history tracking before they are moved
+    let cairo_version_str = project_meta.cairo_version.to_string();
+    let scarb_version_str = project_meta.scarb_version.to_string();
+    let dojo_version = project_meta.dojo_version.clone();
+
+    let job_id = api_client
+        .verify_class(
+            class_hash,
+            Some(license_info.display_string().to_string()),
+            contract_name,
+            project_meta,
+            &context.file_infos,
+        )
+        .map_err(CliError::from)?;
+
+    let network = network_label(args);
+
+    // Record which commit the sources came from, when the project is under git
+    let commit_hash = git::head_commit(args.path.root_dir()?);
+    if let Some(ref commit) = commit_hash {
+        debug!("Sources submitted from commit {commit}");
+    }
+
+    // Save verification record to history database; simulated jobs and jobs on
+    // other explorers don't exist on Voyager
+    if args.simulate || !record_history {
+        debug!("Not recording job {job_id} in history");
+    } else if let Err(e) = save_to_history(&HistoryParams {
+        job_id: &job_id,
+        class_hash,
+        contract_name,
+        network,
+        cairo_version: &cairo_version_str,
+        scarb_version: &scarb_version_str,
+        dojo_version: dojo_version.as_deref(),
+        package_name: &context.package_meta.name,
+        commit_hash: commit_hash.as_deref(),
+        note: args.note.as_deref(),
+        payload: payload_stats(&context.file_infos),
+        retention: args.history_retention,
+    }) {
+        warn!("Failed to save verification to history: {e}");
+        // Don't fail the verification if history save fails
+    }
+
+    Ok(job_id)
+}
+
+/// Project metadata sent with the request for `context`
+///
+/// # Errors
+///
+/// Returns an error if the project metadata cannot be loaded.
+fn project_metadata_info(
+    args: &VerifyArgs,
+    context: &VerificationContext,
+) -> Result<ProjectMetadataInfo, CliError> {
+    let metadata = args.path.load()?;
     let cairo_version = metadata.app_version_info.cairo.version.clone();
     let scarb_version = metadata.app_version_info.version.clone();
 
-    // Save version strings for history tracking before they are moved
-    let cairo_version_str = cairo_version.to_string();
-    let scarb_version_str = scarb_version.to_string();
-
     // Create project metadata with build tool information
     debug!(
         "Creating ProjectMetadataInfo with project_type: {:?}",
@@ -278,8 +1299,8 @@ pub fn execute_verification(
             context.project_dir_path
         );
         debug!(
-            "📍 args.path.root_dir() (workspace root): {}",
-            args.path.root_dir()
+            "📍 args.path.root_dir()? (workspace root): {}",
+            args.path.root_dir()?
         );
         debug!(
             "📍 context.package_meta.root (package root): {}",
@@ -287,7 +1308,7 @@ pub fn execute_verification(
         );
 
         // Extract from package root first, then fallback to workspace root
-        let workspace_root = args.path.root_dir().to_string();
+        let workspace_root = args.path.root_dir()?.to_string();
         let package_root = context.package_meta.root.to_string();
 
         // Only pass package root if it's different from workspace root (i.e., workspace scenario)
@@ -310,38 +1331,79 @@ pub fn execute_verification(
         None
     };
 
-    // Save package name before it's moved
-    let package_name = context.package_meta.name.clone();
-
     let project_meta = ProjectMetadataInfo::new(
         cairo_version,
         scarb_version,
-        context.project_dir_path,
-        context.contract_file,
-        context.package_meta.name,
+        context.project_dir_path.clone(),
+        context.contract_file.clone(),
+        context.package_meta.name.clone(),
         context.project_type,
-        dojo_version.clone(),
-    );
+        dojo_version,
+    )
+    .with_note(args.note.clone());
     debug!(
         "Created ProjectMetadataInfo with build_tool: {}, dojo_version: {:?}",
         project_meta.build_tool, project_meta.dojo_version
     );
 
-    let job_id = api_client
-        .verify_class(
-            class_hash,
-            Some(license_info.display_string().to_string()),
-            contract_name,
-            project_meta,
-            &context.file_infos,
-        )
-        .map_err(CliError::from)?;
+    Ok(project_meta)
+}
+
+/// Send the prepared submission to every target
+///
+/// # Errors
+///
+/// Returns the error of the first target if no target accepted the
+/// submission. With a single target, its error is returned as is.
+fn submit_to_each(
+    targets: &[Target<'_>],
+    args: &VerifyArgs,
+    context: &VerificationContext,
+    license_info: &license::LicenseInfo,
+) -> Result<Vec<TargetJob>, CliError> {
+    let mut jobs = Vec::with_capacity(targets.len());
+    let mut first_error = None;
+    for target in targets {
+        match execute_verification(
+            target.client,
+            args,
+            context,
+            license_info,
+            target.record_history,
+        ) {
+            Ok(job_id) => jobs.push(TargetJob {
+                target: target.name.to_string(),
+                outcome: Ok(job_id),
+            }),
+            Err(e) if targets.len() > 1 => {
+                let reason = e.to_string();
+                let reason = reason.lines().next().unwrap_or_default().to_string();
+                sink::warning(format!(
+                    "Warning: Submitting to {} failed: {reason}",
+                    target.name
+                ));
+                jobs.push(TargetJob {
+                    target: target.name.to_string(),
+                    outcome: Err(reason),
+                });
+                first_error.get_or_insert(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
 
-    // Determine network from args
-    let network = args.network.as_ref().map_or_else(
+    match first_error {
+        Some(e) if jobs.iter().all(|job| job.outcome.is_err()) => Err(e),
+        _ => Ok(jobs),
+    }
+}
+
+/// Network recorded in history for a submission with `args`
+pub(super) fn network_label(args: &VerifyArgs) -> &'static str {
+    args.network.as_ref().map_or_else(
         || {
             // Extract from URL if network not specified
-            let url = args.network_url.url.as_str();
+            let url = args.network_url.url().map_or("", Url::as_str);
             if url.contains("sepolia") {
                 "sepolia"
             } else if url.contains("dev") {
@@ -352,48 +1414,48 @@ pub fn execute_verification(
                 "custom"
             }
         },
-        |net| match net {
-            crate::cli::args::NetworkKind::Mainnet => "mainnet",
-            crate::cli::args::NetworkKind::Sepolia => "sepolia",
-            crate::cli::args::NetworkKind::Dev => "dev",
-        },
-    );
-
-    // Save verification record to history database
-    if let Err(e) = save_to_history(&HistoryParams {
-        job_id: &job_id,
-        class_hash,
-        contract_name,
-        network,
-        cairo_version: &cairo_version_str,
-        scarb_version: &scarb_version_str,
-        dojo_version: dojo_version.as_deref(),
-        package_name: &package_name,
-    }) {
-        warn!("Failed to save verification to history: {e}");
-        // Don't fail the verification if history save fails
-    }
-
-    Ok(job_id)
+        voyager::network_name,
+    )
 }
 
 /// Parameters for saving verification history
-struct HistoryParams<'a> {
-    job_id: &'a str,
-    class_hash: &'a super::class_hash::ClassHash,
-    contract_name: &'a str,
-    network: &'a str,
-    cairo_version: &'a str,
-    scarb_version: &'a str,
-    dojo_version: Option<&'a str>,
-    package_name: &'a str,
+pub(super) struct HistoryParams<'a> {
+    pub(super) job_id: &'a str,
+    pub(super) class_hash: &'a super::class_hash::ClassHash,
+    pub(super) contract_name: &'a str,
+    pub(super) network: &'a str,
+    pub(super) cairo_version: &'a str,
+    pub(super) scarb_version: &'a str,
+    pub(super) dojo_version: Option<&'a str>,
+    pub(super) package_name: &'a str,
+    pub(super) commit_hash: Option<&'a str>,
+    pub(super) note: Option<&'a str>,
+    pub(super) payload: Option<PayloadStats>,
+    pub(super) retention: RetentionPolicy,
+}
+
+/// Sizes of the submitted files, recorded in history to follow payload growth
+pub(super) fn payload_stats(file_infos: &[FileInfo]) -> Option<PayloadStats> {
+    let files = file_infos
+        .iter()
+        .filter_map(|info| {
+            Some((
+                info.name.clone(),
+                Utf8PathBuf::from_path_buf(info.path.clone()).ok()?,
+            ))
+        })
+        .collect::<std::collections::HashMap<_, _>>();
+    PayloadAnalysis::of_files(&files)
+        .inspect_err(|e| debug!("Not recording the payload size: {e}"))
+        .ok()
+        .map(|analysis| analysis.stats())
 }
 
 /// Save a verification record to the history database
-fn save_to_history(
+pub(super) fn save_to_history(
     params: &HistoryParams<'_>,
 ) -> Result<(), crate::storage::history::HistoryError> {
-    let db = HistoryDb::open()?;
+    let db = HistoryDb::open()?.with_retention(params.retention);
 
     let record = VerificationRecord::new(
         params.job_id.to_string(),
@@ -405,7 +1467,12 @@ fn save_to_history(
         params.scarb_version.to_string(),
         params.cairo_version.to_string(),
         params.dojo_version.map(String::from),
-    );
+    )
+    .with_commit_hash(params.commit_hash.map(String::from))
+    .with_submitter(Submitter::detect())
+    .with_run_id(run_id::current())
+    .with_note(params.note.map(String::from))
+    .with_payload(params.payload.clone());
 
     db.insert(&record)?;
     info!("Saved verification record to history database");
@@ -432,6 +1499,46 @@ fn update_history_status(
     Ok(())
 }
 
+/// Copy the explorer's record of a verified class into the job's history entry
+///
+/// Called once a job succeeds so that `history status` shows the verification
+/// time, name, and version displayed on the website. Failures are only logged,
+/// since local history must never fail the command.
+pub fn reconcile_with_explorer(api_client: &ApiClient, job_id: &str) {
+    if let Err(e) = try_reconcile_with_explorer(api_client, job_id) {
+        warn!("Failed to update verification history from explorer: {e}");
+    }
+}
+
+fn try_reconcile_with_explorer(
+    api_client: &ApiClient,
+    job_id: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db = HistoryDb::open()?;
+    let Some(record) = db.get_by_job_id(job_id)? else {
+        debug!("Job {job_id} not found in history database, skipping explorer lookup");
+        return Ok(());
+    };
+
+    let class_hash = super::class_hash::ClassHash::new(&record.class_hash)?;
+    let ClassVerification::Verified(info) = api_client.check_class_verification(&class_hash)?
+    else {
+        debug!("Explorer does not report {class_hash} as verified yet");
+        return Ok(());
+    };
+
+    db.update_explorer_info(
+        job_id,
+        info.verified_timestamp
+            .map(crate::output::status::timestamp_to_datetime),
+        info.name.as_deref(),
+        info.version.as_deref(),
+    )?;
+    debug!("Updated verification history for job {job_id} from explorer");
+
+    Ok(())
+}
+
 /// Check the status of a verification job
 ///
 /// This function polls the verification service for the status of a job and
@@ -443,6 +1550,8 @@ fn update_history_status(
 /// * `api_client` - The API client for communicating with the verification service
 /// * `job_id` - The unique identifier of the verification job
 /// * `format` - The output format (Text, Json, or Table)
+/// * `stream_logs` - Print the remote compiler output while the job is processing
+///   (text format only, if the API exposes job logs)
 ///
 /// # Returns
 ///
@@ -455,33 +1564,45 @@ pub fn check(
     api_client: &ApiClient,
     job_id: &str,
     format: &crate::cli::args::OutputFormat,
+    timezone: crate::cli::args::Timezone,
+    stream_logs: bool,
 ) -> Result<VerificationJob, CliError> {
     // Use polling with callback to show status updates during watch
     let format_copy = *format;
 
     // For text format, show live inline status updates
     if format_copy == crate::cli::args::OutputFormat::Text {
-        use std::io::Write;
+        let live = std::cell::RefCell::new(LiveStatus::new());
+        let log_offset = std::cell::Cell::new(stream_logs.then_some(0));
         let callback = |status: &VerificationJob| {
+            if *status.status() == VerifyJobStatus::Processing {
+                print_job_logs(api_client, job_id, &log_offset, &mut live.borrow_mut());
+            }
             let inline_status = crate::output::status::format_inline_status(status);
-            // Clear line and update with new status
-            print!("\r\x1B[2K{inline_status}");
-            std::io::stdout().flush().ok();
+            live.borrow_mut()
+                .update(&status.status().to_string(), &inline_status);
         };
 
         let status =
             crate::api::poll_verification_status_with_callback(api_client, job_id, Some(&callback))
                 .map_err(CliError::from)?;
+        // Output written after the last poll
+        if log_offset.get().is_some_and(|offset| offset > 0) {
+            print_job_logs(api_client, job_id, &log_offset, &mut live.borrow_mut());
+        }
 
         // Update history database with latest status
         if let Err(e) = update_history_status(job_id, *status.status()) {
             warn!("Failed to update verification history: {e}");
         }
+        if *status.status() == VerifyJobStatus::Success {
+            reconcile_with_explorer(api_client, job_id);
+        }
 
-        // Print newline and show final detailed status
-        println!();
-        let output = crate::output::status::format_status(&status, format);
-        println!("{output}");
+        // End the live status and show final detailed status
+        live.borrow_mut().finish();
+        let output = crate::output::status::format_status(&status, format, timezone);
+        sink::line(output);
 
         Ok(status)
     } else {
@@ -492,14 +1613,45 @@ pub fn check(
         if let Err(e) = update_history_status(job_id, *status.status()) {
             warn!("Failed to update verification history: {e}");
         }
+        if *status.status() == VerifyJobStatus::Success {
+            reconcile_with_explorer(api_client, job_id);
+        }
 
-        let output = crate::output::status::format_status(&status, format);
-        println!("{output}");
+        let output = crate::output::status::format_status(&status, format, timezone);
+        sink::line(output);
 
         Ok(status)
     }
 }
 
+/// Print the compiler output of a job from `offset` on, advancing `offset`
+///
+/// `offset` is `None` once streaming is off: not requested, or not supported
+/// by the API. Failed requests are retried on the next poll.
+fn print_job_logs(
+    api_client: &ApiClient,
+    job_id: &str,
+    offset: &std::cell::Cell<Option<usize>>,
+    live: &mut LiveStatus,
+) {
+    let Some(from) = offset.get() else {
+        return;
+    };
+    match api_client.get_job_logs(job_id, from) {
+        Ok(Some(logs)) => {
+            for line in &logs.lines {
+                live.log(&format!("  {} {line}", sink::paint("│", Tone::Muted)));
+            }
+            offset.set(Some(logs.next_offset));
+        }
+        Ok(None) => {
+            debug!("The API does not expose job logs, not streaming compiler output");
+            offset.set(None);
+        }
+        Err(e) => debug!("Failed to fetch job logs: {e}"),
+    }
+}
+
 /// Display a verification job ID to the user
 ///
 /// Formats and displays the verification job ID in a visually distinct way
@@ -509,9 +1661,12 @@ pub fn check(
 ///
 /// * `job_id` - The verification job ID to display
 pub fn display_verification_job_id(job_id: &str) {
-    println!();
-    println!("verification job id: {}", job_id.green().bold());
-    println!();
+    sink::line("");
+    sink::line(format!(
+        "verification job id: {}",
+        sink::paint(job_id, Tone::Success)
+    ));
+    sink::line("");
 }
 
 /// Display verbose error information
@@ -530,9 +1685,15 @@ pub fn display_verbose_error(error: &CliError) {
             | VerificationError::VerificationFailure(msg) => msg,
         };
 
-        eprintln!("\n{}", "--- Detailed Error Output ---".bright_yellow());
-        eprintln!("{raw_message}");
-        eprintln!("{}\n", "--- End Error Output ---".bright_yellow());
+        sink::warning(format!(
+            "\n{}",
+            sink::paint("--- Detailed Error Output ---", Tone::Notice)
+        ));
+        sink::warning(raw_message);
+        sink::warning(format!(
+            "{}\n",
+            sink::paint("--- End Error Output ---", Tone::Notice)
+        ));
     }
 }
 
@@ -555,6 +1716,12 @@ pub struct BatchVerificationResult {
     pub job_id: Option<String>,
     pub status: Option<VerifyJobStatus>,
     pub error: Option<String>,
+    /// Next steps for `error`
+    pub suggestions: Vec<Suggestion>,
+    /// Fingerprint of the submitted sources, if they were collected
+    pub source_fingerprint: Option<String>,
+    /// Seconds the job took, or has taken so far; only known when watching
+    pub duration: Option<u64>,
 }
 
 /// Summary of batch verification
@@ -578,7 +1745,22 @@ pub struct BatchVerificationSummary {
 /// * `api_client` - The API client for communicating with the verification service
 /// * `args` - Base verification arguments (network, watch, etc.)
 /// * `config` - Configuration containing the list of contracts to verify
-/// * `license_info` - License information for the contracts
+///
+/// The license for each contract is resolved from `--license`, or else from the
+/// manifest of the package that contract belongs to.
+///
+/// With a `plan`, contracts the plan marks as done are not submitted again, and
+/// every contract is marked in the plan file as soon as it is submitted, so an
+/// interrupted batch can be resumed. The plan's contracts must be the ones in
+/// `config`.
+///
+/// With a `state`, contracts whose class hash and sources match the state are
+/// not submitted; their results carry the fingerprint of their sources, as do
+/// those of contracts found to be verified already.
+///
+/// Contracts are submitted in [`submission_order`]. Before a contract with
+/// `depends-on` is submitted, the jobs of its dependencies are polled until
+/// they finish; if one of them does not verify, the contract is skipped.
 ///
 /// # Returns
 ///
@@ -591,106 +1773,59 @@ pub fn submit_batch(
     api_client: &ApiClient,
     args: &VerifyArgs,
     config: &crate::cli::config::Config,
-    license_info: &license::LicenseInfo,
+    mut plan: Option<&mut BatchPlan>,
+    state: Option<&WorkspaceState>,
 ) -> Result<BatchVerificationSummary, CliError> {
     info!(
         "🚀 Starting batch verification for {} contracts",
         config.contracts.len()
     );
 
-    let mut results = Vec::new();
+    let order = submission_order(&config.contracts)?;
     let total = config.contracts.len();
+    if args.jobs > 1 {
+        let results = submit_concurrently(api_client, args, config, &order, plan, state)?;
+        return Ok(BatchVerificationSummary {
+            total,
+            submitted: results.iter().filter(|r| r.job_id.is_some()).count(),
+            results,
+        });
+    }
 
-    for (index, contract_config) in config.contracts.iter().enumerate() {
-        println!(
+    let mut results: Vec<BatchVerificationResult> = Vec::new();
+    for (position, index) in order.into_iter().enumerate() {
+        let contract_config = &config.contracts[index];
+        sink::line(format!(
             "\n{} Verifying: {}",
-            format!("[{}/{}]", index + 1, total).bright_cyan().bold(),
-            contract_config.contract_name.bright_white().bold()
-        );
+            sink::paint(format!("[{}/{}]", position + 1, total), Tone::Heading),
+            sink::paint(&contract_config.contract_name, Tone::Emphasis)
+        ));
 
-        // Parse class hash
-        let class_hash = match super::class_hash::ClassHash::new(&contract_config.class_hash) {
-            Ok(hash) => hash,
-            Err(e) => {
-                let error_msg = format!("Invalid class hash: {e}");
-                println!("  {} {}", "✗".red().bold(), error_msg.red());
-                if args.fail_fast {
-                    return Err(CliError::from(e));
-                }
-                // Skip this contract and continue with the next one
-                continue;
-            }
-        };
+        if let Some(done) = plan.as_deref().and_then(|plan| plan.completed(index)) {
+            results.extend(resumed_result(contract_config, done, "  "));
+            continue;
+        }
 
-        // Create individual VerifyArgs for this contract
-        let mut contract_args = args.clone();
-        contract_args.class_hash = Some(class_hash.clone());
-        contract_args.contract_name = Some(contract_config.contract_name.clone());
-        contract_args.package = contract_config
-            .package
-            .clone()
-            .or_else(|| contract_args.package.clone());
-
-        // Submit using existing submit() function (reuse all existing logic!)
-        let result = match submit(api_client, &contract_args, license_info) {
-            Ok(job_id) if job_id != "dry-run" => {
-                println!(
-                    "  {} Submitted - Job ID: {}",
-                    "✓".green().bold(),
-                    job_id.green()
-                );
-                BatchVerificationResult {
-                    contract: BatchContract {
-                        class_hash: class_hash.clone(),
-                        contract_name: contract_config.contract_name.clone(),
-                        package: contract_config.package.clone(),
-                    },
-                    job_id: Some(job_id),
-                    status: Some(VerifyJobStatus::Submitted),
-                    error: None,
-                }
-            }
-            Ok(_) => {
-                // dry-run mode
-                BatchVerificationResult {
-                    contract: BatchContract {
-                        class_hash: class_hash.clone(),
-                        contract_name: contract_config.contract_name.clone(),
-                        package: contract_config.package.clone(),
-                    },
-                    job_id: None,
-                    status: None,
-                    error: None,
-                }
-            }
-            Err(e) => {
-                println!("  {} Failed: {}", "✗".red().bold(), e.to_string().red());
-                if args.fail_fast {
-                    return Err(e);
-                }
-                BatchVerificationResult {
-                    contract: BatchContract {
-                        class_hash: class_hash.clone(),
-                        contract_name: contract_config.contract_name.clone(),
-                        package: contract_config.package.clone(),
-                    },
-                    job_id: None,
-                    status: None,
-                    error: Some(e.to_string()),
-                }
-            }
+        let step = verify_batch_contract(api_client, args, contract_config, state, "  ", || {
+            wait_for_dependencies(api_client, contract_config, &mut results)
+        })?;
+        let Some(step) = step else {
+            continue;
         };
-
-        results.push(result);
+        if step.completed() {
+            record_batch_progress(plan.as_deref_mut(), index, step.result.job_id.clone());
+        }
+        let submitted = step.submitted;
+        results.push(step.result);
 
         // Rate limiting delay between submissions
-        if index < total - 1 {
+        if submitted && position < total - 1 {
             if let Some(delay_secs) = args.batch_delay {
-                println!(
+                sink::line(format!(
                     "  {} Waiting {} seconds before next submission...",
-                    "⏳".yellow(),
+                    sink::marker(Marker::Pending),
                     delay_secs
-                );
+                ));
                 std::thread::sleep(std::time::Duration::from_secs(delay_secs));
             }
         }
@@ -705,6 +1840,675 @@ pub fn submit_batch(
     })
 }
 
+/// What became of one contract of a batch
+struct BatchStep {
+    result: BatchVerificationResult,
+    /// Whether the contract went through [`submit`], successfully or not;
+    /// `--batch-delay` only applies after those
+    submitted: bool,
+}
+
+impl BatchStep {
+    /// Whether to record the contract as done in the batch plan: it was
+    /// submitted or needed no job; failures and dry runs are not recorded
+    fn completed(&self) -> bool {
+        let result = &self.result;
+        result.error.is_none()
+            && (result.job_id.is_some() || result.status == Some(VerifyJobStatus::Success))
+    }
+}
+
+/// Result of a contract the batch plan marks as done
+fn resumed_result(
+    contract_config: &ContractConfig,
+    done: &CompletedContract,
+    indent: &str,
+) -> Option<BatchVerificationResult> {
+    let class_hash = super::class_hash::ClassHash::new(&contract_config.class_hash).ok()?;
+    match done.job_id {
+        Some(ref job_id) => sink::line(format!(
+            "{indent}{} Submitted before resuming - Job ID: {}",
+            sink::marker(Marker::Success),
+            sink::paint(job_id, Tone::Success)
+        )),
+        None => sink::line(format!(
+            "{indent}{} Already verified before resuming, skipping",
+            sink::marker(Marker::Success)
+        )),
+    }
+    Some(BatchVerificationResult {
+        contract: BatchContract {
+            class_hash,
+            contract_name: contract_config.contract_name.clone(),
+            package: contract_config.package.clone(),
+        },
+        job_id: done.job_id.clone(),
+        status: Some(if done.job_id.is_some() {
+            VerifyJobStatus::Submitted
+        } else {
+            VerifyJobStatus::Success
+        }),
+        error: None,
+        suggestions: Vec::new(),
+        source_fingerprint: None,
+        duration: None,
+    })
+}
+
+/// Verify one contract of a batch
+///
+/// Returns `None` if the contract cannot be verified at all, such as for an
+/// invalid class hash. `wait_for_dependencies` is called right before the
+/// contract would be submitted. Progress lines start with `indent`.
+///
+/// # Errors
+///
+/// With `--fail-fast`, returns the error that failed the contract.
+fn verify_batch_contract(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    contract_config: &ContractConfig,
+    state: Option<&WorkspaceState>,
+    indent: &str,
+    wait_for_dependencies: impl FnOnce() -> Result<(), CliError>,
+) -> Result<Option<BatchStep>, CliError> {
+    let class_hash = match batch_class_hash(contract_config, indent) {
+        Ok(hash) => hash,
+        Err(e) if args.fail_fast => return Err(CliError::from(e)),
+        // Skip this contract and continue with the next one
+        Err(_) => return Ok(None),
+    };
+    let batch_result =
+        |job_id, status, error: Option<&CliError>, source_fingerprint| BatchVerificationResult {
+            contract: BatchContract {
+                class_hash: class_hash.clone(),
+                contract_name: contract_config.contract_name.clone(),
+                package: contract_config.package.clone(),
+            },
+            job_id,
+            status,
+            error: error.map(ToString::to_string),
+            suggestions: error.map(CliError::suggestions).unwrap_or_default(),
+            source_fingerprint,
+            duration: None,
+        };
+    let skipped = |result| {
+        Ok(Some(BatchStep {
+            result,
+            submitted: false,
+        }))
+    };
+
+    let contract_args = batch_contract_args(args, contract_config, &class_hash);
+    let fingerprint = state.and_then(|_| fingerprint_sources(&contract_args));
+    if let (Some(state), Some(fingerprint)) = (state, &fingerprint) {
+        if state.is_unchanged(
+            &contract_config.contract_name,
+            class_hash.as_ref(),
+            fingerprint,
+        ) {
+            sink::line(format!(
+                "{indent}{} Unchanged since the last verified state, skipping",
+                sink::marker(Marker::Success)
+            ));
+            return skipped(batch_result(
+                None,
+                Some(VerifyJobStatus::Success),
+                None,
+                Some(fingerprint.clone()),
+            ));
+        }
+    }
+
+    if args.should_skip_verified() && already_verified(api_client, &class_hash).is_some() {
+        sink::line(format!(
+            "{indent}{} Already verified, skipping (use --force to re-verify)",
+            sink::marker(Marker::Success)
+        ));
+        return skipped(batch_result(
+            None,
+            Some(VerifyJobStatus::Success),
+            None,
+            fingerprint,
+        ));
+    }
+
+    if let Err(e) = wait_for_dependencies() {
+        sink::line(format!(
+            "{indent}{} Skipped: {}",
+            sink::marker(Marker::Failure),
+            sink::paint(e.to_string(), Tone::Failure)
+        ));
+        if args.fail_fast {
+            return Err(e);
+        }
+        return skipped(batch_result(None, None, Some(&e), None));
+    }
+
+    let license_info = license::resolve_license_info(
+        contract_args.license,
+        contract_args
+            .path
+            .get_license(contract_args.package.as_deref())?,
+        contract_args.path.manifest_path()?,
+    );
+    license::warn_if_no_license(&license_info);
+
+    let result = match submit_batch_contract(api_client, &contract_args, &license_info, indent) {
+        Ok(Submission {
+            job_id: Some(job_id),
+            source_fingerprint,
+            ..
+        }) => batch_result(
+            Some(job_id),
+            Some(VerifyJobStatus::Submitted),
+            None,
+            source_fingerprint,
+        ),
+        // dry-run mode
+        Ok(Submission {
+            job_id: None,
+            source_fingerprint,
+            ..
+        }) => batch_result(None, None, None, source_fingerprint),
+        Err(e) if args.fail_fast => return Err(e),
+        Err(e) => batch_result(None, None, Some(&e), None),
+    };
+
+    Ok(Some(BatchStep {
+        result,
+        submitted: true,
+    }))
+}
+
+/// Class hash of a batch contract, reporting it on a line starting with
+/// `indent` if it is invalid
+///
+/// # Errors
+///
+/// Returns the error from parsing the class hash.
+fn batch_class_hash(
+    contract_config: &ContractConfig,
+    indent: &str,
+) -> Result<super::class_hash::ClassHash, super::class_hash::ClassHashError> {
+    super::class_hash::ClassHash::new(&contract_config.class_hash).inspect_err(|e| {
+        sink::line(format!(
+            "{indent}{} {}",
+            sink::marker(Marker::Failure),
+            sink::paint(format!("Invalid class hash: {e}"), Tone::Failure)
+        ));
+    })
+}
+
+/// Arguments to verify `contract_config` of a batch run with `args`
+fn batch_contract_args(
+    args: &VerifyArgs,
+    contract_config: &ContractConfig,
+    class_hash: &super::class_hash::ClassHash,
+) -> VerifyArgs {
+    let mut contract_args = args.clone();
+    // Batches run unattended; only single submissions are confirmed
+    contract_args.yes = true;
+    contract_args.class_hash = Some(class_hash.clone());
+    contract_args.contract_name = Some(contract_config.contract_name.clone());
+    contract_args
+        .contract_file
+        .clone_from(&contract_config.contract_file);
+    if contract_config.package.is_some() {
+        contract_args.package.clone_from(&contract_config.package);
+    }
+    contract_args
+}
+
+/// Submit one contract of a batch, reporting the job or the failure on a
+/// line starting with `indent`
+///
+/// # Errors
+///
+/// Returns the error from [`submit`].
+fn submit_batch_contract(
+    api_client: &ApiClient,
+    contract_args: &VerifyArgs,
+    license_info: &license::LicenseInfo,
+    indent: &str,
+) -> Result<Submission, CliError> {
+    let submission = submit(api_client, contract_args, license_info);
+    match &submission {
+        Ok(Submission {
+            job_id: Some(job_id),
+            ..
+        }) => sink::line(format!(
+            "{indent}{} Submitted - Job ID: {}",
+            sink::marker(Marker::Success),
+            sink::paint(job_id, Tone::Success)
+        )),
+        Ok(_) => {}
+        Err(e) => sink::line(format!(
+            "{indent}{} Failed: {}",
+            sink::marker(Marker::Failure),
+            sink::paint(e.to_string(), Tone::Failure)
+        )),
+    }
+    submission
+}
+
+/// Submit a batch with `--jobs` workers
+///
+/// Contracts are submitted in waves: a contract only starts once every
+/// contract it depends on was submitted in an earlier wave. Within a wave,
+/// each worker takes the next contract in [`submission_order`] and waits
+/// `--batch-delay` after each of its own submissions. Results are returned in
+/// submission order.
+///
+/// # Errors
+///
+/// With `--fail-fast`, returns the first error; workers finish the contract
+/// they are on but start no new ones.
+fn submit_concurrently(
+    api_client: &ApiClient,
+    args: &VerifyArgs,
+    config: &crate::cli::config::Config,
+    order: &[usize],
+    plan: Option<&mut BatchPlan>,
+    state: Option<&WorkspaceState>,
+) -> Result<Vec<BatchVerificationResult>, CliError> {
+    let total = config.contracts.len();
+    let plan = Mutex::new(plan);
+    let results: Mutex<Vec<BatchVerificationResult>> = Mutex::new(Vec::new());
+    let failure: Mutex<Option<CliError>> = Mutex::new(None);
+
+    for wave in submission_waves(&config.contracts, order) {
+        let queue = Mutex::new(wave.into_iter());
+        let finished: Mutex<Vec<(usize, BatchVerificationResult)>> = Mutex::new(Vec::new());
+
+        let worker = || loop {
+            if lock(&failure).is_some() {
+                break;
+            }
+            let Some((position, index)) = lock(&queue).next() else {
+                break;
+            };
+            let contract_config = &config.contracts[index];
+            let indent = format!("  {}: ", contract_config.contract_name);
+            sink::line(format!(
+                "\n{} Verifying: {}",
+                sink::paint(format!("[{}/{}]", position + 1, total), Tone::Heading),
+                sink::paint(&contract_config.contract_name, Tone::Emphasis)
+            ));
+
+            let done = lock(&plan)
+                .as_deref()
+                .and_then(|plan| plan.completed(index))
+                .cloned();
+            if let Some(done) = done {
+                if let Some(result) = resumed_result(contract_config, &done, &indent) {
+                    lock(&finished).push((position, result));
+                }
+                continue;
+            }
+
+            let step =
+                verify_batch_contract(api_client, args, contract_config, state, &indent, || {
+                    wait_for_shared_dependencies(api_client, contract_config, &results)
+                });
+            let step = match step {
+                Ok(Some(step)) => step,
+                Ok(None) => continue,
+                Err(e) => {
+                    lock(&failure).get_or_insert(e);
+                    break;
+                }
+            };
+            if step.completed() {
+                record_batch_progress(
+                    lock(&plan).as_deref_mut(),
+                    index,
+                    step.result.job_id.clone(),
+                );
+            }
+            let submitted = step.submitted;
+            lock(&finished).push((position, step.result));
+
+            // Rate limiting delay between this worker's submissions
+            if let (true, Some(delay_secs)) = (submitted, args.batch_delay) {
+                if lock(&queue).len() > 0 {
+                    std::thread::sleep(std::time::Duration::from_secs(delay_secs));
+                }
+            }
+        };
+        std::thread::scope(|scope| {
+            for _ in 0..args.jobs {
+                scope.spawn(worker);
+            }
+        });
+
+        let failed = lock(&failure).take();
+        if let Some(e) = failed {
+            return Err(e);
+        }
+        let mut finished = finished
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        finished.sort_by_key(|(position, _)| *position);
+        lock(&results).extend(finished.into_iter().map(|(_, result)| result));
+    }
+
+    Ok(results.into_inner().unwrap_or_else(PoisonError::into_inner))
+}
+
+/// Split `order` into waves of (position, index) pairs, each wave holding the
+/// contracts whose dependencies are all in earlier waves
+fn submission_waves(contracts: &[ContractConfig], order: &[usize]) -> Vec<Vec<(usize, usize)>> {
+    let mut wave_of = vec![0; contracts.len()];
+    let mut waves: Vec<Vec<(usize, usize)>> = Vec::new();
+    for (position, &index) in order.iter().enumerate() {
+        // Dependencies come earlier in the order, so their wave is known
+        let wave = contracts[index]
+            .depends_on
+            .iter()
+            .flat_map(|dependency| {
+                contracts
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, c)| c.contract_name == *dependency)
+                    .map(|(i, _)| wave_of[i] + 1)
+            })
+            .max()
+            .unwrap_or(0);
+        wave_of[index] = wave;
+        if waves.len() <= wave {
+            waves.resize_with(wave + 1, Vec::new);
+        }
+        waves[wave].push((position, index));
+    }
+    waves
+}
+
+/// Lock `mutex`, even if a worker panicked while holding it
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Resolve the package of batch contracts given by glob
+///
+/// A `package` glob, or `members_filter` for contracts without a `package`,
+/// is expanded against the workspace members. If several members match, the
+/// one whose sources define the contract is taken.
+///
+/// # Errors
+///
+/// Returns [`CliError::PackagePatternUnresolved`] if a glob is invalid, or
+/// if no member or several members match and define the contract.
+pub fn resolve_contract_packages(
+    contracts: &mut [ContractConfig],
+    metadata: &scarb_metadata::Metadata,
+    members_filter: &[String],
+) -> Result<(), CliError> {
+    let members: Vec<PackageMetadata> = metadata
+        .packages
+        .iter()
+        .filter(|package| metadata.workspace.members.contains(&package.id))
+        .cloned()
+        .collect();
+    for contract in contracts {
+        let patterns: Vec<&str> = match contract.package.as_deref() {
+            Some(package) if is_package_glob(package) => vec![package],
+            Some(_) => continue,
+            None if members_filter.is_empty() => continue,
+            None => members_filter.iter().map(String::as_str).collect(),
+        };
+        let unresolved = |reason: String| CliError::PackagePatternUnresolved {
+            contract: contract.contract_name.clone(),
+            pattern: patterns.join(", "),
+            reason,
+        };
+
+        let members = members_matching(&members, &metadata.workspace.root, &patterns)
+            .map_err(|e| unresolved(e.to_string()))?;
+        let candidates: Vec<_> = if members.len() > 1 {
+            members
+                .iter()
+                .filter(|member| package_defines_contract(member, &contract.contract_name))
+                .collect()
+        } else {
+            members.iter().collect()
+        };
+        let package = match candidates.as_slice() {
+            [package] => package.name.clone(),
+            [] if members.is_empty() => {
+                return Err(unresolved("no workspace member matches".to_string()));
+            }
+            [] => {
+                return Err(unresolved(format!(
+                    "none of the matching members ({}) defines the contract",
+                    members.iter().map(|member| &member.name).join(", ")
+                )));
+            }
+            _ => {
+                return Err(unresolved(format!(
+                    "several members define the contract ({})",
+                    candidates.iter().map(|member| &member.name).join(", ")
+                )));
+            }
+        };
+        info!(
+            "Resolved package of {} to {package}",
+            contract.contract_name
+        );
+        contract.package = Some(package);
+    }
+    Ok(())
+}
+
+/// Order in which batch contracts are submitted
+///
+/// Returns indices into `contracts`. Every contract comes after the contracts
+/// named in its `depends-on`; otherwise the configured order is kept.
+///
+/// # Errors
+///
+/// Returns [`CliError::InvalidContractDependencies`] if a contract depends on
+/// itself or on a name that is not in `contracts`, or if dependencies form a
+/// cycle.
+pub fn submission_order(contracts: &[ContractConfig]) -> Result<Vec<usize>, CliError> {
+    for contract in contracts {
+        for dependency in &contract.depends_on {
+            if *dependency == contract.contract_name {
+                return Err(CliError::InvalidContractDependencies {
+                    reason: format!("'{dependency}' depends on itself"),
+                });
+            }
+            if !contracts.iter().any(|c| c.contract_name == *dependency) {
+                return Err(CliError::InvalidContractDependencies {
+                    reason: format!(
+                        "'{}' depends on '{dependency}', which is not in [[contracts]]",
+                        contract.contract_name
+                    ),
+                });
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = Vec::with_capacity(contracts.len());
+    while order.len() < contracts.len() {
+        let ready = (0..contracts.len()).find(|&index| {
+            !order.contains(&index)
+                && contracts[index].depends_on.iter().all(|dependency| {
+                    contracts
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, c)| c.contract_name == *dependency)
+                        .all(|(i, _)| order.contains(&i))
+                })
+        });
+        let Some(index) = ready else {
+            let cycle = (0..contracts.len())
+                .filter(|index| !order.contains(index))
+                .map(|index| contracts[index].contract_name.as_str())
+                .join(", ");
+            return Err(CliError::InvalidContractDependencies {
+                reason: format!("dependency cycle between {cycle}"),
+            });
+        };
+        order.push(index);
+    }
+
+    Ok(order)
+}
+
+/// Wait until the dependencies of `contract` have verified
+///
+/// Jobs still in progress are polled until they finish, and their results
+/// are updated with the final status. In dry-run mode nothing was submitted,
+/// so there is nothing to wait for.
+///
+/// # Errors
+///
+/// Returns [`CliError::DependencyNotVerified`] if a dependency was not
+/// submitted or its verification failed.
+fn wait_for_dependencies(
+    api_client: &ApiClient,
+    contract: &ContractConfig,
+    results: &mut [BatchVerificationResult],
+) -> Result<(), CliError> {
+    for dependency in &contract.depends_on {
+        let not_verified = |reason: String| CliError::DependencyNotVerified {
+            contract: contract.contract_name.clone(),
+            dependency: dependency.clone(),
+            reason,
+        };
+
+        let mut found = false;
+        for result in results
+            .iter_mut()
+            .filter(|r| r.contract.contract_name == *dependency)
+        {
+            found = true;
+            if let Some(error) = &result.error {
+                return Err(not_verified(first_line(error)));
+            }
+            match (result.status, result.job_id.as_deref()) {
+                (Some(VerifyJobStatus::Success), _) | (None, None) => {}
+                (Some(VerifyJobStatus::Fail | VerifyJobStatus::CompileFailed), _) => {
+                    return Err(not_verified("verification failed".to_string()));
+                }
+                (_, Some(job_id)) => {
+                    sink::line(format!(
+                        "  {} Waiting for {dependency} (job {job_id})...",
+                        sink::marker(Marker::Pending)
+                    ));
+                    let live = std::cell::RefCell::new(LiveStatus::new());
+                    let callback = |job: &VerificationJob| {
+                        let inline_status = crate::output::status::format_inline_status(job);
+                        live.borrow_mut()
+                            .update(&job.status().to_string(), &format!("  {inline_status}"));
+                    };
+                    let polled = crate::api::poll_verification_status_with_callback(
+                        api_client,
+                        job_id,
+                        Some(&callback),
+                    );
+                    live.borrow_mut().finish();
+                    match polled {
+                        Ok(job) => result.status = Some(*job.status()),
+                        Err(e) => {
+                            result.status = match e {
+                                ApiClientError::Verify(VerificationError::CompilationFailure(
+                                    _,
+                                )) => Some(VerifyJobStatus::CompileFailed),
+                                ApiClientError::Verify(VerificationError::VerificationFailure(
+                                    _,
+                                )) => Some(VerifyJobStatus::Fail),
+                                _ => result.status,
+                            };
+                            return Err(not_verified(first_line(&e.to_string())));
+                        }
+                    }
+                }
+                (_, None) => return Err(not_verified("it was not submitted".to_string())),
+            }
+        }
+
+        if !found {
+            return Err(not_verified("it was not submitted".to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// [`wait_for_dependencies`] on the `results` shared by the workers of
+/// [`submit_concurrently`]
+///
+/// The lock is only held to copy the dependencies out and to write back the
+/// statuses polled, so other workers are not held up while jobs are polled.
+fn wait_for_shared_dependencies(
+    api_client: &ApiClient,
+    contract: &ContractConfig,
+    results: &Mutex<Vec<BatchVerificationResult>>,
+) -> Result<(), CliError> {
+    let (positions, mut dependencies): (Vec<usize>, Vec<BatchVerificationResult>) = lock(results)
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| contract.depends_on.contains(&result.contract.contract_name))
+        .map(|(position, result)| (position, result.clone()))
+        .unzip();
+    let waited = wait_for_dependencies(api_client, contract, &mut dependencies);
+    // Results only grow between waves, so the positions still hold
+    let mut results = lock(results);
+    for (position, dependency) in positions.into_iter().zip(dependencies) {
+        results[position].status = dependency.status;
+    }
+    waited
+}
+
+/// Fingerprint of the sources `args` would submit, or `None` if they cannot
+/// be collected
+fn fingerprint_sources(args: &VerifyArgs) -> Option<String> {
+    determine_project_type(args)
+        .and_then(|project_type| collect_project_files(args, project_type))
+        .map_err(|e| e.to_string())
+        .and_then(|(file_infos, ..)| source_fingerprint(&file_infos).map_err(|e| e.to_string()))
+        .map_err(|e| warn!("Failed to fingerprint sources: {e}"))
+        .ok()
+}
+
+/// Record the contracts of `summary` that are verified in `state`
+///
+/// Contracts whose sources were not fingerprinted are left as they were.
+pub fn record_verified_state(state: &mut WorkspaceState, summary: &BatchVerificationSummary) {
+    for result in &summary.results {
+        if let (Some(VerifyJobStatus::Success), Some(fingerprint)) =
+            (result.status, &result.source_fingerprint)
+        {
+            state.record(
+                &result.contract.contract_name,
+                result.contract.class_hash.as_ref(),
+                fingerprint.clone(),
+                result.job_id.clone(),
+            );
+        }
+    }
+}
+
+/// First line of an error message, without its suggestions
+fn first_line(message: &str) -> String {
+    message.lines().next().unwrap_or_default().to_string()
+}
+
+/// Mark a batch contract as done in the plan file, if the batch has one
+///
+/// Failing to save only loses the ability to resume, so it is not fatal.
+fn record_batch_progress(plan: Option<&mut BatchPlan>, index: usize, job_id: Option<String>) {
+    let Some(plan) = plan else {
+        return;
+    };
+    plan.record(index, job_id);
+    if let Err(e) = plan.save() {
+        warn!("Failed to save progress of batch {}: {e}", plan.id);
+    }
+}
+
 /// Watch all batch verification jobs until completion
 ///
 /// This function polls all submitted jobs in the batch until they reach
@@ -738,14 +2542,20 @@ pub fn watch_batch(
         return Ok(summary.clone()); // Nothing to watch
     }
 
-    println!(
+    sink::line(format!(
         "\n{} Watching {} verification job(s)...\n",
-        "⏳".yellow(),
+        sink::marker(Marker::Pending),
         job_ids.len()
-    );
+    ));
+
+    // Batches check every job per round, so poll less often than a single job by default
+    let default_strategy = FixedInterval::new(Duration::from_secs(5), u32::MAX);
+    let strategy = api_client.poll_strategy().unwrap_or(&default_strategy);
 
     let mut updated_results = summary.results.clone();
     let mut iteration = 0;
+    let mut live = LiveStatus::new();
+    let started = Instant::now();
 
     // Poll all jobs until complete
     loop {
@@ -772,6 +2582,7 @@ pub fn watch_batch(
                         let new_status = *status.status();
                         let status_changed = result.status != Some(new_status);
                         result.status = Some(new_status);
+                        result.duration = crate::output::status::job_elapsed(&status);
 
                         // Check if still pending
                         if !matches!(
@@ -783,9 +2594,15 @@ pub fn watch_batch(
                             all_complete = false;
                         }
 
-                        // Log status change
+                        // Log status change and keep history in sync
                         if status_changed {
                             debug!("Job {job_id} status changed to {new_status}");
+                            if let Err(e) = update_history_status(job_id, new_status) {
+                                warn!("Failed to update verification history: {e}");
+                            }
+                            if new_status == VerifyJobStatus::Success {
+                                reconcile_with_explorer(api_client, job_id);
+                            }
                         }
                     }
                     Ok(None) => {
@@ -795,22 +2612,35 @@ pub fn watch_batch(
                     Err(e) => {
                         warn!("Failed to check job {job_id}: {e}");
                         result.error = Some(e.to_string());
+                        result.suggestions = e.suggestions();
                     }
                 }
             }
         }
 
         // Display status update
-        if output_format == &crate::cli::args::OutputFormat::Text {
-            print_batch_status_inline(&updated_results, iteration);
+        if matches!(
+            output_format,
+            crate::cli::args::OutputFormat::Text | crate::cli::args::OutputFormat::Table
+        ) {
+            print_batch_status_inline(&mut live, &updated_results, iteration);
         }
 
         if all_complete {
-            println!(); // Newline after inline status
+            live.finish();
             break;
         }
 
-        std::thread::sleep(std::time::Duration::from_secs(5));
+        let Some(delay) =
+            strategy.next_delay_within(iteration, api_client.retry_after(), started.elapsed())
+        else {
+            live.finish();
+            sink::warning(format!(
+                "Warning: Stopped watching after {iteration} checks; some jobs are still pending. Use 'voyager status --job <JOB_ID>' to follow up."
+            ));
+            break;
+        };
+        std::thread::sleep(delay);
     }
 
     Ok(BatchVerificationSummary {
@@ -821,9 +2651,11 @@ pub fn watch_batch(
 }
 
 /// Print batch verification status inline (for live updates)
-fn print_batch_status_inline(results: &[BatchVerificationResult], _iteration: u32) {
-    use std::io::Write;
-
+fn print_batch_status_inline(
+    live: &mut LiveStatus,
+    results: &[BatchVerificationResult],
+    _iteration: u32,
+) {
     let succeeded = results
         .iter()
         .filter(|r| matches!(r.status, Some(VerifyJobStatus::Success)))
@@ -853,16 +2685,62 @@ fn print_batch_status_inline(results: &[BatchVerificationResult], _iteration: u3
         })
         .count();
 
-    print!(
-        "\r\x1B[2K  {} {} | {} {} | {} {}",
-        "✓".green(),
-        format!("{succeeded} Succeeded").green(),
-        "⏳".yellow(),
-        format!("{pending} Pending").yellow(),
-        "✗".red(),
-        format!("{failed} Failed").red()
+    let counts = format!(
+        "  {} {} | {} {} | {} {}",
+        sink::marker(Marker::Success),
+        sink::paint(format!("{succeeded} Succeeded"), Tone::Success),
+        sink::marker(Marker::Pending),
+        sink::paint(format!("{pending} Pending"), Tone::Pending),
+        sink::marker(Marker::Failure),
+        sink::paint(format!("{failed} Failed"), Tone::Failure)
     );
-    std::io::stdout().flush().ok();
+    live.update(&format!("{succeeded}/{pending}/{failed}"), &counts);
+}
+
+/// Look up whether a class is already verified, for the `--skip-if-verified` fast path
+///
+/// Returns the verification info only if the class is verified. Lookup failures
+/// are logged and treated as "not verified" so that a flaky check endpoint
+/// never blocks a submission.
+#[must_use]
+pub fn already_verified(
+    api_client: &ApiClient,
+    class_hash: &super::class_hash::ClassHash,
+) -> Option<ClassVerificationInfo> {
+    match api_client.check_class_verification(class_hash) {
+        Ok(ClassVerification::Verified(info)) => Some(info),
+        Ok(ClassVerification::NotVerified | ClassVerification::NotDeclared) => None,
+        Err(e) => {
+            warn!("Could not check whether {class_hash} is already verified: {e}");
+            None
+        }
+    }
+}
+
+/// Confirm on the Starknet node at `rpc_url` that `class_hash` is declared
+///
+/// When `network` is known, the node is first checked to be on its chain, so
+/// that a node of another network does not report the class as missing.
+///
+/// # Errors
+///
+/// Returns [`CliError::NotDeclared`] if the class is not declared, or an RPC
+/// error if the node cannot be queried or is on another chain.
+pub fn ensure_declared(
+    rpc_url: &Url,
+    class_hash: &super::class_hash::ClassHash,
+    network: Option<&NetworkKind>,
+) -> Result<(), CliError> {
+    let rpc = RpcClient::new(rpc_url.clone());
+    if let Some(expected) = network.and_then(voyager::chain_id) {
+        rpc.ensure_chain_id(expected)?;
+    }
+    if rpc.is_declared(class_hash.as_ref())? {
+        debug!("Class {class_hash} is declared according to {rpc_url}");
+        Ok(())
+    } else {
+        Err(CliError::NotDeclared(class_hash.clone()))
+    }
 }
 
 /// Display batch verification summary
@@ -906,79 +2784,173 @@ pub fn display_batch_summary(summary: &BatchVerificationSummary) {
         })
         .count();
 
-    println!("\n{}", "═".repeat(60).bright_cyan());
-    println!("{}", "Batch Verification Summary".bright_cyan().bold());
-    println!("{}", "═".repeat(60).bright_cyan());
-    println!("Total contracts:  {}", summary.total);
-    println!("Submitted:        {}", summary.submitted.to_string().cyan());
-    println!("Succeeded:        {}", succeeded.to_string().green());
-    println!("Failed:           {}", failed.to_string().red());
-    println!("Pending:          {}", pending.to_string().yellow());
-    println!("{}", "═".repeat(60).bright_cyan());
+    sink::line(format!("\n{}", sink::paint("═".repeat(60), Tone::Heading)));
+    sink::line(sink::paint("Batch Verification Summary", Tone::Heading));
+    sink::line(sink::paint("═".repeat(60), Tone::Heading));
+    sink::line(format!("Total contracts:  {}", summary.total));
+    sink::line(format!(
+        "Submitted:        {}",
+        sink::paint(summary.submitted, Tone::Accent)
+    ));
+    sink::line(format!(
+        "Succeeded:        {}",
+        sink::paint(succeeded, Tone::Success)
+    ));
+    sink::line(format!(
+        "Failed:           {}",
+        sink::paint(failed, Tone::Failure)
+    ));
+    sink::line(format!(
+        "Pending:          {}",
+        sink::paint(pending, Tone::Pending)
+    ));
+    sink::line(sink::paint("═".repeat(60), Tone::Heading));
 
     // Show detailed results
-    println!("\n{}", "Contract Details:".bright_white().bold());
+    sink::line(format!(
+        "\n{}",
+        sink::paint("Contract Details:", Tone::Emphasis)
+    ));
     for result in &summary.results {
-        let contract_name = result.contract.contract_name.bright_white().bold();
-        let class_hash_short = format!(
-            "{}...{}",
-            &result.contract.class_hash.to_string()[..10],
-            &result.contract.class_hash.to_string()
-                [result.contract.class_hash.to_string().len() - 6..]
-        );
+        display_batch_result(result);
+    }
+    sink::line("");
+}
 
-        match (&result.status, &result.error) {
-            (Some(VerifyJobStatus::Success), _) => {
-                let job_id = result.job_id.as_deref().unwrap_or("-");
-                println!(
-                    "  {} {} ({})",
-                    "✓".green().bold(),
-                    contract_name,
-                    class_hash_short.bright_black()
-                );
-                println!("    Job ID: {}", job_id.cyan());
-            }
-            (Some(VerifyJobStatus::Fail | VerifyJobStatus::CompileFailed), _) => {
-                println!(
-                    "  {} {} ({})",
-                    "✗".red().bold(),
-                    contract_name,
-                    class_hash_short.bright_black()
-                );
-                println!("    Status: {}", "Failed".red());
-            }
-            (Some(status), _) => {
-                let job_id = result.job_id.as_deref().unwrap_or("-");
-                println!(
-                    "  {} {} ({})",
-                    "⏳".yellow(),
-                    contract_name,
-                    class_hash_short.bright_black()
-                );
-                println!("    Status: {}", status.to_string().yellow());
-                println!("    Job ID: {}", job_id.cyan());
-            }
-            (None, Some(err)) => {
-                println!(
-                    "  {} {} ({})",
-                    "✗".red().bold(),
-                    contract_name,
-                    class_hash_short.bright_black()
-                );
-                // Extract just the first line of the error (before suggestions)
-                let error_line = err.lines().next().unwrap_or(err);
-                println!("    Error: {}", error_line.red());
-            }
-            (None, None) => {
-                println!(
-                    "  {} {} ({})",
-                    "○".bright_black(),
-                    contract_name,
-                    class_hash_short.bright_black()
-                );
-                println!("    Status: Not submitted");
+/// Display the details of one contract in the batch summary
+fn display_batch_result(result: &BatchVerificationResult) {
+    let contract_name = sink::paint(&result.contract.contract_name, Tone::Emphasis);
+    let class_hash_short = format!(
+        "{}...{}",
+        &result.contract.class_hash.to_string()[..10],
+        &result.contract.class_hash.to_string()[result.contract.class_hash.to_string().len() - 6..]
+    );
+
+    match (&result.status, &result.error) {
+        (Some(VerifyJobStatus::Success), _) => {
+            sink::line(format!(
+                "  {} {} ({})",
+                sink::marker(Marker::Success),
+                contract_name,
+                sink::paint(&class_hash_short, Tone::Muted)
+            ));
+            match result.job_id.as_deref() {
+                Some(job_id) => {
+                    sink::line(format!("    Job ID: {}", sink::paint(job_id, Tone::Accent)));
+                }
+                // Only skipped contracts succeed without a job
+                None => sink::line(format!(
+                    "    Status: {}",
+                    sink::paint("Already verified", Tone::Success)
+                )),
             }
         }
+        (Some(VerifyJobStatus::Fail | VerifyJobStatus::CompileFailed), _) => {
+            sink::line(format!(
+                "  {} {} ({})",
+                sink::marker(Marker::Failure),
+                contract_name,
+                sink::paint(&class_hash_short, Tone::Muted)
+            ));
+            sink::line(format!(
+                "    Status: {}",
+                sink::paint("Failed", Tone::Failure)
+            ));
+        }
+        (Some(status), _) => {
+            let job_id = result.job_id.as_deref().unwrap_or("-");
+            sink::line(format!(
+                "  {} {} ({})",
+                sink::marker(Marker::Pending),
+                contract_name,
+                sink::paint(&class_hash_short, Tone::Muted)
+            ));
+            sink::line(format!(
+                "    Status: {}",
+                sink::paint(status, Tone::Pending)
+            ));
+            sink::line(format!("    Job ID: {}", sink::paint(job_id, Tone::Accent)));
+        }
+        (None, Some(err)) => {
+            sink::line(format!(
+                "  {} {} ({})",
+                sink::marker(Marker::Failure),
+                contract_name,
+                sink::paint(&class_hash_short, Tone::Muted)
+            ));
+            // Extract just the first line of the error (before suggestions)
+            let error_line = err.lines().next().unwrap_or(err);
+            sink::line(format!(
+                "    Error: {}",
+                sink::paint(error_line, Tone::Failure)
+            ));
+        }
+        (None, None) => {
+            sink::line(format!(
+                "  {} {} ({})",
+                sink::marker(Marker::Skipped),
+                contract_name,
+                sink::paint(&class_hash_short, Tone::Muted)
+            ));
+            sink::line("    Status: Not submitted");
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn contract(name: &str, depends_on: &[&str]) -> ContractConfig {
+        ContractConfig {
+            class_hash: "0x1234".to_string(),
+            contract_name: name.to_string(),
+            package: None,
+            contract_file: None,
+            depends_on: depends_on.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_submission_order() {
+        let contracts = [
+            contract("Router", &["Vault", "Token"]),
+            contract("Token", &[]),
+            contract("Vault", &["Token"]),
+            contract("Oracle", &[]),
+        ];
+        assert_eq!(submission_order(&contracts).unwrap(), [1, 2, 0, 3]);
+
+        let missing = [contract("Router", &["Vault"])];
+        assert!(matches!(
+            submission_order(&missing),
+            Err(CliError::InvalidContractDependencies { .. })
+        ));
+
+        let cycle = [
+            contract("Token", &["Vault"]),
+            contract("Vault", &["Token"]),
+            contract("Oracle", &[]),
+        ];
+        let Err(CliError::InvalidContractDependencies { reason }) = submission_order(&cycle) else {
+            panic!("cycle not detected");
+        };
+        assert_eq!(reason, "dependency cycle between Token, Vault");
+    }
+
+    #[test]
+    fn test_submission_waves() {
+        let contracts = [
+            contract("Router", &["Vault", "Token"]),
+            contract("Token", &[]),
+            contract("Vault", &["Token"]),
+            contract("Oracle", &[]),
+        ];
+        let order = submission_order(&contracts).unwrap();
+        assert_eq!(
+            submission_waves(&contracts, &order),
+            [vec![(0, 1), (3, 3)], vec![(1, 2)], vec![(2, 0)]]
+        );
     }
-    println!();
 }
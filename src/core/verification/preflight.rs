@@ -0,0 +1,157 @@
+//! Local build of a payload before it is submitted
+//!
+//! `voyager verify --preflight` writes the files about to be uploaded, as the
+//! payload transforms leave them, to a temporary directory and builds them
+//! there with `scarb build`, or `sozo build` for Dojo projects. Only the
+//! submitted files are present, so a project that compiles locally thanks to
+//! files left out of the payload fails here instead of on the remote compiler.
+
+use crate::api::PayloadFiles;
+use crate::core::project::ProjectType;
+use crate::filesystem::collector::ScratchDir;
+use crate::utils::errors::CliError;
+use camino::Utf8Path;
+use log::info;
+use std::process::Command;
+
+/// Most diagnostics reported when the build fails
+const MAX_DIAGNOSTICS: usize = 10;
+
+/// Lines of build output reported when no diagnostic can be singled out
+const FALLBACK_LINES: usize = 20;
+
+/// Build `files` as the remote compiler would
+///
+/// The build runs in `project_dir_path`, relative to the files, for
+/// `package`.
+///
+/// # Errors
+///
+/// Returns [`CliError::PreflightBuildFailed`] with the compiler diagnostics if
+/// the files do not compile, or [`CliError::LocalBuildFailed`] if the build
+/// tool cannot be run.
+pub fn build(
+    files: &PayloadFiles,
+    project_dir_path: &str,
+    package: &str,
+    project_type: ProjectType,
+) -> Result<(), CliError> {
+    let (program, args) = match project_type {
+        ProjectType::Dojo => ("sozo", vec!["build"]),
+        ProjectType::Scarb | ProjectType::Auto => ("scarb", vec!["build", "--package", package]),
+    };
+    let command = format!("{program} {}", args.join(" "));
+    let build_failed = |status: String| CliError::LocalBuildFailed {
+        command: command.clone(),
+        status,
+    };
+
+    let scratch = ScratchDir::new("preflight").map_err(|e| build_failed(e.to_string()))?;
+    for (name, contents) in files {
+        scratch
+            .write(name, contents.as_bytes())
+            .map_err(|e| build_failed(format!("failed to write {name}: {e}")))?;
+    }
+
+    info!(
+        "Preflight: running '{command}' on the {} file(s) to submit",
+        files.len()
+    );
+    let output = Command::new(program)
+        .args(&args)
+        .current_dir(scratch.path().join(project_dir_path))
+        .output()
+        .map_err(|e| build_failed(e.to_string()))?;
+    if output.status.success() {
+        info!("Preflight build succeeded");
+        return Ok(());
+    }
+
+    let log = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Err(CliError::PreflightBuildFailed {
+        command,
+        diagnostics: diagnostics(&log, scratch.path()),
+    })
+}
+
+/// The errors reported in build `output`, with paths relative to `root`
+///
+/// Each diagnostic is an `error` line with the location and snippet lines
+/// that follow it. Without any, the end of the output is returned instead.
+fn diagnostics(output: &str, root: &Utf8Path) -> Vec<String> {
+    let output = output.replace(&format!("{root}/"), "");
+    let mut blocks = Vec::new();
+    let mut current: Option<Vec<&str>> = None;
+    for line in output.lines() {
+        let summary = line.starts_with("error: could not compile");
+        if line.starts_with("error") && !summary {
+            blocks.extend(current.replace(vec![line]).map(|block| block.join("\n")));
+        } else if summary || line.trim().is_empty() || line.starts_with("warning") {
+            blocks.extend(current.take().map(|block| block.join("\n")));
+        } else if let Some(block) = current.as_mut() {
+            block.push(line);
+        }
+    }
+    blocks.extend(current.map(|block| block.join("\n")));
+
+    if blocks.is_empty() {
+        let lines: Vec<&str> = output
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let tail = lines[lines.len().saturating_sub(FALLBACK_LINES)..].join("\n");
+        return vec![tail];
+    }
+    if blocks.len() > MAX_DIAGNOSTICS {
+        let more = blocks.len() - MAX_DIAGNOSTICS;
+        blocks.truncate(MAX_DIAGNOSTICS);
+        blocks.push(format!("... and {more} more error(s)"));
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics() {
+        let root = Utf8Path::new("/tmp/voyager-preflight-42");
+        let output = "   Compiling token v0.1.0 (/tmp/voyager-preflight-42/Scarb.toml)
+warning: Unused variable.
+ --> /tmp/voyager-preflight-42/src/lib.cairo:3:9
+        let x = 1;
+            ^
+
+error: Identifier not found.
+ --> /tmp/voyager-preflight-42/src/token.cairo:12:5
+    mint();
+    ^^^^
+
+error[E0006]: Module file not found. Expected path: /tmp/voyager-preflight-42/src/utils.cairo
+ --> /tmp/voyager-preflight-42/src/lib.cairo:2:1
+mod utils;
+^^^^^^^^^^
+error: could not compile `token` due to previous error
+";
+        assert_eq!(
+            diagnostics(output, root),
+            [
+                "error: Identifier not found.\n --> src/token.cairo:12:5\n    mint();\n    ^^^^",
+                "error[E0006]: Module file not found. Expected path: src/utils.cairo\n --> src/lib.cairo:2:1\nmod utils;\n^^^^^^^^^^",
+            ]
+        );
+
+        assert_eq!(
+            diagnostics(
+                "Updating git repository\nfailed to resolve dependencies\n",
+                root
+            ),
+            ["Updating git repository\nfailed to resolve dependencies"]
+        );
+    }
+}
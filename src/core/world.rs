@@ -0,0 +1,313 @@
+//! Classes registered with a deployed Dojo world
+//!
+//! `voyager verify --world <ADDRESS>` reads the systems, models and events
+//! of a world from the events it emitted when they were registered, so a
+//! world can be verified without its manifest. Systems keep their address
+//! across upgrades, so their current class is read from the node; models and
+//! events are redeployed on upgrade, and their `ModelUpgraded` and
+//! `EventUpgraded` events are followed to the latest class.
+
+use super::{
+    class_hash::ClassHash,
+    deployments::{Deployment, DojoResource},
+};
+use crate::api::{BlockId, EmittedEvent, RpcClient, RpcError};
+use log::{debug, warn};
+use std::collections::HashMap;
+
+/// `sn_keccak("ContractRegistered")`
+const CONTRACT_REGISTERED: &str =
+    "0x206ba27d5bbda42a63e108ee1ac7a6455c197ee34cd40a268e61b06f78dbc9a";
+
+/// `sn_keccak("ModelRegistered")`
+const MODEL_REGISTERED: &str = "0x2474f33346a1e5cfe0f6bedbc1facc0224efeb1be69ea9160b8dd8886a100dd";
+
+/// `sn_keccak("EventRegistered")`
+const EVENT_REGISTERED: &str = "0xb76210508ae32b1edabae03977822391fd60465414b2ddbdafbebd1f0240f8";
+
+/// `sn_keccak("ModelUpgraded")`
+const MODEL_UPGRADED: &str = "0x126edba6875c3928ffe266fef7bd0a9237bcc30f00131985c4f2f08963f7cf9";
+
+/// `sn_keccak("EventUpgraded")`
+const EVENT_UPGRADED: &str = "0x4bc60aab1cacb5fb8bcc476999b925f12908e8c710add9374a38851de14662";
+
+/// A resource as registered with the world
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Registration {
+    resource: DojoResource,
+    namespace: String,
+    name: String,
+    address: String,
+    class_hash: String,
+}
+
+/// The world class and the classes of the resources registered with the
+/// world at `world`
+///
+/// # Errors
+///
+/// Returns [`RpcError::ContractNotFound`] if nothing is deployed at `world`,
+/// or an error if a request fails.
+pub fn world_resources(
+    rpc: &RpcClient,
+    world: &str,
+) -> Result<Vec<(DojoResource, Deployment)>, RpcError> {
+    let world_class = rpc
+        .class_hash_at(world, BlockId::Latest)?
+        .ok_or_else(|| RpcError::ContractNotFound(world.to_string()))?;
+    let events = rpc.events(
+        world,
+        &[
+            CONTRACT_REGISTERED,
+            MODEL_REGISTERED,
+            EVENT_REGISTERED,
+            MODEL_UPGRADED,
+            EVENT_UPGRADED,
+        ],
+    )?;
+    debug!(
+        "World {world} emitted {} registration event(s)",
+        events.len()
+    );
+
+    let mut registrations = registrations(&events);
+    for registration in &mut registrations {
+        if registration.resource == DojoResource::Contract {
+            if let Some(class_hash) = rpc.class_hash_at(&registration.address, BlockId::Latest)? {
+                registration.class_hash = class_hash;
+            }
+        }
+    }
+
+    let world = deployment("world".to_string(), &world_class, world.to_string())
+        .map(|world| (DojoResource::World, world));
+    Ok(world
+        .into_iter()
+        .chain(registrations.into_iter().filter_map(|registration| {
+            let name = registration.name;
+            deployment(name, &registration.class_hash, registration.address)
+                .map(|deployment| (registration.resource, deployment))
+        }))
+        .collect())
+}
+
+fn deployment(name: String, class_hash: &str, address: String) -> Option<Deployment> {
+    match ClassHash::new(class_hash) {
+        Ok(class_hash) => Some(Deployment {
+            name,
+            class_hash,
+            address: Some(address),
+        }),
+        Err(e) => {
+            warn!("Skipping {name}: {e}");
+            None
+        }
+    }
+}
+
+/// Resources registered by `events`, at their latest model and event classes
+///
+/// Events that cannot be decoded are skipped.
+fn registrations(events: &[EmittedEvent]) -> Vec<Registration> {
+    let mut registrations = Vec::new();
+    // Address a model or event was upgraded from, to its new class and address
+    let mut upgrades: HashMap<&str, (&str, &str)> = HashMap::new();
+
+    for event in events {
+        let Some((selector, keys)) = event.keys.split_first() else {
+            continue;
+        };
+        // Nodes may pad felts with leading zeros
+        let selector = format!(
+            "0x{}",
+            selector.trim_start_matches("0x").trim_start_matches('0')
+        );
+        let resource = match selector.as_str() {
+            CONTRACT_REGISTERED => DojoResource::Contract,
+            MODEL_REGISTERED => DojoResource::Model,
+            EVENT_REGISTERED => DojoResource::Event,
+            MODEL_UPGRADED | EVENT_UPGRADED => {
+                if let [class_hash, address, prev_address, ..] = event.data.as_slice() {
+                    upgrades.insert(prev_address, (class_hash, address));
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let Some((name, keys)) = read_byte_array(keys) else {
+            debug!("Skipping registration event with undecodable name: {event:?}");
+            continue;
+        };
+        let Some((namespace, _)) = read_byte_array(keys) else {
+            debug!("Skipping registration event with undecodable namespace: {event:?}");
+            continue;
+        };
+        // Contracts are registered with their address first, other resources
+        // with their class hash
+        let (address, class_hash) = match (resource, event.data.as_slice()) {
+            (DojoResource::Contract, [address, class_hash, ..])
+            | (_, [class_hash, address, ..]) => (address.clone(), class_hash.clone()),
+            _ => continue,
+        };
+        registrations.push(Registration {
+            resource,
+            namespace,
+            name,
+            address,
+            class_hash,
+        });
+    }
+
+    for registration in &mut registrations {
+        if registration.resource == DojoResource::Contract {
+            continue;
+        }
+        // Each upgrade deploys the class at a new address
+        let mut hops = 0;
+        while let Some((class_hash, address)) = upgrades.get(registration.address.as_str()) {
+            registration.class_hash = (*class_hash).to_string();
+            registration.address = (*address).to_string();
+            hops += 1;
+            if hops > upgrades.len() {
+                break;
+            }
+        }
+        debug!(
+            "{} {}-{} is at class {}",
+            registration.resource,
+            registration.namespace,
+            registration.name,
+            registration.class_hash
+        );
+    }
+    registrations
+}
+
+/// Decode a Cairo `ByteArray` from the start of `felts`
+///
+/// A `ByteArray` is serialized as the number of full 31-byte words, the
+/// words, the pending word and its length in bytes. Returns the string and
+/// the remaining felts.
+fn read_byte_array(felts: &[String]) -> Option<(String, &[String])> {
+    let (count, rest) = felts.split_first()?;
+    let count = usize::try_from(parse_felt(count)?).ok()?;
+    if rest.len() < count + 2 {
+        return None;
+    }
+    let (words, rest) = rest.split_at(count);
+    let mut bytes = Vec::with_capacity(count * 31 + 31);
+    for word in words {
+        bytes.extend(felt_bytes(word, 31)?);
+    }
+    let pending_len = usize::try_from(parse_felt(&rest[1])?).ok()?;
+    if pending_len > 30 {
+        return None;
+    }
+    bytes.extend(felt_bytes(&rest[0], pending_len)?);
+    Some((String::from_utf8(bytes).ok()?, &rest[2..]))
+}
+
+/// Small felt as an integer
+fn parse_felt(felt: &str) -> Option<u64> {
+    u64::from_str_radix(felt.strip_prefix("0x")?, 16).ok()
+}
+
+/// The last `len` bytes of `felt`, big-endian
+fn felt_bytes(felt: &str, len: usize) -> Option<Vec<u8>> {
+    let digits = felt.strip_prefix("0x")?.trim_start_matches('0');
+    if digits.len() > len * 2 {
+        return None;
+    }
+    let digits = format!("{digits:0>width$}", width = len * 2);
+    (0..len)
+        .map(|i| u8::from_str_radix(&digits[2 * i..2 * i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    fn felts(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    /// `ByteArray` serialization of a string shorter than 31 bytes
+    fn short(text: &str) -> Vec<String> {
+        let hex = text.bytes().map(|byte| format!("{byte:02x}")).join("");
+        felts(&["0x0", &format!("0x{hex}"), &format!("{:#x}", text.len())])
+    }
+
+    fn registered(selector: &str, name: &str, data: &[&str]) -> EmittedEvent {
+        let mut keys = felts(&[selector]);
+        keys.extend(short(name));
+        keys.extend(short("game"));
+        EmittedEvent {
+            keys,
+            data: felts(data),
+            block_number: None,
+        }
+    }
+
+    #[test]
+    fn test_read_byte_array() {
+        let long = "a".repeat(31) + "bc";
+        let mut serialized = felts(&["0x1", &format!("0x{}", "61".repeat(31)), "0x6263", "0x2"]);
+        serialized.push("0x7".to_string());
+        let (text, rest) = read_byte_array(&serialized).unwrap();
+        assert_eq!(text, long);
+        assert_eq!(rest, ["0x7"]);
+
+        let serialized = short("actions");
+        let (text, rest) = read_byte_array(&serialized).unwrap();
+        assert_eq!(text, "actions");
+        assert!(rest.is_empty());
+
+        assert!(read_byte_array(&felts(&["0x2", "0x61"])).is_none());
+    }
+
+    #[test]
+    fn test_registrations_follow_model_upgrades() {
+        let events = [
+            registered(CONTRACT_REGISTERED, "actions", &["0xc1", "0xa1", "0x0"]),
+            registered(MODEL_REGISTERED, "Position", &["0xb1", "0xd1"]),
+            registered(EVENT_REGISTERED, "Moved", &["0xe1", "0xf1"]),
+            EmittedEvent {
+                keys: felts(&[MODEL_UPGRADED, "0x5e1ec7"]),
+                data: felts(&["0xb2", "0xd2", "0xd1"]),
+                block_number: None,
+            },
+            EmittedEvent {
+                keys: felts(&[MODEL_UPGRADED, "0x5e1ec7"]),
+                data: felts(&["0xb3", "0xd3", "0xd2"]),
+                block_number: None,
+            },
+            EmittedEvent {
+                keys: felts(&["0x1234"]),
+                data: Vec::new(),
+                block_number: None,
+            },
+        ];
+
+        let found: Vec<_> = registrations(&events)
+            .into_iter()
+            .map(|r| (r.resource, r.name, r.address, r.class_hash))
+            .collect();
+        let expected = [
+            (DojoResource::Contract, "actions", "0xc1", "0xa1"),
+            (DojoResource::Model, "Position", "0xd3", "0xb3"),
+            (DojoResource::Event, "Moved", "0xf1", "0xe1"),
+        ]
+        .map(|(resource, name, address, class_hash)| {
+            (
+                resource,
+                name.to_string(),
+                address.to_string(),
+                class_hash.to_string(),
+            )
+        });
+        assert_eq!(found, expected);
+    }
+}
@@ -6,18 +6,228 @@
 //! - Adding manifest files (Scarb.toml, workspace manifests)
 //! - Finding contract files
 //! - Converting paths to `FileInfo` structures
+//! - Estimating the size of the verification request
+//! - Fingerprinting the collected sources
 //! - Logging verification information
 
 use super::resolver;
-use crate::api::FileInfo;
+use crate::api::{Capabilities, FileInfo, PayloadFiles};
 use crate::cli::args::VerifyArgs;
+use crate::core::analyze::format_size;
 use crate::utils::{errors::CliError, license, voyager};
 use camino::{Utf8Path, Utf8PathBuf};
+use flate2::{write::GzEncoder, Compression};
 use itertools::Itertools;
 use log::{debug, info, warn};
 use scarb_metadata::PackageMetadata;
+use serde::Serialize;
 use std::collections::HashMap;
 
+/// Largest request the verification API accepts
+pub const MAX_PAYLOAD_SIZE: u64 = 1024 * 1024 * 10; // 10MB limit
+
+/// Largest single file accepted by default
+pub const MAX_FILE_SIZE: u64 = 1024 * 1024 * 20; // 20MB limit
+
+/// Documentation submitted with `--include-docs` unless `doc-paths` is
+/// configured, as globs relative to the workspace root and each package root
+pub const DEFAULT_DOC_PATHS: [&str; 3] = ["docs/**/*.md", "README.md", "README"];
+
+/// Size limits enforced on the files of a submission
+///
+/// Configured with `[voyager.limits]` and narrowed to the limits the API
+/// reports before uploading. No file can be larger than the payload it is
+/// part of, so the per-file limit in effect is never above the payload limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeLimits {
+    pub max_file_size: u64,
+    pub max_payload_size: u64,
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: MAX_FILE_SIZE,
+            max_payload_size: MAX_PAYLOAD_SIZE,
+        }
+    }
+}
+
+impl SizeLimits {
+    /// The stricter of these limits and the ones reported by the API
+    #[must_use]
+    pub fn with_server(self, capabilities: &Capabilities) -> Self {
+        Self {
+            max_file_size: capabilities
+                .max_file_size
+                .map_or(self.max_file_size, |limit| limit.min(self.max_file_size)),
+            max_payload_size: capabilities
+                .max_payload_size
+                .map_or(self.max_payload_size, |limit| {
+                    limit.min(self.max_payload_size)
+                }),
+        }
+    }
+
+    /// Largest single file allowed
+    #[must_use]
+    pub fn file_limit(&self) -> u64 {
+        self.max_file_size.min(self.max_payload_size)
+    }
+
+    /// Ensure no file and not the files together exceed the limits
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::FileSizeLimit`] for the first file over the file
+    /// limit, or [`CliError::PayloadTooLarge`] if the files together exceed
+    /// the payload limit.
+    pub fn check(&self, files: &[FileInfo]) -> Result<(), CliError> {
+        let mut sizes = file_sizes(files, self.file_limit())?;
+        let total = sizes.iter().map(|(_, size)| size).sum();
+        if total > self.max_payload_size {
+            sizes.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+            return Err(CliError::PayloadTooLarge {
+                size: total,
+                limit: self.max_payload_size,
+                largest: largest_listed(&sizes),
+            });
+        }
+        Ok(())
+    }
+
+    /// Ensure no file exceeds the file limit, leaving the payload limit to
+    /// the estimate of a compressed request
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::FileSizeLimit`] for the first file over the file
+    /// limit.
+    pub fn check_files(&self, files: &[FileInfo]) -> Result<(), CliError> {
+        file_sizes(files, self.max_file_size).map(|_| ())
+    }
+}
+
+/// Size on disk of each of `files`, by name, unless one exceeds `limit`
+fn file_sizes(files: &[FileInfo], limit: u64) -> Result<Vec<(String, u64)>, CliError> {
+    let mut sizes = Vec::with_capacity(files.len());
+    for file in files {
+        let Ok(metadata) = std::fs::metadata(&file.path) else {
+            continue;
+        };
+        if metadata.len() > limit {
+            return Err(CliError::FileSizeLimit {
+                path: Utf8PathBuf::try_from(file.path.clone())
+                    .unwrap_or_else(|_| file.name.clone().into()),
+                max_size: limit,
+                actual_size: metadata.len(),
+            });
+        }
+        sizes.push((file.name.clone(), metadata.len()));
+    }
+    Ok(sizes)
+}
+
+/// Number of files listed when a payload exceeds its limit
+const LARGEST_LISTED: usize = 5;
+
+/// Bytes the metadata fields of a verification request add to its files
+///
+/// Versions, names and paths stay well below this in practice.
+const REQUEST_OVERHEAD: u64 = 1024;
+
+/// Size of a verification request as serialized to JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadEstimate {
+    /// Bytes each file adds to the request, by name, largest first
+    pub files: Vec<(String, u64)>,
+    /// Bytes of the whole request, metadata included
+    pub total: u64,
+    /// Bytes of the whole request once gzip-compressed, if it is sent
+    /// compressed
+    pub compressed: Option<u64>,
+}
+
+impl PayloadEstimate {
+    /// Ensure the request, compressed if it is, fits in `limit` bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CliError::PayloadTooLarge`], listing the largest files, if
+    /// the request exceeds `limit`.
+    pub fn check(&self, limit: u64) -> Result<(), CliError> {
+        let size = self.compressed.unwrap_or(self.total);
+        if size > limit {
+            return Err(CliError::PayloadTooLarge {
+                size,
+                limit,
+                largest: largest_listed(&self.files),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Estimate the size of the verification request submitting `files`,
+/// gzip-compressed if `compress`
+///
+/// `files` are the contents after the payload transforms, such as the
+/// filtering of Scarb.toml, as returned by
+/// [`ApiClient::payload_files`](crate::api::ApiClient::payload_files).
+/// Escaping makes the request larger than the files are on disk.
+#[must_use]
+pub fn estimate_payload_size(files: &PayloadFiles, compress: bool) -> PayloadEstimate {
+    let json_len = |text: &str| serde_json::to_string(text).map_or(text.len(), |json| json.len());
+    let mut sizes: Vec<(String, u64)> = files
+        .iter()
+        .map(|(name, content)| {
+            // "name":"content",
+            let size = json_len(name) + json_len(content) + 2;
+            (name.clone(), size as u64)
+        })
+        .collect();
+    sizes.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    let total = REQUEST_OVERHEAD + sizes.iter().map(|(_, size)| size).sum::<u64>();
+    PayloadEstimate {
+        files: sizes,
+        total,
+        compressed: compress.then(|| REQUEST_OVERHEAD + compressed_size(files)),
+    }
+}
+
+/// Size of `files` serialized to JSON and gzip-compressed
+fn compressed_size(files: &PayloadFiles) -> u64 {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    // Writing to memory cannot fail
+    let _ = serde_json::to_writer(&mut encoder, files);
+    encoder
+        .finish()
+        .map_or(u64::MAX, |gzipped| gzipped.len() as u64)
+}
+
+/// The first [`LARGEST_LISTED`] of `sizes`, sorted largest first, for an
+/// error message
+fn largest_listed(sizes: &[(String, u64)]) -> Vec<String> {
+    sizes
+        .iter()
+        .take(LARGEST_LISTED)
+        .map(|(name, size)| format!("{name} ({})", format_size(*size)))
+        .collect()
+}
+
+/// Combined size in bytes of the given source files
+///
+/// Files that cannot be read count as empty; they are reported later when
+/// the payload is built.
+#[must_use]
+pub fn sources_size(sources: &[Utf8PathBuf]) -> u64 {
+    sources
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
 /// Prepare project for verification
 ///
 /// This is the main entry point for preparing a project's files for verification.
@@ -48,10 +258,16 @@ pub fn prepare_project_for_verification(
     packages: &[PackageMetadata],
     sources: &[Utf8PathBuf],
 ) -> Result<(Vec<FileInfo>, PackageMetadata, String, String), CliError> {
-    let prefix = resolver::biggest_common_prefix(sources, args.path.root_dir());
+    let prefix = resolver::biggest_common_prefix(sources, args.path.root_dir()?);
 
     // Build file map
-    let files = build_file_map(sources, &prefix, metadata, args)?;
+    let files = build_file_map(
+        sources,
+        &prefix,
+        metadata,
+        args.lock_file,
+        &args.size_limits,
+    )?;
 
     // Filter packages and get the target package
     let filtered_packages: Vec<&PackageMetadata> = args.package.as_ref().map_or_else(
@@ -71,8 +287,11 @@ pub fn prepare_project_for_verification(
             message: "contract_name should be present".to_string(),
         })?;
 
-    // Find contract file
-    let contract_file_path = find_contract_file(package_meta, sources, contract_name)?;
+    // Find contract file, unless it was given explicitly
+    let contract_file_path = match args.contract_file {
+        Some(ref path) => resolve_contract_file(path, args.path.root_dir()?, sources)?,
+        None => find_contract_file(package_meta, sources, contract_name)?,
+    };
     let contract_file =
         contract_file_path
             .strip_prefix(&prefix)
@@ -95,6 +314,109 @@ pub fn prepare_project_for_verification(
     ))
 }
 
+/// Collect the files of the directories given with `--extra-source`
+///
+/// Relative directories are resolved against `project_root`. Hidden entries
+/// and `target` build directories are skipped; every other file must pass
+/// [`validate_file_type`], so a directory cannot smuggle in unsupported files.
+///
+/// # Errors
+///
+/// Returns a `CliError` if a directory cannot be read or holds a file of an
+/// unsupported type
+pub fn collect_extra_sources(
+    dirs: &[Utf8PathBuf],
+    project_root: &Utf8Path,
+) -> Result<Vec<Utf8PathBuf>, CliError> {
+    let mut sources = vec![];
+    for dir in dirs {
+        let unreadable = |reason: String| CliError::ExtraSourceUnreadable {
+            path: dir.clone(),
+            reason,
+        };
+        let dir = project_root
+            .join(dir)
+            .canonicalize_utf8()
+            .map_err(|e| unreadable(e.to_string()))?;
+        if !dir.is_dir() {
+            return Err(unreadable("not a directory".to_string()));
+        }
+
+        let walker = walkdir::WalkDir::new(&dir)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                entry.depth() == 0 || !(name.starts_with('.') || name == "target")
+            });
+        for entry in walker {
+            let entry = entry.map_err(|e| unreadable(e.to_string()))?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path =
+                Utf8PathBuf::try_from(entry.into_path()).map_err(|e| unreadable(e.to_string()))?;
+            validate_file_type(&path)?;
+            sources.push(path);
+        }
+        debug!("Collected extra sources from {dir}");
+    }
+    Ok(sources)
+}
+
+/// Collect the documentation files matching `patterns` under each of `roots`
+///
+/// Patterns are globs relative to each root; `*` does not cross `/`, `**`
+/// does. Hidden entries and `target` build directories are skipped, and so
+/// are invalid patterns and matching files of types that cannot be submitted,
+/// with a warning.
+#[must_use]
+pub fn collect_docs(roots: &[&Utf8Path], patterns: &[String]) -> Vec<Utf8PathBuf> {
+    let mut globs = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        match globset::GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+        {
+            Ok(glob) => {
+                globs.add(glob);
+            }
+            Err(e) => warn!("Ignoring invalid doc path '{pattern}': {e}"),
+        }
+    }
+    let Ok(globs) = globs.build() else {
+        return vec![];
+    };
+
+    let mut docs = vec![];
+    for root in roots {
+        let walker = walkdir::WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                entry.depth() == 0 || !(name.starts_with('.') || name == "target")
+            })
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file());
+        for entry in walker {
+            let Ok(path) = Utf8PathBuf::try_from(entry.into_path()) else {
+                continue;
+            };
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if !globs.is_match(relative.as_str()) || docs.contains(&path) {
+                continue;
+            }
+            if let Err(e) = validate_file_type(&path) {
+                warn!("Skipping documentation file {path}: {}", e.error_code());
+                continue;
+            }
+            docs.push(path);
+        }
+    }
+    docs
+}
+
 /// Build file map
 ///
 /// Creates a map of relative file paths to absolute file paths, including:
@@ -109,7 +431,8 @@ pub fn prepare_project_for_verification(
 /// * `sources` - Source file paths
 /// * `prefix` - Common prefix to strip from paths
 /// * `metadata` - Scarb metadata
-/// * `args` - Verification arguments
+/// * `include_lock_file` - Whether to add the workspace's Scarb.lock (`--lock-file`)
+/// * `limits` - Size limits files are validated against
 ///
 /// # Returns
 ///
@@ -122,7 +445,8 @@ pub fn build_file_map(
     sources: &[Utf8PathBuf],
     prefix: &Utf8Path,
     metadata: &scarb_metadata::Metadata,
-    args: &VerifyArgs,
+    include_lock_file: bool,
+    limits: &SizeLimits,
 ) -> Result<HashMap<String, Utf8PathBuf>, CliError> {
     let mut files: HashMap<String, Utf8PathBuf> = sources
         .iter()
@@ -139,43 +463,47 @@ pub fn build_file_map(
     add_manifest_files(&mut files, metadata, prefix)?;
 
     // Add lock file if requested
-    add_lock_file_if_requested(&mut files, args, prefix)?;
+    add_lock_file_if_requested(
+        &mut files,
+        include_lock_file,
+        &metadata.workspace.root,
+        prefix,
+    )?;
 
     // Validate file sizes
-    validate_file_sizes(&files)?;
+    validate_file_sizes(&files, limits)?;
 
     Ok(files)
 }
 
 /// Validate file sizes
 ///
-/// Ensures all files are under the maximum size limit (20MB).
+/// Ensures all files are under the file size limit of `limits`.
 /// Also validates file types using `validate_file_type`.
 ///
 /// # Arguments
 ///
 /// * `files` - Map of files to validate
+/// * `limits` - Size limits to enforce
 ///
 /// # Errors
 ///
 /// Returns a `CliError` if any file exceeds the size limit or has invalid type
 pub fn validate_file_sizes<S: std::hash::BuildHasher>(
     files: &HashMap<String, Utf8PathBuf, S>,
+    limits: &SizeLimits,
 ) -> Result<(), CliError> {
-    const MAX_FILE_SIZE: usize = 1024 * 1024 * 20; // 20MB limit
-
     for path in files.values() {
         // Validate file type
         validate_file_type(path)?;
 
         // Validate file size
         if let Ok(metadata) = std::fs::metadata(path) {
-            let size = usize::try_from(metadata.len()).unwrap_or(MAX_FILE_SIZE + 1);
-            if size > MAX_FILE_SIZE {
+            if metadata.len() > limits.file_limit() {
                 return Err(CliError::FileSizeLimit {
                     path: path.clone(),
-                    max_size: MAX_FILE_SIZE,
-                    actual_size: size,
+                    max_size: limits.file_limit(),
+                    actual_size: metadata.len(),
                 });
             }
         }
@@ -320,7 +648,8 @@ pub fn add_workspace_manifest_if_needed<S: std::hash::BuildHasher>(
 /// # Arguments
 ///
 /// * `files` - File map to add to
-/// * `args` - Verification arguments
+/// * `requested` - Whether the --lock-file flag is set
+/// * `workspace_root` - Directory containing Scarb.lock
 /// * `prefix` - Common prefix to strip from paths
 ///
 /// # Errors
@@ -328,11 +657,12 @@ pub fn add_workspace_manifest_if_needed<S: std::hash::BuildHasher>(
 /// Returns a `CliError` if path manipulation fails
 pub fn add_lock_file_if_requested<S: std::hash::BuildHasher>(
     files: &mut HashMap<String, Utf8PathBuf, S>,
-    args: &VerifyArgs,
+    requested: bool,
+    workspace_root: &Utf8Path,
     prefix: &Utf8Path,
 ) -> Result<(), CliError> {
-    if args.lock_file {
-        let lock_file_path = args.path.root_dir().join("Scarb.lock");
+    if requested {
+        let lock_file_path = workspace_root.join("Scarb.lock");
         if lock_file_path.exists() {
             let lock_file_rel =
                 lock_file_path
@@ -350,6 +680,44 @@ pub fn add_lock_file_if_requested<S: std::hash::BuildHasher>(
     Ok(())
 }
 
+/// Resolve a contract file given with `--contract-file`
+///
+/// Relative paths are resolved against the project root. The file must exist
+/// and be one of the collected source files, since the API only receives those.
+///
+/// # Errors
+///
+/// Returns [`CliError::ContractFileNotFound`] if the file does not exist, or
+/// [`CliError::ContractFileNotCollected`] if it is not among `sources`.
+pub fn resolve_contract_file(
+    path: &Utf8Path,
+    root: &Utf8Path,
+    sources: &[Utf8PathBuf],
+) -> Result<Utf8PathBuf, CliError> {
+    let full_path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        root.join(path)
+    };
+
+    let canonical = full_path
+        .canonicalize_utf8()
+        .map_err(|_| CliError::ContractFileNotFound {
+            path: full_path.clone(),
+        })?;
+
+    sources
+        .iter()
+        .find(|source| {
+            source
+                .canonicalize_utf8()
+                .is_ok_and(|source| source == canonical)
+        })
+        .cloned()
+        .inspect(|source| debug!("Using contract file from --contract-file: {source}"))
+        .ok_or(CliError::ContractFileNotCollected { path: full_path })
+}
+
 /// Find contract file
 ///
 /// Locates the main contract file for verification by searching for the actual
@@ -439,6 +807,58 @@ pub fn find_contract_file(
     Ok(contract_file_path)
 }
 
+/// Whether the sources of `package` define the Starknet contract `contract_name`
+#[must_use]
+pub fn package_defines_contract(package: &PackageMetadata, contract_name: &str) -> bool {
+    resolver::package_sources(package)
+        .map_err(|e| debug!("Failed to collect sources of {}: {e}", package.name))
+        .is_ok_and(|sources| {
+            find_contract_by_pattern(&sources, contract_name, &package.root).is_some()
+        })
+}
+
+/// A Starknet contract defined in the sources of a package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContractDefinition {
+    /// Module name, as given to `--contract-name`
+    pub name: String,
+    /// File defining the module
+    pub file: Utf8PathBuf,
+}
+
+/// Starknet contracts defined in the sources of `package`
+///
+/// Scans the same files as [`package_defines_contract`], so every name
+/// returned is found by `--contract-name`. Contracts are sorted by file, then
+/// by their position in it.
+///
+/// # Errors
+///
+/// Returns an error if the sources of the package cannot be collected.
+pub fn package_contracts(
+    package: &PackageMetadata,
+) -> Result<Vec<ContractDefinition>, resolver::Error> {
+    let mut sources = resolver::package_sources(package)?;
+    sources.retain(|path| path.starts_with(&package.root) && path.extension() == Some("cairo"));
+    sources.sort();
+
+    let mut contracts = Vec::new();
+    for file in sources {
+        match std::fs::read_to_string(&file) {
+            Ok(content) => {
+                contracts.extend(contract_definitions(&content).into_iter().map(|name| {
+                    ContractDefinition {
+                        name,
+                        file: file.clone(),
+                    }
+                }));
+            }
+            Err(e) => debug!("Failed to read file {file}: {e}"),
+        }
+    }
+    Ok(contracts)
+}
+
 /// Find contract file by searching for the Starknet contract definition pattern
 ///
 /// Searches through all Cairo source files for the pattern:
@@ -504,7 +924,16 @@ fn find_contract_by_pattern(
 ///
 /// Returns true if the contract definition is found
 fn contains_contract_definition(content: &str, contract_name: &str) -> bool {
+    contract_definitions(content)
+        .iter()
+        .any(|module_name| module_name == contract_name)
+}
+
+/// Names of the modules marked `#[starknet::contract]` in `content`, in
+/// the order they are defined
+fn contract_definitions(content: &str) -> Vec<String> {
     let lines: Vec<&str> = content.lines().collect();
+    let mut names = Vec::new();
 
     for (i, line) in lines.iter().enumerate() {
         let trimmed = line.trim();
@@ -523,17 +952,14 @@ fn contains_contract_definition(content: &str, contract_name: &str) -> bool {
 
                 // Look for module declaration: "pub mod ContractName" or "mod ContractName"
                 if let Some(module_name) = extract_module_name(next_line) {
-                    if module_name == contract_name {
-                        return true;
-                    }
-                    // If we found a different module name, this isn't our contract
+                    names.push(module_name);
                     break;
                 }
             }
         }
     }
 
-    false
+    names
 }
 
 /// Extract module name from a Cairo module declaration line
@@ -631,6 +1057,98 @@ pub fn convert_to_file_info<S: std::hash::BuildHasher>(
         .collect_vec()
 }
 
+/// Compute a fingerprint of the files submitted for verification
+///
+/// The fingerprint is a SHA-256 digest over every file's relative name and
+/// contents, taken in name order, so the same sources always produce the same
+/// fingerprint regardless of the machine or collection order. It is rendered as
+/// `sha256:<hex>`.
+///
+/// # Errors
+///
+/// Returns an error if any of the files cannot be read.
+pub fn source_fingerprint(file_infos: &[FileInfo]) -> std::io::Result<String> {
+    let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+
+    for file_info in file_infos.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+        let contents = std::fs::read(&file_info.path)?;
+        // Length prefixes keep name/content boundaries unambiguous
+        context.update(&(file_info.name.len() as u64).to_le_bytes());
+        context.update(file_info.name.as_bytes());
+        context.update(&(contents.len() as u64).to_le_bytes());
+        context.update(&contents);
+    }
+
+    let digest = context.finish();
+    Ok(format!(
+        "sha256:{}",
+        digest.as_ref().iter().map(|b| format!("{b:02x}")).join("")
+    ))
+}
+
+/// SHA-256 digest of a file's contents, rendered as `sha256:<hex>`
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn file_digest(path: &std::path::Path) -> std::io::Result<String> {
+    Ok(content_digest(&std::fs::read(path)?))
+}
+
+/// SHA-256 digest of `contents`, rendered as `sha256:<hex>`
+#[must_use]
+pub fn content_digest(contents: &[u8]) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, contents);
+    format!(
+        "sha256:{}",
+        digest.as_ref().iter().map(|b| format!("{b:02x}")).join("")
+    )
+}
+
+/// Temporary directory holding files written from memory, removed on drop
+#[derive(Debug)]
+pub struct ScratchDir {
+    path: Utf8PathBuf,
+    // Removes the directory when dropped
+    _dir: tempfile::TempDir,
+}
+
+impl ScratchDir {
+    /// Create a new directory named `voyager-<purpose>-<random>` in the
+    /// system's temporary directory, readable by the current user only
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be created.
+    pub fn new(purpose: &str) -> std::io::Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix(&format!("voyager-{purpose}-"))
+            .tempdir()?;
+        let path = Utf8PathBuf::try_from(dir.path().to_path_buf())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        Ok(Self { path, _dir: dir })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    /// Write `contents` to `name`, relative to the directory, and return its path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file or its parent directories cannot be written.
+    pub fn write(&self, name: &str, contents: &[u8]) -> std::io::Result<Utf8PathBuf> {
+        let path = self.path.join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+}
+
 /// Log verification info
 ///
 /// Logs detailed information about the verification job including:
@@ -668,3 +1186,270 @@ pub fn log_verification_info(
         info!("{}", file_info.path.display());
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_estimate_payload_size() {
+        let files = PayloadFiles::from([
+            ("src/lib.cairo".to_string(), "fn main() {}\n".to_string()),
+            (
+                "Scarb.toml".to_string(),
+                "[package]\nname = \"a\"\n".to_string(),
+            ),
+        ]);
+        let estimate = estimate_payload_size(&files, false);
+        // Quotes and newlines are escaped in the request
+        assert_eq!(
+            estimate.files,
+            [
+                ("Scarb.toml".to_string(), 41),
+                ("src/lib.cairo".to_string(), 33)
+            ]
+        );
+        assert_eq!(estimate.total, REQUEST_OVERHEAD + 74);
+        assert!(estimate.check(REQUEST_OVERHEAD + 74).is_ok());
+
+        let sources =
+            PayloadFiles::from([("src/lib.cairo".to_string(), "fn main() {}\n".repeat(1000))]);
+        let estimate = estimate_payload_size(&sources, true);
+        let compressed = estimate.compressed.unwrap();
+        assert!(compressed < estimate.total / 10, "{estimate:?}");
+        assert!(estimate.check(compressed).is_ok());
+        assert!(estimate.check(compressed - 1).is_err());
+
+        let err = estimate_payload_size(&files, false)
+            .check(1000)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Largest files:\n  • Scarb.toml (41 B)\n  • src/lib.cairo (33 B)\n"));
+    }
+
+    fn write_files(dir: &TempDir, files: &[(&str, &str)]) -> Vec<FileInfo> {
+        files
+            .iter()
+            .map(|(name, contents)| {
+                let path = dir.path().join(name);
+                std::fs::write(&path, contents).unwrap();
+                FileInfo {
+                    name: (*name).to_string(),
+                    path,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_source_fingerprint_ignores_order() {
+        let dir = TempDir::new().unwrap();
+        let mut files = write_files(&dir, &[("lib.cairo", "mod a;"), ("a.cairo", "fn f() {}")]);
+
+        let first = source_fingerprint(&files).unwrap();
+        files.reverse();
+        let second = source_fingerprint(&files).unwrap();
+
+        assert_eq!(first, second);
+        assert!(first.starts_with("sha256:"));
+        assert_eq!(first.len(), "sha256:".len() + 64);
+    }
+
+    #[test]
+    fn test_source_fingerprint_changes_with_contents() {
+        let dir = TempDir::new().unwrap();
+        let files = write_files(&dir, &[("lib.cairo", "mod a;")]);
+        let before = source_fingerprint(&files).unwrap();
+
+        std::fs::write(&files[0].path, "mod b;").unwrap();
+        let after = source_fingerprint(&files).unwrap();
+
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let dir = TempDir::new().unwrap();
+        let files = write_files(&dir, &[("lib.cairo", "0123456789"), ("a.cairo", "01234")]);
+
+        let limits = SizeLimits {
+            max_file_size: 100,
+            max_payload_size: 15,
+        };
+        assert_eq!(limits.file_limit(), 15);
+        assert!(limits.check(&files).is_ok());
+
+        let server = limits.with_server(&Capabilities {
+            max_file_size: None,
+            max_payload_size: Some(12),
+            ..Capabilities::default()
+        });
+        assert_eq!(server.max_file_size, 100);
+        assert!(matches!(
+            server.check(&files),
+            Err(CliError::PayloadTooLarge {
+                size: 15,
+                limit: 12,
+                ref largest,
+            }) if largest == &["lib.cairo (10 B)", "a.cairo (5 B)"]
+        ));
+
+        let server = limits.with_server(&Capabilities {
+            max_file_size: Some(8),
+            max_payload_size: Some(1000),
+            ..Capabilities::default()
+        });
+        assert_eq!(server.max_payload_size, 15);
+        assert!(matches!(
+            server.check(&files),
+            Err(CliError::FileSizeLimit {
+                max_size: 8,
+                actual_size: 10,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_collect_extra_sources() {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf())
+            .unwrap()
+            .canonicalize_utf8()
+            .unwrap();
+        for name in [
+            "workspace/Scarb.toml",
+            "shared/math/src/lib.cairo",
+            "shared/math/README.md",
+            "shared/math/.git/config",
+            "shared/math/target/dev/math.sierra.json",
+        ] {
+            let path = root.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let project_root = root.join("workspace");
+        let mut sources = vec![project_root.join("Scarb.toml")];
+        sources.extend(
+            collect_extra_sources(&[Utf8PathBuf::from("../shared/math")], &project_root).unwrap(),
+        );
+        let prefix = resolver::biggest_common_prefix(&sources, &project_root);
+        let names: Vec<_> = sources
+            .iter()
+            .map(|path| path.strip_prefix(&prefix).unwrap().as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "workspace/Scarb.toml",
+                "shared/math/README.md",
+                "shared/math/src/lib.cairo"
+            ]
+        );
+
+        std::fs::write(root.join("shared/math/logo.png"), "").unwrap();
+        let error = collect_extra_sources(&[root.join("shared")], &project_root).unwrap_err();
+        assert_eq!(error.error_code(), "E024");
+        let error =
+            collect_extra_sources(&[Utf8PathBuf::from("missing")], &project_root).unwrap_err();
+        assert_eq!(error.error_code(), "E067");
+    }
+
+    #[test]
+    fn test_collect_docs() {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        for name in [
+            "README.md",
+            "docs/guide.md",
+            "docs/api/token.md",
+            "docs/logo.png",
+            "notes.md",
+            "token/README",
+            "token/docs/usage.md",
+            "token/target/doc/index.md",
+        ] {
+            let path = root.join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+
+        let relative = |docs: Vec<Utf8PathBuf>| -> Vec<String> {
+            docs.iter()
+                .map(|path| path.strip_prefix(&root).unwrap().to_string())
+                .collect()
+        };
+        let package = root.join("token");
+        let defaults = DEFAULT_DOC_PATHS.map(String::from);
+        assert_eq!(
+            relative(collect_docs(&[&root, &package], &defaults)),
+            [
+                "README.md",
+                "docs/api/token.md",
+                "docs/guide.md",
+                "token/README",
+                "token/docs/usage.md",
+            ]
+        );
+
+        let patterns = ["docs/*.*".to_string(), "[".to_string()];
+        assert_eq!(
+            relative(collect_docs(&[&root], &patterns)),
+            ["docs/guide.md"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_contract_file() {
+        let dir = TempDir::new().unwrap();
+        let root = Utf8PathBuf::try_from(dir.path().to_path_buf()).unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        for name in ["src/lib.cairo", "src/generated.cairo", "notes.cairo"] {
+            std::fs::write(root.join(name), "").unwrap();
+        }
+        let sources = vec![root.join("src/lib.cairo"), root.join("src/generated.cairo")];
+
+        let resolved =
+            resolve_contract_file(Utf8Path::new("src/generated.cairo"), &root, &sources).unwrap();
+        assert_eq!(resolved, root.join("src/generated.cairo"));
+
+        let absolute = root.join("src/lib.cairo");
+        assert_eq!(
+            resolve_contract_file(&absolute, &root, &sources).unwrap(),
+            absolute
+        );
+
+        let err =
+            resolve_contract_file(Utf8Path::new("src/missing.cairo"), &root, &sources).unwrap_err();
+        assert_eq!(err.error_code(), "E055");
+
+        let err = resolve_contract_file(Utf8Path::new("notes.cairo"), &root, &sources).unwrap_err();
+        assert_eq!(err.error_code(), "E056");
+    }
+
+    #[test]
+    fn test_contract_definitions() {
+        let content = r#"
+#[starknet::interface]
+pub trait IToken<T> {}
+
+#[starknet::contract]
+// The token itself
+pub mod MyToken {}
+
+#[starknet::contract]
+#[feature("deprecated")]
+mod Vault{
+}
+
+mod helpers {}
+"#;
+        assert_eq!(contract_definitions(content), ["MyToken", "Vault"]);
+        assert!(contains_contract_definition(content, "Vault"));
+        assert!(!contains_contract_definition(content, "helpers"));
+    }
+}
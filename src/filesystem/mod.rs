@@ -1,2 +1,3 @@
 pub mod collector;
 pub mod resolver;
+pub mod tar;
@@ -1,37 +1,71 @@
+use crate::utils::suggestion::{Suggestion, SuggestionList};
 use camino::{Utf8Path, Utf8PathBuf};
 use itertools::Itertools;
-use log::debug;
+use log::{debug, warn};
 use scarb_metadata::{Metadata, MetadataCommand, PackageMetadata};
 use serde::Deserialize;
 use std::{
     collections::{HashMap, HashSet},
     ffi::OsStr,
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 use thiserror::Error;
 use url::Url;
 use walkdir::WalkDir;
 
+// E025, E026 and E027 are shared with project type errors, which are the ones
+// `voyager explain` describes, so these lists are kept here.
+
+/// Suggestions for [`Error::TomlParseError`]
+const TOML_PARSE_SUGGESTIONS: &[&str] = &[
+    "Check TOML syntax is valid",
+    "Verify file is not corrupted",
+    "Use a TOML validator tool",
+];
+
+/// Suggestions for [`Error::IoError`]
+const IO_SUGGESTIONS: &[&str] = &[
+    "Check file exists and is readable",
+    "Verify file permissions",
+    "Ensure disk space is available",
+];
+
+/// Suggestions for [`Error::ModuleNotFound`]
+const MODULE_NOT_FOUND_SUGGESTIONS: &[&str] = &[
+    "Check that the module file exists",
+    "Verify module name spelling",
+    "Ensure proper file structure (module.rs or module/mod.rs)",
+];
+
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("[E012] Invalid dependency path for '{name}': {path}\n\nSuggestions:\n  • Check that the path exists and is accessible\n  • Use relative paths from the current directory\n  • Verify the path format is correct\n  • Example: path:../my-dependency")]
+    #[error("[E012] Invalid dependency path for '{name}': {path}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     DependencyPath { name: String, path: String },
 
-    #[error("[E013] Failed to read metadata for '{name}' at path: {path}\n\nSuggestions:\n  • Check that Scarb.toml exists at the specified path\n  • Verify the Scarb.toml file is valid\n  • Run 'scarb metadata' in the target directory to test\n  • Ensure scarb is installed and accessible")]
+    #[error("[E013] Failed to read metadata for '{name}' at path: {path}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     MetadataError { name: String, path: PathBuf },
 
-    #[error("[E014] Path contains invalid UTF-8 characters\n\nSuggestions:\n  • Use only ASCII characters in file paths\n  • Avoid special characters in directory names\n  • Check for hidden or control characters in the path")]
+    #[error("[E014] Path contains invalid UTF-8 characters{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Utf8(#[from] camino::FromPathBufError),
 
-    #[error("[E025] Failed to parse TOML file '{path}': {error}\n\nSuggestions:\n  • Check TOML syntax is valid\n  • Verify file is not corrupted\n  • Use a TOML validator tool")]
+    #[error("[E025] Failed to parse TOML file '{path}': {error}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     TomlParseError { path: String, error: String },
 
-    #[error("[E026] I/O error reading file '{path}': {error}\n\nSuggestions:\n  • Check file exists and is readable\n  • Verify file permissions\n  • Ensure disk space is available")]
+    #[error("[E026] I/O error reading file '{path}': {error}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     IoError { path: String, error: String },
 
-    #[error("[E027] Module not found: '{module}' from '{parent_file}'\n\nSuggestions:\n  • Check that the module file exists\n  • Verify module name spelling\n  • Ensure proper file structure (module.rs or module/mod.rs)")]
+    #[error("[E027] Module not found: '{module}' from '{parent_file}'{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     ModuleNotFound { module: String, parent_file: String },
+
+    #[error("[E078] Cannot {operation} '{path}' of package '{package}': {error}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    SourceUnreadable {
+        package: String,
+        path: String,
+        /// What was being done, e.g. "open" or "follow the symlink"
+        operation: &'static str,
+        error: String,
+    },
 }
 
 impl Error {
@@ -44,6 +78,23 @@ impl Error {
             Self::TomlParseError { .. } => "E025",
             Self::IoError { .. } => "E026",
             Self::ModuleNotFound { .. } => "E027",
+            Self::SourceUnreadable { .. } => "E078",
+        }
+    }
+
+    /// Next steps for making the sources of the package resolvable
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        match self {
+            Self::TomlParseError { .. } => Suggestion::from_templates(TOML_PARSE_SUGGESTIONS, &[]),
+            Self::IoError { .. } => Suggestion::from_templates(IO_SUGGESTIONS, &[]),
+            Self::ModuleNotFound { .. } => {
+                Suggestion::from_templates(MODULE_NOT_FOUND_SUGGESTIONS, &[])
+            }
+            Self::DependencyPath { .. }
+            | Self::MetadataError { .. }
+            | Self::Utf8(_)
+            | Self::SourceUnreadable { .. } => Suggestion::for_code(self.error_code(), &[]),
         }
     }
 }
@@ -149,64 +200,75 @@ pub fn package_sources_with_test_files(
         // Fall through to regular Cairo file collection
     }
 
-    let mut sources: Vec<Utf8PathBuf> = WalkDir::new(package_metadata.root.clone())
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|f| f.file_type().is_file())
-        .filter(|f| {
-            // Check if this is a test file
-            if let Some(path_str) = f.path().to_str() {
-                // Check if the path contains test directories but only if it's in src/
-                let is_in_src = path_str.contains("/src/");
-                let has_test_in_path = path_str.contains("/test") || path_str.contains("/tests/");
-
-                if is_in_src && has_test_in_path {
-                    // This is a test file in src/
-                    return include_test_files;
+    let mut sources: Vec<Utf8PathBuf> =
+        walk_package(&package_metadata.root, &package_metadata.name)?
+            .into_iter()
+            .filter(|f| f.path().is_file())
+            .filter(|f| {
+                // Check if this is a test file
+                if let Some(path_str) = f.path().to_str() {
+                    // Check if the path contains test directories but only if it's in src/
+                    let is_in_src = path_str.contains("/src/");
+                    let has_test_in_path =
+                        path_str.contains("/test") || path_str.contains("/tests/");
+
+                    if is_in_src && has_test_in_path {
+                        // This is a test file in src/
+                        return include_test_files;
+                    }
+
+                    // Exclude test directories outside src/
+                    if path_str.contains("/tests/")
+                        || path_str.contains("/test/")
+                        || path_str.contains("/examples/")
+                        || path_str.contains("/benchmarks/")
+                    {
+                        return false;
+                    }
                 }
 
-                // Exclude test directories outside src/
-                if path_str.contains("/tests/")
-                    || path_str.contains("/test/")
-                    || path_str.contains("/examples/")
-                    || path_str.contains("/benchmarks/")
-                {
-                    return false;
+                // Include Cairo files
+                if let Some(ext) = f.path().extension() {
+                    if ext == OsStr::new(CAIRO_EXT) {
+                        return true;
+                    }
+                    // Only include Rust files if the package has been validated as a procedural macro
+                    if ext == OsStr::new("rs") {
+                        // This will be handled by the procedural macro collection logic above
+                        // if this package is a valid procedural macro package
+                        return false;
+                    }
                 }
-            }
 
-            // Include Cairo files
-            if let Some(ext) = f.path().extension() {
-                if ext == OsStr::new(CAIRO_EXT) {
+                // Include Scarb.toml files
+                if f.file_name() == OsStr::new("Scarb.toml") {
                     return true;
                 }
-                // Only include Rust files if the package has been validated as a procedural macro
-                if ext == OsStr::new("rs") {
-                    // This will be handled by the procedural macro collection logic above
-                    // if this package is a valid procedural macro package
+
+                // Only include Cargo.toml if this package is a validated procedural macro
+                // (handled by the specialized collection above) or if there's no Cargo.toml
+                // indicating this is a pure Cairo package
+                if f.file_name() == OsStr::new("Cargo.toml") {
+                    // Don't include Cargo.toml files from non-procedural macro packages
+                    // They will be included by the specialized collection if they are valid proc macros
                     return false;
                 }
-            }
 
-            // Include Scarb.toml files
-            if f.file_name() == OsStr::new("Scarb.toml") {
-                return true;
-            }
-
-            // Only include Cargo.toml if this package is a validated procedural macro
-            // (handled by the specialized collection above) or if there's no Cargo.toml
-            // indicating this is a pure Cairo package
-            if f.file_name() == OsStr::new("Cargo.toml") {
-                // Don't include Cargo.toml files from non-procedural macro packages
-                // They will be included by the specialized collection if they are valid proc macros
-                return false;
-            }
+                false
+            })
+            .map(walkdir::DirEntry::into_path)
+            .map(Utf8PathBuf::try_from)
+            .try_collect()?;
 
-            false
-        })
-        .map(walkdir::DirEntry::into_path)
-        .map(Utf8PathBuf::try_from)
-        .try_collect()?;
+    // Fail here, naming the file, rather than when the payload is read
+    for source in &sources {
+        fs::File::open(source).map_err(|e| Error::SourceUnreadable {
+            package: package_metadata.name.clone(),
+            path: source.to_string(),
+            operation: "open",
+            error: e.to_string(),
+        })?;
+    }
 
     // Ensure the package's own manifest is included
     if !sources.contains(&package_metadata.manifest_path) {
@@ -238,6 +300,56 @@ pub fn package_sources_with_test_files(
     Ok(sources)
 }
 
+/// Every entry under `root`, the root of `package`
+///
+/// Symlinks are not followed into directories, but a symlinked source file
+/// must resolve. A directory that cannot be listed fails the walk if it is
+/// under `src/`, where sources live, and is skipped with a warning elsewhere.
+fn walk_package(root: &Utf8Path, package: &str) -> Result<Vec<walkdir::DirEntry>, Error> {
+    let unreadable = |path: &Path, operation, error: String| Error::SourceUnreadable {
+        package: package.to_string(),
+        path: path.display().to_string(),
+        operation,
+        error,
+    };
+    let src_dir = root.join("src");
+
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(root) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = e
+                    .path()
+                    .map_or_else(|| root.as_std_path().to_path_buf(), Path::to_path_buf);
+                let error = e
+                    .io_error()
+                    .map_or_else(|| e.to_string(), ToString::to_string);
+                if path.starts_with(&src_dir) || is_source_file(&path) {
+                    return Err(unreadable(&path, "read", error));
+                }
+                warn!(
+                    "Skipping '{}' of package '{package}': {error}",
+                    path.display()
+                );
+                continue;
+            }
+        };
+        if entry.path_is_symlink() && is_source_file(entry.path()) {
+            fs::metadata(entry.path())
+                .map_err(|e| unreadable(entry.path(), "follow the symlink", e.to_string()))?;
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Whether `path` names a Cairo source or a Scarb manifest
+fn is_source_file(path: &Path) -> bool {
+    path.extension() == Some(OsStr::new(CAIRO_EXT))
+        || path.file_name() == Some(OsStr::new("Scarb.toml"))
+}
+
 pub fn biggest_common_prefix<P: AsRef<Utf8Path> + Clone>(
     paths: &[Utf8PathBuf],
     first_guess: P,
@@ -703,7 +815,6 @@ fn should_exclude_rust_file(file_path: &Utf8Path) -> bool {
     false
 }
 
-use crate::cli::args::VerifyArgs;
 use crate::utils::{
     errors::{self, CliError},
     voyager,
@@ -718,7 +829,7 @@ use crate::utils::{
 /// # Arguments
 ///
 /// * `metadata` - Scarb metadata containing package information
-/// * `args` - Verification arguments with optional package filter
+/// * `package` - Optional package filter (`--package`)
 ///
 /// # Returns
 ///
@@ -731,23 +842,23 @@ use crate::utils::{
 /// - Workspace project detected without --package argument
 pub fn gather_packages_and_validate(
     metadata: &Metadata,
-    args: &VerifyArgs,
+    package: Option<&str>,
 ) -> Result<Vec<PackageMetadata>, CliError> {
     let mut packages: Vec<PackageMetadata> = vec![];
     gather_packages(metadata, &mut packages)?;
 
     // Filter packages based on --package argument
-    let filtered_packages: Vec<&PackageMetadata> = args.package.as_ref().map_or_else(
+    let filtered_packages: Vec<&PackageMetadata> = package.map_or_else(
         || packages.iter().collect(),
-        |package_id| packages.iter().filter(|p| p.name == *package_id).collect(),
+        |package_id| packages.iter().filter(|p| p.name == package_id).collect(),
     );
 
     // Validate package selection
     if filtered_packages.is_empty() {
-        if let Some(package_name) = &args.package {
+        if let Some(package_name) = package {
             let available_packages: Vec<String> = packages.iter().map(|p| p.name.clone()).collect();
             return Err(CliError::from(errors::MissingContract::new(
-                package_name.clone(),
+                package_name.to_string(),
                 available_packages,
             )));
         }
@@ -758,7 +869,7 @@ pub fn gather_packages_and_validate(
     let manifest_path = voyager::manifest_path(metadata);
     let is_workspace = workspace_manifest != manifest_path && metadata.workspace.members.len() > 1;
 
-    if args.package.is_none() && is_workspace {
+    if package.is_none() && is_workspace {
         let available_packages: Vec<String> = packages.iter().map(|p| p.name.clone()).collect();
         return Err(CliError::from(errors::MissingContract::new(
             "Workspace project detected - use --package argument".to_string(),
@@ -800,6 +911,75 @@ pub fn collect_source_files(
     Ok(sources)
 }
 
+/// Whether `pattern` is a glob rather than a package name
+#[must_use]
+pub fn is_package_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '[', '{'])
+}
+
+/// Workspace `members` matching any of the globs `patterns`
+///
+/// A member matches if its name or its directory relative to `workspace_root`
+/// does, so `contracts/*` selects every member under `contracts/`. `*` does
+/// not cross `/`.
+///
+/// # Errors
+///
+/// Returns an error if a pattern is not a valid glob.
+pub fn members_matching<'a>(
+    members: &'a [PackageMetadata],
+    workspace_root: &Utf8Path,
+    patterns: &[&str],
+) -> Result<Vec<&'a PackageMetadata>, globset::Error> {
+    let mut globs = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        globs.add(
+            globset::GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()?,
+        );
+    }
+    let globs = globs.build()?;
+
+    Ok(members
+        .iter()
+        .filter(|package| {
+            let dir = package
+                .root
+                .strip_prefix(workspace_root)
+                .unwrap_or(&package.root);
+            globs.is_match(&package.name) || globs.is_match(dir.as_str())
+        })
+        .collect())
+}
+
+/// The package named `target` and every package it depends on, directly or
+/// transitively, among `packages`
+///
+/// Dependencies that are not in `packages` (the corelib, registry and git
+/// dependencies) are ignored, since they are not uploaded anyway. The
+/// original order of `packages` is kept.
+#[must_use]
+pub fn dependency_closure(packages: &[PackageMetadata], target: &str) -> Vec<PackageMetadata> {
+    let mut required: HashSet<&str> = HashSet::new();
+    let mut pending = vec![target];
+
+    while let Some(name) = pending.pop() {
+        if !required.insert(name) {
+            continue;
+        }
+        if let Some(package) = packages.iter().find(|package| package.name == name) {
+            pending.extend(package.dependencies.iter().map(|dep| dep.name.as_str()));
+        }
+    }
+
+    packages
+        .iter()
+        .filter(|package| required.contains(package.name.as_str()))
+        .cloned()
+        .collect()
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -853,6 +1033,31 @@ mod tests {
         assert!(error_message.contains("Check that the path exists"));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_broken_symlink_is_reported_with_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp_dir.path()).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src").join("lib.cairo"), "").unwrap();
+        std::fs::write(root.join("notes.txt"), "").unwrap();
+        std::os::unix::fs::symlink(root.join("notes.txt"), root.join("link.txt")).unwrap();
+        assert!(walk_package(root, "token").is_ok());
+
+        std::os::unix::fs::symlink(
+            root.join("gone.cairo"),
+            root.join("src").join("erc20.cairo"),
+        )
+        .unwrap();
+        let error = walk_package(root, "token").unwrap_err();
+        assert_eq!(error.error_code(), "E078");
+        let message = error.to_string();
+        assert!(message.starts_with(&format!(
+            "[E078] Cannot follow the symlink '{}' of package 'token': ",
+            root.join("src").join("erc20.cairo")
+        )));
+    }
+
     #[test]
     fn test_cairo_extension_constant() {
         assert_eq!(CAIRO_EXT, "cairo");
@@ -1124,4 +1329,69 @@ struct MyStruct {
             "/src/utils.rs"
         )));
     }
+
+    fn package(name: &str, dependencies: &[&str]) -> PackageMetadata {
+        let dependencies: Vec<_> = dependencies
+            .iter()
+            .map(|dep| {
+                serde_json::json!({
+                    "name": dep,
+                    "version_req": "*",
+                    "source": format!("path+file:///workspace/{dep}/Scarb.toml"),
+                    "kind": null,
+                })
+            })
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "id": format!("{name} 0.1.0 (path+file:///workspace/{name}/Scarb.toml)"),
+            "name": name,
+            "version": "0.1.0",
+            "source": format!("path+file:///workspace/{name}/Scarb.toml"),
+            "manifest_path": format!("/workspace/{name}/Scarb.toml"),
+            "root": format!("/workspace/{name}"),
+            "dependencies": dependencies,
+            "targets": [],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_members_matching() {
+        let mut members = vec![package("token", &[]), package("vault", &[])];
+        members[0].root = Utf8PathBuf::from("/workspace/contracts/token");
+        members[1].root = Utf8PathBuf::from("/workspace/contracts/defi/vault");
+        let root = Utf8Path::new("/workspace");
+        let names = |patterns: &[&str]| -> Vec<String> {
+            members_matching(&members, root, patterns)
+                .unwrap()
+                .iter()
+                .map(|p| p.name.clone())
+                .collect()
+        };
+
+        assert_eq!(names(&["contracts/*"]), ["token"]);
+        assert_eq!(names(&["contracts/**"]), ["token", "vault"]);
+        assert_eq!(names(&["va*"]), ["vault"]);
+        assert!(names(&["tests/*"]).is_empty());
+        assert!(members_matching(&members, root, &["contracts/[*"]).is_err());
+        assert!(is_package_glob("contracts/*"));
+        assert!(!is_package_glob("token"));
+    }
+
+    #[test]
+    fn test_dependency_closure_prunes_unrelated_members() {
+        let packages = [
+            package("token", &["utils", "core"]),
+            package("utils", &["math"]),
+            package("math", &[]),
+            package("governance", &["utils"]),
+            package("game", &[]),
+        ];
+
+        let closure = dependency_closure(&packages, "token");
+        let names: Vec<&str> = closure.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["token", "utils", "math"]);
+
+        assert!(dependency_closure(&packages, "missing").is_empty());
+    }
 }
@@ -0,0 +1,198 @@
+//! Reading of tar archives
+//!
+//! `voyager verify --stdin-tar` receives its sources as a tar stream. Only
+//! what build systems write is supported: regular files and directories in the
+//! ustar format, with GNU long names and pax `path` records for names longer
+//! than 100 bytes. Links and other special entries are skipped.
+
+use log::debug;
+use std::io::{self, Read};
+
+const BLOCK_SIZE: usize = 512;
+
+/// A regular file of an archive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarEntry {
+    /// Relative path, without a leading `./`
+    pub path: String,
+    pub contents: Vec<u8>,
+}
+
+/// Read the regular files of the tar archive in `reader`
+///
+/// # Errors
+///
+/// Returns an error if the stream is not a tar archive, is truncated, or has
+/// an entry whose path is absolute or leaves the archive root.
+pub fn read_tar(mut reader: impl Read) -> io::Result<Vec<TarEntry>> {
+    let mut entries = Vec::new();
+    let mut long_name: Option<String> = None;
+
+    loop {
+        let mut header = [0u8; BLOCK_SIZE];
+        if !read_block(&mut reader, &mut header)? || header.iter().all(|&b| b == 0) {
+            return Ok(entries);
+        }
+        verify_checksum(&header)?;
+
+        // The buffer grows with what is read, so a corrupt size cannot
+        // allocate more than the stream holds
+        let size = octal(&header[124..136])?;
+        let mut contents = Vec::new();
+        (&mut reader).take(size).read_to_end(&mut contents)?;
+        if contents.len() as u64 != size {
+            return Err(invalid("truncated tar archive"));
+        }
+        let padding = (BLOCK_SIZE as u64 - size % BLOCK_SIZE as u64) % BLOCK_SIZE as u64;
+        io::copy(&mut (&mut reader).take(padding), &mut io::sink())?;
+
+        let mut name = field(&header[0..100]);
+        if &header[257..262] == b"ustar" {
+            let prefix = field(&header[345..500]);
+            if !prefix.is_empty() {
+                name = format!("{prefix}/{name}");
+            }
+        }
+
+        match header[156] {
+            b'0' | 0 | b'7' => {
+                let path = normalize(&long_name.take().unwrap_or(name))?;
+                entries.push(TarEntry { path, contents });
+            }
+            b'L' => long_name = Some(field(&contents)),
+            b'x' => long_name = pax_path(&contents).or(long_name),
+            kind => {
+                debug!("Skipping tar entry {name} of type '{}'", kind as char);
+                long_name = None;
+            }
+        }
+    }
+}
+
+/// Fill `block`, returning `false` if the stream ended before it began
+fn read_block(reader: &mut impl Read, block: &mut [u8; BLOCK_SIZE]) -> io::Result<bool> {
+    let mut filled = 0;
+    while filled < BLOCK_SIZE {
+        match reader.read(&mut block[filled..])? {
+            0 if filled == 0 => return Ok(false),
+            0 => return Err(invalid("truncated tar archive")),
+            read => filled += read,
+        }
+    }
+    Ok(true)
+}
+
+fn verify_checksum(header: &[u8; BLOCK_SIZE]) -> io::Result<()> {
+    let expected = octal(&header[148..156])?;
+    let actual: u64 = header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            if (148..156).contains(&i) {
+                32
+            } else {
+                u64::from(b)
+            }
+        })
+        .sum();
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(invalid("not a tar archive (header checksum mismatch)"))
+    }
+}
+
+/// A NUL-terminated text field
+fn field(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// An octal number field, padded with spaces or NULs
+fn octal(bytes: &[u8]) -> io::Result<u64> {
+    let text = field(bytes);
+    let text = text.trim_matches(' ');
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8).map_err(|_| invalid("invalid number in tar header"))
+}
+
+/// The `path` record of a pax extended header
+fn pax_path(records: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(records)
+        .lines()
+        .filter_map(|record| record.split_once(' '))
+        .find_map(|(_, record)| record.strip_prefix("path=").map(String::from))
+}
+
+fn normalize(path: &str) -> io::Result<String> {
+    let relative = path.trim_start_matches("./");
+    if relative.starts_with('/') || relative.split('/').any(|part| part == "..") {
+        return Err(invalid(&format!(
+            "entry '{path}' is outside of the archive root"
+        )));
+    }
+    Ok(relative.to_string())
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    /// A ustar header for `name` of `size` bytes and type `kind`
+    fn header(name: &str, size: usize, kind: u8) -> Vec<u8> {
+        let mut block = vec![0u8; BLOCK_SIZE];
+        block[..name.len()].copy_from_slice(name.as_bytes());
+        block[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        block[156] = kind;
+        block[257..263].copy_from_slice(b"ustar\0");
+        block[148..156].fill(b' ');
+        let checksum: u64 = block.iter().map(|&b| u64::from(b)).sum();
+        block[148..155].copy_from_slice(format!("{checksum:06o}\0").as_bytes());
+        block
+    }
+
+    fn entry(archive: &mut Vec<u8>, name: &str, contents: &[u8], kind: u8) {
+        archive.extend(header(name, contents.len(), kind));
+        archive.extend(contents);
+        archive.resize(archive.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+    }
+
+    #[test]
+    fn test_read_tar() {
+        let long = format!("src/{}.cairo", "nested_module".repeat(10));
+        let mut archive = Vec::new();
+        entry(&mut archive, "./src/", b"", b'5');
+        entry(
+            &mut archive,
+            "./Scarb.toml",
+            b"[package]\nname = \"token\"\n",
+            b'0',
+        );
+        entry(&mut archive, "././@LongLink", long.as_bytes(), b'L');
+        entry(&mut archive, "src/truncated", b"mod token;\n", b'0');
+        archive.extend([0u8; 2 * BLOCK_SIZE]);
+
+        let entries = read_tar(archive.as_slice()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "Scarb.toml");
+        assert_eq!(entries[1].path, long);
+        assert_eq!(entries[1].contents, b"mod token;\n");
+
+        let mut escaping = Vec::new();
+        entry(&mut escaping, "../secret.cairo", b"", b'0');
+        assert!(read_tar(escaping.as_slice()).is_err());
+        assert!(read_tar(&b"{\"not\": \"a tar\"}"[..]).is_err());
+
+        // A size beyond the end of the stream is an error, not an allocation
+        let mut corrupt = header("src/lib.cairo", 0o77_777_777_777, b'0');
+        corrupt.extend([0u8; BLOCK_SIZE]);
+        assert!(read_tar(corrupt.as_slice()).is_err());
+    }
+}
@@ -1,31 +1,120 @@
 use verifier::cli::args::{Args, Commands};
 
 use clap::Parser;
-use verifier::cli::{commands, config::Config};
+use std::sync::Arc;
+use verifier::api;
+use verifier::cli::{
+    commands,
+    config::{Config, ConfigError},
+    terminal::Terminal,
+};
+use verifier::output::sink;
+use verifier::utils::{explain, log_capture};
 
 fn main() -> anyhow::Result<()> {
-    env_logger::init();
+    sink::install(Arc::new(Terminal::detect()));
 
-    // Load configuration file if it exists
-    let config = Config::find_and_load().unwrap_or_else(|err| {
-        eprintln!("Warning: Failed to load config file: {err}");
-        None
-    });
+    let Args {
+        command: cmd,
+        record_http,
+    } = Args::parse();
 
-    let Args { command: cmd } = Args::parse();
+    // Recent records are only kept for a triage bundle to include
+    log_capture::init(matches!(&cmd, Commands::Verify(args) if args.bundle.is_some()))?;
 
+    if let Some(dir) = record_http {
+        if let Err(e) = api::record_http_to(&dir) {
+            eprintln!("Error: cannot record API traffic to {}: {e}", dir.display());
+            std::process::exit(1);
+        }
+        eprintln!("Recording API traffic to {}", dir.display());
+    }
+
+    // Load configuration file if it exists and the command reads it
+    let config = match cmd.reads_config().then(Config::find_and_load) {
+        None => None,
+        Some(Ok(config)) => config,
+        // A policy that cannot be enforced, or a setting of the environment
+        // that cannot be read, must not be skipped silently
+        Some(Err(err @ (ConfigError::Policy { .. } | ConfigError::Env { .. }))) => {
+            fail(&err.into())
+        }
+        Some(Err(err)) => {
+            eprintln!("Warning: Failed to load config file: {err}");
+            None
+        }
+    };
+
+    if let Err(e) = run(cmd, config.as_ref()) {
+        fail(&e);
+    }
+    Ok(())
+}
+
+/// Report `e` with a pointer to its explanation, and exit with status 1
+fn fail(e: &anyhow::Error) -> ! {
+    eprintln!("Error: {e:?}");
+    if let Some(hint) = explain::hint_for(&e.to_string()) {
+        eprintln!("\n{hint}");
+    }
+    std::process::exit(1);
+}
+
+fn run(cmd: Commands, config: Option<&Config>) -> anyhow::Result<()> {
     match cmd {
         Commands::Verify(args) => {
-            commands::verify::handle_verify_command(args, config.as_ref())?;
+            commands::verify::handle_verify_command(args, config)?;
         }
         Commands::Status(args) => {
-            commands::status::handle_status_command(args, config.as_ref())?;
+            commands::status::handle_status_command(args, config)?;
         }
         Commands::History(args) => {
-            commands::history::handle_history_command(args, config.as_ref())?;
+            commands::history::handle_history_command(args, config)?;
+        }
+        Commands::Contracts(args) => {
+            commands::contracts::handle_contracts_command(&args, config)?;
         }
         Commands::Check(args) => {
-            commands::check::handle_check_command(args, config.as_ref())?;
+            commands::check::handle_check_command(args, config)?;
+        }
+        Commands::Init(args) => {
+            commands::init::handle_init_command(&args)?;
+        }
+        Commands::VerifyUpgrade(args) => {
+            commands::verify_upgrade::handle_verify_upgrade_command(args, config)?;
+        }
+        Commands::Search(args) => {
+            commands::search::handle_search_command(args, config)?;
+        }
+        Commands::Deps(args) => {
+            commands::deps::handle_deps_command(args, config)?;
+        }
+        Commands::ListContracts(args) => {
+            commands::list_contracts::handle_list_contracts_command(&args)?;
+        }
+        Commands::Analyze(args) => {
+            commands::analyze::handle_analyze_command(args, config)?;
+        }
+        Commands::Reproduce(args) => {
+            commands::reproduce::handle_reproduce_command(args, config)?;
+        }
+        Commands::Explain(args) => commands::explain::handle_explain_command(&args),
+        Commands::Daemon(args) => {
+            commands::daemon::handle_daemon_command(&args, config)?;
+        }
+        Commands::DiffDryrun(args) => commands::diff_dryrun::handle_diff_dryrun_command(&args),
+        Commands::Pack(args) => {
+            commands::pack::handle_pack_command(args, config)?;
+        }
+        Commands::Share(args) => {
+            commands::share::handle_share_command(args, config)?;
+        }
+        Commands::Flatten(args) => {
+            commands::flatten::handle_flatten_command(args, config)?;
+        }
+        #[cfg(feature = "dev-tools")]
+        Commands::Dev(args) => {
+            commands::dev::handle_dev_command(args, config)?;
         }
     }
     Ok(())
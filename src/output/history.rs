@@ -0,0 +1,455 @@
+//! Machine-readable output of `voyager history`
+//!
+//! `history list`, `status`, `stats` and `recheck` print colored text blocks by
+//! default. With `--format json` they print the structures of this module
+//! instead, and with `--format table` or `csv` one row per record, so
+//! dashboards can be built over local history without scraping the text.
+//! Timestamps are RFC 3339 in UTC, whatever `--timezone` says.
+//!
+//! `history stats --payloads` lists the size of every recorded submission
+//! instead, and draws it as a bar chart in text mode.
+
+use super::{
+    results::{csv_row, render_table},
+    status::format_absolute,
+};
+use crate::cli::args::{OutputFormat, Timezone};
+use crate::core::analyze::format_size;
+use crate::storage::history::{HistoryStats, VerificationRecord};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use std::{collections::HashMap, fmt::Write as _};
+
+/// Width of the longest bar of the payload chart, in characters
+const CHART_WIDTH: usize = 24;
+
+/// Output that renders as JSON or as rows
+pub trait HistoryOutput: Serialize {
+    /// Column names of [`rows`](Self::rows)
+    fn header(&self) -> &'static [&'static str];
+
+    fn rows(&self) -> Vec<Vec<String>>;
+}
+
+/// Render `output` in `format`; text, which the commands print themselves,
+/// falls back to the table
+#[must_use]
+pub fn render(output: &impl HistoryOutput, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Json => serde_json::to_string_pretty(output)
+            .unwrap_or_else(|e| format!("{{\"error\": \"failed to serialize output: {e}\"}}")),
+        OutputFormat::Csv => {
+            let mut csv = csv_row(output.header());
+            for row in output.rows() {
+                csv.push_str(&csv_row(
+                    &row.iter().map(String::as_str).collect::<Vec<_>>(),
+                ));
+            }
+            csv.trim_end().to_string()
+        }
+        OutputFormat::Text | OutputFormat::Table => render_table(output.header(), &output.rows())
+            .trim_end()
+            .to_string(),
+    }
+}
+
+/// A verification job recorded in history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecordOutput {
+    pub job_id: String,
+    pub class_hash: String,
+    pub contract_name: String,
+    pub network: String,
+    pub status: String,
+    pub submitted_at: String,
+    pub completed_at: Option<String>,
+    pub package_name: Option<String>,
+    pub cairo_version: String,
+    pub scarb_version: String,
+    pub dojo_version: Option<String>,
+    pub commit_hash: Option<String>,
+    pub submitted_by: Option<String>,
+    pub submitted_host: Option<String>,
+    pub ci_job_url: Option<String>,
+    pub run_id: Option<String>,
+    pub note: Option<String>,
+    pub verified_at: Option<String>,
+    pub explorer_name: Option<String>,
+    pub explorer_version: Option<String>,
+}
+
+impl From<&VerificationRecord> for RecordOutput {
+    fn from(record: &VerificationRecord) -> Self {
+        Self {
+            job_id: record.job_id.clone(),
+            class_hash: record.class_hash.clone(),
+            contract_name: record.contract_name.clone(),
+            network: record.network.clone(),
+            status: record.status.clone(),
+            submitted_at: record.submitted_at.to_rfc3339(),
+            completed_at: record.completed_at.map(|at| at.to_rfc3339()),
+            package_name: record.package_name.clone(),
+            cairo_version: record.cairo_version.clone(),
+            scarb_version: record.scarb_version.clone(),
+            dojo_version: record.dojo_version.clone(),
+            commit_hash: record.commit_hash.clone(),
+            submitted_by: record.submitted_by.clone(),
+            submitted_host: record.submitted_host.clone(),
+            ci_job_url: record.ci_job_url.clone(),
+            run_id: record.run_id.clone(),
+            note: record.note.clone(),
+            verified_at: record.verified_at.map(|at| at.to_rfc3339()),
+            explorer_name: record.explorer_name.clone(),
+            explorer_version: record.explorer_version.clone(),
+        }
+    }
+}
+
+const RECORD_HEADER: &[&str] = &[
+    "job_id",
+    "contract_name",
+    "class_hash",
+    "network",
+    "status",
+    "submitted_at",
+    "completed_at",
+    "package_name",
+];
+
+impl RecordOutput {
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.job_id.clone(),
+            self.contract_name.clone(),
+            self.class_hash.clone(),
+            self.network.clone(),
+            self.status.clone(),
+            self.submitted_at.clone(),
+            self.completed_at.clone().unwrap_or_default(),
+            self.package_name.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl HistoryOutput for RecordOutput {
+    fn header(&self) -> &'static [&'static str] {
+        RECORD_HEADER
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![self.row()]
+    }
+}
+
+impl HistoryOutput for Vec<RecordOutput> {
+    fn header(&self) -> &'static [&'static str] {
+        RECORD_HEADER
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter().map(RecordOutput::row).collect()
+    }
+}
+
+/// Counts of the jobs in history by outcome
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatsOutput {
+    pub total: usize,
+    pub successful: usize,
+    pub failed: usize,
+    pub pending: usize,
+    pub expired: usize,
+}
+
+impl From<&HistoryStats> for StatsOutput {
+    fn from(stats: &HistoryStats) -> Self {
+        Self {
+            total: stats.total,
+            successful: stats.successful,
+            failed: stats.failed,
+            pending: stats.pending,
+            expired: stats.expired,
+        }
+    }
+}
+
+impl HistoryOutput for StatsOutput {
+    fn header(&self) -> &'static [&'static str] {
+        &["total", "successful", "failed", "pending", "expired"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![[
+            self.total,
+            self.successful,
+            self.failed,
+            self.pending,
+            self.expired,
+        ]
+        .map(|count| count.to_string())
+        .to_vec()]
+    }
+}
+
+/// Size of a submitted payload
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PayloadOutput {
+    pub job_id: String,
+    pub contract_name: String,
+    pub network: String,
+    pub submitted_at: DateTime<Utc>,
+    /// Total size of the files, in bytes
+    pub size: u64,
+    pub files: usize,
+    pub compressed_size: u64,
+    pub largest_file: String,
+    pub largest_file_size: u64,
+    /// Change in size since the previous submission of the contract on the
+    /// same network, in percent
+    pub growth_percent: Option<i64>,
+}
+
+impl PayloadOutput {
+    /// Payloads of `records`, oldest first; records without a payload size
+    /// are skipped
+    #[must_use]
+    pub fn from_records(records: &[VerificationRecord]) -> Vec<Self> {
+        let mut previous: HashMap<(&str, &str), u64> = HashMap::new();
+        records
+            .iter()
+            .filter_map(|record| {
+                let payload = record.payload.as_ref()?;
+                let growth_percent = previous
+                    .insert(
+                        (record.contract_name.as_str(), record.network.as_str()),
+                        payload.size,
+                    )
+                    .filter(|&size| size > 0)
+                    .map(|size| growth_percent(size, payload.size));
+                Some(Self {
+                    job_id: record.job_id.clone(),
+                    contract_name: record.contract_name.clone(),
+                    network: record.network.clone(),
+                    submitted_at: record.submitted_at,
+                    size: payload.size,
+                    files: payload.files,
+                    compressed_size: payload.compressed_size,
+                    largest_file: payload.largest_file.clone(),
+                    largest_file_size: payload.largest_file_size,
+                    growth_percent,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether the payload at least doubled since the previous submission
+    #[must_use]
+    pub fn doubled(&self) -> bool {
+        self.growth_percent.is_some_and(|growth| growth >= 100)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn growth_percent(before: u64, after: u64) -> i64 {
+    ((i128::from(after) - i128::from(before)) * 100 / i128::from(before)) as i64
+}
+
+impl HistoryOutput for Vec<PayloadOutput> {
+    fn header(&self) -> &'static [&'static str] {
+        &[
+            "submitted_at",
+            "contract_name",
+            "network",
+            "job_id",
+            "size",
+            "files",
+            "compressed_size",
+            "largest_file",
+            "largest_file_size",
+            "growth_percent",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.iter()
+            .map(|payload| {
+                vec![
+                    payload.submitted_at.to_rfc3339(),
+                    payload.contract_name.clone(),
+                    payload.network.clone(),
+                    payload.job_id.clone(),
+                    payload.size.to_string(),
+                    payload.files.to_string(),
+                    payload.compressed_size.to_string(),
+                    payload.largest_file.clone(),
+                    payload.largest_file_size.to_string(),
+                    payload
+                        .growth_percent
+                        .map(|growth| growth.to_string())
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Bar chart of payload sizes, one line per submission, flagging payloads
+/// that doubled
+#[must_use]
+pub fn render_payload_chart(payloads: &[PayloadOutput], timezone: Timezone) -> String {
+    let max = payloads.iter().map(|p| p.size).max().unwrap_or(0).max(1);
+    let name_width = payloads
+        .iter()
+        .map(|p| p.contract_name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut chart = String::new();
+    for payload in payloads {
+        let width = usize::try_from(payload.size.saturating_mul(CHART_WIDTH as u64) / max)
+            .unwrap_or(CHART_WIDTH)
+            .max(usize::from(payload.size > 0));
+        let growth = match payload.growth_percent {
+            Some(growth) if payload.doubled() => format!("+{growth}% doubled").yellow().bold(),
+            Some(growth) if growth > 0 => format!("+{growth}%").normal(),
+            Some(growth) => format!("{growth}%").normal(),
+            None => "".normal(),
+        };
+        // Writing to a String cannot fail
+        let _ = writeln!(
+            chart,
+            "{}  {:<name_width$}  {:<CHART_WIDTH$}  {:>10}  {:>4} files  {growth}",
+            format_absolute(payload.submitted_at, timezone),
+            payload.contract_name,
+            "█".repeat(width),
+            format_size(payload.size),
+            payload.files,
+        );
+    }
+    chart
+}
+
+/// A pending job checked again by `history recheck`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RecheckedJob {
+    pub job_id: String,
+    pub previous_status: String,
+    /// Status after the check, or `None` if the API could not be asked
+    pub status: Option<String>,
+    /// Whether the API no longer knows the job
+    pub orphaned: bool,
+    pub error: Option<String>,
+}
+
+/// Outcome of `history recheck`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RecheckOutput {
+    pub updated: usize,
+    pub orphans: usize,
+    pub jobs: Vec<RecheckedJob>,
+}
+
+impl HistoryOutput for RecheckOutput {
+    fn header(&self) -> &'static [&'static str] {
+        &["job_id", "previous_status", "status", "orphaned", "error"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.jobs
+            .iter()
+            .map(|job| {
+                vec![
+                    job.job_id.clone(),
+                    job.previous_status.clone(),
+                    job.status.clone().unwrap_or_default(),
+                    job.orphaned.to_string(),
+                    job.error.clone().unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_history_output() {
+        let stats = StatsOutput {
+            total: 4,
+            successful: 2,
+            failed: 1,
+            pending: 1,
+            expired: 0,
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&stats, OutputFormat::Json)).unwrap();
+        assert_eq!(json["successful"], 2);
+        assert_eq!(
+            render(&stats, OutputFormat::Csv),
+            "total,successful,failed,pending,expired\n4,2,1,1,0"
+        );
+
+        let recheck = RecheckOutput {
+            updated: 0,
+            orphans: 1,
+            jobs: vec![RecheckedJob {
+                job_id: "job-1".to_string(),
+                previous_status: "Submitted".to_string(),
+                status: None,
+                orphaned: true,
+                error: Some("Job 'job-1' not found".to_string()),
+            }],
+        };
+        let table = render(&recheck, OutputFormat::Table);
+        assert!(table.starts_with('┌'));
+        assert!(table.contains("│ job-1  │ Submitted       │        │ true     │"));
+    }
+
+    #[test]
+    fn test_payload_growth_per_contract() {
+        use crate::{core::class_hash::ClassHash, storage::history::PayloadStats};
+
+        let class_hash = ClassHash::new("0x1234").unwrap();
+        let record = |job_id: &str, contract: &str, size: u64| {
+            VerificationRecord::new(
+                job_id.to_string(),
+                &class_hash,
+                contract.to_string(),
+                "mainnet".to_string(),
+                crate::api::VerifyJobStatus::Submitted,
+                None,
+                "2.11.4".to_string(),
+                "2.11.4".to_string(),
+                None,
+            )
+            .with_payload(Some(PayloadStats {
+                size,
+                files: 2,
+                compressed_size: size / 3,
+                largest_file: "src/lib.cairo".to_string(),
+                largest_file_size: size / 2,
+            }))
+        };
+        let payloads = PayloadOutput::from_records(&[
+            record("job-1", "MyToken", 4_000),
+            record("job-2", "Vault", 1_000),
+            record("job-3", "MyToken", 9_000),
+            record("job-4", "MyToken", 8_100),
+        ]);
+        let growth: Vec<_> = payloads.iter().map(|p| p.growth_percent).collect();
+        assert_eq!(growth, [None, None, Some(125), Some(-10)]);
+        assert!(payloads[2].doubled());
+        assert!(!payloads[3].doubled());
+
+        colored::control::set_override(false);
+        let chart = render_payload_chart(&payloads, Timezone::Utc);
+        let lines: Vec<&str> = chart.lines().collect();
+        assert!(lines[2].contains(&format!("MyToken  {}  ", "█".repeat(CHART_WIDTH))));
+        assert!(lines[2].ends_with("+125% doubled"));
+        assert!(lines[1].contains("Vault    ██ "));
+    }
+}
@@ -0,0 +1,212 @@
+//! Live status line of watchers
+//!
+//! When the installed [`sink`] shows progress lines, `--watch` rewrites a
+//! single status line in place. Otherwise, as in CI logs, every rewrite would
+//! be kept as another frame, so the status is written as a timestamped line
+//! instead, only when it changes and otherwise at most every [`LOG_INTERVAL`].
+
+use super::sink;
+use chrono::Utc;
+use std::time::{Duration, Instant};
+
+/// How often an unchanged status is repeated when it cannot be rewritten
+pub const LOG_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Status line that is rewritten where the sink shows progress and logged
+/// elsewhere
+#[derive(Debug)]
+pub struct LiveStatus {
+    terminal: bool,
+    /// State of the last shown line, and when it was shown
+    last: Option<(String, Instant)>,
+    /// Last status line, redrawn below logged output on a terminal
+    line: String,
+}
+
+impl Default for LiveStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiveStatus {
+    /// Rewrite the line if the installed sink shows progress, log it otherwise
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_terminal(sink::current().shows_progress())
+    }
+
+    #[must_use]
+    pub const fn with_terminal(terminal: bool) -> Self {
+        Self {
+            terminal,
+            last: None,
+            line: String::new(),
+        }
+    }
+
+    /// Show `line`, which describes `state`
+    ///
+    /// Only a change of `state` counts as a new status; `line` may change on
+    /// every call, e.g. with the elapsed time.
+    pub fn update(&mut self, state: &str, line: &str) {
+        let now = Instant::now();
+        if self.terminal {
+            self.last = Some((state.to_string(), now));
+            line.clone_into(&mut self.line);
+            sink::progress(Some(line));
+        } else if let Some(output) = self.logged(state, line, now) {
+            sink::line(output);
+        }
+    }
+
+    /// Write `text` on its own line above the status line
+    pub fn log(&mut self, text: &str) {
+        if self.terminal && self.last.is_some() {
+            // Clear the status line, then draw it again below
+            sink::progress(None);
+            sink::line(text);
+            sink::progress(Some(&self.line));
+        } else {
+            sink::line(text);
+        }
+    }
+
+    /// End the live line, keeping the last status in the output
+    pub fn finish(&mut self) {
+        if self.last.take().is_some() && self.terminal {
+            sink::progress(None);
+            sink::line(&self.line);
+        }
+    }
+
+    /// The timestamped line to log for `line`, if `state` changed or was last
+    /// logged [`LOG_INTERVAL`] ago
+    fn logged(&mut self, state: &str, line: &str, now: Instant) -> Option<String> {
+        let due = self.last.as_ref().is_none_or(|(last, logged_at)| {
+            last != state || now.duration_since(*logged_at) >= LOG_INTERVAL
+        });
+        if !due {
+            return None;
+        }
+        self.last = Some((state.to_string(), now));
+        Some(format!("[{}] {line}", Utc::now().format("%H:%M:%S")))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::output::sink::OutputSink;
+    use std::{
+        sync::{Arc, Mutex},
+        thread::{self, ThreadId},
+    };
+
+    /// Sink recording what the current test thread writes, as other tests
+    /// may write to the installed sink concurrently
+    #[derive(Default)]
+    struct Recording(Mutex<Vec<(ThreadId, String)>>);
+
+    impl Recording {
+        fn record(&self, event: String) {
+            self.0.lock().unwrap().push((thread::current().id(), event));
+        }
+
+        fn events(&self) -> Vec<String> {
+            let id = thread::current().id();
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(thread, _)| *thread == id)
+                .map(|(_, event)| event.clone())
+                .collect()
+        }
+    }
+
+    impl OutputSink for Recording {
+        fn line(&self, text: &str) {
+            self.record(format!("line {text}"));
+        }
+
+        fn warning(&self, text: &str) {
+            self.record(format!("warning {text}"));
+        }
+
+        fn progress(&self, text: Option<&str>) {
+            self.record(format!("progress {}", text.unwrap_or("<clear>")));
+        }
+    }
+
+    #[test]
+    fn test_log_lines_without_terminal() {
+        let mut live = LiveStatus::with_terminal(false);
+        let start = Instant::now();
+
+        let first = live
+            .logged("Submitted", "⏳ Submitted [1s]", start)
+            .unwrap();
+        assert!(first.starts_with('[') && first.ends_with("] ⏳ Submitted [1s]"));
+        assert!(live
+            .logged(
+                "Submitted",
+                "⏳ Submitted [3s]",
+                start + Duration::from_secs(2)
+            )
+            .is_none());
+        assert!(live
+            .logged(
+                "Processing",
+                "⏳ Compiling [5s]",
+                start + Duration::from_secs(4)
+            )
+            .is_some());
+        assert!(live
+            .logged("Processing", "⏳ Compiling [1m 5s]", start + LOG_INTERVAL)
+            .is_none());
+        assert!(live
+            .logged(
+                "Processing",
+                "⏳ Compiling [1m 7s]",
+                start + LOG_INTERVAL * 2
+            )
+            .is_some());
+    }
+
+    #[test]
+    fn test_writes_through_installed_sink() {
+        let recording = Arc::new(Recording::default());
+        sink::install(recording.clone());
+
+        let mut live = LiveStatus::with_terminal(true);
+        live.log("   Resolving");
+        live.update("Submitted", "⏳ Submitted");
+        live.log("   Compiling token");
+        live.update("Processing", "⏳ Compiling");
+        live.finish();
+
+        let mut logged = LiveStatus::with_terminal(false);
+        logged.update("Submitted", "⏳ Submitted");
+        logged.finish();
+
+        sink::install(Arc::new(sink::Discard));
+        let events = recording.events();
+        assert_eq!(
+            events[..8],
+            [
+                "line    Resolving",
+                "progress ⏳ Submitted",
+                "progress <clear>",
+                "line    Compiling token",
+                "progress ⏳ Submitted",
+                "progress ⏳ Compiling",
+                "progress <clear>",
+                "line ⏳ Compiling",
+            ]
+        );
+        assert!(events[8].starts_with("line [") && events[8].ends_with("] ⏳ Submitted"));
+        assert_eq!(events.len(), 9);
+    }
+}
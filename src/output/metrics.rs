@@ -0,0 +1,331 @@
+//! Prometheus metrics of a watching verifier
+//!
+//! `voyager verify --watch --metrics-addr 127.0.0.1:9464` serves `/metrics`
+//! in the Prometheus text format for as long as the verifier runs, so
+//! pipelines that verify many contracts can be scraped and alerted on;
+//! `voyager daemon --metrics-addr` serves it across scheduled runs.
+//! [`Metrics`] is attached to the [`ApiClient`](crate::api::ApiClient) as its
+//! progress observer and counts submissions, finished jobs, and API requests.
+
+use super::sink::SinkProgress;
+use crate::api::{ProgressObserver, VerifyJobStatus};
+use itertools::Itertools;
+use log::debug;
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// Longest a scraper may take to send its request or read the response
+const IO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest dropping the server waits for the request being answered
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Requests and their latency for one endpoint
+#[derive(Debug, Default)]
+struct EndpointStats {
+    /// Requests per HTTP status code, `error` when there was no response
+    codes: BTreeMap<String, u64>,
+    count: u64,
+    seconds: f64,
+}
+
+/// Counters of a verification run
+#[derive(Debug, Default)]
+pub struct Metrics {
+    submissions: AtomicU64,
+    succeeded: AtomicU64,
+    failed: AtomicU64,
+    endpoints: Mutex<BTreeMap<String, EndpointStats>>,
+    /// Shows the progress the counters are taken from
+    display: SinkProgress,
+}
+
+impl ProgressObserver for Metrics {
+    fn bytes_uploaded(&self, _bytes: u64) {
+        self.submissions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn status_changed(&self, _job_id: &str, status: VerifyJobStatus) {
+        match status {
+            VerifyJobStatus::Success => {
+                self.succeeded.fetch_add(1, Ordering::Relaxed);
+            }
+            VerifyJobStatus::Fail | VerifyJobStatus::CompileFailed => {
+                self.failed.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => {}
+        }
+    }
+
+    fn request_completed(&self, endpoint: &str, status: Option<u16>, elapsed: Duration) {
+        let Ok(mut endpoints) = self.endpoints.lock() else {
+            return;
+        };
+        let requests = endpoints.entry(endpoint_label(endpoint)).or_default();
+        let code = status.map_or_else(|| "error".to_string(), |status| status.to_string());
+        *requests.codes.entry(code).or_default() += 1;
+        requests.count += 1;
+        requests.seconds += elapsed.as_secs_f64();
+    }
+
+    fn upload_progress(&self, sent: u64, total: u64) {
+        self.display.upload_progress(sent, total);
+    }
+
+    fn rate_limited(&self, endpoint: &str, delay: Duration) {
+        self.display.rate_limited(endpoint, delay);
+    }
+}
+
+impl Metrics {
+    /// The metrics in the Prometheus text exposition format
+    #[must_use]
+    pub fn render(&self) -> String {
+        let submissions = self.submissions.load(Ordering::Relaxed);
+        let succeeded = self.succeeded.load(Ordering::Relaxed);
+        let failed = self.failed.load(Ordering::Relaxed);
+
+        let mut out = String::new();
+        for (name, kind, help, value) in [
+            (
+                "voyager_submissions_total",
+                "counter",
+                "Verification jobs submitted",
+                submissions,
+            ),
+            (
+                "voyager_verifications_succeeded_total",
+                "counter",
+                "Verification jobs that succeeded",
+                succeeded,
+            ),
+            (
+                "voyager_verifications_failed_total",
+                "counter",
+                "Verification jobs that failed or did not compile",
+                failed,
+            ),
+            (
+                "voyager_queue_depth",
+                "gauge",
+                "Submitted verification jobs that have not finished",
+                submissions.saturating_sub(succeeded + failed),
+            ),
+        ] {
+            let _ = writeln!(
+                out,
+                "# HELP {name} {help}.\n# TYPE {name} {kind}\n{name} {value}"
+            );
+        }
+
+        let Ok(endpoints) = self.endpoints.lock() else {
+            return out;
+        };
+        let _ = writeln!(
+            out,
+            "# HELP voyager_api_requests_total API requests by endpoint and HTTP status.\n\
+             # TYPE voyager_api_requests_total counter"
+        );
+        for (endpoint, stats) in endpoints.iter() {
+            for (code, count) in &stats.codes {
+                let _ = writeln!(
+                    out,
+                    "voyager_api_requests_total{{endpoint=\"{endpoint}\",code=\"{code}\"}} {count}"
+                );
+            }
+        }
+        let _ = writeln!(
+            out,
+            "# HELP voyager_api_request_duration_seconds Time taken by API requests.\n\
+             # TYPE voyager_api_request_duration_seconds summary"
+        );
+        for (endpoint, stats) in endpoints.iter() {
+            let _ = writeln!(
+                out,
+                "voyager_api_request_duration_seconds_sum{{endpoint=\"{endpoint}\"}} {}\n\
+                 voyager_api_request_duration_seconds_count{{endpoint=\"{endpoint}\"}} {}",
+                stats.seconds, stats.count
+            );
+        }
+        out
+    }
+}
+
+/// `endpoint` without its query, with job IDs and class hashes replaced by
+/// `:id` to keep the number of label values small
+fn endpoint_label(endpoint: &str) -> String {
+    let path = endpoint.split('?').next().unwrap_or(endpoint);
+    path.split('/')
+        .map(|segment| {
+            if segment.chars().any(|c| c.is_ascii_digit()) {
+                ":id"
+            } else {
+                segment
+            }
+        })
+        .join("/")
+}
+
+/// Server for `/metrics` running on a background thread
+///
+/// The server stops when the value is dropped.
+pub struct MetricsServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Serve `metrics` at `http://<addr>/metrics`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `addr` cannot be bound.
+    pub fn start(addr: SocketAddr, metrics: Arc<Metrics>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let result = stream.and_then(|stream| serve(stream, &metrics));
+                    if let Err(e) = result {
+                        debug!("Metrics connection failed: {e}");
+                    }
+                }
+            })
+        };
+
+        debug!("Serving metrics at http://{addr}/metrics");
+        Ok(Self {
+            addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Address the server listens on
+    #[must_use]
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect_timeout(&self.addr, SHUTDOWN_TIMEOUT);
+        let Some(handle) = self.handle.take() else {
+            return;
+        };
+        // A slow scraper must not keep the verifier from exiting
+        let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+        while !handle.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        if handle.is_finished() {
+            let _ = handle.join();
+        } else {
+            debug!("Not waiting for the metrics request still being answered");
+        }
+    }
+}
+
+/// Answer one request; every response closes the connection
+fn serve(mut stream: TcpStream, metrics: &Metrics) -> io::Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", metrics.render()),
+        _ => ("404 Not Found", "Not found\n".to_string()),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_metrics_endpoint() {
+        let metrics = Arc::new(Metrics::default());
+        metrics.bytes_uploaded(1024);
+        metrics.bytes_uploaded(2048);
+        metrics.status_changed("job-1", VerifyJobStatus::Processing);
+        metrics.status_changed("job-1", VerifyJobStatus::Success);
+        metrics.request_completed(
+            "class-verify/job/3fa85f64-5717-4562-b3fc-2c963f66afa6/logs?offset=2",
+            Some(200),
+            Duration::from_millis(250),
+        );
+        metrics.request_completed("class-verify/job/abc-123", None, Duration::ZERO);
+
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap(), metrics).unwrap();
+        let mut stream = TcpStream::connect(server.addr()).unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("\nvoyager_submissions_total 2\n"));
+        assert!(response.contains("\nvoyager_verifications_succeeded_total 1\n"));
+        assert!(response.contains("\nvoyager_queue_depth 1\n"));
+        assert!(response.contains(
+            "voyager_api_requests_total{endpoint=\"class-verify/job/:id/logs\",code=\"200\"} 1"
+        ));
+        assert!(response.contains(
+            "voyager_api_requests_total{endpoint=\"class-verify/job/:id\",code=\"error\"} 1"
+        ));
+        assert!(response.contains(
+            "voyager_api_request_duration_seconds_sum{endpoint=\"class-verify/job/:id/logs\"} 0.25"
+        ));
+    }
+
+    #[test]
+    fn test_silent_scraper_does_not_block_shutdown() {
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap(), Arc::default()).unwrap();
+        let _silent = TcpStream::connect(server.addr()).unwrap();
+        // Let the server start reading from the silent connection
+        thread::sleep(Duration::from_millis(50));
+
+        let started = Instant::now();
+        drop(server);
+        assert!(started.elapsed() < IO_TIMEOUT);
+    }
+}
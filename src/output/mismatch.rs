@@ -0,0 +1,126 @@
+//! Class hash mismatch reports
+//!
+//! When a verification fails, the class hash the API computed (if it reports
+//! one) is compared with the declared one, followed by the checklist of
+//! [`SubmissionFacts::causes`].
+
+use crate::{
+    api::{ApiClient, ApiClientError, VerificationError},
+    core::mismatch::SubmissionFacts,
+    utils::errors::CliError,
+};
+use colored::Colorize;
+use log::debug;
+use std::fmt::Write as _;
+
+/// Explain a failed verification of `job_id` as a class hash mismatch
+///
+/// Does nothing unless `error` is a verification failure; compilation
+/// failures have their own suggestions.
+pub fn explain_failure(
+    api_client: &ApiClient,
+    job_id: &str,
+    error: &CliError,
+    facts: &SubmissionFacts,
+) {
+    if !matches!(
+        error,
+        CliError::Api(ApiClientError::Verify(
+            VerificationError::VerificationFailure(_)
+        ))
+    ) {
+        return;
+    }
+
+    let job = api_client
+        .get_job_status_raw(job_id.to_string())
+        .map_err(|e| debug!("Failed to fetch job {job_id} for the mismatch report: {e}"))
+        .ok();
+    let expected = job.as_ref().and_then(|job| job.class_hash.as_deref());
+    let computed = job
+        .as_ref()
+        .and_then(|job| job.computed_class_hash.as_deref());
+    eprintln!("{}", format_report(expected, computed, facts));
+}
+
+/// The comparison of the class hashes and the checklist of causes
+#[must_use]
+pub fn format_report(
+    expected: Option<&str>,
+    computed: Option<&str>,
+    facts: &SubmissionFacts,
+) -> String {
+    let mut report = format!("\n{}\n", "Class hash comparison".bold());
+    let _ = writeln!(
+        report,
+        "  Expected (declared): {}",
+        expected.unwrap_or("unknown")
+    );
+    match computed {
+        Some(computed) => {
+            let _ = writeln!(report, "  Computed (remote):   {computed}");
+            if let Some(index) = expected.and_then(|expected| first_difference(expected, computed))
+            {
+                let _ = writeln!(
+                    report,
+                    "                       {}{}",
+                    " ".repeat(index),
+                    "^ first difference".yellow()
+                );
+            }
+        }
+        None => {
+            let _ = writeln!(
+                report,
+                "  Computed (remote):   {}",
+                "not reported by the API".bright_black()
+            );
+        }
+    }
+
+    let _ = writeln!(report, "\n{}", "Common causes".bold());
+    for cause in facts.causes() {
+        let marker = if cause.likely {
+            "!".yellow().bold()
+        } else {
+            "•".normal()
+        };
+        let _ = writeln!(report, "  {marker} {}: {}", cause.title, cause.detail);
+    }
+    report
+}
+
+/// Index of the first differing character, if the hashes differ
+fn first_difference(expected: &str, computed: &str) -> Option<usize> {
+    if expected == computed {
+        return None;
+    }
+    expected
+        .chars()
+        .zip(computed.chars())
+        .position(|(a, b)| !a.eq_ignore_ascii_case(&b))
+        .or_else(|| Some(expected.len().min(computed.len())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report() {
+        colored::control::set_override(false);
+        let report = format_report(
+            Some("0x04ab12"),
+            Some("0x04ac12"),
+            &SubmissionFacts::default(),
+        );
+        assert!(report.contains("  Expected (declared): 0x04ab12\n"));
+        assert!(report.contains("  Computed (remote):   0x04ac12\n"));
+        assert!(report.contains(&format!("{}^ first difference", " ".repeat(23 + 4))));
+        assert!(report.contains("  • Compiler version: "));
+
+        let report = format_report(Some("0x1"), None, &SubmissionFacts::default());
+        assert!(report.contains("not reported by the API"));
+        assert!(!report.contains("first difference"));
+    }
+}
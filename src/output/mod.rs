@@ -1,2 +1,11 @@
+pub mod history;
+pub mod live;
+pub mod metrics;
+pub mod mismatch;
 pub mod notifications;
+pub mod results;
+pub mod share;
+pub mod sink;
 pub mod status;
+pub mod summary;
+pub mod triage;
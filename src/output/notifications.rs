@@ -76,6 +76,42 @@ pub fn send_verification_notification(
     Ok(())
 }
 
+/// Send a desktop notification about a contract that is no longer verified
+///
+/// # Errors
+///
+/// Returns an error if the notification system is unavailable or fails to send.
+#[cfg(feature = "notifications")]
+pub fn send_regression_notification(
+    contract_name: &str,
+    class_hash: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut notification = Notification::new();
+    notification
+        .summary("⚠️ Verification Lost")
+        .body(&format!(
+            "Contract '{contract_name}' is no longer verified.\n\nClass hash: {class_hash}"
+        ))
+        .timeout(notify_rust::Timeout::Milliseconds(6000));
+
+    #[cfg(target_os = "linux")]
+    notification.urgency(notify_rust::Urgency::Critical);
+
+    notification.show()?;
+
+    Ok(())
+}
+
+/// Stub function when notifications feature is disabled
+#[cfg(not(feature = "notifications"))]
+pub fn send_regression_notification(
+    _contract_name: &str,
+    _class_hash: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Notifications disabled - do nothing
+    Ok(())
+}
+
 /// Stub function when notifications feature is disabled
 #[cfg(not(feature = "notifications"))]
 pub fn send_verification_notification(
@@ -0,0 +1,399 @@
+//! Machine-readable batch verification results
+//!
+//! This module writes the outcome of a batch verification to a JSON file
+//! (`--results results.json`) so release tooling can pick up job IDs, statuses,
+//! source fingerprints, and explorer links without scraping console output.
+//! It also renders the summary of a batch as a table or as CSV for
+//! `voyager verify --format table|csv`.
+
+use super::status;
+use crate::core::verification::{BatchVerificationResult, BatchVerificationSummary};
+use crate::utils::{errors::CliError, run_id, submitter::Submitter, suggestion::Suggestion};
+use camino::Utf8Path;
+use chrono::Utc;
+use serde::Serialize;
+use std::fmt::Write;
+use url::Url;
+
+/// Columns of the CSV batch summary
+const CSV_HEADER: [&str; 8] = [
+    "contract_name",
+    "package",
+    "class_hash",
+    "job_id",
+    "status",
+    "duration_secs",
+    "error",
+    "explorer_url",
+];
+
+/// Results of a batch verification run
+#[derive(Debug, Serialize)]
+pub struct BatchResults {
+    /// RFC 3339 timestamp at which the results were written
+    pub generated_at: String,
+    /// API endpoint the contracts were submitted to
+    pub api_url: String,
+    /// Run id of the invocation that submitted the batch
+    pub run_id: String,
+    /// Who and where the batch was submitted from
+    pub submitter: Submitter,
+    pub total: usize,
+    pub submitted: usize,
+    pub contracts: Vec<ContractResult>,
+}
+
+/// Result for a single contract in a batch
+#[derive(Debug, Serialize)]
+pub struct ContractResult {
+    pub contract_name: String,
+    pub class_hash: String,
+    pub package: Option<String>,
+    pub job_id: Option<String>,
+    pub status: Option<String>,
+    pub error: Option<String>,
+    /// Next steps for `error`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<Suggestion>,
+    pub source_fingerprint: Option<String>,
+    /// Seconds the job took, if the batch was watched
+    pub duration_secs: Option<u64>,
+    pub explorer_url: Option<String>,
+}
+
+impl BatchResults {
+    #[must_use]
+    pub fn new(summary: &BatchVerificationSummary, api_url: &Url, explorer: Option<&Url>) -> Self {
+        Self {
+            generated_at: Utc::now().to_rfc3339(),
+            api_url: api_url.to_string(),
+            run_id: run_id::current().to_string(),
+            submitter: Submitter::detect(),
+            total: summary.total,
+            submitted: summary.submitted,
+            contracts: contract_results(summary, explorer),
+        }
+    }
+}
+
+impl ContractResult {
+    fn new(result: &BatchVerificationResult, explorer: Option<&Url>) -> Self {
+        Self {
+            contract_name: result.contract.contract_name.clone(),
+            class_hash: result.contract.class_hash.to_string(),
+            package: result.contract.package.clone(),
+            job_id: result.job_id.clone(),
+            status: result.status.map(|status| status.to_string()),
+            // Only the headline; the suggestions are listed separately
+            error: result
+                .error
+                .as_deref()
+                .map(|err| err.lines().next().unwrap_or(err).to_string()),
+            suggestions: result.suggestions.clone(),
+            source_fingerprint: result.source_fingerprint.clone(),
+            duration_secs: result.duration,
+            explorer_url: explorer.map(|explorer| {
+                format!(
+                    "{}/class/{}",
+                    explorer.as_str().trim_end_matches('/'),
+                    result.contract.class_hash
+                )
+            }),
+        }
+    }
+}
+
+impl ContractResult {
+    /// Status for display: the job status, or why there is none
+    fn status_label(&self) -> &str {
+        match (&self.status, &self.job_id, &self.error) {
+            (Some(status), Some(_), _) => status,
+            // Only skipped contracts succeed without a job
+            (Some(_), None, _) => "Already verified",
+            (None, _, Some(_)) => "Error",
+            (None, _, None) => "Not submitted",
+        }
+    }
+}
+
+/// Render the summary of a batch as a table, followed by the errors
+#[must_use]
+pub fn format_table(summary: &BatchVerificationSummary, explorer: Option<&Url>) -> String {
+    let contracts = contract_results(summary, explorer);
+    let header = ["Contract", "Status", "Duration", "Job ID", "Explorer"];
+    let rows: Vec<Vec<String>> = contracts
+        .iter()
+        .map(|contract| {
+            vec![
+                contract.contract_name.clone(),
+                contract.status_label().to_string(),
+                contract
+                    .duration_secs
+                    .map_or_else(|| "-".to_string(), status::format_duration),
+                contract.job_id.clone().unwrap_or_else(|| "-".to_string()),
+                contract
+                    .explorer_url
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    let mut output = render_table(&header, &rows);
+
+    let errors: Vec<_> = contracts
+        .iter()
+        .filter_map(|contract| Some((&contract.contract_name, contract.error.as_ref()?)))
+        .collect();
+    if !errors.is_empty() {
+        output.push_str("\nErrors:\n");
+        for (name, error) in errors {
+            let _ = writeln!(output, "  {name}: {error}");
+        }
+    }
+    output
+}
+
+/// Render `rows` under `header` as a box-drawn table
+#[must_use]
+pub fn render_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = header.iter().map(|cell| cell.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let rule = |left: &str, middle: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        format!("{left}{}{right}\n", segments.join(middle))
+    };
+    let line = |cells: &[&str]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {cell:<width$} "))
+            .collect();
+        format!("│{}│\n", cells.join("│"))
+    };
+
+    let mut output = rule("┌", "┬", "┐");
+    output.push_str(&line(header));
+    output.push_str(&rule("├", "┼", "┤"));
+    for row in rows {
+        output.push_str(&line(&row.iter().map(String::as_str).collect::<Vec<_>>()));
+    }
+    output.push_str(&rule("└", "┴", "┘"));
+    output
+}
+
+/// Render the summary of a batch as CSV with a header row
+#[must_use]
+pub fn format_csv(summary: &BatchVerificationSummary, explorer: Option<&Url>) -> String {
+    let mut output = csv_row(&CSV_HEADER);
+    for contract in contract_results(summary, explorer) {
+        let duration = contract.duration_secs.map(|secs| secs.to_string());
+        output.push_str(&csv_row(&[
+            &contract.contract_name,
+            contract.package.as_deref().unwrap_or_default(),
+            &contract.class_hash,
+            contract.job_id.as_deref().unwrap_or_default(),
+            contract.status.as_deref().unwrap_or_default(),
+            duration.as_deref().unwrap_or_default(),
+            contract.error.as_deref().unwrap_or_default(),
+            contract.explorer_url.as_deref().unwrap_or_default(),
+        ]));
+    }
+    output
+}
+
+/// One CSV line, quoting fields that contain separators, quotes or newlines
+#[must_use]
+pub fn csv_row(fields: &[&str]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                (*field).to_string()
+            }
+        })
+        .collect();
+    format!("{}\n", fields.join(","))
+}
+
+fn contract_results(
+    summary: &BatchVerificationSummary,
+    explorer: Option<&Url>,
+) -> Vec<ContractResult> {
+    summary
+        .results
+        .iter()
+        .map(|result| ContractResult::new(result, explorer))
+        .collect()
+}
+
+/// Render the results of a batch as pretty-printed JSON, in the format of the
+/// `--results` file
+///
+/// # Errors
+///
+/// Returns an error if the results cannot be serialized.
+pub fn format_json(
+    summary: &BatchVerificationSummary,
+    api_url: &Url,
+    explorer: Option<&Url>,
+) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&BatchResults::new(summary, api_url, explorer))
+}
+
+/// Write batch results as pretty-printed JSON to `path`
+///
+/// # Errors
+///
+/// Returns [`CliError::ResultsWriteFailed`] if the file cannot be written.
+pub fn write_batch_results(
+    path: &Utf8Path,
+    summary: &BatchVerificationSummary,
+    api_url: &Url,
+    explorer: Option<&Url>,
+) -> Result<(), CliError> {
+    let json =
+        format_json(summary, api_url, explorer).map_err(|e| CliError::ResultsWriteFailed {
+            path: path.to_path_buf(),
+            source: e.into(),
+        })?;
+
+    std::fs::write(path, json).map_err(|source| CliError::ResultsWriteFailed {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::api::VerifyJobStatus;
+    use crate::core::{class_hash::ClassHash, verification::BatchContract};
+    use camino::Utf8PathBuf;
+    use tempfile::TempDir;
+
+    fn result(name: &str, job_id: Option<&str>, error: Option<&str>) -> BatchVerificationResult {
+        BatchVerificationResult {
+            contract: BatchContract {
+                class_hash: ClassHash::new("0x1234").unwrap(),
+                contract_name: name.to_string(),
+                package: None,
+            },
+            job_id: job_id.map(String::from),
+            status: job_id.map(|_| VerifyJobStatus::Success),
+            error: error.map(String::from),
+            suggestions: error
+                .map(|_| vec![Suggestion::new("Drop --test-files")])
+                .unwrap_or_default(),
+            source_fingerprint: job_id.map(|_| "sha256:abc".to_string()),
+            duration: job_id.map(|_| 95),
+        }
+    }
+
+    #[test]
+    fn test_write_batch_results() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::try_from(temp_dir.path().join("results.json")).unwrap();
+        let summary = BatchVerificationSummary {
+            total: 2,
+            submitted: 1,
+            results: vec![
+                result("Token", Some("job-1"), None),
+                result(
+                    "Nft",
+                    None,
+                    Some("[E019] File too large\n\nSuggestions:\n  • Drop --test-files"),
+                ),
+            ],
+        };
+        let api_url = Url::parse("https://sepolia-api.voyager.online/beta").unwrap();
+        let explorer = Url::parse("https://sepolia.voyager.online").unwrap();
+
+        write_batch_results(&path, &summary, &api_url, Some(&explorer)).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["total"], 2);
+        assert_eq!(json["submitted"], 1);
+        assert!(json["submitter"].is_object());
+
+        let token = &json["contracts"][0];
+        assert_eq!(token["job_id"], "job-1");
+        assert_eq!(token["status"], "Success");
+        assert_eq!(token["source_fingerprint"], "sha256:abc");
+        assert_eq!(token["duration_secs"], 95);
+        assert_eq!(
+            token["explorer_url"],
+            format!(
+                "https://sepolia.voyager.online/class/{}",
+                token["class_hash"].as_str().unwrap()
+            )
+        );
+
+        let nft = &json["contracts"][1];
+        assert!(nft["job_id"].is_null());
+        assert_eq!(nft["error"], "[E019] File too large");
+        assert_eq!(nft["suggestions"][0]["kind"], "use_flag");
+        assert_eq!(nft["suggestions"][0]["flag"], "--test-files");
+        assert!(token.get("suggestions").is_none());
+    }
+
+    #[test]
+    fn test_format_batch_summary() {
+        let summary = BatchVerificationSummary {
+            total: 2,
+            submitted: 1,
+            results: vec![
+                result("Token", Some("job-1"), None),
+                result("Nft", None, Some("[E019] File too large, see below")),
+            ],
+        };
+        let explorer = Url::parse("https://voyager.online").unwrap();
+
+        let table = format_table(&summary, Some(&explorer));
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[1].starts_with("│ Contract │ Status  │ Duration │ Job ID │ Explorer "));
+        assert_eq!(
+            lines[3],
+            "│ Token    │ Success │ 1m 35s   │ job-1  │ https://voyager.online/class/0x1234 │"
+        );
+        assert_eq!(
+            lines[4],
+            "│ Nft      │ Error   │ -        │ -      │ https://voyager.online/class/0x1234 │"
+        );
+        assert_eq!(lines[8], "  Nft: [E019] File too large, see below");
+
+        let csv = format_csv(&summary, Some(&explorer));
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            rows[0],
+            "contract_name,package,class_hash,job_id,status,duration_secs,error,explorer_url"
+        );
+        assert_eq!(
+            rows[1],
+            "Token,,0x1234,job-1,Success,95,,https://voyager.online/class/0x1234"
+        );
+        assert_eq!(
+            rows[2],
+            "Nft,,0x1234,,,,\"[E019] File too large, see below\",https://voyager.online/class/0x1234"
+        );
+
+        let api_url = Url::parse("https://api.voyager.online/beta").unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&format_json(&summary, &api_url, Some(&explorer)).unwrap())
+                .unwrap();
+        assert_eq!(json["api_url"], "https://api.voyager.online/beta");
+        assert_eq!(json["submitted"], 1);
+        assert_eq!(json["contracts"][0]["job_id"], "job-1");
+        assert_eq!(
+            json["contracts"][1]["error"],
+            "[E019] File too large, see below"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Links for sharing a verification job
+//!
+//! After a submission, `voyager verify` prints the command that checks the
+//! job and, for the public networks, the job's page on the explorer.
+//! `voyager share` turns the same two into a markdown snippet for pull
+//! request descriptions.
+
+use crate::cli::args::NetworkKind;
+use crate::utils::voyager;
+use std::fmt::Write;
+use url::Url;
+
+/// A verification job and where it can be followed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobShare {
+    pub job_id: String,
+    /// API the job was submitted to
+    pub api_url: Url,
+    /// Network of the API, if known
+    pub network: Option<NetworkKind>,
+    /// Explorer the job is shown on, if known
+    pub explorer: Option<Url>,
+    /// Contract and class hash, when known from history
+    pub contract: Option<(String, String)>,
+}
+
+impl JobShare {
+    /// Share the job `job_id` of the API at `api_url`
+    ///
+    /// The network and explorer are derived from `api_url` for the public
+    /// Voyager APIs, and from `network` otherwise.
+    #[must_use]
+    pub fn new(job_id: &str, api_url: &Url, network: Option<NetworkKind>) -> Self {
+        let network = voyager::network_for_api_url(api_url).or(network);
+        let explorer = network
+            .as_ref()
+            .and_then(|network| Url::parse(voyager::explorer_url(network)).ok());
+        Self {
+            job_id: job_id.to_string(),
+            api_url: api_url.clone(),
+            network,
+            explorer,
+            contract: None,
+        }
+    }
+
+    /// Command printing the status of the job
+    #[must_use]
+    pub fn status_command(&self) -> String {
+        let api = match (&self.network, voyager::network_for_api_url(&self.api_url)) {
+            (Some(network), Some(_)) => format!("--network {}", voyager::network_name(network)),
+            _ => format!("--url {}", self.api_url),
+        };
+        format!("voyager status {api} --job {}", self.job_id)
+    }
+
+    /// Explorer page of the job, if the explorer is known
+    #[must_use]
+    pub fn job_link(&self) -> Option<String> {
+        self.explorer
+            .as_ref()
+            .map(|explorer| voyager::explorer_job_url(explorer, &self.job_id))
+    }
+
+    /// Markdown snippet with the job, its link and the status command
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut output = match &self.contract {
+            Some((name, class_hash)) => {
+                format!("**Contract verification:** `{name}` (`{class_hash}`)\n\n")
+            }
+            None => "**Contract verification**\n\n".to_string(),
+        };
+        // Writing to a String cannot fail
+        let _ = match self.job_link() {
+            Some(link) => writeln!(output, "- Job: [`{}`]({link})", self.job_id),
+            None => writeln!(output, "- Job: `{}`", self.job_id),
+        };
+        let _ = write!(
+            output,
+            "- Check the status with:\n\n  ```bash\n  {}\n  ```\n",
+            self.status_command()
+        );
+        output
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_public_and_custom_api() {
+        let sepolia = Url::parse(voyager::api_url(&NetworkKind::Sepolia)).unwrap();
+        let mut share = JobShare::new("abc-123", &sepolia, None);
+        assert_eq!(
+            share.status_command(),
+            "voyager status --network sepolia --job abc-123"
+        );
+        assert_eq!(
+            share.job_link().as_deref(),
+            Some("https://sepolia.voyager.online/verify/job/abc-123")
+        );
+
+        share.contract = Some(("MyToken".to_string(), "0x1234".to_string()));
+        let markdown = share.to_markdown();
+        assert!(markdown.starts_with("**Contract verification:** `MyToken` (`0x1234`)\n"));
+        assert!(markdown
+            .contains("- Job: [`abc-123`](https://sepolia.voyager.online/verify/job/abc-123)\n"));
+        assert!(markdown.contains("  voyager status --network sepolia --job abc-123\n"));
+
+        let custom = Url::parse("https://verifier.example.com/api").unwrap();
+        let share = JobShare::new("abc-123", &custom, Some(NetworkKind::Mainnet));
+        assert_eq!(
+            share.status_command(),
+            "voyager status --url https://verifier.example.com/api --job abc-123"
+        );
+        assert_eq!(
+            share.job_link().as_deref(),
+            Some("https://voyager.online/verify/job/abc-123")
+        );
+
+        let share = JobShare::new("abc-123", &custom, None);
+        assert!(share.to_markdown().contains("- Job: `abc-123`\n"));
+    }
+}
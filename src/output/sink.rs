@@ -0,0 +1,294 @@
+//! Where the verification flow writes its messages
+//!
+//! `core::verification` reports submissions, dry runs and batch progress as
+//! lines of text. It hands them to the [`OutputSink`] installed with
+//! [`install`] instead of printing them, so applications embedding `submit`
+//! or `watch_batch` decide where they go. Until a sink is installed, they are
+//! discarded.
+//!
+//! Colors and emoji are opted into separately: a sink that returns `false`
+//! from [`OutputSink::colors`] receives no ANSI escape codes, and one that
+//! returns `false` from [`OutputSink::emoji`] gets plain-text markers instead
+//! of symbols. The CLI installs a sink writing to the terminal with both
+//! enabled.
+
+use super::status;
+use crate::api::ProgressObserver;
+use colored::{ColoredString, Colorize};
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+
+/// Receives the lines the verification flow would print
+pub trait OutputSink: Send + Sync {
+    /// A line of regular output
+    fn line(&self, text: &str);
+
+    /// A warning or error detail, kept apart from regular output
+    fn warning(&self, text: &str);
+
+    /// A progress line replacing the previous one, or `None` to clear it
+    ///
+    /// Sinks that cannot rewrite a line in place ignore it, as by default.
+    fn progress(&self, _text: Option<&str>) {}
+
+    /// Whether progress lines are shown, so that a status can be rewritten in
+    /// place instead of being logged line by line
+    fn shows_progress(&self) -> bool {
+        false
+    }
+
+    /// Whether lines may contain ANSI colors
+    fn colors(&self) -> bool {
+        false
+    }
+
+    /// Whether lines may contain emoji and other symbols
+    fn emoji(&self) -> bool {
+        false
+    }
+}
+
+/// Sink that drops every line; used until another one is installed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Discard;
+
+impl OutputSink for Discard {
+    fn line(&self, _text: &str) {}
+
+    fn warning(&self, _text: &str) {}
+}
+
+static SINK: RwLock<Option<Arc<dyn OutputSink>>> = RwLock::new(None);
+
+/// Send the output of the verification flow to `sink` from now on
+pub fn install(sink: Arc<dyn OutputSink>) {
+    *SINK.write().unwrap_or_else(PoisonError::into_inner) = Some(sink);
+}
+
+/// The installed sink, or [`Discard`]
+#[must_use]
+pub fn current() -> Arc<dyn OutputSink> {
+    SINK.read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+        .unwrap_or_else(|| Arc::new(Discard))
+}
+
+/// Write a line of regular output to the installed sink
+pub fn line(text: impl AsRef<str>) {
+    current().line(text.as_ref());
+}
+
+/// Write a warning or error detail to the installed sink
+pub fn warning(text: impl AsRef<str>) {
+    current().warning(text.as_ref());
+}
+
+/// Show a progress line on the installed sink, or clear it with `None`
+pub fn progress(text: Option<&str>) {
+    current().progress(text);
+}
+
+/// How often the upload progress line is redrawn
+const UPLOAD_REDRAW: Duration = Duration::from_millis(100);
+
+/// Progress observer showing uploads and rate limits on the installed sink
+///
+/// Attached to the clients of the CLI, so that large submissions and waits on
+/// rate limited APIs are shown rather than looking like a hang.
+#[derive(Debug, Default)]
+pub struct SinkProgress {
+    upload: Mutex<Option<Upload>>,
+}
+
+/// A streamed upload being shown
+#[derive(Debug)]
+struct Upload {
+    started: Instant,
+    sent: u64,
+    drawn: Option<Instant>,
+}
+
+impl Upload {
+    /// Record that `sent` of `total` bytes of the upload in `slot` were sent
+    ///
+    /// Returns when the upload started if its line is due to be redrawn.
+    fn advance(slot: &mut Option<Self>, sent: u64, total: u64, now: Instant) -> Option<Instant> {
+        let upload = match slot.as_mut() {
+            // A request sent again starts over
+            Some(upload) if upload.sent <= sent => upload,
+            _ => slot.insert(Self {
+                started: now,
+                sent,
+                drawn: None,
+            }),
+        };
+        upload.sent = sent;
+        if sent < total
+            && upload
+                .drawn
+                .is_some_and(|drawn| now.duration_since(drawn) < UPLOAD_REDRAW)
+        {
+            return None;
+        }
+        upload.drawn = Some(now);
+        Some(upload.started)
+    }
+}
+
+impl ProgressObserver for SinkProgress {
+    fn upload_progress(&self, sent: u64, total: u64) {
+        let now = Instant::now();
+        let due = Upload::advance(
+            &mut self.upload.lock().unwrap_or_else(PoisonError::into_inner),
+            sent,
+            total,
+            now,
+        );
+        let Some(started) = due else {
+            return;
+        };
+        if sent >= total {
+            progress(None);
+            return;
+        }
+        progress(Some(&status::format_upload_progress(
+            sent,
+            total,
+            now.duration_since(started),
+        )));
+    }
+
+    fn rate_limited(&self, endpoint: &str, delay: Duration) {
+        warning(format!(
+            "{} Rate limited by the API on {endpoint}; retrying in {}s",
+            marker(Marker::Warning),
+            delay.as_secs()
+        ));
+    }
+}
+
+/// `text` in `tone`, if the installed sink takes colors
+#[must_use]
+pub fn paint(text: impl fmt::Display, tone: Tone) -> String {
+    tone.render(text, current().colors())
+}
+
+/// The symbol of `marker` in the style the installed sink takes
+#[must_use]
+pub fn marker(marker: Marker) -> String {
+    let sink = current();
+    marker.render(sink.colors(), sink.emoji())
+}
+
+/// What a piece of text stands for, rendered as a color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tone {
+    Success,
+    Failure,
+    Pending,
+    /// Identifiers such as job ids
+    Accent,
+    /// Details of lesser interest, such as shortened class hashes
+    Muted,
+    /// Section titles
+    Heading,
+    /// Names of what is being worked on
+    Emphasis,
+    /// Raw output that needs attention
+    Notice,
+}
+
+impl Tone {
+    fn style(self, text: ColoredString) -> ColoredString {
+        match self {
+            Self::Success => text.green(),
+            Self::Failure => text.red(),
+            Self::Pending => text.yellow(),
+            Self::Accent => text.cyan(),
+            Self::Muted => text.bright_black(),
+            Self::Heading => text.bright_cyan().bold(),
+            Self::Emphasis => text.bright_white().bold(),
+            Self::Notice => text.bright_yellow(),
+        }
+    }
+
+    /// `text` in this tone, or as is without `colors`
+    #[must_use]
+    pub fn render(self, text: impl fmt::Display, colors: bool) -> String {
+        let text = text.to_string();
+        if colors {
+            self.style(text.as_str().normal()).to_string()
+        } else {
+            text
+        }
+    }
+}
+
+/// Symbol marking the outcome of a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    Success,
+    Failure,
+    Pending,
+    Skipped,
+    /// The end of a dry run
+    Done,
+    Warning,
+}
+
+impl Marker {
+    /// Symbol and plain-text replacement
+    const fn symbols(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Success => ("✓", "[ok]"),
+            Self::Failure => ("✗", "[failed]"),
+            Self::Pending => ("⏳", "[pending]"),
+            Self::Skipped => ("○", "[skipped]"),
+            Self::Done => ("✅", "[done]"),
+            Self::Warning => ("⚠️ ", "[warning]"),
+        }
+    }
+
+    const fn tone(self) -> Option<Tone> {
+        match self {
+            Self::Success => Some(Tone::Success),
+            Self::Failure => Some(Tone::Failure),
+            Self::Pending => Some(Tone::Pending),
+            Self::Skipped => Some(Tone::Muted),
+            Self::Done | Self::Warning => None,
+        }
+    }
+
+    /// The marker with or without colors and emoji
+    #[must_use]
+    pub fn render(self, colors: bool, emoji: bool) -> String {
+        let (symbol, plain) = self.symbols();
+        let text = if emoji { symbol } else { plain };
+        match self.tone() {
+            Some(tone) if colors => tone.style(text.bold()).to_string(),
+            _ => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_colors_and_emoji_are_gated_separately() {
+        assert_eq!(Tone::Failure.render("Failed", false), "Failed");
+        assert_eq!(Marker::Success.render(false, false), "[ok]");
+        assert_eq!(Marker::Success.render(false, true), "✓");
+        assert_eq!(Marker::Skipped.render(false, true), "○");
+        assert_eq!(Marker::Done.render(true, true), "✅");
+
+        // Nothing is printed before a sink is installed
+        assert_eq!(paint("0x1234", Tone::Accent), "0x1234");
+        assert_eq!(marker(Marker::Pending), "[pending]");
+    }
+}
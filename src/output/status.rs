@@ -5,16 +5,18 @@
 //! - JSON output for programmatic parsing
 //! - Table format for batch operations
 
-use crate::api::{VerificationJob, VerifyJobStatus};
-use crate::cli::args::OutputFormat;
-use chrono::{DateTime, Utc};
+use super::results::csv_row;
+use crate::api::{ClassVerification, ClassVerificationInfo, VerificationJob, VerifyJobStatus};
+use crate::cli::args::{OutputFormat, Timezone};
+use crate::core::{analyze::format_size, class_hash::ClassHash};
+use chrono::{DateTime, Local, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt::Write;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-/// Format timestamp as human-readable string
+/// Convert a Unix timestamp in seconds to a UTC datetime
 #[must_use]
-pub fn format_timestamp(timestamp: f64) -> String {
+pub fn timestamp_to_datetime(timestamp: f64) -> DateTime<Utc> {
     // Safely convert f64 to i64, clamping to valid i64 range
     #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
     let timestamp_secs = if timestamp.is_finite() {
@@ -23,9 +25,62 @@ pub fn format_timestamp(timestamp: f64) -> String {
         0
     };
 
-    let datetime = DateTime::from_timestamp(timestamp_secs, 0)
-        .unwrap_or_else(|| DateTime::<Utc>::from(UNIX_EPOCH));
-    datetime.format("%Y-%m-%d %H:%M:%S UTC").to_string()
+    DateTime::from_timestamp(timestamp_secs, 0).unwrap_or_else(|| DateTime::<Utc>::from(UNIX_EPOCH))
+}
+
+/// Format timestamp as human-readable string in `timezone`, followed by the
+/// time relative to now (e.g. "2024-01-01 00:00:00 UTC (3 minutes ago)")
+#[must_use]
+pub fn format_timestamp(timestamp: f64, timezone: Timezone) -> String {
+    format_datetime(timestamp_to_datetime(timestamp), timezone)
+}
+
+/// Format a datetime like [`format_timestamp`]
+#[must_use]
+pub fn format_datetime(datetime: DateTime<Utc>, timezone: Timezone) -> String {
+    format!(
+        "{} ({})",
+        format_absolute(datetime, timezone),
+        format_relative(datetime, Utc::now())
+    )
+}
+
+/// Format a datetime without the relative part
+/// Render `datetime` in `timezone`, without the relative time
+#[must_use]
+pub fn format_absolute(datetime: DateTime<Utc>, timezone: Timezone) -> String {
+    match timezone {
+        Timezone::Utc => datetime.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+        Timezone::Local => datetime
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M:%S %:z")
+            .to_string(),
+    }
+}
+
+/// Describe `datetime` relative to `now`, e.g. "3 minutes ago" or "in 2 hours"
+#[must_use]
+pub fn format_relative(datetime: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - datetime).num_seconds();
+    let amount = seconds.unsigned_abs();
+
+    // Small differences are usually clock skew between us and the API
+    if amount < 60 {
+        return "just now".to_string();
+    }
+
+    let (value, unit) = match amount {
+        ..3_600 => (amount / 60, "minute"),
+        3_600..86_400 => (amount / 3_600, "hour"),
+        _ => (amount / 86_400, "day"),
+    };
+    let plural = if value == 1 { "" } else { "s" };
+
+    if seconds < 0 {
+        format!("in {value} {unit}{plural}")
+    } else {
+        format!("{value} {unit}{plural} ago")
+    }
 }
 
 /// Calculate elapsed time in seconds from creation to now
@@ -82,8 +137,19 @@ fn calculate_elapsed_between(created: Option<f64>, updated: Option<f64>) -> Opti
     Some(end_secs.saturating_sub(start_secs))
 }
 
+/// Seconds a job took, or has taken so far if it has not finished
+#[must_use]
+pub fn job_elapsed(job: &VerificationJob) -> Option<u64> {
+    if job.is_completed() {
+        calculate_elapsed_between(job.created_timestamp(), job.updated_timestamp())
+    } else {
+        calculate_elapsed(job.created_timestamp(), job.updated_timestamp())
+    }
+}
+
 /// Format duration in seconds to human-readable string
-fn format_duration(seconds: u64) -> String {
+#[must_use]
+pub fn format_duration(seconds: u64) -> String {
     if seconds < 60 {
         format!("{seconds}s")
     } else if seconds < 3600 {
@@ -105,7 +171,7 @@ fn get_average_from_history() -> Option<u64> {
     use crate::storage::history::HistoryDb;
 
     // Try to open history DB and get average
-    HistoryDb::open().ok().and_then(|db| {
+    HistoryDb::open_read_only().ok().and_then(|db| {
         db.get_average_verification_time(10, 3) // Last 10 samples, min 3
             .ok()
             .flatten()
@@ -184,12 +250,32 @@ fn progress_bar(percentage: u8) -> String {
     )
 }
 
+/// Single line showing the upload of `sent` of `total` bytes of a
+/// verification request, started `elapsed` ago
+#[must_use]
+pub fn format_upload_progress(sent: u64, total: u64, elapsed: Duration) -> String {
+    #[allow(clippy::cast_possible_truncation)]
+    let percentage = (u128::from(sent) * 100 / u128::from(total.max(1))).min(100) as u8;
+    let mut line = format!(
+        "⏳ Uploading {} {} / {}",
+        progress_bar(percentage),
+        format_size(sent),
+        format_size(total)
+    );
+    if sent > 0 {
+        #[allow(clippy::cast_precision_loss)]
+        let remaining = elapsed.mul_f64(total.saturating_sub(sent) as f64 / sent as f64);
+        let _ = write!(line, " [ETA {}]", format_duration(remaining.as_secs()));
+    }
+    line
+}
+
 /// Format verification job as enhanced text
 ///
 /// # Errors
 ///
 /// Returns `std::fmt::Error` if writing to the output string fails (should never happen in practice).
-pub fn format_text(job: &VerificationJob) -> Result<String, std::fmt::Error> {
+pub fn format_text(job: &VerificationJob, timezone: Timezone) -> Result<String, std::fmt::Error> {
     let mut output = String::new();
 
     // Header with status emoji
@@ -224,20 +310,20 @@ pub fn format_text(job: &VerificationJob) -> Result<String, std::fmt::Error> {
 
     // Time information
     if let Some(created) = job.created_timestamp() {
-        writeln!(output, "Started: {}", format_timestamp(created))?;
+        writeln!(output, "Started: {}", format_timestamp(created, timezone))?;
     }
     if let Some(updated) = job.updated_timestamp() {
-        writeln!(output, "Last Updated: {}", format_timestamp(updated))?;
+        writeln!(
+            output,
+            "Last Updated: {}",
+            format_timestamp(updated, timezone)
+        )?;
     }
 
     // Elapsed and estimated time
     // For completed jobs, show actual elapsed time between created and updated
     // For in-progress jobs, show elapsed time from created to now
-    let elapsed = if job.is_completed() {
-        calculate_elapsed_between(job.created_timestamp(), job.updated_timestamp())
-    } else {
-        calculate_elapsed(job.created_timestamp(), job.updated_timestamp())
-    };
+    let elapsed = job_elapsed(job);
 
     if let Some(elapsed_secs) = elapsed {
         writeln!(output, "Elapsed: {}", format_duration(elapsed_secs))?;
@@ -263,6 +349,9 @@ pub fn format_text(job: &VerificationJob) -> Result<String, std::fmt::Error> {
     if let Some(license) = job.license() {
         writeln!(output, "License: {license}")?;
     }
+    if let Some(note) = job.note() {
+        writeln!(output, "Note: {note}")?;
+    }
 
     // Status-specific messages
     match job.status() {
@@ -317,15 +406,13 @@ pub struct JsonOutput {
     pub license: Option<String>,
     pub address: Option<String>,
     pub build_tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
 }
 
 /// Format verification job as JSON
 pub fn format_json(job: &VerificationJob) -> String {
-    let elapsed = if job.is_completed() {
-        calculate_elapsed_between(job.created_timestamp(), job.updated_timestamp())
-    } else {
-        calculate_elapsed(job.created_timestamp(), job.updated_timestamp())
-    };
+    let elapsed = job_elapsed(job);
     let estimated_remaining = elapsed.and_then(|e| estimate_remaining_time(*job.status(), e));
 
     let output = JsonOutput {
@@ -341,8 +428,13 @@ pub fn format_json(job: &VerificationJob) -> String {
         status_description: job.status_description().map(String::from),
         message: job.message().map(String::from),
         error_category: job.error_category().map(String::from),
-        created_at: job.created_timestamp().map(format_timestamp),
-        updated_at: job.updated_timestamp().map(format_timestamp),
+        // Machine-readable output always uses absolute UTC timestamps
+        created_at: job
+            .created_timestamp()
+            .map(|ts| format_absolute(timestamp_to_datetime(ts), Timezone::Utc)),
+        updated_at: job
+            .updated_timestamp()
+            .map(|ts| format_absolute(timestamp_to_datetime(ts), Timezone::Utc)),
         elapsed_seconds: elapsed,
         estimated_remaining_seconds: estimated_remaining,
         cairo_version: job.version().map(String::from),
@@ -350,6 +442,7 @@ pub fn format_json(job: &VerificationJob) -> String {
         license: job.license().map(String::from),
         address: job.address().map(String::from),
         build_tool: job.build_tool().map(String::from),
+        note: job.note().map(String::from),
     };
 
     serde_json::to_string_pretty(&output).unwrap_or_else(|e| {
@@ -360,12 +453,56 @@ pub fn format_json(job: &VerificationJob) -> String {
     })
 }
 
+/// JSON output of a class check
+///
+/// The fields of the check endpoint, with the state of the class so scripts
+/// can tell a class to verify from one to declare first.
+#[derive(Debug, Serialize)]
+pub struct ClassCheckOutput {
+    /// `verified`, `not_verified` or `not_declared`
+    pub state: &'static str,
+    #[serde(flatten)]
+    pub info: ClassVerificationInfo,
+    /// Age of a result served from the local cache
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_age_secs: Option<u64>,
+}
+
+impl ClassCheckOutput {
+    #[must_use]
+    pub fn new(verification: &ClassVerification, class_hash: &ClassHash) -> Self {
+        let info = verification
+            .info()
+            .cloned()
+            .unwrap_or_else(|| ClassVerificationInfo {
+                verified: false,
+                class_hash: class_hash.to_string(),
+                name: None,
+                version: None,
+                license: None,
+                verified_timestamp: None,
+                contract_file: None,
+            });
+        Self {
+            state: verification.state(),
+            info,
+            cache_age_secs: None,
+        }
+    }
+
+    /// Pretty-printed JSON
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| format!("{self:?}"))
+    }
+}
+
 /// Format verification job as table (primarily for batch operations)
 ///
 /// # Errors
 ///
 /// Returns `std::fmt::Error` if writing to the output string fails (should never happen in practice).
-pub fn format_table(job: &VerificationJob) -> Result<String, std::fmt::Error> {
+pub fn format_table(job: &VerificationJob, timezone: Timezone) -> Result<String, std::fmt::Error> {
     let mut output = String::new();
 
     // Table header
@@ -406,14 +543,10 @@ pub fn format_table(job: &VerificationJob) -> Result<String, std::fmt::Error> {
     }
 
     if let Some(created) = job.created_timestamp() {
-        add_row(&mut output, "Started", &format_timestamp(created))?;
+        add_row(&mut output, "Started", &format_timestamp(created, timezone))?;
     }
 
-    let elapsed = if job.is_completed() {
-        calculate_elapsed_between(job.created_timestamp(), job.updated_timestamp())
-    } else {
-        calculate_elapsed(job.created_timestamp(), job.updated_timestamp())
-    };
+    let elapsed = job_elapsed(job);
     if let Some(elapsed_secs) = elapsed {
         add_row(&mut output, "Elapsed", &format_duration(elapsed_secs))?;
     }
@@ -469,17 +602,52 @@ pub fn format_inline_status(job: &VerificationJob) -> String {
     }
 }
 
+/// Format verification job as CSV: a header row and the job's row
+///
+/// Timestamps are RFC 3339 in UTC, so they sort and parse regardless of
+/// `--timezone`.
+#[must_use]
+pub fn format_csv(job: &VerificationJob) -> String {
+    let timestamp = |ts: Option<f64>| {
+        ts.map(|ts| timestamp_to_datetime(ts).to_rfc3339())
+            .unwrap_or_default()
+    };
+    let elapsed = job_elapsed(job).map(|secs| secs.to_string());
+    let mut output = csv_row(&[
+        "job_id",
+        "status",
+        "class_hash",
+        "contract_name",
+        "created",
+        "updated",
+        "elapsed_secs",
+        "cairo_version",
+    ]);
+    output.push_str(&csv_row(&[
+        job.job_id(),
+        &job.status().to_string(),
+        job.class_hash.as_deref().unwrap_or_default(),
+        job.name().unwrap_or_default(),
+        &timestamp(job.created_timestamp()),
+        &timestamp(job.updated_timestamp()),
+        elapsed.as_deref().unwrap_or_default(),
+        job.version().unwrap_or_default(),
+    ]));
+    output.trim_end().to_string()
+}
+
 /// Main formatting function that delegates to specific formatters
 #[must_use]
-pub fn format_status(job: &VerificationJob, format: &OutputFormat) -> String {
+pub fn format_status(job: &VerificationJob, format: &OutputFormat, timezone: Timezone) -> String {
     match format {
         OutputFormat::Text => {
-            format_text(job).unwrap_or_else(|_| "Error formatting text".to_string())
+            format_text(job, timezone).unwrap_or_else(|_| "Error formatting text".to_string())
         }
         OutputFormat::Json => format_json(job),
         OutputFormat::Table => {
-            format_table(job).unwrap_or_else(|_| "Error formatting table".to_string())
+            format_table(job, timezone).unwrap_or_else(|_| "Error formatting table".to_string())
         }
+        OutputFormat::Csv => format_csv(job),
     }
 }
 
@@ -487,6 +655,33 @@ pub fn format_status(job: &VerificationJob, format: &OutputFormat) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn test_class_check_output_states() {
+        let class_hash = ClassHash::new("0x1234").unwrap();
+        let json = |verification: &ClassVerification| {
+            serde_json::to_value(ClassCheckOutput::new(verification, &class_hash)).unwrap()
+        };
+
+        let not_declared = json(&ClassVerification::NotDeclared);
+        assert_eq!(not_declared["state"], "not_declared");
+        assert_eq!(not_declared["verified"], false);
+        assert_eq!(not_declared["class_hash"], "0x1234");
+        assert_eq!(
+            json(&ClassVerification::NotVerified)["state"],
+            "not_verified"
+        );
+
+        let info: ClassVerificationInfo = serde_json::from_str(
+            r#"{"verified": true, "class_hash": "0x1234", "name": "MyToken"}"#,
+        )
+        .unwrap();
+        let verified = json(&ClassVerification::Verified(info));
+        assert_eq!(verified["state"], "verified");
+        assert_eq!(verified["name"], "MyToken");
+        assert!(verified.get("cache_age_secs").is_none());
+    }
+
     #[test]
     fn test_format_duration() {
         assert_eq!(format_duration(30), "30s");
@@ -510,10 +705,33 @@ mod tests {
         assert!(bar.contains("░"));
     }
 
+    #[test]
+    fn test_format_upload_progress() {
+        let line = format_upload_progress(1024 * 1024, 4 * 1024 * 1024, Duration::from_secs(2));
+        assert!(line.starts_with("⏳ Uploading "));
+        assert!(line.ends_with("(25%) 1.0 MiB / 4.0 MiB [ETA 6s]"), "{line}");
+        assert!(!format_upload_progress(0, 1024, Duration::ZERO).contains("ETA"));
+    }
+
     #[test]
     fn test_format_timestamp() {
         let ts = 1_704_067_200.0; // 2024-01-01 00:00:00 UTC
-        let formatted = format_timestamp(ts);
+        let formatted = format_timestamp(ts, Timezone::Utc);
         assert!(formatted.contains("2024-01-01"));
+        assert!(formatted.starts_with("2024-01-01 00:00:00 UTC ("));
+    }
+
+    #[test]
+    fn test_format_relative() {
+        let now = timestamp_to_datetime(1_704_067_200.0);
+        let ago = |secs: i64| format_relative(now - chrono::Duration::seconds(secs), now);
+
+        assert_eq!(ago(5), "just now");
+        assert_eq!(ago(-5), "just now");
+        assert_eq!(ago(60), "1 minute ago");
+        assert_eq!(ago(3 * 60 + 59), "3 minutes ago");
+        assert_eq!(ago(2 * 3_600), "2 hours ago");
+        assert_eq!(ago(86_400), "1 day ago");
+        assert_eq!(ago(-2 * 3_600), "in 2 hours");
     }
 }
@@ -0,0 +1,124 @@
+//! Summary of a submission, shown before uploading
+//!
+//! Gathers what is otherwise spread over log lines and the dry-run payload into
+//! one box, so the contract, sources and toolchain can be checked at a glance
+//! before `voyager verify` asks to submit them.
+
+use crate::core::analyze::format_size;
+use std::fmt::Write;
+
+/// What is about to be submitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionSummary {
+    pub contract_name: String,
+    pub class_hash: String,
+    pub package: String,
+    pub contract_file: String,
+    pub files: usize,
+    /// Total size of the files, in bytes
+    pub payload_size: u64,
+    pub cairo_version: String,
+    pub scarb_version: String,
+    /// `scarb` or `sozo`
+    pub build_tool: &'static str,
+    pub license: String,
+    /// Network name and API URL
+    pub network: String,
+    pub fingerprint: Option<String>,
+    /// Why the verification is submitted, shown only when given
+    pub note: Option<String>,
+}
+
+impl SubmissionSummary {
+    /// The summary as a box of label and value rows
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut rows = vec![
+            ("Contract", self.contract_name.clone()),
+            ("Class hash", self.class_hash.clone()),
+            ("Package", self.package.clone()),
+            ("Contract file", self.contract_file.clone()),
+            (
+                "Files",
+                format!("{} ({})", self.files, format_size(self.payload_size)),
+            ),
+            (
+                "Compiler",
+                format!(
+                    "cairo {}, scarb {} ({} build)",
+                    self.cairo_version, self.scarb_version, self.build_tool
+                ),
+            ),
+            ("License", self.license.clone()),
+            ("Network", self.network.clone()),
+            (
+                "Fingerprint",
+                self.fingerprint
+                    .clone()
+                    .unwrap_or_else(|| "unavailable".to_string()),
+            ),
+        ];
+        if let Some(note) = &self.note {
+            rows.push(("Note", note.clone()));
+        }
+
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let width = rows
+            .iter()
+            .map(|(_, value)| label_width + 2 + value.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let title = " Submission ";
+        let mut output = format!(
+            "┌─{title}{}─┐\n",
+            "─".repeat(width.saturating_sub(title.len()))
+        );
+        for (label, value) in &rows {
+            let padding = width - label_width - 2 - value.chars().count();
+            // Writing to a String cannot fail
+            let _ = writeln!(
+                output,
+                "│ {label:<label_width$}  {value}{} │",
+                " ".repeat(padding)
+            );
+        }
+        let _ = write!(output, "└─{}─┘", "─".repeat(width.max(title.len())));
+        output
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_submission_summary() {
+        let summary = SubmissionSummary {
+            contract_name: "MyToken".to_string(),
+            class_hash: "0x044dc2b3239382230d8b1e943df23b96f52eebcac93efe6e8bde92f9a2f1da18"
+                .to_string(),
+            package: "token".to_string(),
+            contract_file: "src/lib.cairo".to_string(),
+            files: 3,
+            payload_size: 2_560,
+            cairo_version: "2.11.4".to_string(),
+            scarb_version: "2.11.4".to_string(),
+            build_tool: "scarb",
+            license: "MIT".to_string(),
+            network: "mainnet (https://api.voyager.online/beta)".to_string(),
+            fingerprint: None,
+            note: Some("release v1.4 hotfix".to_string()),
+        };
+        let rendered = summary.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert!(lines[0].starts_with("┌─ Submission ─"));
+        assert!(rendered.contains("│ Files          3 (2.5 KiB) "));
+        assert!(rendered.contains("│ Fingerprint    unavailable "));
+        assert!(rendered.contains("│ Note           release v1.4 hotfix "));
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+    }
+}
@@ -0,0 +1,219 @@
+//! Persisted plans of batch verifications
+//!
+//! Before a batch is submitted, its contract list is written to
+//! `~/.voyager/batches/<id>.json`, and each contract is marked as done there
+//! once it was submitted or found to be verified already. If the process dies
+//! mid-batch, `voyager verify --resume` reads the plan back and only submits
+//! the contracts that are not done. Contracts that failed to submit are not
+//! marked, so a resumed batch retries them.
+//!
+//! The plan file is removed once every contract is done.
+
+use crate::cli::config::ContractConfig;
+use camino::Utf8PathBuf;
+use chrono::Utc;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+use url::Url;
+
+/// A contract of the plan that does not need to be submitted again
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletedContract {
+    /// Position of the contract in [`BatchPlan::contracts`]
+    pub index: usize,
+    /// Job of the submission, or `None` if the class was already verified
+    pub job_id: Option<String>,
+}
+
+/// Contracts of a batch verification and which of them are done
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchPlan {
+    pub id: String,
+    /// Unix timestamp (seconds) at which the batch was started
+    pub created_at: i64,
+    /// Root directory of the project the sources are collected from
+    pub project: Utf8PathBuf,
+    pub api_url: String,
+    pub contracts: Vec<ContractConfig>,
+    pub completed: Vec<CompletedContract>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl BatchPlan {
+    /// The completion record of the contract at `index`, if it is done
+    #[must_use]
+    pub fn completed(&self, index: usize) -> Option<&CompletedContract> {
+        self.completed.iter().find(|done| done.index == index)
+    }
+
+    /// Mark the contract at `index` as done
+    pub fn record(&mut self, index: usize, job_id: Option<String>) {
+        if self.completed(index).is_none() {
+            self.completed.push(CompletedContract { index, job_id });
+        }
+    }
+
+    /// Number of contracts that are not done yet
+    #[must_use]
+    pub const fn remaining(&self) -> usize {
+        self.contracts.len().saturating_sub(self.completed.len())
+    }
+
+    /// Write the plan to its file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the batches directory or the plan file cannot be
+    /// written.
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            &self.path,
+            serde_json::to_vec_pretty(self).map_err(io::Error::other)?,
+        )
+    }
+
+    /// Remove the plan file, once the batch no longer needs to be resumed
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be removed.
+    pub fn remove(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+/// Directory of persisted batch plans
+pub struct BatchStore {
+    dir: PathBuf,
+}
+
+impl BatchStore {
+    /// Open the store in the default location (`~/.voyager/batches`)
+    ///
+    /// Returns `None` if the home directory cannot be determined.
+    #[must_use]
+    pub fn open() -> Option<Self> {
+        let home = dirs::home_dir()?;
+        Some(Self::at(home.join(".voyager").join("batches")))
+    }
+
+    /// Use `dir` as the store directory
+    #[must_use]
+    pub const fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Start a new plan; it is written on the first [`BatchPlan::save`]
+    #[must_use]
+    pub fn create(
+        &self,
+        project: Utf8PathBuf,
+        api_url: &Url,
+        contracts: Vec<ContractConfig>,
+    ) -> BatchPlan {
+        let now = Utc::now();
+        let id = format!("{}-{}", now.format("%Y%m%d-%H%M%S"), std::process::id());
+        BatchPlan {
+            path: self.dir.join(format!("{id}.json")),
+            id,
+            created_at: now.timestamp(),
+            project,
+            api_url: api_url.to_string(),
+            contracts,
+            completed: vec![],
+        }
+    }
+
+    /// Load the plan with the given id
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the plan does not exist or cannot be read.
+    pub fn load(&self, id: &str) -> io::Result<BatchPlan> {
+        let path = self.dir.join(format!("{id}.json"));
+        let content = fs::read(&path)?;
+        let mut plan: BatchPlan = serde_json::from_slice(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        plan.path = path;
+        Ok(plan)
+    }
+
+    /// The most recent unfinished plan for the project at `project`
+    #[must_use]
+    pub fn latest(&self, project: &Utf8PathBuf) -> Option<BatchPlan> {
+        fs::read_dir(&self.dir)
+            .ok()?
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                let id = name.to_str()?.strip_suffix(".json")?;
+                self.load(id)
+                    .map_err(|e| debug!("Ignoring unreadable batch plan {id}: {e}"))
+                    .ok()
+            })
+            .filter(|plan| &plan.project == project && plan.remaining() > 0)
+            .max_by_key(|plan| plan.created_at)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn contract(name: &str) -> ContractConfig {
+        ContractConfig {
+            class_hash: "0x1234".to_string(),
+            contract_name: name.to_string(),
+            package: None,
+            contract_file: None,
+            depends_on: vec![],
+        }
+    }
+
+    #[test]
+    fn test_resume_latest_unfinished_plan() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = BatchStore::at(temp_dir.path().to_path_buf());
+        let url = Url::parse("https://api.voyager.online/beta").unwrap();
+        let project = Utf8PathBuf::from("/workspace/token");
+
+        let mut plan = store.create(
+            project.clone(),
+            &url,
+            vec![contract("Token"), contract("Vault"), contract("Router")],
+        );
+        plan.record(0, Some("job-1".to_string()));
+        plan.record(1, None);
+        plan.record(0, Some("job-2".to_string()));
+        plan.save().unwrap();
+
+        let resumed = store.latest(&project).unwrap();
+        assert_eq!(resumed.id, plan.id);
+        assert_eq!(resumed.remaining(), 1);
+        assert_eq!(
+            resumed.completed(0).and_then(|done| done.job_id.as_deref()),
+            Some("job-1")
+        );
+        assert!(resumed.completed(2).is_none());
+        assert!(store.latest(&Utf8PathBuf::from("/elsewhere")).is_none());
+
+        // Finished plans are not offered for resuming
+        let mut resumed = resumed;
+        resumed.record(2, Some("job-3".to_string()));
+        resumed.save().unwrap();
+        assert!(store.latest(&project).is_none());
+
+        resumed.remove().unwrap();
+        assert!(store.load(&plan.id).is_err());
+    }
+}
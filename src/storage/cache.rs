@@ -0,0 +1,326 @@
+//! Local caches for class verification checks and submissions
+//!
+//! Results of the check endpoint are stored as small JSON files under
+//! `~/.voyager/cache/check`, keyed by API endpoint and class hash, so that many
+//! CI pipelines pre-checking the same classes don't each hit the API.
+//!
+//! Only verified classes are cached. A class that is not verified yet can
+//! become verified at any moment, while a verified class stays verified, so a
+//! cached positive answer is safe to reuse until its TTL expires.
+//!
+//! Submissions are stored under `~/.voyager/cache/submissions` with the same
+//! keys, together with the digest of the request body. Sending the same
+//! request again, with the same sources after the payload transforms, license,
+//! versions, package and contract, reuses the job instead of uploading it once
+//! more.
+
+use crate::api::ClassVerificationInfo;
+use crate::core::class_hash::ClassHash;
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use url::Url;
+
+/// Default time-to-live for cached check results, in seconds
+pub const DEFAULT_CHECK_CACHE_TTL_SECS: u64 = 300;
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// Unix timestamp (seconds) at which the result was stored
+    cached_at: i64,
+    info: ClassVerificationInfo,
+}
+
+/// A check result served from the cache
+#[derive(Debug)]
+pub struct CachedCheck {
+    pub info: ClassVerificationInfo,
+    pub cached_at: DateTime<Utc>,
+}
+
+impl CachedCheck {
+    /// Seconds elapsed since the result was cached
+    #[must_use]
+    pub fn age_secs(&self) -> u64 {
+        u64::try_from((Utc::now() - self.cached_at).num_seconds()).unwrap_or(0)
+    }
+}
+
+/// File-backed cache of class verification check results
+pub struct CheckCache {
+    dir: PathBuf,
+}
+
+impl CheckCache {
+    /// Open the cache in the default location (`~/.voyager/cache/check`)
+    ///
+    /// Returns `None` if the home directory cannot be determined.
+    #[must_use]
+    pub fn open() -> Option<Self> {
+        let home = dirs::home_dir()?;
+        Some(Self::at(home.join(".voyager").join("cache").join("check")))
+    }
+
+    /// Use `dir` as the cache directory
+    #[must_use]
+    pub const fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn entry_path(&self, api_url: &Url, class_hash: &ClassHash) -> PathBuf {
+        entry_path(&self.dir, api_url, class_hash)
+    }
+
+    /// Look up a cached result that is younger than `ttl`
+    #[must_use]
+    pub fn get(&self, api_url: &Url, class_hash: &ClassHash, ttl: Duration) -> Option<CachedCheck> {
+        let path = self.entry_path(api_url, class_hash);
+        let content = fs::read_to_string(&path).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content)
+            .map_err(|e| debug!("Ignoring unreadable cache entry {}: {e}", path.display()))
+            .ok()?;
+
+        let cached = CachedCheck {
+            info: entry.info,
+            cached_at: DateTime::from_timestamp(entry.cached_at, 0)?,
+        };
+
+        if cached.age_secs() >= ttl.as_secs() {
+            debug!("Cache entry for {class_hash} expired");
+            return None;
+        }
+
+        Some(cached)
+    }
+
+    /// Store a check result; results for unverified classes are skipped
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory or entry file cannot be written.
+    pub fn put(
+        &self,
+        api_url: &Url,
+        class_hash: &ClassHash,
+        info: &ClassVerificationInfo,
+    ) -> io::Result<()> {
+        if !info.verified {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir)?;
+
+        let entry = CacheEntry {
+            cached_at: Utc::now().timestamp(),
+            info: info.clone(),
+        };
+        fs::write(
+            self.entry_path(api_url, class_hash),
+            serde_json::to_vec(&entry).map_err(io::Error::other)?,
+        )
+    }
+}
+
+/// Cache file for `class_hash` on the API at `api_url`
+///
+/// The file is named after a digest of the whole API URL, so endpoints that
+/// differ only in their scheme, port or query don't share entries. The URL
+/// is normalized first: credentials and fragment are dropped, as is a
+/// trailing slash.
+fn entry_path(dir: &Path, api_url: &Url, class_hash: &ClassHash) -> PathBuf {
+    let mut endpoint = api_url.clone();
+    let _ = endpoint.set_username("");
+    let _ = endpoint.set_password(None);
+    endpoint.set_fragment(None);
+    let endpoint = endpoint.as_str();
+    let endpoint = endpoint.strip_suffix('/').unwrap_or(endpoint);
+    let digest = ring::digest::digest(&ring::digest::SHA256, endpoint.as_bytes());
+    let digest = digest.as_ref().iter().map(|b| format!("{b:02x}")).join("");
+    dir.join(format!("{digest}-{class_hash}.json"))
+}
+
+/// A job that verified a request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedSubmission {
+    pub job_id: String,
+    /// Digest of the request body (see
+    /// [`content_digest`](crate::filesystem::collector::content_digest))
+    pub request_digest: String,
+    /// Unix timestamp (seconds) at which the job was submitted
+    pub submitted_at: i64,
+}
+
+/// File-backed cache of the last submission of each class
+pub struct SubmissionCache {
+    dir: PathBuf,
+}
+
+impl SubmissionCache {
+    /// Open the cache in the default location (`~/.voyager/cache/submissions`)
+    ///
+    /// Returns `None` if the home directory cannot be determined.
+    #[must_use]
+    pub fn open() -> Option<Self> {
+        let home = dirs::home_dir()?;
+        Some(Self::at(
+            home.join(".voyager").join("cache").join("submissions"),
+        ))
+    }
+
+    /// Use `dir` as the cache directory
+    #[must_use]
+    pub const fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The job that was submitted with the same request body, if any
+    #[must_use]
+    pub fn get(
+        &self,
+        api_url: &Url,
+        class_hash: &ClassHash,
+        request_digest: &str,
+    ) -> Option<CachedSubmission> {
+        let path = entry_path(&self.dir, api_url, class_hash);
+        let content = fs::read_to_string(&path).ok()?;
+        let submission: CachedSubmission = serde_json::from_str(&content)
+            .map_err(|e| debug!("Ignoring unreadable cache entry {}: {e}", path.display()))
+            .ok()?;
+
+        (submission.request_digest == request_digest).then_some(submission)
+    }
+
+    /// Store the job submitted for `class_hash`, replacing the previous one
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory or entry file cannot be written.
+    pub fn put(
+        &self,
+        api_url: &Url,
+        class_hash: &ClassHash,
+        submission: &CachedSubmission,
+    ) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            entry_path(&self.dir, api_url, class_hash),
+            serde_json::to_vec(submission).map_err(io::Error::other)?,
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn info(verified: bool) -> ClassVerificationInfo {
+        ClassVerificationInfo {
+            verified,
+            class_hash: "0x1234".to_string(),
+            name: Some("MyContract".to_string()),
+            version: None,
+            license: Some("MIT".to_string()),
+            verified_timestamp: Some(1_700_000_000.0),
+            contract_file: None,
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_verified_result() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CheckCache::at(temp_dir.path().to_path_buf());
+        let url = Url::parse("https://api.voyager.online/beta").unwrap();
+        let hash = ClassHash::new("0x1234").unwrap();
+
+        cache.put(&url, &hash, &info(true)).unwrap();
+
+        let cached = cache.get(&url, &hash, Duration::from_mins(1)).unwrap();
+        assert!(cached.info.verified);
+        assert_eq!(cached.info.name.as_deref(), Some("MyContract"));
+        assert_eq!(cached.info.verified_timestamp, Some(1_700_000_000.0));
+        assert!(cached.age_secs() < 60);
+
+        // A different endpoint must not share entries
+        let other = Url::parse("https://sepolia-api.voyager.online/beta").unwrap();
+        assert!(cache.get(&other, &hash, Duration::from_mins(1)).is_none());
+    }
+
+    #[test]
+    fn test_entries_are_keyed_on_the_whole_url() {
+        let dir = Path::new("cache");
+        let hash = ClassHash::new("0x1234").unwrap();
+        let path = |url: &str| entry_path(dir, &Url::parse(url).unwrap(), &hash);
+
+        assert_eq!(
+            path("http://localhost:3030/api"),
+            path("http://localhost:3030/api/")
+        );
+        assert_eq!(
+            path("https://api.voyager.online/beta"),
+            path("https://api.voyager.online:443/beta#docs")
+        );
+        assert_ne!(
+            path("http://localhost:3030/api"),
+            path("http://localhost:3031/api")
+        );
+        assert_ne!(
+            path("http://localhost:3030/api"),
+            path("https://localhost:3030/api")
+        );
+        assert_ne!(
+            path("https://api.example.com/beta?network=mainnet"),
+            path("https://api.example.com/beta?network=sepolia")
+        );
+    }
+
+    #[test]
+    fn test_unverified_results_are_not_cached() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CheckCache::at(temp_dir.path().to_path_buf());
+        let url = Url::parse("https://api.voyager.online/beta").unwrap();
+        let hash = ClassHash::new("0x1234").unwrap();
+
+        cache.put(&url, &hash, &info(false)).unwrap();
+        assert!(cache.get(&url, &hash, Duration::from_mins(1)).is_none());
+    }
+
+    #[test]
+    fn test_zero_ttl_disables_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = CheckCache::at(temp_dir.path().to_path_buf());
+        let url = Url::parse("https://api.voyager.online/beta").unwrap();
+        let hash = ClassHash::new("0x1234").unwrap();
+
+        cache.put(&url, &hash, &info(true)).unwrap();
+        assert!(cache.get(&url, &hash, Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn test_submission_reused_only_for_identical_requests() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = SubmissionCache::at(temp_dir.path().to_path_buf());
+        let url = Url::parse("https://api.voyager.online/beta").unwrap();
+        let hash = ClassHash::new("0x1234").unwrap();
+        let submission = CachedSubmission {
+            job_id: "job-1".to_string(),
+            request_digest: "sha256:abc".to_string(),
+            submitted_at: 1_700_000_000,
+        };
+
+        assert!(cache.get(&url, &hash, "sha256:abc").is_none());
+        cache.put(&url, &hash, &submission).unwrap();
+
+        assert_eq!(cache.get(&url, &hash, "sha256:abc"), Some(submission));
+        assert!(cache.get(&url, &hash, "sha256:def").is_none());
+        let other = Url::parse("https://sepolia-api.voyager.online/beta").unwrap();
+        assert!(cache.get(&other, &hash, "sha256:abc").is_none());
+    }
+}
@@ -6,27 +6,43 @@
 //! - Query past verifications
 //! - Re-check verification status
 //! - Clean old records
+//!
+//! A [`RetentionPolicy`] (the `[history]` section of `.voyager.toml`) keeps the
+//! database small by pruning old records whenever a new one is inserted.
 
 use crate::api::VerifyJobStatus;
 use crate::core::class_hash::ClassHash;
+use crate::utils::{
+    submitter::Submitter,
+    suggestion::{Suggestion, SuggestionList},
+};
 use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection};
+use log::{debug, info, warn};
+use rusqlite::{params, Connection, ErrorCode, OpenFlags};
 use std::fmt::Write as _;
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
+use url::Url;
+
+/// Suggestions for [`HistoryError::Format`], which has no explanation
+const FORMAT_SUGGESTIONS: &[&str] = &[
+    "This is an internal error, please report it",
+    "Check if query parameters are valid",
+];
 
 #[derive(Error, Debug)]
 pub enum HistoryError {
-    #[error("[E040] Failed to access history database: {0}\n\nSuggestions:\n  • Check that ~/.voyager directory exists and is writable\n  • Verify disk space is available\n  • Ensure no other process is accessing the database")]
+    #[error("[E040] Failed to access history database: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Database(#[from] rusqlite::Error),
 
-    #[error("[E041] Failed to create history directory: {0}\n\nSuggestions:\n  • Check permissions for home directory\n  • Verify disk space is available\n  • Ensure ~/.voyager directory can be created")]
+    #[error("[E041] Failed to create history directory: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Io(#[from] std::io::Error),
 
-    #[error("[E042] Unable to determine home directory\n\nSuggestions:\n  • Check that HOME environment variable is set\n  • Verify user has a valid home directory")]
+    #[error("[E042] Unable to determine home directory{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     NoHomeDir,
 
-    #[error("[E043] Failed to format query string: {0}\n\nSuggestions:\n  • This is an internal error, please report it\n  • Check if query parameters are valid")]
+    #[error("[E043] Failed to format query string: {0}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     Format(#[from] std::fmt::Error),
 }
 
@@ -40,8 +56,33 @@ impl HistoryError {
             Self::Format(_) => "E043",
         }
     }
+
+    /// Next steps for restoring access to the history database
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        match self {
+            Self::Format(_) => Suggestion::from_templates(FORMAT_SUGGESTIONS, &[]),
+            Self::Database(_) | Self::Io(_) | Self::NoHomeDir => {
+                Suggestion::for_code(self.error_code(), &[])
+            }
+        }
+    }
 }
 
+/// How long read-only queries wait while the database is locked, e.g. for a
+/// checkpoint of its write-ahead log
+pub const READ_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Status of records whose job the API no longer knows (expired or purged)
+pub const EXPIRED_STATUS: &str = "Expired";
+
+/// Columns selected when loading a `VerificationRecord`, in `record_from_row` order
+const RECORD_COLUMNS: &str = "id, job_id, class_hash, contract_name, network, status, \
+     submitted_at, completed_at, package_name, scarb_version, cairo_version, dojo_version, \
+     commit_hash, verified_at, explorer_name, explorer_version, submitted_by, submitted_host, \
+     ci_job_url, run_id, note, payload_size, payload_files, payload_compressed_size, \
+     largest_file, largest_file_size";
+
 /// A record of a verification job
 #[derive(Debug, Clone)]
 pub struct VerificationRecord {
@@ -57,6 +98,39 @@ pub struct VerificationRecord {
     pub scarb_version: String,
     pub cairo_version: String,
     pub dojo_version: Option<String>,
+    /// Git commit the sources were submitted from, if known
+    pub commit_hash: Option<String>,
+    /// Verification time reported by the explorer
+    pub verified_at: Option<DateTime<Utc>>,
+    /// Contract name displayed by the explorer
+    pub explorer_name: Option<String>,
+    /// Version displayed by the explorer
+    pub explorer_version: Option<String>,
+    /// User who submitted the verification
+    pub submitted_by: Option<String>,
+    /// Machine the verification was submitted from
+    pub submitted_host: Option<String>,
+    /// CI job that submitted the verification
+    pub ci_job_url: Option<String>,
+    /// Run id of the invocation that submitted the verification
+    pub run_id: Option<String>,
+    /// Why the verification was submitted, from `--note`
+    pub note: Option<String>,
+    /// Size of the submitted files; unknown for older records
+    pub payload: Option<PayloadStats>,
+}
+
+/// Size of the files submitted with a job
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadStats {
+    /// Total size of the files, in bytes
+    pub size: u64,
+    pub files: usize,
+    /// Total size after deflate compression, in bytes
+    pub compressed_size: u64,
+    /// Name of the largest file, as submitted
+    pub largest_file: String,
+    pub largest_file_size: u64,
 }
 
 impl VerificationRecord {
@@ -87,9 +161,72 @@ impl VerificationRecord {
             scarb_version,
             cairo_version,
             dojo_version,
+            commit_hash: None,
+            verified_at: None,
+            explorer_name: None,
+            explorer_version: None,
+            submitted_by: None,
+            submitted_host: None,
+            ci_job_url: None,
+            run_id: None,
+            note: None,
+            payload: None,
+        }
+    }
+
+    /// Attach the git commit the sources were submitted from
+    #[must_use]
+    pub fn with_commit_hash(mut self, commit_hash: Option<String>) -> Self {
+        self.commit_hash = commit_hash;
+        self
+    }
+
+    /// Attach the run id of the submitting invocation
+    #[must_use]
+    pub fn with_run_id(mut self, run_id: impl Into<String>) -> Self {
+        self.run_id = Some(run_id.into());
+        self
+    }
+
+    /// Attach the note on why the verification was submitted
+    #[must_use]
+    pub fn with_note(mut self, note: Option<String>) -> Self {
+        self.note = note;
+        self
+    }
+
+    /// Attach the size of the submitted files
+    #[must_use]
+    pub fn with_payload(mut self, payload: Option<PayloadStats>) -> Self {
+        self.payload = payload;
+        self
+    }
+
+    /// Attach who and where the verification was submitted from
+    #[must_use]
+    pub fn with_submitter(mut self, submitter: Submitter) -> Self {
+        self.submitted_by = submitter.user;
+        self.submitted_host = submitter.hostname;
+        self.ci_job_url = submitter.ci_job_url;
+        self
+    }
+
+    /// Who and where the verification was submitted from
+    #[must_use]
+    pub fn submitter(&self) -> Submitter {
+        Submitter {
+            user: self.submitted_by.clone(),
+            hostname: self.submitted_host.clone(),
+            ci_job_url: self.ci_job_url.clone(),
         }
     }
 
+    /// Mark this record as expired because the API no longer knows its job
+    pub fn mark_expired(&mut self) {
+        self.status = EXPIRED_STATUS.to_string();
+        self.completed_at = Some(Utc::now());
+    }
+
     /// Update the status of this record
     pub fn update_status(&mut self, status: VerifyJobStatus) {
         self.status = status.to_string();
@@ -104,23 +241,52 @@ impl VerificationRecord {
     }
 }
 
+/// Limits on the records kept in the history database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Keep at most this many of the most recent records
+    pub max_records: Option<usize>,
+    /// Delete records submitted more than this many days ago
+    pub max_age_days: Option<u32>,
+}
+
+impl RetentionPolicy {
+    /// Whether the policy never deletes anything
+    #[must_use]
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_records.is_none() && self.max_age_days.is_none()
+    }
+}
+
 /// History database manager
 pub struct HistoryDb {
     conn: Connection,
+    retention: RetentionPolicy,
 }
 
 impl HistoryDb {
     /// Get the path to the history database file
     fn get_db_path() -> Result<PathBuf, HistoryError> {
-        let home = dirs::home_dir().ok_or(HistoryError::NoHomeDir)?;
-        let voyager_dir = home.join(".voyager");
+        let db_path = Self::db_path()?;
 
         // Create directory if it doesn't exist
-        if !voyager_dir.exists() {
-            std::fs::create_dir_all(&voyager_dir)?;
+        if let Some(voyager_dir) = db_path.parent() {
+            if !voyager_dir.exists() {
+                std::fs::create_dir_all(voyager_dir)?;
+            }
         }
 
-        Ok(voyager_dir.join("history.db"))
+        Ok(db_path)
+    }
+
+    /// Path of the history database file, without creating its directory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home directory cannot be determined
+    pub fn db_path() -> Result<PathBuf, HistoryError> {
+        let home = dirs::home_dir().ok_or(HistoryError::NoHomeDir)?;
+        Ok(home.join(".voyager").join("history.db"))
     }
 
     /// Open or create the history database
@@ -133,9 +299,148 @@ impl HistoryDb {
     /// - The database file cannot be opened
     /// - Database tables or indices cannot be created
     pub fn open() -> Result<Self, HistoryError> {
-        let db_path = Self::get_db_path()?;
+        Self::open_at(&Self::get_db_path()?)
+    }
+
+    /// Open the history database for queries only
+    ///
+    /// The connection only ever takes shared locks. Databases are written in
+    /// WAL mode, so another process saving records does not block its
+    /// queries; while the database is locked otherwise they wait for up to
+    /// [`READ_BUSY_TIMEOUT`] instead of failing. Nothing is created on disk:
+    /// without a database file the queries see an empty history, which keeps
+    /// history commands working on read-only filesystems, where a WAL
+    /// database that `SQLite` cannot read in place is read as immutable
+    /// instead. Columns missing from a database written by an older version
+    /// read as empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home directory cannot be determined or the
+    /// database file cannot be opened.
+    pub fn open_read_only() -> Result<Self, HistoryError> {
+        Self::open_read_only_at(&Self::db_path()?)
+    }
+
+    /// Open the history database at `db_path` for queries only
+    fn open_read_only_at(db_path: &std::path::Path) -> Result<Self, HistoryError> {
+        if !db_path.exists() {
+            let conn = Connection::open_in_memory()?;
+            Self::create_schema(&conn)?;
+            return Ok(Self {
+                conn,
+                retention: RetentionPolicy::default(),
+            });
+        }
+
+        let conn = match Self::connect_read_only(db_path, OpenFlags::empty()) {
+            Ok(conn) => conn,
+            // A writer holds the lock; its changes must not be read around it
+            Err(e) if e.sqlite_error_code() == Some(ErrorCode::DatabaseBusy) => {
+                return Err(e.into())
+            }
+            Err(e) => {
+                debug!(
+                    "Cannot read {} ({e}), reading it as immutable",
+                    db_path.display()
+                );
+                Self::connect_immutable(db_path).ok_or(e)?
+            }
+        };
+
+        // The file is left as it is; a temporary view adds the missing
+        // columns over the older table
+        let missing = Self::missing_columns(&conn)?;
+        if !missing.is_empty() {
+            let columns = RECORD_COLUMNS
+                .split(',')
+                .map(str::trim)
+                .map(|column| {
+                    if missing.contains(&column) {
+                        format!("NULL AS {column}")
+                    } else {
+                        column.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            conn.execute_batch(&format!(
+                "CREATE TEMP VIEW verification_history AS \
+                 SELECT {columns} FROM main.verification_history"
+            ))?;
+        }
+
+        Ok(Self {
+            conn,
+            retention: RetentionPolicy::default(),
+        })
+    }
+
+    /// Read-only connection to `path`, opened with `flags` on top of the
+    /// read-only ones
+    ///
+    /// `SQLite` opens the file lazily, so the schema is read to make sure the
+    /// database can be read at all.
+    fn connect_read_only(
+        path: impl AsRef<std::path::Path>,
+        flags: OpenFlags,
+    ) -> rusqlite::Result<Connection> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX | flags,
+        )?;
+        conn.busy_timeout(READ_BUSY_TIMEOUT)?;
+        conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(()))?;
+        Ok(conn)
+    }
+
+    /// Connection to `db_path` as an immutable database
+    ///
+    /// A WAL database is read through a `-shm` file next to it, which `SQLite`
+    /// cannot create on a read-only filesystem. An immutable database is read
+    /// as it is, without that file or any lock; nothing can write to a
+    /// read-only filesystem anyway.
+    fn connect_immutable(db_path: &std::path::Path) -> Option<Connection> {
+        let mut uri = Url::from_file_path(db_path).ok()?;
+        uri.set_query(Some("immutable=1"));
+        Self::connect_read_only(uri.as_str(), OpenFlags::SQLITE_OPEN_URI)
+            .inspect_err(|e| debug!("Cannot read {uri}: {e}"))
+            .ok()
+    }
+
+    /// Columns this version reads that the history table lacks
+    fn missing_columns(conn: &Connection) -> Result<Vec<&'static str>, HistoryError> {
+        let mut stmt = conn.prepare("PRAGMA table_info(verification_history)")?;
+        let columns: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .flatten()
+            .collect();
+
+        Ok(RECORD_COLUMNS
+            .split(',')
+            .map(str::trim)
+            .filter(|column| !columns.iter().any(|name| name == column))
+            .collect())
+    }
+
+    /// Open or create a history database at `db_path`
+    ///
+    /// The database is switched to WAL mode, in which saving records does not
+    /// block readers.
+    fn open_at(db_path: &std::path::Path) -> Result<Self, HistoryError> {
         let conn = Connection::open(db_path)?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        Self::create_schema(&conn)?;
 
+        Ok(Self {
+            conn,
+            retention: RetentionPolicy::default(),
+        })
+    }
+
+    /// Create the history table and indices, and add columns missing from
+    /// databases created by older versions
+    fn create_schema(conn: &Connection) -> Result<(), HistoryError> {
         // Create table if it doesn't exist
         conn.execute(
             "CREATE TABLE IF NOT EXISTS verification_history (
@@ -150,11 +455,41 @@ impl HistoryDb {
                 package_name TEXT,
                 scarb_version TEXT NOT NULL,
                 cairo_version TEXT NOT NULL,
-                dojo_version TEXT
+                dojo_version TEXT,
+                commit_hash TEXT,
+                verified_at TEXT,
+                explorer_name TEXT,
+                explorer_version TEXT,
+                submitted_by TEXT,
+                submitted_host TEXT,
+                ci_job_url TEXT,
+                run_id TEXT,
+                note TEXT,
+                payload_size INTEGER,
+                payload_files INTEGER,
+                payload_compressed_size INTEGER,
+                largest_file TEXT,
+                largest_file_size INTEGER
             )",
             [],
         )?;
 
+        // Databases created by older versions lack the newer columns
+        Self::add_column_if_missing(conn, "commit_hash", "TEXT")?;
+        Self::add_column_if_missing(conn, "verified_at", "TEXT")?;
+        Self::add_column_if_missing(conn, "explorer_name", "TEXT")?;
+        Self::add_column_if_missing(conn, "explorer_version", "TEXT")?;
+        Self::add_column_if_missing(conn, "submitted_by", "TEXT")?;
+        Self::add_column_if_missing(conn, "submitted_host", "TEXT")?;
+        Self::add_column_if_missing(conn, "ci_job_url", "TEXT")?;
+        Self::add_column_if_missing(conn, "run_id", "TEXT")?;
+        Self::add_column_if_missing(conn, "note", "TEXT")?;
+        Self::add_column_if_missing(conn, "payload_size", "INTEGER")?;
+        Self::add_column_if_missing(conn, "payload_files", "INTEGER")?;
+        Self::add_column_if_missing(conn, "payload_compressed_size", "INTEGER")?;
+        Self::add_column_if_missing(conn, "largest_file", "TEXT")?;
+        Self::add_column_if_missing(conn, "largest_file_size", "INTEGER")?;
+
         // Create indices for common queries
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_job_id ON verification_history(job_id)",
@@ -177,20 +512,107 @@ impl HistoryDb {
             [],
         )?;
 
-        Ok(Self { conn })
+        Ok(())
+    }
+
+    /// Add a column to the history table unless it already exists
+    fn add_column_if_missing(
+        conn: &Connection,
+        column: &str,
+        definition: &str,
+    ) -> Result<(), HistoryError> {
+        let mut stmt = conn.prepare("PRAGMA table_info(verification_history)")?;
+        let exists = stmt
+            .query_map([], |row| row.get::<_, String>(1))?
+            .flatten()
+            .any(|name| name == column);
+
+        if !exists {
+            conn.execute(
+                &format!("ALTER TABLE verification_history ADD COLUMN {column} {definition}"),
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Build a record from a row selected with `RECORD_COLUMNS`
+    fn record_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<VerificationRecord> {
+        Ok(VerificationRecord {
+            id: Some(row.get(0)?),
+            job_id: row.get(1)?,
+            class_hash: row.get(2)?,
+            contract_name: row.get(3)?,
+            network: row.get(4)?,
+            status: row.get(5)?,
+            submitted_at: row
+                .get::<_, String>(6)?
+                .parse()
+                .unwrap_or_else(|_| Utc::now()),
+            completed_at: row
+                .get::<_, Option<String>>(7)?
+                .and_then(|s| s.parse().ok()),
+            package_name: row.get(8)?,
+            scarb_version: row.get(9)?,
+            cairo_version: row.get(10)?,
+            dojo_version: row.get(11)?,
+            commit_hash: row.get(12)?,
+            verified_at: row
+                .get::<_, Option<String>>(13)?
+                .and_then(|s| s.parse().ok()),
+            explorer_name: row.get(14)?,
+            explorer_version: row.get(15)?,
+            submitted_by: row.get(16)?,
+            submitted_host: row.get(17)?,
+            ci_job_url: row.get(18)?,
+            run_id: row.get(19)?,
+            note: row.get(20)?,
+            payload: Self::payload_from_row(row)?,
+        })
+    }
+
+    /// Payload columns of a row selected with `RECORD_COLUMNS`, if recorded
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn payload_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Option<PayloadStats>> {
+        let Some(size) = row.get::<_, Option<i64>>(21)? else {
+            return Ok(None);
+        };
+        Ok(Some(PayloadStats {
+            size: size as u64,
+            files: row.get::<_, Option<i64>>(22)?.unwrap_or(0) as usize,
+            compressed_size: row.get::<_, Option<i64>>(23)?.unwrap_or(0) as u64,
+            largest_file: row.get::<_, Option<String>>(24)?.unwrap_or_default(),
+            largest_file_size: row.get::<_, Option<i64>>(25)?.unwrap_or(0) as u64,
+        }))
+    }
+
+    /// Prune records according to `retention` after every insert
+    #[must_use]
+    pub const fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
     }
 
     /// Insert a new verification record
     ///
+    /// Older records are pruned afterwards if a retention policy is set. The
+    /// record is kept even if pruning fails, which is only logged.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the database insert operation fails
+    /// Returns an error if the database insert fails
     pub fn insert(&self, record: &VerificationRecord) -> Result<i64, HistoryError> {
+        let payload = record.payload.as_ref();
         self.conn.execute(
             "INSERT INTO verification_history
              (job_id, class_hash, contract_name, network, status, submitted_at,
-              completed_at, package_name, scarb_version, cairo_version, dojo_version)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+              completed_at, package_name, scarb_version, cairo_version, dojo_version,
+              commit_hash, submitted_by, submitted_host, ci_job_url, run_id, note,
+              payload_size, payload_files, payload_compressed_size, largest_file,
+              largest_file_size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                     ?18, ?19, ?20, ?21, ?22)",
             params![
                 record.job_id,
                 record.class_hash,
@@ -203,10 +625,60 @@ impl HistoryDb {
                 record.scarb_version,
                 record.cairo_version,
                 record.dojo_version,
+                record.commit_hash,
+                record.submitted_by,
+                record.submitted_host,
+                record.ci_job_url,
+                record.run_id,
+                record.note,
+                payload.map(|p| i64::try_from(p.size).unwrap_or(i64::MAX)),
+                payload.map(|p| i64::try_from(p.files).unwrap_or(i64::MAX)),
+                payload.map(|p| i64::try_from(p.compressed_size).unwrap_or(i64::MAX)),
+                payload.map(|p| p.largest_file.as_str()),
+                payload.map(|p| i64::try_from(p.largest_file_size).unwrap_or(i64::MAX)),
             ],
         )?;
+        let id = self.conn.last_insert_rowid();
+        self.apply_retention();
+        Ok(id)
+    }
 
-        Ok(self.conn.last_insert_rowid())
+    /// Prune the records the retention policy does not keep, logging failures
+    fn apply_retention(&self) {
+        if self.retention.is_unlimited() {
+            return;
+        }
+        match self.prune(&self.retention) {
+            Ok(0) => {}
+            Ok(pruned) => info!("Pruned {pruned} old record(s) from verification history"),
+            Err(e) => warn!("Failed to prune the verification history: {e}"),
+        }
+    }
+
+    /// Delete the records that `policy` does not keep
+    ///
+    /// Returns the number of deleted records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database delete operation fails
+    pub fn prune(&self, policy: &RetentionPolicy) -> Result<usize, HistoryError> {
+        let mut deleted = match policy.max_age_days {
+            Some(days) => self.clean_older_than(days)?,
+            None => 0,
+        };
+
+        if let Some(max_records) = policy.max_records {
+            deleted += self.conn.execute(
+                "DELETE FROM verification_history WHERE id NOT IN (
+                     SELECT id FROM verification_history
+                     ORDER BY submitted_at DESC, id DESC LIMIT ?1
+                 )",
+                params![i64::try_from(max_records).unwrap_or(i64::MAX)],
+            )?;
+        }
+
+        Ok(deleted)
     }
 
     /// Update the status of a verification record by job ID
@@ -229,41 +701,90 @@ impl HistoryDb {
         Ok(())
     }
 
+    /// Record the explorer's view of a verified class on a job's history entry
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update operation fails
+    pub fn update_explorer_info(
+        &self,
+        job_id: &str,
+        verified_at: Option<DateTime<Utc>>,
+        explorer_name: Option<&str>,
+        explorer_version: Option<&str>,
+    ) -> Result<(), HistoryError> {
+        self.conn.execute(
+            "UPDATE verification_history
+             SET verified_at = ?1, explorer_name = ?2, explorer_version = ?3
+             WHERE job_id = ?4",
+            params![
+                verified_at.map(|dt| dt.to_rfc3339()),
+                explorer_name,
+                explorer_version,
+                job_id,
+            ],
+        )?;
+        Ok(())
+    }
+
     /// Get a verification record by job ID
     ///
     /// # Errors
     ///
     /// Returns an error if the database query operation fails
     pub fn get_by_job_id(&self, job_id: &str) -> Result<Option<VerificationRecord>, HistoryError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, job_id, class_hash, contract_name, network, status,
-                    submitted_at, completed_at, package_name, scarb_version,
-                    cairo_version, dojo_version
-             FROM verification_history
-             WHERE job_id = ?1",
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RECORD_COLUMNS} FROM verification_history WHERE job_id = ?1"
+        ))?;
 
-        let record = stmt.query_row(params![job_id], |row| {
-            Ok(VerificationRecord {
-                id: Some(row.get(0)?),
-                job_id: row.get(1)?,
-                class_hash: row.get(2)?,
-                contract_name: row.get(3)?,
-                network: row.get(4)?,
-                status: row.get(5)?,
-                submitted_at: row
-                    .get::<_, String>(6)?
-                    .parse()
-                    .unwrap_or_else(|_| Utc::now()),
-                completed_at: row
-                    .get::<_, Option<String>>(7)?
-                    .and_then(|s| s.parse().ok()),
-                package_name: row.get(8)?,
-                scarb_version: row.get(9)?,
-                cairo_version: row.get(10)?,
-                dojo_version: row.get(11)?,
-            })
-        });
+        let record = stmt.query_row(params![job_id], Self::record_from_row);
+
+        match record {
+            Ok(rec) => Ok(Some(rec)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Most recently submitted record for a class hash, optionally only on
+    /// `network`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query operation fails
+    pub fn latest_job_for_class_hash(
+        &self,
+        class_hash: &str,
+        network: Option<&str>,
+    ) -> Result<Option<VerificationRecord>, HistoryError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RECORD_COLUMNS} FROM verification_history WHERE lower(class_hash) = lower(?1) \
+             AND (?2 IS NULL OR network = ?2) ORDER BY submitted_at DESC, id DESC LIMIT 1"
+        ))?;
+
+        match stmt.query_row(params![class_hash, network], Self::record_from_row) {
+            Ok(rec) => Ok(Some(rec)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Most relevant record for a class hash: the latest successful
+    /// verification, or the latest submission if none succeeded
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query operation fails
+    pub fn latest_for_class_hash(
+        &self,
+        class_hash: &str,
+    ) -> Result<Option<VerificationRecord>, HistoryError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RECORD_COLUMNS} FROM verification_history WHERE lower(class_hash) = lower(?1) \
+             ORDER BY status = 'Success' DESC, submitted_at DESC LIMIT 1"
+        ))?;
+
+        let record = stmt.query_row(params![class_hash], Self::record_from_row);
 
         match record {
             Ok(rec) => Ok(Some(rec)),
@@ -281,14 +802,10 @@ impl HistoryDb {
         &self,
         status_filter: Option<&str>,
         network_filter: Option<&str>,
+        run_id_filter: Option<&str>,
         limit: Option<usize>,
     ) -> Result<Vec<VerificationRecord>, HistoryError> {
-        let mut query = String::from(
-            "SELECT id, job_id, class_hash, contract_name, network, status,
-                    submitted_at, completed_at, package_name, scarb_version,
-                    cairo_version, dojo_version
-             FROM verification_history WHERE 1=1",
-        );
+        let mut query = format!("SELECT {RECORD_COLUMNS} FROM verification_history WHERE 1=1");
 
         let mut params: Vec<String> = Vec::new();
         if let Some(s) = status_filter {
@@ -299,6 +816,10 @@ impl HistoryDb {
             params.push(n.to_string());
             write!(query, " AND network = ?{}", params.len())?;
         }
+        if let Some(r) = run_id_filter {
+            params.push(r.to_string());
+            write!(query, " AND run_id = ?{}", params.len())?;
+        }
         query.push_str(" ORDER BY submitted_at DESC");
 
         if let Some(lim) = limit {
@@ -311,27 +832,7 @@ impl HistoryDb {
         let param_refs: Vec<&dyn rusqlite::ToSql> =
             params.iter().map(|s| s as &dyn rusqlite::ToSql).collect();
 
-        let records = stmt.query_map(&param_refs[..], |row| {
-            Ok(VerificationRecord {
-                id: Some(row.get(0)?),
-                job_id: row.get(1)?,
-                class_hash: row.get(2)?,
-                contract_name: row.get(3)?,
-                network: row.get(4)?,
-                status: row.get(5)?,
-                submitted_at: row
-                    .get::<_, String>(6)?
-                    .parse()
-                    .unwrap_or_else(|_| Utc::now()),
-                completed_at: row
-                    .get::<_, Option<String>>(7)?
-                    .and_then(|s| s.parse().ok()),
-                package_name: row.get(8)?,
-                scarb_version: row.get(9)?,
-                cairo_version: row.get(10)?,
-                dojo_version: row.get(11)?,
-            })
-        })?;
+        let records = stmt.query_map(&param_refs[..], Self::record_from_row)?;
 
         let mut result = Vec::new();
         for record in records {
@@ -340,6 +841,81 @@ impl HistoryDb {
         Ok(result)
     }
 
+    /// Records with a known payload size, oldest first
+    ///
+    /// With `limit`, only the most recent `limit` records are returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query operation fails
+    pub fn payload_history(
+        &self,
+        contract_filter: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<VerificationRecord>, HistoryError> {
+        let mut query = format!(
+            "SELECT {RECORD_COLUMNS} FROM verification_history WHERE payload_size IS NOT NULL"
+        );
+        if contract_filter.is_some() {
+            query.push_str(" AND contract_name = ?1");
+        }
+        query.push_str(" ORDER BY submitted_at DESC");
+        if let Some(lim) = limit {
+            write!(query, " LIMIT {lim}")?;
+        }
+
+        let mut stmt = self.conn.prepare(&query)?;
+        let records = match contract_filter {
+            Some(contract) => stmt.query_map(params![contract], Self::record_from_row)?,
+            None => stmt.query_map([], Self::record_from_row)?,
+        };
+        let mut result = records.collect::<rusqlite::Result<Vec<_>>>()?;
+        result.reverse();
+        Ok(result)
+    }
+
+    /// The latest verified class of every contract, per network
+    ///
+    /// Entries are sorted by contract name, then network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query operation fails
+    pub fn verified_contracts(
+        &self,
+        network_filter: Option<&str>,
+    ) -> Result<Vec<ContractEntry>, HistoryError> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {RECORD_COLUMNS} FROM verification_history WHERE status = 'Success' \
+             AND (?1 IS NULL OR network = ?1) \
+             ORDER BY contract_name, network, submitted_at DESC, id DESC"
+        ))?;
+        let records = stmt.query_map(params![network_filter], Self::record_from_row)?;
+
+        let mut entries: Vec<ContractEntry> = Vec::new();
+        let mut classes: Vec<String> = Vec::new();
+        for record in records {
+            let record = record?;
+            match entries.last_mut() {
+                Some(entry)
+                    if entry.contract_name == record.contract_name
+                        && entry.network == record.network =>
+                {
+                    let class_hash = record.class_hash.to_lowercase();
+                    if !classes.contains(&class_hash) {
+                        classes.push(class_hash);
+                        entry.verified_classes += 1;
+                    }
+                }
+                _ => {
+                    classes = vec![record.class_hash.to_lowercase()];
+                    entries.push(ContractEntry::latest(record));
+                }
+            }
+        }
+        Ok(entries)
+    }
+
     /// Delete records older than a specified number of days
     ///
     /// # Errors
@@ -397,12 +973,19 @@ impl HistoryDb {
             |row| row.get(0),
         )?;
 
+        let expired: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM verification_history WHERE status = ?1",
+            params![EXPIRED_STATUS],
+            |row| row.get(0),
+        )?;
+
         #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
         Ok(HistoryStats {
             total: total as usize,
             successful: successful as usize,
             failed: failed as usize,
             pending: pending as usize,
+            expired: expired as usize,
         })
     }
 
@@ -433,15 +1016,20 @@ impl HistoryDb {
             let submitted_str: String = row.get(0)?;
             let completed_str: String = row.get(1)?;
 
-            let submitted: DateTime<Utc> = submitted_str.parse().unwrap_or_else(|_| Utc::now());
-            let completed: DateTime<Utc> = completed_str.parse().unwrap_or_else(|_| Utc::now());
+            // Malformed timestamps are left out rather than skewing the average
+            let (Ok(submitted), Ok(completed)) = (
+                submitted_str.parse::<DateTime<Utc>>(),
+                completed_str.parse::<DateTime<Utc>>(),
+            ) else {
+                return Ok(None);
+            };
 
             let duration = (completed - submitted).num_seconds();
             #[allow(clippy::cast_sign_loss)]
-            Ok(duration.max(0) as u64)
+            Ok(Some(duration.max(0) as u64))
         })?;
 
-        for duration in rows.flatten() {
+        for duration in rows.flatten().flatten() {
             durations.push(duration);
         }
 
@@ -455,6 +1043,298 @@ impl HistoryDb {
 
         Ok(Some(avg))
     }
+
+    /// Problems found by `SQLite`'s integrity check; empty if there are none
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the check cannot be run
+    pub fn integrity_check(&self) -> Result<Vec<String>, HistoryError> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+        let messages = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(messages
+            .into_iter()
+            .filter(|message| message != "ok")
+            .collect())
+    }
+
+    /// Records whose timestamps or status cannot be read back
+    ///
+    /// Such values are otherwise replaced when records are loaded: a
+    /// malformed submission time reads as the current time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails
+    pub fn malformed_records(&self) -> Result<Vec<RecordIssue>, HistoryError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, job_id, status, submitted_at, completed_at, verified_at
+             FROM verification_history ORDER BY id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                    row.get::<_, Option<String>>(5)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut issues = Vec::new();
+        for (id, job_id, status, submitted_at, completed_at, verified_at) in rows {
+            let mut push = |problem| {
+                issues.push(RecordIssue {
+                    id,
+                    job_id: job_id.clone(),
+                    problem,
+                });
+            };
+            if !is_known_status(&status) {
+                push(RecordProblem::Status(status));
+            }
+            let timestamps = [
+                ("submitted_at", submitted_at.or_else(|| Some(String::new()))),
+                ("completed_at", completed_at),
+                ("verified_at", verified_at),
+            ];
+            for (column, value) in timestamps {
+                if let Some(value) = value.filter(|value| value.parse::<DateTime<Utc>>().is_err()) {
+                    push(RecordProblem::Timestamp { column, value });
+                }
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Apply the [`RecordIssue::remedy`] of each of `issues`
+    ///
+    /// A record with an issue that cannot be fixed is moved, as is, to the
+    /// `verification_history_quarantine` table, where it can still be
+    /// inspected with `sqlite3`. Everything is applied in one transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database operation fails; nothing is changed then
+    pub fn repair(&mut self, issues: &[RecordIssue]) -> Result<RepairSummary, HistoryError> {
+        let quarantined: Vec<(i64, String)> = issues
+            .iter()
+            .filter(|issue| issue.remedy() == Remedy::Quarantine)
+            .map(|issue| (issue.id, issue.problem.to_string()))
+            .collect();
+
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS verification_history_quarantine AS
+             SELECT *, '' AS reason, '' AS quarantined_at FROM verification_history WHERE 0",
+            [],
+        )?;
+
+        let mut summary = RepairSummary::default();
+        for issue in issues {
+            if quarantined.iter().any(|(id, _)| *id == issue.id) {
+                continue;
+            }
+            let column = match &issue.problem {
+                RecordProblem::Timestamp { column, .. } => *column,
+                RecordProblem::Status(_) => "status",
+            };
+            let sql = format!("UPDATE verification_history SET {column} = ?1 WHERE id = ?2");
+            match issue.remedy() {
+                Remedy::Set(value) => {
+                    tx.execute(&sql, params![value, issue.id])?;
+                    summary.fixed += 1;
+                }
+                Remedy::Clear => {
+                    tx.execute(&sql, params![None::<String>, issue.id])?;
+                    summary.cleared += 1;
+                }
+                Remedy::Quarantine => {}
+            }
+        }
+
+        let now = Utc::now().to_rfc3339();
+        let mut moved: Vec<i64> = Vec::new();
+        for (id, reason) in &quarantined {
+            if moved.contains(id) {
+                continue;
+            }
+            tx.execute(
+                "INSERT INTO verification_history_quarantine
+                 SELECT *, ?1, ?2 FROM verification_history WHERE id = ?3",
+                params![reason, now, id],
+            )?;
+            tx.execute(
+                "DELETE FROM verification_history WHERE id = ?1",
+                params![id],
+            )?;
+            moved.push(*id);
+        }
+        summary.quarantined = moved.len();
+
+        tx.commit()?;
+        Ok(summary)
+    }
+}
+
+/// Whether `status` is a status the verifier records
+fn is_known_status(status: &str) -> bool {
+    status == EXPIRED_STATUS
+        || KNOWN_STATUSES
+            .iter()
+            .any(|known| known.to_string() == status)
+}
+
+/// Statuses of the API that are recorded as is
+const KNOWN_STATUSES: [VerifyJobStatus; 7] = [
+    VerifyJobStatus::Submitted,
+    VerifyJobStatus::Compiled,
+    VerifyJobStatus::CompileFailed,
+    VerifyJobStatus::Fail,
+    VerifyJobStatus::Success,
+    VerifyJobStatus::Processing,
+    VerifyJobStatus::Unknown,
+];
+
+/// A value of a history record that cannot be read back
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordProblem {
+    /// A timestamp that is not RFC 3339
+    Timestamp { column: &'static str, value: String },
+    /// A status the verifier never records
+    Status(String),
+}
+
+impl std::fmt::Display for RecordProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timestamp { column, value } if value.is_empty() => write!(f, "{column} is empty"),
+            Self::Timestamp { column, value } => write!(f, "{column} '{value}' is not a timestamp"),
+            Self::Status(status) => write!(f, "status '{status}' is unknown"),
+        }
+    }
+}
+
+/// A problem of the record `id`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordIssue {
+    pub id: i64,
+    pub job_id: String,
+    pub problem: RecordProblem,
+}
+
+/// How `history doctor --fix` resolves a [`RecordIssue`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Remedy {
+    /// Rewrite the value in its canonical form
+    Set(String),
+    /// Forget an optional timestamp
+    Clear,
+    /// Move the record out of the history
+    Quarantine,
+}
+
+impl std::fmt::Display for Remedy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Set(value) => write!(f, "rewrite as '{value}'"),
+            Self::Clear => write!(f, "clear"),
+            Self::Quarantine => write!(f, "quarantine"),
+        }
+    }
+}
+
+impl RecordIssue {
+    /// How the issue is resolved
+    ///
+    /// Timestamps in other common formats and statuses in another case are
+    /// rewritten. Other optional timestamps are cleared; a record without a
+    /// readable submission time or status is quarantined.
+    #[must_use]
+    pub fn remedy(&self) -> Remedy {
+        match &self.problem {
+            RecordProblem::Timestamp { column, value } => match parse_lenient_timestamp(value) {
+                Some(timestamp) => Remedy::Set(timestamp.to_rfc3339()),
+                None if *column == "submitted_at" => Remedy::Quarantine,
+                None => Remedy::Clear,
+            },
+            RecordProblem::Status(status) => KNOWN_STATUSES
+                .iter()
+                .map(ToString::to_string)
+                .chain(std::iter::once(EXPIRED_STATUS.to_string()))
+                .find(|known| known.eq_ignore_ascii_case(status.trim()))
+                .map_or(Remedy::Quarantine, Remedy::Set),
+        }
+    }
+}
+
+/// What [`HistoryDb::repair`] changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepairSummary {
+    /// Values rewritten in their canonical form
+    pub fixed: usize,
+    /// Optional timestamps cleared
+    pub cleared: usize,
+    /// Records moved to the quarantine table
+    pub quarantined: usize,
+}
+
+/// A timestamp in a format `SQLite` or other tools commonly write, in UTC
+/// unless it has an offset
+fn parse_lenient_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Utc));
+    }
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+        .map(|naive| naive.and_utc())
+        .or_else(|| {
+            value
+                .parse::<i64>()
+                .ok()
+                .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        })
+}
+
+/// Latest verified class of a contract on a network
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ContractEntry {
+    pub contract_name: String,
+    pub network: String,
+    pub class_hash: String,
+    /// When the class was verified, or its job completed or was submitted if
+    /// that is not known
+    pub verified_at: DateTime<Utc>,
+    pub package_name: Option<String>,
+    pub commit_hash: Option<String>,
+    pub job_id: String,
+    /// Number of distinct classes verified under this name on this network
+    pub verified_classes: usize,
+}
+
+impl ContractEntry {
+    fn latest(record: VerificationRecord) -> Self {
+        Self {
+            verified_at: record
+                .verified_at
+                .or(record.completed_at)
+                .unwrap_or(record.submitted_at),
+            contract_name: record.contract_name,
+            network: record.network,
+            class_hash: record.class_hash,
+            package_name: record.package_name,
+            commit_hash: record.commit_hash,
+            job_id: record.job_id,
+            verified_classes: 1,
+        }
+    }
 }
 
 /// Statistics about verification history
@@ -464,26 +1344,37 @@ pub struct HistoryStats {
     pub successful: usize,
     pub failed: usize,
     pub pending: usize,
+    /// Jobs marked expired by `history recheck --fix-orphans`
+    pub expired: usize,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_create_verification_record() -> Result<(), Box<dyn std::error::Error>> {
-        let class_hash = ClassHash::new("0x1234567890abcdef")?;
-        let record = VerificationRecord::new(
-            "job-123".to_string(),
-            &class_hash,
+    /// A record of `TestContract` on mainnet, built with Scarb 2.11.2 and
+    /// Cairo 2.11.4
+    fn record(job_id: &str, class_hash: &ClassHash, status: VerifyJobStatus) -> VerificationRecord {
+        VerificationRecord::new(
+            job_id.to_string(),
+            class_hash,
             "TestContract".to_string(),
             "mainnet".to_string(),
-            VerifyJobStatus::Submitted,
-            Some("test_package".to_string()),
+            status,
+            None,
             "2.11.2".to_string(),
             "2.11.4".to_string(),
             None,
-        );
+        )
+    }
+
+    #[test]
+    fn test_create_verification_record() -> Result<(), Box<dyn std::error::Error>> {
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        let record = VerificationRecord {
+            package_name: Some("test_package".to_string()),
+            ..record("job-123", &class_hash, VerifyJobStatus::Submitted)
+        };
 
         assert_eq!(record.job_id, "job-123");
         assert_eq!(record.contract_name, "TestContract");
@@ -495,17 +1386,10 @@ mod tests {
     #[test]
     fn test_update_status() -> Result<(), Box<dyn std::error::Error>> {
         let class_hash = ClassHash::new("0x1234567890abcdef")?;
-        let mut record = VerificationRecord::new(
-            "job-123".to_string(),
-            &class_hash,
-            "TestContract".to_string(),
-            "mainnet".to_string(),
-            VerifyJobStatus::Submitted,
-            Some("test_package".to_string()),
-            "2.11.2".to_string(),
-            "2.11.4".to_string(),
-            None,
-        );
+        let mut record = VerificationRecord {
+            package_name: Some("test_package".to_string()),
+            ..record("job-123", &class_hash, VerifyJobStatus::Submitted)
+        };
 
         assert!(record.completed_at.is_none());
 
@@ -514,4 +1398,487 @@ mod tests {
         assert!(record.completed_at.is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_with_commit_hash() -> Result<(), Box<dyn std::error::Error>> {
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        let record = record("job-123", &class_hash, VerifyJobStatus::Submitted);
+        assert!(record.commit_hash.is_none());
+
+        let record = record.with_commit_hash(Some("abc123".to_string()));
+        assert_eq!(record.commit_hash.as_deref(), Some("abc123"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_payload_history() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?;
+
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        let submitted = |job_id: &str, contract: &str, hours_ago: i64, size: Option<u64>| {
+            let mut record = VerificationRecord {
+                contract_name: contract.to_string(),
+                ..record(job_id, &class_hash, VerifyJobStatus::Submitted)
+            }
+            .with_payload(size.map(|size| PayloadStats {
+                size,
+                files: 3,
+                compressed_size: size / 4,
+                largest_file: "src/lib.cairo".to_string(),
+                largest_file_size: size / 2,
+            }));
+            record.submitted_at = Utc::now() - chrono::Duration::hours(hours_ago);
+            record
+        };
+        db.insert(&submitted("job-1", "MyToken", 3, Some(1_000)))?;
+        db.insert(&submitted("job-2", "MyToken", 2, None))?;
+        db.insert(&submitted("job-3", "MyToken", 1, Some(2_500)))?;
+        db.insert(&submitted("job-4", "Vault", 0, Some(700)))?;
+
+        let all = db.payload_history(None, None)?;
+        let jobs: Vec<_> = all.iter().map(|r| r.job_id.as_str()).collect();
+        assert_eq!(jobs, ["job-1", "job-3", "job-4"]);
+        let payload = all[1].payload.as_ref().ok_or("payload not read back")?;
+        assert_eq!(payload.size, 2_500);
+        assert_eq!(payload.largest_file, "src/lib.cairo");
+        assert_eq!(payload.largest_file_size, 1_250);
+
+        let latest = db.payload_history(Some("MyToken"), Some(1))?;
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].job_id, "job-3");
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_explorer_info() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?;
+
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        db.insert(&record("job-123", &class_hash, VerifyJobStatus::Success))?;
+
+        let verified_at = DateTime::from_timestamp(1_700_000_000, 0);
+        db.update_explorer_info("job-123", verified_at, Some("Test Contract"), Some("0.1.0"))?;
+
+        let record = db.get_by_job_id("job-123")?.ok_or("record not found")?;
+        assert_eq!(record.verified_at, verified_at);
+        assert_eq!(record.explorer_name.as_deref(), Some("Test Contract"));
+        assert_eq!(record.explorer_version.as_deref(), Some("0.1.0"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_submitter_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?;
+
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        let record = record("job-123", &class_hash, VerifyJobStatus::Submitted)
+            .with_submitter(Submitter {
+                user: Some("ci".to_string()),
+                hostname: Some("runner-1".to_string()),
+                ci_job_url: Some("https://github.com/org/repo/actions/runs/42".to_string()),
+            })
+            .with_run_id("20250101T000000Z-0a1b2c3d")
+            .with_note(Some("release v1.4 hotfix".to_string()));
+        db.insert(&record)?;
+
+        let record = db.get_by_job_id("job-123")?.ok_or("record not found")?;
+        assert_eq!(record.submitted_by.as_deref(), Some("ci"));
+        assert_eq!(record.submitted_host.as_deref(), Some("runner-1"));
+        assert_eq!(
+            record.ci_job_url.as_deref(),
+            Some("https://github.com/org/repo/actions/runs/42")
+        );
+        assert_eq!(record.run_id.as_deref(), Some("20250101T000000Z-0a1b2c3d"));
+        assert_eq!(record.note.as_deref(), Some("release v1.4 hotfix"));
+        assert_eq!(
+            db.list(None, None, Some("20250101T000000Z-0a1b2c3d"), None)?
+                .len(),
+            1
+        );
+        assert!(db.list(None, None, Some("other-run"), None)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_expired_records_are_counted() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?;
+
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        for job_id in ["job-1", "job-2"] {
+            db.insert(&record(job_id, &class_hash, VerifyJobStatus::Submitted))?;
+        }
+
+        let mut record = db.get_by_job_id("job-1")?.ok_or("record not found")?;
+        record.mark_expired();
+        db.update_status(&record.job_id, &record.status, record.completed_at)?;
+
+        let stats = db.get_stats()?;
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.pending, 1);
+        assert_eq!(stats.expired, 1);
+
+        let record = db.get_by_job_id("job-1")?.ok_or("record not found")?;
+        assert_eq!(record.status, EXPIRED_STATUS);
+        assert!(record.completed_at.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_read_only() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db_path = temp_dir.path().join("history.db");
+
+        let db = HistoryDb::open_read_only_at(&db_path)?;
+        assert!(db.list(None, None, None, None)?.is_empty());
+        assert!(!db_path.exists());
+
+        let record = record(
+            "job-1",
+            &ClassHash::new("0x1234567890abcdef")?,
+            VerifyJobStatus::Submitted,
+        );
+        let writer = HistoryDb::open_at(&db_path)?;
+        writer.insert(&record)?;
+
+        let reader = HistoryDb::open_read_only_at(&db_path)?;
+        assert_eq!(reader.get_stats()?.total, 1);
+        assert!(reader.get_by_job_id("job-1")?.is_some());
+        assert!(reader.insert(&record).is_err());
+
+        // The reader does not keep the writer from saving records
+        let journal_mode: String = writer
+            .conn
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))?;
+        assert_eq!(journal_mode, "wal");
+        writer.update_status("job-1", "Success", Some(Utc::now()))?;
+        assert_eq!(
+            reader
+                .get_by_job_id("job-1")?
+                .ok_or("record not found")?
+                .status,
+            "Success"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_open_read_only_wal_in_read_only_dir() -> Result<(), Box<dyn std::error::Error>> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let db_path = temp_dir.path().join("history.db");
+        let writer = HistoryDb::open_at(&db_path)?;
+        writer.insert(&record(
+            "job-1",
+            &ClassHash::new("0x1234567890abcdef")?,
+            VerifyJobStatus::Success,
+        ))?;
+        // Closing the last connection removes the -wal and -shm files
+        drop(writer);
+        assert!(!temp_dir.path().join("history.db-shm").exists());
+
+        std::fs::set_permissions(temp_dir.path(), std::fs::Permissions::from_mode(0o555))?;
+        let reader = HistoryDb::open_read_only_at(&db_path);
+        // Permissions do not bind every user, so the fallback is read directly too
+        let immutable = HistoryDb::connect_immutable(&db_path);
+        std::fs::set_permissions(temp_dir.path(), std::fs::Permissions::from_mode(0o755))?;
+
+        assert_eq!(reader?.get_stats()?.total, 1);
+        let immutable = immutable.ok_or("cannot open the database as immutable")?;
+        let count: i64 =
+            immutable.query_row("SELECT COUNT(*) FROM verification_history", [], |row| {
+                row.get(0)
+            })?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_read_only_older_schema() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db_path = temp_dir.path().join("history.db");
+        let conn = Connection::open(&db_path)?;
+        conn.execute_batch(
+            "CREATE TABLE verification_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                job_id TEXT NOT NULL UNIQUE,
+                class_hash TEXT NOT NULL,
+                contract_name TEXT NOT NULL,
+                network TEXT NOT NULL,
+                status TEXT NOT NULL,
+                submitted_at TEXT NOT NULL,
+                completed_at TEXT,
+                package_name TEXT,
+                scarb_version TEXT NOT NULL,
+                cairo_version TEXT NOT NULL,
+                dojo_version TEXT
+            );
+            INSERT INTO verification_history (job_id, class_hash, contract_name, network,
+                status, submitted_at, scarb_version, cairo_version)
+            VALUES ('job-1', '0x1234', 'TestContract', 'mainnet', 'Success',
+                '2025-01-01T00:00:00Z', '2.11.2', '2.11.4');",
+        )?;
+        drop(conn);
+
+        let reader = HistoryDb::open_read_only_at(&db_path)?;
+        let record = reader.get_by_job_id("job-1")?.ok_or("record not found")?;
+        assert_eq!(record.contract_name, "TestContract");
+        assert!(record.commit_hash.is_none());
+
+        // The file is not upgraded
+        let conn = Connection::open(&db_path)?;
+        let missing = HistoryDb::missing_columns(&conn)?;
+        assert!(missing.contains(&"commit_hash"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_latest_for_class_hash_prefers_success() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?;
+
+        let class_hash = ClassHash::new("0x1234567890ABCDEF")?;
+        for (job_id, status) in [
+            ("job-1", VerifyJobStatus::Success),
+            ("job-2", VerifyJobStatus::Fail),
+        ] {
+            db.insert(&record(job_id, &class_hash, status))?;
+        }
+
+        let record = db
+            .latest_for_class_hash("0x1234567890abcdef")?
+            .ok_or("record not found")?;
+        assert_eq!(record.job_id, "job-1");
+        assert!(db.latest_for_class_hash("0x1")?.is_none());
+
+        // The latest job, whatever its status, on the requested network
+        let latest = db
+            .latest_job_for_class_hash("0x1234567890abcdef", None)?
+            .ok_or("record not found")?;
+        assert_eq!(latest.job_id, "job-2");
+        assert!(db
+            .latest_job_for_class_hash("0x1234567890abcdef", Some("sepolia"))?
+            .is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verified_contracts() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?;
+
+        for (job_id, class_hash, name, network, status, days_ago) in [
+            (
+                "job-1",
+                "0x1",
+                "Token",
+                "mainnet",
+                VerifyJobStatus::Success,
+                5,
+            ),
+            (
+                "job-2",
+                "0x2",
+                "Token",
+                "mainnet",
+                VerifyJobStatus::Success,
+                3,
+            ),
+            ("job-3", "0x3", "Token", "mainnet", VerifyJobStatus::Fail, 1),
+            (
+                "job-4",
+                "0x2",
+                "Token",
+                "mainnet",
+                VerifyJobStatus::Success,
+                2,
+            ),
+            (
+                "job-5",
+                "0x4",
+                "Token",
+                "sepolia",
+                VerifyJobStatus::Success,
+                4,
+            ),
+            (
+                "job-6",
+                "0x5",
+                "Vault",
+                "mainnet",
+                VerifyJobStatus::Processing,
+                1,
+            ),
+        ] {
+            let mut record = VerificationRecord {
+                contract_name: name.to_string(),
+                network: network.to_string(),
+                ..record(job_id, &ClassHash::new(class_hash)?, status)
+            };
+            record.submitted_at = Utc::now() - chrono::Duration::days(days_ago);
+            db.insert(&record)?;
+        }
+
+        let entries = db.verified_contracts(None)?;
+        let summary: Vec<_> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.contract_name.as_str(),
+                    e.network.as_str(),
+                    e.job_id.as_str(),
+                    e.verified_classes,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Token", "mainnet", "job-4", 2),
+                ("Token", "sepolia", "job-5", 1)
+            ]
+        );
+        assert_eq!(db.verified_contracts(Some("sepolia"))?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_retention_policy_prunes_on_insert() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?.with_retention(
+            RetentionPolicy {
+                max_records: Some(2),
+                max_age_days: Some(30),
+            },
+        );
+
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        let aged = |job_id: &str, days_ago: i64| {
+            let mut record = record(job_id, &class_hash, VerifyJobStatus::Submitted);
+            record.submitted_at = Utc::now() - chrono::Duration::days(days_ago);
+            record
+        };
+
+        // Too old as soon as it is inserted
+        db.insert(&aged("job-old", 90))?;
+        assert!(db.get_by_job_id("job-old")?.is_none());
+
+        for (job_id, days_ago) in [("job-1", 3), ("job-2", 2), ("job-3", 1)] {
+            db.insert(&aged(job_id, days_ago))?;
+        }
+        assert_eq!(db.get_stats()?.total, 2);
+        assert!(db.get_by_job_id("job-1")?.is_none());
+        assert!(db.get_by_job_id("job-3")?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_pruning_keeps_the_insert() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?.with_retention(
+            RetentionPolicy {
+                max_records: Some(1),
+                max_age_days: None,
+            },
+        );
+        db.conn.execute_batch(
+            "CREATE TRIGGER keep_history BEFORE DELETE ON verification_history
+             BEGIN SELECT RAISE(ABORT, 'history is append-only'); END;",
+        )?;
+
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        for job_id in ["job-1", "job-2"] {
+            db.insert(&record(job_id, &class_hash, VerifyJobStatus::Submitted))?;
+        }
+        assert!(db.get_by_job_id("job-2")?.is_some());
+        assert_eq!(db.get_stats()?.total, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_doctor_repairs_malformed_records() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let mut db = HistoryDb::open_at(&temp_dir.path().join("history.db"))?;
+
+        let class_hash = ClassHash::new("0x1234567890abcdef")?;
+        for job_id in ["job-1", "job-2", "job-3", "job-4"] {
+            db.insert(&record(job_id, &class_hash, VerifyJobStatus::Success))?;
+        }
+        assert!(db.integrity_check()?.is_empty());
+        assert!(db.malformed_records()?.is_empty());
+
+        let corrupt = |sql: &str, job_id: &str| db.conn.execute(sql, params![job_id]);
+        corrupt(
+            "UPDATE verification_history SET submitted_at = '2025-01-02 03:04:05', status = 'success' WHERE job_id = ?1",
+            "job-1",
+        )?;
+        corrupt(
+            "UPDATE verification_history SET completed_at = 'yesterday' WHERE job_id = ?1",
+            "job-2",
+        )?;
+        corrupt(
+            "UPDATE verification_history SET submitted_at = 'garbage' WHERE job_id = ?1",
+            "job-3",
+        )?;
+        corrupt(
+            "UPDATE verification_history SET status = 'Exploded' WHERE job_id = ?1",
+            "job-4",
+        )?;
+
+        let issues = db.malformed_records()?;
+        let remedies: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.job_id.as_str(), issue.remedy()))
+            .collect();
+        assert_eq!(
+            remedies,
+            [
+                ("job-1", Remedy::Set("Success".to_string())),
+                (
+                    "job-1",
+                    Remedy::Set("2025-01-02T03:04:05+00:00".to_string())
+                ),
+                ("job-2", Remedy::Clear),
+                ("job-3", Remedy::Quarantine),
+                ("job-4", Remedy::Quarantine),
+            ]
+        );
+
+        let summary = db.repair(&issues)?;
+        assert_eq!(
+            summary,
+            RepairSummary {
+                fixed: 2,
+                cleared: 1,
+                quarantined: 2,
+            }
+        );
+        assert!(db.malformed_records()?.is_empty());
+        assert_eq!(db.get_stats()?.total, 2);
+
+        let record = db.get_by_job_id("job-1")?.ok_or("record not found")?;
+        assert_eq!(record.status, "Success");
+        assert_eq!(
+            record.submitted_at.to_rfc3339(),
+            "2025-01-02T03:04:05+00:00"
+        );
+        assert!(db
+            .get_by_job_id("job-2")?
+            .ok_or("record not found")?
+            .completed_at
+            .is_none());
+
+        let quarantined: i64 = db.conn.query_row(
+            "SELECT COUNT(*) FROM verification_history_quarantine WHERE reason != ''",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(quarantined, 2);
+        Ok(())
+    }
 }
@@ -1 +1,5 @@
+pub mod batch;
+pub mod cache;
 pub mod history;
+pub mod preflight;
+pub mod state;
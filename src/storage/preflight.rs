@@ -0,0 +1,193 @@
+//! Pre-flight checks for local state under `~/.voyager`
+//!
+//! The history database and the check cache are written as a side effect of
+//! verification. A read-only home directory or a full disk used to surface as an
+//! `io::Error` from deep inside rusqlite halfway through a submission. These
+//! checks probe every location up front so that a single warning listing the
+//! affected paths can be shown before anything is sent.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Free space below which a location is reported, in bytes
+pub const MIN_FREE_BYTES: u64 = 16 * 1024 * 1024;
+
+/// A directory the verifier writes local state to
+#[derive(Debug, Clone)]
+pub struct StateLocation {
+    /// What is stored there, for the warning message
+    pub purpose: &'static str,
+    pub dir: PathBuf,
+}
+
+/// A problem found with a local state location
+#[derive(Debug)]
+pub enum StateIssue {
+    NotWritable {
+        location: StateLocation,
+        reason: String,
+    },
+    LowDiskSpace {
+        location: StateLocation,
+        available: u64,
+    },
+    UnknownDiskSpace {
+        location: StateLocation,
+        reason: String,
+    },
+}
+
+impl fmt::Display for StateIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotWritable { location, reason } => write!(
+                f,
+                "{} ({}) is not writable: {reason}",
+                location.dir.display(),
+                location.purpose
+            ),
+            Self::LowDiskSpace {
+                location,
+                available,
+            } => write!(
+                f,
+                "{} ({}) has only {} KiB of free disk space",
+                location.dir.display(),
+                location.purpose,
+                available / 1024
+            ),
+            Self::UnknownDiskSpace { location, reason } => write!(
+                f,
+                "{} ({}) has an unknown amount of free disk space: {reason}",
+                location.dir.display(),
+                location.purpose
+            ),
+        }
+    }
+}
+
+/// Locations written during verification
+///
+/// Returns an empty list if the home directory cannot be determined; opening
+/// the history database reports that case on its own.
+#[must_use]
+pub fn state_locations() -> Vec<StateLocation> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let voyager_dir = home.join(".voyager");
+
+    vec![
+        StateLocation {
+            purpose: "history database",
+            dir: voyager_dir.clone(),
+        },
+        StateLocation {
+            purpose: "check cache",
+            dir: voyager_dir.join("cache").join("check"),
+        },
+    ]
+}
+
+/// Check that every location can be created, written to, and has disk space left
+#[must_use]
+pub fn check_locations(locations: &[StateLocation]) -> Vec<StateIssue> {
+    let mut issues = Vec::new();
+
+    for location in locations {
+        if let Err(e) = probe_writable(&location.dir) {
+            issues.push(StateIssue::NotWritable {
+                location: location.clone(),
+                reason: e.to_string(),
+            });
+            continue;
+        }
+
+        match fs4::available_space(&location.dir) {
+            Ok(available) if available < MIN_FREE_BYTES => {
+                issues.push(StateIssue::LowDiskSpace {
+                    location: location.clone(),
+                    available,
+                });
+            }
+            Ok(_) => {}
+            Err(e) => issues.push(StateIssue::UnknownDiskSpace {
+                location: location.clone(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    issues
+}
+
+/// Check the default locations and print one consolidated warning for all issues
+///
+/// Problems are not fatal: verification still works, but history and caching
+/// will be unavailable for this run.
+pub fn warn_on_state_issues() {
+    let issues = check_locations(&state_locations());
+    if issues.is_empty() {
+        return;
+    }
+
+    eprintln!("Warning: Local state may not be saved for this verification:");
+    for issue in &issues {
+        eprintln!("  • {issue}");
+    }
+    eprintln!("Verification will continue, but history tracking and caching may fail.");
+}
+
+/// Create `dir` if needed and write and remove a probe file in it
+fn probe_writable(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let probe = dir.join(format!(".write-test-{}", std::process::id()));
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_locations_creates_missing_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let location = StateLocation {
+            purpose: "test",
+            dir: temp_dir.path().join("nested").join("state"),
+        };
+
+        let issues = check_locations(std::slice::from_ref(&location));
+
+        assert!(
+            !issues.iter().any(|issue| matches!(
+                issue,
+                StateIssue::NotWritable { .. } | StateIssue::UnknownDiskSpace { .. }
+            )),
+            "unexpected issues: {issues:?}"
+        );
+        assert!(location.dir.is_dir());
+    }
+
+    #[test]
+    fn test_check_locations_reports_unwritable_path() {
+        let temp_dir = TempDir::new().unwrap();
+        // A regular file where a directory is expected can never be written into
+        let file = temp_dir.path().join("not-a-dir");
+        fs::write(&file, b"").unwrap();
+
+        let issues = check_locations(&[StateLocation {
+            purpose: "test",
+            dir: file.join("state"),
+        }]);
+
+        assert_eq!(issues.len(), 1);
+        assert!(matches!(issues[0], StateIssue::NotWritable { .. }));
+        assert!(issues[0].to_string().contains("not-a-dir"));
+    }
+}
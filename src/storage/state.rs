@@ -0,0 +1,155 @@
+//! Verified state of a workspace, kept for incremental CI
+//!
+//! `voyager verify --state .voyager-state.json` records, for every contract
+//! of a batch that ends up verified, its class hash and the fingerprint of the
+//! sources it was verified with. Later batches read the file back and skip the
+//! contracts whose class hash and sources match what was recorded, so a CI
+//! pipeline that commits or caches the file only verifies what changed.
+//!
+//! Unlike batch plans, the state file lives in the project, at a path chosen
+//! by the caller.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io};
+use url::Url;
+
+/// Version of the state file format
+const STATE_VERSION: u32 = 1;
+
+/// What a contract was last verified with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractState {
+    pub class_hash: String,
+    /// Fingerprint of the verified sources (see [`source_fingerprint`](crate::filesystem::collector::source_fingerprint))
+    pub source_fingerprint: String,
+    /// Job that verified the contract, or `None` if it was verified already
+    pub job_id: Option<String>,
+    /// Unix timestamp (seconds) at which the contract was recorded
+    pub verified_at: i64,
+}
+
+/// Verified contracts of a workspace, by contract name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceState {
+    pub version: u32,
+    pub api_url: String,
+    pub contracts: BTreeMap<String, ContractState>,
+    #[serde(skip)]
+    path: Utf8PathBuf,
+}
+
+impl WorkspaceState {
+    /// Load the state at `path` for the API at `api_url`
+    ///
+    /// A missing file gives an empty state. So does a file recorded against
+    /// another API, since its contracts say nothing about this one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(path: &Utf8Path, api_url: &Url) -> io::Result<Self> {
+        let empty = Self {
+            version: STATE_VERSION,
+            api_url: api_url.to_string(),
+            contracts: BTreeMap::new(),
+            path: path.to_path_buf(),
+        };
+        let content = match fs::read(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(empty),
+            Err(e) => return Err(e),
+        };
+        let mut state: Self = serde_json::from_slice(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if state.api_url != api_url.as_str() {
+            return Ok(empty);
+        }
+        state.path = path.to_path_buf();
+        Ok(state)
+    }
+
+    /// Whether `contract_name` was verified with `class_hash` and sources
+    /// fingerprinted as `source_fingerprint`
+    #[must_use]
+    pub fn is_unchanged(
+        &self,
+        contract_name: &str,
+        class_hash: &str,
+        source_fingerprint: &str,
+    ) -> bool {
+        self.contracts.get(contract_name).is_some_and(|state| {
+            state.class_hash.eq_ignore_ascii_case(class_hash)
+                && state.source_fingerprint == source_fingerprint
+        })
+    }
+
+    /// Record `contract_name` as verified, replacing what was recorded before
+    pub fn record(
+        &mut self,
+        contract_name: &str,
+        class_hash: &str,
+        source_fingerprint: String,
+        job_id: Option<String>,
+    ) {
+        self.contracts.insert(
+            contract_name.to_string(),
+            ContractState {
+                class_hash: class_hash.to_string(),
+                source_fingerprint,
+                job_id,
+                verified_at: Utc::now().timestamp(),
+            },
+        );
+    }
+
+    /// Write the state to its file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self) -> io::Result<()> {
+        let mut content = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+        content.push(b'\n');
+        fs::write(&self.path, content)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_workspace_state_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(temp_dir.path().join(".voyager-state.json")).unwrap();
+        let mainnet = Url::parse("https://api.voyager.online/beta").unwrap();
+
+        let mut state = WorkspaceState::load(&path, &mainnet).unwrap();
+        assert!(state.contracts.is_empty());
+        state.record(
+            "Token",
+            "0xABC",
+            "sha256:01".to_string(),
+            Some("job-1".to_string()),
+        );
+        state.record("Vault", "0xdef", "sha256:02".to_string(), None);
+        state.save().unwrap();
+
+        let state = WorkspaceState::load(&path, &mainnet).unwrap();
+        assert!(state.is_unchanged("Token", "0xabc", "sha256:01"));
+        assert!(!state.is_unchanged("Token", "0xabc", "sha256:03"));
+        assert!(!state.is_unchanged("Vault", "0x123", "sha256:02"));
+        assert!(!state.is_unchanged("Router", "0xabc", "sha256:01"));
+        assert_eq!(state.contracts["Token"].job_id.as_deref(), Some("job-1"));
+
+        let sepolia = Url::parse("https://sepolia-api.voyager.online/beta").unwrap();
+        assert!(WorkspaceState::load(&path, &sepolia)
+            .unwrap()
+            .contracts
+            .is_empty());
+    }
+}
@@ -5,7 +5,10 @@ use std::fmt::{self, Formatter};
 use thiserror::Error;
 use url::Url;
 
-use super::voyager;
+use super::{
+    suggestion::{Suggestion, SuggestionList},
+    voyager,
+};
 use crate::api::ApiClientError;
 use crate::core::class_hash::ClassHash;
 use crate::filesystem::resolver;
@@ -114,6 +117,23 @@ impl MissingPackage {
     pub const fn error_code(&self) -> ErrorCode {
         ErrorCode::E001
     }
+
+    /// Next steps, which depend on whether the workspace has any packages
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        if self.available.is_empty() {
+            Suggestion::from_templates(
+                &[
+                    "Check if you're in the correct directory",
+                    "Verify that Scarb.toml exists and is valid",
+                    "Run 'scarb metadata' to check workspace structure",
+                ],
+                &[],
+            )
+        } else {
+            Suggestion::for_code(self.error_code().as_str(), &[])
+        }
+    }
 }
 
 impl fmt::Display for MissingPackage {
@@ -127,13 +147,6 @@ impl fmt::Display for MissingPackage {
 
         if self.available.is_empty() {
             writeln!(formatter, "\nNo packages are available in this workspace.")?;
-            writeln!(formatter, "\nSuggestions:")?;
-            writeln!(formatter, "  • Check if you're in the correct directory")?;
-            writeln!(formatter, "  • Verify that Scarb.toml exists and is valid")?;
-            writeln!(
-                formatter,
-                "  • Run 'scarb metadata' to check workspace structure"
-            )?;
         } else {
             writeln!(formatter, "\nAvailable packages in this workspace:")?;
             for package in &self.available {
@@ -151,14 +164,9 @@ impl fmt::Display for MissingPackage {
             {
                 writeln!(formatter, "\nDid you mean '{suggestion}'?")?;
             }
-
-            writeln!(formatter, "\nSuggestions:")?;
-            writeln!(formatter, "  • Use --package <name> to specify a package")?;
-            writeln!(formatter, "  • Check spelling of the package name")?;
-            writeln!(formatter, "  • Run 'scarb metadata' to list all packages")?;
         }
 
-        Ok(())
+        write_suggestions(formatter, &self.suggestions())
     }
 }
 
@@ -182,6 +190,48 @@ impl RequestFailure {
     pub const fn error_code(&self) -> ErrorCode {
         ErrorCode::E002
     }
+
+    /// Next steps for the HTTP status the API returned
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let templates: &[&str] = match self.status.as_u16() {
+            400 => &[
+                "Check that all required parameters are provided",
+                "Verify the request format is correct",
+            ],
+            401 => &[
+                "Check your authentication credentials",
+                "Verify API key is valid and not expired",
+            ],
+            403 => &[
+                "Check that you have permission for this operation",
+                "Verify your account has the required access level",
+            ],
+            404 => &[
+                "Check that the URL is correct: {url}",
+                "Verify the resource exists",
+                "Check if the service is running",
+            ],
+            413 => &[
+                "The request payload is too large (maximum 10MB)",
+                "Consider reducing the size of your project files",
+                "Remove unnecessary files or large assets",
+                "Try without --test-files or --lock-file flags",
+                "Check for large binary files or dependencies",
+            ],
+            429 => &[
+                "Wait a moment before retrying",
+                "Consider reducing request frequency",
+            ],
+            500..=599 => &[
+                "The server is experiencing issues",
+                "Try again in a few minutes",
+                "Check service status if available",
+            ],
+            _ => return Suggestion::for_code(self.error_code().as_str(), &[]),
+        };
+        Suggestion::from_templates(templates, &[("url", &self.url)])
+    }
 }
 
 impl fmt::Display for RequestFailure {
@@ -198,70 +248,7 @@ impl fmt::Display for RequestFailure {
             writeln!(formatter, "\nServer response: {}", self.msg)?;
         }
 
-        writeln!(formatter, "\nSuggestions:")?;
-        match self.status.as_u16() {
-            400 => {
-                writeln!(
-                    formatter,
-                    "  • Check that all required parameters are provided"
-                )?;
-                writeln!(formatter, "  • Verify the request format is correct")?;
-            }
-            401 => {
-                writeln!(formatter, "  • Check your authentication credentials")?;
-                writeln!(formatter, "  • Verify API key is valid and not expired")?;
-            }
-            403 => {
-                writeln!(
-                    formatter,
-                    "  • Check that you have permission for this operation"
-                )?;
-                writeln!(
-                    formatter,
-                    "  • Verify your account has the required access level"
-                )?;
-            }
-            404 => {
-                writeln!(formatter, "  • Check that the URL is correct: {}", self.url)?;
-                writeln!(formatter, "  • Verify the resource exists")?;
-                writeln!(formatter, "  • Check if the service is running")?;
-            }
-            413 => {
-                writeln!(
-                    formatter,
-                    "  • The request payload is too large (maximum 10MB)"
-                )?;
-                writeln!(
-                    formatter,
-                    "  • Consider reducing the size of your project files"
-                )?;
-                writeln!(formatter, "  • Remove unnecessary files or large assets")?;
-                writeln!(
-                    formatter,
-                    "  • Try without --test-files or --lock-file flags"
-                )?;
-                writeln!(
-                    formatter,
-                    "  • Check for large binary files or dependencies"
-                )?;
-            }
-            429 => {
-                writeln!(formatter, "  • Wait a moment before retrying")?;
-                writeln!(formatter, "  • Consider reducing request frequency")?;
-            }
-            500..=599 => {
-                writeln!(formatter, "  • The server is experiencing issues")?;
-                writeln!(formatter, "  • Try again in a few minutes")?;
-                writeln!(formatter, "  • Check service status if available")?;
-            }
-            _ => {
-                writeln!(formatter, "  • Check your internet connection")?;
-                writeln!(formatter, "  • Verify the server URL is correct")?;
-                writeln!(formatter, "  • Try again in a few moments")?;
-            }
-        }
-
-        Ok(())
+        write_suggestions(formatter, &self.suggestions())
     }
 }
 
@@ -281,6 +268,23 @@ impl MissingContract {
     pub const fn error_code(&self) -> ErrorCode {
         ErrorCode::E003
     }
+
+    /// Next steps, which depend on whether the manifest defines any contracts
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        if self.available.is_empty() {
+            Suggestion::from_templates(
+                &[
+                    "Add a [tool.voyager] section to your Scarb.toml",
+                    "Define your contracts in the manifest file",
+                    "Check the documentation for contract configuration",
+                ],
+                &[],
+            )
+        } else {
+            Suggestion::for_code(self.error_code().as_str(), &[])
+        }
+    }
 }
 
 impl fmt::Display for MissingContract {
@@ -297,16 +301,6 @@ impl fmt::Display for MissingContract {
                 formatter,
                 "\nNo contracts are defined in the manifest file."
             )?;
-            writeln!(formatter, "\nSuggestions:")?;
-            writeln!(
-                formatter,
-                "  • Add a [tool.voyager] section to your Scarb.toml"
-            )?;
-            writeln!(formatter, "  • Define your contracts in the manifest file")?;
-            writeln!(
-                formatter,
-                "  • Check the documentation for contract configuration"
-            )?;
         } else {
             writeln!(formatter, "\nAvailable contracts:")?;
             for contract in &self.available {
@@ -317,23 +311,70 @@ impl fmt::Display for MissingContract {
             if let Some(suggestion) = find_closest_match(&self.name, &self.available) {
                 writeln!(formatter, "\nDid you mean '{suggestion}'?")?;
             }
+        }
 
-            writeln!(formatter, "\nSuggestions:")?;
-            writeln!(
-                formatter,
-                "  • Use --contract-name <name> to specify a contract"
-            )?;
-            writeln!(formatter, "  • Check spelling of the contract name")?;
-            writeln!(
-                formatter,
-                "  • Verify the contract is defined in [tool.voyager] section"
-            )?;
+        write_suggestions(formatter, &self.suggestions())
+    }
+}
+
+/// The contract name does not match any contract in the package's build artifacts
+#[derive(Debug, Error)]
+pub struct ContractNotInArtifacts {
+    pub name: String,
+    pub package: String,
+    pub available: Vec<String>,
+}
+
+impl ContractNotInArtifacts {
+    #[must_use]
+    pub const fn error_code(&self) -> &'static str {
+        "E053"
+    }
+
+    /// Next steps for matching the name against the built contracts
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        Suggestion::for_code(self.error_code(), &[])
+    }
+}
+
+impl fmt::Display for ContractNotInArtifacts {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        writeln!(
+            formatter,
+            "[{}] Contract '{}' not found in the build artifacts of package '{}'.",
+            self.error_code(),
+            self.name,
+            self.package
+        )?;
+
+        writeln!(formatter, "\nBuilt contracts:")?;
+        if self.available.is_empty() {
+            writeln!(formatter, "  (none)")?;
+        }
+        for contract in &self.available {
+            writeln!(formatter, "  • {contract}")?;
+        }
+
+        // Names are case-sensitive, so case mismatches get a suggestion too
+        if let Some(suggestion) = find_closest_match(&self.name, &self.available) {
+            writeln!(formatter, "\nDid you mean '{suggestion}'?")?;
         }
 
-        Ok(())
+        write_suggestions(formatter, &self.suggestions())
     }
 }
 
+/// Write `suggestions` as the closing "Suggestions:" section of a message
+/// whose sections each end with a newline
+fn write_suggestions(formatter: &mut Formatter, suggestions: &[Suggestion]) -> fmt::Result {
+    writeln!(formatter, "\nSuggestions:")?;
+    for suggestion in suggestions {
+        writeln!(formatter, "  • {}", suggestion.text)?;
+    }
+    Ok(())
+}
+
 /// Main CLI error type that wraps all possible errors
 #[derive(Debug, Error)]
 pub enum CliError {
@@ -343,24 +384,30 @@ pub enum CliError {
     #[error(transparent)]
     Api(#[from] ApiClientError),
 
+    #[error(transparent)]
+    Rpc(#[from] crate::api::RpcError),
+
     #[error(transparent)]
     ClassHash(#[from] crate::core::class_hash::ClassHashError),
 
     #[error(transparent)]
     MissingPackage(#[from] MissingPackage),
 
-    #[error("[E015] Class hash '{0}' is not declared\n\nSuggestions:\n  • Verify the class hash is correct\n  • Check that the contract has been declared on the network\n  • Ensure you're using the correct network (mainnet/testnet)\n  • Use a block explorer to verify the class hash exists")]
+    #[error("[E015] Class hash '{0}' is not declared{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     NotDeclared(ClassHash),
 
-    #[error("[E016] No contracts selected for verification\n\nSuggestions:\n  • Use --contract-name <name> to specify a contract\n  • Check that contracts are defined in [tool.voyager] section\n  • Verify your Scarb.toml contains contract definitions\n  • Use 'scarb metadata' to list available contracts")]
+    #[error("[E016] No contracts selected for verification{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     NoTarget,
 
-    #[error("[E017] Multiple contracts found - only single contract verification is supported\n\nSuggestions:\n  • Use --contract-name <name> to specify which contract to verify\n  • Choose one from the available contracts\n  • Verify each contract separately")]
-    MultipleContracts,
+    #[error("[E017] Multiple contracts found - only single contract verification is supported\n\nAvailable contracts:\n  • {}{suggestions}", candidates.join("\n  • "), suggestions = SuggestionList(self.suggestions()))]
+    MultipleContracts { candidates: Vec<String> },
 
     #[error(transparent)]
     MissingContract(#[from] MissingContract),
 
+    #[error(transparent)]
+    ContractNotInArtifacts(#[from] ContractNotInArtifacts),
+
     #[error(transparent)]
     Resolver(#[from] resolver::Error),
 
@@ -376,34 +423,172 @@ pub enum CliError {
     #[error(transparent)]
     Voyager(#[from] voyager::Error),
 
-    #[error("[E019] File '{path}' exceeds maximum size limit of {max_size} bytes (actual: {actual_size} bytes)\n\nSuggestions:\n  • Reduce the file size by removing unnecessary content\n  • Split large files into smaller modules\n  • Check if the file contains generated or temporary content\n  • Use .gitignore to exclude large files that shouldn't be verified")]
+    #[error("[E019] File '{path}' exceeds maximum size limit of {max_size} bytes (actual: {actual_size} bytes){suggestions}", suggestions = SuggestionList(self.suggestions()))]
     FileSizeLimit {
         path: Utf8PathBuf,
-        max_size: usize,
-        actual_size: usize,
+        max_size: u64,
+        actual_size: u64,
     },
 
-    #[error("[E024] File '{path}' has invalid file type (extension: {extension})\n\nSuggestions:\n  • Only include Cairo source files (.cairo)\n  • Include project configuration files (.toml, .lock)\n  • Include documentation files (.md, .txt)\n  • Remove binary or executable files from the project\n  • Allowed extensions: .cairo, .toml, .lock, .md, .txt, .json")]
+    #[error("[E024] File '{path}' has invalid file type (extension: {extension}){suggestions}", suggestions = SuggestionList(self.suggestions()))]
     InvalidFileType {
         path: Utf8PathBuf,
         extension: String,
     },
 
-    #[error("[E025] Invalid project type specified\n\nSpecified: {specified}\nDetected: {detected}\n\nSuggestions:\n{}", suggestions.join("\n  • "))]
+    #[error("[E025] Invalid project type specified\n\nSpecified: {specified}\nDetected: {detected}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     InvalidProjectType {
         specified: String,
         detected: String,
         suggestions: Vec<String>,
     },
 
-    #[error("[E026] Dojo project validation failed\n\nSuggestions:\n  • Ensure dojo-core is listed in dependencies\n  • Check that Scarb.toml is properly configured for Dojo\n  • Verify project structure follows Dojo conventions\n  • Run 'sozo build' to test project compilation")]
+    #[error("[E026] Dojo project validation failed{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     DojoValidationFailed,
 
-    #[error("[E027] Interactive prompt failed\n\nSuggestions:\n  • Use --project-type=scarb or --project-type=dojo to skip prompt\n  • Ensure terminal supports interactive input\n  • Check that stdin is available")]
+    #[error("[E027] Interactive prompt failed{suggestions}", suggestions = SuggestionList(self.suggestions()))]
     InteractivePromptFailed(#[from] dialoguer::Error),
 
     #[error("[E028] Internal error: {message}\n\nThis is an internal error that should not occur. Please report this issue with:\n  • The full command you ran\n  • The context in which this error occurred\n  • Any relevant logs or output")]
     InternalError { message: String },
+
+    #[error("[E029] Working tree at '{path}' has uncommitted changes\n\nUncommitted files:\n  • {}{suggestions}", files.join("\n  • "), suggestions = SuggestionList(self.suggestions()))]
+    DirtyWorkingTree {
+        path: Utf8PathBuf,
+        files: Vec<String>,
+    },
+
+    #[error("[E050] Unable to determine git status for '{path}'{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    GitStatusUnavailable { path: Utf8PathBuf },
+
+    #[error("[E051] Workspace members declare licenses that differ from the submitted license '{license}'\n\nConflicting packages:\n  • {}{suggestions}", packages.join("\n  • "), suggestions = SuggestionList(self.suggestions()))]
+    LicenseConflict {
+        license: String,
+        packages: Vec<String>,
+    },
+
+    #[error("[E052] Failed to write results file '{path}': {source}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ResultsWriteFailed {
+        path: Utf8PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("[E054] Failed to write triage bundle '{path}': {source}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    BundleWriteFailed {
+        path: Utf8PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("[E055] Contract file '{path}' does not exist{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ContractFileNotFound { path: Utf8PathBuf },
+
+    #[error("[E056] Contract file '{path}' is not part of the submitted sources{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ContractFileNotCollected { path: Utf8PathBuf },
+
+    #[error("[E057] Local build with '{command}' failed ({status}){suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    LocalBuildFailed { command: String, status: String },
+
+    #[error("[E058] No compiled Sierra class for '{contract_name}' in package '{package}'{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    SierraClassMissing {
+        package: String,
+        contract_name: String,
+    },
+
+    #[error("[E059] Failed to compute the class hash of '{path}': {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ClassHashComputationFailed { path: Utf8PathBuf, reason: String },
+
+    #[error("[E064] Invalid depends-on in [[contracts]]: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    InvalidContractDependencies { reason: String },
+
+    #[error("[E065] '{contract}' was not submitted because its dependency '{dependency}' did not verify: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    DependencyNotVerified {
+        contract: String,
+        dependency: String,
+        reason: String,
+    },
+
+    #[error("[E066] Cannot read receipt '{path}': {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    InvalidReceipt { path: Utf8PathBuf, reason: String },
+
+    #[error("[E067] Cannot read extra source directory '{path}': {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ExtraSourceUnreadable { path: Utf8PathBuf, reason: String },
+
+    #[error("[E068] Cannot resolve the package of '{contract}' from '{pattern}': {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    PackagePatternUnresolved {
+        contract: String,
+        pattern: String,
+        reason: String,
+    },
+
+    #[error("[E069] Submission of {size} bytes exceeds the payload limit of {limit} bytes\n\nLargest files:\n  • {}{suggestions}", largest.join("\n  • "), suggestions = SuggestionList(self.suggestions()))]
+    PayloadTooLarge {
+        size: u64,
+        limit: u64,
+        /// The largest files with their size, largest first
+        largest: Vec<String>,
+    },
+
+    #[error("[E071] Cairo sources lack the SPDX header of the submitted license '{license}'\n\nFixes:\n  • {}{suggestions}", fixes.join("\n  • "), suggestions = SuggestionList(self.suggestions()))]
+    LicenseHeaderMismatch { license: String, fixes: Vec<String> },
+
+    #[error("[E073] Sources piped to --stdin-tar are invalid: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    InvalidPipedSources { reason: String },
+
+    #[error("[E074] The files to submit do not compile with '{command}'\n\nDiagnostics:\n{}{suggestions}", diagnostics.join("\n\n"), suggestions = SuggestionList(self.suggestions()))]
+    PreflightBuildFailed {
+        command: String,
+        diagnostics: Vec<String>,
+    },
+
+    #[error("[E077] Class {class_hash} already has a pending verification job {job_id} ({status}){suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    DuplicateSubmission {
+        class_hash: String,
+        job_id: String,
+        status: String,
+    },
+
+    #[error("[E079] Verification package '{path}' is invalid: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    InvalidPack { path: Utf8PathBuf, reason: String },
+
+    #[error("[E080] Failed to write verification package '{path}': {source}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    PackWriteFailed {
+        path: Utf8PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("[E081] No verification API selected{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ApiUrlUnset,
+
+    #[error("[E082] Deployments file '{path}' is invalid: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    InvalidDeployments { path: Utf8PathBuf, reason: String },
+
+    #[error("[E086] Cannot use {option} with batch verification{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    BatchOptionConflict {
+        option: &'static str,
+        fix: &'static str,
+    },
+
+    #[error("[E087] Cannot resume the batch: {reason}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    BatchNotResumable { reason: String },
+
+    #[error("[E088] Cannot read verified state '{path}': {source}{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    StateUnreadable {
+        path: Utf8PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("[E089] Verification did not succeed on target(s): {}{suggestions}", targets.join(", "), suggestions = SuggestionList(self.suggestions()))]
+    TargetsFailed { targets: Vec<String> },
+
+    #[error("[E090] .tool-versions pins scarb {pinned}, but scarb {active} is active{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ToolchainMismatch { pinned: String, active: String },
+
+    #[error("[E091] {path} is from {receipt}, but --network {selected} was given{suggestions}", suggestions = SuggestionList(self.suggestions()))]
+    ReceiptNetworkMismatch {
+        path: Utf8PathBuf,
+        receipt: String,
+        selected: String,
+    },
 }
 
 impl CliError {
@@ -412,12 +597,14 @@ impl CliError {
         match self {
             Self::Args(_) => "E020",
             Self::Api(e) => e.error_code(),
+            Self::Rpc(e) => e.error_code(),
             Self::ClassHash(e) => e.error_code(),
             Self::MissingPackage(e) => e.error_code().as_str(),
             Self::NotDeclared(_) => "E015",
             Self::NoTarget => "E016",
-            Self::MultipleContracts => "E017",
+            Self::MultipleContracts { .. } => "E017",
             Self::MissingContract(e) => e.error_code().as_str(),
+            Self::ContractNotInArtifacts(e) => e.error_code(),
             Self::Resolver(e) => e.error_code(),
             Self::StripPrefix { .. } => "E018",
             Self::Utf8(_) => "E023",
@@ -428,6 +615,115 @@ impl CliError {
             Self::DojoValidationFailed => "E026",
             Self::InteractivePromptFailed(_) => "E027",
             Self::InternalError { .. } => "E028",
+            Self::DirtyWorkingTree { .. } => "E029",
+            Self::GitStatusUnavailable { .. } => "E050",
+            Self::LicenseConflict { .. } => "E051",
+            Self::ResultsWriteFailed { .. } => "E052",
+            Self::BundleWriteFailed { .. } => "E054",
+            Self::ContractFileNotFound { .. } => "E055",
+            Self::ContractFileNotCollected { .. } => "E056",
+            Self::LocalBuildFailed { .. } => "E057",
+            Self::SierraClassMissing { .. } => "E058",
+            Self::ClassHashComputationFailed { .. } => "E059",
+            Self::InvalidContractDependencies { .. } => "E064",
+            Self::DependencyNotVerified { .. } => "E065",
+            Self::InvalidReceipt { .. } => "E066",
+            Self::ExtraSourceUnreadable { .. } => "E067",
+            Self::PackagePatternUnresolved { .. } => "E068",
+            Self::PayloadTooLarge { .. } => "E069",
+            Self::LicenseHeaderMismatch { .. } => "E071",
+            Self::InvalidPipedSources { .. } => "E073",
+            Self::PreflightBuildFailed { .. } => "E074",
+            Self::DuplicateSubmission { .. } => "E077",
+            Self::InvalidPack { .. } => "E079",
+            Self::PackWriteFailed { .. } => "E080",
+            Self::ApiUrlUnset => "E081",
+            Self::InvalidDeployments { .. } => "E082",
+            Self::BatchOptionConflict { .. } => "E086",
+            Self::BatchNotResumable { .. } => "E087",
+            Self::StateUnreadable { .. } => "E088",
+            Self::TargetsFailed { .. } => "E089",
+            Self::ToolchainMismatch { .. } => "E090",
+            Self::ReceiptNetworkMismatch { .. } => "E091",
+        }
+    }
+
+    /// Next steps for the error, as listed under "Suggestions:" in its
+    /// message
+    ///
+    /// Wrapped errors provide their own; internal errors have none.
+    #[must_use]
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let code = self.error_code();
+        match self {
+            Self::Args(e) => e.suggestions(),
+            Self::Api(e) => e.suggestions(),
+            Self::Rpc(e) => e.suggestions(),
+            Self::ClassHash(e) => e.suggestions(),
+            Self::MissingPackage(e) => e.suggestions(),
+            Self::MissingContract(e) => e.suggestions(),
+            Self::ContractNotInArtifacts(e) => e.suggestions(),
+            Self::Resolver(e) => e.suggestions(),
+            Self::StripPrefix { .. }
+            | Self::Utf8(_)
+            | Self::Voyager(_)
+            | Self::InternalError { .. } => Vec::new(),
+            Self::InvalidProjectType { suggestions, .. } => {
+                suggestions.iter().map(Suggestion::new).collect()
+            }
+            Self::SierraClassMissing { package, .. } => {
+                Suggestion::for_code(code, &[("package", package)])
+            }
+            Self::ClassHashComputationFailed { path, .. } => {
+                Suggestion::for_code(code, &[("path", path)])
+            }
+            Self::DependencyNotVerified {
+                contract,
+                dependency,
+                ..
+            } => Suggestion::for_code(code, &[("contract", contract), ("dependency", dependency)]),
+            Self::LicenseHeaderMismatch { license, .. } => {
+                Suggestion::for_code(code, &[("license", license)])
+            }
+            Self::DuplicateSubmission { job_id, .. } => {
+                Suggestion::for_code(code, &[("job_id", job_id)])
+            }
+            Self::BatchOptionConflict { fix, .. } => vec![Suggestion::new(*fix)],
+            Self::StateUnreadable { path, .. } => Suggestion::for_code(code, &[("path", path)]),
+            Self::ToolchainMismatch { pinned, .. } => {
+                Suggestion::for_code(code, &[("pinned", pinned)])
+            }
+            Self::ReceiptNetworkMismatch {
+                receipt, selected, ..
+            } => Suggestion::for_code(code, &[("receipt", receipt), ("selected", selected)]),
+            Self::NotDeclared(_)
+            | Self::NoTarget
+            | Self::MultipleContracts { .. }
+            | Self::FileSizeLimit { .. }
+            | Self::InvalidFileType { .. }
+            | Self::DojoValidationFailed
+            | Self::InteractivePromptFailed(_)
+            | Self::DirtyWorkingTree { .. }
+            | Self::GitStatusUnavailable { .. }
+            | Self::LicenseConflict { .. }
+            | Self::ResultsWriteFailed { .. }
+            | Self::BundleWriteFailed { .. }
+            | Self::ContractFileNotFound { .. }
+            | Self::ContractFileNotCollected { .. }
+            | Self::LocalBuildFailed { .. }
+            | Self::InvalidContractDependencies { .. }
+            | Self::InvalidReceipt { .. }
+            | Self::ExtraSourceUnreadable { .. }
+            | Self::PackagePatternUnresolved { .. }
+            | Self::PayloadTooLarge { .. }
+            | Self::InvalidPipedSources { .. }
+            | Self::PreflightBuildFailed { .. }
+            | Self::InvalidPack { .. }
+            | Self::PackWriteFailed { .. }
+            | Self::ApiUrlUnset
+            | Self::InvalidDeployments { .. }
+            | Self::BatchNotResumable { .. }
+            | Self::TargetsFailed { .. } => Suggestion::for_code(code, &[]),
         }
     }
 }
@@ -0,0 +1,1234 @@
+//! Extended explanations of error codes
+//!
+//! Every error the verifier reports starts with a code such as `[E019]`.
+//! `voyager explain <CODE>` prints the entry for a code from the table below,
+//! which is compiled into the binary so it works offline. When a command fails
+//! with a code that has an entry, the CLI points at `voyager explain` too.
+//!
+//! The remediation steps of an entry are also the suggestions that errors
+//! with its code list in their messages, so both always agree.
+
+use std::fmt;
+
+/// Explanation of one error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Explanation {
+    pub code: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub causes: &'static [&'static str],
+    /// Next steps, which the errors with this code list as their suggestions
+    ///
+    /// `{name}` stands for a value of the error, such as `{package}` for the
+    /// package it is about; see
+    /// [`Suggestion::for_code`](super::suggestion::Suggestion::for_code).
+    pub remediation: &'static [&'static str],
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.code, self.title)?;
+        writeln!(f)?;
+        writeln!(f, "{}", self.description)?;
+
+        if !self.causes.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Common causes:")?;
+            for cause in self.causes {
+                writeln!(f, "  • {cause}")?;
+            }
+        }
+
+        if !self.remediation.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Remediation:")?;
+            for (index, step) in self.remediation.iter().enumerate() {
+                let step = step.replace('{', "<").replace('}', ">");
+                writeln!(f, "  {}. {step}", index + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Look up the explanation of `code`
+///
+/// The code is matched case-insensitively and may omit the `E` prefix or the
+/// leading zeros, so `E019`, `e019` and `19` are the same code.
+#[must_use]
+pub fn explain(code: &str) -> Option<&'static Explanation> {
+    let code = normalize(code)?;
+    EXPLANATIONS.iter().find(|entry| entry.code == code)
+}
+
+/// Hint pointing at `voyager explain` for the code an error message starts with
+///
+/// Returns `None` if the message has no code or the code has no explanation.
+#[must_use]
+pub fn hint_for(message: &str) -> Option<String> {
+    let code = message.strip_prefix('[')?.split_once(']')?.0;
+    explain(code).map(|entry| {
+        format!(
+            "For more information about this error, try `voyager explain {}`.",
+            entry.code
+        )
+    })
+}
+
+/// `E` followed by the three digit number, e.g. `E007` for `7`
+fn normalize(code: &str) -> Option<String> {
+    let code = code.trim();
+    let digits = code
+        .strip_prefix('E')
+        .or_else(|| code.strip_prefix('e'))
+        .unwrap_or(code);
+    let number: u16 = digits.parse().ok()?;
+    (number < 1000).then(|| format!("E{number:03}"))
+}
+
+/// All explained error codes, in numeric order
+pub static EXPLANATIONS: &[Explanation] = &[
+    Explanation {
+        code: "E001",
+        title: "Package not found in workspace",
+        description: "The package selected with --package (or workspace.default-package in \
+            .voyager.toml) is not a member of the Scarb workspace.",
+        causes: &[
+            "A typo in the package name",
+            "The package is not listed in the workspace members of Scarb.toml",
+            "The command was run from another project's directory",
+        ],
+        remediation: &[
+            "Use --package <name> to specify a package",
+            "Check spelling of the package name",
+            "Run 'scarb metadata' to list all packages",
+        ],
+    },
+    Explanation {
+        code: "E002",
+        title: "HTTP request failed",
+        description: "A request to the Voyager API returned an unexpected HTTP status.",
+        causes: &[
+            "The API is temporarily unavailable or rate limiting requests",
+            "The payload exceeds the API's size limit (413)",
+            "--url points at something that is not a Voyager API",
+        ],
+        remediation: &[
+            "Check your internet connection",
+            "Verify the server URL is correct",
+            "Try again in a few moments",
+        ],
+    },
+    Explanation {
+        code: "E003",
+        title: "Contract not found in manifest",
+        description: "The contract named with --contract-name is not listed in the \
+            [tool.voyager] section of Scarb.toml and could not be detected otherwise.",
+        causes: &[
+            "A typo or case difference in the contract name",
+            "The contract is declared in another package",
+        ],
+        remediation: &[
+            "Use --contract-name <name> to specify a contract",
+            "Check spelling of the contract name",
+            "Verify the contract is defined in [tool.voyager] section",
+        ],
+    },
+    Explanation {
+        code: "E004",
+        title: "Compilation failed",
+        description: "The remote compiler could not build the submitted sources. The \
+            sources, dependencies or compiler settings differ from the local build.",
+        causes: &[
+            "A module is declared but its file was not submitted",
+            "The contract imports test-only code without --test-files",
+            "Dependencies resolve to other versions without Scarb.lock",
+            "The Cairo or Scarb version differs from the one used locally",
+            "The Cairo, Scarb or Sierra version is not supported by the API, which \
+             'voyager verify' warns about before uploading",
+        ],
+        remediation: &[
+            "Check that all dependencies are properly declared in Scarb.toml",
+            "Verify that the contract syntax is correct",
+            "Ensure all imports are valid and accessible",
+            "Check for typos in function names and variable declarations",
+        ],
+    },
+    Explanation {
+        code: "E005",
+        title: "Verification failed",
+        description: "The sources compiled, but the resulting class hash differs from the \
+            declared class hash, so the sources are not the ones that were deployed.",
+        causes: &[
+            "The sources changed since the class was declared",
+            "Dependencies resolved to other versions than at deployment",
+            "The release profile or compiler version differs",
+            "The class hash belongs to another contract",
+        ],
+        remediation: &[
+            "Ensure the compiled class hash matches the declared class hash",
+            "Verify that the source code corresponds to the deployed contract",
+            "Check that all dependencies are at the correct versions",
+            "Confirm that the contract was compiled with the same Cairo version",
+        ],
+    },
+    Explanation {
+        code: "E006",
+        title: "Invalid base URL",
+        description: "The API URL cannot be used as a base for API paths.",
+        causes: &["The URL given with --url or in .voyager.toml is not an http(s) URL"],
+        remediation: &[
+            "Provide a valid HTTP or HTTPS URL",
+            "Example: https://api.example.com",
+            "Ensure the URL includes the protocol (http:// or https://)",
+        ],
+    },
+    Explanation {
+        code: "E007",
+        title: "Verification job still in progress",
+        description: "The job has not reached a final state yet.",
+        causes: &["The status was checked before the remote compiler finished"],
+        remediation: &[
+            "Wait a moment before checking again",
+            "Use --wait to automatically wait for completion",
+            "Check the job status periodically",
+        ],
+    },
+    Explanation {
+        code: "E008",
+        title: "Job not found",
+        description: "The API does not know the job ID.",
+        causes: &[
+            "A typo in the job ID",
+            "The job was submitted to another network or URL",
+            "The job expired on the server",
+        ],
+        remediation: &[
+            "Check that the job ID is correct",
+            "Verify the job was submitted successfully",
+            "The job may have expired from the server",
+            "Try submitting a new verification request",
+        ],
+    },
+    Explanation {
+        code: "E009",
+        title: "Invalid URL format",
+        description: "An API URL could not be built from the configured endpoint.",
+        causes: &["The endpoint contains characters that are not valid in a URL"],
+        remediation: &[
+            "Check the URL format is correct",
+            "Ensure proper encoding of special characters",
+            "Use absolute URLs with protocol (http:// or https://)",
+        ],
+    },
+    Explanation {
+        code: "E010",
+        title: "Invalid class hash format",
+        description: "A class hash must be a 0x-prefixed hexadecimal number of at most 64 \
+            digits.",
+        causes: &[
+            "The 0x prefix is missing",
+            "The hash contains non-hexadecimal characters or was truncated",
+            "A contract address was passed instead of a class hash",
+        ],
+        remediation: &[
+            "Check that the hash starts with '0x'",
+            "Verify all characters are hexadecimal (0-9, a-f, A-F)",
+            "Ensure the hash is not longer than 66 characters total",
+        ],
+    },
+    Explanation {
+        code: "E011",
+        title: "Internal regex compilation error",
+        description: "The pattern used to validate class hashes failed to compile. This is \
+            a bug in the verifier.",
+        causes: &[],
+        remediation: &["Report the issue with the full error message"],
+    },
+    Explanation {
+        code: "E012",
+        title: "Invalid dependency path",
+        description: "A path dependency in Scarb.toml points to a location that cannot be \
+            resolved.",
+        causes: &[
+            "The dependency directory was moved or deleted",
+            "The path is relative to another directory than the manifest",
+        ],
+        remediation: &[
+            "Check that the path exists and is accessible",
+            "Use relative paths from the current directory",
+            "Verify the path format is correct",
+            "Example: path:../my-dependency",
+        ],
+    },
+    Explanation {
+        code: "E013",
+        title: "Failed to read dependency metadata",
+        description: "The Scarb.toml of a path dependency could not be read or parsed.",
+        causes: &[
+            "The dependency's Scarb.toml is missing or has invalid TOML",
+            "The file is not readable",
+        ],
+        remediation: &[
+            "Check that Scarb.toml exists at the specified path",
+            "Verify the Scarb.toml file is valid",
+            "Run 'scarb metadata' in the target directory to test",
+            "Ensure scarb is installed and accessible",
+        ],
+    },
+    Explanation {
+        code: "E014",
+        title: "Dependency path contains invalid UTF-8",
+        description: "Paths are sent to the API as UTF-8 strings, so every dependency path \
+            must be valid UTF-8.",
+        causes: &["A directory or file name uses a non-UTF-8 encoding"],
+        remediation: &[
+            "Use only ASCII characters in file paths",
+            "Avoid special characters in directory names",
+            "Check for hidden or control characters in the path",
+        ],
+    },
+    Explanation {
+        code: "E015",
+        title: "Class hash not declared",
+        description: "The Starknet node given with --rpc-url reports that the class hash \
+            has not been declared on its network. Nothing was uploaded.",
+        causes: &[
+            "The class was declared on another network",
+            "A contract address was passed instead of a class hash",
+            "The declaration transaction is not accepted yet",
+        ],
+        remediation: &[
+            "Verify the class hash is correct",
+            "Check that the contract has been declared on the network",
+            "Ensure you're using the correct network (mainnet/testnet)",
+            "Use a block explorer to verify the class hash exists",
+        ],
+    },
+    Explanation {
+        code: "E016",
+        title: "No contracts selected",
+        description: "No contract was named and none could be detected in the package.",
+        causes: &["--contract-name was not given and the package has no contract target"],
+        remediation: &[
+            "Use --contract-name <name> to specify a contract",
+            "Check that contracts are defined in [tool.voyager] section",
+            "Verify your Scarb.toml contains contract definitions",
+            "Use 'scarb metadata' to list available contracts",
+        ],
+    },
+    Explanation {
+        code: "E017",
+        title: "Multiple contracts found",
+        description: "The package contains several contracts, so the one to verify must be \
+            named.",
+        causes: &["--contract-name was not given for a package with more than one contract"],
+        remediation: &[
+            "Use --contract-name <name> to specify which contract to verify",
+            "Choose one from the available contracts",
+            "Verify each contract separately",
+        ],
+    },
+    Explanation {
+        code: "E018",
+        title: "Path processing error",
+        description: "A collected file is not inside the directory it was expected in, so \
+            its path relative to the project could not be computed.",
+        causes: &["Symbolic links that point outside the project"],
+        remediation: &[
+            "Replace symbolic links in the project with regular files",
+            "Report the issue with the paths from the error message",
+        ],
+    },
+    Explanation {
+        code: "E019",
+        title: "File size limit exceeded",
+        description: "Every submitted file must be smaller than the per-file limit. The \
+            sources of a Cairo project are normally far below it.",
+        causes: &[
+            "Generated or vendored files under src/",
+            "Large fixtures included with --test-files",
+        ],
+        remediation: &[
+            "Reduce the file size by removing unnecessary content",
+            "Split large files into smaller modules",
+            "Check if the file contains generated or temporary content",
+            "Use .gitignore to exclude large files that shouldn't be verified",
+        ],
+    },
+    Explanation {
+        code: "E020",
+        title: "Scarb manifest not found",
+        description: "No Scarb.toml exists at the project path.",
+        causes: &[
+            "The command was run outside a Scarb project",
+            "--path points elsewhere",
+        ],
+        remediation: &[
+            "Check that you're in a Scarb project directory",
+            "Verify that Scarb.toml exists in the specified path",
+            "Run 'scarb init' to create a new project",
+            "Use --manifest-path to specify the correct path",
+        ],
+    },
+    Explanation {
+        code: "E021",
+        title: "Failed to read project metadata",
+        description: "'scarb metadata' failed for the project.",
+        causes: &[
+            "Scarb.toml has invalid syntax or unknown dependencies",
+            "Scarb is not installed or is too old",
+        ],
+        remediation: &[
+            "Check that Scarb.toml is valid TOML format",
+            "Verify all dependencies are properly declared",
+            "Run 'scarb metadata --format-version 1' to see the full error",
+            "Run 'scarb check' to validate your project",
+            "Ensure scarb is installed and up to date",
+        ],
+    },
+    Explanation {
+        code: "E022",
+        title: "File system error",
+        description: "A file or directory of the project could not be read.",
+        causes: &[
+            "Missing permissions",
+            "Files removed while the verifier was running",
+        ],
+        remediation: &[
+            "Check file permissions",
+            "Verify the path exists and is accessible",
+            "Ensure you have read access to the directory",
+        ],
+    },
+    Explanation {
+        code: "E023",
+        title: "Path contains invalid UTF-8",
+        description: "A path of the project is not valid UTF-8 and cannot be submitted.",
+        causes: &["A file or directory name uses a non-UTF-8 encoding"],
+        remediation: &[
+            "Use only ASCII characters in file paths",
+            "Avoid special characters in directory names",
+            "Check for hidden or control characters in the path",
+        ],
+    },
+    Explanation {
+        code: "E024",
+        title: "Invalid file type",
+        description: "Only Cairo sources, manifests, lock files and documentation are \
+            submitted; the file has another type.",
+        causes: &["Binary or generated files inside the source directory"],
+        remediation: &[
+            "Only include Cairo source files (.cairo)",
+            "Include project configuration files (.toml, .lock)",
+            "Include documentation files (.md, .txt)",
+            "Remove binary or executable files from the project",
+            "Allowed extensions: .cairo, .toml, .lock, .md, .txt, .json",
+        ],
+    },
+    Explanation {
+        code: "E025",
+        title: "Invalid project type",
+        description: "The project type given with --project-type does not match the \
+            project.",
+        causes: &["--project-type dojo for a project without a Dojo dependency"],
+        remediation: &[
+            "Use --project-type auto to detect the type",
+            "Pass the type that matches the project",
+        ],
+    },
+    Explanation {
+        code: "E026",
+        title: "Dojo project validation failed",
+        description: "The project was treated as a Dojo project but does not depend on \
+            Dojo.",
+        causes: &["The dojo dependency is missing from Scarb.toml"],
+        remediation: &[
+            "Ensure dojo-core is listed in dependencies",
+            "Check that Scarb.toml is properly configured for Dojo",
+            "Verify project structure follows Dojo conventions",
+            "Run 'sozo build' to test project compilation",
+        ],
+    },
+    Explanation {
+        code: "E027",
+        title: "Interactive prompt failed",
+        description: "A prompt of the wizard could not be shown or read.",
+        causes: &[
+            "The terminal is not interactive, e.g. in CI or with redirected input",
+            "A prompt was not answered within --prompt-timeout",
+        ],
+        remediation: &[
+            "Use --project-type=scarb or --project-type=dojo to skip prompt",
+            "Ensure terminal supports interactive input",
+            "Check that stdin is available",
+        ],
+    },
+    Explanation {
+        code: "E028",
+        title: "Internal error",
+        description: "An unexpected condition was reached. This is a bug in the verifier.",
+        causes: &[],
+        remediation: &["Report the issue with the full error message and --verbose output"],
+    },
+    Explanation {
+        code: "E029",
+        title: "Uncommitted changes in working tree",
+        description: "--require-clean-git is enabled and the project has modified, staged \
+            or untracked files, so the submitted sources would not match any commit.",
+        causes: &["Local edits or build outputs that are not ignored"],
+        remediation: &[
+            "Commit or stash your changes so the verified sources match a commit",
+            "Use --allow-dirty to submit anyway",
+            "Disable 'require-clean-git' in .voyager.toml if this policy is not wanted",
+        ],
+    },
+    Explanation {
+        code: "E030",
+        title: "Failed to read config file",
+        description: ".voyager.toml was found but could not be read.",
+        causes: &["Missing read permissions"],
+        remediation: &[
+            "Check file permissions",
+            "Verify the file exists and is accessible",
+            "Ensure you have read access to the file",
+        ],
+    },
+    Explanation {
+        code: "E031",
+        title: "Failed to parse config file",
+        description: ".voyager.toml is not valid TOML or contains unknown values.",
+        causes: &[
+            "A syntax error such as an unclosed section header",
+            "A setting with the wrong type",
+        ],
+        remediation: &[
+            "Check that .voyager.toml is valid TOML format",
+            "Verify all field names are spelled correctly",
+            "Ensure values match expected types (e.g., boolean, string)",
+            "Run a TOML validator on your config file",
+        ],
+    },
+    Explanation {
+        code: "E032",
+        title: "Invalid UTF-8 path in config",
+        description: "The path of .voyager.toml is not valid UTF-8.",
+        causes: &["A parent directory name uses a non-UTF-8 encoding"],
+        remediation: &[
+            "Use only ASCII characters in file paths",
+            "Avoid special characters in directory names",
+        ],
+    },
+    Explanation {
+        code: "E033",
+        title: "Organization policy cannot be applied",
+        description: "~/.voyager/config.toml pins an organization policy, but the policy \
+            could not be loaded or its signature did not verify. No command runs until the \
+            policy can be enforced.",
+        causes: &[
+            "The policy file or its .sig file is missing",
+            "The policy was edited after it was signed",
+            "The policy was signed with a different key than the pinned one",
+            "The policy is not a valid .voyager.toml, or defines [[contracts]]",
+        ],
+        remediation: &[
+            "Install the current policy and its .sig file from your organization",
+            "Check that the public key in ~/.voyager/config.toml is the one your organization publishes",
+            "Do not edit the policy file; its signature covers every byte",
+        ],
+    },
+    Explanation {
+        code: "E040",
+        title: "Failed to access history database",
+        description: "The history database at ~/.voyager/history.db could not be opened or \
+            queried.",
+        causes: &[
+            "Another process holds a lock on the database",
+            "The file is corrupted or not writable",
+        ],
+        remediation: &[
+            "Check that ~/.voyager directory exists and is writable",
+            "Verify disk space is available",
+            "Ensure no other process is accessing the database",
+        ],
+    },
+    Explanation {
+        code: "E041",
+        title: "Failed to create history directory",
+        description: "~/.voyager could not be created.",
+        causes: &["The home directory is not writable", "The disk is full"],
+        remediation: &[
+            "Check permissions for home directory",
+            "Verify disk space is available",
+            "Ensure ~/.voyager directory can be created",
+        ],
+    },
+    Explanation {
+        code: "E042",
+        title: "Unable to determine home directory",
+        description: "History is stored in the home directory, which could not be found.",
+        causes: &["HOME is not set, e.g. in minimal containers"],
+        remediation: &[
+            "Check that HOME environment variable is set",
+            "Verify user has a valid home directory",
+        ],
+    },
+    Explanation {
+        code: "E050",
+        title: "Git status unavailable",
+        description: "--require-clean-git is enabled, but the git status of the project \
+            could not be read.",
+        causes: &[
+            "git is not installed",
+            "The project is not inside a git repository",
+        ],
+        remediation: &[
+            "Ensure git is installed and available in PATH",
+            "Check that the project is inside a git repository",
+            "Use --allow-dirty to submit without the clean working tree check",
+        ],
+    },
+    Explanation {
+        code: "E051",
+        title: "Conflicting workspace licenses",
+        description: "A single --license applies to every submitted package, but some \
+            workspace members declare a different license and the submission was not \
+            confirmed.",
+        causes: &["Workspace members with different license fields in Scarb.toml"],
+        remediation: &[
+            "Pass --license matching the package being verified",
+            "Align the license fields of workspace members in their Scarb.toml files",
+            "Use --allow-license-mismatch to submit anyway",
+        ],
+    },
+    Explanation {
+        code: "E052",
+        title: "Failed to write results file",
+        description: "The file given with --results could not be written. The verification \
+            jobs were still submitted.",
+        causes: &["The parent directory does not exist or is not writable"],
+        remediation: &[
+            "Check that the parent directory exists and is writable",
+            "Choose a different path with --results",
+        ],
+    },
+    Explanation {
+        code: "E053",
+        title: "Contract not found in build artifacts",
+        description: "The package was built, but no compiled contract is named exactly \
+            like --contract-name. Names are case-sensitive.",
+        causes: &[
+            "A case difference, e.g. mytoken instead of MyToken",
+            "Stale artifacts in target/",
+        ],
+        remediation: &[
+            "Use the contract module name exactly as declared with #[starknet::contract]",
+            "Run 'scarb build' again if the artifacts in target/ are out of date",
+        ],
+    },
+    Explanation {
+        code: "E054",
+        title: "Failed to write triage bundle",
+        description: "The archive given with --bundle could not be written. The original \
+            verification error is still reported.",
+        causes: &["The parent directory does not exist or is not writable"],
+        remediation: &[
+            "Check that the parent directory exists and is writable",
+            "Choose a different path with --bundle",
+        ],
+    },
+    Explanation {
+        code: "E055",
+        title: "Contract file not found",
+        description: "The file given with --contract-file (or contract-file in \
+            [[contracts]]) does not exist. Relative paths are resolved against the \
+            project root.",
+        causes: &["The path is relative to the current directory instead of the project"],
+        remediation: &[
+            "Check the path given with --contract-file",
+            "Relative paths are resolved against the project root, not the current directory",
+        ],
+    },
+    Explanation {
+        code: "E056",
+        title: "Contract file not in submitted sources",
+        description: "The contract file exists but is not among the collected sources, so \
+            the API would never receive it.",
+        causes: &[
+            "The file belongs to another package",
+            "The file is a test file and --test-files was not given",
+        ],
+        remediation: &[
+            "Pass a file from the package being verified (see --package)",
+            "Use --test-files if the contract is defined in a test file",
+            "Run with --dry-run to list the collected files",
+        ],
+    },
+    Explanation {
+        code: "E057",
+        title: "Local build failed",
+        description: "'voyager reproduce' could not build the package with 'scarb build', \
+            or 'voyager verify --preflight' could not run scarb or sozo. The compiler \
+            output is printed above the error.",
+        causes: &[
+            "Compilation errors",
+            "A different Scarb version than the project pins",
+        ],
+        remediation: &[
+            "Fix the compilation errors printed above",
+            "Check that the toolchain pinned in .tool-versions is installed",
+            "Run the command yourself to reproduce the failure",
+        ],
+    },
+    Explanation {
+        code: "E058",
+        title: "No compiled Sierra class",
+        description: "The build produced no Sierra class for the contract.",
+        causes: &[
+            "The starknet-contract target does not enable sierra",
+            "--contract-name does not match the contract module",
+            "Stale artifacts used with --no-build",
+        ],
+        remediation: &[
+            "Add a [[target.starknet-contract]] section with sierra = true to Scarb.toml",
+            "Check that --contract-name matches the contract module name exactly",
+            "Run 'scarb build' and look for '{package}.starknet_artifacts.json' under target/",
+        ],
+    },
+    Explanation {
+        code: "E059",
+        title: "Class hash computation failed",
+        description: "Class hashes of local builds are computed with 'starkli class-hash', \
+            which is missing or failed.",
+        causes: &["starkli is not installed or not in PATH"],
+        remediation: &[
+            "Install starkli (https://book.starkli.rs) and make sure it is in PATH",
+            "Run 'starkli class-hash {path}' to see the full error",
+        ],
+    },
+    Explanation {
+        code: "E060",
+        title: "RPC request failed",
+        description: "The Starknet node given with --rpc-url could not be reached or did \
+            not answer with JSON-RPC.",
+        causes: &[
+            "A wrong or unreachable RPC URL",
+            "The provider is rate limiting requests",
+        ],
+        remediation: &[
+            "Check that the RPC URL is correct and the node is reachable",
+            "Ensure the node serves the Starknet JSON-RPC API",
+            "Try again later if the node is rate limiting requests",
+        ],
+    },
+    Explanation {
+        code: "E061",
+        title: "RPC method returned an error",
+        description: "The node rejected a JSON-RPC call.",
+        causes: &["The node does not support the method or the spec version"],
+        remediation: &[
+            "Check that the node supports Starknet JSON-RPC v0.7 or later",
+            "Try a different RPC provider",
+        ],
+    },
+    Explanation {
+        code: "E062",
+        title: "No contract at address",
+        description: "Nothing is deployed at the contract address on the node's network.",
+        causes: &[
+            "A typo in the address",
+            "The RPC URL belongs to another network than the contract",
+        ],
+        remediation: &[
+            "Check that the contract address is correct",
+            "Ensure the RPC URL points to the same network as the contract",
+        ],
+    },
+    Explanation {
+        code: "E063",
+        title: "RPC node on another chain",
+        description: "The node's chain id does not belong to the selected network, so \
+            on-chain data would be read from the wrong chain.",
+        causes: &["A Sepolia RPC URL used with --network mainnet, or the other way round"],
+        remediation: &[
+            "Use an RPC URL for the same network as --network or --url",
+            "Or select the network the node is on with --network",
+        ],
+    },
+    Explanation {
+        code: "E064",
+        title: "Invalid contract dependencies",
+        description: "The depends-on entries of [[contracts]] in .voyager.toml cannot be \
+            ordered, so no contract of the batch is submitted.",
+        causes: &[
+            "A dependency names a contract that is not in [[contracts]]",
+            "A contract depends on itself",
+            "Dependencies form a cycle",
+        ],
+        remediation: &[
+            "List contracts in depends-on by their contract-name",
+            "Remove dependencies that form a cycle",
+        ],
+    },
+    Explanation {
+        code: "E065",
+        title: "Dependency not verified",
+        description: "A batch contract was skipped because a contract in its depends-on \
+            failed to submit or to verify.",
+        causes: &["The dependency's compilation or verification failed"],
+        remediation: &[
+            "Fix the verification of '{dependency}' first",
+            "Drop '{dependency}' from depends-on if '{contract}' does not need it",
+        ],
+    },
+    Explanation {
+        code: "E066",
+        title: "Invalid receipt",
+        description: "The file passed to --from-receipt does not name exactly one class to verify.",
+        causes: &[
+            "The file is not the JSON output of a declare or deploy",
+            "The deployment did not go through the Universal Deployer Contract",
+            "The file lists several different class hashes",
+        ],
+        remediation: &[
+            "Pass the JSON printed by 'starkli transaction', 'starkli receipt' or 'sncast --json declare'",
+            "Use --class-hash if the file holds several classes or none",
+        ],
+    },
+    Explanation {
+        code: "E067",
+        title: "Extra source directory unreadable",
+        description: "A directory given with --extra-source cannot be read.",
+        causes: &[
+            "The directory does not exist or is a file",
+            "A relative path was written relative to the current directory instead of the project root",
+            "A file in the directory cannot be accessed",
+        ],
+        remediation: &[
+            "Check the path given with --extra-source",
+            "Relative paths are resolved against the project root, not the current directory",
+        ],
+    },
+    Explanation {
+        code: "E068",
+        title: "Package glob unresolved",
+        description: "A package glob of a batch contract does not select exactly one workspace member defining the contract.",
+        causes: &[
+            "No member name or directory matches the glob",
+            "None of the matching members defines the contract module",
+            "Several matching members define a contract of the same name",
+            "The glob is malformed, e.g. an unclosed '['",
+        ],
+        remediation: &[
+            "Globs match workspace member names or their directories relative to the workspace root",
+            "Check the package glob in [[contracts]] or [workspace] members-filter",
+            "Set package to the exact package name if several members define the contract",
+        ],
+    },
+    Explanation {
+        code: "E069",
+        title: "Payload too large",
+        description: "The files to submit, or the JSON request they make once escaped, are \
+            larger than the payload limit: the smaller of max-payload-size in \
+            [voyager.limits] and the limit the API reports. The check runs before uploading, \
+            so nothing was submitted; the largest files are listed.",
+        causes: &[
+            "Every workspace member is uploaded because --package was not given",
+            "Large test fixtures included with --test-files",
+            "Big files under an --extra-source directory",
+        ],
+        remediation: &[
+            "Run 'voyager analyze' to see the largest files and unneeded packages",
+            "Verify a single package with --package so only it and its dependencies are uploaded",
+            "Drop --test-files or --extra-source directories that aren't needed to compile",
+            "Pass --compress to send the request gzip-compressed",
+            "Check max-payload-size under [voyager.limits] in .voyager.toml",
+        ],
+    },
+    Explanation {
+        code: "E070",
+        title: "Class search not supported",
+        description: "The API has no class search endpoint.",
+        causes: &["--url points at an API without search support"],
+        remediation: &[
+            "Search for the contract name on the explorer website instead",
+            "Use 'voyager check --class-hash <HASH>' if you know the class hash",
+            "Check that --url points to a Voyager API that supports search",
+        ],
+    },
+    Explanation {
+        code: "E071",
+        title: "License header mismatch",
+        description: "require-license-headers is enabled and some Cairo files to submit do not \
+            start with an SPDX-License-Identifier comment naming the submitted license. \
+            The check runs before uploading, so nothing was submitted.",
+        causes: &[
+            "New files were added without the license header",
+            "Files carry the header of a different license than Scarb.toml or --license",
+        ],
+        remediation: &[
+            "Start each file with '// SPDX-License-Identifier: {license}' as listed above",
+            "Pass --license matching the headers if the submitted license is wrong",
+            "Disable 'require-license-headers' in .voyager.toml if this policy is not wanted",
+        ],
+    },
+    Explanation {
+        code: "E072",
+        title: "Payload transform rejected",
+        description: "A transform listed under payload-transforms in .voyager.toml refused \
+            the files about to be submitted. Transforms run before uploading, so nothing \
+            was submitted.",
+        causes: &[
+            "secret-scan found a private key or mnemonic in a file to submit",
+            "A transform attached by an application embedding the verifier refused the files",
+        ],
+        remediation: &[
+            "Remove the flagged content from the submitted files",
+            "If it is a false positive, remove '{transform}' from payload-transforms in .voyager.toml",
+        ],
+    },
+    Explanation {
+        code: "E073",
+        title: "Invalid piped sources",
+        description: "voyager verify --stdin-tar could not use the archive read from standard \
+            input. It must be an uncompressed tar archive holding the sources and a \
+            voyager-manifest.json at its root; nothing was submitted.",
+        causes: &[
+            "The input is not a tar archive, is compressed, or was cut off",
+            "The archive has no voyager-manifest.json, or the manifest is not valid JSON",
+            "contract_file in the manifest names a file that is not in the archive",
+            "Neither the command line nor the manifest gives the class hash or contract name",
+        ],
+        remediation: &[
+            "Pipe an uncompressed tar archive, e.g. 'tar -cf - . | voyager verify --stdin-tar ...'",
+            "Add a voyager-manifest.json at the root of the archive",
+            "Check that contract_file in the manifest names a file of the archive",
+            "Pass --class-hash and --contract-name if the manifest omits them",
+        ],
+    },
+    Explanation {
+        code: "E074",
+        title: "Preflight build failed",
+        description: "voyager verify --preflight built the files about to be uploaded in a \
+            temporary directory, and they do not compile. Only the submitted files are \
+            there, after the payload transforms, so nothing was submitted.",
+        causes: &[
+            "Compilation errors in the sources",
+            "A file the build needs is not submitted, e.g. outside the selected package",
+            "A payload transform removed something the build needs",
+        ],
+        remediation: &[
+            "Fix the errors above; the remote compiler would report them too",
+            "If a needed file is missing, check --package, --test-files and payload-transforms",
+            "Run with --dry-run to list the submitted files",
+        ],
+    },
+    Explanation {
+        code: "E075",
+        title: "Contract not found",
+        description: "voyager verify --contract-address looks up the class of the contract \
+            on the explorer of the selected network, and the explorer does not know a \
+            contract at that address.",
+        causes: &[
+            "A typo in the contract address",
+            "The contract is deployed on another network",
+            "The contract was deployed moments ago and is not indexed yet",
+        ],
+        remediation: &[
+            "Check that the contract address is correct",
+            "Ensure you're using the correct network (mainnet/sepolia)",
+            "Pass --class-hash instead if the explorer has not indexed the contract yet",
+        ],
+    },
+    Explanation {
+        code: "E076",
+        title: "Endpoint cannot be used",
+        description: "--endpoint selects a verification API defined under [endpoints] in \
+            .voyager.toml, together with its network, API key, RPC node and explorer. \
+            The named endpoint is missing or one of its settings is invalid.",
+        causes: &[
+            "No [endpoints.<name>] section with that name",
+            "The environment variable named by api-key-env is not set",
+            "An invalid url, rpc-url or explorer-url",
+            "An unknown network",
+        ],
+        remediation: &[
+            "Define it as [endpoints.{name}] with a url in .voyager.toml",
+            "Export the environment variable named by its api-key-env",
+            "Check the spelling of --endpoint",
+        ],
+    },
+    Explanation {
+        code: "E077",
+        title: "Duplicate submission",
+        description: "Before submitting, voyager verify looks up the last job for the class \
+            on the same network in history. With --on-duplicate abort, it refuses to queue \
+            another job while that one is still pending on the API.",
+        causes: &[
+            "The command was re-run while a slow first attempt is still queued",
+            "Another pipeline on the same machine submitted the class",
+        ],
+        remediation: &[
+            "Run 'voyager status --job {job_id}' to follow the pending job",
+            "Wait for it to finish before submitting again",
+            "Use --on-duplicate warn or --force to submit anyway",
+        ],
+    },
+    Explanation {
+        code: "E078",
+        title: "Source unreadable",
+        description: "While collecting the sources of a package, a file or directory could \
+            not be read. The message names the path, its package and what was being done.",
+        causes: &[
+            "A source file is not readable by the current user",
+            "A symlinked .cairo file or Scarb.toml points to a missing file",
+            "A directory under src/ cannot be listed",
+        ],
+        remediation: &[
+            "Check that the path exists and is readable by the current user",
+            "Fix or remove broken symlinks in the package directory",
+            "Move files that should not be submitted out of the package's src/ directory",
+        ],
+    },
+    Explanation {
+        code: "E079",
+        title: "Invalid verification package",
+        description: "voyager verify --from-pack checks a package written by voyager pack \
+            before submitting it: its manifest must be readable and match its digest, and every \
+            source must match the digest recorded when it was packed.",
+        causes: &[
+            "The package was truncated or altered while being copied",
+            "A source was edited inside the package after packing",
+            "The package was written by a newer version of voyager",
+        ],
+        remediation: &[
+            "Check that the file was written by 'voyager pack' and copied completely",
+            "Re-create it with 'voyager pack' if its sources were changed",
+            "Upgrade voyager if the package was written by a newer version",
+        ],
+    },
+    Explanation {
+        code: "E080",
+        title: "Package write failed",
+        description: "voyager pack could not create or write the package file.",
+        causes: &[
+            "The parent directory does not exist or is not writable",
+            "The disk is full",
+        ],
+        remediation: &[
+            "Check that the parent directory exists and is writable",
+            "Choose a different path with --output",
+        ],
+    },
+    Explanation {
+        code: "E081",
+        title: "No verification API selected",
+        description: "The command needs the verification API, but neither the command line \
+            nor .voyager.toml selects one.",
+        causes: &[
+            "--network and --url were not given",
+            ".voyager.toml sets neither network nor url",
+        ],
+        remediation: &[
+            "Pass --network mainnet, sepolia or dev, or --url <URL>",
+            "Set 'network' or 'url' in .voyager.toml",
+            "Select an [endpoints.<name>] entry of .voyager.toml with --endpoint",
+        ],
+    },
+    Explanation {
+        code: "E082",
+        title: "Invalid deployments file",
+        description: "voyager verify --all-deployed reads the contracts to verify from the file \
+            given with --address-file: a JSON list of contracts, or an object with a contracts \
+            list, each with a name or Dojo tag and a class hash. voyager verify \
+            --from-dojo-manifest reads them from a manifest written by sozo migrate, and \
+            voyager init --from-deployments also from an sncast script state file.",
+        causes: &[
+            "The file does not exist or is not JSON",
+            "The contracts are not in a list",
+            "A contract has no name, tag or class_hash",
+            "A class hash is malformed",
+        ],
+        remediation: &[
+            "List the contracts as a JSON array, or under 'contracts', with a name or tag and a class_hash each",
+            "Pass the manifest written by the deployment, e.g. a Dojo manifest_<profile>.json",
+            "Use [[contracts]] in .voyager.toml to list the contracts by hand",
+        ],
+    },
+    Explanation {
+        code: "E083",
+        title: "API not usable",
+        description: "When the API is set with --url or url in .voyager.toml, voyager verify \
+            asks it for its capabilities before reading the project, so a wrong URL fails \
+            before the sources are collected.",
+        causes: &[
+            "The host cannot be reached or refuses the connection",
+            "The API answers with a server error",
+            "The URL points at a website, such as the explorer, rather than the verifier API",
+        ],
+        remediation: &[
+            "Check that --url (or url in .voyager.toml) is the base URL of the verifier API, e.g. https://api.voyager.online/beta",
+            "Check your network connection and proxy settings",
+            "Use --network mainnet or --network sepolia for the public Voyager APIs",
+        ],
+    },
+    Explanation {
+        code: "E084",
+        title: "Invalid environment variable",
+        description: "VOYAGER_* environment variables override the [voyager] settings of \
+            .voyager.toml, e.g. VOYAGER_WATCH for watch. A variable whose value the setting \
+            cannot take stops the command rather than being ignored.",
+        causes: &[
+            "A flag such as VOYAGER_WATCH is not true, false, 1 or 0",
+            "A number such as VOYAGER_CHECK_CACHE_TTL is not a whole number",
+        ],
+        remediation: &[
+            "Set it to a value the matching .voyager.toml setting accepts",
+            "Unset it to use .voyager.toml and the defaults",
+        ],
+    },
+    Explanation {
+        code: "E085",
+        title: "API key refused",
+        description: "The API answered 401 Unauthorized or 403 Forbidden. Private and \
+            rate-limited APIs require an API key, sent as a bearer token with every request.",
+        causes: &[
+            "No API key was given to an API that requires one",
+            "The key has expired or was revoked",
+            "The key does not grant access to this API, network or request",
+        ],
+        remediation: &[
+            "Pass the API key with --api-key or the VOYAGER_API_KEY environment variable",
+            "Renew the key if it has expired or was revoked, or ask the operator of the API for one",
+            "Check that the key grants access to this API and network",
+        ],
+    },
+    Explanation {
+        code: "E086",
+        title: "Option not allowed in batch mode",
+        description: "voyager verify runs in batch mode when .voyager.toml lists [[contracts]] \
+            or a batch is resumed. Options that select a single contract, or the wizard, have \
+            no meaning for a batch.",
+        causes: &[
+            "--class-hash, --contract-address or --contract-file was given with [[contracts]]",
+            "--targets or --wizard was given with [[contracts]]",
+        ],
+        remediation: &[
+            "Set the option on the [[contracts]] entries in .voyager.toml instead",
+            "Remove [[contracts]] from .voyager.toml to verify a single contract",
+        ],
+    },
+    Explanation {
+        code: "E087",
+        title: "Batch cannot be resumed",
+        description: "voyager verify --resume continues a batch from the plan kept in \
+            ~/.voyager/batches until every contract is submitted. The plan must exist and be \
+            resumed against the API the batch was submitted to.",
+        causes: &[
+            "No interrupted batch was found for the project, or none with the given ID",
+            "The batch was submitted to another API",
+            "The home directory could not be determined",
+        ],
+        remediation: &[
+            "Pass the same --network or --url the batch was submitted to",
+            "Run voyager verify without --resume to start a new batch",
+        ],
+    },
+    Explanation {
+        code: "E088",
+        title: "Verified state unreadable",
+        description: "voyager verify --state records the contracts verified by a batch, and \
+            skips them in later runs. The file exists but cannot be read as a state file.",
+        causes: &[
+            "The file is not valid JSON, or not a state file written by --state",
+            "The file is not readable",
+        ],
+        remediation: &["Fix or delete {path} to verify every contract again"],
+    },
+    Explanation {
+        code: "E089",
+        title: "Verification failed on a target",
+        description: "voyager verify --targets submits the contract to several explorers. A \
+            target that did not accept the submission or whose verification failed fails the \
+            command, after the jobs of every target are shown.",
+        causes: &[
+            "A target refused the submission",
+            "The verification failed on a target",
+        ],
+        remediation: &[
+            "Check the status column of the jobs table for the reason",
+            "Verify on the failed target alone with --network or --url",
+        ],
+    },
+    Explanation {
+        code: "E090",
+        title: "Scarb version differs from the pinned one",
+        description: "voyager reproduce and voyager verify --compare-class-hash build the \
+            package locally. Another Scarb version than the one pinned in .tool-versions may \
+            compile the same sources to another class, so the build is refused.",
+        causes: &[
+            "asdf is not installed, or the pinned Scarb version is not installed",
+            "Another scarb comes first in PATH",
+        ],
+        remediation: &[
+            "Run 'asdf install scarb {pinned}' in the project and retry",
+            "Check 'scarb --version' in the project directory",
+            "Build with the pinned Scarb yourself and pass --no-build to voyager reproduce",
+        ],
+    },
+    Explanation {
+        code: "E091",
+        title: "Receipt from another network",
+        description: "voyager verify --from-receipt takes the network from the receipt when it \
+            names one. A class declared on one network cannot be verified on another, so a \
+            different --network is refused.",
+        causes: &[
+            "--network or the network in .voyager.toml is not the one the class was declared on",
+            "The receipt is from an older deployment",
+        ],
+        remediation: &[
+            "Drop --network to verify on {receipt}",
+            "Pass the receipt of the deployment on {selected}",
+        ],
+    },
+    Explanation {
+        code: "E999",
+        title: "General error",
+        description: "Network, I/O and other errors that have no dedicated code.",
+        causes: &[
+            "No internet connection, DNS failures or a proxy blocking requests",
+            "Unreadable [tool.voyager] settings in Scarb.toml",
+        ],
+        remediation: &[
+            "Check the underlying error printed with the code",
+            "Retry the command",
+            "Re-run with --verbose for details",
+        ],
+    },
+];
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_normalizes_codes() {
+        for code in ["E019", "e019", "19", " E19 "] {
+            assert_eq!(explain(code).unwrap().code, "E019");
+        }
+        assert!(explain("E043").is_none());
+        assert!(explain("E1000").is_none());
+        assert!(explain("bogus").is_none());
+
+        assert_eq!(
+            hint_for("[E063] RPC node at 'x' is on chain SN_SEPOLIA").as_deref(),
+            Some("For more information about this error, try `voyager explain E063`.")
+        );
+        assert!(hint_for("no code here").is_none());
+
+        // Numeric order, which the listing relies on
+        assert!(EXPLANATIONS.windows(2).all(|w| w[0].code < w[1].code));
+    }
+}
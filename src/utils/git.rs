@@ -0,0 +1,103 @@
+//! Git working tree inspection
+//!
+//! Small helpers around the `git` binary used to tie verified sources to a
+//! commit. Every function degrades to `None` when git is not installed or the
+//! directory is not inside a repository, so callers decide whether that is an
+//! error.
+
+use camino::Utf8Path;
+use log::debug;
+use std::process::Command;
+
+/// Run a git command in `dir` and return its stdout on success
+fn run_git(dir: &Utf8Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| debug!("Failed to run git: {e}"))
+        .ok()?;
+
+    if !output.status.success() {
+        debug!(
+            "git {} failed in {dir}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Get the commit hash currently checked out in the repository containing `dir`
+///
+/// Returns `None` if git is unavailable, `dir` is not in a repository, or the
+/// repository has no commits yet.
+#[must_use]
+pub fn head_commit(dir: &Utf8Path) -> Option<String> {
+    run_git(dir, &["rev-parse", "HEAD"])
+        .map(|out| out.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+}
+
+/// List uncommitted changes (modified, staged, and untracked files) under `dir`
+///
+/// Only paths inside `dir` are reported, so changes elsewhere in a monorepo do
+/// not affect the result. Returns `None` if git status cannot be determined.
+#[must_use]
+pub fn uncommitted_changes(dir: &Utf8Path) -> Option<Vec<String>> {
+    run_git(dir, &["status", "--porcelain", "--", "."]).map(|out| parse_porcelain(&out))
+}
+
+/// Extract file paths from `git status --porcelain` output
+fn parse_porcelain(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.len() > 3)
+        .map(|line| {
+            let path = &line[3..];
+            // Renames are reported as "old -> new"; the new path is what will be collected
+            path.rsplit(" -> ").next().unwrap_or(path).to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use camino::Utf8PathBuf;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output =
+            " M src/lib.cairo\n?? src/new.cairo\nR  old.cairo -> renamed.cairo\nA  Scarb.toml\n";
+        let files = parse_porcelain(output);
+        assert_eq!(
+            files,
+            vec![
+                "src/lib.cairo",
+                "src/new.cairo",
+                "renamed.cairo",
+                "Scarb.toml"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_porcelain_clean() {
+        assert!(parse_porcelain("").is_empty());
+    }
+
+    #[test]
+    fn test_outside_repository() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = Utf8PathBuf::try_from(temp_dir.path().to_path_buf()).unwrap();
+
+        assert!(head_commit(&dir).is_none());
+        assert!(uncommitted_changes(&dir).is_none());
+    }
+}
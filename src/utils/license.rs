@@ -1,5 +1,9 @@
+use crate::api::FileInfo;
+use crate::cli::args::license_value_parser;
 use camino::Utf8Path;
+use colored::Colorize;
 use log::{debug, warn};
+use scarb_metadata::Metadata;
 use spdx::LicenseId;
 use std::fs;
 
@@ -88,6 +92,228 @@ pub fn warn_if_no_license(license_info: &LicenseInfo) {
     }
 }
 
+/// License declared by a workspace member in its own manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageLicense {
+    pub package: String,
+    pub license: Option<String>,
+}
+
+impl PackageLicense {
+    /// Whether this package declares a license other than `applied`
+    ///
+    /// Licenses are compared by SPDX identifier, so `GPL-3.0` and
+    /// `GPL-3.0-only` are the same license. Licenses that are not SPDX
+    /// licenses are compared as written, ignoring case.
+    #[must_use]
+    pub fn differs_from(&self, applied: &LicenseInfo) -> bool {
+        let applied = applied.display_string();
+        self.license.as_deref().is_some_and(|declared| {
+            match (spdx_identifier(declared), spdx_identifier(applied)) {
+                (Some(declared), Some(applied)) => declared != applied,
+                _ => !declared.trim().eq_ignore_ascii_case(applied),
+            }
+        })
+    }
+}
+
+/// SPDX identifier of `license`, parsed like `--license`
+///
+/// Deprecated GNU identifiers are replaced by the `-only` or `-or-later`
+/// identifier that superseded them.
+fn spdx_identifier(license: &str) -> Option<&'static str> {
+    let license = license.trim();
+    let id = license_value_parser(license).ok()?;
+    if id.is_deprecated() {
+        let suffix = if license.ends_with('+') {
+            "-or-later"
+        } else {
+            "-only"
+        };
+        if let Some(current) = spdx::license_id(&format!("{}{suffix}", id.name)) {
+            return Some(current.name);
+        }
+    }
+    Some(id.name)
+}
+
+/// Collect the license declared by each workspace member
+#[must_use]
+pub fn workspace_member_licenses(metadata: &Metadata) -> Vec<PackageLicense> {
+    metadata
+        .packages
+        .iter()
+        .filter(|pkg| metadata.workspace.members.contains(&pkg.id))
+        .map(|pkg| PackageLicense {
+            package: pkg.name.clone(),
+            license: pkg.manifest_metadata.license.clone(),
+        })
+        .collect()
+}
+
+/// Check whether any workspace member declares a license other than the one being submitted
+///
+/// Only multi-member workspaces can conflict; members without a declared
+/// license are not considered conflicting.
+#[must_use]
+pub fn conflicts_with(members: &[PackageLicense], applied: &LicenseInfo) -> bool {
+    if members.len() < 2 {
+        return false;
+    }
+
+    members.iter().any(|member| member.differs_from(applied))
+}
+
+/// Print every workspace member's declared license next to the submitted one
+pub fn print_license_conflicts(members: &[PackageLicense], applied: &LicenseInfo) {
+    let width = members
+        .iter()
+        .map(|member| member.package.len())
+        .max()
+        .unwrap_or(0)
+        .max("Package".len());
+
+    eprintln!(
+        "\n{} Workspace members declare different licenses, but all sources will be submitted as {}",
+        "⚠️ ".yellow(),
+        applied.display_string().bold()
+    );
+    eprintln!();
+    eprintln!("  {:<width$}  Declared license", "Package");
+    for member in members {
+        let declared = member.license.as_deref().unwrap_or("(none)");
+        let declared = if member.differs_from(applied) {
+            declared.yellow()
+        } else {
+            declared.normal()
+        };
+        eprintln!("  {:<width$}  {declared}", member.package);
+    }
+    eprintln!();
+}
+
+/// Comment tag naming the license of a source file
+const SPDX_TAG: &str = "SPDX-License-Identifier:";
+
+/// A Cairo file whose SPDX header does not name the submitted license
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderMismatch {
+    /// File name as submitted
+    pub file: String,
+    /// Line of the file's SPDX header, 1 if it has none
+    pub line: usize,
+    /// License named by the header, `None` if the file has no header
+    pub found: Option<String>,
+}
+
+impl HeaderMismatch {
+    /// How to fix the header, as `file:line: action`
+    #[must_use]
+    pub fn fix(&self, license: &str) -> String {
+        self.found.as_ref().map_or_else(
+            || format!("{}:1: insert '// {SPDX_TAG} {license}'", self.file),
+            |found| {
+                format!(
+                    "{}:{}: replace '{found}' with '{license}'",
+                    self.file, self.line
+                )
+            },
+        )
+    }
+}
+
+/// License named by the SPDX header of a Cairo source, and its line
+///
+/// Only the comments at the top of the file are searched.
+#[must_use]
+pub fn spdx_header(source: &str) -> Option<(usize, &str)> {
+    source
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .take_while(|(_, line)| line.is_empty() || line.starts_with("//"))
+        .find_map(|(index, line)| {
+            let (_, license) = line.split_once(SPDX_TAG)?;
+            Some((index + 1, license.trim()))
+        })
+}
+
+/// Cairo files among `files` whose SPDX header does not name `license`
+///
+/// Files that cannot be read are left for the upload to report.
+#[must_use]
+pub fn check_license_headers(files: &[FileInfo], license: &str) -> Vec<HeaderMismatch> {
+    files
+        .iter()
+        .filter(|file| file.path.extension().is_some_and(|ext| ext == "cairo"))
+        .filter_map(|file| {
+            let source = fs::read_to_string(&file.path).ok()?;
+            match spdx_header(&source) {
+                Some((_, found)) if found.eq_ignore_ascii_case(license) => None,
+                Some((line, found)) => Some(HeaderMismatch {
+                    file: file.name.clone(),
+                    line,
+                    found: Some(found.to_string()),
+                }),
+                None => Some(HeaderMismatch {
+                    file: file.name.clone(),
+                    line: 1,
+                    found: None,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// SPDX licenses matching `query`, best matches first
+///
+/// Matches the identifier and the full name case-insensitively. Exact
+/// identifiers rank first, then identifier prefixes, substrings of the
+/// identifier or name, and finally identifiers containing the query's
+/// characters in order (`apa2` finds `Apache-2.0`). Deprecated identifiers
+/// are only returned when typed exactly.
+#[must_use]
+pub fn search_licenses(query: &str, limit: usize) -> Vec<LicenseId> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return vec![];
+    }
+
+    let mut matches: Vec<(u8, LicenseId)> = spdx::identifiers::LICENSES
+        .iter()
+        .filter_map(|(name, _, _)| spdx::license_id(name))
+        .filter_map(|id| {
+            let name = id.name.to_lowercase();
+            let rank = if name == query {
+                0
+            } else if id.is_deprecated() {
+                return None;
+            } else if name.starts_with(&query) {
+                1
+            } else if name.contains(&query) {
+                2
+            } else if id.full_name.to_lowercase().contains(&query) {
+                3
+            } else if is_subsequence(&query, &name) {
+                4
+            } else {
+                return None;
+            };
+            Some((rank, id))
+        })
+        .collect();
+
+    // Within a rank, shorter identifiers first ("MIT" before "MIT-0") and
+    // newer versions first ("Apache-2.0" before "Apache-1.1")
+    matches.sort_by_key(|(rank, id)| (*rank, id.name.len(), std::cmp::Reverse(id.name)));
+    matches.into_iter().take(limit).map(|(_, id)| id).collect()
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut haystack = haystack.chars();
+    needle.chars().all(|c| haystack.any(|h| h == c))
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -211,4 +437,102 @@ version = "1.0.0"
         assert!(matches!(result, LicenseInfo::None));
         assert_eq!(result.display_string(), "NONE");
     }
+
+    fn member(package: &str, license: Option<&str>) -> PackageLicense {
+        PackageLicense {
+            package: package.to_string(),
+            license: license.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_conflicts_with_mixed_workspace() {
+        let members = vec![
+            member("token", Some("MIT")),
+            member("nft", Some("Apache-2.0")),
+        ];
+        let applied = LicenseInfo::Manifest("MIT".to_string());
+
+        assert!(conflicts_with(&members, &applied));
+        assert!(!members[0].differs_from(&applied));
+        assert!(members[1].differs_from(&applied));
+    }
+
+    #[test]
+    fn test_conflicts_with_matching_or_undeclared() {
+        let applied = LicenseInfo::Manifest("MIT".to_string());
+
+        let matching = vec![member("token", Some("mit")), member("nft", None)];
+        assert!(!conflicts_with(&matching, &applied));
+
+        // Deprecated identifiers name the same license as their successors
+        let gpl = LicenseInfo::Manifest("GPL-3.0-only".to_string());
+        assert!(!member("token", Some("GPL-3.0")).differs_from(&gpl));
+        assert!(member("token", Some("GPL-3.0+")).differs_from(&gpl));
+        let gpl_or_later = LicenseInfo::Manifest("GPL-3.0-or-later".to_string());
+        assert!(!member("token", Some("GPL-3.0+")).differs_from(&gpl_or_later));
+        let custom = LicenseInfo::Manifest("Proprietary".to_string());
+        assert!(!member("token", Some("proprietary")).differs_from(&custom));
+
+        // A single package cannot conflict with itself
+        let single = vec![member("token", Some("Apache-2.0"))];
+        assert!(!conflicts_with(&single, &applied));
+    }
+
+    #[test]
+    fn test_search_licenses() {
+        let names = |query| {
+            search_licenses(query, 5)
+                .into_iter()
+                .map(|id| id.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("mit").first(), Some(&"MIT"));
+        assert_eq!(names("apache").first(), Some(&"Apache-2.0"));
+        assert!(names("apa2").contains(&"Apache-2.0"));
+        // Full names are searched too
+        assert!(names("Mozilla Public").contains(&"MPL-2.0"));
+        // Deprecated identifiers only when typed exactly
+        assert!(!names("gpl-3").contains(&"GPL-3.0"));
+        assert!(names("gpl-3").contains(&"GPL-3.0-only"));
+        assert_eq!(names("gpl-3.0").first(), Some(&"GPL-3.0"));
+        assert!(names("").is_empty());
+        assert!(names("zzzz-no-such-license").is_empty());
+    }
+
+    #[test]
+    fn test_check_license_headers() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = |name: &str, source: &str| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, source).unwrap();
+            FileInfo {
+                name: format!("src/{name}"),
+                path,
+            }
+        };
+        let files = [
+            file("lib.cairo", "// SPDX-License-Identifier: MIT\nmod token;\n"),
+            file(
+                "token.cairo",
+                "// Token contract\n\n// SPDX-License-Identifier: GPL-3.0-only\n",
+            ),
+            file(
+                "utils.cairo",
+                "fn f() {}\n// SPDX-License-Identifier: MIT\n",
+            ),
+            file("Scarb.toml", "[package]\n"),
+        ];
+
+        let mismatches = check_license_headers(&files, "mit");
+        let actions: Vec<_> = mismatches.iter().map(|m| m.fix("MIT")).collect();
+        assert_eq!(
+            actions,
+            [
+                "src/token.cairo:3: replace 'GPL-3.0-only' with 'MIT'",
+                "src/utils.cairo:1: insert '// SPDX-License-Identifier: MIT'",
+            ]
+        );
+    }
 }
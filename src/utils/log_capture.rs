@@ -0,0 +1,113 @@
+//! In-memory copy of recent log records
+//!
+//! Logs are only printed when `RUST_LOG` asks for them, which is rarely the
+//! case when a verification fails unexpectedly. When a triage bundle is
+//! requested, this logger forwards records to `env_logger` as usual and
+//! additionally keeps the most recent debug-level records of the verifier
+//! itself, so the bundle can include them after the fact. Other runs only
+//! install `env_logger`. Both the printed and the captured records carry the
+//! run id of the invocation.
+
+use super::run_id;
+use chrono::Utc;
+use log::{LevelFilter, Log, Metadata, Record};
+use std::{collections::VecDeque, io::Write, sync::Mutex};
+
+/// Number of records kept in memory
+const MAX_CAPTURED_RECORDS: usize = 10_000;
+
+static CAPTURED: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+struct CaptureLogger {
+    inner: env_logger::Logger,
+}
+
+impl CaptureLogger {
+    /// Debug records of the verifier, and info and above from dependencies
+    fn captures(metadata: &Metadata<'_>) -> bool {
+        let own =
+            metadata.target().starts_with("verifier") || metadata.target().starts_with("voyager");
+        metadata.level()
+            <= if own {
+                LevelFilter::Debug
+            } else {
+                LevelFilter::Info
+            }
+    }
+}
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        Self::captures(metadata) || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if Self::captures(record.metadata()) {
+            if let Ok(mut captured) = CAPTURED.lock() {
+                if captured.len() == MAX_CAPTURED_RECORDS {
+                    captured.pop_front();
+                }
+                captured.push_back(format!(
+                    "[{} {:<5} {} {}] {}",
+                    Utc::now().to_rfc3339(),
+                    record.level(),
+                    record.target(),
+                    run_id::current(),
+                    record.args()
+                ));
+            }
+        }
+
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the logger, configured from `RUST_LOG` like `env_logger::init`
+///
+/// Records are only captured if `capture` is set, that is when a triage
+/// bundle may be written.
+///
+/// # Errors
+///
+/// Returns an error if a logger was already installed.
+pub fn init(capture: bool) -> Result<(), log::SetLoggerError> {
+    let inner = env_logger::Builder::from_default_env()
+        .format(|buf, record| {
+            let style = buf.default_level_style(record.level());
+            writeln!(
+                buf,
+                "[{} {style}{:<5}{style:#} {} {}] {}",
+                buf.timestamp(),
+                record.level(),
+                record.target(),
+                run_id::current(),
+                record.args()
+            )
+        })
+        .build();
+    let max_level = inner.filter();
+
+    if capture {
+        log::set_boxed_logger(Box::new(CaptureLogger { inner }))?;
+        log::set_max_level(max_level.max(LevelFilter::Debug));
+    } else {
+        log::set_boxed_logger(Box::new(inner))?;
+        log::set_max_level(max_level);
+    }
+    Ok(())
+}
+
+/// The captured records, oldest first; empty unless capturing was enabled
+#[must_use]
+pub fn captured() -> Vec<String> {
+    CAPTURED
+        .lock()
+        .map(|captured| captured.iter().cloned().collect())
+        .unwrap_or_default()
+}
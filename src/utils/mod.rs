@@ -1,3 +1,9 @@
 pub mod errors;
+pub mod explain;
+pub mod git;
 pub mod license;
+pub mod log_capture;
+pub mod run_id;
+pub mod submitter;
+pub mod suggestion;
 pub mod voyager;
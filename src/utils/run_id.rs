@@ -0,0 +1,63 @@
+//! Per-invocation run id
+//!
+//! Every invocation of the CLI gets a run id that is written to log lines,
+//! history rows, batch results, and triage bundles. When several
+//! verifications run concurrently, e.g. in parallel CI jobs, the id ties the
+//! records of one invocation together. A wrapper script can set
+//! `VOYAGER_RUN_ID` to use its own id instead.
+
+use chrono::Utc;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::sync::OnceLock;
+
+/// Environment variable overriding the generated run id
+pub const RUN_ID_ENV: &str = "VOYAGER_RUN_ID";
+
+static RUN_ID: OnceLock<String> = OnceLock::new();
+
+/// The run id of the current invocation
+///
+/// Generated on first use and stable for the rest of the process.
+pub fn current() -> &'static str {
+    RUN_ID.get_or_init(|| {
+        std::env::var(RUN_ID_ENV)
+            .ok()
+            .map(|id| id.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .unwrap_or_else(generate)
+    })
+}
+
+/// A new run id: the UTC start time followed by 32 random bits
+///
+/// Sorting ids puts runs in chronological order, while the random suffix
+/// keeps runs started within the same second apart.
+fn generate() -> String {
+    let mut suffix = [0u8; 4];
+    if SystemRandom::new().fill(&mut suffix).is_err() {
+        suffix = std::process::id().to_be_bytes();
+    }
+    format!(
+        "{}-{:08x}",
+        Utc::now().format("%Y%m%dT%H%M%SZ"),
+        u32::from_be_bytes(suffix)
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_id() {
+        let id = generate();
+        let (time, suffix) = id.split_once('-').unwrap();
+        assert_eq!(time.len(), "20250101T000000Z".len());
+        assert_eq!(suffix.len(), 8);
+        assert!(suffix.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(generate(), id);
+
+        assert_eq!(current(), current());
+    }
+}
@@ -0,0 +1,145 @@
+//! Submitter metadata
+//!
+//! Identifies where a verification was submitted from: the local user, the
+//! machine, and the CI job when running in a pipeline. The values are stored
+//! in the history database and written to batch results so teams can trace
+//! which pipeline produced a given verification. Every field is best effort
+//! and `None` when it cannot be determined.
+
+use log::debug;
+use serde::Serialize;
+use std::process::Command;
+
+/// Who and where a verification was submitted from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Submitter {
+    pub user: Option<String>,
+    pub hostname: Option<String>,
+    /// Link to the CI job that submitted the verification
+    pub ci_job_url: Option<String>,
+}
+
+impl Submitter {
+    /// Detect the submitter from the environment of the current process
+    #[must_use]
+    pub fn detect() -> Self {
+        let mut submitter = Self::from_env(|name| std::env::var(name).ok());
+        if submitter.hostname.is_none() {
+            submitter.hostname = system_hostname();
+        }
+        submitter
+    }
+
+    /// Build submitter metadata from environment variables looked up with `var`
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let non_empty = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+
+        Self {
+            user: non_empty("USER").or_else(|| non_empty("USERNAME")),
+            hostname: non_empty("HOSTNAME").or_else(|| non_empty("COMPUTERNAME")),
+            ci_job_url: ci_job_url(&non_empty),
+        }
+    }
+
+    /// `user@host`, or whichever of the two is known
+    #[must_use]
+    pub fn identity(&self) -> Option<String> {
+        match (&self.user, &self.hostname) {
+            (Some(user), Some(host)) => Some(format!("{user}@{host}")),
+            (Some(user), None) => Some(user.clone()),
+            (None, Some(host)) => Some(host.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// URL of the current CI job for the CI systems that expose one
+fn ci_job_url(var: &impl Fn(&str) -> Option<String>) -> Option<String> {
+    // GitLab CI
+    if let Some(url) = var("CI_JOB_URL") {
+        return Some(url);
+    }
+
+    // GitHub Actions
+    if let (Some(server), Some(repository), Some(run_id)) = (
+        var("GITHUB_SERVER_URL"),
+        var("GITHUB_REPOSITORY"),
+        var("GITHUB_RUN_ID"),
+    ) {
+        return Some(format!(
+            "{}/{repository}/actions/runs/{run_id}",
+            server.trim_end_matches('/')
+        ));
+    }
+
+    // CircleCI, Buildkite, Jenkins
+    ["CIRCLE_BUILD_URL", "BUILDKITE_BUILD_URL", "BUILD_URL"]
+        .into_iter()
+        .find_map(var)
+}
+
+/// Hostname reported by the `hostname` binary
+fn system_hostname() -> Option<String> {
+    let output = Command::new("hostname")
+        .output()
+        .map_err(|e| debug!("Failed to run hostname: {e}"))
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let hostname = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!hostname.is_empty()).then_some(hostname)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn from_vars(vars: &[(&str, &str)]) -> Submitter {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect();
+        Submitter::from_env(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_github_actions_run_url() {
+        let submitter = from_vars(&[
+            ("USER", "runner"),
+            ("GITHUB_SERVER_URL", "https://github.com"),
+            ("GITHUB_REPOSITORY", "org/contracts"),
+            ("GITHUB_RUN_ID", "42"),
+        ]);
+
+        assert_eq!(
+            submitter.ci_job_url.as_deref(),
+            Some("https://github.com/org/contracts/actions/runs/42")
+        );
+        assert_eq!(submitter.identity().as_deref(), Some("runner"));
+    }
+
+    #[test]
+    fn test_gitlab_job_url_and_identity() {
+        let submitter = from_vars(&[
+            ("USERNAME", "alice"),
+            ("USER", ""),
+            ("HOSTNAME", "build-01"),
+            ("CI_JOB_URL", "https://gitlab.com/org/contracts/-/jobs/7"),
+            ("BUILD_URL", "https://jenkins.example/job/1"),
+        ]);
+
+        assert_eq!(
+            submitter.ci_job_url.as_deref(),
+            Some("https://gitlab.com/org/contracts/-/jobs/7")
+        );
+        assert_eq!(submitter.identity().as_deref(), Some("alice@build-01"));
+    }
+
+    #[test]
+    fn test_no_ci() {
+        assert_eq!(from_vars(&[]), Submitter::default());
+    }
+}
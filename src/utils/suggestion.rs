@@ -0,0 +1,199 @@
+//! Structured next steps for errors
+//!
+//! Every error message ends with a "Suggestions:" list of actionable next
+//! steps. The error types build them through their `suggestions()` methods,
+//! mostly from the remediation steps of their code in
+//! [`EXPLANATIONS`](super::explain::EXPLANATIONS), and render them into their
+//! messages with [`SuggestionList`]. [`Suggestion`] carries a machine-readable
+//! kind, plus the command or flag it mentions, so JSON output and other
+//! frontends can render them without parsing messages.
+
+use super::explain;
+use serde::Serialize;
+use std::fmt;
+
+/// Commands recognized in quoted spans of a suggestion
+const COMMANDS: [&str; 6] = ["voyager", "scarb", "sozo", "git", "starkli", "sncast"];
+
+/// What acting on a suggestion involves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SuggestionKind {
+    /// Run the command in [`Suggestion::command`]
+    RunCommand,
+    /// Pass or change the flag in [`Suggestion::flag`]
+    UseFlag,
+    /// Edit `.voyager.toml` or `Scarb.toml`
+    EditConfig,
+    /// Check something by hand
+    Check,
+}
+
+/// An actionable next step for an error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Suggestion {
+    pub kind: SuggestionKind,
+    /// The suggestion as shown to users
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<String>,
+}
+
+impl Suggestion {
+    /// Classify a suggestion by the command, flag, or file it mentions
+    #[must_use]
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let command = quoted_command(&text);
+        let flag = flag(&text);
+        let kind = if command.is_some() {
+            SuggestionKind::RunCommand
+        } else if flag.is_some() {
+            SuggestionKind::UseFlag
+        } else if mentions_config(&text) {
+            SuggestionKind::EditConfig
+        } else {
+            SuggestionKind::Check
+        };
+
+        Self {
+            kind,
+            text,
+            command,
+            flag,
+        }
+    }
+
+    /// The remediation steps of `code`, with each `{name}` replaced by the
+    /// value given for `name`
+    ///
+    /// Returns no suggestions if `code` has no explanation.
+    #[must_use]
+    pub fn for_code(code: &str, values: &[(&str, &dyn fmt::Display)]) -> Vec<Self> {
+        explain::explain(code).map_or_else(Vec::new, |entry| {
+            Self::from_templates(entry.remediation, values)
+        })
+    }
+
+    /// Suggestions from `templates`, with each `{name}` replaced by the value
+    /// given for `name`
+    #[must_use]
+    pub fn from_templates(templates: &[&str], values: &[(&str, &dyn fmt::Display)]) -> Vec<Self> {
+        templates
+            .iter()
+            .map(|template| {
+                let text = values
+                    .iter()
+                    .fold(template.to_string(), |text, (name, value)| {
+                        text.replace(&format!("{{{name}}}"), &value.to_string())
+                    });
+                Self::new(text)
+            })
+            .collect()
+    }
+}
+
+/// The "Suggestions:" block ending an error message
+///
+/// Renders nothing if there are no suggestions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestionList(pub Vec<Suggestion>);
+
+impl fmt::Display for SuggestionList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, "\n\nSuggestions:")?;
+        for suggestion in &self.0 {
+            write!(f, "\n  • {}", suggestion.text)?;
+        }
+        Ok(())
+    }
+}
+
+/// The first quoted span that starts with a known command
+fn quoted_command(text: &str) -> Option<String> {
+    ['\'', '`'].into_iter().find_map(|quote| {
+        text.split(quote)
+            .skip(1)
+            .step_by(2)
+            .find(|span| {
+                COMMANDS.iter().any(|command| {
+                    span.strip_prefix(command)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+                })
+            })
+            .map(ToString::to_string)
+    })
+}
+
+/// The first `--flag` mentioned
+fn flag(text: &str) -> Option<String> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, ',' | '(' | ')' | '\'' | '`'))
+        .find(|word| {
+            word.strip_prefix("--")
+                .and_then(|name| name.chars().next())
+                .is_some_and(|c| c.is_ascii_lowercase())
+        })
+        .map(|word| word.split(['=', '.']).next().unwrap_or(word).to_string())
+}
+
+fn mentions_config(text: &str) -> bool {
+    [
+        ".voyager.toml",
+        "Scarb.toml",
+        "[tool.",
+        "[voyager",
+        "[workspace",
+        "[[contracts]]",
+    ]
+    .iter()
+    .any(|marker| text.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_code() {
+        let suggestions = Suggestion::for_code("E070", &[]);
+        let kinds: Vec<_> = suggestions.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SuggestionKind::Check,
+                SuggestionKind::RunCommand,
+                SuggestionKind::UseFlag,
+            ]
+        );
+        assert_eq!(
+            suggestions[1].command.as_deref(),
+            Some("voyager check --class-hash <HASH>")
+        );
+        assert_eq!(suggestions[1].flag.as_deref(), Some("--class-hash"));
+        assert_eq!(suggestions[2].flag.as_deref(), Some("--url"));
+
+        let suggestions = Suggestion::for_code("E076", &[("name", &"staging")]);
+        assert!(suggestions[0].text.contains("[endpoints.staging]"));
+        assert_eq!(suggestions[0].kind, SuggestionKind::EditConfig);
+
+        assert!(Suggestion::for_code("E043", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_suggestion_list() {
+        let list = SuggestionList(Suggestion::from_templates(
+            &["Set url in .voyager.toml", "Pass --url {url}"],
+            &[("url", &"https://example.com")],
+        ));
+        assert_eq!(
+            list.to_string(),
+            "\n\nSuggestions:\n  • Set url in .voyager.toml\n  • Pass --url https://example.com"
+        );
+        assert_eq!(SuggestionList(Vec::new()).to_string(), "");
+    }
+}
@@ -1,8 +1,12 @@
 use camino::Utf8PathBuf;
+use itertools::Itertools;
 use scarb_metadata::{Metadata, PackageId};
 use serde::Deserialize;
 use std::{collections::HashMap, path::PathBuf};
 use thiserror::Error;
+use url::Url;
+
+use crate::{cli::args::NetworkKind, core::class_hash::ClassHash};
 
 pub type ContractMap = HashMap<String, Voyager>;
 
@@ -53,6 +57,121 @@ pub fn tool_section(metadata: &Metadata) -> Result<HashMap<PackageId, ContractMa
     Ok(voyager)
 }
 
+/// Public explorer address for a Voyager API endpoint
+///
+/// Maps `api.voyager.online` to `https://voyager.online` and
+/// `<network>-api.voyager.online` to `https://<network>.voyager.online`.
+/// Returns `None` for custom endpoints, whose explorer is unknown.
+#[must_use]
+pub fn explorer_base_url(api_url: &Url) -> Option<String> {
+    let host = api_url.host_str()?;
+    if host == "api.voyager.online" {
+        return Some("https://voyager.online".to_string());
+    }
+    let network = host.strip_suffix("-api.voyager.online")?;
+    Some(format!("https://{network}.voyager.online"))
+}
+
+/// Public network served by a Voyager API endpoint
+///
+/// Returns `None` for custom endpoints.
+#[must_use]
+pub fn network_for_api_url(api_url: &Url) -> Option<NetworkKind> {
+    match api_url.host_str()? {
+        "api.voyager.online" => Some(NetworkKind::Mainnet),
+        "sepolia-api.voyager.online" => Some(NetworkKind::Sepolia),
+        "dev-api.voyager.online" => Some(NetworkKind::Dev),
+        _ => None,
+    }
+}
+
+/// Name of a network as used in `.voyager.toml` and verification history
+#[must_use]
+pub const fn network_name(network: &NetworkKind) -> &'static str {
+    match network {
+        NetworkKind::Mainnet => "mainnet",
+        NetworkKind::Sepolia => "sepolia",
+        NetworkKind::Dev => "dev",
+    }
+}
+
+/// Verification API endpoint of a network, including the version prefix
+#[must_use]
+pub const fn api_url(network: &NetworkKind) -> &'static str {
+    match network {
+        NetworkKind::Mainnet => "https://api.voyager.online/beta",
+        NetworkKind::Sepolia => "https://sepolia-api.voyager.online/beta",
+        NetworkKind::Dev => "https://dev-api.voyager.online/beta",
+    }
+}
+
+/// Public explorer of a network
+#[must_use]
+pub const fn explorer_url(network: &NetworkKind) -> &'static str {
+    match network {
+        NetworkKind::Mainnet => "https://voyager.online",
+        NetworkKind::Sepolia => "https://sepolia.voyager.online",
+        NetworkKind::Dev => "https://dev.voyager.online",
+    }
+}
+
+/// Starknet chain id of a network, as reported by `starknet_chainId`
+///
+/// Returns `None` for the dev network, which has no fixed chain.
+#[must_use]
+pub const fn chain_id(network: &NetworkKind) -> Option<&'static str> {
+    match network {
+        NetworkKind::Mainnet => Some("SN_MAIN"),
+        NetworkKind::Sepolia => Some("SN_SEPOLIA"),
+        NetworkKind::Dev => None,
+    }
+}
+
+/// Network of a Starknet chain id, given as a short string (`SN_MAIN`) or its hex encoding
+#[must_use]
+pub fn network_for_chain_id(id: &str) -> Option<NetworkKind> {
+    [NetworkKind::Mainnet, NetworkKind::Sepolia]
+        .into_iter()
+        .find(|network| {
+            chain_id(network).is_some_and(|name| {
+                let hex = name.bytes().map(|byte| format!("{byte:02x}")).join("");
+                id.eq_ignore_ascii_case(name)
+                    || id
+                        .strip_prefix("0x")
+                        .is_some_and(|digits| digits.eq_ignore_ascii_case(&hex))
+            })
+        })
+}
+
+/// Explorer page of a class
+#[must_use]
+pub fn class_url(network: &NetworkKind, class_hash: &ClassHash) -> String {
+    format!("{}/class/{class_hash}", explorer_url(network))
+}
+
+/// Explorer page of a deployed contract
+#[must_use]
+pub fn contract_url(network: &NetworkKind, address: &str) -> String {
+    format!("{}/contract/{address}", explorer_url(network))
+}
+
+/// API endpoint reporting the status of a verification job
+///
+/// Same URL that `ApiClient::get_job_status` polls.
+#[must_use]
+pub fn job_url(network: &NetworkKind, job_id: &str) -> String {
+    format!("{}/class-verify/job/{job_id}", api_url(network))
+}
+
+/// Explorer page of a verification job, for sharing
+#[must_use]
+pub fn explorer_job_url(explorer: &Url, job_id: &str) -> String {
+    format!(
+        "{}/verify/job/{job_id}",
+        explorer.as_str().trim_end_matches('/')
+    )
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -117,6 +236,57 @@ mod tests {
         assert!(debug_str.contains("0x123"));
     }
 
+    #[test]
+    fn test_explorer_base_url() {
+        let url = |s: &str| Url::parse(s).unwrap();
+        assert_eq!(
+            explorer_base_url(&url("https://api.voyager.online/beta")).as_deref(),
+            Some("https://voyager.online")
+        );
+        assert_eq!(
+            explorer_base_url(&url("https://sepolia-api.voyager.online/beta")).as_deref(),
+            Some("https://sepolia.voyager.online")
+        );
+        assert_eq!(
+            explorer_base_url(&url("https://custom-api.example.com/beta")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_url_builders() {
+        let hash = ClassHash::new("0x1234").unwrap();
+        assert_eq!(
+            class_url(&NetworkKind::Mainnet, &hash),
+            format!("https://voyager.online/class/{hash}")
+        );
+        assert_eq!(
+            contract_url(&NetworkKind::Sepolia, "0x5678"),
+            "https://sepolia.voyager.online/contract/0x5678"
+        );
+        assert_eq!(
+            job_url(&NetworkKind::Dev, "abc-123"),
+            "https://dev-api.voyager.online/beta/class-verify/job/abc-123"
+        );
+        assert_eq!(
+            explorer_job_url(
+                &Url::parse(explorer_url(&NetworkKind::Sepolia)).unwrap(),
+                "abc-123"
+            ),
+            "https://sepolia.voyager.online/verify/job/abc-123"
+        );
+
+        // Every network's API URL maps back to it
+        for network in [NetworkKind::Mainnet, NetworkKind::Sepolia, NetworkKind::Dev] {
+            let api = Url::parse(api_url(&network)).unwrap();
+            assert_eq!(network_for_api_url(&api), Some(network));
+        }
+        assert_eq!(
+            network_for_api_url(&Url::parse("https://custom-api.example.com/beta").unwrap()),
+            None
+        );
+    }
+
     #[test]
     fn test_error_display() {
         let json_error = serde_json::from_str::<serde_json::Value>("invalid json").unwrap_err();
@@ -5,7 +5,8 @@ use url::Url;
 use verifier::api::{ApiClientError, VerificationError};
 use verifier::core::class_hash::{ClassHash, ClassHashError};
 use verifier::filesystem::resolver;
-use verifier::utils::errors::{MissingContract, RequestFailure};
+use verifier::utils::errors::{CliError, MissingContract, RequestFailure};
+use verifier::utils::suggestion::SuggestionKind;
 
 #[test]
 fn test_missing_contract_error_with_suggestions() {
@@ -39,6 +40,21 @@ fn test_missing_contract_error_with_no_available() {
     assert!(error_message.contains("Add a [tool.voyager] section"));
 }
 
+#[test]
+fn test_multiple_contracts_error_lists_candidates() {
+    let error = CliError::MultipleContracts {
+        candidates: vec![
+            "MyToken (src/token.cairo)".to_string(),
+            "Vault (src/vault.cairo)".to_string(),
+        ],
+    };
+
+    let error_message = error.to_string();
+    assert_eq!(error.error_code(), "E017");
+    assert!(error_message.contains("  • MyToken (src/token.cairo)\n  • Vault (src/vault.cairo)"));
+    assert!(error_message.contains("Use --contract-name"));
+}
+
 #[test]
 fn test_request_failure_error_with_status_specific_suggestions() {
     let url = Url::parse("https://api.example.com/verify").unwrap();
@@ -178,3 +194,95 @@ fn test_error_message_structure() {
     // Should use bullet points
     assert!(error_message.contains("•"));
 }
+
+#[test]
+fn test_dirty_working_tree_error_lists_files() {
+    let error = CliError::DirtyWorkingTree {
+        path: "/project".into(),
+        files: vec!["src/lib.cairo".to_string(), "Scarb.toml".to_string()],
+    };
+
+    let error_message = format!("{error}");
+
+    assert_eq!(error.error_code(), "E029");
+    assert!(error_message.contains("[E029]"));
+    assert!(error_message.contains("  • src/lib.cairo"));
+    assert!(error_message.contains("  • Scarb.toml"));
+    assert!(error_message.contains("--allow-dirty"));
+}
+
+#[test]
+fn test_structured_suggestions() {
+    let error = CliError::DirtyWorkingTree {
+        path: "/project".into(),
+        files: vec!["src/lib.cairo".to_string()],
+    };
+    let suggestions = error.suggestions();
+    assert_eq!(suggestions.len(), 3);
+    assert_eq!(suggestions[1].kind, SuggestionKind::UseFlag);
+    assert_eq!(suggestions[1].flag.as_deref(), Some("--allow-dirty"));
+    assert_eq!(suggestions[2].kind, SuggestionKind::EditConfig);
+
+    // Verification failures carry their suggestions outside the message
+    let error = CliError::Api(ApiClientError::Verify(
+        VerificationError::VerificationFailure("Hash mismatch".to_string()),
+    ));
+    assert!(!error.suggestions().is_empty());
+    assert!(error
+        .suggestions()
+        .iter()
+        .any(|s| s.text.contains("class hash")));
+}
+
+#[test]
+fn test_invalid_project_type_lists_each_suggestion() {
+    let error = CliError::InvalidProjectType {
+        specified: "dojo".to_string(),
+        detected: "scarb".to_string(),
+        suggestions: vec![
+            "Use --project-type=scarb".to_string(),
+            "Add dojo-core to the dependencies".to_string(),
+        ],
+    };
+    let error_message = error.to_string();
+    assert!(error_message.ends_with(
+        "Suggestions:\n  • Use --project-type=scarb\n  • Add dojo-core to the dependencies"
+    ));
+    assert_eq!(error.suggestions().len(), 2);
+    assert_eq!(
+        error.suggestions()[0].flag.as_deref(),
+        Some("--project-type")
+    );
+}
+
+#[test]
+fn test_suggestions_render_into_message() {
+    let errors = [
+        CliError::DependencyNotVerified {
+            contract: "Game".to_string(),
+            dependency: "Token".to_string(),
+            reason: "compilation failed".to_string(),
+        },
+        CliError::from(MissingContract::new(
+            "MyContract".to_string(),
+            vec!["Token".to_string()],
+        )),
+        CliError::Api(ApiClientError::Failure(RequestFailure::new(
+            Url::parse("https://api.example.com/class").unwrap(),
+            StatusCode::NOT_FOUND,
+            "",
+        ))),
+    ];
+    for error in errors {
+        let error_message = error.to_string();
+        let suggestions = error.suggestions();
+        assert!(!suggestions.is_empty());
+        for suggestion in suggestions {
+            assert!(
+                error_message.contains(&format!("  • {}", suggestion.text)),
+                "{error_message} lacks {}",
+                suggestion.text
+            );
+        }
+    }
+}
@@ -187,6 +187,27 @@ fn test_path_handling_integration() {
     assert_eq!(common_prefix, Utf8PathBuf::from("/home"));
 }
 
+#[test]
+fn test_project_path_is_parsed_without_scarb() {
+    use verifier::cli::args::{project_value_parser, ProjectError};
+
+    // Parsing --path only locates the manifest; scarb runs on first use
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("Scarb.toml"),
+        "[package]\nname = \"token\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    let project = project_value_parser(temp_dir.path().to_str().unwrap()).unwrap();
+    assert!(project.to_string().ends_with("Scarb.toml"));
+
+    let missing = temp_dir.path().join("missing");
+    assert!(matches!(
+        project_value_parser(missing.to_str().unwrap()),
+        Err(ProjectError::MissingManifest(_))
+    ));
+}
+
 #[test]
 fn test_status_display_integration() {
     let statuses = vec![
@@ -209,7 +230,81 @@ fn test_status_display_integration() {
         "Unknown",
     ];
 
-    for (status, expected) in statuses.into_iter().zip(expected_displays.into_iter()) {
+    for (status, expected) in statuses.into_iter().zip(expected_displays) {
         assert_eq!(format!("{status}"), expected);
     }
 }
+
+#[test]
+fn test_simulated_verification_flow() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use verifier::api::{
+        poll_verification_status, ApiClient, FileInfo, FixedInterval, MockServer,
+        ProjectMetadataInfo,
+    };
+    use verifier::core::project::ProjectType;
+
+    let server = MockServer::start(Duration::from_millis(50)).unwrap();
+    let client = ApiClient::new(server.url().clone())
+        .unwrap()
+        .with_poll_strategy(Arc::new(FixedInterval::new(Duration::from_millis(20), 100)));
+
+    let temp_dir = TempDir::new().unwrap();
+    let source = temp_dir.path().join("lib.cairo");
+    std::fs::write(&source, "mod contract;").unwrap();
+
+    let class_hash = ClassHash::new("0x1234").unwrap();
+    let job_id = client
+        .verify_class(
+            &class_hash,
+            Some("MIT".to_string()),
+            "MyContract",
+            ProjectMetadataInfo::new(
+                semver::Version::new(2, 11, 4),
+                semver::Version::new(2, 11, 4),
+                ".".to_string(),
+                "src/lib.cairo".to_string(),
+                "my_contract".to_string(),
+                ProjectType::Scarb,
+                None,
+            ),
+            &[FileInfo {
+                name: "src/lib.cairo".to_string(),
+                path: source,
+            }],
+        )
+        .unwrap();
+
+    // Not verified until the job has gone through every stage
+    assert!(!client
+        .check_class_verification(&class_hash)
+        .unwrap()
+        .is_verified());
+
+    let job = poll_verification_status(&client, &job_id).unwrap();
+    assert_eq!(*job.status(), VerifyJobStatus::Success);
+    assert_eq!(job.name.as_deref(), Some("MyContract"));
+
+    let verification = client.check_class_verification(&class_hash).unwrap();
+    let info = verification.info().unwrap();
+    assert_eq!(info.license.as_deref(), Some("MIT"));
+}
+
+#[test]
+fn test_project_metadata_errors_are_returned() {
+    use camino::Utf8PathBuf;
+    use verifier::cli::args::Project;
+
+    let temp_dir = TempDir::new().unwrap();
+    let manifest = Utf8PathBuf::try_from(temp_dir.path().join("Scarb.toml")).unwrap();
+    std::fs::write(&manifest, "[package\nname = ").unwrap();
+
+    let project = Project::new(&manifest).unwrap();
+    let copy = project.clone();
+    assert!(project.root_dir().is_err());
+    assert!(project.manifest_path().is_err());
+    assert!(project.get_license(None).is_err());
+    // Projects compare by manifest, whatever was read of them
+    assert_eq!(project, copy);
+}